@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use homer_core::analyze::api_surface::{ApiChange, ApiChangeKind, ApiDiff, api_diff};
+use homer_core::store::sqlite::SqliteStore;
+
+#[derive(Args, Debug)]
+pub struct ApiDiffArgs {
+    /// Old reference (tag, branch, SHA)
+    pub ref1: String,
+    /// New reference (tag, branch, SHA, or HEAD)
+    pub ref2: String,
+    /// Output format
+    #[arg(long, default_value = "text", value_parser = ["text", "json", "markdown"])]
+    pub format: String,
+    /// Path to git repository (default: current directory)
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Exit with an error if any change is breaking
+    #[arg(long)]
+    pub fail_on_breaking: bool,
+}
+
+pub async fn run(args: ApiDiffArgs) -> anyhow::Result<()> {
+    let repo_path = std::fs::canonicalize(&args.path)
+        .with_context(|| format!("Cannot resolve path: {}", args.path.display()))?;
+
+    let homer_dir = repo_path.join(".homer");
+    if !homer_dir.exists() {
+        anyhow::bail!(
+            "Homer is not initialized in {}. Run `homer init` first.",
+            repo_path.display()
+        );
+    }
+
+    let db_path = super::resolve_db_path(&repo_path);
+    let store = SqliteStore::open(&db_path)
+        .with_context(|| format!("Cannot open database: {}", db_path.display()))?;
+    let config = super::load_config(&repo_path).unwrap_or_default();
+
+    let diff = api_diff(&repo_path, &store, &config, &args.ref1, &args.ref2)
+        .await
+        .context("Failed to compare public APIs")?;
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+        "markdown" => print_markdown(&diff),
+        _ => print_text(&diff),
+    }
+
+    let breaking = diff.breaking().count();
+    if args.fail_on_breaking && breaking > 0 {
+        anyhow::bail!(
+            "API check failed: {breaking} breaking changes between {} and {}",
+            diff.from,
+            diff.to
+        );
+    }
+    Ok(())
+}
+
+fn short(commit: &str) -> &str {
+    commit.get(..8).unwrap_or(commit)
+}
+
+fn describe(change: &ApiChange) -> String {
+    let sig = |s: &Option<String>| s.clone().unwrap_or_else(|| "?".to_string());
+    match change.change {
+        ApiChangeKind::Removed => format!("removed {} {}", change.kind, change.name),
+        ApiChangeKind::Added => format!("added {} {}", change.kind, change.name),
+        ApiChangeKind::SignatureChanged => format!(
+            "changed {}: `{}` -> `{}`",
+            change.name,
+            sig(&change.old_signature),
+            sig(&change.new_signature)
+        ),
+    }
+}
+
+fn print_text(diff: &ApiDiff) {
+    println!(
+        "API Diff: {} ({}) -> {} ({})",
+        diff.from,
+        short(&diff.from_commit),
+        diff.to,
+        short(&diff.to_commit)
+    );
+    println!();
+
+    if diff.changes.is_empty() {
+        println!("No public API changes.");
+        return;
+    }
+
+    for (title, changes) in [
+        ("Breaking", diff.breaking().collect::<Vec<_>>()),
+        ("Non-breaking", diff.non_breaking().collect()),
+    ] {
+        if changes.is_empty() {
+            continue;
+        }
+        println!("{title} ({}):", changes.len());
+        for change in changes {
+            println!("  {}", describe(change));
+        }
+        println!();
+    }
+}
+
+fn print_markdown(diff: &ApiDiff) {
+    println!("# API Diff: {} -> {}", diff.from, diff.to);
+    println!();
+    println!(
+        "Comparing `{}` with `{}`.",
+        short(&diff.from_commit),
+        short(&diff.to_commit)
+    );
+    println!();
+
+    if diff.changes.is_empty() {
+        println!("No public API changes.");
+        return;
+    }
+
+    for (title, changes) in [
+        ("Breaking Changes", diff.breaking().collect::<Vec<_>>()),
+        ("Non-breaking Changes", diff.non_breaking().collect()),
+    ] {
+        if changes.is_empty() {
+            continue;
+        }
+        println!("## {title}");
+        println!();
+        println!("| Item | Change | Old Signature | New Signature |");
+        println!("|------|--------|---------------|---------------|");
+        for c in changes {
+            let change = match c.change {
+                ApiChangeKind::Removed => "removed",
+                ApiChangeKind::Added => "added",
+                ApiChangeKind::SignatureChanged => "signature",
+            };
+            let sig = |s: &Option<String>| {
+                s.as_ref()
+                    .map_or_else(String::new, |s| format!("`{}`", s.replace('|', "\\|")))
+            };
+            println!(
+                "| `{}` | {change} | {} | {} |",
+                c.name,
+                sig(&c.old_signature),
+                sig(&c.new_signature)
+            );
+        }
+        println!();
+    }
+}
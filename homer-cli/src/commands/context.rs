@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use homer_core::query::{self, ContextSet};
+use homer_core::store::sqlite::SqliteStore;
+
+#[derive(Args, Debug)]
+pub struct ContextArgs {
+    /// Seed entities: file paths, function names, or free-text queries
+    #[arg(required = true)]
+    pub seeds: Vec<String>,
+
+    /// Path to git repository (default: current directory)
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Token budget for the returned context set
+    #[arg(long, default_value_t = query::DEFAULT_CONTEXT_BUDGET)]
+    pub budget: u64,
+
+    /// Maximum number of entities to return
+    #[arg(long, default_value_t = query::DEFAULT_CONTEXT_TOP)]
+    pub top: usize,
+
+    /// Output format: text, json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+}
+
+pub async fn run(args: ContextArgs) -> anyhow::Result<()> {
+    let repo_path = std::fs::canonicalize(&args.path)
+        .with_context(|| format!("Cannot resolve path: {}", args.path.display()))?;
+
+    let db_path = super::resolve_db_path(&repo_path);
+    if !db_path.exists() {
+        anyhow::bail!(
+            "Homer is not initialized in {}. Run `homer init` first.",
+            repo_path.display()
+        );
+    }
+
+    let db = SqliteStore::open(&db_path)
+        .with_context(|| format!("Cannot open database: {}", db_path.display()))?;
+
+    let ctx = query::task_context(&db, &args.seeds, args.budget, args.top).await?;
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&ctx)?),
+        _ => print_text(&ctx),
+    }
+
+    Ok(())
+}
+
+fn print_text(ctx: &ContextSet) {
+    for seed in &ctx.unresolved {
+        println!("No entity or text match for seed: {seed}");
+    }
+    if ctx.entries.is_empty() {
+        println!("No context found for the given seeds.");
+        return;
+    }
+
+    println!(
+        "Context for {} ({} of {} tokens):",
+        ctx.seeds.join(", "),
+        ctx.total_tokens,
+        ctx.budget
+    );
+    println!();
+    println!("{:<4} {:<56} {:>8} {:>7}", "#", "Entity", "Score", "Tokens");
+    println!("{:-<80}", "");
+
+    for (i, entry) in ctx.entries.iter().enumerate() {
+        let chars = entry.name.chars().count();
+        let display_name = if chars > 54 {
+            let tail: String = entry.name.chars().skip(chars - 52).collect();
+            format!("..{tail}")
+        } else {
+            entry.name.clone()
+        };
+        let marker = if entry.seed { "*" } else { " " };
+        println!(
+            "{:<4} {marker}{:<55} {:>8.4} {:>7}",
+            i + 1,
+            display_name,
+            entry.score,
+            entry.tokens
+        );
+    }
+    println!();
+    println!("* seed entity");
+}
@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use anyhow::Context;
 use clap::Args;
 
+use homer_core::query::{self, ContractBlastRadius, CoveringTest};
 use homer_core::store::HomerStore;
 use homer_core::store::sqlite::SqliteStore;
 use homer_core::types::{AnalysisKind, NodeFilter, NodeKind};
@@ -22,7 +23,7 @@ pub struct DiffArgs {
     /// Path to git repository (default: current directory)
     #[arg(long, default_value = ".")]
     pub path: PathBuf,
-    /// Sections to include: topology, centrality, communities, coupling
+    /// Sections to include: topology, centrality, communities, coupling, tests, contracts
     #[arg(long, value_delimiter = ',')]
     pub include: Option<Vec<String>>,
 }
@@ -144,6 +145,8 @@ struct ImpactReport {
     low_bus_factor_touched: Vec<(String, u64)>,
     modules_affected: Vec<String>,
     communities_affected: Vec<String>,
+    tests_to_run: Vec<CoveringTest>,
+    contracts: ContractBlastRadius,
     topology: Topology,
 }
 
@@ -180,11 +183,17 @@ async fn assess_impact(
     modules.sort();
     modules.dedup();
 
+    let owned_paths: Vec<String> = changed_files.iter().map(|f| f.path.clone()).collect();
+    let tests_to_run = query::tests_for_paths(db, &owned_paths).await?;
+    let contracts = query::contract_blast_radius(db, &owned_paths).await?;
+
     Ok(ImpactReport {
         high_salience_touched,
         low_bus_factor_touched,
         modules_affected: modules,
         communities_affected,
+        tests_to_run,
+        contracts,
         topology,
     })
 }
@@ -312,6 +321,8 @@ struct SectionFilter {
     show_centrality: bool,
     show_communities: bool,
     show_coupling: bool,
+    show_tests: bool,
+    show_contracts: bool,
 }
 
 impl SectionFilter {
@@ -322,6 +333,8 @@ impl SectionFilter {
                 show_centrality: true,
                 show_communities: true,
                 show_coupling: true,
+                show_tests: true,
+                show_contracts: true,
             };
         };
         Self {
@@ -329,6 +342,8 @@ impl SectionFilter {
             show_centrality: sections.contains(&"centrality"),
             show_communities: sections.contains(&"communities"),
             show_coupling: sections.contains(&"coupling"),
+            show_tests: sections.contains(&"tests"),
+            show_contracts: sections.contains(&"contracts"),
         }
     }
 }
@@ -393,6 +408,44 @@ fn print_text(
             "Communities Affected: {}",
             impact.communities_affected.join(", ")
         );
+        println!();
+    }
+
+    if filter.show_tests && !impact.tests_to_run.is_empty() {
+        println!("Tests to Run:");
+        for test in &impact.tests_to_run {
+            println!("  {} [{}]", test.name, test.framework);
+        }
+        println!();
+    }
+
+    if filter.show_contracts && !impact.contracts.elements.is_empty() {
+        println!("Contract Blast Radius:");
+        for element in &impact.contracts.elements {
+            let how = if element.changed {
+                "changed"
+            } else {
+                "refers to a change"
+            };
+            println!("  {} ({}, {how})", element.element, element.kind);
+            for code in element.bound_code.iter().take(5) {
+                println!("    <- {code}");
+            }
+            if element.bound_code.len() > 5 {
+                println!("    ... and {} more", element.bound_code.len() - 5);
+            }
+        }
+        if !impact.contracts.services.is_empty() {
+            println!("Services Affected:");
+            for service in &impact.contracts.services {
+                println!(
+                    "  {} [{}] ({} files)",
+                    service.service,
+                    service.languages.join(", "),
+                    service.files.len()
+                );
+            }
+        }
     }
 }
 
@@ -464,6 +517,58 @@ fn print_markdown(
         );
         println!();
     }
+
+    if filter.show_tests && !impact.tests_to_run.is_empty() {
+        println!("## Tests to Run");
+        println!();
+        for test in &impact.tests_to_run {
+            println!("- `{}` ({})", test.name, test.framework);
+        }
+        println!();
+    }
+
+    if filter.show_contracts && !impact.contracts.elements.is_empty() {
+        print_contracts_markdown(&impact.contracts);
+    }
+}
+
+fn print_contracts_markdown(contracts: &ContractBlastRadius) {
+    println!("## Contract Blast Radius");
+    println!();
+    println!("| Contract Element | Kind | Change | Bound Code |");
+    println!("|------------------|------|--------|------------|");
+    for element in &contracts.elements {
+        let how = if element.changed {
+            "changed"
+        } else {
+            "refers to a change"
+        };
+        let code = element
+            .bound_code
+            .iter()
+            .map(|c| format!("`{c}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "| `{}` | {} | {how} | {code} |",
+            element.element, element.kind
+        );
+    }
+    println!();
+    if !contracts.services.is_empty() {
+        println!("| Service | Languages | Files | Contract Elements |");
+        println!("|---------|-----------|-------|-------------------|");
+        for service in &contracts.services {
+            println!(
+                "| {} | {} | {} | {} |",
+                service.service,
+                service.languages.join(", "),
+                service.files.len(),
+                service.elements.len()
+            );
+        }
+        println!();
+    }
 }
 
 fn print_json(
@@ -546,6 +651,20 @@ fn print_json(
         );
     }
 
+    if filter.show_tests {
+        obj.insert(
+            "tests_to_run".into(),
+            serde_json::json!(impact.tests_to_run),
+        );
+    }
+
+    if filter.show_contracts {
+        obj.insert(
+            "contract_impact".into(),
+            serde_json::json!(impact.contracts),
+        );
+    }
+
     println!("{}", serde_json::to_string_pretty(&json)?);
     Ok(())
 }
@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use homer_core::query::{self, BlastRadius, ImpactedEntity};
+use homer_core::store::sqlite::SqliteStore;
+
+#[derive(Args, Debug)]
+pub struct ImpactArgs {
+    /// File path, function name, or qualified name to change
+    pub entity: String,
+
+    /// Path to git repository (default: current directory)
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Minimum path probability (product of edge confidences) to report
+    #[arg(long, default_value_t = 0.1)]
+    pub min_probability: f64,
+
+    /// Maximum number of reverse-dependency hops
+    #[arg(long, default_value_t = 10)]
+    pub depth: u32,
+
+    /// Output format: text, json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+}
+
+pub async fn run(args: ImpactArgs) -> anyhow::Result<()> {
+    let repo_path = std::fs::canonicalize(&args.path)
+        .with_context(|| format!("Cannot resolve path: {}", args.path.display()))?;
+
+    let db_path = super::resolve_db_path(&repo_path);
+    if !db_path.exists() {
+        anyhow::bail!(
+            "Homer is not initialized in {}. Run `homer init` first.",
+            repo_path.display()
+        );
+    }
+
+    let db = SqliteStore::open(&db_path)
+        .with_context(|| format!("Cannot open database: {}", db_path.display()))?;
+
+    let Some(node) = query::find_entity(&db, &args.entity).await? else {
+        println!("No entity found matching: {}", args.entity);
+        return Ok(());
+    };
+
+    let radius = query::blast_radius(&db, &node, args.min_probability, args.depth).await?;
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&radius)?),
+        _ => print_text(&radius),
+    }
+
+    Ok(())
+}
+
+fn print_text(radius: &BlastRadius) {
+    println!(
+        "Blast radius of {} (probability >= {}):",
+        radius.target, radius.min_probability
+    );
+    println!();
+
+    if radius.affected.is_empty() && radius.tests.is_empty() {
+        println!("No dependents found.");
+        return;
+    }
+
+    println!("Affected entities ({}):", radius.affected.len());
+    print_entities(&radius.affected);
+
+    if !radius.communities.is_empty() {
+        println!();
+        println!("By community:");
+        for group in &radius.communities {
+            println!(
+                "  {:<40} {:>4} entities  (max p={:.2})",
+                group.community,
+                group.entities.len(),
+                group.max_probability
+            );
+        }
+    }
+
+    if !radius.uncovered.is_empty() {
+        println!();
+        println!("Without associated tests ({}):", radius.uncovered.len());
+        for name in &radius.uncovered {
+            println!("  {name}");
+        }
+    }
+
+    println!();
+    println!("Affected tests ({}):", radius.tests.len());
+    print_entities(&radius.tests);
+}
+
+fn print_entities(entities: &[ImpactedEntity]) {
+    if entities.is_empty() {
+        println!("  (none)");
+        return;
+    }
+    println!("  {:<60} {:>6} {:>5}", "Entity", "Prob", "Hops");
+    for entity in entities {
+        let chars = entity.name.chars().count();
+        let display_name = if chars > 58 {
+            let tail: String = entity.name.chars().skip(chars - 56).collect();
+            format!("..{tail}")
+        } else {
+            entity.name.clone()
+        };
+        println!(
+            "  {:<60} {:>6.2} {:>5}",
+            display_name, entity.probability, entity.depth
+        );
+    }
+}
@@ -1,6 +1,10 @@
+pub mod api_diff;
+pub mod context;
 pub mod diff;
 pub mod graph;
+pub mod impact;
 pub mod init;
+pub mod path;
 pub mod query;
 pub mod render;
 pub mod risk_check;
@@ -24,10 +28,18 @@ pub enum Command {
     Status(status::StatusArgs),
     /// Query the Homer knowledge base for an entity
     Query(query::QueryArgs),
+    /// Rank the context most relevant to a set of seed entities
+    Context(context::ContextArgs),
+    /// Show what transitively depends on an entity (blast radius)
+    Impact(impact::ImpactArgs),
+    /// Find the shortest dependency paths between two entities
+    Path(path::PathArgs),
     /// Explore graph analysis results
     Graph(graph::GraphArgs),
     /// Compare architectural state between two git refs
     Diff(diff::DiffArgs),
+    /// Compare the public API between two git refs
+    ApiDiff(api_diff::ApiDiffArgs),
     /// Regenerate output artifacts without re-extraction
     Render(render::RenderArgs),
     /// Manage graph snapshots for temporal comparison
@@ -61,8 +73,12 @@ pub async fn run(cmd: Command) -> anyhow::Result<()> {
         Command::Update(args) => update::run(args).await,
         Command::Status(args) => status::run(args).await,
         Command::Query(args) => query::run(args).await,
+        Command::Context(args) => context::run(args).await,
+        Command::Impact(args) => impact::run(args).await,
+        Command::Path(args) => path::run(args).await,
         Command::Graph(args) => graph::run(args).await,
         Command::Diff(args) => diff::run(args).await,
+        Command::ApiDiff(args) => api_diff::run(args).await,
         Command::Render(args) => render::run(args).await,
         Command::Snapshot(args) => snapshot::run(args).await,
         Command::RiskCheck(args) => risk_check::run(args).await,
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use homer_core::query::{self, EntityPath};
+use homer_core::store::sqlite::SqliteStore;
+
+#[derive(Args, Debug)]
+pub struct PathArgs {
+    /// Starting entity: file path, function name, or qualified name
+    pub from: String,
+
+    /// Destination entity: file path, function name, or qualified name
+    pub to: String,
+
+    /// Path to git repository (default: current directory)
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Number of shortest paths to return
+    #[arg(short = 'k', long, default_value_t = 3)]
+    pub count: usize,
+
+    /// Ignore edges below this confidence (e.g. 0.8 to skip heuristic calls)
+    #[arg(long, default_value_t = 0.0)]
+    pub min_confidence: f64,
+
+    /// Output format: text, json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+}
+
+pub async fn run(args: PathArgs) -> anyhow::Result<()> {
+    let repo_path = std::fs::canonicalize(&args.path)
+        .with_context(|| format!("Cannot resolve path: {}", args.path.display()))?;
+
+    let db_path = super::resolve_db_path(&repo_path);
+    if !db_path.exists() {
+        anyhow::bail!(
+            "Homer is not initialized in {}. Run `homer init` first.",
+            repo_path.display()
+        );
+    }
+
+    let db = SqliteStore::open(&db_path)
+        .with_context(|| format!("Cannot open database: {}", db_path.display()))?;
+
+    let Some(from) = query::find_entity(&db, &args.from).await? else {
+        println!("No entity found matching: {}", args.from);
+        return Ok(());
+    };
+    let Some(to) = query::find_entity(&db, &args.to).await? else {
+        println!("No entity found matching: {}", args.to);
+        return Ok(());
+    };
+
+    let paths = query::shortest_paths(&db, &from, &to, args.count, args.min_confidence).await?;
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&paths)?),
+        _ => print_text(&from.name, &to.name, &paths),
+    }
+
+    Ok(())
+}
+
+fn print_text(from: &str, to: &str, paths: &[EntityPath]) {
+    if paths.is_empty() {
+        println!("No path from {from} to {to}.");
+        return;
+    }
+
+    println!("Paths from {from} to {to}:");
+    for (i, path) in paths.iter().enumerate() {
+        println!();
+        println!(
+            "#{} ({} hops, confidence {:.2})",
+            i + 1,
+            path.hops.len(),
+            path.confidence
+        );
+        println!("  {from}");
+        for hop in &path.hops {
+            let location = match (&hop.file, hop.site_line.or(hop.start_line)) {
+                (Some(file), Some(line)) => format!("  [{file}:{line}]"),
+                (Some(file), None) => format!("  [{file}]"),
+                _ => String::new(),
+            };
+            println!(
+                "    --{} ({:.2})-->{location}",
+                hop.edge.to_lowercase(),
+                hop.confidence
+            );
+            println!("  {}", hop.to);
+        }
+    }
+}
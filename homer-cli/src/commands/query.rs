@@ -23,7 +23,7 @@ pub struct QueryArgs {
     #[arg(long, default_value = "text")]
     pub format: String,
 
-    /// Sections to include (comma-separated): summary, metrics, callers, callees, history, all
+    /// Sections to include (comma-separated): summary, metrics, callers, callees, history, clones, tests, schema, config, all
     #[arg(long, default_value = "all")]
     pub include: String,
 
@@ -39,6 +39,10 @@ struct IncludeSections {
     callers: bool,
     callees: bool,
     history: bool,
+    clones: bool,
+    tests: bool,
+    schema: bool,
+    config: bool,
 }
 
 impl IncludeSections {
@@ -51,6 +55,10 @@ impl IncludeSections {
             callers: all || parts.contains("callers"),
             callees: all || parts.contains("callees"),
             history: all || parts.contains("history"),
+            clones: all || parts.contains("clones"),
+            tests: all || parts.contains("tests"),
+            schema: all || parts.contains("schema"),
+            config: all || parts.contains("config"),
         }
     }
 }
@@ -110,6 +118,18 @@ async fn print_text(
     if sections.history {
         emit_text_history(db, node).await?;
     }
+    if sections.clones {
+        emit_text_clones(db, node).await?;
+    }
+    if sections.tests {
+        emit_text_tests(db, node).await?;
+    }
+    if sections.schema {
+        emit_text_schema(db, node).await?;
+    }
+    if sections.config {
+        emit_text_config(db, node).await?;
+    }
     Ok(())
 }
 
@@ -272,6 +292,157 @@ async fn emit_text_history(db: &SqliteStore, node: &homer_core::types::Node) ->
     Ok(())
 }
 
+async fn emit_text_clones(db: &SqliteStore, node: &homer_core::types::Node) -> anyhow::Result<()> {
+    let Some(clones) = db.get_analysis(node.id, AnalysisKind::CloneGroup).await? else {
+        return Ok(());
+    };
+    println!();
+    println!("Clones:");
+    for (name, similarity) in clone_members(&clones.data) {
+        println!("  {name} ({:.0}% similar)", similarity * 100.0);
+    }
+    for change in divergent_changes(&clones.data) {
+        println!("  Diverged in {change}");
+    }
+    Ok(())
+}
+
+async fn emit_text_tests(db: &SqliteStore, node: &homer_core::types::Node) -> anyhow::Result<()> {
+    let tests = query::tests_covering(db, node).await?;
+    if tests.is_empty() {
+        return Ok(());
+    }
+    println!();
+    println!("Tests:");
+    for test in &tests {
+        println!("  {} [{}] ({} hops)", test.name, test.framework, test.depth);
+    }
+    Ok(())
+}
+
+async fn emit_text_schema(db: &SqliteStore, node: &homer_core::types::Node) -> anyhow::Result<()> {
+    let migrations = migration_history(node);
+    if !migrations.is_empty() {
+        println!();
+        println!("Migrations:");
+        for (version, file, change) in &migrations {
+            println!("  {version} {change} ({file})");
+        }
+    }
+    let accesses = query::schema_accesses(db, node).await?;
+    if accesses.is_empty() {
+        return Ok(());
+    }
+    println!();
+    println!("Schema access:");
+    for access in &accesses {
+        println!("  {} -> {} [{}]", access.code, access.target, access.via);
+    }
+    Ok(())
+}
+
+async fn emit_text_config(db: &SqliteStore, node: &homer_core::types::Node) -> anyhow::Result<()> {
+    let usages = query::config_usage(db, node).await?;
+    if usages.is_empty() {
+        return Ok(());
+    }
+    println!();
+    println!("Configuration:");
+    for usage in &usages {
+        println!("  {} [{}]", usage.variable, usage.kind);
+        for reader in &usage.readers {
+            println!("    read by {reader}");
+        }
+        if usage.definers.is_empty() {
+            println!("    undocumented");
+        }
+        for definer in &usage.definers {
+            println!("    defined in {definer}");
+        }
+    }
+    Ok(())
+}
+
+/// Migration history of a table or column: version, file, change.
+fn migration_history(node: &homer_core::types::Node) -> Vec<(&str, &str, &str)> {
+    node.metadata
+        .get("history")
+        .and_then(serde_json::Value::as_array)
+        .map(|events| {
+            events
+                .iter()
+                .filter_map(|e| {
+                    Some((
+                        e.get("version")?.as_str()?,
+                        e.get("file")?.as_str()?,
+                        e.get("change")?.as_str()?,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Other members of a clone group with their similarity to this one.
+fn clone_members(data: &serde_json::Value) -> Vec<(&str, f64)> {
+    data.get("members")
+        .and_then(serde_json::Value::as_array)
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| {
+                    let name = m.get("name")?.as_str()?;
+                    let similarity = m.get("similarity")?.as_f64()?;
+                    Some((name, similarity))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Divergent changes of a diverged clone group, newest first, as
+/// `<commit>: changed <names>, not <names>`.
+fn divergent_changes(data: &serde_json::Value) -> Vec<String> {
+    if data.get("diverged").and_then(serde_json::Value::as_bool) != Some(true) {
+        return Vec::new();
+    }
+    let names = |change: &serde_json::Value, key: &str| -> String {
+        change
+            .get(key)
+            .and_then(serde_json::Value::as_array)
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default()
+    };
+    data.get("divergent_changes")
+        .and_then(serde_json::Value::as_array)
+        .map(|changes| {
+            changes
+                .iter()
+                .map(|c| {
+                    let commit: String = c
+                        .get("commit")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or("?")
+                        .chars()
+                        .take(7)
+                        .collect();
+                    format!(
+                        "{commit}: changed {}, not {}",
+                        names(c, "changed"),
+                        names(c, "unchanged")
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 // ── Markdown format ─────────────────────────────────────────────
 
 #[allow(clippy::too_many_lines)]
@@ -315,6 +486,83 @@ async fn print_markdown(
         emit_md_history(&mut out, db, node).await?;
     }
 
+    if sections.clones {
+        if let Some(clones) = db.get_analysis(node.id, AnalysisKind::CloneGroup).await? {
+            let _ = writeln!(out, "## Clones\n");
+            for (name, similarity) in clone_members(&clones.data) {
+                let _ = writeln!(out, "- `{name}` ({:.0}% similar)", similarity * 100.0);
+            }
+            for change in divergent_changes(&clones.data) {
+                let _ = writeln!(out, "- **Diverged** in {change}");
+            }
+            let _ = writeln!(out);
+        }
+    }
+
+    if sections.tests {
+        let tests = query::tests_covering(db, node).await?;
+        if !tests.is_empty() {
+            let _ = writeln!(out, "## Tests\n");
+            for test in &tests {
+                let _ = writeln!(
+                    out,
+                    "- `{}` ({}, {} hops)",
+                    test.name, test.framework, test.depth
+                );
+            }
+            let _ = writeln!(out);
+        }
+    }
+
+    if sections.schema {
+        let migrations = migration_history(node);
+        if !migrations.is_empty() {
+            let _ = writeln!(out, "## Migrations\n");
+            for (version, file, change) in &migrations {
+                let _ = writeln!(out, "- `{version}` {change} (`{file}`)");
+            }
+            let _ = writeln!(out);
+        }
+        let accesses = query::schema_accesses(db, node).await?;
+        if !accesses.is_empty() {
+            let _ = writeln!(out, "## Schema Access\n");
+            for access in &accesses {
+                let _ = writeln!(
+                    out,
+                    "- `{}` → `{}` ({})",
+                    access.code, access.target, access.via
+                );
+            }
+            let _ = writeln!(out);
+        }
+    }
+
+    if sections.config {
+        let usages = query::config_usage(db, node).await?;
+        if !usages.is_empty() {
+            let _ = writeln!(out, "## Configuration\n");
+            for usage in &usages {
+                let quoted = |names: &[String]| {
+                    names
+                        .iter()
+                        .map(|n| format!("`{n}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let defined = if usage.definers.is_empty() {
+                    "undocumented".to_string()
+                } else {
+                    format!("defined in {}", quoted(&usage.definers))
+                };
+                let _ = writeln!(out, "- `{}` ({}): {defined}", usage.variable, usage.kind);
+                if !usage.readers.is_empty() {
+                    let _ = writeln!(out, "  - read by {}", quoted(&usage.readers));
+                }
+            }
+            let _ = writeln!(out);
+        }
+    }
+
     print!("{out}");
     Ok(())
 }
@@ -440,6 +688,12 @@ async fn print_json(
         data["analyses"] = serde_json::Value::Object(analyses);
     }
 
+    if sections.clones {
+        if let Some(clones) = db.get_analysis(node.id, AnalysisKind::CloneGroup).await? {
+            data["clones"] = clones.data;
+        }
+    }
+
     if sections.callers {
         let callers = query::collect_neighbors_bfs(db, node.id, "callee", "caller", depth).await?;
         data["callers"] =
@@ -452,6 +706,18 @@ async fn print_json(
             serde_json::Value::Array(callees.into_iter().map(|(_, n)| n.into()).collect());
     }
 
+    if sections.tests {
+        data["tests"] = serde_json::to_value(query::tests_covering(db, node).await?)?;
+    }
+
+    if sections.schema {
+        data["schema_access"] = serde_json::to_value(query::schema_accesses(db, node).await?)?;
+    }
+
+    if sections.config {
+        data["config_usage"] = serde_json::to_value(query::config_usage(db, node).await?)?;
+    }
+
     println!("{}", serde_json::to_string_pretty(&data)?);
     Ok(())
 }
@@ -5,7 +5,6 @@ use clap::Args;
 
 use homer_core::config::HomerConfig;
 use homer_core::pipeline::HomerPipeline;
-use homer_core::render::traits::merge_with_preserve;
 use homer_core::store::sqlite::SqliteStore;
 
 #[derive(Args, Debug)]
@@ -200,7 +199,7 @@ async fn show_diff(
         let final_content = if existing.is_empty() {
             new_content
         } else {
-            merge_with_preserve(&existing, &new_content)
+            renderer.merge(&existing, &new_content)
         };
 
         if final_content == existing {
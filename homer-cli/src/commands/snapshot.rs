@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use anyhow::Context;
 use clap::{Args, Subcommand};
 
+use homer_core::backfill::{BackfillOptions, backfill_snapshots};
 use homer_core::store::HomerStore;
 use homer_core::store::sqlite::SqliteStore;
 
@@ -30,6 +31,17 @@ pub enum SnapshotAction {
         /// Label of the snapshot to delete
         label: String,
     },
+    /// Re-extract past releases in scratch worktrees and store true
+    /// historical snapshots
+    Backfill {
+        /// Also snapshot every N first-parent commits
+        #[arg(long)]
+        every_n_commits: Option<u32>,
+
+        /// Recompute snapshots that were already backfilled
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 pub async fn run(args: SnapshotArgs) -> anyhow::Result<()> {
@@ -92,6 +104,32 @@ pub async fn run(args: SnapshotArgs) -> anyhow::Result<()> {
                 println!("Snapshot '{label}' not found");
             }
         }
+        SnapshotAction::Backfill {
+            every_n_commits,
+            force,
+        } => {
+            let config = super::load_config(&repo_path).unwrap_or_default();
+            let options = BackfillOptions {
+                every_n_commits,
+                force,
+            };
+            let result = backfill_snapshots(&repo_path, &store, &config, &options)
+                .await
+                .context("Failed to backfill snapshots")?;
+
+            for label in &result.created {
+                println!("Backfilled snapshot '{label}'");
+            }
+            for (label, error) in &result.failed {
+                println!("Failed to backfill '{label}': {error}");
+            }
+            println!(
+                "{} created, {} already backfilled, {} failed",
+                result.created.len(),
+                result.skipped.len(),
+                result.failed.len()
+            );
+        }
     }
 
     Ok(())
@@ -0,0 +1,687 @@
+// Public API surface per release, and breaking-change classification.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+
+use crate::backfill::{ScratchWorktree, resolve_commit};
+use crate::config::{ApiSurfaceConfig, HomerConfig};
+use crate::error::{AnalyzeError, ExtractError, HomerError};
+use crate::extract::graph::GraphExtractor;
+use crate::extract::structure::StructureExtractor;
+use crate::extract::traits::Extractor;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::store::sqlite::SqliteStore;
+use crate::types::{AnalysisKind, AnalysisResult, AnalysisResultId, NodeFilter, NodeId, NodeKind};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Stores the exported functions and types of each recent release, with
+/// their declaration signatures, on the Release node. Opt-in through
+/// `[analysis.api] enabled`, since every release not yet recorded at its
+/// current commit is checked out and re-extracted.
+#[derive(Debug)]
+pub struct ApiSurfaceAnalyzer {
+    repo_path: PathBuf,
+    /// Settings of the upcoming run, so a change to them triggers a rerun.
+    settings: ApiSurfaceConfig,
+}
+
+impl ApiSurfaceAnalyzer {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.analysis.api.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Analyzer for ApiSurfaceAnalyzer {
+    fn name(&self) -> &'static str {
+        "api_surface"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::ApiSurface]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = release_state(store, &self.settings).await?;
+        incremental::needs_extraction(store, "analyze:api_surface", &state).await
+    }
+
+    #[instrument(skip_all, name = "api_surface_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+        let settings = &config.analysis.api;
+        let max_releases = if settings.enabled {
+            settings.max_releases
+        } else {
+            0
+        };
+
+        // Snapshots of releases that are still in range and still point at
+        // the same commit are kept; everything else is recomputed or dropped.
+        let mut previous: HashMap<NodeId, serde_json::Value> = store
+            .get_analyses_by_kind(AnalysisKind::ApiSurface)
+            .await?
+            .into_iter()
+            .map(|r| (r.node_id, r.data))
+            .collect();
+        let mut stale: HashSet<NodeId> = previous.keys().copied().collect();
+
+        let mut releases = Vec::new();
+        if max_releases > 0 {
+            releases = resolve_releases(&self.repo_path, store).await?;
+            releases.truncate(max_releases);
+        }
+
+        for (idx, release) in releases.iter().enumerate() {
+            let data = match previous.remove(&release.node_id) {
+                Some(data) if data["commit"] == release.commit.as_str() => data,
+                _ => match extract_surface(&self.repo_path, &release.commit, config, idx).await {
+                    Ok(items) => surface_data(&release.commit, release.released_at, &items),
+                    Err(e) => {
+                        warn!(release = %release.name, error = %e, "API snapshot failed");
+                        stats.errors.push((release.name.clone(), e));
+                        continue;
+                    }
+                },
+            };
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: release.node_id,
+                    kind: AnalysisKind::ApiSurface,
+                    data,
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stale.remove(&release.node_id);
+            stats.results_stored += 1;
+        }
+
+        // Releases not re-stored are out of range, gone, or failed.
+        for node_id in stale {
+            store
+                .invalidate_analyses_by_kinds(node_id, &[AnalysisKind::ApiSurface])
+                .await?;
+        }
+
+        let state = release_state(store, settings).await?;
+        store.set_checkpoint("analyze:api_surface", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            releases = releases.len(),
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "API surface analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Release tags and their targets plus the settings; retagging, new
+/// releases, or a settings change trigger a rerun.
+async fn release_state(
+    store: &dyn HomerStore,
+    settings: &ApiSurfaceConfig,
+) -> crate::error::Result<String> {
+    let mut tags: Vec<String> = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Release),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(|r| {
+            let target = r.metadata.get("target").and_then(|v| v.as_str());
+            format!("{}={}", r.name, target.unwrap_or_default())
+        })
+        .collect();
+    tags.sort();
+    Ok(format!(
+        "{}:{:x}:{}",
+        tags.len(),
+        incremental::content_hash(tags.join("\n").as_bytes()),
+        serde_json::to_string(settings).unwrap_or_default()
+    ))
+}
+
+/// A release resolved to its commit.
+struct ResolvedRelease {
+    node_id: NodeId,
+    name: String,
+    commit: String,
+    released_at: DateTime<Utc>,
+}
+
+/// Releases that resolve to a commit, newest first.
+async fn resolve_releases(
+    repo_path: &Path,
+    store: &dyn HomerStore,
+) -> crate::error::Result<Vec<ResolvedRelease>> {
+    let nodes = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Release),
+            ..Default::default()
+        })
+        .await?;
+
+    let mut releases = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let rev = node
+            .metadata
+            .get("target")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&node.name);
+        match resolve_commit(repo_path, rev) {
+            Ok((commit, released_at)) => releases.push(ResolvedRelease {
+                node_id: node.id,
+                name: node.name,
+                commit,
+                released_at,
+            }),
+            Err(e) => warn!(release = %node.name, error = %e, "Cannot resolve release"),
+        }
+    }
+    releases.sort_by_key(|r| std::cmp::Reverse(r.released_at));
+    Ok(releases)
+}
+
+fn surface_data(commit: &str, released_at: DateTime<Utc>, items: &[ApiItem]) -> serde_json::Value {
+    serde_json::json!({
+        "commit": commit,
+        "released_at": released_at.to_rfc3339(),
+        "count": items.len(),
+        "items": items,
+    })
+}
+
+// ── Surfaces ────────────────────────────────────────────────────────
+
+/// An exported function or type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiItem {
+    /// Node name (`{file}::{qualified_name}`).
+    pub name: String,
+    /// `Function` or `Type`.
+    pub kind: String,
+    /// Declaration text without the body, if one was extracted.
+    pub signature: Option<String>,
+}
+
+/// The public API at one commit.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiSurface {
+    /// The ref as given.
+    pub rev: String,
+    /// Resolved commit SHA.
+    pub commit: String,
+    pub items: Vec<ApiItem>,
+}
+
+/// Exported items of the current graph in `store`, sorted by name.
+pub async fn exported_items(store: &dyn HomerStore) -> crate::error::Result<Vec<ApiItem>> {
+    let mut items = Vec::new();
+    for kind in [NodeKind::Function, NodeKind::Type] {
+        let nodes = store
+            .find_nodes(&NodeFilter {
+                kind: Some(kind.clone()),
+                ..Default::default()
+            })
+            .await?;
+        items.extend(
+            nodes
+                .into_iter()
+                .filter(|n| n.metadata.get("exported") == Some(&serde_json::Value::Bool(true)))
+                .map(|n| ApiItem {
+                    signature: n
+                        .metadata
+                        .get("signature")
+                        .and_then(|v| v.as_str())
+                        .map(String::from),
+                    kind: kind.as_str().to_string(),
+                    name: n.name,
+                }),
+        );
+    }
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(items)
+}
+
+/// Re-extract `commit` in a scratch worktree and collect its exported items.
+///
+/// Extractors are not `Send`, so extraction runs on a blocking thread with
+/// its own single-threaded runtime.
+async fn extract_surface(
+    repo_path: &Path,
+    commit: &str,
+    config: &HomerConfig,
+    idx: usize,
+) -> crate::error::Result<Vec<ApiItem>> {
+    let repo_path = repo_path.to_path_buf();
+    let commit = commit.to_string();
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| HomerError::Extract(ExtractError::Io(e)))?;
+        runtime.block_on(async {
+            let worktree = ScratchWorktree::create(&repo_path, "api", &commit, idx)?;
+            let scratch = SqliteStore::in_memory()?;
+            StructureExtractor::new(worktree.path())
+                .extract(&scratch, &config)
+                .await?;
+            GraphExtractor::new(worktree.path())
+                .extract(&scratch, &config)
+                .await?;
+            exported_items(&scratch).await
+        })
+    })
+    .await
+    .map_err(|e| AnalyzeError::Computation(format!("API extraction task failed: {e}")))?
+}
+
+/// The public API at `rev`, from a stored release snapshot at the same
+/// commit when there is one, otherwise extracted on demand.
+pub async fn surface_at(
+    repo_path: &Path,
+    store: &dyn HomerStore,
+    config: &HomerConfig,
+    rev: &str,
+) -> crate::error::Result<ApiSurface> {
+    let (commit, _) = resolve_commit(repo_path, rev)?;
+
+    for result in store.get_analyses_by_kind(AnalysisKind::ApiSurface).await? {
+        if result.data["commit"] != commit.as_str() {
+            continue;
+        }
+        if let Ok(items) = serde_json::from_value(result.data["items"].clone()) {
+            return Ok(ApiSurface {
+                rev: rev.to_string(),
+                commit,
+                items,
+            });
+        }
+    }
+
+    let items = extract_surface(repo_path, &commit, config, 0).await?;
+    Ok(ApiSurface {
+        rev: rev.to_string(),
+        commit,
+        items,
+    })
+}
+
+// ── Diffing ─────────────────────────────────────────────────────────
+
+/// How an item differs between two surfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiChangeKind {
+    Removed,
+    Added,
+    SignatureChanged,
+}
+
+/// One difference between two API surfaces.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiChange {
+    pub name: String,
+    pub kind: String,
+    pub change: ApiChangeKind,
+    pub old_signature: Option<String>,
+    pub new_signature: Option<String>,
+    /// Whether existing callers can stop compiling or working.
+    pub breaking: bool,
+}
+
+/// Differences between two API surfaces.
+#[derive(Debug, Serialize)]
+pub struct ApiDiff {
+    pub from: String,
+    pub from_commit: String,
+    pub to: String,
+    pub to_commit: String,
+    pub changes: Vec<ApiChange>,
+}
+
+impl ApiDiff {
+    pub fn breaking(&self) -> impl Iterator<Item = &ApiChange> {
+        self.changes.iter().filter(|c| c.breaking)
+    }
+
+    pub fn non_breaking(&self) -> impl Iterator<Item = &ApiChange> {
+        self.changes.iter().filter(|c| !c.breaking)
+    }
+}
+
+/// Compare the public API at two refs.
+pub async fn api_diff(
+    repo_path: &Path,
+    store: &dyn HomerStore,
+    config: &HomerConfig,
+    from: &str,
+    to: &str,
+) -> crate::error::Result<ApiDiff> {
+    let old = surface_at(repo_path, store, config, from).await?;
+    let new = surface_at(repo_path, store, config, to).await?;
+    Ok(ApiDiff {
+        changes: diff_items(&old.items, &new.items),
+        from: old.rev,
+        from_commit: old.commit,
+        to: new.rev,
+        to_commit: new.commit,
+    })
+}
+
+/// Classify every item that was removed, added, or changed signature,
+/// sorted by name. Removals are breaking, additions are not, and a
+/// signature change is breaking unless it only appends optional parameters
+/// (defaults, `?` types, variadics).
+pub fn diff_items(old: &[ApiItem], new: &[ApiItem]) -> Vec<ApiChange> {
+    let old: BTreeMap<&str, &ApiItem> = old.iter().map(|i| (i.name.as_str(), i)).collect();
+    let new: BTreeMap<&str, &ApiItem> = new.iter().map(|i| (i.name.as_str(), i)).collect();
+
+    let mut changes = Vec::new();
+    for (name, item) in &old {
+        let Some(after) = new.get(name) else {
+            changes.push(ApiChange {
+                name: (*name).to_string(),
+                kind: item.kind.clone(),
+                change: ApiChangeKind::Removed,
+                old_signature: item.signature.clone(),
+                new_signature: None,
+                breaking: true,
+            });
+            continue;
+        };
+        if item.signature == after.signature {
+            continue;
+        }
+        let breaking = match (&item.signature, &after.signature) {
+            (Some(before), Some(now)) => !only_appends_optional_params(before, now),
+            _ => true,
+        };
+        changes.push(ApiChange {
+            name: (*name).to_string(),
+            kind: after.kind.clone(),
+            change: ApiChangeKind::SignatureChanged,
+            old_signature: item.signature.clone(),
+            new_signature: after.signature.clone(),
+            breaking,
+        });
+    }
+    for (name, item) in &new {
+        if !old.contains_key(name) {
+            changes.push(ApiChange {
+                name: (*name).to_string(),
+                kind: item.kind.clone(),
+                change: ApiChangeKind::Added,
+                old_signature: None,
+                new_signature: item.signature.clone(),
+                breaking: false,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
+
+/// True when `new` is `old` with optional parameters appended and nothing
+/// else changed.
+fn only_appends_optional_params(old: &str, new: &str) -> bool {
+    let (Some((old_head, old_params, old_tail)), Some((new_head, new_params, new_tail))) =
+        (split_params(old), split_params(new))
+    else {
+        return false;
+    };
+    if old_head != new_head || old_tail != new_tail || new_params.len() <= old_params.len() {
+        return false;
+    }
+    new_params[..old_params.len()] == old_params[..]
+        && new_params[old_params.len()..]
+            .iter()
+            .all(|p| is_optional_param(p))
+}
+
+/// Split a signature into the text before its parameter list, the
+/// parameters, and the text after it.
+fn split_params(signature: &str) -> Option<(&str, Vec<&str>, &str)> {
+    let open = signature.find('(')?;
+    let mut depth = 0usize;
+    let mut close = None;
+    for (i, c) in signature[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+
+    let inner = &signature[open + 1..close];
+    let mut params = Vec::new();
+    let (mut depth, mut start, mut prev) = (0usize, 0, ' ');
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            // `->` and `=>` are arrows, not closing brackets.
+            '>' if prev == '-' || prev == '=' => {}
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                params.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    params.push(inner[start..].trim());
+    params.retain(|p| !p.is_empty());
+
+    Some((&signature[..open], params, &signature[close + 1..]))
+}
+
+/// Parameters callers may omit: defaults, optional (`?`) types, and
+/// variadics.
+fn is_optional_param(param: &str) -> bool {
+    param.starts_with('*')
+        || param.contains("...")
+        || param.contains("?:")
+        || param.replace("=>", "").contains('=')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+    use crate::extract::git::GitExtractor;
+
+    fn item(name: &str, signature: &str) -> ApiItem {
+        ApiItem {
+            name: name.to_string(),
+            kind: "Function".to_string(),
+            signature: Some(signature.to_string()),
+        }
+    }
+
+    #[test]
+    fn classifies_removed_added_and_changed_items() {
+        let old = vec![
+            item("lib.rs::parse", "pub fn parse(input: &str) -> Ast"),
+            item("lib.rs::render", "pub fn render(ast: &Ast) -> String"),
+            item("api.py::fetch", "def fetch(url)"),
+            item("api.py::close", "def close(conn)"),
+        ];
+        let new = vec![
+            item(
+                "lib.rs::parse",
+                "pub fn parse(input: &str, strict: bool) -> Ast",
+            ),
+            item("lib.rs::format", "pub fn format(ast: &Ast) -> String"),
+            item("api.py::fetch", "def fetch(url, retries=3, *args)"),
+            item("api.py::close", "def close(conn) -> None"),
+        ];
+
+        let changes = diff_items(&old, &new);
+        let find = |name: &str| changes.iter().find(|c| c.name == name).unwrap();
+
+        assert_eq!(changes.len(), 5);
+        assert_eq!(find("lib.rs::render").change, ApiChangeKind::Removed);
+        assert!(find("lib.rs::render").breaking);
+        assert_eq!(find("lib.rs::format").change, ApiChangeKind::Added);
+        assert!(!find("lib.rs::format").breaking);
+        assert_eq!(
+            find("lib.rs::parse").change,
+            ApiChangeKind::SignatureChanged
+        );
+        assert!(find("lib.rs::parse").breaking, "required parameter added");
+        assert!(!find("api.py::fetch").breaking, "optional parameters added");
+        assert!(find("api.py::close").breaking, "return type changed");
+    }
+
+    #[test]
+    fn optional_parameters_need_matching_prefix() {
+        assert!(only_appends_optional_params(
+            "function get(url: string): Promise<T>",
+            "function get(url: string, opts?: Options): Promise<T>"
+        ));
+        assert!(only_appends_optional_params(
+            "function on(event)",
+            "function on(event, cb = () => {})"
+        ));
+        assert!(!only_appends_optional_params(
+            "function get(url: string)",
+            "function get(opts?: Options, url: string)"
+        ));
+        assert!(!only_appends_optional_params(
+            "function get(url: string)",
+            "function get(url: string, cb: (e: E) => void)"
+        ));
+        assert!(!only_appends_optional_params(
+            "pub struct Config<T>",
+            "pub struct Config<T, U>"
+        ));
+    }
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .expect("git command failed");
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn commit_and_tag(dir: &Path, content: &str, tag: &str) {
+        std::fs::write(dir.join("lib.rs"), content).unwrap();
+        run_git(dir, &["add", "."]);
+        run_git(dir, &["commit", "-q", "-m", tag]);
+        run_git(dir, &["tag", tag]);
+    }
+
+    #[tokio::test]
+    async fn snapshots_releases_and_diffs_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        run_git(dir, &["init", "-q"]);
+        commit_and_tag(
+            dir,
+            "pub fn parse(input: &str) -> u32 {\n    input.len() as u32\n}\n\n\
+             pub fn render(n: u32) -> String {\n    n.to_string()\n}\n\n\
+             fn helper() {}\n",
+            "v1.0",
+        );
+        commit_and_tag(
+            dir,
+            "pub fn parse(input: &str, strict: bool) -> u32 {\n    input.len() as u32\n}\n\n\
+             pub fn describe(n: u32) -> String {\n    n.to_string()\n}\n",
+            "v2.0",
+        );
+
+        let store = SqliteStore::in_memory().unwrap();
+        let mut config = HomerConfig::default();
+        GitExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+
+        // Snapshots are opt-in; enabling them triggers a rerun.
+        let stats = ApiSurfaceAnalyzer::new(dir, &config)
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        assert_eq!(stats.results_stored, 0);
+        config.analysis.api.enabled = true;
+        let analyzer = ApiSurfaceAnalyzer::new(dir, &config);
+        assert!(analyzer.needs_rerun(&store).await.unwrap());
+        let stats = analyzer.analyze(&store, &config).await.unwrap();
+        assert_eq!(stats.results_stored, 2, "errors: {:?}", stats.errors);
+        assert!(!analyzer.needs_rerun(&store).await.unwrap());
+
+        let v1 = store
+            .get_node_by_name(NodeKind::Release, "v1.0")
+            .await
+            .unwrap()
+            .unwrap();
+        let surface = store
+            .get_analysis(v1.id, AnalysisKind::ApiSurface)
+            .await
+            .unwrap()
+            .unwrap();
+        let names: Vec<&str> = surface.data["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|i| i["name"].as_str())
+            .collect();
+        assert_eq!(names, vec!["lib.rs::parse", "lib.rs::render"]);
+
+        // Narrowing the range keeps one snapshot and drops the other.
+        let mut narrow = config.clone();
+        narrow.analysis.api.max_releases = 1;
+        analyzer.analyze(&store, &narrow).await.unwrap();
+        let kept = store
+            .get_analyses_by_kind(AnalysisKind::ApiSurface)
+            .await
+            .unwrap();
+        assert_eq!(kept.len(), 1);
+
+        let diff = api_diff(dir, &store, &config, "v1.0", "v2.0")
+            .await
+            .unwrap();
+        let breaking: Vec<&str> = diff.breaking().map(|c| c.name.as_str()).collect();
+        let added: Vec<&str> = diff.non_breaking().map(|c| c.name.as_str()).collect();
+        assert_eq!(breaking, vec!["lib.rs::parse", "lib.rs::render"]);
+        assert_eq!(added, vec!["lib.rs::describe"]);
+    }
+}
@@ -12,8 +12,8 @@ use std::time::Instant;
 use chrono::Utc;
 use tracing::{info, instrument};
 
-use crate::config::HomerConfig;
-use crate::contracts;
+use crate::config::{CoChangeConfig, HomerConfig};
+use crate::contracts::{self, roles};
 use crate::store::HomerStore;
 use crate::store::incremental;
 use crate::types::{
@@ -24,8 +24,20 @@ use crate::types::{
 use super::AnalyzeStats;
 use super::traits::Analyzer;
 
-#[derive(Debug)]
-pub struct BehavioralAnalyzer;
+#[derive(Debug, Default)]
+pub struct BehavioralAnalyzer {
+    /// Co-change settings of the upcoming run, so a change to them
+    /// triggers a rerun.
+    pub co_change: CoChangeConfig,
+}
+
+impl BehavioralAnalyzer {
+    pub fn new(config: &HomerConfig) -> Self {
+        Self {
+            co_change: config.analysis.co_change.clone(),
+        }
+    }
+}
 
 #[async_trait::async_trait]
 impl Analyzer for BehavioralAnalyzer {
@@ -44,19 +56,15 @@ impl Analyzer for BehavioralAnalyzer {
     }
 
     async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
-        let filter = NodeFilter {
-            kind: Some(NodeKind::Commit),
-            ..Default::default()
-        };
-        let commit_count = store.find_nodes(&filter).await?.len();
-        incremental::needs_extraction(store, "analyze:behavioral", &commit_count.to_string()).await
+        let state = behavioral_state(store, &self.co_change).await?;
+        incremental::needs_extraction(store, "analyze:behavioral", &state).await
     }
 
     #[instrument(skip_all, name = "behavioral_analyze")]
     async fn analyze(
         &self,
         store: &dyn HomerStore,
-        _config: &HomerConfig,
+        config: &HomerConfig,
     ) -> crate::error::Result<AnalyzeStats> {
         let start = Instant::now();
         let mut stats = AnalyzeStats::default();
@@ -85,7 +93,7 @@ impl Analyzer for BehavioralAnalyzer {
         compute_bus_factor(store, &commit_data, &mut stats).await?;
 
         // Compute and store co-change sets
-        compute_co_change(store, &commit_data, &mut stats).await?;
+        compute_co_change(store, &commit_data, &config.analysis.co_change, &mut stats).await?;
 
         // Compute documentation coverage
         compute_doc_coverage(store, &mut stats).await?;
@@ -94,14 +102,8 @@ impl Analyzer for BehavioralAnalyzer {
         compute_doc_freshness(store, &commit_data, &mut stats).await?;
 
         // Set checkpoint so we can skip rerun if nothing changed.
-        let filter = NodeFilter {
-            kind: Some(NodeKind::Commit),
-            ..Default::default()
-        };
-        let commit_count = store.find_nodes(&filter).await?.len();
-        store
-            .set_checkpoint("analyze:behavioral", &commit_count.to_string())
-            .await?;
+        let state = behavioral_state(store, &config.analysis.co_change).await?;
+        store.set_checkpoint("analyze:behavioral", &state).await?;
 
         stats.duration = start.elapsed();
         info!(
@@ -113,12 +115,28 @@ impl Analyzer for BehavioralAnalyzer {
     }
 }
 
+/// Checkpoint state: the commit count plus the co-change settings.
+async fn behavioral_state(
+    store: &dyn HomerStore,
+    co_change: &CoChangeConfig,
+) -> crate::error::Result<String> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Commit),
+        ..Default::default()
+    };
+    let commit_count = store.find_nodes(&filter).await?.len();
+    let settings = serde_json::to_string(co_change).unwrap_or_default();
+    Ok(format!("{commit_count}:{settings}"))
+}
+
 // ── Data collection ───────────────────────────────────────────────
 
 /// Intermediate data collected from the store for analysis.
 struct CommitData {
     file_commits: HashMap<NodeId, Vec<FileChange>>,
     commit_files: HashMap<NodeId, HashSet<NodeId>>,
+    /// Commit → (author time, author)
+    commit_info: HashMap<NodeId, (chrono::DateTime<Utc>, Option<NodeId>)>,
     /// Total commit count
     commit_count: usize,
 }
@@ -133,6 +151,7 @@ struct FileChange {
 async fn collect_commit_data(store: &dyn HomerStore) -> crate::error::Result<CommitData> {
     let mut file_commits: HashMap<NodeId, Vec<FileChange>> = HashMap::new();
     let mut commit_files: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
+    let mut commit_info = HashMap::new();
 
     // Get all Modifies edges
     let modifies_edges = store.get_edges_by_kind(HyperedgeKind::Modifies).await?;
@@ -148,6 +167,8 @@ async fn collect_commit_data(store: &dyn HomerStore) -> crate::error::Result<Com
         }
     }
 
+    let moves = load_file_moves(store).await?;
+
     for edge in &modifies_edges {
         let commit_member = edge.members.iter().find(|m| m.role == "commit");
         let Some(commit_m) = commit_member else {
@@ -156,6 +177,7 @@ async fn collect_commit_data(store: &dyn HomerStore) -> crate::error::Result<Com
         let commit_id = commit_m.node_id;
         let commit_time = edge.last_updated;
         let author_id = commit_author.get(&commit_id).copied();
+        commit_info.insert(commit_id, (commit_time, author_id));
 
         // Parse per-file diff data from metadata
         let files_meta = edge
@@ -168,6 +190,7 @@ async fn collect_commit_data(store: &dyn HomerStore) -> crate::error::Result<Com
         let file_members: Vec<_> = edge.members.iter().filter(|m| m.role == "file").collect();
 
         for (idx, file_m) in file_members.iter().enumerate() {
+            let file_id = follow_moves(file_m.node_id, commit_time, &moves);
             let (added, deleted) = files_meta.get(idx).map_or((0, 0), |f| {
                 let a = f
                     .get("lines_added")
@@ -180,20 +203,14 @@ async fn collect_commit_data(store: &dyn HomerStore) -> crate::error::Result<Com
                 (a, d)
             });
 
-            file_commits
-                .entry(file_m.node_id)
-                .or_default()
-                .push(FileChange {
-                    commit_time,
-                    lines_added: added,
-                    lines_deleted: deleted,
-                    author_id,
-                });
+            file_commits.entry(file_id).or_default().push(FileChange {
+                commit_time,
+                lines_added: added,
+                lines_deleted: deleted,
+                author_id,
+            });
 
-            commit_files
-                .entry(commit_id)
-                .or_default()
-                .insert(file_m.node_id);
+            commit_files.entry(commit_id).or_default().insert(file_id);
         }
     }
 
@@ -201,10 +218,48 @@ async fn collect_commit_data(store: &dyn HomerStore) -> crate::error::Result<Com
     Ok(CommitData {
         file_commits,
         commit_files,
+        commit_info,
         commit_count,
     })
 }
 
+/// Detected file moves: old file → (new file, time of the move).
+async fn load_file_moves(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<NodeId, (NodeId, chrono::DateTime<Utc>)>> {
+    let mut moves = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Aliases).await? {
+        if edge.metadata.get("refactoring").and_then(|v| v.as_str()) != Some("move_file") {
+            continue;
+        }
+        let old = edge.members.iter().find(|m| m.role == roles::ALIAS_OLD);
+        let new = edge.members.iter().find(|m| m.role == roles::ALIAS_NEW);
+        if let (Some(old), Some(new)) = (old, new) {
+            moves.insert(old.node_id, (new.node_id, edge.last_updated));
+        }
+    }
+    Ok(moves)
+}
+
+/// The file that carries a change's history: changes made before a file
+/// moved count toward its new path. A path reused after the move keeps its
+/// own later changes.
+fn follow_moves(
+    file: NodeId,
+    at: chrono::DateTime<Utc>,
+    moves: &HashMap<NodeId, (NodeId, chrono::DateTime<Utc>)>,
+) -> NodeId {
+    let mut current = file;
+    // Bounded like alias resolution in the store, in case of cycles.
+    for _ in 0..10 {
+        match moves.get(&current) {
+            Some(&(next, moved_at)) if at < moved_at => current = next,
+            _ => break,
+        }
+    }
+    current
+}
+
 // ── Change frequency ──────────────────────────────────────────────
 
 async fn compute_change_frequency(
@@ -430,15 +485,15 @@ async fn compute_bus_factor(
 
 // ── Co-change detection (seed-and-grow) ───────────────────────────
 
-/// Configuration for co-change detection (per ANALYZERS.md spec).
-struct CoChangeConfig {
+/// Thresholds for co-change detection (per ANALYZERS.md spec).
+struct CoChangeThresholds {
     min_confidence: f64,
     min_co_occurrences: u32,
     max_group_size: usize,
     min_marginal_gain: f64,
 }
 
-impl Default for CoChangeConfig {
+impl Default for CoChangeThresholds {
     fn default() -> Self {
         Self {
             min_confidence: 0.3,
@@ -449,6 +504,75 @@ impl Default for CoChangeConfig {
     }
 }
 
+/// Commits treated as one change, with the time each file was first touched.
+struct ChangeSet {
+    commits: Vec<NodeId>,
+    first_touch: HashMap<NodeId, chrono::DateTime<Utc>>,
+}
+
+impl ChangeSet {
+    /// Hours between the first and last of `files` to be changed, or `None`
+    /// when a single commit in the set changed all of them.
+    fn lag_hours(
+        &self,
+        files: &[NodeId],
+        commit_files: &HashMap<NodeId, HashSet<NodeId>>,
+    ) -> Option<f64> {
+        let together = self.commits.iter().any(|c| {
+            commit_files
+                .get(c)
+                .is_some_and(|changed| files.iter().all(|f| changed.contains(f)))
+        });
+        if together {
+            return None;
+        }
+        let times: Vec<_> = files
+            .iter()
+            .filter_map(|f| self.first_touch.get(f))
+            .collect();
+        let first = times.iter().min()?;
+        let last = times.iter().max()?;
+        Some((**last - **first).num_seconds() as f64 / 3600.0)
+    }
+}
+
+/// How long after one another co-changing files are changed.
+#[derive(Clone, Default)]
+struct LagStats {
+    same_commit: u32,
+    follow_up_hours: Vec<f64>,
+}
+
+impl LagStats {
+    fn record(&mut self, lag: Option<f64>) {
+        match lag {
+            Some(hours) => self.follow_up_hours.push(hours),
+            None => self.same_commit += 1,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut hours = self.follow_up_hours.clone();
+        hours.sort_by(f64::total_cmp);
+        let count = |lo: f64, hi: f64| hours.iter().filter(|&&h| h >= lo && h < hi).count();
+        // Nearest-rank percentile over follow-up commits only.
+        let percentile = |p: f64| {
+            let rank = ((p * hours.len() as f64).ceil() as usize).max(1);
+            hours.get(rank - 1).map(|h| (h * 10.0).round() / 10.0)
+        };
+        serde_json::json!({
+            "same_commit": self.same_commit,
+            "follow_ups": hours.len(),
+            "under_1h": count(0.0, 1.0),
+            "1h_to_1d": count(1.0, 24.0),
+            "1d_to_1w": count(24.0, 168.0),
+            "over_1w": count(168.0, f64::INFINITY),
+            "median_hours": percentile(0.5),
+            "p90_hours": percentile(0.9),
+        })
+    }
+}
+
 /// A scored pair of files with their co-occurrence count and confidence.
 #[derive(Clone)]
 struct ScoredPair {
@@ -456,63 +580,181 @@ struct ScoredPair {
     file_b: NodeId,
     count: u32,
     confidence: f64,
+    lag: LagStats,
+    /// Follow-ups in which `file_a` (`.0`) or `file_b` (`.1`) changed first.
+    led: (u32, u32),
+}
+
+/// Co-occurrence tallies for a file pair across change sets.
+#[derive(Default)]
+struct PairTally {
+    count: u32,
+    lag: LagStats,
+    led: (u32, u32),
+}
+
+/// Group commits into change sets: one per commit, joined by the configured
+/// time window and by pull request.
+async fn build_change_sets(
+    store: &dyn HomerStore,
+    data: &CommitData,
+    config: &CoChangeConfig,
+) -> crate::error::Result<Vec<ChangeSet>> {
+    let mut commits: Vec<NodeId> = data.commit_files.keys().copied().collect();
+    commits.sort_unstable_by_key(|c| c.0);
+    let index: HashMap<NodeId, usize> = commits.iter().enumerate().map(|(i, c)| (*c, i)).collect();
+    let mut parent: Vec<usize> = (0..commits.len()).collect();
+
+    if config.window_hours > 0 {
+        let window = chrono::Duration::hours(i64::from(config.window_hours));
+        let mut streams: HashMap<Option<NodeId>, Vec<(chrono::DateTime<Utc>, usize)>> =
+            HashMap::new();
+        for (i, commit) in commits.iter().enumerate() {
+            let Some(&(time, author)) = data.commit_info.get(commit) else {
+                continue;
+            };
+            let key = if config.window_same_author {
+                // Without an author there is nothing to scope the window to.
+                let Some(author) = author else { continue };
+                Some(author)
+            } else {
+                None
+            };
+            streams.entry(key).or_default().push((time, i));
+        }
+        // Each set spans at most one window from its first commit, so a
+        // steady committer does not chain their whole history together.
+        for stream in streams.values_mut() {
+            stream.sort_unstable();
+            let mut set_start: Option<(chrono::DateTime<Utc>, usize)> = None;
+            for &(time, i) in stream.iter() {
+                match set_start {
+                    Some((start, first)) if time - start <= window => {
+                        union(&mut parent, first, i);
+                    }
+                    _ => set_start = Some((time, i)),
+                }
+            }
+        }
+    }
+
+    if config.group_pull_requests {
+        let mut pr_commit: HashMap<NodeId, usize> = HashMap::new();
+        for edge in store.get_edges_by_kind(HyperedgeKind::Includes).await? {
+            let Some(pr) = edge.members.iter().find(|m| m.role == "pull_request") else {
+                continue;
+            };
+            for member in &edge.members {
+                let Some(&i) = index.get(&member.node_id) else {
+                    continue;
+                };
+                let first = *pr_commit.entry(pr.node_id).or_insert(i);
+                union(&mut parent, first, i);
+            }
+        }
+    }
+
+    let mut sets: HashMap<usize, ChangeSet> = HashMap::new();
+    for (i, commit) in commits.iter().enumerate() {
+        let root = find(&mut parent, i);
+        let set = sets.entry(root).or_insert_with(|| ChangeSet {
+            commits: Vec::new(),
+            first_touch: HashMap::new(),
+        });
+        set.commits.push(*commit);
+        let Some(&(time, _)) = data.commit_info.get(commit) else {
+            continue;
+        };
+        for file in &data.commit_files[commit] {
+            set.first_touch
+                .entry(*file)
+                .and_modify(|t| *t = (*t).min(time))
+                .or_insert(time);
+        }
+    }
+    Ok(sets.into_values().collect())
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[ra.max(rb)] = ra.min(rb);
+    }
 }
 
 #[allow(clippy::too_many_lines)]
 async fn compute_co_change(
     store: &dyn HomerStore,
     data: &CommitData,
+    config: &CoChangeConfig,
     stats: &mut AnalyzeStats,
 ) -> crate::error::Result<()> {
-    let cfg = CoChangeConfig::default();
+    let cfg = CoChangeThresholds::default();
     let now = Utc::now();
 
-    // Step 1: Build co-occurrence matrix
-    let mut co_occur: HashMap<(NodeId, NodeId), u32> = HashMap::new();
+    let change_sets = build_change_sets(store, data, config).await?;
+
+    // Step 1: Build co-occurrence matrix over change sets
+    let mut co_occur: HashMap<(NodeId, NodeId), PairTally> = HashMap::new();
+    let mut file_set_count: HashMap<NodeId, usize> = HashMap::new();
 
-    for file_set in data.commit_files.values() {
-        let files: Vec<NodeId> = file_set.iter().copied().collect();
+    for set in &change_sets {
+        let mut files: Vec<NodeId> = set.first_touch.keys().copied().collect();
+        files.sort_unstable_by_key(|f| f.0);
+        for &file in &files {
+            *file_set_count.entry(file).or_default() += 1;
+        }
         for i in 0..files.len() {
             for j in (i + 1)..files.len() {
-                let pair = if files[i].0 < files[j].0 {
-                    (files[i], files[j])
-                } else {
-                    (files[j], files[i])
-                };
-                *co_occur.entry(pair).or_default() += 1;
+                let (a, b) = (files[i], files[j]);
+                let tally = co_occur.entry((a, b)).or_default();
+                tally.count += 1;
+                let lag = set.lag_hours(&[a, b], &data.commit_files);
+                tally.lag.record(lag);
+                if lag.is_some() {
+                    match set.first_touch[&a].cmp(&set.first_touch[&b]) {
+                        std::cmp::Ordering::Less => tally.led.0 += 1,
+                        std::cmp::Ordering::Greater => tally.led.1 += 1,
+                        std::cmp::Ordering::Equal => {}
+                    }
+                }
             }
         }
     }
 
     // Step 2: Compute pairwise confidence, filter by thresholds
     let mut scored_pairs: Vec<ScoredPair> = Vec::new();
-    // Also build a quick lookup: file_id → commit count
-    let file_commit_count: HashMap<NodeId, usize> = data
-        .file_commits
-        .iter()
-        .map(|(id, changes)| (*id, changes.len()))
-        .collect();
 
-    for (&(file_a, file_b), &count) in &co_occur {
-        if count < cfg.min_co_occurrences {
+    for ((file_a, file_b), tally) in co_occur {
+        if tally.count < cfg.min_co_occurrences {
             continue;
         }
 
-        let commits_a = file_commit_count.get(&file_a).copied().unwrap_or(0);
-        let commits_b = file_commit_count.get(&file_b).copied().unwrap_or(0);
+        let sets_a = file_set_count.get(&file_a).copied().unwrap_or(0);
+        let sets_b = file_set_count.get(&file_b).copied().unwrap_or(0);
 
-        let min_changes = commits_a.min(commits_b) as f64;
+        let min_changes = sets_a.min(sets_b) as f64;
         if min_changes < 1.0 {
             continue;
         }
-        let confidence = count as f64 / min_changes;
+        let confidence = tally.count as f64 / min_changes;
 
         if confidence >= cfg.min_confidence {
             scored_pairs.push(ScoredPair {
                 file_a,
                 file_b,
-                count,
+                count: tally.count,
                 confidence,
+                lag: tally.lag,
+                led: tally.led,
             });
         }
     }
@@ -560,7 +802,7 @@ async fn compute_co_change(
     }
 
     // Step 4: Emit CoChanges hyperedges
-    let total_commits = data.commit_count as f64;
+    let total_sets = change_sets.len() as f64;
 
     for (group_members, group_conf) in &groups {
         let members: Vec<HyperedgeMember> = group_members
@@ -573,9 +815,10 @@ async fn compute_co_change(
             })
             .collect();
 
-        // Compute group co-occurrence: commits where ALL members appear
-        let group_co_occur = count_group_co_occurrence(group_members, &data.commit_files);
-        let support = group_co_occur as f64 / total_commits.max(1.0);
+        // Compute group co-occurrence: change sets where ALL members appear
+        let (group_co_occur, group_lag) =
+            group_co_occurrence(group_members, &change_sets, &data.commit_files);
+        let support = group_co_occur as f64 / total_sets.max(1.0);
 
         let mut meta = HashMap::new();
         meta.insert(
@@ -584,6 +827,7 @@ async fn compute_co_change(
         );
         meta.insert("support".to_string(), serde_json::json!(support));
         meta.insert("arity".to_string(), serde_json::json!(group_members.len()));
+        meta.insert("lag".to_string(), group_lag.to_json());
 
         store
             .upsert_hyperedge(&Hyperedge {
@@ -600,36 +844,48 @@ async fn compute_co_change(
 
     // Step 5: Also enrich per-file ChangeFrequency with top co-change partners
     // (for quick per-file lookup in renderers)
-    let mut file_partners: HashMap<NodeId, Vec<(NodeId, u32, f64)>> = HashMap::new();
+    // (partner, pair, follow-ups in which this file changed first)
+    let mut file_partners: HashMap<NodeId, Vec<(NodeId, &ScoredPair, u32)>> = HashMap::new();
     for pair in &scored_pairs {
-        file_partners.entry(pair.file_a).or_default().push((
-            pair.file_b,
-            pair.count,
-            pair.confidence,
-        ));
-        file_partners.entry(pair.file_b).or_default().push((
-            pair.file_a,
-            pair.count,
-            pair.confidence,
-        ));
+        file_partners
+            .entry(pair.file_a)
+            .or_default()
+            .push((pair.file_b, pair, pair.led.0));
+        file_partners
+            .entry(pair.file_b)
+            .or_default()
+            .push((pair.file_a, pair, pair.led.1));
     }
 
     for (file_id, mut partners) in file_partners {
-        partners.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+        partners.sort_by(|a, b| {
+            b.1.confidence
+                .partial_cmp(&a.1.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
         partners.truncate(10);
 
         let mut partner_data = Vec::new();
-        for (partner_id, count, confidence) in &partners {
+        for (partner_id, pair, led) in &partners {
             let name = store
                 .get_node(*partner_id)
                 .await?
                 .map_or_else(|| format!("node:{}", partner_id.0), |n| n.name);
-            partner_data.push(serde_json::json!({
+            let mut entry = serde_json::json!({
                 "file": name,
-                "co_occurrences": count,
-                "confidence": (confidence * 100.0).round() / 100.0,
-                "support": *count as f64 / total_commits,
-            }));
+                "co_occurrences": pair.count,
+                "confidence": (pair.confidence * 100.0).round() / 100.0,
+                "support": pair.count as f64 / total_sets,
+                "lag": pair.lag.to_json(),
+            });
+            let follow_ups = pair.lag.follow_up_hours.len();
+            if follow_ups > 0 {
+                // Share of follow-ups in which the partner changed after this file
+                entry["partner_follows"] = serde_json::json!(
+                    (f64::from(*led) / follow_ups as f64 * 100.0).round() / 100.0
+                );
+            }
+            partner_data.push(entry);
         }
 
         if let Ok(Some(mut freq)) = store
@@ -648,6 +904,7 @@ async fn compute_co_change(
 
     info!(
         groups = groups.len(),
+        change_sets = change_sets.len(),
         pairs = scored_pairs.len(),
         "Co-change analysis complete"
     );
@@ -660,7 +917,7 @@ fn grow_group(
     group_min_conf: &mut f64,
     pair_confidence: &HashMap<(NodeId, NodeId), f64>,
     consumed: &mut HashSet<(NodeId, NodeId)>,
-    cfg: &CoChangeConfig,
+    cfg: &CoChangeThresholds,
 ) {
     loop {
         if group.len() >= cfg.max_group_size {
@@ -734,18 +991,22 @@ fn grow_group(
     }
 }
 
-/// Count how many commits contain ALL members of a group.
-fn count_group_co_occurrence(
+/// Count how many change sets contain ALL members of a group, with the lag
+/// between the first and last member's change in each.
+fn group_co_occurrence(
     group: &[NodeId],
+    change_sets: &[ChangeSet],
     commit_files: &HashMap<NodeId, HashSet<NodeId>>,
-) -> u32 {
+) -> (u32, LagStats) {
     let mut count = 0u32;
-    for file_set in commit_files.values() {
-        if group.iter().all(|id| file_set.contains(id)) {
+    let mut lag = LagStats::default();
+    for set in change_sets {
+        if group.iter().all(|id| set.first_touch.contains_key(id)) {
             count += 1;
+            lag.record(set.lag_hours(group, commit_files));
         }
     }
-    count
+    (count, lag)
 }
 
 // ── Documentation coverage ────────────────────────────────────────
@@ -887,6 +1148,8 @@ async fn compute_doc_freshness(
 mod tests {
     use super::*;
     use crate::config::HomerConfig;
+    use crate::extract::forge_common::link_pr_commits;
+    use crate::extract::traits::ExtractStats;
     use crate::store::sqlite::SqliteStore;
     use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember, Node};
 
@@ -1060,7 +1323,7 @@ mod tests {
         let store = SqliteStore::in_memory().unwrap();
         setup_test_data(&store).await;
 
-        let analyzer = BehavioralAnalyzer;
+        let analyzer = BehavioralAnalyzer::default();
         let config = HomerConfig::default();
         let stats = analyzer.analyze(&store, &config).await.unwrap();
 
@@ -1258,7 +1521,7 @@ mod tests {
             }
         }
 
-        let analyzer = BehavioralAnalyzer;
+        let analyzer = BehavioralAnalyzer::default();
         let config = HomerConfig::default();
         analyzer.analyze(&store, &config).await.unwrap();
 
@@ -1390,7 +1653,7 @@ mod tests {
                 .unwrap();
         }
 
-        let analyzer = BehavioralAnalyzer;
+        let analyzer = BehavioralAnalyzer::default();
         let config = HomerConfig::default();
         analyzer.analyze(&store, &config).await.unwrap();
 
@@ -1438,12 +1701,412 @@ mod tests {
         assert_eq!(co_occur, 5, "All 5 commits touch all 3 files");
     }
 
+    async fn add_node(store: &SqliteStore, kind: NodeKind, name: &str) -> NodeId {
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn add_commit(
+        store: &SqliteStore,
+        name: &str,
+        author: NodeId,
+        time: chrono::DateTime<Utc>,
+        files: &[NodeId],
+    ) -> NodeId {
+        let commit = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Commit,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: time,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Authored,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: author,
+                        role: "author".to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: commit,
+                        role: "commit".to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence: 1.0,
+                last_updated: time,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let mut members = vec![HyperedgeMember {
+            node_id: commit,
+            role: "commit".to_string(),
+            position: 0,
+        }];
+        for (pos, &file) in files.iter().enumerate() {
+            members.push(HyperedgeMember {
+                node_id: file,
+                role: "file".to_string(),
+                position: (pos + 1) as u32,
+            });
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Modifies,
+                members,
+                confidence: 1.0,
+                last_updated: time,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        commit
+    }
+
+    /// Three rounds of `src/api.rs` changes followed by a separate commit to
+    /// `src/client.rs` `gap` later, each round linked to its own pull request.
+    async fn setup_follow_ups(
+        gap: chrono::Duration,
+        second_author: bool,
+    ) -> (SqliteStore, NodeId, NodeId) {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for name in [
+            "src/api.rs",
+            "src/client.rs",
+            "dev@test.com",
+            "ops@test.com",
+        ] {
+            let kind = if name.contains('@') {
+                NodeKind::Contributor
+            } else {
+                NodeKind::File
+            };
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+        let (api, client, dev, ops) = (ids[0], ids[1], ids[2], ids[3]);
+        let follower = if second_author { ops } else { dev };
+
+        for round in 0..3 {
+            let at = now - chrono::Duration::days(30 - round * 7);
+            add_commit(&store, &format!("api-{round}"), dev, at, &[api]).await;
+            add_commit(
+                &store,
+                &format!("client-{round}"),
+                follower,
+                at + gap,
+                &[client],
+            )
+            .await;
+            // Shaped like forge extractor output: one Includes edge with
+            // the merge commit and every branch commit.
+            let merge = format!("merge-{round}");
+            add_commit(
+                &store,
+                &merge,
+                dev,
+                at + gap + chrono::Duration::hours(1),
+                &[],
+            )
+            .await;
+            let pr = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::PullRequest,
+                    name: format!("PR#{round}"),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            link_pr_commits(
+                &store,
+                &mut ExtractStats::default(),
+                pr,
+                Some(&merge),
+                &[format!("api-{round}"), format!("client-{round}")],
+            )
+            .await
+            .unwrap();
+        }
+        (store, api, client)
+    }
+
+    fn co_change_partner(freq: &AnalysisResult, name: &str) -> Option<serde_json::Value> {
+        freq.data
+            .get("co_change_partners")?
+            .as_array()?
+            .iter()
+            .find(|p| p.get("file").and_then(|f| f.as_str()) == Some(name))
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn time_window_links_follow_up_commits() {
+        let gap = chrono::Duration::hours(2);
+
+        // Same-commit co-change only: the follow-ups are invisible.
+        let (store, api, _) = setup_follow_ups(gap, false).await;
+        BehavioralAnalyzer::default()
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        let freq = store
+            .get_analysis(api, AnalysisKind::ChangeFrequency)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(co_change_partner(&freq, "src/client.rs").is_none());
+
+        let mut config = HomerConfig::default();
+        config.analysis.co_change.window_hours = 24;
+
+        // A different author's follow-up stays separate by default.
+        let (store, api, _) = setup_follow_ups(gap, true).await;
+        BehavioralAnalyzer::default()
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        let freq = store
+            .get_analysis(api, AnalysisKind::ChangeFrequency)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(co_change_partner(&freq, "src/client.rs").is_none());
+
+        let (store, api, client) = setup_follow_ups(gap, false).await;
+        BehavioralAnalyzer::default()
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        let freq = store
+            .get_analysis(api, AnalysisKind::ChangeFrequency)
+            .await
+            .unwrap()
+            .unwrap();
+        let partner = co_change_partner(&freq, "src/client.rs").expect("window links the pair");
+        assert_eq!(partner["co_occurrences"], 3);
+        assert_eq!(partner["lag"]["same_commit"], 0);
+        assert_eq!(partner["lag"]["follow_ups"], 3);
+        assert_eq!(partner["lag"]["1h_to_1d"], 3);
+        assert_eq!(partner["lag"]["median_hours"], 2.0);
+        assert_eq!(partner["partner_follows"], 1.0);
+
+        // From the client's side, the API never follows.
+        let freq = store
+            .get_analysis(client, AnalysisKind::ChangeFrequency)
+            .await
+            .unwrap()
+            .unwrap();
+        let partner = co_change_partner(&freq, "src/api.rs").unwrap();
+        assert_eq!(partner["partner_follows"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn moved_files_keep_their_history() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for (kind, name) in [
+            (NodeKind::File, "src/parser.rs"),
+            (NodeKind::File, "src/parse.rs"),
+            (NodeKind::Contributor, "dev@test.com"),
+        ] {
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+        let (old, new, dev) = (ids[0], ids[1], ids[2]);
+        let moved_at = now - chrono::Duration::days(10);
+        add_commit(
+            &store,
+            "a",
+            dev,
+            moved_at - chrono::Duration::days(5),
+            &[old],
+        )
+        .await;
+        add_commit(
+            &store,
+            "b",
+            dev,
+            moved_at - chrono::Duration::days(2),
+            &[old],
+        )
+        .await;
+        add_commit(&store, "c", dev, moved_at, &[new]).await;
+        add_commit(&store, "d", dev, now - chrono::Duration::days(1), &[new]).await;
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Aliases,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: old,
+                        role: roles::ALIAS_OLD.to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: new,
+                        role: roles::ALIAS_NEW.to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence: 1.0,
+                last_updated: moved_at,
+                metadata: [("refactoring".to_string(), serde_json::json!("move_file"))]
+                    .into_iter()
+                    .collect(),
+            })
+            .await
+            .unwrap();
+
+        BehavioralAnalyzer::default()
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        let freq = store
+            .get_analysis(new, AnalysisKind::ChangeFrequency)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            freq.data["total"], 4,
+            "pre-move commits count toward the new path"
+        );
+        assert!(
+            store
+                .get_analysis(old, AnalysisKind::ChangeFrequency)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn pull_requests_group_commits_into_change_sets() {
+        let mut config = HomerConfig::default();
+        config.analysis.co_change.group_pull_requests = true;
+        let (store, api, client) = setup_follow_ups(chrono::Duration::days(3), true).await;
+        BehavioralAnalyzer::default()
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+
+        let edges = store
+            .get_edges_by_kind(HyperedgeKind::CoChanges)
+            .await
+            .unwrap();
+        let edge = edges
+            .iter()
+            .find(|e| {
+                let ids: HashSet<NodeId> = e.members.iter().map(|m| m.node_id).collect();
+                ids.contains(&api) && ids.contains(&client)
+            })
+            .expect("pull requests link the pair");
+        assert_eq!(edge.metadata["co_occurrences"], 3);
+        assert_eq!(edge.metadata["lag"]["1d_to_1w"], 3);
+        assert_eq!(edge.metadata["lag"]["p90_hours"], 72.0);
+    }
+
+    #[tokio::test]
+    async fn window_is_bounded_by_first_commit_of_set() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let dev = add_node(&store, NodeKind::Contributor, "dev@test.com").await;
+        // One commit a day, each to its own file.
+        for day in 0..5 {
+            let file = add_node(&store, NodeKind::File, &format!("src/day{day}.rs")).await;
+            let at = now - chrono::Duration::days(10 - day);
+            add_commit(&store, &format!("day-{day}"), dev, at, &[file]).await;
+        }
+
+        let config = CoChangeConfig {
+            window_hours: 36,
+            ..CoChangeConfig::default()
+        };
+        let data = collect_commit_data(&store).await.unwrap();
+        let sets = build_change_sets(&store, &data, &config).await.unwrap();
+        let mut sizes: Vec<usize> = sets.iter().map(|s| s.commits.len()).collect();
+        sizes.sort_unstable();
+        assert_eq!(
+            sizes,
+            [1, 2, 2],
+            "daily commits must not chain into one set"
+        );
+    }
+
+    #[tokio::test]
+    async fn co_change_settings_trigger_rerun() {
+        let (store, _, _) = setup_follow_ups(chrono::Duration::hours(2), false).await;
+        let config = HomerConfig::default();
+        BehavioralAnalyzer::new(&config)
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        assert!(
+            !BehavioralAnalyzer::new(&config)
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+
+        let mut windowed = config.clone();
+        windowed.analysis.co_change.window_hours = 24;
+        assert!(
+            BehavioralAnalyzer::new(&windowed)
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+    }
+
     #[test]
     fn grow_group_respects_max_size() {
         // Test that grow_group stops at max_group_size
-        let cfg = CoChangeConfig {
+        let cfg = CoChangeThresholds {
             max_group_size: 3,
-            ..CoChangeConfig::default()
+            ..CoChangeThresholds::default()
         };
 
         // 5 files, all pairs have confidence 1.0
@@ -1610,7 +2273,7 @@ mod tests {
                 .unwrap();
         }
 
-        let analyzer = BehavioralAnalyzer;
+        let analyzer = BehavioralAnalyzer::default();
         let config = HomerConfig::default();
         analyzer.analyze(&store, &config).await.unwrap();
 
@@ -13,10 +13,12 @@ use std::time::Instant;
 
 use chrono::Utc;
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
 use rayon::prelude::*;
 use tracing::{info, instrument};
 
 use crate::config::HomerConfig;
+use crate::extract::coverage::CoverageSummary;
 use crate::store::HomerStore;
 use crate::store::incremental;
 use crate::types::{
@@ -79,6 +81,8 @@ impl Analyzer for CentralityAnalyzer {
         &[
             AnalysisKind::ChangeFrequency,
             AnalysisKind::ContributorConcentration,
+            AnalysisKind::DeadCode,
+            AnalysisKind::RuntimeHotness,
         ]
     }
 
@@ -91,7 +95,16 @@ impl Analyzer for CentralityAnalyzer {
         let import_edge_count = store.get_edges_by_kind(HyperedgeKind::Imports).await?.len();
         let call_edge_count = store.get_edges_by_kind(HyperedgeKind::Calls).await?.len();
         let edge_count = import_edge_count + call_edge_count;
-        let state = format!("{file_count}:{edge_count}");
+        // Ingested coverage feeds test_presence; profiles feed runtime.
+        let coverage = store
+            .get_checkpoint("coverage_state")
+            .await?
+            .unwrap_or_default();
+        let profiles = store
+            .get_checkpoint("profiles_state")
+            .await?
+            .unwrap_or_default();
+        let state = format!("{file_count}:{edge_count}:{coverage}:{profiles}");
         incremental::needs_extraction(store, "analyze:centrality", &state).await
     }
 
@@ -99,7 +112,7 @@ impl Analyzer for CentralityAnalyzer {
     async fn analyze(
         &self,
         store: &dyn HomerStore,
-        _config: &HomerConfig,
+        config: &HomerConfig,
     ) -> crate::error::Result<AnalyzeStats> {
         let start = Instant::now();
         let mut stats = AnalyzeStats::default();
@@ -166,6 +179,7 @@ impl Analyzer for CentralityAnalyzer {
             &pagerank_scores,
             &betweenness_scores,
             &authority_scores,
+            config.analysis.runtime.salience_weight,
         )
         .await?;
         stats.results_stored += salience_count;
@@ -410,6 +424,81 @@ pub fn hits_power_iteration(graph: &DiGraph<NodeId, f64>, max_iter: usize) -> (V
     (hubs, authorities)
 }
 
+// ── Personalized PageRank ──────────────────────────────────────────
+
+/// Personalized `PageRank` (random walk with restart) via power iteration.
+///
+/// At each step the walker follows an out-edge with probability `damping`,
+/// choosing edges proportionally to their weight (edge confidence), and
+/// otherwise restarts at a seed drawn from `seeds` (weights need not sum to 1).
+/// Mass at dangling nodes is returned to the seeds, so scores always sum to 1.
+/// Returns an empty vector if the graph is empty or no seed has positive weight.
+pub fn personalized_pagerank(
+    graph: &DiGraph<NodeId, f64>,
+    seeds: &[(NodeIndex, f64)],
+    damping: f64,
+    max_iter: usize,
+) -> Vec<f64> {
+    let n = graph.node_count();
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut restart = vec![0.0_f64; n];
+    for &(idx, weight) in seeds {
+        if idx.index() < n && weight > 0.0 {
+            restart[idx.index()] += weight;
+        }
+    }
+    let restart_total: f64 = restart.iter().sum();
+    if restart_total <= 0.0 {
+        return vec![];
+    }
+    for r in &mut restart {
+        *r /= restart_total;
+    }
+
+    let out_weight: Vec<f64> = graph
+        .node_indices()
+        .map(|v| graph.edges(v).map(|e| e.weight().max(0.0)).sum())
+        .collect();
+
+    let mut scores = restart.clone();
+    for _ in 0..max_iter {
+        let mut next = vec![0.0_f64; n];
+        let mut dangling = 0.0_f64;
+
+        for v in graph.node_indices() {
+            let mass = scores[v.index()];
+            let total = out_weight[v.index()];
+            if total <= 0.0 {
+                dangling += mass;
+                continue;
+            }
+            for edge in graph.edges(v) {
+                next[edge.target().index()] += damping * mass * edge.weight().max(0.0) / total;
+            }
+        }
+
+        let restart_mass = (1.0 - damping) + damping * dangling;
+        for (s, r) in next.iter_mut().zip(restart.iter()) {
+            *s += restart_mass * r;
+        }
+
+        let diff: f64 = next
+            .iter()
+            .zip(scores.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        scores = next;
+        if diff < 1e-10 {
+            break;
+        }
+    }
+
+    scores
+}
+
 // ── Storage helpers ────────────────────────────────────────────────
 
 async fn store_centrality_results(
@@ -540,6 +629,20 @@ const W_BUS_FACTOR_RISK: f64 = 0.10;
 const W_CODE_SIZE: f64 = 0.05;
 const W_TEST_PRESENCE: f64 = 0.10;
 
+/// The graph-structural part of a composite salience score.
+///
+/// Historical snapshots are re-extracted without git history, so only these
+/// components are comparable between a snapshot and the live graph.
+pub(crate) fn structural_salience(data: &serde_json::Value) -> Option<f64> {
+    let components = data.get("components")?;
+    let component = |key: &str| components.get(key).and_then(serde_json::Value::as_f64);
+    Some(
+        component("pagerank")? * W_PAGERANK
+            + component("betweenness")? * W_BETWEENNESS
+            + component("authority")? * W_AUTHORITY,
+    )
+}
+
 async fn compute_and_store_salience(
     store: &dyn HomerStore,
     call_graph: &InMemoryGraph,
@@ -547,6 +650,7 @@ async fn compute_and_store_salience(
     pagerank_scores: &[f64],
     betweenness_scores: &[f64],
     authority_scores: &[f64],
+    runtime_weight: f64,
 ) -> crate::error::Result<u64> {
     // Collect all unique node IDs across both graphs
     let mut all_nodes: HashMap<NodeId, SalienceInputs> = HashMap::new();
@@ -570,6 +674,15 @@ async fn compute_and_store_salience(
         }
     }
 
+    // Unreachable code is not load-bearing, however central it looks.
+    for result in store.get_analyses_by_kind(AnalysisKind::DeadCode).await? {
+        if all_nodes.remove(&result.node_id).is_some() {
+            store
+                .invalidate_analyses_by_kinds(result.node_id, &[AnalysisKind::CompositeSalience])
+                .await?;
+        }
+    }
+
     // Enrich with code_size and test_presence from file nodes
     enrich_code_size_and_test_presence(store, &mut all_nodes).await?;
 
@@ -608,6 +721,10 @@ async fn compute_and_store_salience(
         }
     }
 
+    // Profiled hotness is always recorded, but only scores when weighted.
+    enrich_runtime(store, &mut all_nodes).await?;
+    let runtime_weight = runtime_weight.clamp(0.0, 1.0);
+
     // Store salience results
     let now = Utc::now();
     let mut count = 0u64;
@@ -620,6 +737,7 @@ async fn compute_and_store_salience(
             + inputs.bus_factor_risk * W_BUS_FACTOR_RISK
             + inputs.code_size * W_CODE_SIZE
             + inputs.test_presence * W_TEST_PRESENCE;
+        let salience = salience * (1.0 - runtime_weight) + inputs.runtime * runtime_weight;
 
         let classification = classify_salience(
             inputs.pagerank,
@@ -638,6 +756,7 @@ async fn compute_and_store_salience(
                 "bus_factor_risk": inputs.bus_factor_risk,
                 "code_size": inputs.code_size,
                 "test_presence": inputs.test_presence,
+                "runtime": inputs.runtime,
             }
         });
 
@@ -656,7 +775,31 @@ async fn compute_and_store_salience(
     Ok(count)
 }
 
+/// Enrich salience inputs with the runtime hotness score from profiles.
+async fn enrich_runtime(
+    store: &dyn HomerStore,
+    all_nodes: &mut HashMap<NodeId, SalienceInputs>,
+) -> crate::error::Result<()> {
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::RuntimeHotness)
+        .await?
+    {
+        if let Some(inputs) = all_nodes.get_mut(&result.node_id) {
+            inputs.runtime = result
+                .data
+                .get("score")
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(0.0);
+        }
+    }
+    Ok(())
+}
+
 /// Enrich salience inputs with `code_size` (normalized file size) and `test_presence`.
+///
+/// `test_presence` is the measured line coverage of the node (or its file)
+/// when a coverage report was ingested, and otherwise 1.0 when a test file
+/// matching the source file exists.
 async fn enrich_code_size_and_test_presence(
     store: &dyn HomerStore,
     all_nodes: &mut HashMap<NodeId, SalienceInputs>,
@@ -670,6 +813,7 @@ async fn enrich_code_size_and_test_presence(
 
     // Build: file_name → size_bytes, and set of test file paths
     let mut file_sizes: HashMap<String, u64> = HashMap::new();
+    let mut file_coverage: HashMap<String, f64> = HashMap::new();
     let mut test_files: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut max_size = 1u64; // avoid division by zero
 
@@ -681,6 +825,9 @@ async fn enrich_code_size_and_test_presence(
             .unwrap_or(0);
         file_sizes.insert(file.name.clone(), size);
         max_size = max_size.max(size);
+        if let Some(coverage) = CoverageSummary::of(file) {
+            file_coverage.insert(file.name.clone(), coverage.line_rate());
+        }
 
         if is_test_file(&file.name) {
             test_files.insert(file.name.clone());
@@ -722,8 +869,13 @@ async fn enrich_code_size_and_test_presence(
                 inputs.code_size = size as f64 / max_size as f64;
             }
 
-            // test_presence: 1.0 if covered by tests, 0.0 otherwise
-            if tested_source_files.contains(file_path) {
+            // test_presence: measured coverage when known, else 1.0 if a
+            // matching test file exists
+            if let Some(coverage) = CoverageSummary::of(&node) {
+                inputs.test_presence = coverage.line_rate();
+            } else if let Some(&rate) = file_coverage.get(file_path) {
+                inputs.test_presence = rate;
+            } else if tested_source_files.contains(file_path) {
                 inputs.test_presence = 1.0;
             }
         }
@@ -733,7 +885,7 @@ async fn enrich_code_size_and_test_presence(
 }
 
 /// Check if a file path matches common test file naming patterns.
-fn is_test_file(path: &str) -> bool {
+pub(crate) fn is_test_file(path: &str) -> bool {
     let name = path.rsplit('/').next().unwrap_or(path);
     let name_lower = name.to_lowercase();
 
@@ -762,7 +914,7 @@ fn is_test_file(path: &str) -> bool {
 }
 
 /// Infer the source file that a test file covers.
-fn infer_source_from_test(test_path: &str) -> Option<String> {
+pub(crate) fn infer_source_from_test(test_path: &str) -> Option<String> {
     let name = test_path.rsplit('/').next().unwrap_or(test_path);
     let dir = test_path.rsplit_once('/').map_or("", |(dir, _)| dir);
 
@@ -832,6 +984,7 @@ struct SalienceInputs {
     bus_factor_risk: f64,
     code_size: f64,
     test_presence: f64,
+    runtime: f64,
 }
 
 /// Classify a node into one of 5 salience classes per spec.
@@ -1203,6 +1356,41 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn salience_excludes_dead_code() {
+        let store = SqliteStore::in_memory().unwrap();
+        setup_call_graph(&store).await;
+        let add = store
+            .get_node_by_name(NodeKind::Function, "add")
+            .await
+            .unwrap()
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: add.id,
+                kind: AnalysisKind::DeadCode,
+                data: serde_json::json!({ "confidence": 0.9 }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let analyzer = CentralityAnalyzer::default();
+        analyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let salience = store
+            .get_analyses_by_kind(AnalysisKind::CompositeSalience)
+            .await
+            .unwrap();
+        assert_eq!(salience.len(), 4, "dead `add` should have no salience");
+        assert!(salience.iter().all(|r| r.node_id != add.id));
+    }
+
     #[tokio::test]
     async fn empty_graph_no_panic() {
         let store = SqliteStore::in_memory().unwrap();
@@ -1267,6 +1455,49 @@ mod tests {
         assert!(diff < 0.01, "B and C should have equal authority scores");
     }
 
+    #[test]
+    fn personalized_pagerank_favors_seed_neighborhood() {
+        // Two chains joined at the far end: A → B → C ← D ← E
+        let mut graph = DiGraph::<NodeId, f64>::new();
+        let na = graph.add_node(NodeId(1));
+        let nb = graph.add_node(NodeId(2));
+        let nc = graph.add_node(NodeId(3));
+        let nd = graph.add_node(NodeId(4));
+        let ne = graph.add_node(NodeId(5));
+        graph.add_edge(na, nb, 1.0);
+        graph.add_edge(nb, nc, 1.0);
+        graph.add_edge(nd, nc, 1.0);
+        graph.add_edge(ne, nd, 1.0);
+
+        let scores = personalized_pagerank(&graph, &[(na, 1.0)], 0.85, 100);
+
+        let total: f64 = scores.iter().sum();
+        assert!(
+            (total - 1.0).abs() < 1e-6,
+            "Scores should sum to 1: {total}"
+        );
+        assert!(
+            scores[na.index()] > scores[nc.index()],
+            "Seed ranks highest"
+        );
+        assert!(
+            scores[nb.index()] > scores[nd.index()],
+            "B is reachable, D is not"
+        );
+        assert!(scores[ne.index()].abs() < f64::EPSILON, "E is unreachable");
+    }
+
+    #[test]
+    fn personalized_pagerank_without_seeds_is_empty() {
+        let mut graph = DiGraph::<NodeId, f64>::new();
+        let a = graph.add_node(NodeId(1));
+        let b = graph.add_node(NodeId(2));
+        graph.add_edge(a, b, 1.0);
+
+        assert!(personalized_pagerank(&graph, &[], 0.85, 100).is_empty());
+        assert!(personalized_pagerank(&graph, &[(a, 0.0)], 0.85, 100).is_empty());
+    }
+
     #[test]
     fn salience_classification_5_classes() {
         // HotCritical: high centrality + high churn + not single-owner
@@ -1451,6 +1682,148 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn measured_coverage_overrides_test_file_heuristic() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let node = |kind, name: &str, metadata: serde_json::Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: now,
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        let coverage = |covered: u64, total: u64| {
+            serde_json::json!({ "coverage": {
+                "lines_covered": covered, "lines_total": total,
+                "branches_covered": 0, "branches_total": 0,
+            }})
+        };
+
+        store
+            .upsert_node(&node(NodeKind::File, "src/auth.rs", coverage(5, 10)))
+            .await
+            .unwrap();
+        store
+            .upsert_node(&node(
+                NodeKind::File,
+                "src/auth_test.rs",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let login = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "src/auth.rs::login",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let check = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "src/auth.rs::check",
+                coverage(1, 4),
+            ))
+            .await
+            .unwrap();
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Calls,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: login,
+                        role: "caller".to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: check,
+                        role: "callee".to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence: 0.9,
+                last_updated: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
+        CentralityAnalyzer::default()
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let test_presence = |id| {
+            let store = &store;
+            async move {
+                store
+                    .get_analysis(id, AnalysisKind::CompositeSalience)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data["components"]["test_presence"]
+                    .as_f64()
+                    .unwrap()
+            }
+        };
+        assert!((test_presence(check).await - 0.25).abs() < 1e-9);
+        assert!(
+            (test_presence(login).await - 0.5).abs() < 1e-9,
+            "falls back to file coverage"
+        );
+    }
+
+    #[tokio::test]
+    async fn runtime_hotness_scores_only_when_weighted() {
+        let store = SqliteStore::in_memory().unwrap();
+        setup_call_graph(&store).await;
+        let hot = store
+            .get_node_by_name(NodeKind::Function, "main")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: hot,
+                kind: AnalysisKind::RuntimeHotness,
+                data: serde_json::json!({ "score": 1.0 }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let salience = |config: HomerConfig| {
+            let store = &store;
+            async move {
+                CentralityAnalyzer::default()
+                    .analyze(store, &config)
+                    .await
+                    .unwrap();
+                store
+                    .get_analysis(hot, AnalysisKind::CompositeSalience)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data
+            }
+        };
+        let structural = salience(HomerConfig::default()).await;
+        assert_eq!(structural["components"]["runtime"], 1.0);
+
+        let mut config = HomerConfig::default();
+        config.analysis.runtime.salience_weight = 0.5;
+        let blended = salience(config).await;
+        let expected = structural["score"].as_f64().unwrap() * 0.5 + 0.5;
+        assert!((blended["score"].as_f64().unwrap() - expected).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn change_frequency_percentile_normalized_to_unit_range() {
         let store = SqliteStore::in_memory().unwrap();
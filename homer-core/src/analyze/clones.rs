@@ -0,0 +1,797 @@
+// Clone analysis: duplicated and near-duplicated function bodies, grouped by
+// fingerprint similarity, with the history of changes that made them diverge.
+
+// Similarity ratios intentionally cast counts to float.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use homer_graphs::clones::CloneFingerprint;
+use tracing::{info, instrument};
+
+use crate::config::HomerConfig;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, DiffHunk, HyperedgeKind, Node, NodeFilter,
+    NodeId, NodeKind,
+};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Fingerprint hashes shared by more functions than this are boilerplate
+/// and are not used to find candidate pairs.
+const MAX_POSTINGS: usize = 50;
+
+/// Divergent changes listed per clone group, newest first.
+const MAX_DIVERGENT_CHANGES: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct CloneAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for CloneAnalyzer {
+    fn name(&self) -> &'static str {
+        "clones"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::CloneGroup]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let functions = load_functions(store).await?;
+        let state = clone_state(store, &functions).await?;
+        incremental::needs_extraction(store, "analyze:clones", &state).await
+    }
+
+    #[instrument(skip_all, name = "clones_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+        let thresholds = &config.analysis.clones;
+
+        // Groups form and dissolve as code changes; recompute all.
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::CloneGroup])
+            .await?;
+
+        let all = load_functions(store).await?;
+        let functions: Vec<&Function> = all
+            .iter()
+            .filter(|f| f.fingerprint.tokens >= thresholds.min_tokens)
+            .collect();
+        let pairs = find_clone_pairs(&functions, thresholds.min_similarity);
+        let groups = group_pairs(functions.len(), &pairs);
+
+        if !groups.is_empty() {
+            let changes = load_changes(store).await?;
+            let mut commit_names: HashMap<NodeId, String> = HashMap::new();
+            for group in &groups {
+                let members: Vec<&Function> = group.iter().map(|&i| functions[i]).collect();
+                let histories: Vec<MemberHistory> = members
+                    .iter()
+                    .map(|f| member_history(&changes, &f.file, f.lines))
+                    .collect();
+                let divergent = divergent_changes(&histories);
+                for (commit, _, _) in &divergent {
+                    if !commit_names.contains_key(commit) {
+                        let name = store
+                            .get_node(*commit)
+                            .await?
+                            .map_or_else(|| format!("node:{}", commit.0), |n| n.name);
+                        commit_names.insert(*commit, name);
+                    }
+                }
+                stats.results_stored +=
+                    store_group(store, &members, &divergent, &commit_names, now).await?;
+            }
+        }
+
+        let state = clone_state(store, &all).await?;
+        store.set_checkpoint("analyze:clones", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            functions = functions.len(),
+            groups = groups.len(),
+            duration = ?stats.duration,
+            "Clone analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// A function with a clone fingerprint and its current location.
+struct Function {
+    node: Node,
+    file: String,
+    /// 1-based first and last line.
+    lines: (u32, u32),
+    fingerprint: CloneFingerprint,
+}
+
+/// Functions that carry a clone fingerprint from extraction.
+async fn load_functions(store: &dyn HomerStore) -> crate::error::Result<Vec<Function>> {
+    let nodes = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Function),
+            ..Default::default()
+        })
+        .await?;
+    let mut functions: Vec<Function> = nodes
+        .into_iter()
+        .filter_map(|node| {
+            let fingerprint: CloneFingerprint =
+                serde_json::from_value(node.metadata.get("clone_fingerprint")?.clone()).ok()?;
+            if fingerprint.hashes.is_empty() {
+                return None;
+            }
+            let file = node.metadata.get("file")?.as_str()?.to_string();
+            let span = node.metadata.get("span")?;
+            let row = |key: &str| {
+                span.get(key)
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|r| u32::try_from(r + 1).ok())
+            };
+            let lines = (row("start_row")?, row("end_row")?);
+            Some(Function {
+                node,
+                file,
+                lines,
+                fingerprint,
+            })
+        })
+        .collect();
+    functions.sort_by(|a, b| a.node.name.cmp(&b.node.name));
+    Ok(functions)
+}
+
+/// Rerun when fingerprints change or new commits arrive.
+async fn clone_state(
+    store: &dyn HomerStore,
+    functions: &[Function],
+) -> crate::error::Result<String> {
+    let fingerprint_sum = functions.iter().fold(0u64, |acc, f| {
+        f.fingerprint
+            .hashes
+            .iter()
+            .fold(acc, |a, &h| a.wrapping_add(u64::from(h)))
+    });
+    let commits = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Commit),
+            ..Default::default()
+        })
+        .await?
+        .len();
+    Ok(format!("{}:{fingerprint_sum}:{commits}", functions.len()))
+}
+
+// ── Grouping ──────────────────────────────────────────────────────
+
+/// Pairs of functions whose fingerprint similarity reaches `min_similarity`.
+/// Fingerprints are winnowed hashes of each function's normalized token
+/// stream, with identifiers and literals abstracted, stored by the graph
+/// extractor.
+fn find_clone_pairs(functions: &[&Function], min_similarity: f64) -> Vec<(usize, usize, f64)> {
+    let mut postings: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, f) in functions.iter().enumerate() {
+        for &h in &f.fingerprint.hashes {
+            postings.entry(h).or_default().push(i);
+        }
+    }
+
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    for ids in postings.values() {
+        if ids.len() < 2 || ids.len() > MAX_POSTINGS {
+            continue;
+        }
+        for (k, &a) in ids.iter().enumerate() {
+            for &b in &ids[k + 1..] {
+                *shared.entry((a, b)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize, f64)> = shared
+        .into_iter()
+        .filter(|&((a, b), _)| !nested(functions[a], functions[b]))
+        .filter_map(|((a, b), count)| {
+            let union = functions[a].fingerprint.hashes.len()
+                + functions[b].fingerprint.hashes.len()
+                - count;
+            let similarity = count as f64 / union as f64;
+            (similarity >= min_similarity).then_some((a, b, similarity))
+        })
+        .collect();
+    pairs.sort_unstable_by_key(|&(a, b, _)| (a, b));
+    pairs
+}
+
+/// A function and a closure or inner function defined inside it.
+fn nested(a: &Function, b: &Function) -> bool {
+    a.file == b.file && a.lines.0 <= b.lines.1 && b.lines.0 <= a.lines.1
+}
+
+/// Connected components of the clone pairs, each sorted, largest first.
+fn group_pairs(count: usize, pairs: &[(usize, usize, f64)]) -> Vec<Vec<usize>> {
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut parent: Vec<usize> = (0..count).collect();
+    for &(a, b, _) in pairs {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        if ra != rb {
+            parent[ra.max(rb)] = ra.min(rb);
+        }
+    }
+
+    let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(a, b, _) in pairs {
+        for i in [a, b] {
+            let root = find(&mut parent, i);
+            components.entry(root).or_default().push(i);
+        }
+    }
+    let mut groups: Vec<Vec<usize>> = components
+        .into_values()
+        .map(|mut g| {
+            g.sort_unstable();
+            g.dedup();
+            g
+        })
+        .collect();
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    groups
+}
+
+// ── History ───────────────────────────────────────────────────────
+
+/// One file diff from a non-merge commit.
+struct FileChange {
+    at: DateTime<Utc>,
+    commit: NodeId,
+    path: String,
+    old_path: Option<String>,
+    hunks: Vec<DiffHunk>,
+}
+
+/// File diffs with hunks, newest first.
+async fn load_changes(store: &dyn HomerStore) -> crate::error::Result<Vec<FileChange>> {
+    let mut changes = Vec::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Modifies).await? {
+        if edge
+            .metadata
+            .get("merge")
+            .and_then(serde_json::Value::as_bool)
+            == Some(true)
+        {
+            continue;
+        }
+        let Some(commit) = edge.members.iter().find(|m| m.role == "commit") else {
+            continue;
+        };
+        let Some(files) = edge.metadata.get("files").and_then(|v| v.as_array()) else {
+            continue;
+        };
+        for file in files {
+            let Some(path) = file.get("path").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            // Edges extracted before hunks were recorded cannot be traced.
+            let Some(hunks) = file
+                .get("hunks")
+                .and_then(|h| serde_json::from_value::<Vec<DiffHunk>>(h.clone()).ok())
+            else {
+                continue;
+            };
+            changes.push(FileChange {
+                at: edge.last_updated,
+                commit: commit.node_id,
+                path: path.to_string(),
+                old_path: file
+                    .get("old_path")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                hunks,
+            });
+        }
+    }
+    changes.sort_by(|a, b| b.at.cmp(&a.at).then_with(|| b.commit.0.cmp(&a.commit.0)));
+    Ok(changes)
+}
+
+/// Commits that touched a function, and when it was introduced.
+#[derive(Debug, Default)]
+struct MemberHistory {
+    /// Touching commits, newest first (excluding the introducing commit).
+    touches: Vec<(NodeId, DateTime<Utc>)>,
+    /// When the function first appeared; `None` if it predates the history.
+    introduced: Option<DateTime<Utc>>,
+}
+
+/// Trace a function's line range back through the file's history.
+fn member_history(changes: &[FileChange], file: &str, lines: (u32, u32)) -> MemberHistory {
+    let mut history = MemberHistory::default();
+    let mut path = file.to_string();
+    let mut range = lines;
+    for change in changes {
+        if change.path != path {
+            continue;
+        }
+        match trace_back(&change.hunks, range) {
+            Trace::Introduced => {
+                history.introduced = Some(change.at);
+                break;
+            }
+            Trace::Touched(older) => {
+                history.touches.push((change.commit, change.at));
+                range = older;
+            }
+            Trace::Untouched(older) => range = older,
+        }
+        // A rename moves the trace to the old path for older commits.
+        if let Some(old) = &change.old_path {
+            path.clone_from(old);
+        }
+    }
+    history
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Trace {
+    /// The range did not exist before this change.
+    Introduced,
+    /// The change edited the range; its lines in the old file.
+    Touched((u32, u32)),
+    /// The change left the range alone; its lines in the old file.
+    Untouched((u32, u32)),
+}
+
+/// Map a 1-based inclusive line range in a change's new file to the old file.
+///
+/// Hunks are zero-context: new lines `new_start..new_start + new_lines`
+/// replaced old lines `old_start..old_start + old_lines`. A pure deletion
+/// (`new_lines == 0`) sits just before new line `new_start`.
+fn trace_back(hunks: &[DiffHunk], (start, end): (u32, u32)) -> Trace {
+    let mut touched = false;
+    let mut old_start = i64::from(start);
+    let mut old_end = i64::from(end);
+    let mut start_mapped = false;
+    let mut end_mapped = false;
+    let mut shift_start = 0i64;
+    let mut shift_end = 0i64;
+
+    for h in hunks {
+        let (ns, nl) = (h.new_start, h.new_lines);
+        let delta = i64::from(h.old_lines) - i64::from(nl);
+        if nl == 0 {
+            if start < ns && ns <= end {
+                touched = true;
+            }
+        } else if ns <= end && ns + nl > start {
+            touched = true;
+        }
+
+        if ns <= start && start < ns + nl {
+            old_start = i64::from(h.old_start);
+            start_mapped = true;
+        } else if ns + nl <= start {
+            shift_start += delta;
+        }
+        if ns <= end && end < ns + nl {
+            old_end = i64::from(h.old_start) + i64::from(h.old_lines) - 1;
+            end_mapped = true;
+        } else if ns + nl <= end {
+            shift_end += delta;
+        }
+    }
+
+    if !start_mapped {
+        old_start += shift_start;
+    }
+    if !end_mapped {
+        old_end += shift_end;
+    }
+    if old_end < old_start || old_end < 1 {
+        return Trace::Introduced;
+    }
+    let older = (
+        u32::try_from(old_start.max(1)).unwrap_or(1),
+        u32::try_from(old_end).unwrap_or(u32::MAX),
+    );
+    if touched {
+        Trace::Touched(older)
+    } else {
+        Trace::Untouched(older)
+    }
+}
+
+/// Commits after every member existed that touched some members but not
+/// all — the classic inconsistent bug fix: `(commit, time, indices of the
+/// members it changed)`, newest first.
+fn divergent_changes(histories: &[MemberHistory]) -> Vec<(NodeId, DateTime<Utc>, Vec<usize>)> {
+    let formed = histories.iter().filter_map(|h| h.introduced).max();
+    let mut by_commit: HashMap<NodeId, (DateTime<Utc>, BTreeSet<usize>)> = HashMap::new();
+    for (i, h) in histories.iter().enumerate() {
+        for &(commit, at) in &h.touches {
+            if formed.is_some_and(|f| at <= f) {
+                continue;
+            }
+            by_commit
+                .entry(commit)
+                .or_insert_with(|| (at, BTreeSet::new()))
+                .1
+                .insert(i);
+        }
+    }
+    let mut divergent: Vec<(NodeId, DateTime<Utc>, Vec<usize>)> = by_commit
+        .into_iter()
+        .filter(|(_, (_, changed))| changed.len() < histories.len())
+        .map(|(commit, (at, changed))| (commit, at, changed.into_iter().collect()))
+        .collect();
+    divergent.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.0.cmp(&a.0.0)));
+    divergent
+}
+
+// ── Results ───────────────────────────────────────────────────────
+
+fn round3(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+/// Store a clone group. Groups whose members are no longer identical and
+/// that have divergent changes are flagged as diverged.
+async fn store_group(
+    store: &dyn HomerStore,
+    members: &[&Function],
+    divergent: &[(NodeId, DateTime<Utc>, Vec<usize>)],
+    commit_names: &HashMap<NodeId, String>,
+    now: DateTime<Utc>,
+) -> crate::error::Result<u64> {
+    let n = members.len();
+    let mut similarity = vec![vec![1.0; n]; n];
+    let mut total = 0.0;
+    let mut identical = true;
+    for a in 0..n {
+        for b in a + 1..n {
+            let s = members[a].fingerprint.similarity(&members[b].fingerprint);
+            similarity[a][b] = s;
+            similarity[b][a] = s;
+            total += s;
+            identical &= s >= 1.0;
+        }
+    }
+    let pairs = n * (n - 1) / 2;
+    let mean = total / pairs as f64;
+    let diverged = !identical && !divergent.is_empty();
+
+    let changes: Vec<serde_json::Value> = divergent
+        .iter()
+        .take(MAX_DIVERGENT_CHANGES)
+        .map(|(commit, at, changed)| {
+            let (edited, untouched): (Vec<_>, Vec<_>) = (0..n).partition(|i| changed.contains(i));
+            let names = |ids: Vec<usize>| -> Vec<&str> {
+                ids.into_iter()
+                    .map(|i| members[i].node.name.as_str())
+                    .collect()
+            };
+            serde_json::json!({
+                "commit": commit_names.get(commit),
+                "at": at.to_rfc3339(),
+                "changed": names(edited),
+                "unchanged": names(untouched),
+            })
+        })
+        .collect();
+
+    for (i, member) in members.iter().enumerate() {
+        let others: Vec<serde_json::Value> = members
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(j, m)| {
+                serde_json::json!({
+                    "name": m.node.name,
+                    "file": m.file,
+                    "similarity": round3(similarity[i][j]),
+                })
+            })
+            .collect();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: member.node.id,
+                kind: AnalysisKind::CloneGroup,
+                data: serde_json::json!({
+                    "group": members[0].node.name,
+                    "size": n,
+                    "similarity": round3(mean),
+                    "tokens": member.fingerprint.tokens,
+                    "members": others,
+                    "diverged": diverged,
+                    "divergent_changes": changes,
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await?;
+    }
+    Ok(n as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+    use homer_graphs::clones::fingerprint;
+
+    fn hunk(old_start: u32, old_lines: u32, new_start: u32, new_lines: u32) -> DiffHunk {
+        DiffHunk {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+        }
+    }
+
+    #[test]
+    fn trace_back_maps_ranges_through_hunks() {
+        // Two lines inserted above the function shift it down.
+        assert_eq!(
+            trace_back(&[hunk(3, 0, 3, 2)], (10, 20)),
+            Trace::Untouched((8, 18))
+        );
+        // A line replaced inside the function touches it.
+        assert_eq!(
+            trace_back(&[hunk(15, 1, 15, 1)], (10, 20)),
+            Trace::Touched((10, 20))
+        );
+        // Lines deleted from inside the function.
+        assert_eq!(
+            trace_back(&[hunk(12, 3, 12, 0)], (10, 20)),
+            Trace::Touched((10, 23))
+        );
+        // Lines deleted below the function leave it alone.
+        assert_eq!(
+            trace_back(&[hunk(30, 3, 30, 0)], (10, 20)),
+            Trace::Untouched((10, 20))
+        );
+        // The whole function was added.
+        assert_eq!(
+            trace_back(&[hunk(9, 0, 9, 14)], (10, 20)),
+            Trace::Introduced
+        );
+        assert_eq!(
+            trace_back(&[hunk(0, 0, 1, 40)], (10, 20)),
+            Trace::Introduced
+        );
+    }
+
+    const BODY: &str = "    let mut total = 0;
+    for item in items {
+        if item.active && item.visible {
+            total += item.price * item.quantity;
+        } else if item.pending {
+            total += item.deposit;
+        }
+    }
+    total";
+
+    async fn add_function(store: &SqliteStore, file: &str, name: &str, source: &str, start: u32) {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(
+                &homer_graphs::LanguageRegistry::new()
+                    .get("rust")
+                    .unwrap()
+                    .tree_sitter_language(),
+            )
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let fp = fingerprint(tree.root_node().child(0).unwrap(), source);
+        let lines = u32::try_from(source.lines().count()).unwrap();
+        let mut metadata = HashMap::new();
+        metadata.insert("file".to_string(), serde_json::json!(file));
+        metadata.insert(
+            "span".to_string(),
+            serde_json::json!({
+                "start_row": start - 1,
+                "start_col": 0,
+                "end_row": start + lines - 2,
+                "end_col": 1,
+            }),
+        );
+        metadata.insert("clone_fingerprint".to_string(), serde_json::json!(fp));
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: format!("{file}::{name}"),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn add_commit(
+        store: &SqliteStore,
+        sha: &str,
+        days_ago: i64,
+        files: &[(&str, Vec<DiffHunk>)],
+    ) {
+        let at = Utc::now() - chrono::Duration::days(days_ago);
+        let commit = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Commit,
+                name: sha.to_string(),
+                content_hash: None,
+                last_extracted: at,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let mut members = vec![HyperedgeMember {
+            node_id: commit,
+            role: "commit".to_string(),
+            position: 0,
+        }];
+        let mut meta = Vec::new();
+        for (pos, (path, hunks)) in files.iter().enumerate() {
+            let file = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: (*path).to_string(),
+                    content_hash: None,
+                    last_extracted: at,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            members.push(HyperedgeMember {
+                node_id: file,
+                role: "file".to_string(),
+                position: u32::try_from(pos + 1).unwrap(),
+            });
+            meta.push(serde_json::json!({ "path": path, "hunks": hunks }));
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Modifies,
+                members,
+                confidence: 1.0,
+                last_updated: at,
+                metadata: [("files".to_string(), serde_json::json!(meta))]
+                    .into_iter()
+                    .collect(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn groups_clones_and_flags_divergent_fixes() {
+        let store = SqliteStore::in_memory().unwrap();
+        let original = format!("fn order_total(items: &[Item]) -> u64 {{\n{BODY}\n}}");
+        let copy = original
+            .replace("order_total", "cart_total")
+            .replace("item", "line");
+        let fixed = original.replace("item.deposit", "item.deposit.min(item.price)");
+        add_function(&store, "src/orders.rs", "order_total", &fixed, 1).await;
+        add_function(&store, "src/cart.rs", "cart_total", &copy, 1).await;
+        add_function(
+            &store,
+            "src/render.rs",
+            "render",
+            "fn render(out: &mut String) {\n    out.push_str(\"<p>\");\n}",
+            1,
+        )
+        .await;
+
+        // Both copies written, then only the original fixed.
+        add_commit(
+            &store,
+            "aaa",
+            30,
+            &[("src/orders.rs", vec![hunk(0, 0, 1, 11)])],
+        )
+        .await;
+        add_commit(
+            &store,
+            "bbb",
+            20,
+            &[("src/cart.rs", vec![hunk(0, 0, 1, 11)])],
+        )
+        .await;
+        add_commit(
+            &store,
+            "ccc",
+            5,
+            &[("src/orders.rs", vec![hunk(7, 1, 7, 1)])],
+        )
+        .await;
+
+        let mut config = HomerConfig::default();
+        config.analysis.clones.min_tokens = 20;
+        config.analysis.clones.min_similarity = 0.6;
+        CloneAnalyzer.analyze(&store, &config).await.unwrap();
+
+        let results = store
+            .get_analyses_by_kind(AnalysisKind::CloneGroup)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2, "the unrelated function is not a clone");
+        let data = &results[0].data;
+        assert_eq!(data["size"], 2);
+        assert_eq!(data["group"], "src/cart.rs::cart_total");
+        assert!(data["similarity"].as_f64().unwrap() < 1.0);
+        assert_eq!(data["diverged"], true);
+        let change = &data["divergent_changes"][0];
+        assert_eq!(change["commit"], "ccc");
+        assert_eq!(change["changed"][0], "src/orders.rs::order_total");
+        assert_eq!(change["unchanged"][0], "src/cart.rs::cart_total");
+
+        // Applying the same fix to both in one commit is consistent.
+        let store = SqliteStore::in_memory().unwrap();
+        add_function(&store, "src/orders.rs", "order_total", &original, 1).await;
+        add_function(&store, "src/cart.rs", "cart_total", &copy, 1).await;
+        add_commit(
+            &store,
+            "aaa",
+            30,
+            &[("src/orders.rs", vec![hunk(0, 0, 1, 11)])],
+        )
+        .await;
+        add_commit(
+            &store,
+            "bbb",
+            20,
+            &[("src/cart.rs", vec![hunk(0, 0, 1, 11)])],
+        )
+        .await;
+        add_commit(
+            &store,
+            "ccc",
+            5,
+            &[
+                ("src/orders.rs", vec![hunk(7, 1, 7, 1)]),
+                ("src/cart.rs", vec![hunk(7, 1, 7, 1)]),
+            ],
+        )
+        .await;
+        CloneAnalyzer.analyze(&store, &config).await.unwrap();
+        let results = store
+            .get_analyses_by_kind(AnalysisKind::CloneGroup)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].data["similarity"], 1.0);
+        assert_eq!(results[0].data["diverged"], false);
+        assert!(
+            results[0].data["divergent_changes"]
+                .as_array()
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
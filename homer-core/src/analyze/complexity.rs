@@ -0,0 +1,422 @@
+// Complexity analysis: per-function metric trends and complexity × churn hotspots.
+//
+// Metrics (cyclomatic complexity, nesting depth, parameter count, length) are
+// computed by homer-graphs during extraction and stored on `Function` nodes.
+// This analyzer keeps one history point per graph snapshot so trends follow
+// the snapshot timeline rather than the number of pipeline runs.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use chrono::Utc;
+use homer_graphs::FunctionMetrics;
+use tracing::{info, instrument};
+
+use crate::config::HomerConfig;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, Node, NodeFilter, NodeId, NodeKind,
+};
+
+use super::AnalyzeStats;
+use super::temporal::{classify_trend, simple_slope};
+use super::traits::Analyzer;
+
+/// Number of snapshot points kept per function.
+const MAX_HISTORY: usize = 10;
+/// Cyclomatic complexity gained or lost per snapshot before a trend counts.
+const TREND_THRESHOLD: f64 = 0.5;
+
+/// History label used before the first snapshot exists.
+const WORKING_TREE: &str = "working-tree";
+
+#[derive(Debug, Default)]
+pub struct ComplexityAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for ComplexityAnalyzer {
+    fn name(&self) -> &'static str {
+        "complexity"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[
+            AnalysisKind::ComplexityTrend,
+            AnalysisKind::ComplexityHotspot,
+        ]
+    }
+
+    fn requires(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::ChangeFrequency]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let functions = load_functions(store).await?;
+        let state = complexity_state(store, &functions).await?;
+        incremental::needs_extraction(store, "analyze:complexity", &state).await
+    }
+
+    #[instrument(skip_all, name = "complexity_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+
+        // Hotspots depend on repo-wide change percentiles, so recompute all.
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::ComplexityHotspot])
+            .await?;
+
+        let functions = load_functions(store).await?;
+        if functions.is_empty() {
+            info!("No function metrics found, skipping complexity analysis");
+            stats.duration = start.elapsed();
+            return Ok(stats);
+        }
+
+        let label = current_snapshot_label(store).await?;
+        let history = load_history(store).await?;
+        let mut trends: HashMap<NodeId, &'static str> = HashMap::new();
+
+        for (func, metrics) in &functions {
+            let mut points = history.get(&func.id).cloned().unwrap_or_default();
+            match points.last_mut() {
+                Some(last) if last.0 == label => last.1 = metrics.cyclomatic,
+                _ => points.push((label.clone(), metrics.cyclomatic)),
+            }
+            if points.len() > MAX_HISTORY {
+                points.drain(..points.len() - MAX_HISTORY);
+            }
+
+            let series: Vec<(f64, f64)> = points
+                .iter()
+                .enumerate()
+                .map(|(i, (_, c))| (i as f64, *c as f64))
+                .collect();
+            let slope = simple_slope(&series);
+            let trend = classify_trend(slope, TREND_THRESHOLD);
+            trends.insert(func.id, trend);
+
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: func.id,
+                    kind: AnalysisKind::ComplexityTrend,
+                    data: serde_json::json!({
+                        "cyclomatic": metrics.cyclomatic,
+                        "max_nesting": metrics.max_nesting,
+                        "parameters": metrics.parameters,
+                        "lines": metrics.lines,
+                        "trend": trend,
+                        "slope": (slope * 1000.0).round() / 1000.0,
+                        "history": points
+                            .iter()
+                            .map(|(s, c)| serde_json::json!({ "snapshot": s, "cyclomatic": c }))
+                            .collect::<Vec<_>>(),
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        stats.results_stored += store_hotspots(store, config, &functions, &trends, now).await?;
+
+        let state = complexity_state(store, &functions).await?;
+        store.set_checkpoint("analyze:complexity", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            functions = functions.len(),
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "Complexity analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Flag complex functions whose file is among the most frequently changed.
+async fn store_hotspots(
+    store: &dyn HomerStore,
+    config: &HomerConfig,
+    functions: &[(Node, FunctionMetrics)],
+    trends: &HashMap<NodeId, &'static str>,
+    now: chrono::DateTime<Utc>,
+) -> crate::error::Result<u64> {
+    let thresholds = &config.analysis.complexity;
+
+    // File path → (total changes, change percentile)
+    let mut churn: HashMap<String, (u64, f64)> = HashMap::new();
+    for r in store
+        .get_analyses_by_kind(AnalysisKind::ChangeFrequency)
+        .await?
+    {
+        let Some(file) = store.get_node(r.node_id).await? else {
+            continue;
+        };
+        let total = r
+            .data
+            .get("total")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        let percentile = r
+            .data
+            .get("percentile")
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.0);
+        churn.insert(file.name, (total, percentile));
+    }
+
+    let max_cyclomatic = functions
+        .iter()
+        .map(|(_, m)| m.cyclomatic)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    let mut count = 0;
+    for (func, metrics) in functions {
+        if metrics.cyclomatic < thresholds.hotspot_min_cyclomatic {
+            continue;
+        }
+        let Some(file) = func
+            .metadata
+            .get("file")
+            .and_then(serde_json::Value::as_str)
+        else {
+            continue;
+        };
+        let Some(&(changes, percentile)) = churn.get(file) else {
+            continue;
+        };
+        if changes == 0 || percentile < thresholds.hotspot_min_change_percentile {
+            continue;
+        }
+
+        let hotspot_score =
+            metrics.cyclomatic as f64 / max_cyclomatic as f64 * (percentile / 100.0);
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: func.id,
+                kind: AnalysisKind::ComplexityHotspot,
+                data: serde_json::json!({
+                    "score": (hotspot_score * 1000.0).round() / 1000.0,
+                    "cyclomatic": metrics.cyclomatic,
+                    "max_nesting": metrics.max_nesting,
+                    "file": file,
+                    "file_changes": changes,
+                    "change_percentile": percentile,
+                    "trend": trends.get(&func.id).copied().unwrap_or("Stable"),
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Functions that carry extraction-time metrics.
+async fn load_functions(
+    store: &dyn HomerStore,
+) -> crate::error::Result<Vec<(Node, FunctionMetrics)>> {
+    let functions = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Function),
+            ..Default::default()
+        })
+        .await?;
+    Ok(functions
+        .into_iter()
+        .filter_map(|f| {
+            let metrics = serde_json::from_value(f.metadata.get("metrics")?.clone()).ok()?;
+            Some((f, metrics))
+        })
+        .collect())
+}
+
+/// Previous `(snapshot label, cyclomatic)` points per function.
+async fn load_history(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<NodeId, Vec<(String, usize)>>> {
+    let existing = store
+        .get_analyses_by_kind(AnalysisKind::ComplexityTrend)
+        .await?;
+    Ok(existing
+        .into_iter()
+        .map(|r| {
+            let points = r
+                .data
+                .get("history")
+                .and_then(serde_json::Value::as_array)
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|p| {
+                            let label = p.get("snapshot")?.as_str()?.to_string();
+                            let value = usize::try_from(p.get("cyclomatic")?.as_u64()?).ok()?;
+                            Some((label, value))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            (r.node_id, points)
+        })
+        .collect())
+}
+
+async fn current_snapshot_label(store: &dyn HomerStore) -> crate::error::Result<String> {
+    Ok(store
+        .list_snapshots()
+        .await?
+        .pop()
+        .map_or_else(|| WORKING_TREE.to_string(), |s| s.label))
+}
+
+/// Checkpoint state: metrics, churn, and snapshot position.
+async fn complexity_state(
+    store: &dyn HomerStore,
+    functions: &[(Node, FunctionMetrics)],
+) -> crate::error::Result<String> {
+    let total: usize = functions.iter().map(|(_, m)| m.cyclomatic).sum();
+    let churn = store
+        .get_analyses_by_kind(AnalysisKind::ChangeFrequency)
+        .await?
+        .len();
+    let label = current_snapshot_label(store).await?;
+    Ok(format!("{}:{total}:{churn}:{label}", functions.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+
+    async fn add_function(
+        store: &SqliteStore,
+        file: &str,
+        name: &str,
+        cyclomatic: usize,
+    ) -> NodeId {
+        let mut metadata = HashMap::new();
+        metadata.insert("file".to_string(), serde_json::json!(file));
+        metadata.insert("qualified_name".to_string(), serde_json::json!(name));
+        metadata.insert(
+            "metrics".to_string(),
+            serde_json::json!(FunctionMetrics {
+                cyclomatic,
+                max_nesting: 2,
+                parameters: 1,
+                lines: 20,
+            }),
+        );
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: format!("{file}::{name}"),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn add_churn(store: &SqliteStore, file: &str, total: u64, percentile: f64) {
+        let file_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::File,
+                name: file.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: file_id,
+                kind: AnalysisKind::ChangeFrequency,
+                data: serde_json::json!({ "total": total, "percentile": percentile }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn flags_complex_functions_in_churning_files() {
+        let store = SqliteStore::in_memory().unwrap();
+        let hot = add_function(&store, "src/hot.rs", "parse", 15).await;
+        let simple = add_function(&store, "src/hot.rs", "helper", 2).await;
+        let cold = add_function(&store, "src/cold.rs", "legacy", 20).await;
+        add_churn(&store, "src/hot.rs", 40, 90.0).await;
+        add_churn(&store, "src/cold.rs", 1, 10.0).await;
+
+        ComplexityAnalyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let hotspots = store
+            .get_analyses_by_kind(AnalysisKind::ComplexityHotspot)
+            .await
+            .unwrap();
+        assert_eq!(hotspots.len(), 1, "hotspots: {hotspots:?}");
+        assert_eq!(hotspots[0].node_id, hot);
+        assert_eq!(hotspots[0].data["file_changes"], 40);
+
+        for id in [hot, simple, cold] {
+            let trend = store
+                .get_analysis(id, AnalysisKind::ComplexityTrend)
+                .await
+                .unwrap()
+                .expect("every function gets a trend");
+            assert_eq!(trend.data["trend"], "Stable");
+        }
+    }
+
+    #[tokio::test]
+    async fn trend_tracks_one_point_per_snapshot() {
+        let store = SqliteStore::in_memory().unwrap();
+        let config = HomerConfig::default();
+        add_function(&store, "src/a.rs", "grow", 3).await;
+
+        store.create_snapshot("v1").await.unwrap();
+        ComplexityAnalyzer.analyze(&store, &config).await.unwrap();
+        // Re-running without a new snapshot replaces the current point.
+        add_function(&store, "src/a.rs", "grow", 5).await;
+        ComplexityAnalyzer.analyze(&store, &config).await.unwrap();
+
+        store.create_snapshot("v2").await.unwrap();
+        let id = add_function(&store, "src/a.rs", "grow", 9).await;
+        assert!(ComplexityAnalyzer.needs_rerun(&store).await.unwrap());
+        ComplexityAnalyzer.analyze(&store, &config).await.unwrap();
+
+        let trend = store
+            .get_analysis(id, AnalysisKind::ComplexityTrend)
+            .await
+            .unwrap()
+            .unwrap();
+        let history = trend.data["history"].as_array().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0]["snapshot"], "v1");
+        assert_eq!(history[0]["cyclomatic"], 5);
+        assert_eq!(history[1]["cyclomatic"], 9);
+        assert_eq!(trend.data["trend"], "Rising");
+        assert!(!ComplexityAnalyzer.needs_rerun(&store).await.unwrap());
+    }
+}
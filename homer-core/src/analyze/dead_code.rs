@@ -0,0 +1,570 @@
+// Dead code detection — reachability over the call graph from inferred roots.
+//
+// Roots are `main`, exported symbols (marked during scope-graph extraction),
+// test functions, and configurable framework-handler patterns. Liveness is the
+// most confident call path from any root; functions and types without a
+// confident path are flagged with a confidence that reflects how likely the
+// call graph is to have missed a real caller.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
+
+use chrono::Utc;
+use tracing::{info, instrument};
+
+use crate::config::{DeadCodeConfig, HomerConfig};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, HyperedgeKind, Node, NodeFilter, NodeId,
+    NodeKind, extract_directed_pair,
+};
+
+use super::AnalyzeStats;
+use super::centrality::is_test_file;
+use super::traits::Analyzer;
+
+/// Entities reachable with at least this path confidence are considered live.
+const LIVE_THRESHOLD: f64 = 0.5;
+
+/// Prior that an unreachable free function is really dead.
+const FUNCTION_PRIOR: f64 = 0.9;
+/// Methods may be called through traits, interfaces, or dynamic dispatch
+/// that the call graph cannot see.
+const METHOD_PRIOR: f64 = 0.6;
+/// Types are only linked to the call graph through their methods.
+const TYPE_PRIOR: f64 = 0.5;
+
+#[derive(Debug, Default)]
+pub struct DeadCodeAnalyzer {
+    /// Root settings of the upcoming run, so a change to them triggers a
+    /// rerun.
+    pub settings: DeadCodeConfig,
+}
+
+impl DeadCodeAnalyzer {
+    pub fn new(config: &HomerConfig) -> Self {
+        Self {
+            settings: config.analysis.dead_code.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Analyzer for DeadCodeAnalyzer {
+    fn name(&self) -> &'static str {
+        "dead_code"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::DeadCode]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = graph_state(store, &self.settings).await?;
+        incremental::needs_extraction(store, "analyze:dead_code", &state).await
+    }
+
+    #[instrument(skip_all, name = "dead_code_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+
+        // Liveness is global: a new caller anywhere can revive any entity.
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::DeadCode])
+            .await?;
+
+        let functions = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Function),
+                ..Default::default()
+            })
+            .await?;
+        if functions.is_empty() {
+            info!("No functions found, skipping dead code analysis");
+            stats.duration = start.elapsed();
+            return Ok(stats);
+        }
+        let types = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Type),
+                ..Default::default()
+            })
+            .await?;
+
+        let patterns: Vec<glob::Pattern> = config
+            .analysis
+            .dead_code
+            .root_patterns
+            .iter()
+            .filter_map(|p| glob::Pattern::new(p).ok())
+            .collect();
+
+        let roots: Vec<&Node> = functions
+            .iter()
+            .filter(|f| root_reason(f, &patterns).is_some())
+            .collect();
+
+        let mut callees: HashMap<NodeId, Vec<(NodeId, f64)>> = HashMap::new();
+        for edge in store.get_edges_by_kind(HyperedgeKind::Calls).await? {
+            if edge.members.len() < 2 {
+                continue;
+            }
+            let (caller, callee) = extract_directed_pair(&edge.members);
+            callees
+                .entry(caller)
+                .or_default()
+                .push((callee, edge.confidence));
+        }
+
+        let liveness = propagate_liveness(roots.iter().map(|r| r.id), &callees);
+
+        let now = Utc::now();
+        let mut live_types: HashSet<String> = HashSet::new();
+
+        for func in &functions {
+            let reachability = liveness.get(&func.id).copied().unwrap_or(0.0);
+            if reachability >= LIVE_THRESHOLD {
+                if let Some(owner) = owner_type(func) {
+                    live_types.insert(owner);
+                }
+                continue;
+            }
+            let prior = if owner_type(func).is_some() {
+                METHOD_PRIOR
+            } else {
+                FUNCTION_PRIOR
+            };
+            store_dead(store, func, prior * (1.0 - reachability), reachability, now).await?;
+            stats.results_stored += 1;
+        }
+
+        for ty in &types {
+            let live = is_exported(ty)
+                || entity_file(ty).is_some_and(is_test_file)
+                || live_types.contains(&ty.name);
+            if !live {
+                store_dead(store, ty, TYPE_PRIOR, 0.0, now).await?;
+                stats.results_stored += 1;
+            }
+        }
+
+        let state = graph_state(store, &config.analysis.dead_code).await?;
+        store.set_checkpoint("analyze:dead_code", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            roots = roots.len(),
+            dead = stats.results_stored,
+            duration = ?stats.duration,
+            "Dead code analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Checkpoint state: function, type, call-edge, exported and test counts,
+/// plus the root settings.
+async fn graph_state(
+    store: &dyn HomerStore,
+    settings: &DeadCodeConfig,
+) -> crate::error::Result<String> {
+    let mut counts = Vec::new();
+    let mut exported = 0;
+    let mut tests = 0;
+    for kind in [NodeKind::Function, NodeKind::Type] {
+        let nodes = store
+            .find_nodes(&NodeFilter {
+                kind: Some(kind),
+                ..Default::default()
+            })
+            .await?;
+        counts.push(nodes.len());
+        exported += nodes.iter().filter(|n| is_exported(n)).count();
+        tests += nodes.iter().filter(|n| is_test(n)).count();
+    }
+    counts.push(store.get_edges_by_kind(HyperedgeKind::Calls).await?.len());
+    counts.push(exported);
+    counts.push(tests);
+    let settings = serde_json::to_string(settings).unwrap_or_default();
+    Ok(format!(
+        "{}:{settings}",
+        counts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(":")
+    ))
+}
+
+async fn store_dead(
+    store: &dyn HomerStore,
+    node: &Node,
+    confidence: f64,
+    reachability: f64,
+    now: chrono::DateTime<Utc>,
+) -> crate::error::Result<()> {
+    store
+        .store_analysis(&AnalysisResult {
+            id: AnalysisResultId(0),
+            node_id: node.id,
+            kind: AnalysisKind::DeadCode,
+            data: serde_json::json!({
+                "confidence": (confidence * 1000.0).round() / 1000.0,
+                "reachability": (reachability * 1000.0).round() / 1000.0,
+                "file": entity_file(node),
+            }),
+            input_hash: 0,
+            computed_at: now,
+        })
+        .await
+}
+
+/// Why a function is an entry point, if it is one.
+fn root_reason(func: &Node, patterns: &[glob::Pattern]) -> Option<&'static str> {
+    let qualified = qualified_name(func);
+    let simple = simple_name(qualified);
+
+    if simple == "main" {
+        return Some("main");
+    }
+    if is_exported(func) {
+        return Some("exported");
+    }
+    if is_test(func) || entity_file(func).is_some_and(is_test_file) || simple.starts_with("test_") {
+        return Some("test");
+    }
+    if patterns.iter().any(|p| p.matches(qualified)) {
+        return Some("pattern");
+    }
+    None
+}
+
+/// Best path confidence from any root to each reachable function.
+fn propagate_liveness(
+    roots: impl Iterator<Item = NodeId>,
+    callees: &HashMap<NodeId, Vec<(NodeId, f64)>>,
+) -> HashMap<NodeId, f64> {
+    let mut best: HashMap<NodeId, f64> = roots.map(|id| (id, 1.0)).collect();
+    let mut queue: VecDeque<NodeId> = best.keys().copied().collect();
+
+    while let Some(id) = queue.pop_front() {
+        let reach = best[&id];
+        for &(callee, confidence) in callees.get(&id).into_iter().flatten() {
+            let next = reach * confidence;
+            if best.get(&callee).is_none_or(|&r| next > r) {
+                best.insert(callee, next);
+                queue.push_back(callee);
+            }
+        }
+    }
+    best
+}
+
+fn is_exported(node: &Node) -> bool {
+    node.metadata
+        .get("exported")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Whether graph extraction recognised the function as a test.
+fn is_test(node: &Node) -> bool {
+    node.metadata.contains_key("test")
+}
+
+fn entity_file(node: &Node) -> Option<&str> {
+    node.metadata
+        .get("file")
+        .and_then(serde_json::Value::as_str)
+}
+
+fn qualified_name(node: &Node) -> &str {
+    node.metadata
+        .get("qualified_name")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or(&node.name)
+}
+
+fn simple_name(qualified: &str) -> &str {
+    let simple = qualified.rsplit("::").next().unwrap_or(qualified);
+    simple.rsplit('.').next().unwrap_or(simple)
+}
+
+/// Full node name of the type a method belongs to (`file::Type`), if any.
+fn owner_type(func: &Node) -> Option<String> {
+    let file = entity_file(func)?;
+    let qualified = qualified_name(func);
+    let owner = qualified
+        .rsplit_once("::")
+        .or_else(|| qualified.rsplit_once('.'))
+        .map(|(owner, _)| owner)?;
+    Some(format!("{file}::{owner}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+
+    async fn add_def(
+        store: &SqliteStore,
+        kind: NodeKind,
+        file: &str,
+        qualified: &str,
+        exported: bool,
+    ) -> NodeId {
+        let mut metadata = HashMap::new();
+        metadata.insert("file".to_string(), serde_json::json!(file));
+        metadata.insert("qualified_name".to_string(), serde_json::json!(qualified));
+        if exported {
+            metadata.insert("exported".to_string(), serde_json::json!(true));
+        }
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: format!("{file}::{qualified}"),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn add_call(store: &SqliteStore, from: NodeId, to: NodeId, confidence: f64) {
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Calls,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: from,
+                        role: "caller".to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: to,
+                        role: "callee".to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence,
+                last_updated: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn flags_unreachable_functions_and_types() {
+        let store = SqliteStore::in_memory().unwrap();
+        let f = NodeKind::Function;
+        let main = add_def(&store, f.clone(), "src/main.rs", "main", false).await;
+        let run = add_def(&store, f.clone(), "src/app.rs", "run", false).await;
+        let helper = add_def(&store, f.clone(), "src/app.rs", "helper", false).await;
+        let guessed = add_def(&store, f.clone(), "src/app.rs", "maybe", false).await;
+        let orphan = add_def(&store, f.clone(), "src/app.rs", "orphan", false).await;
+        let api = add_def(&store, f.clone(), "src/lib.rs", "public_api", true).await;
+        let test = add_def(&store, f.clone(), "tests/app.rs", "it_runs", false).await;
+        let handler = add_def(&store, f.clone(), "src/web.rs", "handle_get", false).await;
+        let method = add_def(&store, f.clone(), "src/app.rs", "Config::load", false).await;
+        let live_ty = add_def(&store, NodeKind::Type, "src/app.rs", "Config", false).await;
+        let dead_ty = add_def(&store, NodeKind::Type, "src/app.rs", "Unused", false).await;
+        add_call(&store, main, run, 1.0).await;
+        add_call(&store, run, helper, 0.9).await;
+        add_call(&store, run, guessed, 0.3).await;
+        add_call(&store, run, method, 1.0).await;
+
+        let stats = DeadCodeAnalyzer::default()
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let dead: HashMap<NodeId, f64> = store
+            .get_analyses_by_kind(AnalysisKind::DeadCode)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.node_id, r.data["confidence"].as_f64().unwrap()))
+            .collect();
+
+        assert_eq!(stats.results_stored, 3, "dead: {dead:?}");
+        assert!(dead.contains_key(&orphan));
+        assert!(dead.contains_key(&guessed), "only a low-confidence caller");
+        assert!(dead.contains_key(&dead_ty));
+        assert!(dead[&orphan] > dead[&guessed]);
+        for live in [main, run, helper, api, test, handler, method, live_ty] {
+            assert!(!dead.contains_key(&live), "{live:?} should be live");
+        }
+    }
+
+    #[tokio::test]
+    async fn rerun_clears_revived_entities() {
+        let store = SqliteStore::in_memory().unwrap();
+        let f = NodeKind::Function;
+        let main = add_def(&store, f.clone(), "src/main.rs", "main", false).await;
+        let later = add_def(&store, f, "src/app.rs", "later", false).await;
+
+        let config = HomerConfig::default();
+        DeadCodeAnalyzer::default()
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        assert!(
+            store
+                .get_analysis(later, AnalysisKind::DeadCode)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        add_call(&store, main, later, 1.0).await;
+        assert!(
+            DeadCodeAnalyzer::default()
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+        DeadCodeAnalyzer::default()
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        assert!(
+            store
+                .get_analysis(later, AnalysisKind::DeadCode)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            !DeadCodeAnalyzer::default()
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn inline_tests_and_their_helpers_are_live() {
+        let store = SqliteStore::in_memory().unwrap();
+        let mut metadata = HashMap::new();
+        metadata.insert("file".to_string(), serde_json::json!("src/lib.rs"));
+        metadata.insert(
+            "qualified_name".to_string(),
+            serde_json::json!("tests::it_works"),
+        );
+        metadata.insert(
+            "test".to_string(),
+            serde_json::json!({ "framework": "rust", "calls": ["fixture"] }),
+        );
+        let it_works = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/lib.rs::tests::it_works".to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap();
+        let fixture = add_def(
+            &store,
+            NodeKind::Function,
+            "src/lib.rs",
+            "tests::fixture",
+            false,
+        )
+        .await;
+        add_call(&store, it_works, fixture, 1.0).await;
+
+        DeadCodeAnalyzer::default()
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        for live in [it_works, fixture] {
+            assert!(
+                store
+                    .get_analysis(live, AnalysisKind::DeadCode)
+                    .await
+                    .unwrap()
+                    .is_none(),
+                "{live:?} should be live"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn root_settings_and_exports_trigger_rerun() {
+        let store = SqliteStore::in_memory().unwrap();
+        add_def(&store, NodeKind::Function, "src/web.rs", "index", false).await;
+        let config = HomerConfig::default();
+        DeadCodeAnalyzer::new(&config)
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+        assert!(
+            !DeadCodeAnalyzer::new(&config)
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+
+        let mut changed = config.clone();
+        changed
+            .analysis
+            .dead_code
+            .root_patterns
+            .push("index".into());
+        assert!(
+            DeadCodeAnalyzer::new(&changed)
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+
+        add_def(&store, NodeKind::Function, "src/web.rs", "index", true).await;
+        assert!(
+            DeadCodeAnalyzer::new(&config)
+                .needs_rerun(&store)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn root_patterns_match_qualified_names() {
+        let patterns: Vec<glob::Pattern> = ["*Controller::*"]
+            .iter()
+            .map(|p| glob::Pattern::new(p).unwrap())
+            .collect();
+        let node = |qualified: &str| Node {
+            id: NodeId(1),
+            kind: NodeKind::Function,
+            name: format!("src/x.rs::{qualified}"),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: HashMap::from([
+                ("file".to_string(), serde_json::json!("src/x.rs")),
+                ("qualified_name".to_string(), serde_json::json!(qualified)),
+            ]),
+        };
+        assert_eq!(
+            root_reason(&node("UserController::index"), &patterns),
+            Some("pattern")
+        );
+        assert_eq!(root_reason(&node("test_parse"), &patterns), Some("test"));
+        assert_eq!(root_reason(&node("main"), &patterns), Some("main"));
+        assert_eq!(root_reason(&node("helper"), &patterns), None);
+    }
+}
@@ -0,0 +1,297 @@
+// Runtime hotness: how much of the profiled runtime each function and file
+// accounts for, from the shares profile ingestion stored on `Function` nodes.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use chrono::Utc;
+use tracing::{info, instrument};
+
+use crate::config::HomerConfig;
+use crate::extract::profile::RUNTIME_KEY;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{AnalysisKind, AnalysisResult, AnalysisResultId, NodeFilter, NodeId, NodeKind};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Hottest functions listed per file.
+const MAX_FILE_FUNCTIONS: usize = 5;
+
+/// Scores profiled functions and rolls them up to files. A file scores as
+/// its hottest function, and its self share is the sum of its functions'
+/// self shares. Composite salience blends the score in when configured to.
+#[derive(Debug, Default)]
+pub struct RuntimeHotnessAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for RuntimeHotnessAnalyzer {
+    fn name(&self) -> &'static str {
+        "runtime_hotness"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::RuntimeHotness]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = store
+            .get_checkpoint("profiles_state")
+            .await?
+            .unwrap_or_default();
+        incremental::needs_extraction(store, "analyze:runtime_hotness", &state).await
+    }
+
+    #[instrument(skip_all, name = "runtime_hotness_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+        let hot_min_share = config.analysis.runtime.hot_min_share;
+
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::RuntimeHotness])
+            .await?;
+
+        let functions: Vec<Profiled> = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Function),
+                ..Default::default()
+            })
+            .await?
+            .into_iter()
+            .filter_map(|func| {
+                let runtime = func.metadata.get(RUNTIME_KEY)?;
+                let share = |key: &str| runtime.get(key).and_then(serde_json::Value::as_f64);
+                Some(Profiled {
+                    id: func.id,
+                    file: func
+                        .metadata
+                        .get("file")
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::to_string),
+                    self_share: share("self_share").unwrap_or(0.0),
+                    total_share: share("total_share")?,
+                    profiles: runtime.get("profiles").cloned(),
+                    name: func.name,
+                })
+            })
+            .collect();
+        if functions.is_empty() {
+            info!("No profiles ingested, skipping runtime hotness");
+        }
+
+        let mut results: Vec<(NodeId, serde_json::Value)> = Vec::new();
+        let max_total = functions.iter().map(|f| f.total_share).fold(0.0, f64::max);
+        let mut totals: Vec<f64> = functions.iter().map(|f| f.total_share).collect();
+        totals.sort_by(f64::total_cmp);
+        let mut by_file: HashMap<&str, Vec<&Profiled>> = HashMap::new();
+        for func in &functions {
+            let percentile = percentile(&totals, func.total_share);
+            results.push((
+                func.id,
+                serde_json::json!({
+                    "self_share": func.self_share,
+                    "total_share": func.total_share,
+                    "score": score(func.total_share, max_total),
+                    "percentile": percentile,
+                    "hot": func.total_share >= hot_min_share,
+                    "profiles": func.profiles,
+                }),
+            ));
+            if let Some(file) = &func.file {
+                by_file.entry(file).or_default().push(func);
+            }
+        }
+
+        for (file, mut funcs) in by_file {
+            let Some(node) = store.get_node_by_name(NodeKind::File, file).await? else {
+                continue;
+            };
+            funcs.sort_by(|a, b| b.total_share.total_cmp(&a.total_share));
+            let total_share = funcs[0].total_share;
+            let self_share: f64 = funcs.iter().map(|f| f.self_share).sum();
+            let hottest: Vec<&str> = funcs
+                .iter()
+                .take(MAX_FILE_FUNCTIONS)
+                .map(|f| f.name.as_str())
+                .collect();
+            results.push((
+                node.id,
+                serde_json::json!({
+                    "self_share": (self_share * 10000.0).round() / 10000.0,
+                    "total_share": total_share,
+                    "score": score(total_share, max_total),
+                    "hot": total_share >= hot_min_share,
+                    "functions": hottest,
+                }),
+            ));
+        }
+
+        for (node_id, data) in results {
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id,
+                    kind: AnalysisKind::RuntimeHotness,
+                    data,
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let state = store
+            .get_checkpoint("profiles_state")
+            .await?
+            .unwrap_or_default();
+        store
+            .set_checkpoint("analyze:runtime_hotness", &state)
+            .await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            functions = functions.len(),
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "Runtime hotness analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// A function with ingested runtime shares.
+struct Profiled {
+    id: NodeId,
+    name: String,
+    file: Option<String>,
+    self_share: f64,
+    total_share: f64,
+    profiles: Option<serde_json::Value>,
+}
+
+/// Total share (time in a function and its callees) relative to the hottest
+/// function, so the entry point of a hot path scores near 1.0. Code never
+/// seen in a profile gets no result at all.
+fn score(total_share: f64, max_total: f64) -> f64 {
+    if max_total <= 0.0 {
+        return 0.0;
+    }
+    (total_share / max_total * 1000.0).round() / 1000.0
+}
+
+/// Percentage (0–100) of the `sorted` values at or below `value`.
+#[allow(clippy::cast_precision_loss)]
+fn percentile(sorted: &[f64], value: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let at_or_below = sorted.partition_point(|v| *v <= value);
+    (at_or_below as f64 / sorted.len() as f64 * 1000.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::Node;
+
+    async fn add(
+        store: &SqliteStore,
+        kind: NodeKind,
+        name: &str,
+        meta: serde_json::Value,
+    ) -> NodeId {
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: serde_json::from_value(meta).unwrap(),
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn scores_functions_and_rolls_up_files() {
+        let store = SqliteStore::in_memory().unwrap();
+        let file = add(
+            &store,
+            NodeKind::File,
+            "src/parse.rs",
+            serde_json::json!({}),
+        )
+        .await;
+        let runtime = |self_share: f64, total_share: f64| {
+            serde_json::json!({
+                "file": "src/parse.rs",
+                "runtime": {"self_share": self_share, "total_share": total_share, "profiles": 1},
+            })
+        };
+        let parse = add(
+            &store,
+            NodeKind::Function,
+            "src/parse.rs::parse",
+            runtime(0.5, 0.8),
+        )
+        .await;
+        let lex = add(
+            &store,
+            NodeKind::Function,
+            "src/parse.rs::lex",
+            runtime(0.02, 0.02),
+        )
+        .await;
+        add(
+            &store,
+            NodeKind::Function,
+            "src/parse.rs::unused",
+            serde_json::json!({}),
+        )
+        .await;
+
+        let analyzer = RuntimeHotnessAnalyzer;
+        assert!(analyzer.needs_rerun(&store).await.unwrap());
+        let stats = analyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.results_stored, 3);
+        assert!(!analyzer.needs_rerun(&store).await.unwrap());
+
+        let result = |id| {
+            let store = &store;
+            async move {
+                store
+                    .get_analysis(id, AnalysisKind::RuntimeHotness)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .data
+            }
+        };
+        let parse = result(parse).await;
+        assert_eq!(parse["score"], 1.0);
+        assert_eq!(parse["hot"], true);
+        let lex = result(lex).await;
+        assert_eq!(lex["score"], 0.025);
+        assert_eq!(lex["hot"], false);
+        assert_eq!(lex["percentile"], 50.0);
+        let file = result(file).await;
+        assert_eq!(file["self_share"], 0.52);
+        assert_eq!(file["total_share"], 0.8);
+        assert_eq!(
+            file["functions"],
+            serde_json::json!(["src/parse.rs::parse", "src/parse.rs::lex"])
+        );
+    }
+}
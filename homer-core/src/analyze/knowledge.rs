@@ -0,0 +1,639 @@
+// Knowledge-loss analysis: how much of the code is known only to people who
+// have stopped contributing.
+
+// Statistical computations intentionally cast int→float.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument};
+
+use crate::config::{HomerConfig, KnowledgeConfig};
+use crate::contracts;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, HyperedgeKind, NodeFilter, NodeId, NodeKind,
+};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Number of contributors listed per file or module.
+const MAX_CONTRIBUTORS: usize = 10;
+
+#[derive(Debug, Default)]
+pub struct KnowledgeLossAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for KnowledgeLossAnalyzer {
+    fn name(&self) -> &'static str {
+        "knowledge_loss"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[
+            AnalysisKind::ContributorActivity,
+            AnalysisKind::KnowledgeLoss,
+        ]
+    }
+
+    fn requires(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::CompositeSalience]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = knowledge_state(store).await?;
+        incremental::needs_extraction(store, "analyze:knowledge_loss", &state).await
+    }
+
+    #[instrument(skip_all, name = "knowledge_loss_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+
+        // Activity is relative to the newest commit, so every result can shift.
+        store
+            .clear_analyses_by_kinds(&[
+                AnalysisKind::ContributorActivity,
+                AnalysisKind::KnowledgeLoss,
+            ])
+            .await?;
+
+        let history = load_history(store).await?;
+        let Some(latest) = history.commits.values().map(|c| c.last_commit).max() else {
+            info!("No authored commits found, skipping knowledge-loss analysis");
+            stats.duration = start.elapsed();
+            return Ok(stats);
+        };
+
+        let thresholds = &config.analysis.knowledge;
+        let activity = classify_activity(&history.commits, latest, thresholds);
+        let people = load_people(store).await?;
+
+        for (author, act) in &activity {
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: *author,
+                    kind: AnalysisKind::ContributorActivity,
+                    data: serde_json::json!({
+                        "active": act.active,
+                        "last_commit": act.last_commit.to_rfc3339(),
+                        "days_since_last_commit": act.days_inactive,
+                        "commits": act.commits,
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let salience = load_salience(store).await?;
+        let paths = load_file_paths(store).await?;
+        let ctx = Context {
+            activity: &activity,
+            people: &people,
+            thresholds,
+        };
+
+        let mut modules: HashMap<String, ModuleKnowledge> = HashMap::new();
+        for (file_id, authors) in &history.file_authors {
+            let Some(path) = paths.get(file_id) else {
+                continue;
+            };
+            let file_salience = salience.get(file_id).copied().unwrap_or(0.0);
+            let summary = ctx.summarize(authors, file_salience);
+
+            for dir in Path::new(path).ancestors().skip(1) {
+                let module = modules
+                    .entry(dir.to_string_lossy().into_owned())
+                    .or_default();
+                for (author, count) in authors {
+                    *module.authors.entry(*author).or_default() += count;
+                }
+                module.salience = module.salience.max(file_salience);
+                module.files += 1;
+                module.orphaned_files += usize::from(summary.orphaned);
+            }
+
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: *file_id,
+                    kind: AnalysisKind::KnowledgeLoss,
+                    data: summary.data,
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let root_module = contracts::find_root_module_id(store).await?;
+        for (dir, module) in &modules {
+            let node_id = if dir.is_empty() {
+                root_module
+            } else {
+                store
+                    .get_node_by_name(NodeKind::Module, dir)
+                    .await?
+                    .map(|n| n.id)
+            };
+            let Some(node_id) = node_id else { continue };
+
+            let mut data = ctx.summarize(&module.authors, module.salience).data;
+            data["files"] = serde_json::json!(module.files);
+            data["orphaned_files"] = serde_json::json!(module.orphaned_files);
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id,
+                    kind: AnalysisKind::KnowledgeLoss,
+                    data,
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let state = knowledge_state(store).await?;
+        store
+            .set_checkpoint("analyze:knowledge_loss", &state)
+            .await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            contributors = activity.len(),
+            inactive = activity.values().filter(|a| !a.active).count(),
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "Knowledge-loss analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Rerun when new commits arrive or salience scores are recomputed.
+async fn knowledge_state(store: &dyn HomerStore) -> crate::error::Result<String> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Commit),
+        ..Default::default()
+    };
+    let commits = store.find_nodes(&filter).await?.len();
+    let salience = store
+        .get_analyses_by_kind(AnalysisKind::CompositeSalience)
+        .await?
+        .len();
+    Ok(format!("{commits}:{salience}"))
+}
+
+// ── Data loading ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct CommitHistory {
+    commits: u64,
+    last_commit: DateTime<Utc>,
+}
+
+struct History {
+    /// Commit count and most recent author time per contributor.
+    commits: HashMap<NodeId, CommitHistory>,
+    /// Non-merge commits per author for each file.
+    file_authors: HashMap<NodeId, HashMap<NodeId, u64>>,
+}
+
+async fn load_history(store: &dyn HomerStore) -> crate::error::Result<History> {
+    let mut commits: HashMap<NodeId, CommitHistory> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Authored).await? {
+        let has_commit = edge.members.iter().any(|m| m.role == "commit");
+        let Some(author) = edge.members.iter().find(|m| m.role == "author") else {
+            continue;
+        };
+        if !has_commit {
+            continue;
+        }
+        commits
+            .entry(author.node_id)
+            .and_modify(|h| {
+                h.commits += 1;
+                h.last_commit = h.last_commit.max(edge.last_updated);
+            })
+            .or_insert(CommitHistory {
+                commits: 1,
+                last_commit: edge.last_updated,
+            });
+    }
+
+    Ok(History {
+        commits,
+        file_authors: contracts::load_file_authors(store).await?,
+    })
+}
+
+/// Email and display name per contributor.
+async fn load_people(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<NodeId, (String, String)>> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Contributor),
+        ..Default::default()
+    };
+    Ok(store
+        .find_nodes(&filter)
+        .await?
+        .into_iter()
+        .map(|n| {
+            let display = n
+                .metadata
+                .get("display_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or(&n.name)
+                .to_string();
+            (n.id, (n.name, display))
+        })
+        .collect())
+}
+
+async fn load_salience(store: &dyn HomerStore) -> crate::error::Result<HashMap<NodeId, f64>> {
+    Ok(store
+        .get_analyses_by_kind(AnalysisKind::CompositeSalience)
+        .await?
+        .into_iter()
+        .filter_map(|r| {
+            let value = r.data.get("score").and_then(serde_json::Value::as_f64)?;
+            Some((r.node_id, value))
+        })
+        .collect())
+}
+
+async fn load_file_paths(store: &dyn HomerStore) -> crate::error::Result<HashMap<NodeId, String>> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::File),
+        ..Default::default()
+    };
+    Ok(store
+        .find_nodes(&filter)
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect())
+}
+
+// ── Classification ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct Activity {
+    active: bool,
+    commits: u64,
+    last_commit: DateTime<Utc>,
+    days_inactive: i64,
+}
+
+/// A contributor is inactive once their last commit is more than
+/// `inactive_after_days` older than `latest`, the newest commit in the
+/// repository. The repository's clock rather than the wall clock keeps an
+/// archived project from reading as abandoned by everyone.
+fn classify_activity(
+    commits: &HashMap<NodeId, CommitHistory>,
+    latest: DateTime<Utc>,
+    thresholds: &KnowledgeConfig,
+) -> HashMap<NodeId, Activity> {
+    commits
+        .iter()
+        .map(|(author, history)| {
+            let days_inactive = (latest - history.last_commit).num_days();
+            let activity = Activity {
+                active: days_inactive <= i64::from(thresholds.inactive_after_days),
+                commits: history.commits,
+                last_commit: history.last_commit,
+                days_inactive,
+            };
+            (*author, activity)
+        })
+        .collect()
+}
+
+/// Commits of every file beneath a module.
+#[derive(Debug, Default)]
+struct ModuleKnowledge {
+    authors: HashMap<NodeId, u64>,
+    /// Highest salience of any file in the module.
+    salience: f64,
+    files: usize,
+    orphaned_files: usize,
+}
+
+struct Summary {
+    orphaned: bool,
+    data: serde_json::Value,
+}
+
+struct Context<'a> {
+    activity: &'a HashMap<NodeId, Activity>,
+    people: &'a HashMap<NodeId, (String, String)>,
+    thresholds: &'a KnowledgeConfig,
+}
+
+impl Context<'_> {
+    fn is_active(&self, author: NodeId) -> bool {
+        self.activity.get(&author).is_some_and(|a| a.active)
+    }
+
+    /// Knowledge of an entity is approximated by each author's share of the
+    /// commits touching it. Salient code whose knowledge sits mostly with
+    /// inactive contributors is orphaned.
+    fn summarize(&self, authors: &HashMap<NodeId, u64>, salience: f64) -> Summary {
+        let total: u64 = authors.values().sum();
+        let inactive: u64 = authors
+            .iter()
+            .filter(|(author, _)| !self.is_active(**author))
+            .map(|(_, count)| count)
+            .sum();
+        let inactive_share = if total == 0 {
+            0.0
+        } else {
+            inactive as f64 / total as f64
+        };
+        let orphaned = inactive_share >= self.thresholds.orphaned_min_inactive_share
+            && salience >= self.thresholds.orphaned_min_salience;
+
+        let mut ranked: Vec<(&NodeId, &u64)> = authors.iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(a.1).then(a.0.0.cmp(&b.0.0)));
+        let contributors: Vec<serde_json::Value> = ranked
+            .into_iter()
+            .take(MAX_CONTRIBUTORS)
+            .map(|(author, count)| {
+                let (email, name) = self
+                    .people
+                    .get(author)
+                    .cloned()
+                    .unwrap_or_else(|| (format!("#{}", author.0), String::new()));
+                serde_json::json!({
+                    "email": email,
+                    "name": name,
+                    "commits": count,
+                    "share": round2(*count as f64 / total.max(1) as f64),
+                    "active": self.is_active(*author),
+                    "last_commit": self.activity.get(author).map(|a| a.last_commit.to_rfc3339()),
+                })
+            })
+            .collect();
+
+        Summary {
+            orphaned,
+            data: serde_json::json!({
+                "total_commits": total,
+                "inactive_share": round2(inactive_share),
+                "active_contributors": authors.keys().filter(|a| self.is_active(**a)).count(),
+                "contributors": contributors,
+                "salience": round2(salience),
+                "orphaned": orphaned,
+            }),
+        }
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember, Node};
+    use chrono::Duration;
+
+    async fn add_node(store: &SqliteStore, kind: NodeKind, name: &str) -> NodeId {
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn member(node_id: NodeId, role: &str, position: u32) -> HyperedgeMember {
+        HyperedgeMember {
+            node_id,
+            role: role.to_string(),
+            position,
+        }
+    }
+
+    async fn add_commit(
+        store: &SqliteStore,
+        sha: &str,
+        author: NodeId,
+        files: &[NodeId],
+        at: DateTime<Utc>,
+    ) {
+        add_commit_with(store, sha, author, files, at, false).await;
+    }
+
+    async fn add_commit_with(
+        store: &SqliteStore,
+        sha: &str,
+        author: NodeId,
+        files: &[NodeId],
+        at: DateTime<Utc>,
+        merge: bool,
+    ) {
+        let mut metadata = HashMap::new();
+        if merge {
+            metadata.insert("merge".to_string(), serde_json::json!(true));
+        }
+        let commit = add_node(store, NodeKind::Commit, sha).await;
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Authored,
+                members: vec![member(author, "author", 0), member(commit, "commit", 1)],
+                confidence: 1.0,
+                last_updated: at,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let mut members = vec![member(commit, "commit", 0)];
+        for (file, position) in files.iter().zip(1..) {
+            members.push(member(*file, "file", position));
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Modifies,
+                members,
+                confidence: 1.0,
+                last_updated: at,
+                metadata,
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn set_salience(store: &SqliteStore, node_id: NodeId, value: f64) {
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({ "score": value }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn flags_salient_code_held_by_departed_contributors() {
+        let store = SqliteStore::in_memory().unwrap();
+        let config = HomerConfig::default();
+
+        let core = add_node(&store, NodeKind::File, "src/core.rs").await;
+        let util = add_node(&store, NodeKind::File, "src/util.rs").await;
+        add_node(&store, NodeKind::Module, "src").await;
+        let alice = add_node(&store, NodeKind::Contributor, "alice@example.com").await;
+        let bob = add_node(&store, NodeKind::Contributor, "bob@example.com").await;
+        set_salience(&store, core, 0.9).await;
+        set_salience(&store, util, 0.2).await;
+
+        // Alice wrote most of the code a year ago and then left; the repo's
+        // latest commit is Bob's, a few days old.
+        let now = Utc::now();
+        for i in 0..4 {
+            let at = now - Duration::days(400 + i);
+            add_commit(&store, &format!("a{i}"), alice, &[core, util], at).await;
+        }
+        add_commit(&store, "b0", bob, &[core], now - Duration::days(3)).await;
+        add_commit(&store, "b1", bob, &[util], now - Duration::days(2)).await;
+
+        KnowledgeLossAnalyzer
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+
+        let alice_activity = store
+            .get_analysis(alice, AnalysisKind::ContributorActivity)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alice_activity.data["active"], false);
+        assert_eq!(alice_activity.data["commits"], 4);
+        let bob_activity = store
+            .get_analysis(bob, AnalysisKind::ContributorActivity)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bob_activity.data["active"], true);
+
+        let core_loss = store
+            .get_analysis(core, AnalysisKind::KnowledgeLoss)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(core_loss.data["inactive_share"], 0.8);
+        assert_eq!(core_loss.data["orphaned"], true);
+        assert_eq!(
+            core_loss.data["contributors"][0]["email"],
+            "alice@example.com"
+        );
+        assert_eq!(core_loss.data["contributors"][0]["active"], false);
+
+        // Same knowledge split, but too peripheral to flag.
+        let util_loss = store
+            .get_analysis(util, AnalysisKind::KnowledgeLoss)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(util_loss.data["orphaned"], false);
+
+        let module = store
+            .get_node_by_name(NodeKind::Module, "src")
+            .await
+            .unwrap()
+            .unwrap();
+        let module_loss = store
+            .get_analysis(module.id, AnalysisKind::KnowledgeLoss)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(module_loss.data["files"], 2);
+        assert_eq!(module_loss.data["orphaned_files"], 1);
+        assert_eq!(module_loss.data["total_commits"], 10);
+    }
+
+    #[tokio::test]
+    async fn merge_commits_do_not_confer_knowledge() {
+        let store = SqliteStore::in_memory().unwrap();
+        let core = add_node(&store, NodeKind::File, "src/core.rs").await;
+        let alice = add_node(&store, NodeKind::Contributor, "alice@example.com").await;
+        let carol = add_node(&store, NodeKind::Contributor, "carol@example.com").await;
+        let now = Utc::now();
+        add_commit(&store, "a0", alice, &[core], now - Duration::days(10)).await;
+        for i in 0..3 {
+            let at = now - Duration::days(i);
+            add_commit_with(&store, &format!("m{i}"), carol, &[core], at, true).await;
+        }
+
+        KnowledgeLossAnalyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let loss = store
+            .get_analysis(core, AnalysisKind::KnowledgeLoss)
+            .await
+            .unwrap()
+            .unwrap();
+        let contributors = loss.data["contributors"].as_array().unwrap();
+        assert_eq!(contributors.len(), 1, "{contributors:?}");
+        assert_eq!(contributors[0]["email"], "alice@example.com");
+    }
+
+    #[test]
+    fn activity_is_relative_to_latest_commit() {
+        let latest = Utc::now() - Duration::days(1000);
+        let commits: HashMap<NodeId, CommitHistory> = [
+            (
+                NodeId(1),
+                CommitHistory {
+                    commits: 3,
+                    last_commit: latest - Duration::days(10),
+                },
+            ),
+            (
+                NodeId(2),
+                CommitHistory {
+                    commits: 1,
+                    last_commit: latest - Duration::days(200),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let activity = classify_activity(&commits, latest, &KnowledgeConfig::default());
+        // Both are years behind the wall clock; only the repo clock matters.
+        assert!(activity[&NodeId(1)].active);
+        assert!(!activity[&NodeId(2)].active);
+        assert_eq!(activity[&NodeId(2)].days_inactive, 200);
+    }
+}
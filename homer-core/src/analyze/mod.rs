@@ -1,10 +1,20 @@
+pub mod api_surface;
 pub mod behavioral;
 pub mod centrality;
+pub mod clones;
 pub mod community;
+pub mod complexity;
 pub mod convention;
+pub mod dead_code;
+pub mod hotness;
+pub mod knowledge;
+pub mod ownership;
 pub mod semantic;
+pub mod survival;
 pub mod task_pattern;
+pub mod teams;
 pub mod temporal;
+pub mod test_health;
 pub mod traits;
 
 use std::time::Duration;
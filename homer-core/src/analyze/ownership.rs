@@ -0,0 +1,444 @@
+// Ownership drift: CODEOWNERS checked against who actually writes the code.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use chrono::Utc;
+use tracing::{info, instrument};
+
+use crate::config::HomerConfig;
+use crate::contracts::{self, roles};
+use crate::extract::codeowners::{is_team, owner_matches};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, HyperedgeKind, NodeFilter, NodeId, NodeKind,
+};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Most frequent committers reported for a drifted file.
+const MAX_AUTHORS: usize = 3;
+
+/// Flags a file with commit history (`ContributorConcentration`) when none
+/// of the owners on its `Owns` edges has ever committed to it. A file owned
+/// only by teams with no known members is never flagged.
+#[derive(Debug, Default)]
+pub struct OwnershipDriftAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for OwnershipDriftAnalyzer {
+    fn name(&self) -> &'static str {
+        "ownership_drift"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::OwnershipDrift]
+    }
+
+    fn requires(&self) -> &'static [AnalysisKind] {
+        &[
+            AnalysisKind::ContributorConcentration,
+            AnalysisKind::TeamAssignment,
+        ]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = drift_state(store).await?;
+        incremental::needs_extraction(store, "analyze:ownership_drift", &state).await
+    }
+
+    #[instrument(skip_all, name = "ownership_drift_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::OwnershipDrift])
+            .await?;
+
+        let owned = load_owned_files(store).await?;
+        if owned.is_empty() {
+            info!("No CODEOWNERS ownership edges, skipping ownership drift");
+        } else {
+            let people = load_people(store).await?;
+            let rosters = load_rosters(store).await?;
+            let commits = contracts::load_file_authors(store).await?;
+            let concentration: HashMap<NodeId, serde_json::Value> = store
+                .get_analyses_by_kind(AnalysisKind::ContributorConcentration)
+                .await?
+                .into_iter()
+                .map(|r| (r.node_id, r.data))
+                .collect();
+
+            for (file, ownership) in &owned {
+                let Some(concentration) = concentration.get(file) else {
+                    continue;
+                };
+                let authors = commits.get(file).cloned().unwrap_or_default();
+                let Some(data) = check_file(ownership, &authors, &people, &rosters, concentration)
+                else {
+                    continue;
+                };
+                store
+                    .store_analysis(&AnalysisResult {
+                        id: AnalysisResultId(0),
+                        node_id: *file,
+                        kind: AnalysisKind::OwnershipDrift,
+                        data,
+                        input_hash: 0,
+                        computed_at: now,
+                    })
+                    .await?;
+                stats.results_stored += 1;
+            }
+        }
+
+        let state = drift_state(store).await?;
+        store
+            .set_checkpoint("analyze:ownership_drift", &state)
+            .await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            drifted = stats.results_stored,
+            duration = ?stats.duration,
+            "Ownership drift analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Rerun when commits, ownership edges, or team assignments change.
+async fn drift_state(store: &dyn HomerStore) -> crate::error::Result<String> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Commit),
+        ..Default::default()
+    };
+    let commits = store.find_nodes(&filter).await?.len();
+    let owns: usize = store
+        .get_edges_by_kind(HyperedgeKind::Owns)
+        .await?
+        .iter()
+        .map(|e| e.members.len())
+        .sum();
+    let teams = store
+        .get_analyses_by_kind(AnalysisKind::TeamAssignment)
+        .await?
+        .len();
+    Ok(format!("{commits}:{owns}:{teams}"))
+}
+
+// ── Data loading ──────────────────────────────────────────────────
+
+/// The owners and deciding rule of one file.
+#[derive(Debug, Clone)]
+struct Ownership {
+    owners: Vec<String>,
+    pattern: String,
+}
+
+async fn load_owned_files(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<NodeId, Ownership>> {
+    let mut owned: HashMap<NodeId, Ownership> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Owns).await? {
+        let owners: Vec<String> = edge
+            .metadata
+            .get("owners")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let pattern = edge
+            .metadata
+            .get("pattern")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        for file in edge.members.iter().filter(|m| m.role == roles::OWNED) {
+            // GitLab sections each contribute owners to the same file.
+            let entry = owned.entry(file.node_id).or_insert_with(|| Ownership {
+                owners: Vec::new(),
+                pattern: pattern.to_string(),
+            });
+            for owner in &owners {
+                if !entry.owners.contains(owner) {
+                    entry.owners.push(owner.clone());
+                }
+            }
+        }
+    }
+    Ok(owned)
+}
+
+/// A commit identity: email and display name.
+struct Person {
+    email: String,
+    name: Option<String>,
+}
+
+async fn load_people(store: &dyn HomerStore) -> crate::error::Result<HashMap<NodeId, Person>> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Contributor),
+        ..Default::default()
+    };
+    Ok(store
+        .find_nodes(&filter)
+        .await?
+        .into_iter()
+        .map(|n| {
+            let name = n
+                .metadata
+                .get("display_name")
+                .and_then(serde_json::Value::as_str)
+                .map(String::from);
+            (
+                n.id,
+                Person {
+                    email: n.name,
+                    name,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Roster team name → member contributors. Teams inferred from CODEOWNERS
+/// are left out: they would vouch for themselves.
+async fn load_rosters(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<String, HashSet<NodeId>>> {
+    let mut rosters: HashMap<String, HashSet<NodeId>> = HashMap::new();
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::TeamAssignment)
+        .await?
+    {
+        if result
+            .data
+            .get("source")
+            .and_then(serde_json::Value::as_str)
+            != Some("roster")
+        {
+            continue;
+        }
+        if let Some(team) = result.data.get("team").and_then(serde_json::Value::as_str) {
+            rosters
+                .entry(team.to_lowercase())
+                .or_default()
+                .insert(result.node_id);
+        }
+    }
+    Ok(rosters)
+}
+
+// ── Validation ────────────────────────────────────────────────────
+
+/// The contributors behind an owner, or `None` when the owner is a team
+/// with no roster members. People match commit identities by email or
+/// login; a team counts through its roster members (`TeamAssignment`).
+fn resolve_owner(
+    owner: &str,
+    people: &HashMap<NodeId, Person>,
+    rosters: &HashMap<String, HashSet<NodeId>>,
+) -> Option<HashSet<NodeId>> {
+    if is_team(owner) {
+        let owner = owner.to_lowercase();
+        let slug = owner.rsplit('/').next().unwrap_or_default();
+        return rosters.get(&owner).or_else(|| rosters.get(slug)).cloned();
+    }
+    Some(
+        people
+            .iter()
+            .filter(|(_, p)| owner_matches(owner, &p.email, p.name.as_deref()))
+            .map(|(id, _)| *id)
+            .collect(),
+    )
+}
+
+/// Drift data for a file whose checkable owners never committed to it.
+fn check_file(
+    ownership: &Ownership,
+    authors: &HashMap<NodeId, u64>,
+    people: &HashMap<NodeId, Person>,
+    rosters: &HashMap<String, HashSet<NodeId>>,
+    concentration: &serde_json::Value,
+) -> Option<serde_json::Value> {
+    let mut unverified = Vec::new();
+    let mut checked = 0;
+    for owner in &ownership.owners {
+        match resolve_owner(owner, people, rosters) {
+            Some(ids) if ids.iter().any(|id| authors.contains_key(id)) => return None,
+            Some(_) => checked += 1,
+            None => unverified.push(owner.as_str()),
+        }
+    }
+    if checked == 0 {
+        return None;
+    }
+
+    let mut top: Vec<(&str, u64)> = authors
+        .iter()
+        .filter_map(|(id, commits)| Some((people.get(id)?.email.as_str(), *commits)))
+        .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    Some(serde_json::json!({
+        "owners": ownership.owners,
+        "pattern": ownership.pattern,
+        "unverified_owners": unverified,
+        "total_commits": concentration.get("total_commits"),
+        "bus_factor": concentration.get("bus_factor"),
+        "top_authors": top
+            .into_iter()
+            .take(MAX_AUTHORS)
+            .map(|(email, commits)| serde_json::json!({ "email": email, "commits": commits }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember, Node};
+
+    async fn add_node(store: &SqliteStore, kind: NodeKind, name: &str) -> NodeId {
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn member(node_id: NodeId, role: &str, position: u32) -> HyperedgeMember {
+        HyperedgeMember {
+            node_id,
+            role: role.to_string(),
+            position,
+        }
+    }
+
+    async fn add_commit(store: &SqliteStore, sha: &str, author: NodeId, file: NodeId) {
+        let commit = add_node(store, NodeKind::Commit, sha).await;
+        for (kind, members) in [
+            (
+                HyperedgeKind::Authored,
+                vec![member(author, "author", 0), member(commit, "commit", 1)],
+            ),
+            (
+                HyperedgeKind::Modifies,
+                vec![member(commit, "commit", 0), member(file, "file", 1)],
+            ),
+        ] {
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind,
+                    members,
+                    confidence: 1.0,
+                    last_updated: Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn add_owns(store: &SqliteStore, owners: &[&str], file: NodeId) {
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Owns,
+                members: vec![member(file, roles::OWNED, 0)],
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::from([
+                    ("owners".to_string(), serde_json::json!(owners)),
+                    ("pattern".to_string(), serde_json::json!("*")),
+                ]),
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn store_result(
+        store: &SqliteStore,
+        node: NodeId,
+        kind: AnalysisKind,
+        data: serde_json::Value,
+    ) {
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: node,
+                kind,
+                data,
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn flags_files_whose_owners_never_committed() {
+        let store = SqliteStore::in_memory().unwrap();
+        let alice = add_node(&store, NodeKind::Contributor, "alice@example.com").await;
+        let bob = add_node(&store, NodeKind::Contributor, "bob@example.com").await;
+        store_result(
+            &store,
+            bob,
+            AnalysisKind::TeamAssignment,
+            serde_json::json!({ "team": "payments", "source": "roster" }),
+        )
+        .await;
+
+        let kept = add_node(&store, NodeKind::File, "src/kept.rs").await;
+        let drifted = add_node(&store, NodeKind::File, "src/drifted.rs").await;
+        let by_team = add_node(&store, NodeKind::File, "src/billing.rs").await;
+        let unknown_team = add_node(&store, NodeKind::File, "src/infra.rs").await;
+        add_owns(&store, &["@alice"], kept).await;
+        add_owns(&store, &["@alice", "carol@example.com"], drifted).await;
+        add_owns(&store, &["@acme/payments"], by_team).await;
+        add_owns(&store, &["@acme/infra"], unknown_team).await;
+
+        add_commit(&store, "c1", alice, kept).await;
+        add_commit(&store, "c2", bob, drifted).await;
+        add_commit(&store, "c3", bob, by_team).await;
+        add_commit(&store, "c4", alice, unknown_team).await;
+        for file in [kept, drifted, by_team, unknown_team] {
+            store_result(
+                &store,
+                file,
+                AnalysisKind::ContributorConcentration,
+                serde_json::json!({ "total_commits": 1, "bus_factor": 1 }),
+            )
+            .await;
+        }
+
+        let stats = OwnershipDriftAnalyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.results_stored, 1, "only src/drifted.rs drifts");
+
+        let drift = store
+            .get_analysis(drifted, AnalysisKind::OwnershipDrift)
+            .await
+            .unwrap()
+            .expect("drift result");
+        assert_eq!(drift.data["owners"][1], "carol@example.com");
+        assert_eq!(drift.data["top_authors"][0]["email"], "bob@example.com");
+        assert!(!OwnershipDriftAnalyzer.needs_rerun(&store).await.unwrap());
+    }
+}
@@ -0,0 +1,720 @@
+// Code survival analysis: line-age distributions, half-life, and blame
+// summaries from replaying the diff hunks recorded on `Modifies` edges.
+
+// Statistical computations intentionally cast int→float.
+#![allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument};
+
+use crate::config::{HomerConfig, SurvivalConfig};
+use crate::contracts;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, DiffHunk, HyperedgeKind, NodeFilter, NodeId,
+    NodeKind,
+};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Authors listed in a file's or module's blame summary.
+const MAX_BLAME_AUTHORS: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct SurvivalAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for SurvivalAnalyzer {
+    fn name(&self) -> &'static str {
+        "survival"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::CodeSurvival]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = survival_state(store).await?;
+        incremental::needs_extraction(store, "analyze:survival", &state).await
+    }
+
+    #[instrument(skip_all, name = "survival_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+
+        // Files, modules and contributors come and go; recompute all.
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::CodeSurvival])
+            .await?;
+
+        let changes = load_changes(store).await?;
+        if changes.is_empty() {
+            info!("No diff hunks found, skipping survival analysis");
+            stats.duration = start.elapsed();
+            return Ok(stats);
+        }
+
+        let history = replay(&changes, now);
+        let thresholds = &config.analysis.survival;
+        let emails = load_emails(store).await?;
+
+        // Files and their containing modules
+        let mut modules: HashMap<String, LineStats> = HashMap::new();
+        for (path, file_stats) in &history.files {
+            for dir in Path::new(path).ancestors().skip(1) {
+                modules
+                    .entry(dir.to_string_lossy().into_owned())
+                    .or_default()
+                    .merge(file_stats);
+            }
+            if file_stats.alive() == 0 {
+                continue;
+            }
+            if let Some(node) = store.get_node_by_name(NodeKind::File, path).await? {
+                store_survival(store, node.id, file_stats, &emails, thresholds, now).await?;
+                stats.results_stored += 1;
+            }
+        }
+
+        let root_module = contracts::find_root_module_id(store).await?;
+        for (dir, module_stats) in &modules {
+            let node_id = if dir.is_empty() {
+                root_module
+            } else {
+                store
+                    .get_node_by_name(NodeKind::Module, dir)
+                    .await?
+                    .map(|n| n.id)
+            };
+            if let Some(id) = node_id.filter(|_| module_stats.alive() > 0) {
+                store_survival(store, id, module_stats, &emails, thresholds, now).await?;
+                stats.results_stored += 1;
+            }
+        }
+
+        for (author, author_stats) in &history.contributors {
+            store_survival(store, *author, author_stats, &emails, thresholds, now).await?;
+            stats.results_stored += 1;
+        }
+
+        stats.results_stored += store_function_ages(store, &history, thresholds, now).await?;
+
+        let state = survival_state(store).await?;
+        store.set_checkpoint("analyze:survival", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "Survival analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Rerun whenever new commits have been extracted.
+async fn survival_state(store: &dyn HomerStore) -> crate::error::Result<String> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Commit),
+        ..Default::default()
+    };
+    Ok(store.find_nodes(&filter).await?.len().to_string())
+}
+
+// ── Replay ────────────────────────────────────────────────────────
+
+/// One file diff from a commit, in the form the replay needs.
+struct FileChange {
+    at: DateTime<Utc>,
+    author: Option<NodeId>,
+    path: String,
+    old_path: Option<String>,
+    deleted: bool,
+    hunks: Vec<DiffHunk>,
+}
+
+/// Who wrote a line and when. Lines that predate the extracted history
+/// have no known origin and are excluded from the statistics.
+#[derive(Debug, Clone, Copy)]
+struct LineOrigin {
+    born: Option<DateTime<Utc>>,
+    author: Option<NodeId>,
+}
+
+const UNKNOWN: LineOrigin = LineOrigin {
+    born: None,
+    author: None,
+};
+
+/// Accumulated survival observations for one entity.
+#[derive(Debug, Default, Clone)]
+struct LineStats {
+    /// Ages in days of surviving lines with a known origin.
+    ages: Vec<f64>,
+    /// Lines written within the observed history.
+    written: u64,
+    /// Lines later replaced or deleted.
+    rewritten: u64,
+    /// Total line-days observed, for rewritten and surviving lines.
+    exposure_days: f64,
+    /// Surviving lines per author (files and modules only).
+    authors: HashMap<NodeId, u64>,
+}
+
+impl LineStats {
+    fn alive(&self) -> usize {
+        self.ages.len()
+    }
+
+    fn merge(&mut self, other: &LineStats) {
+        self.ages.extend_from_slice(&other.ages);
+        self.written += other.written;
+        self.rewritten += other.rewritten;
+        self.exposure_days += other.exposure_days;
+        for (author, lines) in &other.authors {
+            *self.authors.entry(*author).or_default() += lines;
+        }
+    }
+
+    /// Exponential survival estimate: ln 2 · exposure / rewrites.
+    fn half_life_days(&self) -> Option<f64> {
+        (self.rewritten > 0)
+            .then(|| std::f64::consts::LN_2 * self.exposure_days / self.rewritten as f64)
+    }
+}
+
+struct History {
+    /// Current line origins per file path.
+    lines: HashMap<String, Vec<LineOrigin>>,
+    files: HashMap<String, LineStats>,
+    contributors: HashMap<NodeId, LineStats>,
+}
+
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds().max(0) as f64 / 86_400.0
+}
+
+/// Record that `change` replaced or deleted a line.
+fn record_rewrite(
+    files: &mut HashMap<String, LineStats>,
+    contributors: &mut HashMap<NodeId, LineStats>,
+    change: &FileChange,
+    dead: LineOrigin,
+) {
+    let Some(born) = dead.born else { return };
+    let lifetime = days_between(born, change.at);
+    let file = files.entry(change.path.clone()).or_default();
+    file.rewritten += 1;
+    file.exposure_days += lifetime;
+    if let Some(author) = dead.author {
+        let contributor = contributors.entry(author).or_default();
+        contributor.rewritten += 1;
+        contributor.exposure_days += lifetime;
+    }
+}
+
+/// Apply every change in order and summarize the surviving lines at `now`.
+/// Lines replaced by a later hunk count as rewritten.
+fn replay(changes: &[FileChange], now: DateTime<Utc>) -> History {
+    let mut lines: HashMap<String, Vec<LineOrigin>> = HashMap::new();
+    let mut files: HashMap<String, LineStats> = HashMap::new();
+    let mut contributors: HashMap<NodeId, LineStats> = HashMap::new();
+
+    for change in changes {
+        let mut state = change
+            .old_path
+            .as_ref()
+            .filter(|old| **old != change.path)
+            .and_then(|old| lines.remove(old))
+            .or_else(|| lines.remove(&change.path))
+            .unwrap_or_default();
+
+        let origin = LineOrigin {
+            born: Some(change.at),
+            author: change.author,
+        };
+        // Apply bottom-up so earlier hunks keep their old-file positions.
+        let mut hunks: Vec<&DiffHunk> = change.hunks.iter().collect();
+        hunks.sort_by_key(|h| std::cmp::Reverse(h.old_start));
+        for hunk in hunks {
+            let start = hunk.old_start.saturating_sub(1) as usize;
+            let end = start + hunk.old_lines as usize;
+            // History cut off by `max_commits`: the lines are unknown.
+            if state.len() < end {
+                state.resize(end, UNKNOWN);
+            }
+            for dead in state.drain(start..end) {
+                record_rewrite(&mut files, &mut contributors, change, dead);
+            }
+            let added = std::iter::repeat_n(origin, hunk.new_lines as usize);
+            state.splice(start..start, added);
+
+            let written = u64::from(hunk.new_lines);
+            files.entry(change.path.clone()).or_default().written += written;
+            if let Some(author) = change.author {
+                contributors.entry(author).or_default().written += written;
+            }
+        }
+
+        if change.deleted {
+            for dead in state.drain(..) {
+                record_rewrite(&mut files, &mut contributors, change, dead);
+            }
+        } else {
+            lines.insert(change.path.clone(), state);
+        }
+    }
+
+    // Surviving lines contribute their age and their exposure so far.
+    for (path, state) in &lines {
+        for line in state {
+            let Some(born) = line.born else { continue };
+            let age = days_between(born, now);
+            let file = files.entry(path.clone()).or_default();
+            file.ages.push(age);
+            file.exposure_days += age;
+            if let Some(author) = line.author {
+                *file.authors.entry(author).or_default() += 1;
+                let contributor = contributors.entry(author).or_default();
+                contributor.ages.push(age);
+                contributor.exposure_days += age;
+            }
+        }
+    }
+
+    History {
+        lines,
+        files,
+        contributors,
+    }
+}
+
+/// Contributor node → email.
+async fn load_emails(store: &dyn HomerStore) -> crate::error::Result<HashMap<NodeId, String>> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Contributor),
+        ..Default::default()
+    };
+    Ok(store
+        .find_nodes(&filter)
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect())
+}
+
+async fn load_changes(store: &dyn HomerStore) -> crate::error::Result<Vec<FileChange>> {
+    let authored = store.get_edges_by_kind(HyperedgeKind::Authored).await?;
+    let commit_author: HashMap<NodeId, NodeId> = authored
+        .iter()
+        .filter_map(|edge| {
+            let author = edge.members.iter().find(|m| m.role == "author")?;
+            let commit = edge.members.iter().find(|m| m.role == "commit")?;
+            Some((commit.node_id, author.node_id))
+        })
+        .collect();
+
+    let mut modifies = store.get_edges_by_kind(HyperedgeKind::Modifies).await?;
+    modifies.sort_by_key(|e| (e.last_updated, e.id.0));
+
+    let mut changes = Vec::new();
+    for edge in &modifies {
+        // Merge diffs repeat changes already replayed from the branch.
+        if edge
+            .metadata
+            .get("merge")
+            .and_then(serde_json::Value::as_bool)
+            == Some(true)
+        {
+            continue;
+        }
+        let author = edge
+            .members
+            .iter()
+            .find(|m| m.role == "commit")
+            .and_then(|m| commit_author.get(&m.node_id).copied());
+        let Some(files) = edge.metadata.get("files").and_then(|v| v.as_array()) else {
+            continue;
+        };
+
+        for file in files {
+            let Some(path) = file.get("path").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            // Edges extracted before hunks were recorded cannot be replayed.
+            let Some(hunks) = file
+                .get("hunks")
+                .and_then(|h| serde_json::from_value::<Vec<DiffHunk>>(h.clone()).ok())
+            else {
+                continue;
+            };
+            changes.push(FileChange {
+                at: edge.last_updated,
+                author,
+                path: path.to_string(),
+                old_path: file
+                    .get("old_path")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                deleted: file.get("status").and_then(|v| v.as_str()) == Some("Deleted"),
+                hunks,
+            });
+        }
+    }
+    Ok(changes)
+}
+
+// ── Results ───────────────────────────────────────────────────────
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        f64::midpoint(values[mid - 1], values[mid])
+    } else {
+        values[mid]
+    }
+}
+
+fn age_distribution(ages: &[f64]) -> serde_json::Value {
+    let count = |lo: f64, hi: f64| ages.iter().filter(|&&a| a >= lo && a < hi).count();
+    serde_json::json!({
+        "under_30d": count(0.0, 30.0),
+        "30d_to_90d": count(30.0, 90.0),
+        "90d_to_1y": count(90.0, 365.0),
+        "over_1y": count(365.0, f64::INFINITY),
+    })
+}
+
+/// Settled code is old and rarely rewritten; churning code is rewritten
+/// soon after it is written, whatever its commit count.
+fn classify(median_age: f64, half_life: Option<f64>, thresholds: &SurvivalConfig) -> &'static str {
+    match half_life {
+        Some(h) if h < thresholds.churning_max_half_life_days => "Churning",
+        _ if median_age >= thresholds.settled_min_age_days => "Settled",
+        _ => "Evolving",
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Authors of the most surviving lines, largest first.
+fn blame(authors: &HashMap<NodeId, u64>, emails: &HashMap<NodeId, String>) -> serde_json::Value {
+    let mut ranked: Vec<(&str, u64)> = authors
+        .iter()
+        .filter_map(|(id, lines)| Some((emails.get(id)?.as_str(), *lines)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    ranked
+        .into_iter()
+        .take(MAX_BLAME_AUTHORS)
+        .map(|(email, lines)| serde_json::json!({ "contributor": email, "lines": lines }))
+        .collect()
+}
+
+async fn store_survival(
+    store: &dyn HomerStore,
+    node_id: NodeId,
+    line_stats: &LineStats,
+    emails: &HashMap<NodeId, String>,
+    thresholds: &SurvivalConfig,
+    now: DateTime<Utc>,
+) -> crate::error::Result<()> {
+    let mut ages = line_stats.ages.clone();
+    let median_age = median(&mut ages);
+    let half_life = line_stats.half_life_days();
+    let rewrite_ratio = if line_stats.written == 0 {
+        0.0
+    } else {
+        line_stats.rewritten as f64 / line_stats.written as f64
+    };
+
+    let mut data = serde_json::json!({
+        "lines": line_stats.alive(),
+        "median_age_days": round2(median_age),
+        "age_distribution": age_distribution(&ages),
+        "lines_written": line_stats.written,
+        "lines_rewritten": line_stats.rewritten,
+        "rewrite_ratio": round2(rewrite_ratio),
+        "half_life_days": half_life.map(round2),
+        "classification": classify(median_age, half_life, thresholds),
+    });
+    if !line_stats.authors.is_empty() {
+        data["blame"] = blame(&line_stats.authors, emails);
+    }
+
+    store
+        .store_analysis(&AnalysisResult {
+            id: AnalysisResultId(0),
+            node_id,
+            kind: AnalysisKind::CodeSurvival,
+            data,
+            input_hash: 0,
+            computed_at: now,
+        })
+        .await?;
+    Ok(())
+}
+
+/// Age of the lines inside each function's current span.
+async fn store_function_ages(
+    store: &dyn HomerStore,
+    history: &History,
+    thresholds: &SurvivalConfig,
+    now: DateTime<Utc>,
+) -> crate::error::Result<u64> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Function),
+        ..Default::default()
+    };
+    let mut count = 0;
+    for func in store.find_nodes(&filter).await? {
+        let Some(lines) = func
+            .metadata
+            .get("file")
+            .and_then(|v| v.as_str())
+            .and_then(|file| history.lines.get(file))
+        else {
+            continue;
+        };
+        let span = func.metadata.get("span");
+        let row = |key: &str| {
+            span.and_then(|s| s.get(key))
+                .and_then(serde_json::Value::as_u64)
+                .map(|r| r as usize)
+        };
+        let (Some(first), Some(last)) = (row("start_row"), row("end_row")) else {
+            continue;
+        };
+
+        let mut ages: Vec<f64> = lines
+            .iter()
+            .skip(first)
+            .take(last.saturating_sub(first) + 1)
+            .filter_map(|l| l.born.map(|b| days_between(b, now)))
+            .collect();
+        if ages.is_empty() {
+            continue;
+        }
+        let median_age = median(&mut ages);
+
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: func.id,
+                kind: AnalysisKind::CodeSurvival,
+                data: serde_json::json!({
+                    "lines": ages.len(),
+                    "median_age_days": round2(median_age),
+                    "age_distribution": age_distribution(&ages),
+                    "classification": classify(median_age, None, thresholds),
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn hunk(old_start: u32, old_lines: u32, new_start: u32, new_lines: u32) -> DiffHunk {
+        DiffHunk {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+        }
+    }
+
+    fn change(days_ago: i64, author: i64, path: &str, hunks: Vec<DiffHunk>) -> FileChange {
+        FileChange {
+            at: Utc::now() - Duration::days(days_ago),
+            author: Some(NodeId(author)),
+            path: path.to_string(),
+            old_path: None,
+            deleted: false,
+            hunks,
+        }
+    }
+
+    #[test]
+    fn separates_settled_from_rewritten_lines() {
+        let changes = vec![
+            change(400, 1, "settled.rs", vec![hunk(0, 0, 1, 10)]),
+            change(40, 1, "churn.rs", vec![hunk(0, 0, 1, 10)]),
+            // churn.rs is rewritten wholesale every 10 days.
+            change(30, 2, "churn.rs", vec![hunk(1, 10, 1, 10)]),
+            change(20, 2, "churn.rs", vec![hunk(1, 10, 1, 10)]),
+            change(10, 2, "churn.rs", vec![hunk(1, 10, 1, 10)]),
+            // A pure insertion keeps every existing line.
+            change(5, 2, "settled.rs", vec![hunk(11, 0, 11, 1)]),
+        ];
+        let history = replay(&changes, Utc::now());
+        let thresholds = SurvivalConfig::default();
+
+        let settled = &history.files["settled.rs"];
+        assert_eq!(settled.alive(), 11);
+        assert_eq!(settled.rewritten, 0);
+        assert!(settled.half_life_days().is_none());
+        let mut ages = settled.ages.clone();
+        assert_eq!(classify(median(&mut ages), None, &thresholds), "Settled");
+
+        let churn = &history.files["churn.rs"];
+        assert_eq!(churn.alive(), 10);
+        assert_eq!(churn.written, 40);
+        assert_eq!(churn.rewritten, 30);
+        let half_life = churn.half_life_days().unwrap();
+        let mut ages = churn.ages.clone();
+        assert_eq!(
+            classify(median(&mut ages), Some(half_life), &thresholds),
+            "Churning"
+        );
+
+        // Author 1's lines mostly survive; author 2's are rewritten in days.
+        let original = history.contributors[&NodeId(1)].half_life_days().unwrap();
+        let rewriter = history.contributors[&NodeId(2)].half_life_days().unwrap();
+        assert!(original > rewriter);
+    }
+
+    #[test]
+    fn renames_carry_line_ages_and_deletes_rewrite_everything() {
+        let mut renamed = change(10, 1, "new.rs", vec![]);
+        renamed.old_path = Some("old.rs".to_string());
+        let mut deleted = change(5, 1, "gone.rs", vec![hunk(1, 3, 0, 0)]);
+        deleted.deleted = true;
+
+        let changes = vec![
+            change(100, 1, "old.rs", vec![hunk(0, 0, 1, 4)]),
+            renamed,
+            change(50, 1, "gone.rs", vec![hunk(0, 0, 1, 3)]),
+            deleted,
+        ];
+        let history = replay(&changes, Utc::now());
+
+        assert!(!history.lines.contains_key("old.rs"));
+        let ages = &history.files["new.rs"].ages;
+        assert_eq!(ages.len(), 4);
+        assert!(ages.iter().all(|&a| a > 99.0));
+
+        assert!(!history.lines.contains_key("gone.rs"));
+        assert_eq!(history.files["gone.rs"].rewritten, 3);
+        assert_eq!(history.files["gone.rs"].alive(), 0);
+    }
+
+    #[test]
+    fn truncated_history_lines_are_unknown() {
+        // First observed change edits line 3 of a file we never saw created.
+        let changes = vec![change(10, 1, "a.rs", vec![hunk(3, 1, 3, 2)])];
+        let history = replay(&changes, Utc::now());
+
+        assert_eq!(history.lines["a.rs"].len(), 4);
+        let stats = &history.files["a.rs"];
+        assert_eq!(stats.alive(), 2, "only the two new lines have an origin");
+        assert_eq!(stats.rewritten, 0, "unknown lines are not observations");
+    }
+
+    #[tokio::test]
+    async fn analyzes_extracted_git_history() {
+        use crate::extract::traits::Extractor;
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .env("GIT_AUTHOR_NAME", "Test")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "Test")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com")
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "--initial-branch=main"]);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "fn a() {}\nfn b() {}\nfn c() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "add lib"]);
+        std::fs::write(dir.join("src/lib.rs"), "fn a() {}\nfn b2() {}\nfn c() {}\n").unwrap();
+        git(&["commit", "-q", "-am", "rewrite b"]);
+
+        let store = SqliteStore::in_memory().unwrap();
+        let config = HomerConfig::default();
+        crate::extract::git::GitExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+        crate::extract::structure::StructureExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+
+        SurvivalAnalyzer.analyze(&store, &config).await.unwrap();
+
+        let file = store
+            .get_node_by_name(NodeKind::File, "src/lib.rs")
+            .await
+            .unwrap()
+            .unwrap();
+        let survival = store
+            .get_analysis(file.id, AnalysisKind::CodeSurvival)
+            .await
+            .unwrap()
+            .expect("file survival");
+        assert_eq!(survival.data["lines"], 3);
+        assert_eq!(survival.data["lines_written"], 4);
+        assert_eq!(survival.data["lines_rewritten"], 1);
+        assert_eq!(survival.data["blame"][0]["contributor"], "test@example.com");
+        assert_eq!(survival.data["blame"][0]["lines"], 3);
+
+        let module = store
+            .get_node_by_name(NodeKind::Module, "src")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            store
+                .get_analysis(module.id, AnalysisKind::CodeSurvival)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        let author = store
+            .get_node_by_name(NodeKind::Contributor, "test@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        let by_author = store
+            .get_analysis(author.id, AnalysisKind::CodeSurvival)
+            .await
+            .unwrap()
+            .expect("contributor survival");
+        assert_eq!(by_author.data["lines"], 3);
+    }
+}
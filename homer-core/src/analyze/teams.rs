@@ -0,0 +1,699 @@
+// Team alignment analysis: Conway's law, measured. Structural communities
+// are matched to the teams committing to them, and commits crossing team
+// boundaries are reported as coordination hotspots.
+
+// Statistical computations intentionally cast int→float.
+#![allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use tracing::{info, instrument};
+
+use crate::config::{HomerConfig, TeamsConfig};
+use crate::contracts::{self, CommitChange};
+use crate::extract::codeowners::{self, Codeowners};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{AnalysisKind, AnalysisResult, AnalysisResultId, NodeFilter, NodeId, NodeKind};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Commits touching more files than this (bulk renames, reformatting,
+/// vendoring) couple everything to everything and are ignored.
+const MAX_COMMIT_FILES: usize = 100;
+
+/// Number of cross-team hotspots kept.
+const MAX_HOTSPOTS: usize = 20;
+
+#[derive(Debug)]
+pub struct TeamAlignmentAnalyzer {
+    repo_path: PathBuf,
+}
+
+impl TeamAlignmentAnalyzer {
+    pub fn new(repo_path: &Path) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+        }
+    }
+
+    fn codeowners(&self, config: &TeamsConfig) -> Option<Codeowners> {
+        if !config.use_codeowners {
+            return None;
+        }
+        Codeowners::discover(&self.repo_path).map(|(_, owners)| owners)
+    }
+}
+
+#[async_trait::async_trait]
+impl Analyzer for TeamAlignmentAnalyzer {
+    fn name(&self) -> &'static str {
+        "team_alignment"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::TeamAssignment, AnalysisKind::TeamAlignment]
+    }
+
+    fn requires(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::CommunityAssignment]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        // The roster is not visible here; roster edits take effect on the
+        // next commit or with `--force`.
+        let state = alignment_state(store, &self.repo_path).await?;
+        incremental::needs_extraction(store, "analyze:team_alignment", &state).await
+    }
+
+    #[instrument(skip_all, name = "team_alignment_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+        let teams_config = &config.analysis.teams;
+
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::TeamAssignment, AnalysisKind::TeamAlignment])
+            .await?;
+
+        let owners = self.codeowners(teams_config);
+        if teams_config.roster.is_empty() && owners.is_none() {
+            info!("No team roster or CODEOWNERS, skipping team alignment");
+            stats.duration = start.elapsed();
+            return Ok(stats);
+        }
+
+        let history = load_history(store).await?;
+        let file_teams = owners
+            .as_ref()
+            .map(|o| codeowners_teams(o, &history.paths))
+            .unwrap_or_default();
+        let assignments = assign_teams(&history, &teams_config.roster, &file_teams);
+
+        for (author, assignment) in &assignments {
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: *author,
+                    kind: AnalysisKind::TeamAssignment,
+                    data: serde_json::json!({
+                        "team": assignment.team,
+                        "source": assignment.source,
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let communities = load_communities(store).await?;
+        if let Some(root) = contracts::find_root_module_id(store).await? {
+            let alignment = compute_alignment(
+                &history,
+                &assignments,
+                &communities,
+                teams_config.min_co_changes,
+            );
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: root,
+                    kind: AnalysisKind::TeamAlignment,
+                    data: alignment,
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let state = alignment_state(store, &self.repo_path).await?;
+        store
+            .set_checkpoint("analyze:team_alignment", &state)
+            .await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            assigned = assignments.len(),
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "Team alignment analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Rerun when commits, communities, or the CODEOWNERS file change.
+async fn alignment_state(store: &dyn HomerStore, repo_path: &Path) -> crate::error::Result<String> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Commit),
+        ..Default::default()
+    };
+    let commits = store.find_nodes(&filter).await?.len();
+    let communities = store
+        .get_analyses_by_kind(AnalysisKind::CommunityAssignment)
+        .await?
+        .len();
+    Ok(format!(
+        "{commits}:{communities}:{:x}",
+        codeowners::fingerprint(repo_path)
+    ))
+}
+
+// ── Data loading ──────────────────────────────────────────────────
+
+struct History {
+    /// Contributor node → email.
+    emails: HashMap<NodeId, String>,
+    /// File node → repo-relative path.
+    paths: HashMap<NodeId, String>,
+    /// Author and touched files of each non-merge commit.
+    commits: Vec<CommitChange>,
+}
+
+async fn load_history(store: &dyn HomerStore) -> crate::error::Result<History> {
+    let contributors = NodeFilter {
+        kind: Some(NodeKind::Contributor),
+        ..Default::default()
+    };
+    let emails = store
+        .find_nodes(&contributors)
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect();
+    let files = NodeFilter {
+        kind: Some(NodeKind::File),
+        ..Default::default()
+    };
+    let paths = store
+        .find_nodes(&files)
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect();
+
+    Ok(History {
+        emails,
+        paths,
+        commits: contracts::load_commit_changes(store).await?,
+    })
+}
+
+/// File node → community ID.
+async fn load_communities(store: &dyn HomerStore) -> crate::error::Result<HashMap<NodeId, u64>> {
+    Ok(store
+        .get_analyses_by_kind(AnalysisKind::CommunityAssignment)
+        .await?
+        .into_iter()
+        .filter_map(|r| {
+            let id = r
+                .data
+                .get("community_id")
+                .and_then(serde_json::Value::as_u64)?;
+            Some((r.node_id, id))
+        })
+        .collect())
+}
+
+// ── Team assignment ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TeamAssignment {
+    team: String,
+    source: &'static str,
+}
+
+/// The owning team of each file according to CODEOWNERS: its first team
+/// owner, or its first individual owner when no team is listed.
+fn codeowners_teams(
+    owners: &Codeowners,
+    paths: &HashMap<NodeId, String>,
+) -> HashMap<NodeId, String> {
+    paths
+        .iter()
+        .filter_map(|(id, path)| {
+            let listed = owners.owners_for(path);
+            let team = listed
+                .iter()
+                .find(|o| codeowners::is_team(o))
+                .or_else(|| listed.first())?;
+            Some((*id, (*team).to_string()))
+        })
+        .collect()
+}
+
+fn roster_team(roster: &BTreeMap<String, Vec<String>>, email: &str) -> Option<String> {
+    let email = email.to_lowercase();
+    roster
+        .iter()
+        .find(|(_, members)| {
+            members.iter().any(|m| {
+                let m = m.to_lowercase();
+                m == email || glob::Pattern::new(&m).is_ok_and(|p| p.matches(&email))
+            })
+        })
+        .map(|(team, _)| team.clone())
+}
+
+/// Roster membership wins; everyone else joins the CODEOWNERS team whose
+/// files they committed to most.
+fn assign_teams(
+    history: &History,
+    roster: &BTreeMap<String, Vec<String>>,
+    file_teams: &HashMap<NodeId, String>,
+) -> HashMap<NodeId, TeamAssignment> {
+    let mut assignments: HashMap<NodeId, TeamAssignment> = history
+        .emails
+        .iter()
+        .filter_map(|(id, email)| {
+            let team = roster_team(roster, email)?;
+            Some((
+                *id,
+                TeamAssignment {
+                    team,
+                    source: "roster",
+                },
+            ))
+        })
+        .collect();
+
+    let mut owned_commits: HashMap<NodeId, BTreeMap<&str, u64>> = HashMap::new();
+    for change in &history.commits {
+        let Some(author) = change.author.filter(|a| !assignments.contains_key(a)) else {
+            continue;
+        };
+        for file in &change.files {
+            if let Some(team) = file_teams.get(file) {
+                *owned_commits
+                    .entry(author)
+                    .or_default()
+                    .entry(team.as_str())
+                    .or_default() += 1;
+            }
+        }
+    }
+    for (author, counts) in owned_commits {
+        // BTreeMap order breaks ties by team name.
+        let best = counts
+            .iter()
+            .fold(None::<(&str, u64)>, |best, (team, count)| match best {
+                Some((_, c)) if c >= *count => best,
+                _ => Some((team, *count)),
+            });
+        if let Some((team, _)) = best {
+            assignments.insert(
+                author,
+                TeamAssignment {
+                    team: team.to_string(),
+                    source: "codeowners",
+                },
+            );
+        }
+    }
+    assignments
+}
+
+// ── Alignment ─────────────────────────────────────────────────────
+
+/// A structural community (from `CommunityAssignment`), owned by the team
+/// with the most commits to its files.
+#[derive(Debug, Default)]
+struct Community {
+    files: HashSet<NodeId>,
+    /// Commits touching the community.
+    commits: u64,
+    /// Team → commits by its members to the community's files.
+    team_commits: BTreeMap<String, u64>,
+}
+
+impl Community {
+    /// The team with the most commits, and its share of attributed commits.
+    fn owner(&self) -> Option<(&str, f64)> {
+        let total: u64 = self.team_commits.values().sum();
+        let (team, count) = self.team_commits.iter().fold(
+            None::<(&String, u64)>,
+            |best, (team, count)| match best {
+                Some((_, c)) if c >= *count => best,
+                _ => Some((team, *count)),
+            },
+        )?;
+        Some((team.as_str(), count as f64 / total.max(1) as f64))
+    }
+}
+
+/// The most common parent directory of a community's files.
+fn community_label(files: &HashSet<NodeId>, paths: &HashMap<NodeId, String>) -> String {
+    let mut dirs: BTreeMap<String, usize> = BTreeMap::new();
+    for file in files {
+        if let Some(path) = paths.get(file) {
+            let dir = Path::new(path)
+                .parent()
+                .map(|p| p.to_string_lossy().into_owned())
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| ".".to_string());
+            *dirs.entry(dir).or_default() += 1;
+        }
+    }
+    dirs.into_iter()
+        .fold(None::<(String, usize)>, |best, (dir, count)| match best {
+            Some((_, c)) if c >= count => best,
+            _ => Some((dir, count)),
+        })
+        .map_or_else(String::new, |(dir, _)| dir)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Commits per community, per team within each community, and per pair of
+/// communities changed together.
+fn tally_communities(
+    history: &History,
+    assignments: &HashMap<NodeId, TeamAssignment>,
+    file_community: &HashMap<NodeId, u64>,
+) -> (BTreeMap<u64, Community>, BTreeMap<(u64, u64), u64>) {
+    let mut communities: BTreeMap<u64, Community> = BTreeMap::new();
+    for (file, id) in file_community {
+        communities.entry(*id).or_default().files.insert(*file);
+    }
+
+    let mut pair_commits: BTreeMap<(u64, u64), u64> = BTreeMap::new();
+    for change in &history.commits {
+        if change.files.len() > MAX_COMMIT_FILES {
+            continue;
+        }
+        let team = change
+            .author
+            .and_then(|a| assignments.get(&a))
+            .map(|a| &a.team);
+        let mut touched: Vec<u64> = Vec::new();
+        for file in &change.files {
+            let Some(id) = file_community.get(file) else {
+                continue;
+            };
+            let community = communities.entry(*id).or_default();
+            if let Some(team) = team {
+                *community.team_commits.entry(team.clone()).or_default() += 1;
+            }
+            if !touched.contains(id) {
+                touched.push(*id);
+            }
+        }
+        touched.sort_unstable();
+        for id in &touched {
+            communities.entry(*id).or_default().commits += 1;
+        }
+        for (i, a) in touched.iter().enumerate() {
+            for b in &touched[i + 1..] {
+                *pair_commits.entry((*a, *b)).or_default() += 1;
+            }
+        }
+    }
+    (communities, pair_commits)
+}
+
+/// Commits touching two communities owned by different teams are cross-team
+/// coordination cost; the pairs where that happens most are hotspots.
+fn compute_alignment(
+    history: &History,
+    assignments: &HashMap<NodeId, TeamAssignment>,
+    file_community: &HashMap<NodeId, u64>,
+    min_co_changes: u32,
+) -> serde_json::Value {
+    let (communities, pair_commits) = tally_communities(history, assignments, file_community);
+
+    let label = |id: u64| community_label(&communities[&id].files, &history.paths);
+    let owner = |id: u64| communities[&id].owner().map(|(team, _)| team.to_string());
+
+    // Alignment: share of cross-community co-change that stays within one team.
+    let mut same_team = 0u64;
+    let mut attributed = 0u64;
+    let mut hotspots = Vec::new();
+    for (&(a, b), &co_changes) in &pair_commits {
+        let (Some(team_a), Some(team_b)) = (owner(a), owner(b)) else {
+            continue;
+        };
+        attributed += co_changes;
+        if team_a == team_b {
+            same_team += co_changes;
+            continue;
+        }
+        if co_changes < u64::from(min_co_changes) {
+            continue;
+        }
+        let smaller = communities[&a].commits.min(communities[&b].commits).max(1);
+        hotspots.push(serde_json::json!({
+            "community_a": a,
+            "label_a": label(a),
+            "team_a": team_a,
+            "community_b": b,
+            "label_b": label(b),
+            "team_b": team_b,
+            "co_changes": co_changes,
+            "coupling": round2(co_changes as f64 / smaller as f64),
+        }));
+    }
+    hotspots.sort_by(|x, y| {
+        let count = |v: &serde_json::Value| v["co_changes"].as_u64().unwrap_or(0);
+        count(y).cmp(&count(x))
+    });
+    hotspots.truncate(MAX_HOTSPOTS);
+
+    let mut teams: BTreeMap<&str, (usize, Vec<u64>)> = BTreeMap::new();
+    for assignment in assignments.values() {
+        teams.entry(assignment.team.as_str()).or_default().0 += 1;
+    }
+    let community_rows: Vec<serde_json::Value> = communities
+        .iter()
+        .map(|(id, community)| {
+            let majority = community.owner();
+            if let Some((team, _)) = majority {
+                teams.entry(team).or_default().1.push(*id);
+            }
+            serde_json::json!({
+                "community_id": id,
+                "label": label(*id),
+                "files": community.files.len(),
+                "owner_team": majority.map(|(team, _)| team),
+                "owner_share": majority.map(|(_, share)| round2(share)),
+                "teams": community.team_commits,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "alignment_score": (attributed > 0).then(|| round2(same_team as f64 / attributed as f64)),
+        "teams": teams
+            .iter()
+            .map(|(team, (members, owned))| serde_json::json!({
+                "team": team,
+                "members": members,
+                "communities": owned,
+            }))
+            .collect::<Vec<_>>(),
+        "communities": community_rows,
+        "cross_team_coupling": hotspots,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node};
+
+    async fn add_node(store: &SqliteStore, kind: NodeKind, name: &str) -> NodeId {
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap()
+    }
+
+    fn member(node_id: NodeId, role: &str, position: u32) -> HyperedgeMember {
+        HyperedgeMember {
+            node_id,
+            role: role.to_string(),
+            position,
+        }
+    }
+
+    async fn add_commit(store: &SqliteStore, sha: &str, author: NodeId, files: &[NodeId]) {
+        let commit = add_node(store, NodeKind::Commit, sha).await;
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Authored,
+                members: vec![member(author, "author", 0), member(commit, "commit", 1)],
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let mut members = vec![member(commit, "commit", 0)];
+        for (file, position) in files.iter().zip(1..) {
+            members.push(member(*file, "file", position));
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Modifies,
+                members,
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn set_community(store: &SqliteStore, file: NodeId, community: u64) {
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: file,
+                kind: AnalysisKind::CommunityAssignment,
+                data: serde_json::json!({ "community_id": community }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn roster_matches_emails_and_globs() {
+        let roster: BTreeMap<String, Vec<String>> = [
+            (
+                "payments".to_string(),
+                vec!["Alice@Example.com".to_string()],
+            ),
+            (
+                "platform".to_string(),
+                vec!["*@infra.example.com".to_string()],
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            roster_team(&roster, "alice@example.com").as_deref(),
+            Some("payments")
+        );
+        assert_eq!(
+            roster_team(&roster, "ops@infra.example.com").as_deref(),
+            Some("platform")
+        );
+        assert_eq!(roster_team(&roster, "bob@example.com"), None);
+    }
+
+    #[tokio::test]
+    async fn reports_cross_team_coupling_between_communities() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("CODEOWNERS"),
+            "/billing/ @acme/payments
+",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        let root = add_node(&store, NodeKind::Module, ".").await;
+        let invoice = add_node(&store, NodeKind::File, "billing/invoice.rs").await;
+        let tax = add_node(&store, NodeKind::File, "billing/tax.rs").await;
+        let login = add_node(&store, NodeKind::File, "auth/login.rs").await;
+        let session = add_node(&store, NodeKind::File, "auth/session.rs").await;
+        set_community(&store, invoice, 0).await;
+        set_community(&store, tax, 0).await;
+        set_community(&store, login, 1).await;
+        set_community(&store, session, 1).await;
+
+        let alice = add_node(&store, NodeKind::Contributor, "alice@example.com").await;
+        let bob = add_node(&store, NodeKind::Contributor, "bob@example.com").await;
+
+        // Bob is in the roster; Alice is placed by CODEOWNERS.
+        for i in 0..3 {
+            add_commit(&store, &format!("a{i}"), alice, &[invoice, tax]).await;
+            add_commit(&store, &format!("b{i}"), bob, &[login, session]).await;
+        }
+        // Every auth change drags billing along.
+        for i in 0..4 {
+            add_commit(&store, &format!("x{i}"), bob, &[login, invoice]).await;
+        }
+
+        let mut config = HomerConfig::default();
+        config
+            .analysis
+            .teams
+            .roster
+            .insert("identity".to_string(), vec!["bob@example.com".to_string()]);
+
+        TeamAlignmentAnalyzer::new(tmp.path())
+            .analyze(&store, &config)
+            .await
+            .unwrap();
+
+        let alice_team = store
+            .get_analysis(alice, AnalysisKind::TeamAssignment)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alice_team.data["team"], "@acme/payments");
+        assert_eq!(alice_team.data["source"], "codeowners");
+        let bob_team = store
+            .get_analysis(bob, AnalysisKind::TeamAssignment)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bob_team.data["source"], "roster");
+
+        let alignment = store
+            .get_analysis(root, AnalysisKind::TeamAlignment)
+            .await
+            .unwrap()
+            .unwrap();
+        let communities = alignment.data["communities"].as_array().unwrap();
+        assert_eq!(communities[0]["label"], "billing");
+        assert_eq!(communities[0]["owner_team"], "@acme/payments");
+        assert_eq!(communities[1]["owner_team"], "identity");
+
+        let hotspots = alignment.data["cross_team_coupling"].as_array().unwrap();
+        assert_eq!(hotspots.len(), 1);
+        assert_eq!(hotspots[0]["co_changes"], 4);
+        assert_eq!(hotspots[0]["team_a"], "@acme/payments");
+        assert_eq!(hotspots[0]["team_b"], "identity");
+        assert_eq!(alignment.data["alignment_score"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn skips_without_roster_or_codeowners() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = SqliteStore::in_memory().unwrap();
+        add_node(&store, NodeKind::Module, ".").await;
+
+        let stats = TeamAlignmentAnalyzer::new(tmp.path())
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.results_stored, 0);
+    }
+}
@@ -21,8 +21,12 @@ use crate::store::incremental;
 use crate::types::{AnalysisKind, AnalysisResult, AnalysisResultId, HyperedgeKind, NodeId};
 
 use super::AnalyzeStats;
+use super::centrality::structural_salience;
 use super::traits::Analyzer;
 
+/// Per-snapshot slope of salience or coupling below which a series is stable.
+const TREND_THRESHOLD: f64 = 0.01;
+
 // ── Temporal Analyzer ──────────────────────────────────────────────
 
 #[derive(Debug, Default)]
@@ -50,11 +54,8 @@ impl Analyzer for TemporalAnalyzer {
     }
 
     async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
-        let community_count = store
-            .get_analyses_by_kind(AnalysisKind::CommunityAssignment)
-            .await?
-            .len();
-        incremental::needs_extraction(store, "analyze:temporal", &community_count.to_string()).await
+        let state = checkpoint_state(store).await?;
+        incremental::needs_extraction(store, "analyze:temporal", &state).await
     }
 
     #[instrument(skip_all, name = "temporal_analyze")]
@@ -80,13 +81,8 @@ impl Analyzer for TemporalAnalyzer {
         stats.results_stored += stability_count;
 
         // Set checkpoint so we can skip rerun if nothing changed.
-        let community_count = store
-            .get_analyses_by_kind(AnalysisKind::CommunityAssignment)
-            .await?
-            .len();
-        store
-            .set_checkpoint("analyze:temporal", &community_count.to_string())
-            .await?;
+        let state = checkpoint_state(store).await?;
+        store.set_checkpoint("analyze:temporal", &state).await?;
 
         stats.duration = start.elapsed();
         info!(
@@ -98,12 +94,23 @@ impl Analyzer for TemporalAnalyzer {
     }
 }
 
+/// Rerun when communities change or snapshots are added (e.g. by backfill).
+async fn checkpoint_state(store: &dyn HomerStore) -> crate::error::Result<String> {
+    let community_count = store
+        .get_analyses_by_kind(AnalysisKind::CommunityAssignment)
+        .await?
+        .len();
+    let snapshot_count = store.list_snapshots().await?.len();
+    Ok(format!("{community_count}:{snapshot_count}"))
+}
+
 // ── Centrality Trends ─────────────────────────────────────────────
 
 /// Compare current centrality scores with previous snapshot to detect trends.
 ///
-/// Uses the `input_hash` field to store a generation counter. Each run
-/// increments the generation; trend is computed from the last two generations.
+/// When snapshots carry salience values (see [`crate::backfill`]), the trend
+/// is fitted over those historical points plus the current graph. Otherwise
+/// each run appends its score to the history kept in the previous result.
 async fn compute_centrality_trends(
     store: &dyn HomerStore,
     now: chrono::DateTime<Utc>,
@@ -117,56 +124,70 @@ async fn compute_centrality_trends(
         return Ok(0);
     }
 
-    // Load existing centrality trend results (if any) for history
-    let existing_trends = store
-        .get_analyses_by_kind(AnalysisKind::CentralityTrend)
-        .await?;
-
-    let history: HashMap<NodeId, Vec<f64>> = existing_trends
-        .into_iter()
-        .map(|r| {
-            let scores = r
-                .data
-                .get("score_history")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(serde_json::Value::as_f64)
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default();
-            (r.node_id, scores)
-        })
-        .collect();
+    // Prefer real history from snapshots that recorded salience values;
+    // otherwise fall back to one score per previous run.
+    let node_ids: Vec<NodeId> = salience_results.iter().map(|r| r.node_id).collect();
+    let snapshot_history = load_snapshot_history(store, &node_ids).await?;
+    let from_snapshots = !snapshot_history.is_empty();
+
+    // Points are (x, score), where x is the position of the snapshot the
+    // score came from, so nodes missing from some snapshots stay aligned.
+    let (history, current_x) = if from_snapshots {
+        (
+            snapshot_history,
+            Some(store.list_snapshots().await?.len() as f64),
+        )
+    } else {
+        // Load existing centrality trend results (if any) for history
+        let runs: HashMap<NodeId, Vec<(f64, f64)>> = store
+            .get_analyses_by_kind(AnalysisKind::CentralityTrend)
+            .await?
+            .into_iter()
+            .map(|r| {
+                let scores = r
+                    .data
+                    .get("score_history")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(serde_json::Value::as_f64)
+                            .enumerate()
+                            .map(|(i, s)| (i as f64, s))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                (r.node_id, scores)
+            })
+            .collect();
+        (runs, None)
+    };
 
     let mut count = 0u64;
 
     for sr in &salience_results {
-        let current_score = sr
-            .data
-            .get("score")
-            .and_then(serde_json::Value::as_f64)
-            .unwrap_or(0.0);
+        // Snapshot values carry no git history, so compare like with like.
+        let current_score = if from_snapshots {
+            structural_salience(&sr.data)
+        } else {
+            sr.data.get("score").and_then(serde_json::Value::as_f64)
+        }
+        .unwrap_or(0.0);
 
         // Build score history: previous scores + current
-        let mut scores = history.get(&sr.node_id).cloned().unwrap_or_default();
-        scores.push(current_score);
+        let mut points = history.get(&sr.node_id).cloned().unwrap_or_default();
+        let x = current_x.unwrap_or(points.len() as f64);
+        points.push((x, current_score));
 
         // Keep last 10 snapshots
-        if scores.len() > 10 {
-            let start_idx = scores.len() - 10;
-            scores = scores[start_idx..].to_vec();
+        if points.len() > 10 {
+            points.drain(..points.len() - 10);
         }
+        let scores: Vec<f64> = points.iter().map(|&(_, s)| s).collect();
 
         // Compute trend via linear regression over history
-        let (slope, trend) = if scores.len() >= 2 {
-            let points: Vec<(f64, f64)> = scores
-                .iter()
-                .enumerate()
-                .map(|(i, &s)| (i as f64, s))
-                .collect();
+        let (slope, trend) = if points.len() >= 2 {
             let slope = simple_slope(&points);
-            let trend = classify_trend(slope);
+            let trend = classify_trend(slope, TREND_THRESHOLD);
             (slope, trend)
         } else {
             (0.0, "Stable")
@@ -182,6 +203,7 @@ async fn compute_centrality_trends(
                 "current_score": (current_score * 10000.0).round() / 10000.0,
                 "score_history": scores,
                 "snapshots": scores.len(),
+                "source": if from_snapshots { "snapshots" } else { "runs" },
             }),
             input_hash: 0,
             computed_at: now,
@@ -193,10 +215,45 @@ async fn compute_centrality_trends(
     Ok(count)
 }
 
-fn classify_trend(slope: f64) -> &'static str {
-    if slope > 0.01 {
+/// Per-node structural salience across all snapshots that recorded
+/// salience values, oldest first, keyed by the snapshot's position in
+/// [`HomerStore::list_snapshots`].
+async fn load_snapshot_history(
+    store: &dyn HomerStore,
+    node_ids: &[NodeId],
+) -> crate::error::Result<HashMap<NodeId, Vec<(f64, f64)>>> {
+    let positions: HashMap<_, f64> = store
+        .list_snapshots()
+        .await?
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.id, i as f64))
+        .collect();
+    let history = store
+        .get_metric_series(node_ids, AnalysisKind::CompositeSalience)
+        .await?
+        .into_iter()
+        .filter_map(|(node_id, series)| {
+            let points: Vec<(f64, f64)> = series
+                .iter()
+                .filter_map(|point| {
+                    Some((
+                        *positions.get(&point.snapshot_id)?,
+                        structural_salience(&point.data)?,
+                    ))
+                })
+                .collect();
+            (!points.is_empty()).then_some((node_id, points))
+        })
+        .collect();
+    Ok(history)
+}
+
+/// Classify a regression slope, treating `|slope| <= threshold` as stable.
+pub(crate) fn classify_trend(slope: f64, threshold: f64) -> &'static str {
+    if slope > threshold {
         "Rising"
-    } else if slope < -0.01 {
+    } else if slope < -threshold {
         "Falling"
     } else {
         "Stable"
@@ -204,7 +261,7 @@ fn classify_trend(slope: f64) -> &'static str {
 }
 
 /// Simple linear regression slope (least squares).
-fn simple_slope(points: &[(f64, f64)]) -> f64 {
+pub(crate) fn simple_slope(points: &[(f64, f64)]) -> f64 {
     let n = points.len() as f64;
     if n < 2.0 {
         return 0.0;
@@ -304,7 +361,7 @@ async fn compute_architectural_drift(
             .enumerate()
             .map(|(i, &r)| (i as f64, r))
             .collect();
-        classify_trend(simple_slope(&points))
+        classify_trend(simple_slope(&points), TREND_THRESHOLD)
     } else {
         "Stable"
     };
@@ -426,10 +483,10 @@ mod tests {
 
     #[test]
     fn trend_classification() {
-        assert_eq!(classify_trend(0.05), "Rising");
-        assert_eq!(classify_trend(-0.05), "Falling");
-        assert_eq!(classify_trend(0.005), "Stable");
-        assert_eq!(classify_trend(0.0), "Stable");
+        assert_eq!(classify_trend(0.05, TREND_THRESHOLD), "Rising");
+        assert_eq!(classify_trend(-0.05, TREND_THRESHOLD), "Falling");
+        assert_eq!(classify_trend(0.005, TREND_THRESHOLD), "Stable");
+        assert_eq!(classify_trend(0.0, TREND_THRESHOLD), "Stable");
     }
 
     #[test]
@@ -555,6 +612,117 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn temporal_trends_use_snapshot_history() {
+        let store = SqliteStore::in_memory().unwrap();
+        setup_temporal_data(&store).await;
+
+        let hub = store
+            .get_node_by_name(NodeKind::Module, "src/a.rs")
+            .await
+            .unwrap()
+            .unwrap();
+        let current = store
+            .get_analysis(hub.id, AnalysisKind::CompositeSalience)
+            .await
+            .unwrap()
+            .unwrap();
+        let current_score = structural_salience(&current.data).unwrap();
+
+        // Two historical snapshots in which the module was far more central.
+        historical_salience(&store, "v0.1", 1, Some((hub.id, 1.0))).await;
+        historical_salience(&store, "v0.2", 2, Some((hub.id, 0.6))).await;
+
+        TemporalAnalyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let trend = store
+            .get_analysis(hub.id, AnalysisKind::CentralityTrend)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trend.data["source"], "snapshots");
+        let history: Vec<f64> = trend.data["score_history"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(serde_json::Value::as_f64)
+            .collect();
+        assert_eq!(history.len(), 3);
+        assert!((history[1] - 0.6 * 0.30).abs() < 1e-9);
+        assert!((history[2] - current_score).abs() < 1e-9);
+        assert_eq!(trend.data["trend"], "Falling");
+    }
+
+    /// Record a backfilled snapshot, optionally with one node's salience.
+    async fn historical_salience(
+        store: &SqliteStore,
+        label: &str,
+        day: u32,
+        value: Option<(NodeId, f64)>,
+    ) {
+        let analyses = value
+            .map(|(node_id, pagerank)| crate::types::SnapshotAnalysis {
+                node_id,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({
+                    "score": pagerank,
+                    "components": { "pagerank": pagerank, "betweenness": 0.0, "authority": 0.0 },
+                }),
+            })
+            .into_iter()
+            .collect();
+        store
+            .create_historical_snapshot(&crate::types::HistoricalSnapshot {
+                label: label.to_string(),
+                snapshot_at: chrono::DateTime::parse_from_rfc3339(&format!(
+                    "2024-01-{day:02}T00:00:00Z"
+                ))
+                .unwrap()
+                .with_timezone(&Utc),
+                node_count: 4,
+                edge_count: 3,
+                node_ids: value.iter().map(|&(id, _)| id).collect(),
+                removed: Vec::new(),
+                analyses,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn snapshot_trend_spaces_points_by_snapshot() {
+        let store = SqliteStore::in_memory().unwrap();
+        setup_temporal_data(&store).await;
+        let hub = store
+            .get_node_by_name(NodeKind::Module, "src/a.rs")
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The module is missing from the middle snapshot.
+        historical_salience(&store, "v0.1", 1, Some((hub.id, 1.0))).await;
+        historical_salience(&store, "v0.2", 2, None).await;
+        historical_salience(&store, "v0.3", 3, Some((hub.id, 0.5))).await;
+
+        TemporalAnalyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        let trend = store
+            .get_analysis(hub.id, AnalysisKind::CentralityTrend)
+            .await
+            .unwrap()
+            .unwrap();
+        let current = trend.data["current_score"].as_f64().unwrap();
+        let expected = simple_slope(&[(0.0, 0.30), (2.0, 0.15), (3.0, current)]);
+        let slope = trend.data["slope"].as_f64().unwrap();
+        assert!((slope - expected).abs() < 1e-3, "{slope} vs {expected}");
+    }
+
     #[tokio::test]
     async fn temporal_architectural_drift() {
         let store = SqliteStore::in_memory().unwrap();
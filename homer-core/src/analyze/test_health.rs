@@ -0,0 +1,575 @@
+// Test health: flaky and slow tests from ingested CI run history.
+
+// Rates and percentiles intentionally cast counts to float.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use chrono::Utc;
+use tracing::{info, instrument};
+
+use crate::config::{HomerConfig, TestHealthConfig};
+use crate::contracts::roles;
+use crate::extract::test_results::{FUNCTION_KEY, TestOutcome, TestRunRecord};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    AnalysisKind, AnalysisResult, AnalysisResultId, HyperedgeKind, Node, NodeFilter, NodeId,
+    NodeKind,
+};
+
+use super::AnalyzeStats;
+use super::traits::Analyzer;
+
+/// Most exercised entities listed per flaky test.
+const MAX_EXERCISED: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct TestHealthAnalyzer;
+
+#[async_trait::async_trait]
+impl Analyzer for TestHealthAnalyzer {
+    fn name(&self) -> &'static str {
+        "test_health"
+    }
+
+    fn produces(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::TestFlakiness, AnalysisKind::SlowTest]
+    }
+
+    fn requires(&self) -> &'static [AnalysisKind] {
+        &[AnalysisKind::CompositeSalience]
+    }
+
+    async fn needs_rerun(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = health_state(store).await?;
+        incremental::needs_extraction(store, "analyze:test_health", &state).await
+    }
+
+    #[instrument(skip_all, name = "test_health_analyze")]
+    async fn analyze(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<AnalyzeStats> {
+        let start = Instant::now();
+        let mut stats = AnalyzeStats::default();
+        let now = Utc::now();
+        let thresholds = &config.analysis.tests;
+
+        store
+            .clear_analyses_by_kinds(&[AnalysisKind::TestFlakiness, AnalysisKind::SlowTest])
+            .await?;
+
+        let tests = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Test),
+                ..Default::default()
+            })
+            .await?;
+        if tests.is_empty() {
+            info!("No test results ingested, skipping test health");
+        }
+
+        let histories: Vec<(&Node, Vec<TestRunRecord>)> = tests
+            .iter()
+            .map(|t| (t, TestRunRecord::history(t)))
+            .collect();
+        let mut results: Vec<(NodeId, AnalysisKind, serde_json::Value)> = Vec::new();
+
+        let flaky: Vec<(&Node, Flakiness)> = histories
+            .iter()
+            .filter_map(|(test, runs)| Some((*test, flakiness(runs)?)))
+            .collect();
+        if !flaky.is_empty() {
+            let exercised = Exercised::load(store).await?;
+            for (test, flakiness) in flaky {
+                let function = test
+                    .metadata
+                    .get(FUNCTION_KEY)
+                    .and_then(serde_json::Value::as_str);
+                let reach = exercised.reach(store, function, thresholds).await?;
+                results.push((
+                    test.id,
+                    AnalysisKind::TestFlakiness,
+                    flakiness.to_json(function, &reach),
+                ));
+            }
+        }
+
+        for (id, data) in slow_tests(&histories, thresholds) {
+            results.push((id, AnalysisKind::SlowTest, data));
+        }
+
+        for (node_id, kind, data) in results {
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id,
+                    kind,
+                    data,
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await?;
+            stats.results_stored += 1;
+        }
+
+        let state = health_state(store).await?;
+        store.set_checkpoint("analyze:test_health", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            tests = tests.len(),
+            results = stats.results_stored,
+            duration = ?stats.duration,
+            "Test health analysis complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Rerun when test results are re-ingested or salience is recomputed.
+async fn health_state(store: &dyn HomerStore) -> crate::error::Result<String> {
+    let ingested = store.get_checkpoint("test_results_state").await?;
+    let salience = store
+        .get_analyses_by_kind(AnalysisKind::CompositeSalience)
+        .await?
+        .len();
+    Ok(format!("{}:{salience}", ingested.unwrap_or_default()))
+}
+
+// ── Flakiness ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+struct Flakiness {
+    /// Commits with both a pass and a fail.
+    flaky_commits: usize,
+    /// Commits run more than once (excluding skips).
+    commits_retried: usize,
+    /// Pass↔fail changes between consecutive runs at the same commit.
+    flips: usize,
+    runs: usize,
+    failures: usize,
+    /// The most recent commit the test flaked at, and when.
+    last_flaky: (String, String),
+}
+
+impl Flakiness {
+    fn to_json(&self, function: Option<&str>, reach: &Reach) -> serde_json::Value {
+        let rate = self.flaky_commits as f64 / self.commits_retried.max(1) as f64;
+        serde_json::json!({
+            "flaky_commits": self.flaky_commits,
+            "commits_retried": self.commits_retried,
+            "flake_rate": (rate * 1000.0).round() / 1000.0,
+            "flips": self.flips,
+            "runs": self.runs,
+            "failures": self.failures,
+            "last_flaky_commit": self.last_flaky.0,
+            "last_flaky_at": self.last_flaky.1,
+            "function": function,
+            "exercises": reach.entities
+                .iter()
+                .map(|(name, salience)| serde_json::json!({ "name": name, "salience": salience }))
+                .collect::<Vec<_>>(),
+            "high_salience_files": reach.salient_files,
+            "max_salience": reach.max_salience,
+            "high_salience": !reach.salient_files.is_empty(),
+        })
+    }
+}
+
+/// Pass/fail flips at the same commit, or `None` when the test never flaked.
+/// The code did not change between such runs, so the outcome depended on
+/// something else. Runs not tied to a commit cannot show this and are
+/// ignored.
+fn flakiness(runs: &[TestRunRecord]) -> Option<Flakiness> {
+    let mut by_commit: BTreeMap<&str, Vec<&TestRunRecord>> = BTreeMap::new();
+    for run in runs.iter().filter(|r| r.outcome != TestOutcome::Skip) {
+        if let Some(commit) = &run.commit {
+            by_commit.entry(commit).or_default().push(run);
+        }
+    }
+
+    let mut result = Flakiness {
+        flaky_commits: 0,
+        commits_retried: 0,
+        flips: 0,
+        runs: runs.len(),
+        failures: runs
+            .iter()
+            .filter(|r| r.outcome == TestOutcome::Fail)
+            .count(),
+        last_flaky: (String::new(), String::new()),
+    };
+    let mut last_at = None;
+    for (commit, mut group) in by_commit {
+        if group.len() < 2 {
+            continue;
+        }
+        result.commits_retried += 1;
+        group.sort_by_key(|r| r.at);
+        let flips = group
+            .windows(2)
+            .filter(|w| w[0].outcome != w[1].outcome)
+            .count();
+        if flips == 0 {
+            continue;
+        }
+        result.flaky_commits += 1;
+        result.flips += flips;
+        let at = group.last().map(|r| r.at);
+        if at > last_at {
+            last_at = at;
+            result.last_flaky = (
+                commit.to_string(),
+                at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            );
+        }
+    }
+    (result.flaky_commits > 0).then_some(result)
+}
+
+/// What a test exercises, ranked by salience.
+#[derive(Debug, Default)]
+struct Reach {
+    entities: Vec<(String, f64)>,
+    /// Files of exercised code whose own or whose code's salience meets
+    /// `flaky_min_salience`.
+    salient_files: Vec<String>,
+    max_salience: f64,
+}
+
+/// `Tests` edges and salience scores, for following flaky tests to code.
+/// A flaky test over salient code erodes the safety net where it matters
+/// most, so its result is marked `high_salience` for the risk map and
+/// AGENTS.md.
+struct Exercised {
+    /// Test function → functions it exercises.
+    tested: HashMap<NodeId, Vec<NodeId>>,
+    salience: HashMap<NodeId, f64>,
+}
+
+impl Exercised {
+    async fn load(store: &dyn HomerStore) -> crate::error::Result<Self> {
+        let mut tested: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for edge in store.get_edges_by_kind(HyperedgeKind::Tests).await? {
+            let test = edge.members.iter().find(|m| m.role == roles::TEST);
+            let target = edge.members.iter().find(|m| m.role == roles::TESTED);
+            if let (Some(test), Some(target)) = (test, target) {
+                tested.entry(test.node_id).or_default().push(target.node_id);
+            }
+        }
+        let salience = store
+            .get_analyses_by_kind(AnalysisKind::CompositeSalience)
+            .await?
+            .into_iter()
+            .filter_map(|r| Some((r.node_id, r.data.get("score")?.as_f64()?)))
+            .collect();
+        Ok(Self { tested, salience })
+    }
+
+    async fn reach(
+        &self,
+        store: &dyn HomerStore,
+        function: Option<&str>,
+        thresholds: &TestHealthConfig,
+    ) -> crate::error::Result<Reach> {
+        let mut reach = Reach::default();
+        let Some(function) = function else {
+            return Ok(reach);
+        };
+        let Some(test_fn) = store.get_node_by_name(NodeKind::Function, function).await? else {
+            return Ok(reach);
+        };
+
+        let mut files: BTreeMap<String, f64> = BTreeMap::new();
+        for target in self.tested.get(&test_fn.id).into_iter().flatten() {
+            let Some(node) = store.get_node(*target).await? else {
+                continue;
+            };
+            let salience = self.salience.get(target).copied().unwrap_or(0.0);
+            if let Some(file) = node
+                .metadata
+                .get("file")
+                .and_then(serde_json::Value::as_str)
+            {
+                let entry = files.entry(file.to_string()).or_insert(0.0);
+                *entry = entry.max(salience);
+            }
+            reach.entities.push((node.name, salience));
+        }
+        for (file, salience) in &mut files {
+            if let Some(node) = store.get_node_by_name(NodeKind::File, file).await? {
+                *salience = salience.max(self.salience.get(&node.id).copied().unwrap_or(0.0));
+            }
+        }
+
+        reach.max_salience = files.values().copied().fold(0.0, f64::max);
+        reach.salient_files = files
+            .into_iter()
+            .filter(|(_, s)| *s >= thresholds.flaky_min_salience)
+            .map(|(file, _)| file)
+            .collect();
+        reach
+            .entities
+            .sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        reach.entities.truncate(MAX_EXERCISED);
+        Ok(reach)
+    }
+}
+
+// ── Slow tests ────────────────────────────────────────────────────
+
+/// Tests whose median duration is in the top percentiles of the suite and
+/// above an absolute floor, so a fast suite has no slow tests.
+fn slow_tests(
+    histories: &[(&Node, Vec<TestRunRecord>)],
+    thresholds: &TestHealthConfig,
+) -> Vec<(NodeId, serde_json::Value)> {
+    let mut timed: Vec<(&Node, f64, f64, usize)> = histories
+        .iter()
+        .filter_map(|(test, runs)| {
+            let mut durations: Vec<f64> = runs
+                .iter()
+                .filter(|r| r.outcome != TestOutcome::Skip)
+                .filter_map(|r| r.duration_ms)
+                .collect();
+            if durations.is_empty() {
+                return None;
+            }
+            durations.sort_by(f64::total_cmp);
+            let median = durations[durations.len() / 2];
+            let max = durations[durations.len() - 1];
+            Some((*test, median, max, durations.len()))
+        })
+        .collect();
+    timed.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let total = timed.len() as f64;
+    timed
+        .iter()
+        .filter_map(|(test, median, max, count)| {
+            // Share of tests at or below this median.
+            let at_or_below = timed.partition_point(|t| t.1 <= *median);
+            let percentile = at_or_below as f64 / total * 100.0;
+            if percentile < thresholds.slow_min_percentile || *median < thresholds.slow_min_ms {
+                return None;
+            }
+            Some((
+                test.id,
+                serde_json::json!({
+                    "median_ms": (median * 10.0).round() / 10.0,
+                    "max_ms": (max * 10.0).round() / 10.0,
+                    "timed_runs": count,
+                    "percentile": (percentile * 10.0).round() / 10.0,
+                    "function": test.metadata.get(FUNCTION_KEY),
+                }),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+
+    fn run(commit: Option<&str>, day: u32, outcome: TestOutcome, ms: f64) -> TestRunRecord {
+        TestRunRecord {
+            run: format!("run-{day}"),
+            commit: commit.map(str::to_string),
+            at: chrono::DateTime::parse_from_rfc3339(&format!("2026-03-{day:02}T10:00:00Z"))
+                .unwrap()
+                .with_timezone(&Utc),
+            outcome,
+            duration_ms: Some(ms),
+        }
+    }
+
+    #[test]
+    fn flakiness_needs_both_outcomes_at_one_commit() {
+        use TestOutcome::{Fail, Pass, Skip};
+        let steady_fix = [
+            run(Some("a1"), 1, Fail, 1.0),
+            run(Some("b2"), 2, Pass, 1.0),
+            run(Some("b2"), 3, Skip, 1.0),
+            run(None, 4, Fail, 1.0),
+        ];
+        assert!(flakiness(&steady_fix).is_none(), "fixed between commits");
+
+        let flaky = [
+            run(Some("a1"), 1, Pass, 1.0),
+            run(Some("a1"), 2, Fail, 1.0),
+            run(Some("a1"), 3, Pass, 1.0),
+            run(Some("b2"), 4, Pass, 1.0),
+            run(Some("b2"), 5, Pass, 1.0),
+        ];
+        let result = flakiness(&flaky).unwrap();
+        assert_eq!(result.flaky_commits, 1);
+        assert_eq!(result.commits_retried, 2);
+        assert_eq!(result.flips, 2);
+        assert_eq!(result.failures, 1);
+        assert_eq!(result.last_flaky.0, "a1");
+    }
+
+    async fn add_node(
+        store: &SqliteStore,
+        kind: NodeKind,
+        name: &str,
+        meta: serde_json::Value,
+    ) -> NodeId {
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: serde_json::from_value(meta).unwrap(),
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn set_salience(store: &SqliteStore, node: NodeId, salience: f64) {
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: node,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({ "score": salience }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+    }
+
+    /// A salient file whose function is exercised by `tests/ledger.rs::posts_twice`.
+    async fn seed_tested_code(store: &SqliteStore) {
+        let ledger = add_node(
+            store,
+            NodeKind::File,
+            "src/ledger.rs",
+            serde_json::json!({}),
+        )
+        .await;
+        set_salience(store, ledger, 0.9).await;
+        let post = add_node(
+            store,
+            NodeKind::Function,
+            "src/ledger.rs::post",
+            serde_json::json!({ "file": "src/ledger.rs" }),
+        )
+        .await;
+        set_salience(store, post, 0.4).await;
+        let test_fn = add_node(
+            store,
+            NodeKind::Function,
+            "tests/ledger.rs::posts_twice",
+            serde_json::json!({ "file": "tests/ledger.rs", "test": { "framework": "rust" } }),
+        )
+        .await;
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Tests,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: test_fn,
+                        role: roles::TEST.to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: post,
+                        role: roles::TESTED.to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn flags_flaky_tests_on_salient_code_and_slow_tests() {
+        use TestOutcome::{Fail, Pass};
+        let store = SqliteStore::in_memory().unwrap();
+        seed_tested_code(&store).await;
+
+        let history = |runs: Vec<TestRunRecord>, function: Option<&str>| serde_json::json!({ "runs": runs, "function": function });
+        let flaky = add_node(
+            &store,
+            NodeKind::Test,
+            "ledger::posts_twice",
+            history(
+                vec![
+                    run(Some("a1"), 1, Pass, 40.0),
+                    run(Some("a1"), 2, Fail, 45.0),
+                ],
+                Some("tests/ledger.rs::posts_twice"),
+            ),
+        )
+        .await;
+        let slow = add_node(
+            &store,
+            NodeKind::Test,
+            "ledger::replays_year",
+            history(
+                vec![
+                    run(Some("a1"), 1, Pass, 4000.0),
+                    run(Some("a1"), 2, Pass, 6000.0),
+                    run(Some("a1"), 3, Pass, 5000.0),
+                ],
+                None,
+            ),
+        )
+        .await;
+        for i in 0..20 {
+            add_node(
+                &store,
+                NodeKind::Test,
+                &format!("ledger::quick_{i}"),
+                history(vec![run(None, 1, Pass, 5.0)], None),
+            )
+            .await;
+        }
+
+        let stats = TestHealthAnalyzer
+            .analyze(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.results_stored, 2);
+
+        let flakiness = store
+            .get_analysis(flaky, AnalysisKind::TestFlakiness)
+            .await
+            .unwrap()
+            .expect("flaky result");
+        assert_eq!(flakiness.data["flaky_commits"], 1);
+        assert_eq!(
+            flakiness.data["high_salience"], true,
+            "file salience counts"
+        );
+        assert_eq!(flakiness.data["high_salience_files"][0], "src/ledger.rs");
+        assert_eq!(
+            flakiness.data["exercises"][0]["name"],
+            "src/ledger.rs::post"
+        );
+
+        let slow = store
+            .get_analysis(slow, AnalysisKind::SlowTest)
+            .await
+            .unwrap()
+            .expect("slow result");
+        assert_eq!(slow.data["median_ms"], 5000.0);
+        assert_eq!(slow.data["max_ms"], 6000.0);
+        assert!(!TestHealthAnalyzer.needs_rerun(&store).await.unwrap());
+    }
+}
@@ -0,0 +1,459 @@
+// Historical snapshot backfill: re-extracts past releases (or every N
+// commits) in scratch git worktrees and records snapshots dated at the commit
+// they describe.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument, warn};
+
+use crate::analyze::centrality::CentralityAnalyzer;
+use crate::analyze::community::CommunityAnalyzer;
+use crate::analyze::dead_code::DeadCodeAnalyzer;
+use crate::analyze::traits::Analyzer;
+use crate::config::HomerConfig;
+use crate::error::{ExtractError, HomerError};
+use crate::extract::graph::GraphExtractor;
+use crate::extract::structure::StructureExtractor;
+use crate::extract::traits::Extractor;
+use crate::store::HomerStore;
+use crate::store::sqlite::SqliteStore;
+use crate::types::{
+    AnalysisKind, EntityRef, HistoricalSnapshot, NodeFilter, NodeKind, SnapshotAnalysis,
+};
+
+/// Prefix of every backfilled snapshot label, keeping them apart from the
+/// release and `auto-{N}` snapshots the pipeline takes.
+pub const LABEL_PREFIX: &str = "backfill-";
+
+/// Which points in history to backfill.
+#[derive(Debug, Clone, Default)]
+pub struct BackfillOptions {
+    /// Also snapshot every N first-parent commits (labelled `backfill-{N}`).
+    pub every_n_commits: Option<u32>,
+    /// Recompute snapshots that were already backfilled.
+    pub force: bool,
+}
+
+/// Outcome of a backfill run.
+#[derive(Debug, Default)]
+pub struct BackfillResult {
+    /// Labels of snapshots that were (re)computed.
+    pub created: Vec<String>,
+    /// Labels skipped because they were already backfilled.
+    pub skipped: Vec<String>,
+    /// Labels that failed, with the error message.
+    pub failed: Vec<(String, String)>,
+}
+
+/// A point in history to snapshot.
+#[derive(Debug, Clone)]
+struct BackfillTarget {
+    label: String,
+    commit: String,
+    committed_at: DateTime<Utc>,
+}
+
+/// Backfill historical snapshots for the repository at `repo_path`.
+///
+/// Release nodes must already be in `store` (run `homer init` or `homer
+/// update` first). Releases are labelled `backfill-{tag}`. Earlier backfilled
+/// snapshots with a matching label are replaced unless they were taken at the
+/// same commit time; snapshots taken by the pipeline are left alone.
+#[instrument(skip_all, fields(repo = %repo_path.display()))]
+pub async fn backfill_snapshots(
+    repo_path: &Path,
+    store: &dyn HomerStore,
+    config: &HomerConfig,
+    options: &BackfillOptions,
+) -> crate::error::Result<BackfillResult> {
+    let mut targets = release_targets(repo_path, store).await?;
+    if let Some(n) = options.every_n_commits.filter(|&n| n > 0) {
+        targets.extend(commit_targets(repo_path, n)?);
+    }
+    targets.sort_by_key(|t| t.committed_at);
+
+    let existing = store.list_snapshots().await?;
+    let mut result = BackfillResult::default();
+
+    for (idx, target) in targets.iter().enumerate() {
+        let already_backfilled = existing
+            .iter()
+            .any(|s| s.label == target.label && s.snapshot_at == target.committed_at);
+        if already_backfilled && !options.force {
+            result.skipped.push(target.label.clone());
+            continue;
+        }
+
+        match backfill_one(repo_path, store, config, target, idx).await {
+            Ok(()) => {
+                info!(label = %target.label, commit = %target.commit, "Backfilled snapshot");
+                result.created.push(target.label.clone());
+            }
+            Err(e) => {
+                warn!(label = %target.label, error = %e, "Snapshot backfill failed");
+                result.failed.push((target.label.clone(), e.to_string()));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+async fn release_targets(
+    repo_path: &Path,
+    store: &dyn HomerStore,
+) -> crate::error::Result<Vec<BackfillTarget>> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Release),
+        ..Default::default()
+    };
+    let releases = store.find_nodes(&filter).await?;
+
+    let mut targets = Vec::with_capacity(releases.len());
+    for release in releases {
+        let rev = release
+            .metadata
+            .get("target")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&release.name);
+        match resolve_commit(repo_path, rev) {
+            Ok((commit, committed_at)) => targets.push(BackfillTarget {
+                label: format!("{LABEL_PREFIX}{}", release.name),
+                commit,
+                committed_at,
+            }),
+            Err(e) => warn!(release = %release.name, error = %e, "Cannot resolve release"),
+        }
+    }
+    Ok(targets)
+}
+
+fn commit_targets(repo_path: &Path, every_n: u32) -> crate::error::Result<Vec<BackfillTarget>> {
+    let log = git(
+        repo_path,
+        &[
+            "log",
+            "--first-parent",
+            "--reverse",
+            "--format=%H %cI",
+            "HEAD",
+        ],
+    )?;
+    let step = every_n as usize;
+
+    Ok(log
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| (i + 1) % step == 0)
+        .filter_map(|(i, line)| {
+            let (commit, date) = line.split_once(' ')?;
+            Some(BackfillTarget {
+                label: format!("{LABEL_PREFIX}{}", i + 1),
+                commit: commit.to_string(),
+                committed_at: parse_git_date(date).ok()?,
+            })
+        })
+        .collect())
+}
+
+/// Extract and analyze one point in history, then record it in `store`.
+async fn backfill_one(
+    repo_path: &Path,
+    store: &dyn HomerStore,
+    config: &HomerConfig,
+    target: &BackfillTarget,
+    idx: usize,
+) -> crate::error::Result<()> {
+    let worktree = ScratchWorktree::create(repo_path, "backfill", &target.commit, idx)?;
+    let scratch = SqliteStore::in_memory()?;
+
+    StructureExtractor::new(worktree.path())
+        .extract(&scratch, config)
+        .await?;
+    GraphExtractor::new(worktree.path())
+        .extract(&scratch, config)
+        .await?;
+    // Dead-code results feed the salience classification, as in the live pipeline.
+    DeadCodeAnalyzer::new(config)
+        .analyze(&scratch, config)
+        .await?;
+    CentralityAnalyzer::default()
+        .analyze(&scratch, config)
+        .await?;
+    CommunityAnalyzer.analyze(&scratch, config).await?;
+
+    let stats = scratch.stats().await?;
+    let mut snapshot = HistoricalSnapshot {
+        label: target.label.clone(),
+        snapshot_at: target.committed_at,
+        node_count: stats.total_nodes,
+        edge_count: stats.total_edges,
+        node_ids: Vec::new(),
+        removed: Vec::new(),
+        analyses: Vec::new(),
+    };
+
+    // Map historical nodes onto the live graph by (kind, name). Entities
+    // gone from the live graph are kept by name so diffs show the removal.
+    // The root module is named after the checkout directory, so it maps
+    // onto the live root instead.
+    let live_root = repo_path
+        .file_name()
+        .map_or_else(|| "root".to_string(), |n| n.to_string_lossy().to_string());
+    let mut live_ids = std::collections::HashMap::new();
+    for node in scratch.find_nodes(&NodeFilter::default()).await? {
+        let is_root = node
+            .metadata
+            .get("is_root")
+            .and_then(serde_json::Value::as_bool);
+        let name = if is_root == Some(true) {
+            &live_root
+        } else {
+            &node.name
+        };
+        if let Some(live) = store.get_node_by_name(node.kind.clone(), name).await? {
+            snapshot.node_ids.push(live.id);
+            live_ids.insert(node.id, live.id);
+        } else {
+            snapshot.removed.push(EntityRef {
+                kind: node.kind,
+                name: node.name,
+            });
+        }
+    }
+
+    for &kind in AnalysisKind::SNAPSHOTTED {
+        for analysis in scratch.get_analyses_by_kind(kind).await? {
+            if let Some(&node_id) = live_ids.get(&analysis.node_id) {
+                snapshot.analyses.push(SnapshotAnalysis {
+                    node_id,
+                    kind,
+                    data: analysis.data,
+                });
+            }
+        }
+    }
+
+    store.create_historical_snapshot(&snapshot).await?;
+    Ok(())
+}
+
+pub(crate) fn resolve_commit(
+    repo_path: &Path,
+    rev: &str,
+) -> crate::error::Result<(String, DateTime<Utc>)> {
+    let peeled = format!("{rev}^{{commit}}");
+    let out = git(repo_path, &["show", "-s", "--format=%H %cI", &peeled])?;
+    let (commit, date) = out
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| git_error(format!("Unexpected output for {rev}: {out}")))?;
+    Ok((commit.to_string(), parse_git_date(date)?))
+}
+
+fn parse_git_date(date: &str) -> crate::error::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date.trim())
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| git_error(format!("Invalid commit date {date}: {e}")))
+}
+
+fn git(repo_path: &Path, args: &[&str]) -> crate::error::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| git_error(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(git_error(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git_error(message: String) -> HomerError {
+    HomerError::Extract(ExtractError::Git(message))
+}
+
+/// A detached worktree in the temp directory, removed on drop.
+#[derive(Debug)]
+pub(crate) struct ScratchWorktree {
+    repo_path: PathBuf,
+    path: PathBuf,
+}
+
+impl ScratchWorktree {
+    /// Check out `commit` under a temp directory named after `purpose`.
+    pub(crate) fn create(
+        repo_path: &Path,
+        purpose: &str,
+        commit: &str,
+        idx: usize,
+    ) -> crate::error::Result<Self> {
+        let path =
+            std::env::temp_dir().join(format!("homer-{purpose}-{}-{idx}", std::process::id()));
+        let path_str = path.to_string_lossy().into_owned();
+        git(
+            repo_path,
+            &["worktree", "add", "--detach", "--force", &path_str, commit],
+        )?;
+        Ok(Self {
+            repo_path: repo_path.to_path_buf(),
+            path,
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchWorktree {
+    fn drop(&mut self) {
+        let path_str = self.path.to_string_lossy().into_owned();
+        if git(
+            &self.repo_path,
+            &["worktree", "remove", "--force", &path_str],
+        )
+        .is_err()
+        {
+            let _ = std::fs::remove_dir_all(&self.path);
+            let _ = git(&self.repo_path, &["worktree", "prune"]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .expect("git command failed")
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn commit_file(dir: &Path, path: &str, content: &str, date: &str) {
+        std::fs::write(dir.join(path), content).unwrap();
+        run_git(dir, &["add", "."]);
+        let status = Command::new("git")
+            .args(["commit", "-q", "-m", path])
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[tokio::test]
+    async fn backfills_release_state_not_current_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        run_git(dir, &["init", "-q", "--initial-branch=main"]);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+
+        commit_file(
+            dir,
+            "src/lib.rs",
+            "pub fn a() { b(); }\npub fn b() {}\n",
+            "2024-01-01T00:00:00Z",
+        );
+        commit_file(dir, "src/old.rs", "pub fn d() {}\n", "2024-01-01T00:00:00Z");
+        run_git(dir, &["tag", "-a", "v0.1.0", "-m", "first"]);
+        run_git(dir, &["rm", "-q", "src/old.rs"]);
+        commit_file(
+            dir,
+            "src/extra.rs",
+            "pub fn c() {}\n",
+            "2024-02-01T00:00:00Z",
+        );
+
+        // Live graph reflects HEAD, including the file added after the tag.
+        let store = SqliteStore::in_memory().unwrap();
+        let config = HomerConfig::default();
+        crate::extract::git::GitExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+        StructureExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+        GraphExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+
+        let options = BackfillOptions::default();
+        let result = backfill_snapshots(dir, &store, &config, &options)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.created,
+            ["backfill-v0.1.0"],
+            "failed: {:?}",
+            result.failed
+        );
+
+        let snapshots = store.list_snapshots().await.unwrap();
+        let snap = snapshots
+            .iter()
+            .find(|s| s.label == "backfill-v0.1.0")
+            .unwrap();
+        assert_eq!(snap.snapshot_at.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+
+        let salience = store
+            .get_snapshot_analyses(snap.id, AnalysisKind::CompositeSalience)
+            .await
+            .unwrap();
+        assert!(!salience.is_empty());
+        for (node_id, _) in &salience {
+            let node = store.get_node(*node_id).await.unwrap().unwrap();
+            assert!(
+                !node.name.contains("extra"),
+                "{} postdates v0.1.0",
+                node.name
+            );
+        }
+
+        // The function deleted since the release shows up as a removal.
+        let head = store.create_snapshot("head").await.unwrap();
+        let diff = store.get_snapshot_diff(snap.id, head).await.unwrap();
+        assert_eq!(
+            diff.removed_entities,
+            vec![EntityRef {
+                kind: NodeKind::Function,
+                name: "src/old.rs::d".to_string(),
+            }]
+        );
+
+        // A second run skips the already backfilled release.
+        let again = backfill_snapshots(dir, &store, &config, &options)
+            .await
+            .unwrap();
+        assert_eq!(again.skipped, ["backfill-v0.1.0"]);
+        assert!(again.created.is_empty());
+        assert!(
+            !std::env::temp_dir()
+                .join(format!("homer-backfill-{}-0", std::process::id()))
+                .exists()
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Analysis depth level — gates which features are enabled.
@@ -117,6 +119,36 @@ pub struct AnalysisSection {
     /// Policy for invalidating stale analysis results.
     #[serde(default)]
     pub invalidation: InvalidationPolicy,
+    /// Entry-point detection for dead code analysis.
+    #[serde(default)]
+    pub dead_code: DeadCodeConfig,
+    /// Thresholds for complexity hotspot detection.
+    #[serde(default)]
+    pub complexity: ComplexityConfig,
+    /// Size and similarity thresholds for clone detection.
+    #[serde(default)]
+    pub clones: CloneConfig,
+    /// Thresholds for classifying code as settled or churning.
+    #[serde(default)]
+    pub survival: SurvivalConfig,
+    /// Inactivity and orphaned-code thresholds for knowledge-loss analysis.
+    #[serde(default)]
+    pub knowledge: KnowledgeConfig,
+    /// Team roster and thresholds for Conway's-law alignment.
+    #[serde(default)]
+    pub teams: TeamsConfig,
+    /// How commits are grouped into change sets for co-change detection.
+    #[serde(default)]
+    pub co_change: CoChangeConfig,
+    /// Which releases have their public API recorded.
+    #[serde(default)]
+    pub api: ApiSurfaceConfig,
+    /// Thresholds for flaky and slow test detection.
+    #[serde(default)]
+    pub tests: TestHealthConfig,
+    /// How profiled runtime hotness is reported and weighed into salience.
+    #[serde(default)]
+    pub runtime: RuntimeHotnessConfig,
 }
 
 impl Default for AnalysisSection {
@@ -126,6 +158,16 @@ impl Default for AnalysisSection {
             llm_salience_threshold: 0.7,
             max_llm_batch_size: 50,
             invalidation: InvalidationPolicy::default(),
+            dead_code: DeadCodeConfig::default(),
+            complexity: ComplexityConfig::default(),
+            clones: CloneConfig::default(),
+            survival: SurvivalConfig::default(),
+            knowledge: KnowledgeConfig::default(),
+            teams: TeamsConfig::default(),
+            co_change: CoChangeConfig::default(),
+            api: ApiSurfaceConfig::default(),
+            tests: TestHealthConfig::default(),
+            runtime: RuntimeHotnessConfig::default(),
         }
     }
 }
@@ -155,6 +197,254 @@ impl Default for InvalidationPolicy {
     }
 }
 
+/// Controls which functions count as entry points for reachability.
+///
+/// `main`, exported symbols, and test functions are always roots. Framework
+/// handlers invoked by reflection, routing tables, or decorators are not
+/// visible in the call graph, so they are added here as glob patterns matched
+/// against a function's qualified name (e.g. `"*Controller::*"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeadCodeConfig {
+    /// Glob patterns for qualified names that are always reachable.
+    pub root_patterns: Vec<String>,
+}
+
+impl Default for DeadCodeConfig {
+    fn default() -> Self {
+        Self {
+            root_patterns: vec![
+                "handle_*".into(),
+                "*::handle_*".into(),
+                "*.handle_*".into(),
+                "__*__".into(),
+                "*.__*__".into(),
+            ],
+        }
+    }
+}
+
+/// Controls when a function is flagged as a complexity hotspot.
+///
+/// A hotspot is a function that is both hard to follow and sits in a file
+/// that changes often — the code most likely to harbour the next bug.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComplexityConfig {
+    /// Minimum cyclomatic complexity for a function to be considered.
+    pub hotspot_min_cyclomatic: usize,
+    /// Minimum change-frequency percentile (0–100) of the containing file.
+    pub hotspot_min_change_percentile: f64,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            hotspot_min_cyclomatic: 10,
+            hotspot_min_change_percentile: 75.0,
+        }
+    }
+}
+
+/// Thresholds for the clone analyzer.
+///
+/// Function bodies are compared by their winnowed, identifier-abstracted
+/// token fingerprints; small bodies are skipped because short idioms
+/// (getters, trivial wrappers) match each other everywhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CloneConfig {
+    /// Minimum number of normalized tokens for a function to be compared.
+    pub min_tokens: usize,
+    /// Minimum fingerprint similarity (0.0–1.0) for two functions to be clones.
+    pub min_similarity: f64,
+}
+
+impl Default for CloneConfig {
+    fn default() -> Self {
+        Self {
+            min_tokens: 50,
+            min_similarity: 0.8,
+        }
+    }
+}
+
+/// Thresholds for the code survival analyzer.
+///
+/// Churn counts alone conflate code that is edited in place with code that is
+/// rewritten wholesale; these separate the two by line age and half-life.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurvivalConfig {
+    /// Median line age (days) at or above which code counts as settled.
+    pub settled_min_age_days: f64,
+    /// Line half-life (days) below which code counts as churning.
+    pub churning_max_half_life_days: f64,
+}
+
+impl Default for SurvivalConfig {
+    fn default() -> Self {
+        Self {
+            settled_min_age_days: 180.0,
+            churning_max_half_life_days: 90.0,
+        }
+    }
+}
+
+/// Thresholds for the knowledge-loss analyzer.
+///
+/// Inactivity is measured against the newest commit in the repository, not
+/// the wall clock, so dormant projects keep meaningful results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KnowledgeConfig {
+    /// Days without a commit after which a contributor counts as inactive.
+    pub inactive_after_days: u32,
+    /// Minimum share (0.0–1.0) of commits by inactive contributors for
+    /// code to count as orphaned.
+    pub orphaned_min_inactive_share: f64,
+    /// Minimum composite salience (0.0–1.0) for code to count as orphaned.
+    pub orphaned_min_salience: f64,
+}
+
+impl Default for KnowledgeConfig {
+    fn default() -> Self {
+        Self {
+            inactive_after_days: 180,
+            orphaned_min_inactive_share: 0.75,
+            orphaned_min_salience: 0.5,
+        }
+    }
+}
+
+/// Thresholds for the test-health analyzer.
+///
+/// A test is flaky when it both passed and failed at the same commit; runs
+/// not tied to a commit only count towards duration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TestHealthConfig {
+    /// Minimum composite salience (0.0–1.0) of the code a flaky test
+    /// exercises for it to count as risky.
+    pub flaky_min_salience: f64,
+    /// Minimum duration percentile (0–100) for a test to count as slow.
+    pub slow_min_percentile: f64,
+    /// Median duration (milliseconds) below which a test is never slow.
+    pub slow_min_ms: f64,
+}
+
+impl Default for TestHealthConfig {
+    fn default() -> Self {
+        Self {
+            flaky_min_salience: 0.5,
+            slow_min_percentile: 95.0,
+            slow_min_ms: 1000.0,
+        }
+    }
+}
+
+/// Settings for runtime hotness from ingested profiles.
+///
+/// Runtime data only changes composite salience when `salience_weight` is
+/// above zero; the report lists hot functions either way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuntimeHotnessConfig {
+    /// Share (0.0–1.0) of composite salience taken from runtime hotness.
+    /// The structural score is scaled down by the same amount.
+    pub salience_weight: f64,
+    /// Minimum share (0.0–1.0) of profiled time spent in a function,
+    /// including its callees, for it to count as hot.
+    pub hot_min_share: f64,
+}
+
+impl Default for RuntimeHotnessConfig {
+    fn default() -> Self {
+        Self {
+            salience_weight: 0.0,
+            hot_min_share: 0.05,
+        }
+    }
+}
+
+/// Change-set formation for co-change detection.
+///
+/// By default only files modified in the same commit change together. A
+/// time window or pull-request grouping also catches follow-up commits
+/// ("forgot to update the client") as part of the same change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoChangeConfig {
+    /// Commits within this many hours of the first commit of a change set
+    /// join it (0 = off).
+    pub window_hours: u32,
+    /// Only join commits into a window when they share an author.
+    pub window_same_author: bool,
+    /// Commits linked to the same pull request form a single change set.
+    /// Forge extractors fetch each PR's commit list when this is set.
+    pub group_pull_requests: bool,
+}
+
+impl Default for CoChangeConfig {
+    fn default() -> Self {
+        Self {
+            window_hours: 0,
+            window_same_author: true,
+            group_pull_requests: false,
+        }
+    }
+}
+
+/// Public API snapshots taken at releases.
+///
+/// Each snapshot re-extracts the release in a scratch worktree, so snapshots
+/// are opt-in and only the most recent releases are recorded; any ref is
+/// still compared on demand by `homer api-diff`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSurfaceConfig {
+    /// Whether analysis snapshots release APIs.
+    pub enabled: bool,
+    /// Number of most recent releases to snapshot (0 = disabled).
+    pub max_releases: usize,
+}
+
+impl Default for ApiSurfaceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_releases: 10,
+        }
+    }
+}
+
+/// Team membership for comparing team boundaries with code communities.
+///
+/// Contributors listed in `roster` belong to that team. Anyone else is
+/// assigned, when `use_codeowners` is set, to the CODEOWNERS team whose
+/// files they commit to most.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeamsConfig {
+    /// Infer teams for contributors missing from the roster from CODEOWNERS.
+    pub use_codeowners: bool,
+    /// Minimum commits touching two communities for their coupling to be
+    /// reported as a cross-team hotspot.
+    pub min_co_changes: u32,
+    /// Team name → member emails or glob patterns (e.g. `"*@payments.example.com"`).
+    pub roster: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for TeamsConfig {
+    fn default() -> Self {
+        Self {
+            use_codeowners: true,
+            min_co_changes: 3,
+            roster: BTreeMap::new(),
+        }
+    }
+}
+
 /// Settings for the extraction pipeline phase.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractionSection {
@@ -175,6 +465,33 @@ pub struct ExtractionSection {
     /// GitLab API extraction settings (MRs, issues, reviews).
     #[serde(default)]
     pub gitlab: GitLabExtractionConfig,
+    /// Refactoring detection across commits (moves, renames, extractions).
+    #[serde(default)]
+    pub refactoring: RefactoringExtractionConfig,
+    /// Coverage report ingestion (lcov, Cobertura, llvm-cov, Go).
+    #[serde(default)]
+    pub coverage: CoverageExtractionConfig,
+    /// Test-result ingestion (`JUnit` XML, TRX, nextest/libtest JSON).
+    #[serde(default)]
+    pub test_results: TestResultsExtractionConfig,
+    /// Profile ingestion (pprof, folded stacks, perf script, Chrome trace).
+    #[serde(default)]
+    pub profiles: ProfileExtractionConfig,
+    /// Infrastructure manifests (Dockerfiles, compose, Kubernetes, Helm, Terraform).
+    #[serde(default)]
+    pub infra: InfraExtractionConfig,
+    /// API contracts (protobuf, `OpenAPI`, `GraphQL`).
+    #[serde(default)]
+    pub contracts: ContractExtractionConfig,
+    /// Database schema migrations (plain SQL, Diesel, Alembic, Rails, Flyway).
+    #[serde(default)]
+    pub schema: SchemaExtractionConfig,
+    /// Environment variable and config-key reads, and where they are documented.
+    #[serde(default)]
+    pub env: EnvExtractionConfig,
+    /// Feature flag evaluations and when each flag appeared.
+    #[serde(default)]
+    pub flags: FlagExtractionConfig,
 }
 
 impl Default for ExtractionSection {
@@ -186,6 +503,15 @@ impl Default for ExtractionSection {
             prompts: PromptExtractionConfig::default(),
             github: GitHubExtractionConfig::default(),
             gitlab: GitLabExtractionConfig::default(),
+            refactoring: RefactoringExtractionConfig::default(),
+            coverage: CoverageExtractionConfig::default(),
+            test_results: TestResultsExtractionConfig::default(),
+            profiles: ProfileExtractionConfig::default(),
+            infra: InfraExtractionConfig::default(),
+            contracts: ContractExtractionConfig::default(),
+            schema: SchemaExtractionConfig::default(),
+            env: EnvExtractionConfig::default(),
+            flags: FlagExtractionConfig::default(),
         }
     }
 }
@@ -277,6 +603,333 @@ impl Default for DocumentExtractionConfig {
     }
 }
 
+/// Controls detection of refactorings between consecutive commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RefactoringExtractionConfig {
+    /// Whether refactoring detection is enabled.
+    pub enabled: bool,
+    /// Minimum body similarity (0.0–1.0) for two definitions or files to
+    /// count as the same code.
+    pub min_similarity: f64,
+}
+
+impl Default for RefactoringExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_similarity: 0.8,
+        }
+    }
+}
+
+/// Controls where coverage reports are looked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoverageExtractionConfig {
+    /// Whether coverage ingestion is enabled.
+    pub enabled: bool,
+    /// Glob patterns, relative to the repo root, for report files. The
+    /// format of each match is detected from its content.
+    pub reports: Vec<String>,
+}
+
+impl Default for CoverageExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reports: vec![
+                "lcov.info".into(),
+                "coverage/lcov.info".into(),
+                "coverage.xml".into(),
+                "cobertura.xml".into(),
+                "coverage/cobertura.xml".into(),
+                "coverage/cobertura-coverage.xml".into(),
+                "coverage.json".into(),
+                "coverage/coverage.json".into(),
+                "coverage.out".into(),
+                "cover.out".into(),
+                "coverage/coverage.out".into(),
+            ],
+        }
+    }
+}
+
+/// Controls where CI test-result artifacts are looked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TestResultsExtractionConfig {
+    /// Whether test-result ingestion is enabled.
+    pub enabled: bool,
+    /// Glob patterns, relative to the repo root, for result files. Each
+    /// file is one test run; its format is detected from its content. A
+    /// commit SHA in the file's path ties the run to that commit.
+    pub artifacts: Vec<String>,
+    /// Most runs kept per test; the oldest are dropped first.
+    pub max_runs: usize,
+}
+
+impl Default for TestResultsExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            artifacts: vec![
+                "test-results/**/*.xml".into(),
+                "test-results/**/*.trx".into(),
+                "test-results/**/*.json".into(),
+                "test-results/**/*.jsonl".into(),
+                "target/nextest/*/junit.xml".into(),
+                "target/surefire-reports/TEST-*.xml".into(),
+                "build/test-results/**/*.xml".into(),
+                "TestResults/**/*.trx".into(),
+            ],
+            max_runs: 200,
+        }
+    }
+}
+
+/// Controls where runtime profiles are looked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileExtractionConfig {
+    /// Whether profile ingestion is enabled.
+    pub enabled: bool,
+    /// Glob patterns, relative to the repo root, for profile files. The
+    /// format of each match is detected from its content.
+    pub profiles: Vec<String>,
+}
+
+impl Default for ProfileExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            profiles: vec![
+                "profiles/**/*.pb.gz".into(),
+                "profiles/**/*.pprof".into(),
+                "profiles/**/*.prof".into(),
+                "profiles/**/*.folded".into(),
+                "profiles/**/*.collapsed".into(),
+                "profiles/**/*.perf".into(),
+                "profiles/**/*.json".into(),
+                "*.pprof".into(),
+                "*.folded".into(),
+            ],
+        }
+    }
+}
+
+/// Controls where infrastructure manifests are looked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InfraExtractionConfig {
+    /// Whether infrastructure extraction is enabled.
+    pub enabled: bool,
+    /// Glob patterns, relative to the repo root, for Dockerfiles, compose
+    /// files, Kubernetes manifests, Helm charts and Terraform. The format of
+    /// each match is detected from its name and content. Matches under the
+    /// structure exclude patterns are skipped.
+    pub manifests: Vec<String>,
+}
+
+impl Default for InfraExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            manifests: vec![
+                "**/Dockerfile".into(),
+                "**/Dockerfile.*".into(),
+                "**/*.Dockerfile".into(),
+                "**/Containerfile".into(),
+                "**/docker-compose*.yml".into(),
+                "**/docker-compose*.yaml".into(),
+                "**/compose.yml".into(),
+                "**/compose.yaml".into(),
+                "**/Chart.yaml".into(),
+                "**/values.yaml".into(),
+                "**/templates/*.yaml".into(),
+                "**/k8s/**/*.yaml".into(),
+                "**/kubernetes/**/*.yaml".into(),
+                "**/manifests/**/*.yaml".into(),
+                "**/deploy/**/*.yaml".into(),
+                "**/*.tf".into(),
+            ],
+        }
+    }
+}
+
+/// Controls where API contract schemas are looked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContractExtractionConfig {
+    /// Whether contract extraction is enabled.
+    pub enabled: bool,
+    /// Glob patterns, relative to the repo root, for protobuf, `OpenAPI` and
+    /// `GraphQL` schemas. YAML and JSON matches are only read when they
+    /// declare `openapi` or `swagger`. Matches under the structure exclude
+    /// patterns are skipped.
+    pub schemas: Vec<String>,
+}
+
+impl Default for ContractExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            schemas: vec![
+                "**/*.proto".into(),
+                "**/*.graphql".into(),
+                "**/*.graphqls".into(),
+                "**/*.gql".into(),
+                "**/openapi*.yaml".into(),
+                "**/openapi*.yml".into(),
+                "**/openapi*.json".into(),
+                "**/swagger*.yaml".into(),
+                "**/swagger*.yml".into(),
+                "**/swagger*.json".into(),
+                "**/*.openapi.yaml".into(),
+                "**/*.openapi.yml".into(),
+                "**/*.openapi.json".into(),
+            ],
+        }
+    }
+}
+
+/// Controls where database migrations are looked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchemaExtractionConfig {
+    /// Whether schema extraction is enabled.
+    pub enabled: bool,
+    /// Glob patterns, relative to the repo root, for migration files. Diesel
+    /// `down.sql` and Flyway undo (`U*__*.sql`) migrations are skipped, as
+    /// are matches under the structure exclude patterns.
+    pub migrations: Vec<String>,
+    /// Migrations touching a table before its schema counts as churny.
+    pub churn_threshold: u32,
+}
+
+impl Default for SchemaExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            migrations: vec![
+                "**/migrations/**/*.sql".into(),
+                "**/migration/**/*.sql".into(),
+                "**/db/migrate/*.rb".into(),
+                "**/alembic/versions/*.py".into(),
+                "**/migrations/versions/*.py".into(),
+                "**/schema.sql".into(),
+            ],
+            churn_threshold: 3,
+        }
+    }
+}
+
+/// Controls where environment variables and config keys are documented, and
+/// which extra calls read config keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvExtractionConfig {
+    /// Whether environment and config extraction is enabled.
+    pub enabled: bool,
+    /// Glob patterns for `KEY=value` templates documenting environment
+    /// variables.
+    pub env_files: Vec<String>,
+    /// Glob patterns for YAML, JSON, TOML, and `.properties` files whose keys
+    /// document config keys.
+    pub config_files: Vec<String>,
+    /// Extra callee patterns for calls reading a config key, added to the
+    /// built-in ones (`config.get`, `viper.Get*`, `@Value`).
+    pub config_calls: Vec<String>,
+    /// Extra objects indexed by config key, added to the built-in ones.
+    pub config_indexes: Vec<String>,
+}
+
+impl Default for EnvExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            env_files: vec![
+                "**/.env.example".into(),
+                "**/.env.sample".into(),
+                "**/.env.template".into(),
+                "**/.env.dist".into(),
+                "**/.env.defaults".into(),
+                "**/*.env.example".into(),
+            ],
+            config_files: vec![
+                "**/application*.properties".into(),
+                "**/application*.yml".into(),
+                "**/application*.yaml".into(),
+                "**/appsettings*.json".into(),
+                "**/config/*.yml".into(),
+                "**/config/*.yaml".into(),
+                "**/config/*.toml".into(),
+                "**/settings.toml".into(),
+            ],
+            config_calls: Vec::new(),
+            config_indexes: Vec::new(),
+        }
+    }
+}
+
+/// Controls which SDK calls evaluate a feature flag and when a flag counts
+/// as stale.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlagExtractionConfig {
+    /// Whether feature flag extraction is enabled.
+    pub enabled: bool,
+    /// Callee patterns for calls whose first argument is a flag key: the
+    /// callee itself or its last segments (`variation` matches
+    /// `ldClient.variation`); a trailing `*` matches any suffix.
+    pub calls: Vec<String>,
+    /// Days since a flag appeared before it is a cleanup candidate.
+    pub stale_after_days: u32,
+}
+
+impl Default for FlagExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            calls: vec![
+                // LaunchDarkly
+                "variation*".into(),
+                "boolVariation*".into(),
+                "stringVariation*".into(),
+                "numberVariation*".into(),
+                "jsonVariation*".into(),
+                "bool_variation*".into(),
+                "BoolVariation*".into(),
+                "StringVariation*".into(),
+                "IntVariation*".into(),
+                "JSONVariation*".into(),
+                // Unleash, Flagsmith, GrowthBook
+                "isEnabled".into(),
+                "is_enabled".into(),
+                "IsEnabled".into(),
+                "isFeatureEnabled".into(),
+                "is_feature_enabled".into(),
+                "hasFeature".into(),
+                "has_feature".into(),
+                "isOn".into(),
+                "is_on".into(),
+                // OpenFeature
+                "getBooleanValue*".into(),
+                "get_boolean_value*".into(),
+                "GetBooleanValue*".into(),
+                "getBooleanDetails".into(),
+                // Split
+                "getTreatment*".into(),
+                "get_treatment*".into(),
+                // Flipper, Waffle
+                "Flipper.enabled?".into(),
+                "switch_is_active".into(),
+            ],
+            stale_after_days: 90,
+        }
+    }
+}
+
 /// Controls extraction of AI prompt and agent session data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::struct_excessive_bools)]
@@ -469,6 +1122,9 @@ pub struct RenderersSection {
     /// Config for the `risk-map` renderer.
     #[serde(default, rename = "risk-map")]
     pub risk_map: RiskMapConfig,
+    /// Config for the `codeowners` renderer.
+    #[serde(default)]
+    pub codeowners: CodeownersConfig,
 }
 
 impl Default for RenderersSection {
@@ -481,6 +1137,7 @@ impl Default for RenderersSection {
             topos_spec: ToposSpecConfig::default(),
             report: ReportConfig::default(),
             risk_map: RiskMapConfig::default(),
+            codeowners: CodeownersConfig::default(),
         }
     }
 }
@@ -602,6 +1259,32 @@ impl Default for RiskMapConfig {
     }
 }
 
+/// Per-renderer config for `codeowners`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeownersConfig {
+    /// Max owners proposed per rule.
+    pub max_owners: u32,
+    /// Min blended share of a directory's surviving lines and commits for
+    /// a contributor to be proposed as owner.
+    pub min_share: f64,
+    /// Directories with fewer commits inherit their parent's owners.
+    pub min_commits: u32,
+    /// Deepest directory level that gets its own rule.
+    pub max_depth: u32,
+}
+
+impl Default for CodeownersConfig {
+    fn default() -> Self {
+        Self {
+            max_owners: 3,
+            min_share: 0.25,
+            min_commits: 5,
+            max_depth: 3,
+        }
+    }
+}
+
 /// LLM provider configuration for semantic analysis.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmSection {
@@ -797,6 +1480,32 @@ every_n_commits = 50
         assert_eq!(config.graph.snapshots.every_n_commits, 50);
     }
 
+    #[test]
+    fn team_roster_from_toml() {
+        let toml_str = r#"
+[analysis]
+depth = "standard"
+llm_salience_threshold = 0.7
+max_llm_batch_size = 50
+
+[analysis.teams]
+min_co_changes = 5
+
+[analysis.teams.roster]
+payments = ["alice@example.com", "*@billing.example.com"]
+"#;
+        let config: HomerConfig = toml::from_str(toml_str).unwrap();
+        let teams = &config.analysis.teams;
+        assert_eq!(teams.min_co_changes, 5);
+        assert!(teams.use_codeowners, "Unset keys keep defaults");
+        assert_eq!(teams.roster["payments"].len(), 2);
+
+        // The roster table must serialize after the scalar keys.
+        let text = toml::to_string(&config).unwrap();
+        let reparsed: HomerConfig = toml::from_str(&text).unwrap();
+        assert_eq!(reparsed.analysis.teams.roster, teams.roster);
+    }
+
     #[test]
     fn mcp_section_defaults() {
         let config = HomerConfig::default();
@@ -3,8 +3,10 @@
 //! This module centralizes role and key strings that are shared between
 //! extractors, analyzers, renderers, CLI, and MCP integration points.
 
+use std::collections::HashMap;
+
 use crate::store::HomerStore;
-use crate::types::{HyperedgeMember, NodeFilter, NodeId, NodeKind};
+use crate::types::{HyperedgeKind, HyperedgeMember, NodeFilter, NodeId, NodeKind};
 
 /// Canonical hyperedge member roles and legacy aliases.
 pub mod roles {
@@ -24,6 +26,61 @@ pub mod roles {
     pub const CODE_ENTITY_LEGACY_A: &str = "entity";
     pub const CODE_ENTITY_LEGACY_B: &str = "subject";
 
+    /// Owns edge roles.
+    pub const OWNER: &str = "owner";
+    pub const OWNED: &str = "file";
+
+    /// Tests edge roles.
+    pub const TEST: &str = "test";
+    pub const TESTED: &str = "tested";
+
+    /// Needs edge roles.
+    pub const DEPENDENT_JOB: &str = "job";
+    pub const NEEDED_JOB: &str = "needed";
+
+    /// Triggers edge roles.
+    pub const TRIGGERED_JOB: &str = "job";
+    pub const TRIGGERING_FILE: &str = "file";
+
+    /// Uses edge roles.
+    pub const DEPENDENT_RESOURCE: &str = "resource";
+    pub const DEPENDENCY: &str = "dependency";
+
+    /// Builds edge roles.
+    pub const BUILT_RESOURCE: &str = "resource";
+    pub const BUILD_SOURCE: &str = "source";
+
+    /// Defines edge roles.
+    pub const DEFINER: &str = "definer";
+    pub const DEFINED_VAR: &str = "variable";
+
+    /// Reads edge roles.
+    pub const READER: &str = "reader";
+    pub const READ_VAR: &str = "variable";
+
+    /// Checks edge roles.
+    pub const CHECKER: &str = "checker";
+    pub const CHECKED_FLAG: &str = "flag";
+
+    /// References edge roles.
+    pub const REFERRER: &str = "referrer";
+    pub const REFERENCED: &str = "referenced";
+
+    /// Binds edge roles.
+    pub const CONTRACT_ELEMENT: &str = "contract";
+    pub const BOUND_CODE: &str = "code";
+
+    /// Accesses edge roles.
+    pub const ACCESSOR: &str = "accessor";
+    pub const ACCESSED: &str = "accessed";
+
+    /// Aliases edge roles. Extractions use `source`/`extracted` instead, so
+    /// the source keeps its own identity.
+    pub const ALIAS_OLD: &str = "old";
+    pub const ALIAS_NEW: &str = "new";
+    pub const EXTRACTED_FROM: &str = "source";
+    pub const EXTRACTED: &str = "extracted";
+
     /// Generic containment roles.
     pub const MEMBER: &str = "member";
     pub const CONTAINER: &str = "container";
@@ -96,6 +153,76 @@ pub fn read_top_contributor_share(data: &serde_json::Value) -> Option<f64> {
         })
 }
 
+/// The author of one non-merge commit and the files it touched.
+#[derive(Debug, Clone)]
+pub struct CommitChange {
+    pub author: Option<NodeId>,
+    pub files: Vec<NodeId>,
+}
+
+/// Author and touched files of every non-merge commit, from `Authored` and
+/// `Modifies` edges. Merge commits are skipped so whoever merges a branch is
+/// not credited with every file on it.
+pub async fn load_commit_changes(
+    store: &dyn HomerStore,
+) -> crate::error::Result<Vec<CommitChange>> {
+    let commit_author: HashMap<NodeId, NodeId> = store
+        .get_edges_by_kind(HyperedgeKind::Authored)
+        .await?
+        .iter()
+        .filter_map(|edge| {
+            let author = edge.members.iter().find(|m| m.role == "author")?;
+            let commit = edge.members.iter().find(|m| m.role == "commit")?;
+            Some((commit.node_id, author.node_id))
+        })
+        .collect();
+
+    let mut changes = Vec::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Modifies).await? {
+        if edge
+            .metadata
+            .get("merge")
+            .and_then(serde_json::Value::as_bool)
+            == Some(true)
+        {
+            continue;
+        }
+        let author = edge
+            .members
+            .iter()
+            .find(|m| m.role == "commit")
+            .and_then(|m| commit_author.get(&m.node_id).copied());
+        let files = edge
+            .members
+            .iter()
+            .filter(|m| m.role == "file")
+            .map(|m| m.node_id)
+            .collect();
+        changes.push(CommitChange { author, files });
+    }
+    Ok(changes)
+}
+
+/// File → author → non-merge commits touching it.
+pub async fn load_file_authors(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<NodeId, HashMap<NodeId, u64>>> {
+    let mut file_authors: HashMap<NodeId, HashMap<NodeId, u64>> = HashMap::new();
+    for change in load_commit_changes(store).await? {
+        let Some(author) = change.author else {
+            continue;
+        };
+        for file in change.files {
+            *file_authors
+                .entry(file)
+                .or_default()
+                .entry(author)
+                .or_default() += 1;
+        }
+    }
+    Ok(file_authors)
+}
+
 /// Find the repository root `Module` node.
 ///
 /// Preferred order:
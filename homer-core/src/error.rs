@@ -49,6 +49,10 @@ pub enum StoreError {
     #[error("Node not found: {0}")]
     NodeNotFound(String),
 
+    /// A stored value could not be read back.
+    #[error("Invalid stored value: {0}")]
+    InvalidValue(String),
+
     /// JSON serialization/deserialization of metadata failed.
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
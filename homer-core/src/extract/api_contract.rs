@@ -0,0 +1,1629 @@
+// API contract extraction: protobuf, OpenAPI and GraphQL schemas become
+// `Type` and `Endpoint` nodes bound to the code that implements them.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use glob::Pattern;
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::config::{ContractExtractionConfig, HomerConfig};
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeFilter, NodeId, NodeKind,
+};
+
+use super::traits::{ExtractStats, Extractor};
+use super::yaml;
+
+/// Code definitions sharing one name beyond which a name is too generic to
+/// bind.
+const MAX_BINDINGS: usize = 25;
+
+/// Shortest normalized operation name bound by name.
+const MIN_OPERATION_LEN: usize = 5;
+
+/// Protobuf scalar types, which are never references.
+const PROTO_SCALARS: &[&str] = &[
+    "double", "float", "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32",
+    "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes",
+];
+
+/// `GraphQL` built-in scalars.
+const GRAPHQL_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "patch", "head", "options", "trace",
+];
+
+/// One element of an API contract. Messages, enums, schemas and `GraphQL`
+/// types become `Type` nodes; gRPC methods, `OpenAPI` operations and
+/// `GraphQL` root fields become `Endpoint` nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractElement {
+    /// `NodeKind::Type` or `NodeKind::Endpoint`.
+    pub kind: NodeKind,
+    /// `protobuf`, `openapi` or `graphql`.
+    pub format: &'static str,
+    /// Repo-relative schema path.
+    pub file: String,
+    /// Name within the schema: `User`, `Outer.Inner`, `UserService.GetUser`,
+    /// `GET /users/{id}`, `Query.user`.
+    pub name: String,
+    /// 1-based line of the declaration, when known.
+    pub line: Option<usize>,
+    /// Operation name code is matched against.
+    pub operation: Option<String>,
+    /// HTTP route template.
+    pub route: Option<String>,
+    /// Type names the element refers to, as written.
+    pub references: Vec<String>,
+    /// Format-specific details stored on the node.
+    pub details: serde_json::Map<String, Value>,
+}
+
+impl ContractElement {
+    fn new(kind: NodeKind, format: &'static str, file: &str, name: String) -> Self {
+        Self {
+            kind,
+            format,
+            file: file.to_string(),
+            name,
+            line: None,
+            operation: None,
+            route: None,
+            references: Vec::new(),
+            details: serde_json::Map::new(),
+        }
+    }
+
+    /// Node name: `<schema file>::<name>`.
+    pub fn node_name(&self) -> String {
+        format!("{}::{}", self.file, self.name)
+    }
+
+    fn refer(&mut self, name: &str) {
+        let name = name.trim_start_matches('.');
+        if !name.is_empty() && !self.references.iter().any(|r| r == name) {
+            self.references.push(name.to_string());
+        }
+    }
+}
+
+// ── Tokenizing ────────────────────────────────────────────────────
+
+/// Identifiers (with dots), strings and single punctuation characters, each
+/// with its 1-based line. `#` starts a comment when `hash_comments` is set;
+/// `//` and `/* */` comments are always skipped.
+fn tokenize(text: &str, hash_comments: bool) -> Vec<(String, usize)> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() || c == ',' && hash_comments => {}
+            '#' if hash_comments => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if !hash_comments && chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if !hash_comments && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' | '\'' => {
+                let start = line;
+                let mut literal = String::from('"');
+                // GraphQL block strings: """ ... """
+                let block = c == '"' && chars.peek() == Some(&'"') && {
+                    chars.next();
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        true
+                    } else {
+                        literal.push('"');
+                        tokens.push((literal.clone(), start));
+                        continue;
+                    }
+                };
+                let mut quotes = 0;
+                while let Some(s) = chars.next() {
+                    if s == '\n' {
+                        line += 1;
+                    }
+                    if s == '\\' {
+                        chars.next();
+                        continue;
+                    }
+                    if block {
+                        quotes = if s == '"' { quotes + 1 } else { 0 };
+                        if quotes == 3 {
+                            break;
+                        }
+                    } else if s == c {
+                        break;
+                    }
+                    literal.push(s);
+                }
+                literal.push('"');
+                tokens.push((literal, start));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => {
+                let mut word = String::from(c);
+                while let Some(&n) = chars.peek() {
+                    if !(n.is_alphanumeric() || n == '_' || n == '.') {
+                        break;
+                    }
+                    word.push(n);
+                    chars.next();
+                }
+                tokens.push((word, line));
+            }
+            c => tokens.push((c.to_string(), line)),
+        }
+    }
+    tokens
+}
+
+/// A cursor over tokens.
+struct Cursor<'a> {
+    tokens: &'a [(String, usize)],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(tokens: &'a [(String, usize)]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).map(|(t, _)| t.as_str())
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a str> {
+        self.tokens.get(self.pos + offset).map(|(t, _)| t.as_str())
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.pos).map_or(0, |(_, l)| *l)
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek()?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skip past the next `end` at this nesting level.
+    fn skip_past(&mut self, end: &str) {
+        let mut depth = 0usize;
+        while let Some(token) = self.next() {
+            match token {
+                "{" | "(" | "[" => depth += 1,
+                "}" | ")" | "]" if depth > 0 => depth -= 1,
+                t if t == end && depth == 0 => return,
+                "}" => {
+                    self.pos -= 1;
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip a balanced group starting at the current opening token.
+    fn skip_group(&mut self) {
+        let (open, close) = match self.peek() {
+            Some("{") => ("{", "}"),
+            Some("(") => ("(", ")"),
+            Some("[") => ("[", "]"),
+            _ => return,
+        };
+        let mut depth = 0usize;
+        while let Some(token) = self.next() {
+            if token == open {
+                depth += 1;
+            } else if token == close {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// ── Protobuf ──────────────────────────────────────────────────────
+
+/// Messages, enums and service methods of a `.proto` file. Nested
+/// declarations are named `Outer.Inner`.
+pub fn parse_proto(path: &str, text: &str) -> Vec<ContractElement> {
+    let tokens = tokenize(text, false);
+    let mut cursor = Cursor::new(&tokens);
+    let mut parser = ProtoParser {
+        path,
+        package: String::new(),
+        out: Vec::new(),
+    };
+    parser.body(&mut cursor, "", None);
+    let package = parser.package;
+    let mut out = parser.out;
+    if !package.is_empty() {
+        for element in &mut out {
+            element.detail("package", package.as_str());
+        }
+    }
+    out
+}
+
+impl ContractElement {
+    fn detail(&mut self, key: &str, value: impl Into<Value>) {
+        self.details.insert(key.to_string(), value.into());
+    }
+}
+
+struct ProtoParser<'p> {
+    path: &'p str,
+    package: String,
+    out: Vec<ContractElement>,
+}
+
+impl ProtoParser<'_> {
+    /// Declarations until the closing brace; `message` is the index of the
+    /// enclosing message, whose field types are collected.
+    fn body(&mut self, cursor: &mut Cursor<'_>, prefix: &str, message: Option<usize>) {
+        while let Some(token) = cursor.peek() {
+            let line = cursor.line();
+            match token {
+                "}" => {
+                    cursor.next();
+                    return;
+                }
+                "package" => {
+                    cursor.next();
+                    self.package = cursor.next().unwrap_or_default().to_string();
+                    cursor.skip_past(";");
+                }
+                "message" | "enum" if cursor.peek_at(2) == Some("{") => {
+                    cursor.next();
+                    let name = format!("{prefix}{}", cursor.next().unwrap_or_default());
+                    cursor.next();
+                    let mut element =
+                        ContractElement::new(NodeKind::Type, "protobuf", self.path, name.clone());
+                    element.line = Some(line);
+                    element.detail("declaration", token);
+                    self.out.push(element);
+                    if token == "message" {
+                        let index = self.out.len() - 1;
+                        self.body(cursor, &format!("{name}."), Some(index));
+                    } else {
+                        cursor.pos -= 1;
+                        cursor.skip_group();
+                    }
+                }
+                "service" if cursor.peek_at(2) == Some("{") => {
+                    cursor.next();
+                    let service = cursor.next().unwrap_or_default().to_string();
+                    cursor.next();
+                    self.service(cursor, &service);
+                }
+                "oneof" if cursor.peek_at(2) == Some("{") => {
+                    cursor.next();
+                    cursor.next();
+                    cursor.next();
+                    self.body(cursor, prefix, message);
+                }
+                "extend" | "option" | "import" | "syntax" | "edition" | "reserved"
+                | "extensions" => {
+                    cursor.next();
+                    if cursor.peek_at(1) == Some("{") {
+                        cursor.next();
+                        cursor.skip_group();
+                    } else {
+                        cursor.skip_past(";");
+                    }
+                }
+                _ => {
+                    let field_types = proto_field_types(cursor);
+                    if let Some(index) = message {
+                        for name in field_types {
+                            self.out[index].refer(&name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn service(&mut self, cursor: &mut Cursor<'_>, service: &str) {
+        while let Some(token) = cursor.peek() {
+            let line = cursor.line();
+            match token {
+                "}" => {
+                    cursor.next();
+                    return;
+                }
+                "rpc" => {
+                    cursor.next();
+                    let method = cursor.next().unwrap_or_default().to_string();
+                    let mut element = ContractElement::new(
+                        NodeKind::Endpoint,
+                        "protobuf",
+                        self.path,
+                        format!("{service}.{method}"),
+                    );
+                    element.line = Some(line);
+                    element.operation = Some(method);
+                    element.detail("protocol", "grpc");
+                    element.detail("service", service);
+                    for key in ["request", "response"] {
+                        if key == "response" {
+                            cursor.eat("returns");
+                        }
+                        cursor.eat("(");
+                        let streaming = cursor.eat("stream");
+                        let name = cursor.next().unwrap_or_default().to_string();
+                        cursor.eat(")");
+                        element.refer(&name);
+                        element.detail(key, name.trim_start_matches('.'));
+                        if streaming {
+                            element.detail(&format!("{key}_streaming"), true);
+                        }
+                    }
+                    if cursor.peek() == Some("{") {
+                        cursor.skip_group();
+                    } else {
+                        cursor.eat(";");
+                    }
+                    self.out.push(element);
+                }
+                _ => cursor.skip_past(";"),
+            }
+        }
+    }
+}
+
+/// Message and enum types of one field declaration, consumed through `;`.
+fn proto_field_types(cursor: &mut Cursor<'_>) -> Vec<String> {
+    let mut types = Vec::new();
+    let mut first = true;
+    while let Some(token) = cursor.next() {
+        match token {
+            ";" => break,
+            "}" => {
+                cursor.pos -= 1;
+                break;
+            }
+            "=" | "[" => {
+                cursor.pos -= 1;
+                cursor.skip_past(";");
+                break;
+            }
+            "map" if cursor.peek() == Some("<") => {
+                cursor.next();
+                while let Some(t) = cursor.next() {
+                    if t == ">" {
+                        break;
+                    }
+                    if t != "," && !PROTO_SCALARS.contains(&t) {
+                        types.push(t.to_string());
+                    }
+                }
+                first = false;
+            }
+            "repeated" | "optional" | "required" => {}
+            t if first => {
+                if !PROTO_SCALARS.contains(&t) {
+                    types.push(t.to_string());
+                }
+                first = false;
+            }
+            _ => {}
+        }
+    }
+    types
+}
+
+// ── GraphQL ───────────────────────────────────────────────────────
+
+/// Types of a `GraphQL` schema, with the fields of the root operation types
+/// (`Query`, `Mutation`, `Subscription`, or those `schema` names) as
+/// endpoints.
+pub fn parse_graphql(path: &str, text: &str) -> Vec<ContractElement> {
+    let tokens = tokenize(text, true);
+    let roots = graphql_roots(&tokens);
+    let mut out = Vec::new();
+    let mut cursor = Cursor::new(&tokens);
+    while let Some(token) = cursor.peek() {
+        let line = cursor.line();
+        cursor.next();
+        match token {
+            "type" | "interface" | "input" => {
+                let Some(name) = cursor.next() else {
+                    break;
+                };
+                let mut header = Vec::new();
+                while let Some(t) = cursor.peek() {
+                    if t == "{" || is_graphql_definition(t) {
+                        break;
+                    }
+                    if t == "(" {
+                        cursor.skip_group();
+                    } else if t == "@" {
+                        cursor.pos += 2;
+                    } else {
+                        header.push(cursor.next().unwrap_or_default());
+                    }
+                }
+                let fields = if cursor.eat("{") {
+                    graphql_fields(&mut cursor)
+                } else {
+                    Vec::new()
+                };
+                if let Some(operation) = roots.get(name) {
+                    out.extend(graphql_endpoints(path, name, operation, &fields));
+                    continue;
+                }
+                let mut element =
+                    ContractElement::new(NodeKind::Type, "graphql", path, name.to_string());
+                element.line = Some(line);
+                element.detail("declaration", token);
+                // `implements A & B` names interfaces.
+                for interface in header
+                    .iter()
+                    .skip_while(|t| **t != "implements")
+                    .skip(1)
+                    .filter(|t| **t != "&")
+                {
+                    element.refer(interface);
+                }
+                for field in &fields {
+                    for reference in &field.types {
+                        element.refer(reference);
+                    }
+                }
+                element.detail(
+                    "fields",
+                    fields.iter().map(|f| f.name.clone()).collect::<Vec<_>>(),
+                );
+                out.push(element);
+            }
+            "enum" | "union" | "scalar" => {
+                let Some(name) = cursor.next() else {
+                    break;
+                };
+                let mut element =
+                    ContractElement::new(NodeKind::Type, "graphql", path, name.to_string());
+                element.line = Some(line);
+                element.detail("declaration", token);
+                while let Some(t) = cursor.peek() {
+                    if is_graphql_definition(t) {
+                        break;
+                    }
+                    match t {
+                        "{" | "(" => cursor.skip_group(),
+                        "@" => {
+                            cursor.next();
+                            cursor.next();
+                        }
+                        _ => {
+                            cursor.next();
+                            if token == "union" && t != "=" && t != "|" {
+                                element.refer(t);
+                            }
+                        }
+                    }
+                }
+                out.push(element);
+            }
+            "{" | "(" => {
+                cursor.pos -= 1;
+                cursor.skip_group();
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// One endpoint per field of a root operation type.
+fn graphql_endpoints(
+    path: &str,
+    root: &str,
+    operation: &str,
+    fields: &[GraphqlField],
+) -> Vec<ContractElement> {
+    fields
+        .iter()
+        .map(|field| {
+            let mut element = ContractElement::new(
+                NodeKind::Endpoint,
+                "graphql",
+                path,
+                format!("{root}.{}", field.name),
+            );
+            element.line = Some(field.line);
+            element.operation = Some(field.name.clone());
+            element.detail("protocol", "graphql");
+            element.detail("operation_type", operation);
+            for reference in &field.types {
+                element.refer(reference);
+            }
+            element
+        })
+        .collect()
+}
+
+/// Root operation types by name: `Query`, `Mutation` and `Subscription`, or
+/// those a `schema { query: Root }` block names instead.
+fn graphql_roots(tokens: &[(String, usize)]) -> HashMap<String, String> {
+    let mut roots: HashMap<String, String> = ["query", "mutation", "subscription"]
+        .iter()
+        .map(|op| {
+            let mut name = op.to_string();
+            name[..1].make_ascii_uppercase();
+            (name, (*op).to_string())
+        })
+        .collect();
+    let mut cursor = Cursor::new(tokens);
+    while let Some(token) = cursor.next() {
+        if token == "schema" && cursor.peek() == Some("{") {
+            roots.clear();
+            cursor.next();
+            while let (Some(op), Some(":"), Some(name)) =
+                (cursor.peek(), cursor.peek_at(1), cursor.peek_at(2))
+            {
+                roots.insert(name.to_string(), op.to_string());
+                cursor.pos += 3;
+            }
+        }
+    }
+    roots
+}
+
+fn is_graphql_definition(token: &str) -> bool {
+    matches!(
+        token,
+        "type"
+            | "interface"
+            | "input"
+            | "enum"
+            | "union"
+            | "scalar"
+            | "schema"
+            | "extend"
+            | "directive"
+    )
+}
+
+struct GraphqlField {
+    name: String,
+    line: usize,
+    /// Named types of the field and its arguments, built-in scalars left out.
+    types: Vec<String>,
+}
+
+/// Fields of a `{ ... }` block, consumed through the closing brace.
+fn graphql_fields(cursor: &mut Cursor<'_>) -> Vec<GraphqlField> {
+    let mut fields = Vec::new();
+    while let Some(token) = cursor.peek() {
+        if token == "}" {
+            cursor.next();
+            break;
+        }
+        if token.starts_with('"') {
+            cursor.next();
+            continue;
+        }
+        let line = cursor.line();
+        cursor.next();
+        let mut field = GraphqlField {
+            name: token.to_string(),
+            line,
+            types: Vec::new(),
+        };
+        if cursor.eat("(") {
+            while let Some(t) = cursor.peek() {
+                if t == ")" {
+                    cursor.next();
+                    break;
+                }
+                if t.starts_with('"') {
+                    cursor.next();
+                } else if cursor.peek_at(1) == Some(":") {
+                    cursor.pos += 2;
+                    field.types.extend(graphql_type(cursor));
+                } else {
+                    graphql_skip_extras(cursor);
+                }
+            }
+        }
+        if cursor.eat(":") {
+            field.types.extend(graphql_type(cursor));
+        }
+        graphql_skip_extras(cursor);
+        fields.push(field);
+    }
+    fields
+}
+
+/// The named type of a type expression (`[User!]!` → `User`), unless it is
+/// a built-in scalar.
+fn graphql_type(cursor: &mut Cursor<'_>) -> Option<String> {
+    while cursor.eat("[") {}
+    let name = cursor.next()?.to_string();
+    while matches!(cursor.peek(), Some("]" | "!")) {
+        cursor.next();
+    }
+    (!GRAPHQL_SCALARS.contains(&name.as_str())).then_some(name)
+}
+
+/// Skip default values and directives after a field or argument.
+fn graphql_skip_extras(cursor: &mut Cursor<'_>) {
+    loop {
+        match cursor.peek() {
+            Some("=") => {
+                cursor.next();
+                if matches!(cursor.peek(), Some("{" | "[")) {
+                    cursor.skip_group();
+                } else {
+                    cursor.next();
+                }
+            }
+            Some("@") => {
+                cursor.next();
+                cursor.next();
+                if cursor.peek() == Some("(") {
+                    cursor.skip_group();
+                }
+            }
+            _ => return,
+        }
+    }
+}
+
+// ── OpenAPI ───────────────────────────────────────────────────────
+
+/// Schemas (`components.schemas`, or Swagger `definitions`) and operations of
+/// an `OpenAPI` or Swagger document. Operations are named
+/// `<METHOD> <route>`; Swagger's `basePath` is kept with each route.
+pub fn parse_openapi(path: &str, doc: &Value, text: &str) -> Vec<ContractElement> {
+    let mut out = Vec::new();
+    let line_of = |needle: &str| {
+        text.lines()
+            .position(|l| {
+                l.trim_start()
+                    .trim_start_matches(['"', '\''])
+                    .starts_with(needle)
+            })
+            .map(|i| i + 1)
+    };
+    let schemas = doc
+        .pointer("/components/schemas")
+        .or_else(|| doc.get("definitions"))
+        .and_then(Value::as_object);
+    for (name, schema) in schemas.into_iter().flatten() {
+        let mut element = ContractElement::new(NodeKind::Type, "openapi", path, name.clone());
+        element.line = line_of(&format!("{name}:")).or_else(|| line_of(&format!("{name}\":")));
+        for reference in schema_refs(schema) {
+            element.refer(&reference);
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            element.detail("fields", properties.keys().cloned().collect::<Vec<_>>());
+        }
+        out.push(element);
+    }
+
+    let base = doc
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    for (route, item) in doc
+        .get("paths")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let shared = schema_refs(item.get("parameters").unwrap_or(&Value::Null));
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method) else {
+                continue;
+            };
+            let method = method.to_ascii_uppercase();
+            let mut element = ContractElement::new(
+                NodeKind::Endpoint,
+                "openapi",
+                path,
+                format!("{method} {route}"),
+            );
+            element.line =
+                line_of(&format!("{route}:")).or_else(|| line_of(&format!("{route}\":")));
+            element.route = Some(format!("{base}{route}"));
+            element.operation = operation
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map(String::from);
+            element.detail("protocol", "http");
+            element.detail("method", method.as_str());
+            if let Some(summary) = operation.get("summary").and_then(Value::as_str) {
+                element.detail("summary", summary);
+            }
+            for reference in shared.iter().chain(&schema_refs(operation)) {
+                element.refer(reference);
+            }
+            out.push(element);
+        }
+    }
+    out
+}
+
+/// Schema names of every local `$ref` under `value`.
+fn schema_refs(value: &Value) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(map) => {
+                if let Some(target) = map.get("$ref").and_then(Value::as_str) {
+                    let name = target.rsplit('/').next().unwrap_or(target);
+                    if target.starts_with("#/") && !refs.iter().any(|r| r == name) {
+                        refs.push(name.to_string());
+                    }
+                }
+                stack.extend(map.values());
+            }
+            Value::Array(items) => stack.extend(items),
+            _ => {}
+        }
+    }
+    refs
+}
+
+// ── Discovery ─────────────────────────────────────────────────────
+
+/// Parse one schema file by extension; YAML and JSON only when they are an
+/// `OpenAPI` or Swagger document.
+pub fn parse_schema(path: &str, text: &str) -> Vec<ContractElement> {
+    let extension = Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("proto") => parse_proto(path, text),
+        Some("graphql" | "graphqls" | "gql") => parse_graphql(path, text),
+        Some("json") => serde_json::from_str::<Value>(text)
+            .ok()
+            .filter(is_openapi)
+            .map(|doc| parse_openapi(path, &doc, text))
+            .unwrap_or_default(),
+        Some("yaml" | "yml") => Some(yaml::parse(text))
+            .filter(is_openapi)
+            .map(|doc| parse_openapi(path, &doc, text))
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_openapi(doc: &Value) -> bool {
+    doc.get("openapi").is_some() || doc.get("swagger").is_some()
+}
+
+/// Every schema matching the configured globs and not excluded: repo-
+/// relative path and contents, sorted by path.
+fn discover(
+    repo_path: &Path,
+    settings: &ContractExtractionConfig,
+    exclude: &[String],
+) -> Vec<(String, String)> {
+    if !settings.enabled {
+        return Vec::new();
+    }
+    let exclude: Vec<Pattern> = exclude
+        .iter()
+        .filter_map(|p| Pattern::new(p).ok())
+        .collect();
+    let root = Pattern::escape(&repo_path.to_string_lossy());
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for pattern in &settings.schemas {
+        let Ok(paths) = glob::glob(&format!("{root}/{pattern}")) else {
+            continue;
+        };
+        for path in paths.flatten() {
+            let relative = path
+                .strip_prefix(repo_path)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if exclude.iter().any(|p| p.matches(&relative)) || !seen.insert(relative.clone()) {
+                continue;
+            }
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                found.push((relative, text));
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+fn fingerprint(files: &[(String, String)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    files.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Resolve each element's references to the type elements they name, in the
+/// same format: by name within the schema (`Outer.Inner`), by
+/// package-qualified name, or by simple name, preferring the same file.
+fn resolve_references(elements: &[ContractElement]) -> Vec<(usize, usize)> {
+    let mut index: HashMap<(&str, String), Vec<usize>> = HashMap::new();
+    for (i, element) in elements.iter().enumerate() {
+        if element.kind != NodeKind::Type {
+            continue;
+        }
+        let mut keys = vec![element.name.clone()];
+        if let Some((_, simple)) = element.name.rsplit_once('.') {
+            keys.push(simple.to_string());
+        }
+        if let Some(package) = element.details.get("package").and_then(Value::as_str) {
+            keys.push(format!("{package}.{}", element.name));
+        }
+        for key in keys {
+            index.entry((element.format, key)).or_default().push(i);
+        }
+    }
+    let mut pairs = Vec::new();
+    for (i, element) in elements.iter().enumerate() {
+        for reference in &element.references {
+            let simple = reference.rsplit('.').next().unwrap_or(reference);
+            let candidates = index
+                .get(&(element.format, reference.clone()))
+                .or_else(|| index.get(&(element.format, simple.to_string())));
+            let Some(candidates) = candidates else {
+                continue;
+            };
+            let target = candidates
+                .iter()
+                .find(|&&c| elements[c].file == element.file)
+                .or_else(|| candidates.first());
+            if let Some(&target) = target {
+                if target != i && !pairs.contains(&(i, target)) {
+                    pairs.push((i, target));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+// ── Binding code ──────────────────────────────────────────────────
+//
+// Code binds to contracts by name, across languages, which covers generated
+// and handwritten servers and clients alike. HTTP operations also bind to
+// the function holding a matching route literal. Names shared by more than
+// `MAX_BINDINGS` definitions are skipped as too generic.
+
+/// Lowercase alphanumerics of a name, so `GetUser`, `get_user` and
+/// `getUser` compare equal.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Last segment of a definition's qualified name.
+fn simple_name(name: &str) -> &str {
+    let name = name.rsplit("::").next().unwrap_or(name);
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Code names an operation binds to, normalized, bare or with a
+/// `handle`/`Handler`/`Async` affix.
+fn operation_keys(operation: &str) -> Vec<String> {
+    let op = normalize(operation);
+    if op.len() < MIN_OPERATION_LEN {
+        return Vec::new();
+    }
+    vec![
+        format!("handle{op}"),
+        format!("{op}handler"),
+        format!("{op}async"),
+        op,
+    ]
+}
+
+/// A route template with every path parameter (`{id}`, `:id`, `<int:id>`,
+/// `${id}`, `*`) replaced by `{}` and the query string dropped. `None` for
+/// strings that are not a route with at least one literal segment.
+fn normalize_route(route: &str) -> Option<String> {
+    let route = route.split(['?', '#']).next()?.trim_end_matches('/');
+    if !route.starts_with('/') || route.contains(char::is_whitespace) {
+        return None;
+    }
+    let mut literal = false;
+    let segments: Vec<&str> = route[1..]
+        .split('/')
+        .map(|s| {
+            if s.starts_with(['{', ':', '<', '*', '$', '[']) {
+                "{}"
+            } else {
+                literal |= !s.is_empty();
+                s
+            }
+        })
+        .collect();
+    literal.then(|| format!("/{}", segments.join("/")))
+}
+
+/// Route-shaped string literals of a source file, with their 0-based rows.
+fn route_literals(text: &str) -> Vec<(String, usize)> {
+    let mut found = Vec::new();
+    for (row, line) in text.lines().enumerate() {
+        let mut rest = line;
+        while let Some(start) = rest.find(['"', '\'', '`']) {
+            let quote = rest[start..].chars().next().unwrap_or('"');
+            let after = &rest[start + 1..];
+            let Some(end) = after.find(quote) else {
+                break;
+            };
+            if let Some(route) = normalize_route(&after[..end]) {
+                found.push((route, row));
+            }
+            rest = &after[end + 1..];
+        }
+    }
+    found
+}
+
+/// Whether a route literal in code matches an operation's route: equal, or
+/// ending in it under a mount prefix when the route has two or more segments.
+fn route_matches(code: &str, route: &str) -> bool {
+    code == route || (route.matches('/').count() >= 2 && code.ends_with(route))
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Stores contract elements with `References` edges to the types they refer
+/// to, so a change to a shared message reaches every endpoint that carries
+/// it.
+#[derive(Debug)]
+pub struct ContractExtractor {
+    repo_path: PathBuf,
+    settings: ContractExtractionConfig,
+    exclude: Vec<String>,
+}
+
+impl ContractExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.contracts.clone(),
+            exclude: config.extraction.structure.exclude_patterns.clone(),
+        }
+    }
+
+    fn schemas(&self) -> Vec<(String, String)> {
+        discover(&self.repo_path, &self.settings, &self.exclude)
+    }
+
+    /// Schemas plus the graph checkpoint, since bindings follow the code.
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let graph = store
+            .get_checkpoint("graph_last_sha")
+            .await?
+            .unwrap_or_default();
+        Ok(format!("{graph}:{:x}", fingerprint(&self.schemas())))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for ContractExtractor {
+    fn name(&self) -> &'static str {
+        "contracts"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "contracts_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "contracts_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        store
+            .delete_edges_by_kind(HyperedgeKind::References)
+            .await?;
+        store.delete_edges_by_kind(HyperedgeKind::Binds).await?;
+
+        let schemas = self.schemas();
+        let elements: Vec<ContractElement> = schemas
+            .iter()
+            .flat_map(|(path, text)| parse_schema(path, text))
+            .collect();
+        let ids = store_elements(store, &elements, &mut stats).await?;
+
+        for (from, to) in resolve_references(&elements) {
+            link(
+                store,
+                HyperedgeKind::References,
+                (ids[from], roles::REFERRER),
+                (ids[to], roles::REFERENCED),
+                1.0,
+                serde_json::json!({ "format": elements[from].format }),
+            )
+            .await?;
+            stats.edges_created += 1;
+        }
+        stats.edges_created += self.bind_code(store, &elements, &ids).await?;
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("contracts_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            schemas = schemas.len(),
+            elements = elements.len(),
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "Contract extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+impl ContractExtractor {
+    /// `Binds` edges from each element to the code matching it by name, and
+    /// from each HTTP operation to the code holding its route.
+    async fn bind_code(
+        &self,
+        store: &dyn HomerStore,
+        elements: &[ContractElement],
+        ids: &[NodeId],
+    ) -> crate::error::Result<u64> {
+        let mut functions: HashMap<String, Vec<NodeId>> = HashMap::new();
+        let mut types: HashMap<String, Vec<NodeId>> = HashMap::new();
+        for (kind, index) in [
+            (NodeKind::Function, &mut functions),
+            (NodeKind::Type, &mut types),
+        ] {
+            for node in store
+                .find_nodes(&NodeFilter {
+                    kind: Some(kind),
+                    ..Default::default()
+                })
+                .await?
+            {
+                if !node.metadata.contains_key("contract") {
+                    index
+                        .entry(normalize(simple_name(&node.name)))
+                        .or_default()
+                        .push(node.id);
+                }
+            }
+        }
+
+        let mut bound: HashSet<(NodeId, NodeId)> = HashSet::new();
+        let mut edges = 0;
+        for (element, &id) in elements.iter().zip(ids) {
+            let matches: Vec<NodeId> = match element.kind {
+                NodeKind::Endpoint => element
+                    .operation
+                    .as_deref()
+                    .map(operation_keys)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|key| functions.get(key))
+                    .flatten()
+                    .copied()
+                    .collect(),
+                _ => types
+                    .get(&normalize(simple_name(&element.name)))
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            if matches.len() > MAX_BINDINGS {
+                continue;
+            }
+            for code in matches {
+                if bound.insert((id, code)) {
+                    let metadata = serde_json::json!({ "match": "name" });
+                    link(
+                        store,
+                        HyperedgeKind::Binds,
+                        (id, roles::CONTRACT_ELEMENT),
+                        (code, roles::BOUND_CODE),
+                        0.7,
+                        metadata,
+                    )
+                    .await?;
+                    edges += 1;
+                }
+            }
+        }
+
+        edges += self.bind_routes(store, elements, ids, &mut bound).await?;
+        Ok(edges)
+    }
+
+    /// `Binds` edges from each HTTP operation to the innermost function
+    /// holding a string literal that matches its route, or to the file.
+    async fn bind_routes(
+        &self,
+        store: &dyn HomerStore,
+        elements: &[ContractElement],
+        ids: &[NodeId],
+        bound: &mut HashSet<(NodeId, NodeId)>,
+    ) -> crate::error::Result<u64> {
+        let routes: Vec<(NodeId, String)> = elements
+            .iter()
+            .zip(ids)
+            .filter_map(|(e, &id)| Some((id, normalize_route(e.route.as_deref()?)?)))
+            .collect();
+        if routes.is_empty() {
+            return Ok(0);
+        }
+        let mut edges = 0;
+        for file in store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            })
+            .await?
+        {
+            let Ok(text) = std::fs::read_to_string(self.repo_path.join(&file.name)) else {
+                continue;
+            };
+            let literals = route_literals(&text);
+            if literals.is_empty() {
+                continue;
+            }
+            let spans = function_spans(store, &file.name).await?;
+            for (literal, row) in literals {
+                for (id, route) in &routes {
+                    if !route_matches(&literal, route) {
+                        continue;
+                    }
+                    let code = spans
+                        .iter()
+                        .filter(|(_, start, end)| (*start..=*end).contains(&row))
+                        .min_by_key(|(_, start, end)| end - start)
+                        .map_or(file.id, |(f, _, _)| *f);
+                    if bound.insert((*id, code)) {
+                        let metadata = serde_json::json!({ "match": "route", "line": row + 1 });
+                        link(
+                            store,
+                            HyperedgeKind::Binds,
+                            (*id, roles::CONTRACT_ELEMENT),
+                            (code, roles::BOUND_CODE),
+                            0.8,
+                            metadata,
+                        )
+                        .await?;
+                        edges += 1;
+                    }
+                }
+            }
+        }
+        Ok(edges)
+    }
+}
+
+/// Functions of a file with their 0-based start and end rows.
+pub(crate) async fn function_spans(
+    store: &dyn HomerStore,
+    file: &str,
+) -> crate::error::Result<Vec<(NodeId, usize, usize)>> {
+    let functions = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Function),
+            name_prefix: Some(format!("{file}::")),
+            ..Default::default()
+        })
+        .await?;
+    Ok(functions
+        .iter()
+        .filter_map(|f| {
+            let span = f.metadata.get("span")?;
+            let row = |key: &str| {
+                span.get(key)
+                    .and_then(Value::as_u64)
+                    .and_then(|r| usize::try_from(r).ok())
+            };
+            Some((f.id, row("start_row")?, row("end_row")?))
+        })
+        .collect())
+}
+
+/// Upsert a node per element, in order, and mark contract nodes that no
+/// longer exist stale.
+async fn store_elements(
+    store: &dyn HomerStore,
+    elements: &[ContractElement],
+    stats: &mut ExtractStats,
+) -> crate::error::Result<Vec<NodeId>> {
+    let mut existing: HashMap<String, NodeId> = HashMap::new();
+    for kind in [NodeKind::Type, NodeKind::Endpoint] {
+        for node in store
+            .find_nodes(&NodeFilter {
+                kind: Some(kind),
+                ..Default::default()
+            })
+            .await?
+        {
+            if node.metadata.contains_key("contract") {
+                existing.insert(node.name, node.id);
+            }
+        }
+    }
+    let mut ids = Vec::with_capacity(elements.len());
+    let mut current = HashSet::new();
+    for element in elements {
+        let mut contract = element.details.clone();
+        contract.insert("format".to_string(), Value::from(element.format));
+        contract.insert("file".to_string(), Value::from(element.file.as_str()));
+        if let Some(line) = element.line {
+            contract.insert("line".to_string(), Value::from(line));
+        }
+        if let Some(operation) = &element.operation {
+            contract.insert("operation".to_string(), Value::from(operation.as_str()));
+        }
+        if let Some(route) = &element.route {
+            contract.insert("route".to_string(), Value::from(route.as_str()));
+        }
+        let name = element.node_name();
+        let id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: element.kind.clone(),
+                name: name.clone(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::from([
+                    ("file".to_string(), Value::from(element.file.as_str())),
+                    ("contract".to_string(), Value::Object(contract)),
+                ]),
+            })
+            .await?;
+        if existing.contains_key(&name) {
+            stats.nodes_updated += 1;
+        } else {
+            stats.nodes_created += 1;
+        }
+        current.insert(name);
+        ids.push(id);
+    }
+    for (name, id) in existing {
+        if !current.contains(&name) {
+            store.mark_node_stale(id).await?;
+        }
+    }
+    Ok(ids)
+}
+
+pub(crate) async fn link(
+    store: &dyn HomerStore,
+    kind: HyperedgeKind,
+    (from, from_role): (NodeId, &str),
+    (to, to_role): (NodeId, &str),
+    confidence: f64,
+    metadata: Value,
+) -> crate::error::Result<()> {
+    store
+        .upsert_hyperedge(&Hyperedge {
+            id: HyperedgeId(0),
+            kind,
+            members: vec![
+                HyperedgeMember {
+                    node_id: from,
+                    role: from_role.to_string(),
+                    position: 0,
+                },
+                HyperedgeMember {
+                    node_id: to,
+                    role: to_role.to_string(),
+                    position: 1,
+                },
+            ],
+            confidence,
+            last_updated: Utc::now(),
+            metadata: serde_json::from_value(metadata).unwrap_or_default(),
+        })
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protobuf_messages_services_and_references() {
+        let elements = parse_proto(
+            "proto/user.proto",
+            "syntax = \"proto3\";\n\
+             package acme.user.v1;\n\
+             import \"google/protobuf/timestamp.proto\";\n\
+             \n\
+             // A user account.\n\
+             message User {\n\
+             \x20 string id = 1;\n\
+             \x20 repeated Address addresses = 2 [deprecated = true];\n\
+             \x20 map<string, Role> roles = 3;\n\
+             \x20 google.protobuf.Timestamp created = 4;\n\
+             \x20 message Address { string city = 1; }\n\
+             \x20 oneof contact { string email = 5; Phone phone = 6; }\n\
+             }\n\
+             enum Role { ROLE_UNSPECIFIED = 0; ADMIN = 1; }\n\
+             service UserService {\n\
+             \x20 option (google.api.default_host) = \"users\";\n\
+             \x20 rpc GetUser(GetUserRequest) returns (User);\n\
+             \x20 rpc Watch(stream WatchRequest) returns (stream .acme.user.v1.User) {\n\
+             \x20   option idempotency_level = NO_SIDE_EFFECTS;\n\
+             \x20 }\n\
+             }\n",
+        );
+        let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "User",
+                "User.Address",
+                "Role",
+                "UserService.GetUser",
+                "UserService.Watch"
+            ]
+        );
+        assert_eq!(elements[0].line, Some(6));
+        assert_eq!(
+            elements[0].references,
+            vec!["Address", "Role", "google.protobuf.Timestamp", "Phone"]
+        );
+        let watch = &elements[4];
+        assert_eq!(watch.kind, NodeKind::Endpoint);
+        assert_eq!(watch.operation.as_deref(), Some("Watch"));
+        assert_eq!(watch.details["response"], "acme.user.v1.User");
+        assert_eq!(watch.details["request_streaming"], true);
+        assert_eq!(watch.details["package"], "acme.user.v1");
+
+        let pairs = resolve_references(&elements);
+        assert!(
+            pairs.contains(&(0, 1)),
+            "Nested Address resolves: {pairs:?}"
+        );
+        assert!(pairs.contains(&(0, 2)));
+        assert!(
+            pairs.contains(&(4, 0)),
+            "Package-qualified response resolves"
+        );
+    }
+
+    #[test]
+    fn graphql_types_and_root_fields() {
+        let elements = parse_graphql(
+            "schema.graphql",
+            "\"\"\"A user.\"\"\"\n\
+             type User implements Node & Entity @key(fields: \"id\") {\n\
+             \x20 id: ID!\n\
+             \x20 posts(first: Int = 10, after: String): [Post!]!\n\
+             }\n\
+             type Post { title: String author: User }\n\
+             union SearchResult = User | Post\n\
+             input NewPost { title: String! tags: [String!] = [] }\n\
+             type Query {\n\
+             \x20 # Look up a user.\n\
+             \x20 user(id: ID!): User\n\
+             \x20 search(term: String!): [SearchResult!]! @deprecated(reason: \"v2\")\n\
+             }\n\
+             extend type Mutation { createPost(input: NewPost!): Post }\n",
+        );
+        let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "User",
+                "Post",
+                "SearchResult",
+                "NewPost",
+                "Query.user",
+                "Query.search",
+                "Mutation.createPost"
+            ]
+        );
+        assert_eq!(elements[0].references, vec!["Node", "Entity", "Post"]);
+        assert_eq!(
+            elements[0].details["fields"],
+            serde_json::json!(["id", "posts"])
+        );
+        assert_eq!(elements[2].references, vec!["User", "Post"]);
+        let create = &elements[6];
+        assert_eq!(create.kind, NodeKind::Endpoint);
+        assert_eq!(create.operation.as_deref(), Some("createPost"));
+        assert_eq!(create.details["operation_type"], "mutation");
+        assert_eq!(create.references, vec!["NewPost", "Post"]);
+    }
+
+    #[test]
+    fn openapi_schemas_operations_and_routes() {
+        let text = "openapi: 3.0.3\n\
+                    paths:\n\
+                    \x20 /users/{id}:\n\
+                    \x20   parameters:\n\
+                    \x20     - $ref: '#/components/parameters/UserId'\n\
+                    \x20   get:\n\
+                    \x20     operationId: getUser\n\
+                    \x20     responses:\n\
+                    \x20       '200':\n\
+                    \x20         content:\n\
+                    \x20           application/json:\n\
+                    \x20             schema: {$ref: '#/components/schemas/User'}\n\
+                    \x20   delete:\n\
+                    \x20     responses: {'204': {description: gone}}\n\
+                    components:\n\
+                    \x20 schemas:\n\
+                    \x20   User:\n\
+                    \x20     type: object\n\
+                    \x20     properties:\n\
+                    \x20       id: {type: string}\n\
+                    \x20       team: {$ref: '#/components/schemas/Team'}\n\
+                    \x20   Team: {type: object}\n";
+        let elements = parse_schema("api/openapi.yaml", text);
+        let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Team", "User", "GET /users/{id}", "DELETE /users/{id}"]
+        );
+        assert_eq!(elements[1].references, vec!["Team"]);
+        assert_eq!(elements[1].line, Some(17));
+        let get = &elements[2];
+        assert_eq!(get.operation.as_deref(), Some("getUser"));
+        assert_eq!(get.line, Some(3));
+        assert_eq!(get.references, vec!["UserId", "User"]);
+        assert!(elements[3].operation.is_none());
+        assert!(parse_schema("config/openapi-settings.yaml", "port: 80\n").is_empty());
+
+        let route = normalize_route("/users/{id}").unwrap();
+        for literal in ["/users/:id", "/users/<int:user_id>", "/api/v1/users/${id}"] {
+            let code = normalize_route(literal).unwrap();
+            assert!(route_matches(&code, &route), "{literal} should match");
+        }
+        assert!(!route_matches(&normalize_route("/users").unwrap(), &route));
+        assert_eq!(normalize_route("/{id}"), None);
+        assert_eq!(
+            route_literals("app.get('/users/:id', handler) // \"/x y\"\n"),
+            vec![("/users/{}".to_string(), 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn binds_code_by_name_and_route() {
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("api")).unwrap();
+        std::fs::write(
+            tmp.path().join("api/openapi.json"),
+            r##"{"openapi": "3.1.0",
+                "paths": {"/users/{id}": {"get": {"operationId": "getUser",
+                  "responses": {"200": {"content": {"application/json":
+                    {"schema": {"$ref": "#/components/schemas/User"}}}}}}}},
+                "components": {"schemas": {"User": {"type": "object"}}}}"##,
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("web")).unwrap();
+        std::fs::write(
+            tmp.path().join("web/client.ts"),
+            "export async function loadProfile(id: string) {\n  return fetch(`/users/${id}`);\n}\n",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        let node = |kind, name: &str, metadata: Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        let handler = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "server/users.go::GetUserHandler",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let model = store
+            .upsert_node(&node(
+                NodeKind::Type,
+                "server/models.py::User",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        store
+            .upsert_node(&node(
+                NodeKind::File,
+                "web/client.ts",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let client = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "web/client.ts::loadProfile",
+                serde_json::json!({ "span": { "start_row": 0, "end_row": 2 } }),
+            ))
+            .await
+            .unwrap();
+
+        let extractor = ContractExtractor::new(tmp.path(), &HomerConfig::default());
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let endpoint = store
+            .get_node_by_name(NodeKind::Endpoint, "api/openapi.json::GET /users/{id}")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(endpoint.metadata["contract"]["operation"], "getUser");
+        assert_eq!(
+            store
+                .get_edges_by_kind(HyperedgeKind::References)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        let binds = store.get_edges_by_kind(HyperedgeKind::Binds).await.unwrap();
+        let bound: HashSet<NodeId> = binds
+            .iter()
+            .filter_map(|e| e.members.iter().find(|m| m.role == roles::BOUND_CODE))
+            .map(|m| m.node_id)
+            .collect();
+        assert_eq!(bound, HashSet::from([handler, model, client]));
+    }
+}
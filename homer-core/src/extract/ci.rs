@@ -0,0 +1,1437 @@
+// CI pipeline and task-runner modelling: jobs become `CiJob` nodes joined by
+// `Needs` edges, and path-filtered jobs trigger the files they cover.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use glob::{MatchOptions, Pattern};
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::config::HomerConfig;
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeFilter, NodeId, NodeKind,
+};
+
+use super::traits::{ExtractStats, Extractor};
+use super::yaml;
+
+/// CI and task-runner files, as globs relative to the repository root.
+pub const CI_LOCATIONS: &[&str] = &[
+    ".github/workflows/*.yml",
+    ".github/workflows/*.yaml",
+    ".gitlab-ci.yml",
+    ".circleci/config.yml",
+    ".circleci/config.yaml",
+    "Jenkinsfile",
+    "justfile",
+    "Justfile",
+    ".justfile",
+    "Taskfile.yml",
+    "Taskfile.yaml",
+    "Taskfile.dist.yml",
+    "Taskfile.dist.yaml",
+];
+
+/// Triggers that fire when code is pushed or proposed.
+pub const CHANGE_TRIGGERS: &[&str] = &[
+    "push",
+    "pull_request",
+    "pull_request_target",
+    "merge_group",
+    "merge_request",
+];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Top-level `.gitlab-ci.yml` keys that are not jobs.
+pub(crate) const GITLAB_RESERVED: &[&str] = &[
+    "after_script",
+    "before_script",
+    "cache",
+    "default",
+    "image",
+    "include",
+    "services",
+    "stages",
+    "types",
+    "variables",
+    "workflow",
+];
+
+/// One CI job, pipeline stage, or task-runner recipe, read from GitHub
+/// Actions, GitLab CI, `CircleCI`, a Jenkinsfile, a justfile or a Taskfile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CiJob {
+    /// What runs it: `github`, `gitlab`, `circleci`, `jenkins`, `just` or `task`.
+    pub provider: &'static str,
+    /// Repo-relative path of the defining file.
+    pub file: String,
+    /// Job id, unique within the file.
+    pub id: String,
+    /// Check name as the CI system shows it.
+    pub label: String,
+    /// Stage, for providers that group jobs into stages.
+    pub stage: Option<String>,
+    /// Events that start the job: `push`, `pull_request`, `schedule`,
+    /// `manual`, ...
+    pub triggers: Vec<String>,
+    /// Branch filters; empty means any branch.
+    pub branches: Vec<String>,
+    /// Path globs a change must touch for the job to run; empty means any.
+    pub paths: Vec<String>,
+    /// Path globs whose changes alone never start the job.
+    pub paths_ignore: Vec<String>,
+    /// Ids of jobs in the same file that must finish first.
+    pub needs: Vec<String>,
+    /// Shell commands the job runs.
+    pub commands: Vec<String>,
+}
+
+impl CiJob {
+    fn new(provider: &'static str, file: &str, id: &str, label: String) -> Self {
+        Self {
+            provider,
+            file: file.to_string(),
+            id: id.to_string(),
+            label,
+            ..Self::default()
+        }
+    }
+
+    /// Node name: `<file>::<id>`.
+    pub fn node_name(&self) -> String {
+        format!("{}::{}", self.file, self.id)
+    }
+
+    /// Whether pushing or proposing a change runs the job, as opposed to
+    /// scheduled, manual and local tasks.
+    pub fn runs_on_change(&self) -> bool {
+        self.triggers
+            .iter()
+            .any(|t| CHANGE_TRIGGERS.contains(&t.as_str()))
+    }
+
+    /// Whether the job has path filters at all.
+    pub fn is_path_filtered(&self) -> bool {
+        !self.paths.is_empty() || !self.paths_ignore.is_empty()
+    }
+
+    /// Whether a change to `path` passes the job's path filters.
+    pub fn watches(&self, path: &str) -> bool {
+        PathFilter::new(&self.paths, &self.paths_ignore).matches(path)
+    }
+}
+
+/// Compiled path filters. Include patterns follow GitHub's rules: the last
+/// matching pattern decides, and a leading `!` excludes.
+#[derive(Debug)]
+pub struct PathFilter {
+    include: Vec<(bool, Vec<Pattern>)>,
+    ignore: Vec<Vec<Pattern>>,
+}
+
+impl PathFilter {
+    pub fn new(paths: &[String], paths_ignore: &[String]) -> Self {
+        Self {
+            include: paths
+                .iter()
+                .map(|p| match p.strip_prefix('!') {
+                    Some(negated) => (false, compile(negated)),
+                    None => (true, compile(p)),
+                })
+                .collect(),
+            ignore: paths_ignore.iter().map(|p| compile(p)).collect(),
+        }
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let hit =
+            |patterns: &[Pattern]| patterns.iter().any(|g| g.matches_with(path, MATCH_OPTIONS));
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .rev()
+                .find(|(_, patterns)| hit(patterns))
+                .is_some_and(|(include, _)| *include);
+        included && !self.ignore.iter().any(|patterns| hit(patterns))
+    }
+}
+
+/// Compile a path glob, expanding `{a,b}` alternatives. A pattern also
+/// matches everything under the directory it names.
+fn compile(pattern: &str) -> Vec<Pattern> {
+    let pattern = pattern.trim_start_matches("./").trim_start_matches('/');
+    expand_braces(pattern)
+        .into_iter()
+        .flat_map(|p| {
+            let dir = format!("{}/**", p.trim_end_matches('/'));
+            [Pattern::new(&p).ok(), Pattern::new(&dir).ok()]
+        })
+        .flatten()
+        .collect()
+}
+
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let Some(len) = pattern[open..].find('}') else {
+        return vec![pattern.to_string()];
+    };
+    let close = open + len;
+    pattern[open + 1..close]
+        .split(',')
+        .flat_map(|alt| {
+            expand_braces(&format!(
+                "{}{alt}{}",
+                &pattern[..open],
+                &pattern[close + 1..]
+            ))
+        })
+        .collect()
+}
+
+// ── Discovery ─────────────────────────────────────────────────────
+
+/// Find every CI file in the repository: repo-relative path and contents,
+/// sorted by path.
+pub fn discover(repo_path: &Path) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    for location in CI_LOCATIONS {
+        let pattern = repo_path.join(location).to_string_lossy().to_string();
+        let Ok(paths) = glob::glob(&pattern) else {
+            continue;
+        };
+        for path in paths.flatten() {
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let relative = path.strip_prefix(repo_path).unwrap_or(&path);
+            found.push((relative.to_string_lossy().replace('\\', "/"), text));
+        }
+    }
+    // `justfile` and `Justfile` are one file on case-insensitive filesystems.
+    let mut seen = HashSet::new();
+    found.retain(|(path, _)| seen.insert(path.to_ascii_lowercase()));
+    found.sort();
+    found
+}
+
+/// Hash of every CI file's path and contents, for change detection.
+pub fn fingerprint(files: &[(String, String)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    files.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Parse one CI file into jobs, choosing the format from its path.
+pub fn parse_file(path: &str, text: &str) -> Vec<CiJob> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if path.starts_with(".github/workflows/") {
+        parse_github(path, &yaml::parse(text))
+    } else if name == ".gitlab-ci.yml" {
+        parse_gitlab(path, &yaml::parse(text))
+    } else if path.starts_with(".circleci/") {
+        parse_circleci(path, &yaml::parse(text))
+    } else if name == "Jenkinsfile" {
+        parse_jenkinsfile(path, text)
+    } else if name.eq_ignore_ascii_case("justfile") || name == ".justfile" {
+        parse_justfile(path, text)
+    } else if name.starts_with("Taskfile") {
+        parse_taskfile(path, &yaml::parse(text))
+    } else {
+        Vec::new()
+    }
+}
+
+/// A string or list of strings, flattening nested lists; other values are
+/// skipped.
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Number(n)) => vec![n.to_string()],
+        Some(Value::Array(items)) => items.iter().flat_map(|v| strings(Some(v))).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, items: impl IntoIterator<Item = String>) {
+    for item in items {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    }
+}
+
+// ── GitHub Actions ────────────────────────────────────────────────
+
+fn parse_github(file: &str, doc: &Value) -> Vec<CiJob> {
+    let stem = file
+        .rsplit('/')
+        .next()
+        .and_then(|n| n.split('.').next())
+        .unwrap_or(file);
+    let workflow = doc.get("name").and_then(Value::as_str).unwrap_or(stem);
+    let on = github_triggers(doc.get("on"));
+
+    let Some(jobs) = doc.get("jobs").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    jobs.iter()
+        .map(|(id, job)| {
+            let name = job.get("name").and_then(Value::as_str).unwrap_or(id);
+            let mut ci = CiJob::new("github", file, id, format!("{workflow} / {name}"));
+            ci.triggers.clone_from(&on.triggers);
+            ci.branches.clone_from(&on.branches);
+            ci.paths.clone_from(&on.paths);
+            ci.paths_ignore.clone_from(&on.paths_ignore);
+            ci.needs = strings(job.get("needs"));
+            ci.commands = job
+                .get("steps")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|step| step.get("run").and_then(Value::as_str))
+                .map(|run| run.trim().to_string())
+                .collect();
+            if let Some(reusable) = job.get("uses").and_then(Value::as_str) {
+                ci.commands.push(format!("uses: {reusable}"));
+            }
+            ci
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct WorkflowOn {
+    triggers: Vec<String>,
+    branches: Vec<String>,
+    paths: Vec<String>,
+    paths_ignore: Vec<String>,
+}
+
+/// Read a workflow's `on:`. Path filters apply only when every push and
+/// pull-request trigger declares them; one unfiltered trigger runs the
+/// workflow for any change.
+fn github_triggers(on: Option<&Value>) -> WorkflowOn {
+    let mut out = WorkflowOn::default();
+    let Some(Value::Object(events)) = on else {
+        out.triggers = strings(on);
+        return out;
+    };
+    let mut unfiltered = false;
+    for (event, config) in events {
+        out.triggers.push(event.clone());
+        if !CHANGE_TRIGGERS.contains(&event.as_str()) {
+            continue;
+        }
+        push_unique(&mut out.branches, strings(config.get("branches")));
+        let paths = strings(config.get("paths"));
+        let ignore = strings(config.get("paths-ignore"));
+        if paths.is_empty() && ignore.is_empty() {
+            unfiltered = true;
+        }
+        push_unique(&mut out.paths, paths);
+        push_unique(&mut out.paths_ignore, ignore);
+    }
+    if unfiltered {
+        out.paths.clear();
+        out.paths_ignore.clear();
+    }
+    out
+}
+
+// ── GitLab CI ─────────────────────────────────────────────────────
+
+fn parse_gitlab(file: &str, doc: &Value) -> Vec<CiJob> {
+    let Some(top) = doc.as_object() else {
+        return Vec::new();
+    };
+    let mut stages = strings(top.get("stages"));
+    if stages.is_empty() {
+        stages = [".pre", "build", "test", "deploy", ".post"]
+            .map(String::from)
+            .to_vec();
+    }
+
+    let mut jobs = Vec::new();
+    for (id, raw) in top {
+        if id.starts_with('.') || GITLAB_RESERVED.contains(&id.as_str()) || !raw.is_object() {
+            continue;
+        }
+        let job = gitlab_extends(top, raw, 0);
+        let mut ci = CiJob::new("gitlab", file, id, id.clone());
+        let stage = job
+            .get("stage")
+            .and_then(Value::as_str)
+            .unwrap_or("test")
+            .to_string();
+        if !stages.contains(&stage) {
+            stages.push(stage.clone());
+        }
+        ci.stage = Some(stage);
+        ci.needs = job
+            .get("needs")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|need| match need {
+                Value::String(s) => Some(s.clone()),
+                other => other.get("job").and_then(Value::as_str).map(String::from),
+            })
+            .collect();
+        ci.commands = strings(job.get("script"));
+        gitlab_conditions(&job, &mut ci);
+        jobs.push(ci);
+    }
+    jobs
+}
+
+/// A job with its `extends` templates merged underneath it.
+fn gitlab_extends(top: &serde_json::Map<String, Value>, job: &Value, depth: usize) -> Value {
+    let mut merged = serde_json::Map::new();
+    if depth < 10 {
+        for parent in strings(job.get("extends")) {
+            if let Some(Value::Object(base)) = top
+                .get(&parent)
+                .map(|base| gitlab_extends(top, base, depth + 1))
+            {
+                merged.extend(base);
+            }
+        }
+    }
+    if let Some(own) = job.as_object() {
+        merged.extend(own.clone());
+    }
+    merged.remove("extends");
+    Value::Object(merged)
+}
+
+/// Triggers and path filters from `rules:` or `only:`/`except:`.
+fn gitlab_conditions(job: &Value, ci: &mut CiJob) {
+    if let Some(rules) = job.get("rules").and_then(Value::as_array) {
+        let mut unfiltered = false;
+        for rule in rules {
+            let when = rule
+                .get("when")
+                .and_then(Value::as_str)
+                .unwrap_or("on_success");
+            if when == "never" {
+                continue;
+            }
+            let condition = rule.get("if").and_then(Value::as_str).unwrap_or_default();
+            let trigger = if when == "manual" {
+                "manual"
+            } else {
+                gitlab_trigger(condition)
+            };
+            push_unique(&mut ci.triggers, [trigger.to_string()]);
+            let changes = rule.get("changes").map(|c| c.get("paths").unwrap_or(c));
+            match changes {
+                Some(changes) => push_unique(&mut ci.paths, strings(Some(changes))),
+                None => unfiltered = true,
+            }
+        }
+        if unfiltered {
+            ci.paths.clear();
+        }
+        return;
+    }
+
+    match job.get("only") {
+        Some(Value::Object(only)) => {
+            gitlab_refs(&strings(only.get("refs")), ci);
+            ci.paths = strings(only.get("changes"));
+        }
+        Some(refs) => gitlab_refs(&strings(Some(refs)), ci),
+        None => {}
+    }
+    if let Some(except) = job.get("except").and_then(Value::as_object) {
+        ci.paths_ignore = strings(except.get("changes"));
+    }
+    if ci.triggers.is_empty() {
+        ci.triggers.push("push".to_string());
+    }
+    if job.get("when").and_then(Value::as_str) == Some("manual") {
+        ci.triggers = vec!["manual".to_string()];
+    }
+}
+
+fn gitlab_trigger(condition: &str) -> &'static str {
+    if condition.contains("merge_request_event") || condition.contains("CI_MERGE_REQUEST_") {
+        "merge_request"
+    } else if condition.contains("\"schedule\"") {
+        "schedule"
+    } else if condition.contains("\"web\"") || condition.contains("\"api\"") {
+        "manual"
+    } else if condition.contains("CI_COMMIT_TAG") {
+        "tag"
+    } else {
+        "push"
+    }
+}
+
+fn gitlab_refs(refs: &[String], ci: &mut CiJob) {
+    for r in refs {
+        let trigger = match r.as_str() {
+            "merge_requests" => "merge_request",
+            "schedules" => "schedule",
+            "tags" => "tag",
+            "web" | "api" | "triggers" => "manual",
+            "branches" | "pushes" => "push",
+            branch => {
+                ci.branches.push(branch.to_string());
+                "push"
+            }
+        };
+        push_unique(&mut ci.triggers, [trigger.to_string()]);
+    }
+}
+
+// ── CircleCI ──────────────────────────────────────────────────────
+
+fn parse_circleci(file: &str, doc: &Value) -> Vec<CiJob> {
+    let mut jobs: Vec<CiJob> = Vec::new();
+    let index = |jobs: &mut Vec<CiJob>, id: &str| {
+        if let Some(i) = jobs.iter().position(|j| j.id == id) {
+            return i;
+        }
+        jobs.push(CiJob::new(
+            "circleci",
+            file,
+            id,
+            format!("ci/circleci: {id}"),
+        ));
+        jobs.len() - 1
+    };
+
+    for (id, job) in doc
+        .get("jobs")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let i = index(&mut jobs, id);
+        jobs[i].commands = job
+            .get("steps")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|step| {
+                let run = step.get("run")?;
+                run.as_str()
+                    .or_else(|| run.get("command").and_then(Value::as_str))
+                    .map(|c| c.trim().to_string())
+            })
+            .collect();
+    }
+
+    let workflows = doc.get("workflows").and_then(Value::as_object);
+    for (name, workflow) in workflows.into_iter().flatten() {
+        let Some(entries) = workflow.get("jobs").and_then(Value::as_array) else {
+            continue;
+        };
+        let trigger = if workflow.get("triggers").is_some() {
+            "schedule"
+        } else {
+            "push"
+        };
+        for entry in entries {
+            let (job_id, config) = match entry {
+                Value::String(id) => (id.as_str(), &Value::Null),
+                Value::Object(map) => match map.iter().next() {
+                    Some((id, config)) => (id.as_str(), config),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let i = index(&mut jobs, job_id);
+            let job = &mut jobs[i];
+            push_unique(&mut job.triggers, [trigger.to_string()]);
+            push_unique(&mut job.needs, strings(config.get("requires")));
+            let only = config.get("filters").and_then(|f| f.get("branches"));
+            push_unique(&mut job.branches, strings(only.and_then(|b| b.get("only"))));
+            job.stage.get_or_insert_with(|| name.clone());
+        }
+    }
+
+    // Without workflows, CircleCI runs the `build` job on every push.
+    if workflows.is_none() {
+        for job in &mut jobs {
+            job.triggers.push("push".to_string());
+        }
+    }
+    jobs
+}
+
+// ── Jenkinsfile ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Open,
+    Close,
+    Other(char),
+}
+
+/// Split Groovy source into identifiers, strings and braces, dropping comments.
+fn tokenize_groovy(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '\'' | '"' => {
+                let rest: String = chars.clone().take(2).collect();
+                let triple = rest == format!("{c}{c}");
+                if triple {
+                    chars.next();
+                    chars.next();
+                }
+                let mut s = String::new();
+                let mut quotes = 0;
+                while let Some(ch) = chars.next() {
+                    if ch == '\\' {
+                        s.extend(chars.next());
+                        continue;
+                    }
+                    if ch == c {
+                        quotes += 1;
+                        if !triple || quotes == 3 {
+                            break;
+                        }
+                        continue;
+                    }
+                    s.extend(std::iter::repeat_n(c, quotes));
+                    quotes = 0;
+                    s.push(ch);
+                }
+                tokens.push(Token::Str(s));
+            }
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(&n) = chars.peek() {
+                    if !(n.is_alphanumeric() || n == '_') {
+                        break;
+                    }
+                    ident.push(n);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            c if c.is_whitespace() => {}
+            c => tokens.push(Token::Other(c)),
+        }
+    }
+    tokens
+}
+
+/// What an open brace belongs to.
+enum Block {
+    Stage(usize),
+    Named {
+        keyword: String,
+        last_stage: Option<usize>,
+    },
+}
+
+/// Declarative and scripted pipeline stages. Stages in one `stages` block
+/// (or a scripted `node`) run in order; stages under `parallel` do not wait
+/// for each other. `when { changeset '...' }` and `when { branch '...' }`
+/// become path and branch filters.
+fn parse_jenkinsfile(file: &str, text: &str) -> Vec<CiJob> {
+    let tokens = tokenize_groovy(text);
+    let mut jobs: Vec<CiJob> = Vec::new();
+    let mut stack: Vec<Block> = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Open => {
+                let block = match stage_before(&tokens, i) {
+                    Some(name) => {
+                        let mut job =
+                            CiJob::new("jenkins", file, &name, format!("jenkins: {name}"));
+                        job.triggers = vec!["push".to_string()];
+                        let index = jobs.len();
+                        if let Some(Block::Named {
+                            keyword,
+                            last_stage,
+                        }) = stack
+                            .iter_mut()
+                            .rev()
+                            .find(|b| matches!(b, Block::Named { .. }))
+                        {
+                            if keyword != "parallel" {
+                                job.needs.extend(last_stage.map(|s| jobs[s].id.clone()));
+                                *last_stage = Some(index);
+                            }
+                        }
+                        jobs.push(job);
+                        Block::Stage(index)
+                    }
+                    None => Block::Named {
+                        keyword: match i.checked_sub(1).map(|p| &tokens[p]) {
+                            Some(Token::Ident(name)) => name.clone(),
+                            _ => String::new(),
+                        },
+                        last_stage: None,
+                    },
+                };
+                stack.push(block);
+            }
+            Token::Close => {
+                stack.pop();
+            }
+            Token::Ident(ident) => {
+                let Some(stage) = stack.iter().rev().find_map(|b| match b {
+                    Block::Stage(s) => Some(*s),
+                    Block::Named { .. } => None,
+                }) else {
+                    continue;
+                };
+                let in_when = stack
+                    .iter()
+                    .any(|b| matches!(b, Block::Named { keyword, .. } if keyword == "when"));
+                let job = &mut jobs[stage];
+                match (ident.as_str(), string_after(&tokens, i)) {
+                    ("sh" | "bat" | "pwsh" | "powershell", Some(cmd)) => {
+                        job.commands.push(cmd.trim().to_string());
+                    }
+                    ("changeset", Some(glob)) if in_when => job.paths.push(glob),
+                    ("branch", Some(branch)) if in_when => job.branches.push(branch),
+                    ("changeRequest", _) if in_when => {
+                        job.triggers = vec!["pull_request".to_string()];
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    jobs.dedup_by(|a, b| a.id == b.id);
+    jobs
+}
+
+/// The name in `stage('name') {` when the brace at `open` follows one.
+fn stage_before(tokens: &[Token], open: usize) -> Option<String> {
+    let window = tokens.get(open.checked_sub(4)?..open)?;
+    match window {
+        [
+            Token::Ident(kw),
+            Token::Other('('),
+            Token::Str(name),
+            Token::Other(')'),
+        ] if kw == "stage" => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// The string argument of a step: `sh 'cmd'`, `sh('cmd')`, `sh script: 'cmd'`,
+/// `changeset pattern: 'glob'`.
+fn string_after(tokens: &[Token], at: usize) -> Option<String> {
+    tokens[at + 1..]
+        .iter()
+        .take(4)
+        .take_while(|t| matches!(t, Token::Str(_) | Token::Ident(_) | Token::Other('(' | ':')))
+        .find_map(|t| match t {
+            Token::Str(s) => Some(s.clone()),
+            _ => None,
+        })
+}
+
+// ── justfile ──────────────────────────────────────────────────────
+
+/// Recipes with their dependencies (`name arg: dep1 (dep2 "x")`) and body
+/// lines.
+fn parse_justfile(file: &str, text: &str) -> Vec<CiJob> {
+    let mut jobs: Vec<CiJob> = Vec::new();
+    let mut in_recipe = false;
+    for line in text.lines() {
+        if line.starts_with([' ', '\t']) {
+            let command = line.trim().trim_start_matches(['@', '-']);
+            if in_recipe && !command.is_empty() && !command.starts_with('#') {
+                if let Some(job) = jobs.last_mut() {
+                    job.commands.push(command.to_string());
+                }
+            }
+            continue;
+        }
+        in_recipe = false;
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || trimmed.starts_with(['#', '['])
+            || ["set ", "export ", "alias ", "import ", "mod "]
+                .iter()
+                .any(|kw| trimmed.starts_with(kw))
+        {
+            continue;
+        }
+        let Some((header, deps)) = trimmed.split_once(':') else {
+            continue;
+        };
+        if deps.starts_with('=') {
+            continue; // `name := value`
+        }
+        let Some(name) = header.split_whitespace().next() else {
+            continue;
+        };
+        let name = name.trim_start_matches('@');
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            continue;
+        }
+        let mut job = CiJob::new("just", file, name, format!("just {name}"));
+        job.triggers = vec!["manual".to_string()];
+        job.needs = recipe_dependencies(deps.split("&&").next().unwrap_or_default());
+        jobs.push(job);
+        in_recipe = true;
+    }
+    jobs
+}
+
+/// Dependency names from `dep1 (dep2 "arg") dep3`, skipping arguments.
+fn recipe_dependencies(deps: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut in_call = false;
+    for token in deps.split_whitespace() {
+        if token.starts_with('#') {
+            break;
+        }
+        if in_call {
+            in_call = !token.ends_with(')');
+            continue;
+        }
+        if let Some(call) = token.strip_prefix('(') {
+            in_call = !call.ends_with(')');
+            names.push(call.trim_end_matches(')').to_string());
+        } else {
+            names.push(token.to_string());
+        }
+    }
+    names
+}
+
+// ── Taskfile ──────────────────────────────────────────────────────
+
+/// Tasks with their `deps`, `cmds`, and `sources` as path filters.
+fn parse_taskfile(file: &str, doc: &Value) -> Vec<CiJob> {
+    let dir = file.rsplit_once('/').map(|(d, _)| format!("{d}/"));
+    let task_name = |v: &Value| match v {
+        Value::String(s) => Some(s.clone()),
+        other => other.get("task").and_then(Value::as_str).map(String::from),
+    };
+    let Some(tasks) = doc.get("tasks").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    tasks
+        .iter()
+        .map(|(id, task)| {
+            let mut job = CiJob::new("task", file, id, format!("task {id}"));
+            job.triggers = vec!["manual".to_string()];
+            let cmds = task.get("cmds").or(task.as_array().map(|_| task));
+            job.commands = cmds
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|cmd| match cmd {
+                    Value::String(s) => Some(s.trim().to_string()),
+                    other => other.get("cmd").and_then(Value::as_str).map(String::from),
+                })
+                .collect();
+            if let Some(cmd) = task.get("cmd").and_then(Value::as_str) {
+                job.commands.push(cmd.trim().to_string());
+            }
+            job.needs = task
+                .get("deps")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(task_name)
+                .collect();
+            job.paths = strings(task.get("sources"))
+                .into_iter()
+                .map(|s| format!("{}{s}", dir.as_deref().unwrap_or_default()))
+                .collect();
+            job
+        })
+        .collect()
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Stores every job as a `CiJob` node named `<file>::<job>`. A job without
+/// path filters runs for every change it is triggered by, so it gets no
+/// `Triggers` edge. Everything is rebuilt when a CI file or the set of
+/// tracked files changes; jobs that disappear are marked stale.
+#[derive(Debug)]
+pub struct CiExtractor {
+    repo_path: PathBuf,
+}
+
+impl CiExtractor {
+    pub fn new(repo_path: &Path) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+        }
+    }
+
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let filter = NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        };
+        let files = store.find_nodes(&filter).await?.len();
+        Ok(format!(
+            "{files}:{:x}",
+            fingerprint(&discover(&self.repo_path))
+        ))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for CiExtractor {
+    fn name(&self) -> &'static str {
+        "ci"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "ci_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "ci_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        // Jobs and file sets both shift; rebuild every edge.
+        store.delete_edges_by_kind(HyperedgeKind::Needs).await?;
+        store.delete_edges_by_kind(HyperedgeKind::Triggers).await?;
+
+        let jobs: Vec<CiJob> = discover(&self.repo_path)
+            .iter()
+            .flat_map(|(path, text)| parse_file(path, text))
+            .collect();
+
+        let ids = store_jobs(store, &jobs, &mut stats).await?;
+        link_needs(store, &jobs, &ids, &mut stats).await?;
+        link_triggers(store, &jobs, &ids, &mut stats).await?;
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("ci_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            jobs = jobs.len(),
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "CI extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Upsert a node per job and mark jobs that no longer exist stale.
+async fn store_jobs(
+    store: &dyn HomerStore,
+    jobs: &[CiJob],
+    stats: &mut ExtractStats,
+) -> crate::error::Result<HashMap<String, NodeId>> {
+    let existing = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::CiJob),
+            ..Default::default()
+        })
+        .await?;
+    let mut ids = HashMap::new();
+    for job in jobs {
+        let name = job.node_name();
+        if ids.contains_key(&name) {
+            continue;
+        }
+        let metadata = serde_json::json!({
+            "provider": job.provider,
+            "file": job.file,
+            "job": job.id,
+            "label": job.label,
+            "stage": job.stage,
+            "triggers": job.triggers,
+            "branches": job.branches,
+            "paths": job.paths,
+            "paths_ignore": job.paths_ignore,
+            "needs": job.needs,
+            "commands": job.commands,
+            "runs_on_change": job.runs_on_change(),
+        });
+        let id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::CiJob,
+                name: name.clone(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: serde_json::from_value(metadata).unwrap_or_default(),
+            })
+            .await?;
+        if existing.iter().any(|n| n.name == name) {
+            stats.nodes_updated += 1;
+        } else {
+            stats.nodes_created += 1;
+        }
+        ids.insert(name, id);
+    }
+
+    for node in existing {
+        if !ids.contains_key(&node.name) {
+            store.mark_node_stale(node.id).await?;
+        }
+    }
+    Ok(ids)
+}
+
+async fn link_needs(
+    store: &dyn HomerStore,
+    jobs: &[CiJob],
+    ids: &HashMap<String, NodeId>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<()> {
+    let mut seen = HashSet::new();
+    for job in jobs {
+        let Some(&dependent) = ids.get(&job.node_name()) else {
+            continue;
+        };
+        for need in &job.needs {
+            let Some(&needed) = ids.get(&format!("{}::{need}", job.file)) else {
+                continue;
+            };
+            if needed == dependent || !seen.insert((dependent, needed)) {
+                continue;
+            }
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Needs,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: dependent,
+                            role: roles::DEPENDENT_JOB.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: needed,
+                            role: roles::NEEDED_JOB.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 1.0,
+                    last_updated: Utc::now(),
+                    metadata: HashMap::from([("source".to_string(), serde_json::json!(job.file))]),
+                })
+                .await?;
+            stats.edges_created += 1;
+        }
+    }
+    Ok(())
+}
+
+/// One `Triggers` edge per path-filtered job, joining it to every tracked
+/// file its filters let through.
+async fn link_triggers(
+    store: &dyn HomerStore,
+    jobs: &[CiJob],
+    ids: &HashMap<String, NodeId>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<()> {
+    if !jobs.iter().any(CiJob::is_path_filtered) {
+        return Ok(());
+    }
+    let files = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        })
+        .await?;
+    for job in jobs.iter().filter(|j| j.is_path_filtered()) {
+        let Some(&job_id) = ids.get(&job.node_name()) else {
+            continue;
+        };
+        let filter = PathFilter::new(&job.paths, &job.paths_ignore);
+        let mut members = vec![HyperedgeMember {
+            node_id: job_id,
+            role: roles::TRIGGERED_JOB.to_string(),
+            position: 0,
+        }];
+        for file in files.iter().filter(|f| filter.matches(&f.name)) {
+            members.push(HyperedgeMember {
+                node_id: file.id,
+                role: roles::TRIGGERING_FILE.to_string(),
+                position: u32::try_from(members.len()).unwrap_or(u32::MAX),
+            });
+        }
+        if members.len() == 1 {
+            continue;
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Triggers,
+                members,
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::from([
+                    ("paths".to_string(), serde_json::json!(job.paths)),
+                    (
+                        "paths_ignore".to_string(),
+                        serde_json::json!(job.paths_ignore),
+                    ),
+                    ("source".to_string(), serde_json::json!(job.file)),
+                ]),
+            })
+            .await?;
+        stats.edges_created += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job<'a>(jobs: &'a [CiJob], id: &str) -> &'a CiJob {
+        jobs.iter()
+            .find(|j| j.id == id)
+            .unwrap_or_else(|| panic!("no job {id} in {jobs:?}"))
+    }
+
+    #[test]
+    fn github_workflow_jobs_triggers_and_filters() {
+        let jobs = parse_file(
+            ".github/workflows/docs.yml",
+            "name: Docs\n\
+             on:\n\
+             \x20 pull_request:\n\
+             \x20   paths: ['docs/**', '!docs/drafts/**', 'mkdocs.yml']\n\
+             \x20 push:\n\
+             \x20   branches: [main]\n\
+             \x20   paths: ['docs/**']\n\
+             \x20 workflow_dispatch:\n\
+             jobs:\n\
+             \x20 build:\n\
+             \x20   runs-on: ubuntu-latest\n\
+             \x20   steps:\n\
+             \x20     - uses: actions/checkout@v4\n\
+             \x20     - run: mkdocs build --strict\n\
+             \x20 deploy:\n\
+             \x20   name: Publish\n\
+             \x20   needs: build\n\
+             \x20   steps:\n\
+             \x20     - run: mkdocs gh-deploy\n",
+        );
+        assert_eq!(jobs.len(), 2);
+        let build = job(&jobs, "build");
+        assert_eq!(build.label, "Docs / build");
+        assert_eq!(build.commands, vec!["mkdocs build --strict"]);
+        assert_eq!(build.branches, vec!["main"]);
+        assert!(build.runs_on_change());
+        assert!(build.watches("docs/index.md"));
+        assert!(build.watches("mkdocs.yml"));
+        assert!(!build.watches("docs/drafts/wip.md"), "negated pattern");
+        assert!(!build.watches("src/lib.rs"));
+        let deploy = job(&jobs, "deploy");
+        assert_eq!(deploy.label, "Docs / Publish");
+        assert_eq!(deploy.needs, vec!["build"]);
+
+        let ci = parse_file(
+            ".github/workflows/ci.yml",
+            "on: [push, pull_request]\njobs:\n  test:\n    steps:\n      - run: cargo test\n",
+        );
+        assert_eq!(ci[0].triggers, vec!["push", "pull_request"]);
+        assert!(!ci[0].is_path_filtered());
+        let partial = parse_file(
+            ".github/workflows/x.yml",
+            "on:\n  push:\n  pull_request:\n    paths-ignore: ['**.md']\njobs:\n  a: {}\n",
+        );
+        assert!(
+            !partial[0].is_path_filtered(),
+            "an unfiltered push runs for any change"
+        );
+    }
+
+    #[test]
+    fn gitlab_stages_needs_rules_and_templates() {
+        let jobs = parse_file(
+            ".gitlab-ci.yml",
+            "stages: [build, test]\n\
+             variables:\n\
+             \x20 CARGO_HOME: .cargo\n\
+             .rust: &rust\n\
+             \x20 image: rust\n\
+             \x20 rules:\n\
+             \x20   - if: $CI_PIPELINE_SOURCE == \"merge_request_event\"\n\
+             \x20     changes: ['src/**/*', 'Cargo.{toml,lock}']\n\
+             compile:\n\
+             \x20 extends: .rust\n\
+             \x20 stage: build\n\
+             \x20 script: cargo build\n\
+             unit:\n\
+             \x20 <<: *rust\n\
+             \x20 needs: [compile, {job: lint, optional: true}]\n\
+             \x20 script:\n\
+             \x20   - cargo test\n\
+             docs:\n\
+             \x20 only:\n\
+             \x20   refs: [main]\n\
+             \x20   changes: [docs/**/*]\n\
+             \x20 script: make docs\n\
+             release:\n\
+             \x20 stage: deploy\n\
+             \x20 when: manual\n\
+             \x20 script: ./release.sh\n",
+        );
+        assert_eq!(jobs.len(), 4, "templates and reserved keys are not jobs");
+        let compile = job(&jobs, "compile");
+        assert_eq!(compile.stage.as_deref(), Some("build"));
+        assert_eq!(compile.triggers, vec!["merge_request"]);
+        assert!(compile.watches("Cargo.lock"));
+        assert!(compile.watches("src/a/b.rs"));
+        assert!(!compile.watches("README.md"));
+        let unit = job(&jobs, "unit");
+        assert_eq!(unit.stage.as_deref(), Some("test"));
+        assert_eq!(unit.needs, vec!["compile", "lint"]);
+        assert_eq!(unit.commands, vec!["cargo test"]);
+        assert!(unit.is_path_filtered());
+        let docs = job(&jobs, "docs");
+        assert_eq!(docs.branches, vec!["main"]);
+        assert!(docs.watches("docs/guide/intro.md"));
+        let release = job(&jobs, "release");
+        assert!(!release.runs_on_change());
+    }
+
+    #[test]
+    fn circleci_and_jenkins_pipelines() {
+        let jobs = parse_file(
+            ".circleci/config.yml",
+            "version: 2.1\n\
+             jobs:\n\
+             \x20 build:\n\
+             \x20   steps:\n\
+             \x20     - checkout\n\
+             \x20     - run: make\n\
+             \x20     - run:\n\
+             \x20         name: Test\n\
+             \x20         command: make test\n\
+             workflows:\n\
+             \x20 main:\n\
+             \x20   jobs:\n\
+             \x20     - build\n\
+             \x20     - deploy:\n\
+             \x20         requires: [build]\n\
+             \x20         filters:\n\
+             \x20           branches:\n\
+             \x20             only: main\n",
+        );
+        let build = job(&jobs, "build");
+        assert_eq!(build.commands, vec!["make", "make test"]);
+        assert_eq!(build.label, "ci/circleci: build");
+        let deploy = job(&jobs, "deploy");
+        assert_eq!(deploy.needs, vec!["build"]);
+        assert_eq!(deploy.branches, vec!["main"]);
+        assert!(deploy.runs_on_change());
+
+        let stages = parse_file(
+            "Jenkinsfile",
+            "pipeline {\n\
+             \x20 agent any\n\
+             \x20 stages {\n\
+             \x20   stage('Build') { steps { sh 'make' } }\n\
+             \x20   stage(\"Checks\") {\n\
+             \x20     parallel {\n\
+             \x20       stage('Lint') { steps { sh(script: 'make lint') } }\n\
+             \x20       stage('Docs') {\n\
+             \x20         when { changeset pattern: 'docs/**' }\n\
+             \x20         steps { sh '''make docs''' } // build docs\n\
+             \x20       }\n\
+             \x20     }\n\
+             \x20   }\n\
+             \x20   stage('Deploy') { when { branch 'main' } steps { sh './deploy' } }\n\
+             \x20 }\n\
+             }\n",
+        );
+        let ids: Vec<&str> = stages.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["Build", "Checks", "Lint", "Docs", "Deploy"]);
+        assert_eq!(job(&stages, "Checks").needs, vec!["Build"]);
+        assert!(job(&stages, "Lint").needs.is_empty(), "parallel branch");
+        assert_eq!(job(&stages, "Lint").commands, vec!["make lint"]);
+        assert_eq!(job(&stages, "Docs").paths, vec!["docs/**"]);
+        assert_eq!(job(&stages, "Docs").commands, vec!["make docs"]);
+        assert_eq!(job(&stages, "Deploy").needs, vec!["Checks"]);
+        assert_eq!(job(&stages, "Deploy").branches, vec!["main"]);
+    }
+
+    #[test]
+    fn justfile_and_taskfile_recipes() {
+        let recipes = parse_file(
+            "justfile",
+            "set shell := [\"bash\", \"-c\"]\n\
+             version := \"1.0\"\n\
+             \n\
+             # Run the tests\n\
+             test filter='': build (lint \"strict\")\n\
+             \x20   cargo test {{filter}}\n\
+             \n\
+             [private]\n\
+             @build:\n\
+             \x20   @cargo build\n\
+             lint mode:\n\
+             \x20   cargo clippy\n",
+        );
+        let ids: Vec<&str> = recipes.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["test", "build", "lint"]);
+        assert_eq!(job(&recipes, "test").needs, vec!["build", "lint"]);
+        assert_eq!(job(&recipes, "build").commands, vec!["cargo build"]);
+        assert!(!job(&recipes, "test").runs_on_change());
+
+        let tasks = parse_file(
+            "Taskfile.yml",
+            "version: '3'\n\
+             tasks:\n\
+             \x20 generate:\n\
+             \x20   sources: ['proto/**/*.proto']\n\
+             \x20   cmds: [buf generate]\n\
+             \x20 build:\n\
+             \x20   deps: [generate, {task: assets}]\n\
+             \x20   cmds:\n\
+             \x20     - go build ./...\n\
+             \x20     - cmd: echo done\n\
+             \x20 assets: [npm run build]\n",
+        );
+        let build = job(&tasks, "build");
+        assert_eq!(build.needs, vec!["generate", "assets"]);
+        assert_eq!(build.commands, vec!["go build ./...", "echo done"]);
+        assert_eq!(job(&tasks, "assets").commands, vec!["npm run build"]);
+        assert!(job(&tasks, "generate").watches("proto/v1/api.proto"));
+    }
+
+    #[tokio::test]
+    async fn extracts_jobs_needs_and_triggers() {
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".github/workflows")).unwrap();
+        let workflow = tmp.path().join(".github/workflows/ci.yml");
+        std::fs::write(
+            &workflow,
+            "name: CI\n\
+             on: pull_request\n\
+             jobs:\n\
+             \x20 test:\n\
+             \x20   steps: [{run: cargo test}]\n\
+             \x20 bench:\n\
+             \x20   needs: test\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join(".github/workflows/docs.yml"),
+            "on:\n  pull_request:\n    paths: ['docs/**']\njobs:\n  build: {}\n",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        for path in ["src/main.rs", "docs/intro.md", "docs/api.md"] {
+            store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: path.to_string(),
+                    content_hash: None,
+                    last_extracted: Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let extractor = CiExtractor::new(tmp.path());
+        assert!(extractor.has_work(&store).await.unwrap());
+        let stats = extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.nodes_created, 3);
+        assert_eq!(stats.edges_created, 2, "one Needs, one Triggers");
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let triggers = store
+            .get_edges_by_kind(HyperedgeKind::Triggers)
+            .await
+            .unwrap();
+        assert_eq!(triggers.len(), 1);
+        let files = triggers[0]
+            .members
+            .iter()
+            .filter(|m| m.role == roles::TRIGGERING_FILE)
+            .count();
+        assert_eq!(files, 2);
+        let bench = store
+            .get_node_by_name(NodeKind::CiJob, ".github/workflows/ci.yml::bench")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bench.metadata["label"], "CI / bench");
+        assert_eq!(bench.metadata["runs_on_change"], true);
+
+        // A removed job is marked stale; edges are rebuilt, not accumulated.
+        std::fs::write(&workflow, "on: pull_request\njobs:\n  test: {}\n").unwrap();
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        let bench = store.get_node(bench.id).await.unwrap().unwrap();
+        assert!(bench.metadata.contains_key("stale"));
+        assert!(
+            store
+                .get_edges_by_kind(HyperedgeKind::Needs)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,545 @@
+// CODEOWNERS parsing for GitHub and GitLab syntax, and extraction of each
+// rule as an `Owns` hyperedge joining its owners to the files it decides.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use glob::{MatchOptions, Pattern};
+use tracing::{info, instrument};
+
+use crate::config::HomerConfig;
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, NodeFilter, NodeKind};
+
+use super::forge_common::ensure_contributor;
+use super::traits::{ExtractStats, Extractor};
+
+/// Where GitHub and GitLab look for the file, in lookup order.
+pub const CODEOWNERS_LOCATIONS: &[&str] = &[
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// One ownership rule. Patterns follow gitignore rules: a leading `/`
+/// anchors to the repo root, a trailing `/` matches directory contents only,
+/// and a pattern with no other `/` matches at any depth.
+#[derive(Debug, Clone)]
+pub struct CodeownersRule {
+    /// The pattern as written.
+    pub pattern: String,
+    /// Owners: `@user`, `@org/team`, or an email address.
+    pub owners: Vec<String>,
+    /// GitLab section name, if the rule sits under a `[Section]` header.
+    pub section: Option<String>,
+    /// 1-based line number in the file.
+    pub line: usize,
+    file_glob: Option<Pattern>,
+    dir_glob: Option<Pattern>,
+}
+
+impl CodeownersRule {
+    fn new(pattern: &str, owners: Vec<String>, section: Option<String>, line: usize) -> Self {
+        let anchored = pattern.starts_with('/');
+        let dir_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_start_matches('/').trim_end_matches('/');
+        let glob = if !anchored && !trimmed.contains('/') {
+            format!("**/{trimmed}")
+        } else {
+            trimmed.to_string()
+        };
+        // Only a trailing `/` or a literal last segment can name a directory;
+        // `docs/*` matches the entries of `docs`, not everything below them.
+        let last = trimmed.rsplit('/').next().unwrap_or(trimmed);
+        let names_dir = dir_only || !last.contains(['*', '?', '[']);
+
+        Self {
+            pattern: pattern.to_string(),
+            owners,
+            section,
+            line,
+            file_glob: (!dir_only).then(|| Pattern::new(&glob).ok()).flatten(),
+            dir_glob: names_dir
+                .then(|| Pattern::new(&format!("{glob}/**")).ok())
+                .flatten(),
+        }
+    }
+
+    /// Whether this rule applies to a repo-relative file path.
+    pub fn matches(&self, path: &str) -> bool {
+        let path = path.trim_start_matches('/');
+        self.file_glob
+            .as_ref()
+            .is_some_and(|g| g.matches_with(path, MATCH_OPTIONS))
+            || self
+                .dir_glob
+                .as_ref()
+                .is_some_and(|g| g.matches_with(path, MATCH_OPTIONS))
+    }
+}
+
+/// A parsed CODEOWNERS file.
+#[derive(Debug, Clone, Default)]
+pub struct Codeowners {
+    pub rules: Vec<CodeownersRule>,
+}
+
+impl Codeowners {
+    /// Parse CODEOWNERS text. Malformed lines are skipped, and rules without
+    /// owners inherit their GitLab section's default owners.
+    pub fn parse(text: &str) -> Self {
+        let mut rules = Vec::new();
+        let mut section: Option<String> = None;
+        let mut section_owners: Vec<String> = Vec::new();
+
+        for (idx, raw) in text.lines().enumerate() {
+            let tokens = tokenize(raw);
+            let Some(first) = tokens.first() else {
+                continue;
+            };
+
+            // GitLab section header: `[Name]`, `^[Optional]`, `[Name][2] @owner`
+            let header = first.strip_prefix('^').unwrap_or(first);
+            if header.starts_with('[') && !raw.trim_start().starts_with("\\[") {
+                if let Some(end) = header.find(']') {
+                    section = Some(header[1..end].to_string());
+                    section_owners = tokens[1..].to_vec();
+                    continue;
+                }
+            }
+
+            let owners = if tokens.len() > 1 {
+                tokens[1..].to_vec()
+            } else {
+                section_owners.clone()
+            };
+            rules.push(CodeownersRule::new(first, owners, section.clone(), idx + 1));
+        }
+
+        Self { rules }
+    }
+
+    /// Find and parse the repository's CODEOWNERS file.
+    pub fn discover(repo_path: &Path) -> Option<(PathBuf, Self)> {
+        CODEOWNERS_LOCATIONS.iter().find_map(|loc| {
+            let path = repo_path.join(loc);
+            let text = std::fs::read_to_string(&path).ok()?;
+            Some((PathBuf::from(loc), Self::parse(&text)))
+        })
+    }
+
+    /// The rules that decide ownership of `path`: the last match in each
+    /// section, in file order. Each GitLab `[Section]` contributes its own
+    /// last match.
+    pub fn matching_rules(&self, path: &str) -> Vec<&CodeownersRule> {
+        self.matching_indices(path)
+            .into_iter()
+            .map(|i| &self.rules[i])
+            .collect()
+    }
+
+    fn matching_indices(&self, path: &str) -> Vec<usize> {
+        let mut winners: Vec<usize> = Vec::new();
+        for (i, rule) in self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.matches(path))
+        {
+            match winners
+                .iter_mut()
+                .find(|w| self.rules[**w].section == rule.section)
+            {
+                Some(winner) => *winner = i,
+                None => winners.push(i),
+            }
+        }
+        winners
+    }
+
+    /// Owners of `path`, deduplicated, in rule order. Empty when unowned.
+    pub fn owners_for(&self, path: &str) -> Vec<&str> {
+        let mut owners: Vec<&str> = Vec::new();
+        for rule in self.matching_rules(path) {
+            for owner in &rule.owners {
+                if !owners.contains(&owner.as_str()) {
+                    owners.push(owner);
+                }
+            }
+        }
+        owners
+    }
+}
+
+/// Whether an owner names a team (`@org/team`) rather than a person.
+pub fn is_team(owner: &str) -> bool {
+    owner.starts_with('@') && owner.contains('/')
+}
+
+/// Whether a person owner (`@login` or email) names the contributor with
+/// this email and display name. Logins match the email's local part, a
+/// GitHub noreply address, or the display name. Teams never match.
+pub fn owner_matches(owner: &str, email: &str, display_name: Option<&str>) -> bool {
+    if is_team(owner) {
+        return false;
+    }
+    let Some(login) = owner.strip_prefix('@') else {
+        return owner.eq_ignore_ascii_case(email);
+    };
+    let local = email.split('@').next().unwrap_or_default();
+    // `12345+login@users.noreply.github.com`
+    let noreply_login = local.split_once('+').map_or(local, |(_, l)| l);
+    local.eq_ignore_ascii_case(login)
+        || noreply_login.eq_ignore_ascii_case(login)
+        || display_name.is_some_and(|n| n.eq_ignore_ascii_case(login))
+}
+
+/// Hash of every CODEOWNERS candidate's contents, for change detection.
+pub fn fingerprint(repo_path: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for loc in CODEOWNERS_LOCATIONS {
+        std::fs::read_to_string(repo_path.join(loc))
+            .ok()
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Split a line into whitespace-separated tokens, honouring `\ ` and `\#`
+/// escapes and dropping trailing comments.
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '#' if current.is_empty() => break,
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Rebuilds the `Owns` edges whenever the CODEOWNERS file or the set of
+/// tracked files changes.
+#[derive(Debug)]
+pub struct CodeownersExtractor {
+    repo_path: PathBuf,
+}
+
+impl CodeownersExtractor {
+    pub fn new(repo_path: &Path) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+        }
+    }
+
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let filter = NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        };
+        let files = store.find_nodes(&filter).await?.len();
+        Ok(format!("{files}:{:x}", fingerprint(&self.repo_path)))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for CodeownersExtractor {
+    fn name(&self) -> &'static str {
+        "codeowners"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "codeowners_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "codeowners_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        // Rules and file sets both shift; rebuild every edge.
+        store.delete_edges_by_kind(HyperedgeKind::Owns).await?;
+
+        if let Some((location, owners)) = Codeowners::discover(&self.repo_path) {
+            let filter = NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            };
+            let mut rule_files: HashMap<usize, Vec<HyperedgeMember>> = HashMap::new();
+            for file in store.find_nodes(&filter).await? {
+                for rule in owners.matching_indices(&file.name) {
+                    let members = rule_files.entry(rule).or_default();
+                    members.push(HyperedgeMember {
+                        node_id: file.id,
+                        role: roles::OWNED.to_string(),
+                        position: u32::try_from(members.len()).unwrap_or(u32::MAX),
+                    });
+                }
+            }
+
+            let mut rules: Vec<_> = rule_files.into_iter().collect();
+            rules.sort_by_key(|(rule, _)| *rule);
+            for (rule, files) in rules {
+                let rule = &owners.rules[rule];
+                if rule.owners.is_empty() {
+                    continue;
+                }
+                let mut members = Vec::new();
+                for (owner, position) in rule.owners.iter().zip(0..) {
+                    let id = ensure_contributor(store, &mut stats, owner_node_name(owner)).await?;
+                    members.push(HyperedgeMember {
+                        node_id: id,
+                        role: roles::OWNER.to_string(),
+                        position,
+                    });
+                }
+                members.extend(files);
+
+                store
+                    .upsert_hyperedge(&Hyperedge {
+                        id: HyperedgeId(0),
+                        kind: HyperedgeKind::Owns,
+                        members,
+                        confidence: 1.0,
+                        last_updated: Utc::now(),
+                        metadata: HashMap::from([
+                            ("owners".to_string(), serde_json::json!(rule.owners)),
+                            ("pattern".to_string(), serde_json::json!(rule.pattern)),
+                            ("section".to_string(), serde_json::json!(rule.section)),
+                            ("line".to_string(), serde_json::json!(rule.line)),
+                            (
+                                "source".to_string(),
+                                serde_json::json!(location.to_string_lossy()),
+                            ),
+                        ]),
+                    })
+                    .await?;
+                stats.edges_created += 1;
+            }
+        } else {
+            info!("No CODEOWNERS file found");
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("codeowners_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "CODEOWNERS extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Contributor node name for an owner: logins and team slugs without the
+/// leading `@`, matching forge contributor nodes; emails as written.
+fn owner_node_name(owner: &str) -> &str {
+    owner.strip_prefix('@').unwrap_or(owner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let owners = Codeowners::parse(
+            "# Default owners\n\
+             *       @acme/core\n\
+             *.md    docs@example.com\n\
+             /src/billing/ @acme/payments @carol\n\
+             apps/   @acme/apps\n",
+        );
+
+        assert_eq!(owners.owners_for("src/main.rs"), vec!["@acme/core"]);
+        assert_eq!(owners.owners_for("README.md"), vec!["docs@example.com"]);
+        assert_eq!(
+            owners.owners_for("src/billing/README.md"),
+            vec!["@acme/payments", "@carol"]
+        );
+        assert_eq!(
+            owners.owners_for("src/billing/tax/rate.rs"),
+            vec!["@acme/payments", "@carol"]
+        );
+        // Unanchored directory patterns match at any depth.
+        assert_eq!(owners.owners_for("web/apps/ui.ts"), vec!["@acme/apps"]);
+        assert!(is_team("@acme/core"));
+        assert!(!is_team("@carol"));
+    }
+
+    #[test]
+    fn anchored_paths_and_globs() {
+        let owners = Codeowners::parse("/docs/*.md @writers\nsrc/**/test_*.py @qa\n");
+
+        assert_eq!(owners.owners_for("docs/intro.md"), vec!["@writers"]);
+        assert!(owners.owners_for("docs/api/intro.md").is_empty());
+        assert!(owners.owners_for("other/docs/intro.md").is_empty());
+        assert_eq!(owners.owners_for("src/a/b/test_x.py"), vec!["@qa"]);
+    }
+
+    #[test]
+    fn wildcard_segments_do_not_match_nested_files() {
+        let owners = Codeowners::parse("docs/* @writers\napps/ @app\n/build/logs @ops\n");
+
+        assert_eq!(owners.owners_for("docs/intro.md"), vec!["@writers"]);
+        assert!(owners.owners_for("docs/api/intro.md").is_empty());
+        assert_eq!(owners.owners_for("apps/web/main.rs"), vec!["@app"]);
+        assert_eq!(owners.owners_for("build/logs/today.log"), vec!["@ops"]);
+    }
+
+    #[test]
+    fn gitlab_sections_combine_and_inherit_default_owners() {
+        let owners = Codeowners::parse(
+            "* @everyone\n\
+             \n\
+             [Backend] @acme/backend\n\
+             src/\n\
+             src/legacy/ @dave\n\
+             \n\
+             ^[Docs][2] @acme/docs\n\
+             *.md\n",
+        );
+
+        let rules = owners.matching_rules("src/legacy/README.md");
+        assert_eq!(rules.len(), 3, "one winner per section: {rules:?}");
+        assert_eq!(
+            owners.owners_for("src/legacy/README.md"),
+            vec!["@everyone", "@dave", "@acme/docs"]
+        );
+        assert_eq!(
+            owners.owners_for("src/api.rs"),
+            vec!["@everyone", "@acme/backend"]
+        );
+        assert_eq!(owners.rules[1].section.as_deref(), Some("Backend"));
+    }
+
+    #[test]
+    fn escapes_and_comments() {
+        let owners = Codeowners::parse("my\\ file.txt @a # trailing comment\n\\#notes @b\n");
+        assert_eq!(owners.rules[0].pattern, "my file.txt");
+        assert_eq!(owners.rules[0].owners, vec!["@a"]);
+        assert_eq!(owners.owners_for("#notes"), vec!["@b"]);
+    }
+
+    #[test]
+    fn owners_match_contributor_identities() {
+        assert!(owner_matches("@alice", "alice@example.com", None));
+        assert!(owner_matches(
+            "@alice",
+            "1234+Alice@users.noreply.github.com",
+            None
+        ));
+        assert!(owner_matches("@ab", "a.b@example.com", Some("AB")));
+        assert!(owner_matches("Bob@Example.com", "bob@example.com", None));
+        assert!(!owner_matches("@alice", "bob@example.com", Some("Bob")));
+        assert!(!owner_matches("@acme/alice", "alice@example.com", None));
+    }
+
+    #[tokio::test]
+    async fn extracts_one_owns_edge_per_deciding_rule() {
+        use crate::store::sqlite::SqliteStore;
+        use crate::types::{Node, NodeId};
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".github")).unwrap();
+        std::fs::write(
+            tmp.path().join(".github/CODEOWNERS"),
+            "* @acme/core\n/docs/ @writers docs@example.com\n*.unused @nobody\n",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        for path in ["src/main.rs", "docs/intro.md", "docs/api.md"] {
+            store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: path.to_string(),
+                    content_hash: None,
+                    last_extracted: Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let extractor = CodeownersExtractor::new(tmp.path());
+        assert!(extractor.has_work(&store).await.unwrap());
+        let stats = extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.edges_created, 2, "unmatched rules produce no edge");
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let mut edges = store.get_edges_by_kind(HyperedgeKind::Owns).await.unwrap();
+        edges.sort_by_key(|e| e.metadata["line"].as_u64());
+        let files = |edge: &Hyperedge| {
+            edge.members
+                .iter()
+                .filter(|m| m.role == roles::OWNED)
+                .count()
+        };
+        assert_eq!(edges[0].metadata["pattern"], "*");
+        assert_eq!(files(&edges[0]), 1, "docs are decided by the later rule");
+        assert_eq!(edges[1].metadata["source"], ".github/CODEOWNERS");
+        assert_eq!(files(&edges[1]), 2);
+        assert!(
+            store
+                .get_node_by_name(NodeKind::Contributor, "writers")
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // Re-extraction replaces rather than accumulates edges.
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .get_edges_by_kind(HyperedgeKind::Owns)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
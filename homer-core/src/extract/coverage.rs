@@ -0,0 +1,888 @@
+// Coverage report ingestion: records line and branch coverage from test-run
+// artifacts on `File` nodes and, by span, on the `Function` nodes inside them.
+
+// Coverage rates intentionally cast counts to float.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::{CoverageExtractionConfig, HomerConfig};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{Node, NodeFilter, NodeKind};
+
+use super::traits::{ExtractStats, Extractor};
+use super::xml::{XmlEvent, XmlReader};
+
+/// Metadata key holding a node's coverage summary.
+pub const COVERAGE_KEY: &str = "coverage";
+
+/// Largest report read, in bytes.
+const MAX_REPORT_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Report formats Homer understands. The format is detected from the
+/// content, so any configured path may hold any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CoverageFormat {
+    Lcov,
+    Cobertura,
+    LlvmJson,
+    GoProfile,
+}
+
+impl CoverageFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lcov => "lcov",
+            Self::Cobertura => "cobertura",
+            Self::LlvmJson => "llvm-cov",
+            Self::GoProfile => "go",
+        }
+    }
+
+    /// Detect the format of a report from its content.
+    pub fn detect(text: &str) -> Option<Self> {
+        let head = text.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with("mode:") {
+            Some(Self::GoProfile)
+        } else if head.starts_with('{') {
+            head.get(..4096.min(head.len()))
+                .unwrap_or(head)
+                .contains("llvm.coverage.json.export")
+                .then_some(Self::LlvmJson)
+        } else if head.starts_with('<') {
+            text.contains("<coverage").then_some(Self::Cobertura)
+        } else if head.lines().any(|l| l.starts_with("SF:")) {
+            Some(Self::Lcov)
+        } else {
+            None
+        }
+    }
+}
+
+/// Covered and total lines and branches of a file or function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverageSummary {
+    pub lines_covered: u64,
+    pub lines_total: u64,
+    pub branches_covered: u64,
+    pub branches_total: u64,
+}
+
+impl CoverageSummary {
+    /// Share of instrumented lines executed at least once (0.0–1.0).
+    pub fn line_rate(&self) -> f64 {
+        if self.lines_total == 0 {
+            return 0.0;
+        }
+        self.lines_covered as f64 / self.lines_total as f64
+    }
+
+    /// Share of branch outcomes taken, if the report had branch data.
+    pub fn branch_rate(&self) -> Option<f64> {
+        (self.branches_total > 0).then(|| self.branches_covered as f64 / self.branches_total as f64)
+    }
+
+    /// The summary stored on a node, if coverage was ingested for it.
+    pub fn of(node: &Node) -> Option<Self> {
+        serde_json::from_value(node.metadata.get(COVERAGE_KEY)?.clone()).ok()
+    }
+
+    fn to_json(self, formats: &[CoverageFormat]) -> serde_json::Value {
+        let round = |r: f64| (r * 1000.0).round() / 1000.0;
+        serde_json::json!({
+            "lines_covered": self.lines_covered,
+            "lines_total": self.lines_total,
+            "line_rate": round(self.line_rate()),
+            "branches_covered": self.branches_covered,
+            "branches_total": self.branches_total,
+            "branch_rate": self.branch_rate().map(round),
+            "formats": formats.iter().map(|f| f.as_str()).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Per-line execution counts and branch outcomes for one source file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// 1-based line → execution count.
+    pub lines: BTreeMap<u32, u64>,
+    /// 1-based line → (branch outcomes, outcomes taken).
+    pub branches: BTreeMap<u32, (u64, u64)>,
+}
+
+impl FileCoverage {
+    fn hit(&mut self, line: u32, count: u64) {
+        let entry = self.lines.entry(line).or_insert(0);
+        *entry = (*entry).max(count);
+    }
+
+    fn branch(&mut self, line: u32, total: u64, taken: u64) {
+        let entry = self.branches.entry(line).or_insert((0, 0));
+        entry.0 = entry.0.max(total);
+        entry.1 = entry.1.max(taken).min(entry.0);
+    }
+
+    /// Combine another report of the same file, keeping the highest count
+    /// per line.
+    fn merge(&mut self, other: &Self) {
+        for (&line, &count) in &other.lines {
+            self.hit(line, count);
+        }
+        for (&line, &(total, taken)) in &other.branches {
+            self.branch(line, total, taken);
+        }
+    }
+
+    /// Summary over the inclusive 1-based line range, or the whole file.
+    pub fn summary(&self, range: Option<(u32, u32)>) -> CoverageSummary {
+        let (first, last) = range.unwrap_or((0, u32::MAX));
+        let mut summary = CoverageSummary::default();
+        for (_, &count) in self.lines.range(first..=last) {
+            summary.lines_total += 1;
+            if count > 0 {
+                summary.lines_covered += 1;
+            }
+        }
+        for (_, &(total, taken)) in self.branches.range(first..=last) {
+            summary.branches_total += total;
+            summary.branches_covered += taken;
+        }
+        summary
+    }
+}
+
+/// A parsed report: coverage keyed by the paths it names.
+#[derive(Debug, Clone, Default)]
+pub struct CoverageReport {
+    pub files: BTreeMap<String, FileCoverage>,
+    /// Source directories that relative paths are resolved against
+    /// (Cobertura `<source>` elements).
+    pub source_roots: Vec<String>,
+}
+
+/// Parse a report, detecting its format.
+pub fn parse_report(text: &str) -> Option<(CoverageFormat, CoverageReport)> {
+    let format = CoverageFormat::detect(text)?;
+    let report = match format {
+        CoverageFormat::Lcov => parse_lcov(text),
+        CoverageFormat::Cobertura => parse_cobertura(text),
+        CoverageFormat::LlvmJson => parse_llvm_json(text)?,
+        CoverageFormat::GoProfile => parse_go_profile(text),
+    };
+    Some((format, report))
+}
+
+/// lcov tracefile: `SF:` opens a file, `DA:line,count` and
+/// `BRDA:line,block,branch,taken` record lines and branches.
+pub fn parse_lcov(text: &str) -> CoverageReport {
+    let mut report = CoverageReport::default();
+    let mut current: Option<(String, FileCoverage)> = None;
+    // (line, block, branch) outcomes seen, to count each once.
+    let mut branches: HashMap<u32, Vec<bool>> = HashMap::new();
+
+    let mut finish = |current: &mut Option<(String, FileCoverage)>,
+                      branches: &mut HashMap<u32, Vec<bool>>| {
+        if let Some((path, mut cov)) = current.take() {
+            for (line, outcomes) in branches.drain() {
+                let taken = outcomes.iter().filter(|t| **t).count() as u64;
+                cov.branch(line, outcomes.len() as u64, taken);
+            }
+            report.files.entry(path).or_default().merge(&cov);
+        }
+    };
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("SF:") {
+            finish(&mut current, &mut branches);
+            current = Some((path.to_string(), FileCoverage::default()));
+        } else if line == "end_of_record" {
+            finish(&mut current, &mut branches);
+        } else if let Some((_, cov)) = current.as_mut() {
+            if let Some(rest) = line.strip_prefix("DA:") {
+                let mut fields = rest.split(',');
+                let (Some(Ok(line)), Some(Ok(count))) = (
+                    fields.next().map(str::parse::<u32>),
+                    fields.next().map(str::parse::<f64>),
+                ) else {
+                    continue;
+                };
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                cov.hit(line, count.max(0.0) as u64);
+            } else if let Some(rest) = line.strip_prefix("BRDA:") {
+                let fields: Vec<&str> = rest.split(',').collect();
+                if let (Some(Ok(line)), Some(taken)) =
+                    (fields.first().map(|l| l.parse::<u32>()), fields.get(3))
+                {
+                    let taken = taken.parse::<u64>().is_ok_and(|t| t > 0);
+                    branches.entry(line).or_default().push(taken);
+                }
+            }
+        }
+    }
+    finish(&mut current, &mut branches);
+    report
+}
+
+/// Cobertura XML: `<class filename>` elements hold `<line number hits>`,
+/// with `branch="true"` lines carrying `condition-coverage="50% (1/2)"`.
+pub fn parse_cobertura(text: &str) -> CoverageReport {
+    let mut report = CoverageReport::default();
+    let mut in_source = false;
+    let mut current: Option<String> = None;
+
+    for event in XmlReader::new(text) {
+        match event {
+            XmlEvent::Start(tag) => match tag.local_name() {
+                "source" => in_source = true,
+                "class" => current = tag.attr("filename").map(str::to_string),
+                "line" => {
+                    let Some(path) = &current else { continue };
+                    let Some(line) = tag.attr("number").and_then(|n| n.parse::<u32>().ok()) else {
+                        continue;
+                    };
+                    let hits = tag
+                        .attr("hits")
+                        .and_then(|h| h.parse::<f64>().ok())
+                        .unwrap_or(0.0);
+                    let cov = report.files.entry(path.clone()).or_default();
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    cov.hit(line, hits.max(0.0) as u64);
+                    if tag.attr("branch") == Some("true") {
+                        if let Some((taken, total)) =
+                            tag.attr("condition-coverage").and_then(condition_counts)
+                        {
+                            cov.branch(line, total, taken);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::Text(text) if in_source => report.source_roots.push(text.trim().to_string()),
+            XmlEvent::End(name) => match super::xml::local_name(&name) {
+                "source" => in_source = false,
+                "class" => current = None,
+                _ => {}
+            },
+            XmlEvent::Text(_) => {}
+        }
+    }
+    report
+}
+
+/// `"50% (1/2)"` → `(1, 2)`.
+fn condition_counts(value: &str) -> Option<(u64, u64)> {
+    let inner = value.split_once('(')?.1.split_once(')')?.0;
+    let (taken, total) = inner.split_once('/')?;
+    Some((taken.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// llvm-cov JSON export. Line counts follow llvm-cov's own rule: a line is
+/// instrumented when a region starts on it or a counted region spans it, and
+/// its count is the highest of the spanning region and those starting on it. Branches contribute their true
+/// and false outcomes.
+pub fn parse_llvm_json(text: &str) -> Option<CoverageReport> {
+    let json: serde_json::Value = serde_json::from_str(text).ok()?;
+    let mut report = CoverageReport::default();
+
+    for data in json.get("data")?.as_array()? {
+        for file in data
+            .get("files")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(path) = file.get("filename").and_then(serde_json::Value::as_str) else {
+                continue;
+            };
+            let mut cov = FileCoverage::default();
+
+            let segments: Vec<LlvmSegment> = file
+                .get("segments")
+                .and_then(serde_json::Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(LlvmSegment::from_json)
+                .collect();
+            llvm_line_counts(&segments, &mut cov);
+
+            for branch in file
+                .get("branches")
+                .and_then(serde_json::Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(serde_json::Value::as_array)
+            {
+                let field = |i: usize| branch.get(i).and_then(serde_json::Value::as_u64);
+                let (Some(line), Some(true_count), Some(false_count)) =
+                    (field(0), field(4), field(5))
+                else {
+                    continue;
+                };
+                let taken = u64::from(true_count > 0) + u64::from(false_count > 0);
+                let line = u32::try_from(line).unwrap_or(u32::MAX);
+                let entry = cov.branches.entry(line).or_insert((0, 0));
+                entry.0 += 2;
+                entry.1 += taken;
+            }
+
+            report
+                .files
+                .entry(path.to_string())
+                .or_default()
+                .merge(&cov);
+        }
+    }
+    Some(report)
+}
+
+/// One entry of an llvm-cov `segments` array:
+/// `[line, col, count, has_count, is_region_entry, is_gap_region]`.
+struct LlvmSegment {
+    line: u32,
+    count: u64,
+    has_count: bool,
+    is_region_entry: bool,
+    is_gap: bool,
+}
+
+impl LlvmSegment {
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let fields = value.as_array()?;
+        let flag = |i: usize| fields.get(i).and_then(serde_json::Value::as_bool);
+        Some(Self {
+            line: u32::try_from(fields.first()?.as_u64()?).ok()?,
+            count: fields.get(2)?.as_u64()?,
+            has_count: flag(3)?,
+            is_region_entry: flag(4)?,
+            is_gap: flag(5).unwrap_or(false),
+        })
+    }
+}
+
+fn llvm_line_counts(segments: &[LlvmSegment], cov: &mut FileCoverage) {
+    let Some(last_line) = segments.last().map(|s| s.line) else {
+        return;
+    };
+    let mut idx = 0;
+    let mut wrapped: Option<&LlvmSegment> = None;
+    for line in segments[0].line..=last_line {
+        let start = idx;
+        while idx < segments.len() && segments[idx].line == line {
+            idx += 1;
+        }
+        let starting = &segments[start..idx];
+
+        let mut mapped = wrapped.is_some_and(|w| w.has_count && !w.is_gap);
+        let mut count = wrapped.filter(|w| w.has_count).map_or(0, |w| w.count);
+        for seg in starting
+            .iter()
+            .filter(|s| s.has_count && !s.is_gap && s.is_region_entry)
+        {
+            mapped = true;
+            count = count.max(seg.count);
+        }
+        if mapped {
+            cov.hit(line, count);
+        }
+        if let Some(last) = starting.last() {
+            wrapped = Some(last);
+        }
+    }
+}
+
+/// Go cover profile: `mode: set` then
+/// `path/file.go:startLine.startCol,endLine.endCol statements count`.
+pub fn parse_go_profile(text: &str) -> CoverageReport {
+    let mut report = CoverageReport::default();
+    for line in text.lines().skip(1) {
+        let Some((path, rest)) = line.rsplit_once(':') else {
+            continue;
+        };
+        let mut fields = rest.split_whitespace();
+        let (Some(range), Some(_statements), Some(count)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let Some((start, end)) = range.split_once(',') else {
+            continue;
+        };
+        let line_of = |pos: &str| pos.split('.').next()?.parse::<u32>().ok();
+        let (Some(start), Some(end), Ok(count)) = (line_of(start), line_of(end), count.parse())
+        else {
+            continue;
+        };
+        let cov = report.files.entry(path.to_string()).or_default();
+        for line in start..=end {
+            cov.hit(line, count);
+        }
+    }
+    report
+}
+
+// ── Path resolution ───────────────────────────────────────────────
+
+/// Maps the paths named in reports onto File node names: directly,
+/// relative to the repo root, or by the longest unambiguous path suffix, so
+/// reports produced in CI containers or with Go import paths still resolve.
+pub(crate) struct PathResolver {
+    root: String,
+    known: HashSet<String>,
+}
+
+impl PathResolver {
+    pub(crate) fn new(root: &Path, known: HashSet<String>) -> Self {
+        Self {
+            root: root.to_string_lossy().replace('\\', "/"),
+            known,
+        }
+    }
+
+    pub(crate) fn resolve(&self, raw: &str, source_roots: &[String]) -> Option<String> {
+        let raw = raw.replace('\\', "/");
+        let mut candidates: Vec<String> = source_roots
+            .iter()
+            .map(|root| format!("{}/{raw}", root.trim_end_matches(['/', '\\'])))
+            .collect();
+        candidates.push(raw);
+
+        for candidate in &candidates {
+            let relative = candidate
+                .strip_prefix(&self.root)
+                .unwrap_or(candidate)
+                .trim_start_matches("./")
+                .trim_start_matches('/');
+            if self.known.contains(relative) {
+                return Some(relative.to_string());
+            }
+        }
+        candidates.iter().find_map(|c| self.resolve_suffix(c))
+    }
+
+    /// The known file whose path is the longest suffix of `path`, or the
+    /// single known file that ends with `path`.
+    fn resolve_suffix(&self, path: &str) -> Option<String> {
+        let path = path.trim_start_matches("./");
+        let mut best: Option<&String> = None;
+        let mut longer: Vec<&String> = Vec::new();
+        for known in &self.known {
+            if path.ends_with(&format!("/{known}")) {
+                if best.is_none_or(|b| known.len() > b.len()) {
+                    best = Some(known);
+                }
+            } else if known.ends_with(&format!("/{path}")) {
+                longer.push(known);
+            }
+        }
+        best.cloned().or_else(|| match longer.as_slice() {
+            [only] => Some((*only).clone()),
+            _ => None,
+        })
+    }
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Stores coverage under each node's `coverage` metadata, reapplied whenever
+/// structure or graph extraction rewrites the nodes or the reports change.
+#[derive(Debug)]
+pub struct CoverageExtractor {
+    repo_path: PathBuf,
+    settings: CoverageExtractionConfig,
+}
+
+impl CoverageExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.coverage.clone(),
+        }
+    }
+
+    /// Report files matching the configured patterns, relative to the repo.
+    fn discover_reports(&self) -> Vec<PathBuf> {
+        if !self.settings.enabled {
+            return Vec::new();
+        }
+        let root = glob::Pattern::escape(&self.repo_path.to_string_lossy());
+        let mut reports: Vec<PathBuf> = self
+            .settings
+            .reports
+            .iter()
+            .filter_map(|pattern| glob::glob(&format!("{root}/{pattern}")).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|p| p.is_file())
+            .collect();
+        reports.sort();
+        reports.dedup();
+        reports
+    }
+
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let mut hasher = DefaultHasher::new();
+        for report in self.discover_reports() {
+            report.hash(&mut hasher);
+            if let Ok(meta) = std::fs::metadata(&report) {
+                meta.len().hash(&mut hasher);
+                meta.modified().ok().hash(&mut hasher);
+            }
+        }
+        // Structure and graph extraction rewrite node metadata.
+        let structure = store.get_checkpoint("structure_last_sha").await?;
+        let graph = store.get_checkpoint("graph_last_sha").await?;
+        Ok(format!(
+            "{}:{}:{:x}",
+            structure.unwrap_or_default(),
+            graph.unwrap_or_default(),
+            hasher.finish()
+        ))
+    }
+
+    /// Parse every report and merge coverage per repository file.
+    fn load_coverage(
+        &self,
+        resolver: &PathResolver,
+    ) -> HashMap<String, (FileCoverage, Vec<CoverageFormat>)> {
+        let mut merged: HashMap<String, (FileCoverage, Vec<CoverageFormat>)> = HashMap::new();
+        for path in self.discover_reports() {
+            if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_REPORT_BYTES) {
+                warn!(report = %path.display(), "Coverage report too large, skipping");
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some((format, report)) = parse_report(&text) else {
+                debug!(report = %path.display(), "Unrecognized coverage format");
+                continue;
+            };
+            let mut unresolved = 0usize;
+            for (raw, cov) in &report.files {
+                let Some(file) = resolver.resolve(raw, &report.source_roots) else {
+                    unresolved += 1;
+                    continue;
+                };
+                let (entry, formats) = merged.entry(file).or_default();
+                entry.merge(cov);
+                if !formats.contains(&format) {
+                    formats.push(format);
+                    formats.sort();
+                }
+            }
+            info!(
+                report = %path.display(),
+                format = format.as_str(),
+                files = report.files.len(),
+                unresolved,
+                "Read coverage report"
+            );
+        }
+        merged
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for CoverageExtractor {
+    fn name(&self) -> &'static str {
+        "coverage"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "coverage_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "coverage_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        let files = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            })
+            .await?;
+        let resolver = PathResolver::new(
+            &self.repo_path,
+            files.iter().map(|f| f.name.clone()).collect(),
+        );
+        let coverage = self.load_coverage(&resolver);
+
+        for file in files {
+            let value = coverage
+                .get(&file.name)
+                .map(|(cov, formats)| (cov.summary(None), formats))
+                .filter(|(summary, _)| summary.lines_total > 0)
+                .map(|(summary, formats)| summary.to_json(formats));
+            if set_coverage(store, file, value).await? {
+                stats.nodes_updated += 1;
+            }
+        }
+
+        let functions = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Function),
+                ..Default::default()
+            })
+            .await?;
+        for func in functions {
+            let value = function_lines(&func).and_then(|(file, range)| {
+                let (cov, formats) = coverage.get(file)?;
+                let summary = cov.summary(Some(range));
+                (summary.lines_total > 0).then(|| summary.to_json(formats))
+            });
+            if set_coverage(store, func, value).await? {
+                stats.nodes_updated += 1;
+            }
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("coverage_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            files = coverage.len(),
+            updated = stats.nodes_updated,
+            duration = ?stats.duration,
+            "Coverage extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// A function's file and inclusive 1-based line range.
+pub(crate) fn function_lines(func: &Node) -> Option<(&str, (u32, u32))> {
+    let file = func.metadata.get("file")?.as_str()?;
+    let span = func.metadata.get("span")?;
+    let row = |key: &str| {
+        span.get(key)
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|r| u32::try_from(r + 1).ok())
+    };
+    Some((file, (row("start_row")?, row("end_row")?)))
+}
+
+/// Store or clear a node's coverage. Returns whether the node changed.
+async fn set_coverage(
+    store: &dyn HomerStore,
+    mut node: Node,
+    value: Option<serde_json::Value>,
+) -> crate::error::Result<bool> {
+    if node.metadata.get(COVERAGE_KEY) == value.as_ref() {
+        return Ok(false);
+    }
+    match value {
+        Some(value) => node.metadata.insert(COVERAGE_KEY.to_string(), value),
+        None => node.metadata.remove(COVERAGE_KEY),
+    };
+    store.upsert_node(&node).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::NodeId;
+
+    #[test]
+    fn parses_lcov_lines_and_branches() {
+        let report = parse_lcov(
+            "TN:\n\
+             SF:/ci/work/repo/src/lib.rs\n\
+             DA:1,4\nDA:2,0\nDA:3,1\n\
+             BRDA:3,0,0,1\nBRDA:3,0,1,-\n\
+             end_of_record\n",
+        );
+        let cov = &report.files["/ci/work/repo/src/lib.rs"];
+        let summary = cov.summary(None);
+        assert_eq!((summary.lines_covered, summary.lines_total), (2, 3));
+        assert_eq!((summary.branches_covered, summary.branches_total), (1, 2));
+        assert_eq!(cov.summary(Some((2, 2))).lines_covered, 0);
+    }
+
+    #[test]
+    fn parses_cobertura_with_source_roots() {
+        let report = parse_cobertura(
+            r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5">
+  <sources><source>/home/ci/app</source></sources>
+  <packages><package name="pkg"><classes>
+    <class name="util" filename="pkg/util.py">
+      <lines>
+        <line number="1" hits="3"/>
+        <line number="2" hits="0" branch="true" condition-coverage="50% (1/2)"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#,
+        );
+        assert_eq!(report.source_roots, vec!["/home/ci/app"]);
+        let summary = report.files["pkg/util.py"].summary(None);
+        assert_eq!((summary.lines_covered, summary.lines_total), (1, 2));
+        assert_eq!((summary.branches_covered, summary.branches_total), (1, 2));
+    }
+
+    #[test]
+    fn parses_llvm_json_segments() {
+        // fn on lines 1-5; an unexecuted branch body opens on line 3 and
+        // covers all of line 4.
+        let json = serde_json::json!({
+            "type": "llvm.coverage.json.export",
+            "version": "2.0.1",
+            "data": [{
+                "files": [{
+                    "filename": "/repo/src/lib.rs",
+                    "segments": [
+                        [1, 1, 5, true, true, false],
+                        [3, 5, 0, true, true, false],
+                        [4, 6, 5, true, false, false],
+                        [5, 2, 0, false, false, false]
+                    ],
+                    "branches": [[2, 8, 2, 12, 5, 0, 0, 0, 4]]
+                }]
+            }]
+        });
+        let report = parse_llvm_json(&json.to_string()).unwrap();
+        let cov = &report.files["/repo/src/lib.rs"];
+        assert_eq!(cov.lines.get(&1), Some(&5));
+        assert_eq!(cov.lines.get(&2), Some(&5));
+        assert_eq!(
+            cov.lines.get(&3),
+            Some(&5),
+            "line also enters a counted region"
+        );
+        assert_eq!(cov.lines.get(&4), Some(&0));
+        let summary = cov.summary(None);
+        assert_eq!((summary.branches_covered, summary.branches_total), (1, 2));
+    }
+
+    #[test]
+    fn parses_go_profiles_and_resolves_import_paths() {
+        let report = parse_go_profile(
+            "mode: set\n\
+             example.com/app/pkg/handler.go:3.20,5.2 2 1\n\
+             example.com/app/pkg/handler.go:7.20,9.2 1 0\n",
+        );
+        assert_eq!(
+            CoverageFormat::detect("mode: atomic\n"),
+            Some(CoverageFormat::GoProfile)
+        );
+        let resolver = PathResolver::new(
+            Path::new("/repo"),
+            ["pkg/handler.go".to_string(), "handler.go".to_string()]
+                .into_iter()
+                .collect(),
+        );
+        let (raw, cov) = report.files.iter().next().unwrap();
+        assert_eq!(
+            resolver.resolve(raw, &[]).as_deref(),
+            Some("pkg/handler.go"),
+            "longest suffix wins"
+        );
+        assert_eq!(cov.summary(None).lines_covered, 3);
+        assert_eq!(cov.summary(None).lines_total, 6);
+    }
+
+    async fn add_node(store: &SqliteStore, kind: NodeKind, name: &str, meta: serde_json::Value) {
+        let metadata = meta
+            .as_object()
+            .map(|m| m.clone().into_iter().collect())
+            .unwrap_or_default();
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: chrono::Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn attaches_coverage_to_files_and_functions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("coverage")).unwrap();
+        std::fs::write(
+            root.join("coverage/lcov.info"),
+            format!(
+                "SF:{}/src/lib.rs\nDA:1,1\nDA:2,1\nDA:5,0\nDA:6,0\nend_of_record\n",
+                root.display()
+            ),
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        add_node(&store, NodeKind::File, "src/lib.rs", serde_json::json!({})).await;
+        add_node(
+            &store,
+            NodeKind::File,
+            "src/other.rs",
+            serde_json::json!({}),
+        )
+        .await;
+        for (name, start, end) in [("covered", 0, 2), ("untested", 4, 6)] {
+            add_node(
+                &store,
+                NodeKind::Function,
+                &format!("src/lib.rs::{name}"),
+                serde_json::json!({
+                    "file": "src/lib.rs",
+                    "span": { "start_row": start, "end_row": end },
+                }),
+            )
+            .await;
+        }
+
+        let extractor = CoverageExtractor::new(root, &HomerConfig::default());
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let node = |kind, name: &'static str| {
+            let store = &store;
+            async move { store.get_node_by_name(kind, name).await.unwrap().unwrap() }
+        };
+        let file = CoverageSummary::of(&node(NodeKind::File, "src/lib.rs").await).unwrap();
+        assert_eq!((file.lines_covered, file.lines_total), (2, 4));
+        assert!(CoverageSummary::of(&node(NodeKind::File, "src/other.rs").await).is_none());
+
+        let covered =
+            CoverageSummary::of(&node(NodeKind::Function, "src/lib.rs::covered").await).unwrap();
+        assert!((covered.line_rate() - 1.0).abs() < f64::EPSILON);
+        let untested =
+            CoverageSummary::of(&node(NodeKind::Function, "src/lib.rs::untested").await).unwrap();
+        assert_eq!(untested.lines_covered, 0);
+        assert_eq!(untested.lines_total, 2);
+
+        // Removing the report clears stale coverage.
+        std::fs::remove_file(root.join("coverage/lcov.info")).unwrap();
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(CoverageSummary::of(&node(NodeKind::File, "src/lib.rs").await).is_none());
+    }
+}
@@ -0,0 +1,843 @@
+// Environment variable and configuration key extraction: `Reads` edges from
+// code and `Defines` edges from the templates, CI jobs and config files that
+// document them.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use glob::Pattern;
+use homer_graphs::LanguageRegistry;
+use homer_graphs::config_access::{self, AccessPatterns, ReadKind, is_env_name};
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::config::{EnvExtractionConfig, HomerConfig};
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeFilter, NodeId, NodeKind,
+};
+
+use super::api_contract::{function_spans, link};
+use super::infra::ensure_env_var;
+use super::traits::{ExtractStats, Extractor};
+use super::{ci, yaml};
+
+/// Most keys read from one config file.
+const MAX_CONFIG_KEYS: usize = 2000;
+
+// ── Definitions ───────────────────────────────────────────────────
+
+/// Variable names an env template documents, commented-out ones included.
+pub fn parse_env_file(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_start().trim_start_matches('#').trim_start();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if is_env_name(key) && !names.iter().any(|n| n == key) {
+            names.push(key.to_string());
+        }
+    }
+    names
+}
+
+/// Variables a CI file sets: `None` for the whole file, else per job id.
+pub fn ci_env(path: &str, doc: &Value) -> Vec<(Option<String>, Vec<String>)> {
+    let keys = |value: Option<&Value>| -> Vec<String> {
+        value
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(k, _)| k.clone())
+            .filter(|k| is_env_name(k))
+            .collect()
+    };
+    let jobs = |key: &str| {
+        doc.get(key)
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+    };
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+
+    let mut found = Vec::new();
+    if path.starts_with(".github/workflows/") {
+        found.push((None, keys(doc.get("env"))));
+        for (id, job) in jobs("jobs") {
+            let mut names = keys(job.get("env"));
+            names.extend(keys(job.pointer("/container/env")));
+            for step in job
+                .get("steps")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                names.extend(keys(step.get("env")));
+            }
+            found.push((Some(id.clone()), names));
+        }
+    } else if file_name == ".gitlab-ci.yml" {
+        found.push((None, keys(doc.get("variables"))));
+        for (id, job) in doc.as_object().into_iter().flatten() {
+            if id.starts_with('.') || ci::GITLAB_RESERVED.contains(&id.as_str()) {
+                continue;
+            }
+            found.push((Some(id.clone()), keys(job.get("variables"))));
+        }
+    } else if path.starts_with(".circleci/") {
+        for (id, job) in jobs("jobs") {
+            let mut names = keys(job.get("environment"));
+            for image in job
+                .get("docker")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                names.extend(keys(image.get("environment")));
+            }
+            found.push((Some(id.clone()), names));
+        }
+    } else if file_name.to_ascii_lowercase().starts_with("taskfile") {
+        found.push((None, keys(doc.get("env"))));
+        for (id, task) in jobs("tasks") {
+            found.push((Some(id.clone()), keys(task.get("env"))));
+        }
+    }
+    for (_, names) in &mut found {
+        let mut seen = HashSet::new();
+        names.retain(|n| seen.insert(n.clone()));
+    }
+    found.retain(|(_, names)| !names.is_empty());
+    found
+}
+
+/// Dotted keys a config file defines, sections included, in file order for
+/// `.properties` and sorted otherwise. Unreadable files yield nothing.
+pub fn config_file_keys(path: &str, text: &str) -> Vec<String> {
+    let extension = path.rsplit('.').next().unwrap_or_default();
+    let mut keys = Vec::new();
+    match extension {
+        "properties" => {
+            for line in text.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with(['#', '!']) {
+                    continue;
+                }
+                let key = line
+                    .split(['=', ':', ' ', '\t'])
+                    .next()
+                    .unwrap_or_default()
+                    .trim();
+                if valid_key(key) {
+                    keys.push(key.to_string());
+                }
+            }
+        }
+        "json" => {
+            if let Ok(value) = serde_json::from_str::<Value>(text) {
+                flatten(&value, "", &mut keys);
+            }
+        }
+        "toml" => {
+            if let Ok(value) = toml::from_str::<toml::Value>(text)
+                && let Ok(value) = serde_json::to_value(value)
+            {
+                flatten(&value, "", &mut keys);
+            }
+        }
+        "yml" | "yaml" => {
+            for doc in yaml::parse_documents(text) {
+                flatten(&doc, "", &mut keys);
+            }
+        }
+        _ => {}
+    }
+    let mut seen = HashSet::new();
+    keys.retain(|k| seen.insert(k.clone()));
+    keys.truncate(MAX_CONFIG_KEYS);
+    keys
+}
+
+/// Every key path under `value`, section keys before their children.
+fn flatten(value: &Value, prefix: &str, out: &mut Vec<String>) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    for (key, child) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if !valid_key(&path) {
+            continue;
+        }
+        out.push(path.clone());
+        flatten(child, &path, out);
+    }
+}
+
+fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 128
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// A config key in the form keys are matched by: lowercase, `:` as `.`,
+/// and without `-` or `_`, so `db.max-pool` matches `Db:MaxPool`.
+pub fn canonical_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| !matches!(c, '-' | '_'))
+        .map(|c| {
+            if c == ':' {
+                '.'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+/// One file or CI job documenting variables or keys: an `.env.example`-style
+/// template, the `env`/`variables`/`environment` of a CI file, or a config
+/// file flattened into dotted paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Definition {
+    /// Repo-relative path of the documenting file.
+    file: String,
+    /// CI job id within the file, when a job rather than the file sets it.
+    job: Option<String>,
+    kind: ReadKind,
+    /// `template`, `ci`, or `config`: what sort of file documents it.
+    via: &'static str,
+    names: Vec<String>,
+}
+
+/// Whether `name` is a config section holding other keys of `definition`.
+fn is_section(definition: &Definition, name: &str) -> bool {
+    definition.kind == ReadKind::ConfigKey
+        && definition.names.iter().any(|n| {
+            n.strip_prefix(name)
+                .is_some_and(|rest| rest.starts_with('.'))
+        })
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Links each read found by `homer_graphs::config_access` from the innermost
+/// function around it (the file when there is none) to an `EnvVar` or
+/// `ConfigKey` node. Its `Defines` edges are tagged with this extractor so
+/// the infra extractor's survive a rebuild.
+#[derive(Debug)]
+pub struct EnvExtractor {
+    repo_path: PathBuf,
+    settings: EnvExtractionConfig,
+    exclude: Vec<String>,
+}
+
+impl EnvExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.env.clone(),
+            exclude: config.extraction.structure.exclude_patterns.clone(),
+        }
+    }
+
+    fn patterns(&self) -> AccessPatterns {
+        let mut patterns = AccessPatterns::default();
+        patterns
+            .config_calls
+            .extend(self.settings.config_calls.iter().cloned());
+        patterns
+            .config_indexes
+            .extend(self.settings.config_indexes.iter().cloned());
+        patterns
+    }
+
+    /// Files matching `globs` and not excluded: repo-relative path and
+    /// contents, sorted by path.
+    fn discover(&self, globs: &[String]) -> Vec<(String, String)> {
+        let exclude: Vec<Pattern> = self
+            .exclude
+            .iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .collect();
+        let root = Pattern::escape(&self.repo_path.to_string_lossy());
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        for pattern in globs {
+            let Ok(paths) = glob::glob(&format!("{root}/{pattern}")) else {
+                continue;
+            };
+            for path in paths.flatten() {
+                let relative = path
+                    .strip_prefix(&self.repo_path)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                if exclude.iter().any(|p| p.matches(&relative)) || !seen.insert(relative.clone()) {
+                    continue;
+                }
+                if let Ok(text) = std::fs::read_to_string(&path) {
+                    found.push((relative, text));
+                }
+            }
+        }
+        found.sort();
+        found
+    }
+
+    /// Every documented variable and config key, by documenting file or job.
+    fn definitions(&self) -> Vec<Definition> {
+        let mut definitions = Vec::new();
+        for (file, text) in self.discover(&self.settings.env_files) {
+            definitions.push(Definition {
+                file,
+                job: None,
+                kind: ReadKind::EnvVar,
+                via: "template",
+                names: parse_env_file(&text),
+            });
+        }
+        for (file, text) in ci::discover(&self.repo_path) {
+            let yaml = Path::new(&file)
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("yml") || e.eq_ignore_ascii_case("yaml"));
+            if !yaml {
+                continue;
+            }
+            for doc in yaml::parse_documents(&text) {
+                for (job, names) in ci_env(&file, &doc) {
+                    definitions.push(Definition {
+                        file: file.clone(),
+                        job,
+                        kind: ReadKind::EnvVar,
+                        via: "ci",
+                        names,
+                    });
+                }
+            }
+        }
+        for (file, text) in self.discover(&self.settings.config_files) {
+            let names = config_file_keys(&file, &text);
+            definitions.push(Definition {
+                file,
+                job: None,
+                kind: ReadKind::ConfigKey,
+                via: "config",
+                names,
+            });
+        }
+        definitions.retain(|d| !d.names.is_empty());
+        definitions
+    }
+
+    /// Documenting files, settings, and the graph checkpoint, since reads
+    /// follow the code.
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let graph = store
+            .get_checkpoint("graph_last_sha")
+            .await?
+            .unwrap_or_default();
+        let mut hasher = DefaultHasher::new();
+        if self.settings.enabled {
+            self.discover(&self.settings.env_files).hash(&mut hasher);
+            self.discover(&self.settings.config_files).hash(&mut hasher);
+            ci::discover(&self.repo_path).hash(&mut hasher);
+            self.settings.config_calls.hash(&mut hasher);
+            self.settings.config_indexes.hash(&mut hasher);
+        }
+        Ok(format!("{graph}:{:x}", hasher.finish()))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for EnvExtractor {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "env_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "env_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        store.delete_edges_by_kind(HyperedgeKind::Reads).await?;
+        store
+            .delete_edges_by_extractor(HyperedgeKind::Defines, "env")
+            .await?;
+
+        let (definitions, reads) = if self.settings.enabled {
+            (self.definitions(), self.scan_reads(store).await?)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let ids = store_variables(store, &definitions, &reads, &mut stats).await?;
+        self.link_definitions(store, &definitions, &ids, &mut stats)
+            .await?;
+
+        let mut linked: HashSet<(NodeId, NodeId)> = HashSet::new();
+        for read in &reads {
+            let Some(&id) = ids.get(&(read.kind, read.key.clone())) else {
+                continue;
+            };
+            if linked.insert((read.reader, id)) {
+                let via = match read.kind {
+                    ReadKind::EnvVar => "env",
+                    ReadKind::ConfigKey => "config",
+                };
+                link(
+                    store,
+                    HyperedgeKind::Reads,
+                    (read.reader, roles::READER),
+                    (id, roles::READ_VAR),
+                    0.9,
+                    serde_json::json!({ "via": via, "line": read.line }),
+                )
+                .await?;
+                stats.edges_created += 1;
+            }
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("env_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            definitions = definitions.len(),
+            reads = reads.len(),
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "Environment extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// A read resolved to the code doing it.
+#[derive(Debug)]
+struct Read {
+    reader: NodeId,
+    kind: ReadKind,
+    key: String,
+    line: usize,
+}
+
+impl EnvExtractor {
+    /// Every variable and key read in a tracked source file.
+    async fn scan_reads(&self, store: &dyn HomerStore) -> crate::error::Result<Vec<Read>> {
+        let registry = LanguageRegistry::new();
+        let patterns = self.patterns();
+        let mut reads = Vec::new();
+        for file in store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            })
+            .await?
+        {
+            let Some(lang) = registry.for_file(Path::new(&file.name)) else {
+                continue;
+            };
+            let Ok(source) = std::fs::read_to_string(self.repo_path.join(&file.name)) else {
+                continue;
+            };
+            if !patterns.may_match(&source) {
+                continue;
+            }
+            let mut parser = tree_sitter::Parser::new();
+            if parser.set_language(&lang.tree_sitter_language()).is_err() {
+                continue;
+            }
+            let Some(tree) = parser.parse(&source, None) else {
+                continue;
+            };
+            let found = config_access::config_reads(&tree, &source, &patterns);
+            if found.is_empty() {
+                continue;
+            }
+            let spans = function_spans(store, &file.name).await?;
+            for read in found {
+                let row = read.span.start_row;
+                let reader = spans
+                    .iter()
+                    .filter(|(_, start, end)| (*start..=*end).contains(&row))
+                    .min_by_key(|(_, start, end)| end - start)
+                    .map_or(file.id, |(f, _, _)| *f);
+                reads.push(Read {
+                    reader,
+                    kind: read.kind,
+                    key: read.key,
+                    line: row + 1,
+                });
+            }
+        }
+        Ok(reads)
+    }
+
+    /// One `Defines` edge per documenting file or CI job.
+    async fn link_definitions(
+        &self,
+        store: &dyn HomerStore,
+        definitions: &[Definition],
+        ids: &HashMap<(ReadKind, String), NodeId>,
+        stats: &mut ExtractStats,
+    ) -> crate::error::Result<()> {
+        // Config sections only have a node when code reads them, so a read
+        // section counts as documented by the file defining it.
+        let mut merged = BTreeMap::new();
+        for definition in definitions {
+            let (_, members) = merged
+                .entry((definition.file.clone(), definition.job.clone()))
+                .or_insert((definition.via, Vec::new()));
+            for name in &definition.names {
+                if let Some(&id) = ids.get(&(definition.kind, name.clone()))
+                    && !members.contains(&id)
+                {
+                    members.push(id);
+                }
+            }
+        }
+
+        for ((file, job), (via, variables)) in merged {
+            if variables.is_empty() {
+                continue;
+            }
+            let definer = definer_node(store, &file, job.as_deref(), stats).await?;
+            let mut members = vec![HyperedgeMember {
+                node_id: definer,
+                role: roles::DEFINER.to_string(),
+                position: 0,
+            }];
+            for id in variables {
+                members.push(HyperedgeMember {
+                    node_id: id,
+                    role: roles::DEFINED_VAR.to_string(),
+                    position: u32::try_from(members.len()).unwrap_or(u32::MAX),
+                });
+            }
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Defines,
+                    members,
+                    confidence: 1.0,
+                    last_updated: Utc::now(),
+                    metadata: HashMap::from([
+                        ("extractor".to_string(), serde_json::json!("env")),
+                        ("source".to_string(), serde_json::json!(file)),
+                        ("via".to_string(), serde_json::json!(via)),
+                    ]),
+                })
+                .await?;
+            stats.edges_created += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The CI job node for `job` when the graph has one, else the file's node,
+/// created if missing.
+async fn definer_node(
+    store: &dyn HomerStore,
+    file: &str,
+    job: Option<&str>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<NodeId> {
+    if let Some(job) = job
+        && let Some(node) = store
+            .get_node_by_name(NodeKind::CiJob, &format!("{file}::{job}"))
+            .await?
+    {
+        return Ok(node.id);
+    }
+    if let Some(node) = store.get_node_by_name(NodeKind::File, file).await? {
+        return Ok(node.id);
+    }
+    stats.nodes_created += 1;
+    store
+        .upsert_node(&Node {
+            id: NodeId(0),
+            kind: NodeKind::File,
+            name: file.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: HashMap::new(),
+        })
+        .await
+}
+
+/// Upsert `EnvVar` and `ConfigKey` nodes for everything defined or read and
+/// mark vanished config keys stale. Reads of a defined config key are
+/// rewritten to its defined spelling. Returns ids keyed by kind and name.
+async fn store_variables(
+    store: &dyn HomerStore,
+    definitions: &[Definition],
+    reads: &[Read],
+    stats: &mut ExtractStats,
+) -> crate::error::Result<HashMap<(ReadKind, String), NodeId>> {
+    let mut spelling: HashMap<String, String> = HashMap::new();
+    for definition in definitions.iter().filter(|d| d.kind == ReadKind::ConfigKey) {
+        for name in &definition.names {
+            spelling
+                .entry(canonical_key(name))
+                .or_insert_with(|| name.clone());
+        }
+    }
+
+    let mut wanted: BTreeSet<(ReadKind, String)> = BTreeSet::new();
+    for definition in definitions {
+        for name in &definition.names {
+            if !is_section(definition, name) {
+                wanted.insert((definition.kind, name.clone()));
+            }
+        }
+    }
+    for read in reads {
+        wanted.insert((read.kind, read.key.clone()));
+    }
+
+    let existing: HashMap<String, NodeId> = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::ConfigKey),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(|n| (n.name, n.id))
+        .collect();
+    let mut ids = HashMap::new();
+    let mut kept = HashSet::new();
+    for (kind, name) in wanted {
+        let id = match kind {
+            ReadKind::EnvVar => ensure_env_var(store, stats, &name).await?,
+            ReadKind::ConfigKey => {
+                let defined = spelling
+                    .get(&canonical_key(&name))
+                    .cloned()
+                    .unwrap_or_else(|| name.clone());
+                let id = store
+                    .upsert_node(&Node {
+                        id: NodeId(0),
+                        kind: NodeKind::ConfigKey,
+                        name: defined.clone(),
+                        content_hash: None,
+                        last_extracted: Utc::now(),
+                        metadata: HashMap::new(),
+                    })
+                    .await?;
+                if existing.contains_key(&defined) {
+                    stats.nodes_updated += 1;
+                } else {
+                    stats.nodes_created += 1;
+                }
+                ids.insert((kind, defined.clone()), id);
+                kept.insert(defined);
+                id
+            }
+        };
+        ids.insert((kind, name), id);
+    }
+    for (name, id) in existing {
+        if !kept.contains(&name) {
+            store.mark_node_stale(id).await?;
+        }
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_templates_and_ci_files() {
+        assert_eq!(
+            parse_env_file(
+                "# Database\nDATABASE_URL=postgres://localhost\nexport PORT=8080\n\
+                 # SENTRY_DSN=\n# Set to true for verbose logs\nlower=ignored\nPORT=9090\n"
+            ),
+            vec!["DATABASE_URL", "PORT", "SENTRY_DSN"]
+        );
+
+        let github = yaml::parse(
+            "env:\n  RUST_LOG: info\njobs:\n  test:\n    env:\n      DATABASE_URL: postgres://ci\n    \
+             steps:\n      - run: cargo test\n        env:\n          API_TOKEN: x\n",
+        );
+        assert_eq!(
+            ci_env(".github/workflows/ci.yml", &github),
+            vec![
+                (None, vec!["RUST_LOG".to_string()]),
+                (
+                    Some("test".to_string()),
+                    vec!["DATABASE_URL".to_string(), "API_TOKEN".to_string()]
+                ),
+            ]
+        );
+
+        let gitlab = yaml::parse(
+            "variables:\n  REGISTRY: r\n.template:\n  variables:\n    HIDDEN: x\n\
+             build:\n  variables:\n    IMAGE_TAG: latest\n  script: make\n",
+        );
+        assert_eq!(
+            ci_env(".gitlab-ci.yml", &gitlab),
+            vec![
+                (None, vec!["REGISTRY".to_string()]),
+                (Some("build".to_string()), vec!["IMAGE_TAG".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn config_files_flatten_to_dotted_keys() {
+        assert_eq!(
+            config_file_keys(
+                "src/main/resources/application.properties",
+                "# comment\nserver.port=8080\nspring.datasource.url: jdbc:x\n"
+            ),
+            vec!["server.port", "spring.datasource.url"]
+        );
+        assert_eq!(
+            config_file_keys(
+                "config/app.yml",
+                "database:\n  host: localhost\n  max-pool: 5\n"
+            ),
+            vec!["database", "database.host", "database.max-pool"]
+        );
+        assert_eq!(
+            config_file_keys(
+                "appsettings.json",
+                r#"{"Logging": {"Level": "Info"}, "Port": 1}"#
+            ),
+            vec!["Logging", "Logging.Level", "Port"]
+        );
+        assert_eq!(
+            config_file_keys("config/app.toml", "[cache]\nttl = 30\n"),
+            vec!["cache", "cache.ttl"]
+        );
+        assert_eq!(
+            canonical_key("Database:Max_Pool"),
+            canonical_key("database.max-pool")
+        );
+    }
+
+    #[tokio::test]
+    async fn links_reads_to_definitions() {
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join(".env.example"),
+            "DATABASE_URL=\nUNUSED_FLAG=\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("config")).unwrap();
+        std::fs::write(
+            tmp.path().join("config/app.yml"),
+            "database:\n  max-pool: 5\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("app")).unwrap();
+        std::fs::write(
+            tmp.path().join("app/db.py"),
+            "import os\n\ndef connect():\n    url = os.environ[\"DATABASE_URL\"]\n    \
+             pool = config.get(\"database.max_pool\")\n    return os.getenv(\"SECRET_KEY\")\n",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        let node = |kind, name: &str, metadata: Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        store
+            .upsert_node(&node(
+                NodeKind::File,
+                "app/db.py",
+                serde_json::json!({ "language": "python" }),
+            ))
+            .await
+            .unwrap();
+        let connect = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "app/db.py::connect",
+                serde_json::json!({ "span": { "start_row": 2, "end_row": 5 } }),
+            ))
+            .await
+            .unwrap();
+
+        let extractor = EnvExtractor::new(tmp.path(), &HomerConfig::default());
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let id = |kind, name: &'static str| {
+            let store = &store;
+            async move {
+                store
+                    .get_node_by_name(kind, name)
+                    .await
+                    .unwrap()
+                    .unwrap_or_else(|| panic!("missing {name}"))
+                    .id
+            }
+        };
+        let database_url = id(NodeKind::EnvVar, "DATABASE_URL").await;
+        let secret = id(NodeKind::EnvVar, "SECRET_KEY").await;
+        let unused = id(NodeKind::EnvVar, "UNUSED_FLAG").await;
+        let pool = id(NodeKind::ConfigKey, "database.max-pool").await;
+
+        let mut reads: Vec<(NodeId, NodeId)> = store
+            .get_edges_by_kind(HyperedgeKind::Reads)
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| {
+                let member = |role| e.members.iter().find(|m| m.role == role).unwrap().node_id;
+                (member(roles::READER), member(roles::READ_VAR))
+            })
+            .collect();
+        reads.sort_by_key(|(_, v)| v.0);
+        let mut expected = vec![(connect, database_url), (connect, secret), (connect, pool)];
+        expected.sort_by_key(|(_, v)| v.0);
+        assert_eq!(reads, expected);
+
+        let defined: HashSet<NodeId> = store
+            .get_edges_by_kind(HyperedgeKind::Defines)
+            .await
+            .unwrap()
+            .iter()
+            .flat_map(|e| e.members.iter())
+            .filter(|m| m.role == roles::DEFINED_VAR)
+            .map(|m| m.node_id)
+            .collect();
+        assert_eq!(defined, HashSet::from([database_url, unused, pool]));
+    }
+}
@@ -0,0 +1,587 @@
+// Feature flag extraction: where flags are evaluated and how old they are.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use homer_graphs::LanguageRegistry;
+use homer_graphs::config_access::{self, AccessForm};
+use serde_json::Value;
+use tracing::{debug, info, instrument};
+
+use crate::config::{FlagExtractionConfig, HomerConfig};
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{HyperedgeKind, Node, NodeFilter, NodeId, NodeKind};
+
+use super::api_contract::{function_spans, link};
+use super::refactoring::read_blob;
+use super::traits::{ExtractStats, Extractor};
+
+/// A flag evaluation resolved to the code doing it.
+#[derive(Debug, Clone)]
+struct Check {
+    checker: NodeId,
+    file: String,
+    flag: String,
+    /// The SDK call, as written (`ldClient.boolVariation`).
+    call: String,
+    line: usize,
+}
+
+/// A commit that introduced or changed a flag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagEvent {
+    pub sha: String,
+    pub at: DateTime<Utc>,
+}
+
+/// When a flag first appeared and when it last changed: the first commit
+/// whose version of a checking file contains its key, and the last commit
+/// that changed how often it occurs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagHistory {
+    pub introduced: FlagEvent,
+    pub last_changed: FlagEvent,
+}
+
+impl FlagHistory {
+    /// Combine histories of one flag from different files.
+    fn merge(&mut self, other: FlagHistory) {
+        if other.introduced.at < self.introduced.at {
+            self.introduced = other.introduced;
+        }
+        if other.last_changed.at > self.last_changed.at {
+            self.last_changed = other.last_changed;
+        }
+    }
+}
+
+/// Whether `key` can be a flag key: short, with a letter, no whitespace.
+fn is_flag_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 128
+        && key.chars().any(char::is_alphanumeric)
+        && !key.chars().any(char::is_whitespace)
+}
+
+/// Quoted occurrences of `key` in `text`.
+fn occurrences(text: &str, key: &str) -> usize {
+    ['"', '\'', '`']
+        .iter()
+        .map(|q| text.matches(&format!("{q}{key}{q}")).count())
+        .sum()
+}
+
+/// History of each key across successive versions of one file, oldest first,
+/// as replayed from the commits that modified it (the git extractor's
+/// `Modifies` edges). A version is `None` where the file was deleted.
+pub fn flag_history(
+    versions: &[(FlagEvent, Option<String>)],
+    keys: &[String],
+) -> HashMap<String, FlagHistory> {
+    let mut previous: HashMap<&str, usize> = HashMap::new();
+    let mut history: HashMap<String, FlagHistory> = HashMap::new();
+    for (event, text) in versions {
+        for key in keys {
+            let count = text.as_deref().map_or(0, |t| occurrences(t, key));
+            let before = previous.insert(key, count).unwrap_or(0);
+            if count == before {
+                continue;
+            }
+            match history.get_mut(key) {
+                Some(h) => h.last_changed = event.clone(),
+                None if count > 0 => {
+                    history.insert(
+                        key.clone(),
+                        FlagHistory {
+                            introduced: event.clone(),
+                            last_changed: event.clone(),
+                        },
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+    history
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Finds flag evaluations with tree-sitter by `homer_graphs::config_access`:
+/// SDK calls matching the `[extraction.flags]` callee patterns whose first
+/// argument is a string key, such as `ldClient.boolVariation("new-checkout",
+/// ...)`. Each flag becomes a `FeatureFlag` node and each evaluation a
+/// `Checks` edge from the innermost function around it (the file when there
+/// is none).
+#[derive(Debug)]
+pub struct FlagExtractor {
+    repo_path: PathBuf,
+    settings: FlagExtractionConfig,
+}
+
+impl FlagExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.flags.clone(),
+        }
+    }
+
+    /// Call patterns plus the graph and git checkpoints, since checks follow
+    /// the code and ages follow history.
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let graph = store
+            .get_checkpoint("graph_last_sha")
+            .await?
+            .unwrap_or_default();
+        let git = store
+            .get_checkpoint("git_last_sha")
+            .await?
+            .unwrap_or_default();
+        let mut hasher = DefaultHasher::new();
+        self.settings.enabled.hash(&mut hasher);
+        self.settings.calls.hash(&mut hasher);
+        Ok(format!("{graph}:{git}:{:x}", hasher.finish()))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for FlagExtractor {
+    fn name(&self) -> &'static str {
+        "flags"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "flags_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "flags_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        store.delete_edges_by_kind(HyperedgeKind::Checks).await?;
+
+        let checks = if self.settings.enabled {
+            self.scan_checks(store).await?
+        } else {
+            Vec::new()
+        };
+        let history = self.history(store, &checks).await?;
+        let ids = store_flags(store, &checks, &history, &mut stats).await?;
+
+        let mut linked: HashSet<(NodeId, NodeId)> = HashSet::new();
+        for check in &checks {
+            let Some(&flag) = ids.get(&check.flag) else {
+                continue;
+            };
+            if linked.insert((check.checker, flag)) {
+                link(
+                    store,
+                    HyperedgeKind::Checks,
+                    (check.checker, roles::CHECKER),
+                    (flag, roles::CHECKED_FLAG),
+                    0.9,
+                    serde_json::json!({ "call": check.call, "line": check.line }),
+                )
+                .await?;
+                stats.edges_created += 1;
+            }
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("flags_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            flags = ids.len(),
+            checks = checks.len(),
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "Feature flag extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+impl FlagExtractor {
+    /// Every flag evaluation in a tracked source file.
+    async fn scan_checks(&self, store: &dyn HomerStore) -> crate::error::Result<Vec<Check>> {
+        let registry = LanguageRegistry::new();
+        let segments: Vec<&str> = self
+            .settings
+            .calls
+            .iter()
+            .map(|p| {
+                p.rsplit(['.', ':', '>', '\\'])
+                    .next()
+                    .unwrap_or(p)
+                    .trim_end_matches('*')
+            })
+            .filter(|s| !s.is_empty())
+            .collect();
+        let mut checks = Vec::new();
+        for file in store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            })
+            .await?
+        {
+            let Some(lang) = registry.for_file(Path::new(&file.name)) else {
+                continue;
+            };
+            let Ok(source) = std::fs::read_to_string(self.repo_path.join(&file.name)) else {
+                continue;
+            };
+            if !segments.iter().any(|s| source.contains(s)) {
+                continue;
+            }
+            let mut parser = tree_sitter::Parser::new();
+            if parser.set_language(&lang.tree_sitter_language()).is_err() {
+                continue;
+            }
+            let Some(tree) = parser.parse(&source, None) else {
+                continue;
+            };
+            let found: Vec<_> = config_access::keyed_accesses(&tree, &source)
+                .into_iter()
+                .filter(|a| {
+                    a.form == AccessForm::Call
+                        && is_flag_key(&a.key)
+                        && self
+                            .settings
+                            .calls
+                            .iter()
+                            .any(|p| config_access::callee_matches(&a.target, p))
+                })
+                .collect();
+            if found.is_empty() {
+                continue;
+            }
+            let spans = function_spans(store, &file.name).await?;
+            for access in found {
+                let row = access.span.start_row;
+                let checker = spans
+                    .iter()
+                    .filter(|(_, start, end)| (*start..=*end).contains(&row))
+                    .min_by_key(|(_, start, end)| end - start)
+                    .map_or(file.id, |(f, _, _)| *f);
+                checks.push(Check {
+                    checker,
+                    file: file.name.clone(),
+                    flag: access.key,
+                    call: access.target,
+                    line: row + 1,
+                });
+            }
+        }
+        Ok(checks)
+    }
+
+    /// Flag histories from the commits that modified each checking file.
+    /// Empty when the repository cannot be opened or git extraction has not
+    /// run.
+    async fn history(
+        &self,
+        store: &dyn HomerStore,
+        checks: &[Check],
+    ) -> crate::error::Result<HashMap<String, FlagHistory>> {
+        let mut by_file: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for check in checks {
+            by_file
+                .entry(check.file.as_str())
+                .or_default()
+                .insert(check.flag.clone());
+        }
+        let Ok(repo) = gix::open(&self.repo_path) else {
+            return Ok(HashMap::new());
+        };
+
+        let mut merged: HashMap<String, FlagHistory> = HashMap::new();
+        for (path, keys) in by_file {
+            let Some(file) = store.get_node_by_name(NodeKind::File, path).await? else {
+                continue;
+            };
+            let mut commits: Vec<(String, DateTime<Utc>)> = Vec::new();
+            for edge in store.get_edges_involving(file.id).await? {
+                if edge.kind != HyperedgeKind::Modifies || edge.metadata.contains_key("merge") {
+                    continue;
+                }
+                let Some(commit) = edge.members.iter().find(|m| m.role == "commit") else {
+                    continue;
+                };
+                if let Some(node) = store.get_node(commit.node_id).await? {
+                    commits.push((node.name, edge.last_updated));
+                }
+            }
+            commits.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+            let versions: Vec<(FlagEvent, Option<String>)> = commits
+                .into_iter()
+                .filter_map(|(sha, at)| {
+                    let oid = gix::ObjectId::from_hex(sha.as_bytes()).ok()?;
+                    let tree = repo.find_commit(oid).ok()?.tree().ok()?;
+                    let text = read_blob(&tree, path);
+                    Some((FlagEvent { sha, at }, text))
+                })
+                .collect();
+            let keys: Vec<String> = keys.into_iter().collect();
+            debug!(path, commits = versions.len(), "Replaying flag history");
+            for (key, history) in flag_history(&versions, &keys) {
+                match merged.get_mut(&key) {
+                    Some(existing) => existing.merge(history),
+                    None => {
+                        merged.insert(key, history);
+                    }
+                }
+            }
+        }
+        Ok(merged)
+    }
+}
+
+/// Upsert a node per flag with its checks, files, and history, and mark
+/// vanished flags stale. Returns ids keyed by flag.
+async fn store_flags(
+    store: &dyn HomerStore,
+    checks: &[Check],
+    history: &HashMap<String, FlagHistory>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<HashMap<String, NodeId>> {
+    let existing: HashMap<String, NodeId> = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::FeatureFlag),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(|n| (n.name, n.id))
+        .collect();
+
+    let mut flags: BTreeMap<&str, (usize, BTreeSet<&str>, BTreeSet<&str>)> = BTreeMap::new();
+    for check in checks {
+        let (count, files, calls) = flags.entry(check.flag.as_str()).or_default();
+        *count += 1;
+        files.insert(check.file.as_str());
+        calls.insert(check.call.as_str());
+    }
+
+    let mut ids = HashMap::new();
+    for (flag, (count, files, calls)) in flags {
+        let mut metadata = HashMap::from([
+            ("checks".to_string(), Value::from(count)),
+            ("files".to_string(), serde_json::json!(files)),
+            ("calls".to_string(), serde_json::json!(calls)),
+        ]);
+        if let Some(h) = history.get(flag) {
+            metadata.insert(
+                "introduced".to_string(),
+                Value::from(h.introduced.at.to_rfc3339()),
+            );
+            metadata.insert(
+                "introduced_in".to_string(),
+                Value::from(h.introduced.sha.as_str()),
+            );
+            metadata.insert(
+                "last_changed".to_string(),
+                Value::from(h.last_changed.at.to_rfc3339()),
+            );
+            metadata.insert(
+                "last_changed_in".to_string(),
+                Value::from(h.last_changed.sha.as_str()),
+            );
+        }
+        let id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::FeatureFlag,
+                name: flag.to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await?;
+        if existing.contains_key(flag) {
+            stats.nodes_updated += 1;
+        } else {
+            stats.nodes_created += 1;
+        }
+        ids.insert(flag.to_string(), id);
+    }
+    for (name, id) in existing {
+        if !ids.contains_key(&name) {
+            store.mark_node_stale(id).await?;
+        }
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(sha: &str, day: u32) -> FlagEvent {
+        FlagEvent {
+            sha: sha.to_string(),
+            at: DateTime::parse_from_rfc3339(&format!("2024-01-{day:02}T00:00:00Z"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn history_tracks_introduction_and_last_change() {
+        let keys = vec!["new-checkout".to_string(), "dark-mode".to_string()];
+        let versions = vec![
+            (event("a", 1), Some("fn main() {}".to_string())),
+            (
+                event("b", 2),
+                Some("if flags.isEnabled(\"new-checkout\") {}".to_string()),
+            ),
+            (
+                event("c", 3),
+                Some("if flags.isEnabled('new-checkout') {} // refactor".to_string()),
+            ),
+            (
+                event("d", 4),
+                Some(
+                    "if flags.isEnabled('new-checkout') {}\nif flags.isEnabled('new-checkout') {}"
+                        .to_string(),
+                ),
+            ),
+        ];
+        let history = flag_history(&versions, &keys);
+        assert_eq!(history["new-checkout"].introduced, event("b", 2));
+        assert_eq!(history["new-checkout"].last_changed, event("d", 4));
+        assert!(!history.contains_key("dark-mode"));
+
+        let mut merged = history["new-checkout"].clone();
+        merged.merge(FlagHistory {
+            introduced: event("x", 1),
+            last_changed: event("x", 1),
+        });
+        assert_eq!(merged.introduced, event("x", 1));
+        assert_eq!(merged.last_changed, event("d", 4));
+        assert!(!is_flag_key("two words"));
+        assert!(is_flag_key("checkout.v2"));
+    }
+
+    #[tokio::test]
+    async fn links_checks_and_dates_flags_from_history() {
+        use std::process::Command;
+
+        use crate::extract::git::GitExtractor;
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let git = |args: &[&str], date: &str| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .env("GIT_AUTHOR_NAME", "Test")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "Test")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com")
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .output()
+                .expect("git command failed");
+            assert!(output.status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"], "2023-01-01T00:00:00Z");
+        std::fs::write(
+            dir.join("checkout.ts"),
+            "export function render(client) {\n  return client.boolVariation(\"new-checkout\", false);\n}\n",
+        )
+        .unwrap();
+        git(&["add", "."], "2023-01-01T00:00:00Z");
+        git(&["commit", "-q", "-m", "Add flag"], "2023-01-01T00:00:00Z");
+        std::fs::write(
+            dir.join("checkout.ts"),
+            "export function render(client) {\n  return client.boolVariation(\"new-checkout\", false)\n    \
+             || unleash.isEnabled(\"fast-cart\");\n}\n",
+        )
+        .unwrap();
+        git(&["add", "."], "2024-06-01T00:00:00Z");
+        git(
+            &["commit", "-q", "-m", "Add cart flag"],
+            "2024-06-01T00:00:00Z",
+        );
+
+        let store = SqliteStore::in_memory().unwrap();
+        let config = HomerConfig::default();
+        GitExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+        let render = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "checkout.ts::render".to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::from([(
+                    "span".to_string(),
+                    serde_json::json!({ "start_row": 0, "end_row": 3 }),
+                )]),
+            })
+            .await
+            .unwrap();
+
+        let extractor = FlagExtractor::new(dir, &config);
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor.extract(&store, &config).await.unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let flag = store
+            .get_node_by_name(NodeKind::FeatureFlag, "new-checkout")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            flag.metadata["introduced"]
+                .as_str()
+                .unwrap()
+                .starts_with("2023-01-01")
+        );
+        assert_eq!(
+            flag.metadata["calls"],
+            serde_json::json!(["client.boolVariation"])
+        );
+        let cart = store
+            .get_node_by_name(NodeKind::FeatureFlag, "fast-cart")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            cart.metadata["introduced"]
+                .as_str()
+                .unwrap()
+                .starts_with("2024-06-01")
+        );
+
+        let checks = store
+            .get_edges_by_kind(HyperedgeKind::Checks)
+            .await
+            .unwrap();
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|e| {
+            e.members
+                .iter()
+                .any(|m| m.node_id == render && m.role == roles::CHECKER)
+        }));
+    }
+}
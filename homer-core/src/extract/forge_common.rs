@@ -5,7 +5,9 @@ use std::collections::HashMap;
 use chrono::Utc;
 
 use crate::store::HomerStore;
-use crate::types::{Node, NodeId, NodeKind};
+use crate::types::{
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeId, NodeKind,
+};
 
 use super::traits::ExtractStats;
 
@@ -33,6 +35,58 @@ pub async fn ensure_contributor(
     Ok(id)
 }
 
+/// Link a PR/MR to its commits with one `Includes` edge: the merge commit
+/// plus each commit on the branch. Commits the git extractor has not
+/// recorded (e.g. rebased away) are skipped.
+pub async fn link_pr_commits(
+    store: &dyn HomerStore,
+    stats: &mut ExtractStats,
+    pr_node_id: NodeId,
+    merge_sha: Option<&str>,
+    commit_shas: &[String],
+) -> crate::error::Result<()> {
+    let mut members = vec![HyperedgeMember {
+        node_id: pr_node_id,
+        role: "pull_request".to_string(),
+        position: 0,
+    }];
+    let shas = merge_sha
+        .map(|sha| (sha, "merge_commit"))
+        .into_iter()
+        .chain(
+            commit_shas
+                .iter()
+                .filter(|sha| Some(sha.as_str()) != merge_sha)
+                .map(|sha| (sha.as_str(), "commit")),
+        );
+    for (sha, role) in shas {
+        if let Some(commit) = store.get_node_by_name(NodeKind::Commit, sha).await? {
+            let position = u32::try_from(members.len()).unwrap_or(u32::MAX);
+            members.push(HyperedgeMember {
+                node_id: commit.id,
+                role: role.to_string(),
+                position,
+            });
+        }
+    }
+    if members.len() < 2 {
+        return Ok(());
+    }
+
+    store
+        .upsert_hyperedge(&Hyperedge {
+            id: HyperedgeId(0),
+            kind: HyperedgeKind::Includes,
+            members,
+            confidence: 1.0,
+            last_updated: Utc::now(),
+            metadata: HashMap::new(),
+        })
+        .await?;
+    stats.edges_created += 1;
+    Ok(())
+}
+
 /// Parse issue cross-references from PR/MR description text.
 /// Matches patterns like "fixes #123", "closes #456", "resolves #789".
 pub fn parse_issue_refs(text: &str) -> Vec<u64> {
@@ -132,7 +132,7 @@ impl Extractor for GitExtractor {
 
 impl GitExtractor {
     /// Check if `candidate_sha` is an ancestor of `head` by walking history.
-    fn is_ancestor(head: &gix::Commit<'_>, candidate_sha: &str) -> bool {
+    pub(crate) fn is_ancestor(head: &gix::Commit<'_>, candidate_sha: &str) -> bool {
         let Ok(walk) = head.ancestors().all() else {
             return false;
         };
@@ -145,7 +145,7 @@ impl GitExtractor {
         false
     }
 
-    fn collect_commits(
+    pub(crate) fn collect_commits(
         head: &gix::Commit<'_>,
         checkpoint_sha: Option<&str>,
         config: &HomerConfig,
@@ -238,8 +238,16 @@ impl GitExtractor {
 
         // Compute diff and store file nodes + Modifies edge
         let diff_stats = compute_diff(repo, &commit)?;
-        self.store_modifies_edge(store, stats, node_id.commit, author_time, &diff_stats)
-            .await?;
+        let is_merge = commit.parent_ids().count() > 1;
+        self.store_modifies_edge(
+            store,
+            stats,
+            node_id.commit,
+            author_time,
+            is_merge,
+            &diff_stats,
+        )
+        .await?;
 
         // Index commit message for FTS
         store
@@ -303,6 +311,7 @@ impl GitExtractor {
         stats: &mut ExtractStats,
         commit_node_id: NodeId,
         author_time: DateTime<Utc>,
+        is_merge: bool,
         diff_stats: &[FileDiffStats],
     ) -> crate::error::Result<()> {
         if diff_stats.is_empty() {
@@ -336,17 +345,27 @@ impl GitExtractor {
                 position,
             });
 
-            files_json.push(serde_json::json!({
+            let mut entry = serde_json::json!({
                 "path": diff.path.to_string_lossy(),
                 "status": diff.status,
                 "lines_added": diff.lines_added,
                 "lines_deleted": diff.lines_deleted,
                 "old_path": diff.old_path.as_ref().map(|p| p.to_string_lossy().to_string()),
-            }));
+            });
+            // Line ranges are only replayed for non-merge commits.
+            if !is_merge {
+                entry["hunks"] = serde_json::json!(diff.hunks);
+            }
+            files_json.push(entry);
         }
 
         let mut modifies_meta = HashMap::new();
         modifies_meta.insert("files".to_string(), serde_json::json!(files_json));
+        if is_merge {
+            // Merge diffs are against the first parent and repeat the
+            // changes already recorded on the merged branch.
+            modifies_meta.insert("merge".to_string(), serde_json::json!(true));
+        }
 
         let modifies_edge = Hyperedge {
             id: HyperedgeId(0),
@@ -715,7 +734,9 @@ fn compute_blob_diff_with_hunks(
     let mut total_deleted = 0u32;
     let mut hunks = Vec::new();
 
-    for group in diff.grouped_ops(3) {
+    // Zero context (like `git diff -U0`): hunks cover exactly the changed
+    // lines, which line-survival tracking relies on.
+    for group in diff.grouped_ops(0) {
         let mut hunk_old_start = u32::MAX;
         let mut hunk_old_end = 0u32;
         let mut hunk_new_start = u32::MAX;
@@ -955,6 +976,14 @@ mod tests {
             total_added + total_deleted > 2,
             "Total churn should exceed initial additions, got added={total_added} deleted={total_deleted}"
         );
+
+        // The one-line rewrite of lib.rs is recorded as a zero-context hunk
+        let rewrite = modifies
+            .iter()
+            .flat_map(|e| e.metadata["files"].as_array().cloned().unwrap_or_default())
+            .find(|f| f["path"] == "src/lib.rs" && f["status"] == "Modified")
+            .expect("lib.rs modification");
+        assert_eq!(rewrite["hunks"], serde_json::json!([[1, 1, 1, 1]]));
     }
 
     #[test]
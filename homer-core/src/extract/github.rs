@@ -22,7 +22,7 @@ use crate::types::{
     Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeId, NodeKind,
 };
 
-use super::forge_common::{ensure_contributor, parse_issue_refs};
+use super::forge_common::{ensure_contributor, link_pr_commits, parse_issue_refs};
 use super::traits::{ExtractStats, Extractor};
 
 /// Maximum retry attempts for rate-limited requests.
@@ -94,7 +94,8 @@ impl Extractor for GitHubExtractor {
         let mut stats = ExtractStats::default();
         let gh_config = &config.extraction.github;
 
-        let estimated = estimate_api_calls(gh_config);
+        let estimated =
+            estimate_api_calls(gh_config, config.analysis.co_change.group_pull_requests);
         info!(
             owner = %self.owner,
             repo = %self.repo,
@@ -116,7 +117,7 @@ impl Extractor for GitHubExtractor {
 
         // Fetch PRs (with optional reviews and comments)
         match self
-            .fetch_pull_requests(store, &mut stats, last_pr, gh_config)
+            .fetch_pull_requests(store, &mut stats, last_pr, config)
             .await
         {
             Ok(max_pr) => {
@@ -171,8 +172,11 @@ impl GitHubExtractor {
         store: &dyn HomerStore,
         stats: &mut ExtractStats,
         since_number: u64,
-        gh_config: &crate::config::GitHubExtractionConfig,
+        config: &HomerConfig,
     ) -> crate::error::Result<u64> {
+        let gh_config = &config.extraction.github;
+        // Branch commits are only needed to group them into change sets.
+        let group_commits = config.analysis.co_change.group_pull_requests;
         let mut max_number = since_number;
         let mut page = 1u32;
         let mut fetched = 0u32;
@@ -201,6 +205,23 @@ impl GitHubExtractor {
 
                 let pr_node_id = self.store_pull_request(store, stats, pr).await?;
 
+                let commit_shas = if group_commits {
+                    self.fetch_pr_commits(pr.number).await.unwrap_or_else(|e| {
+                        debug!(pr = pr.number, error = %e, "Failed to fetch commits");
+                        Vec::new()
+                    })
+                } else {
+                    Vec::new()
+                };
+                link_pr_commits(
+                    store,
+                    stats,
+                    pr_node_id,
+                    pr.merge_commit_sha.as_deref(),
+                    &commit_shas,
+                )
+                .await?;
+
                 if gh_config.include_reviews {
                     if let Err(e) = self
                         .fetch_pr_reviews(store, stats, pr.number, pr_node_id)
@@ -228,7 +249,6 @@ impl GitHubExtractor {
         Ok(max_number)
     }
 
-    #[allow(clippy::too_many_lines)]
     async fn store_pull_request(
         &self,
         store: &dyn HomerStore,
@@ -339,37 +359,20 @@ impl GitHubExtractor {
             }
         }
 
-        // Link PR to its merge commit if available
-        if let Some(sha) = &pr.merge_commit_sha {
-            if let Some(commit_node) = store.get_node_by_name(NodeKind::Commit, sha).await? {
-                store
-                    .upsert_hyperedge(&Hyperedge {
-                        id: HyperedgeId(0),
-                        kind: HyperedgeKind::Includes,
-                        members: vec![
-                            HyperedgeMember {
-                                node_id: pr_node_id,
-                                role: "pull_request".to_string(),
-                                position: 0,
-                            },
-                            HyperedgeMember {
-                                node_id: commit_node.id,
-                                role: "merge_commit".to_string(),
-                                position: 1,
-                            },
-                        ],
-                        confidence: 1.0,
-                        last_updated: Utc::now(),
-                        metadata: HashMap::new(),
-                    })
-                    .await?;
-                stats.edges_created += 1;
-            }
-        }
-
         Ok(pr_node_id)
     }
 
+    /// SHAs of the commits on a PR's branch (first 100).
+    async fn fetch_pr_commits(&self, pr_number: u64) -> crate::error::Result<Vec<String>> {
+        let commits = self
+            .api_get::<Vec<GhCommitRef>>(&format!(
+                "/repos/{}/{}/pulls/{pr_number}/commits?per_page=100",
+                self.owner, self.repo
+            ))
+            .await?;
+        Ok(commits.into_iter().map(|c| c.sha).collect())
+    }
+
     // ── Reviews & Comments ───────────────────────────────────────
 
     async fn fetch_pr_reviews(
@@ -675,6 +678,11 @@ struct GhPullRequest {
     user: Option<GhUser>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GhCommitRef {
+    sha: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GhIssue {
     number: u64,
@@ -714,7 +722,7 @@ struct GhComment {
 
 /// Estimate upper bound of API calls for a GitHub extraction run.
 /// When limits are 0 (unlimited), uses a reasonable upper bound for estimation.
-fn estimate_api_calls(gh_config: &crate::config::GitHubExtractionConfig) -> u32 {
+fn estimate_api_calls(gh_config: &crate::config::GitHubExtractionConfig, pr_commits: bool) -> u32 {
     let pr_limit = if gh_config.max_pr_history == 0 {
         1000
     } else {
@@ -733,6 +741,9 @@ fn estimate_api_calls(gh_config: &crate::config::GitHubExtractionConfig) -> u32
     if gh_config.include_comments {
         calls += pr_limit;
     }
+    if pr_commits {
+        calls += pr_limit;
+    }
     calls += issue_limit / 100 + 1;
     calls
 }
@@ -814,8 +825,10 @@ mod tests {
             ..crate::config::GitHubExtractionConfig::default()
         };
         // PR pages (2) + reviews (100) + comments (100) + issue pages (3) = 205
-        let est = estimate_api_calls(&config);
+        let est = estimate_api_calls(&config, false);
         assert_eq!(est, 205);
+        // Plus one commit listing per PR.
+        assert_eq!(estimate_api_calls(&config, true), 305);
     }
 
     #[test]
@@ -828,7 +841,7 @@ mod tests {
             ..crate::config::GitHubExtractionConfig::default()
         };
         // PR pages (2) + issue pages (3) = 5
-        let est = estimate_api_calls(&config);
+        let est = estimate_api_calls(&config, false);
         assert_eq!(est, 5);
     }
 
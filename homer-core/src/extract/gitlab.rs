@@ -21,7 +21,7 @@ use crate::types::{
     Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeId, NodeKind,
 };
 
-use super::forge_common::{ensure_contributor, parse_issue_refs};
+use super::forge_common::{ensure_contributor, link_pr_commits, parse_issue_refs};
 use super::traits::{ExtractStats, Extractor};
 
 /// GitLab REST API extractor.
@@ -187,7 +187,9 @@ impl GitLabExtractor {
                 }
                 max_iid = max_iid.max(mr.iid);
                 fetched += 1;
-                self.store_merge_request(store, stats, mr, config).await?;
+                let mr_node_id = self.store_merge_request(store, stats, mr, config).await?;
+                self.link_commits(store, stats, mr, mr_node_id, config)
+                    .await?;
             }
 
             if mrs.len() < 100 || (max_limit > 0 && fetched >= max_limit) {
@@ -205,7 +207,7 @@ impl GitLabExtractor {
         stats: &mut ExtractStats,
         mr: &GlMergeRequest,
         config: &HomerConfig,
-    ) -> crate::error::Result<()> {
+    ) -> crate::error::Result<NodeId> {
         let mut metadata = HashMap::new();
         metadata.insert("title".to_string(), serde_json::json!(mr.title));
         metadata.insert("state".to_string(), serde_json::json!(mr.state));
@@ -309,7 +311,44 @@ impl GitLabExtractor {
             }
         }
 
-        Ok(())
+        Ok(mr_node_id)
+    }
+
+    /// Include the MR's merge commit and, when grouping by pull request,
+    /// its branch commits.
+    async fn link_commits(
+        &self,
+        store: &dyn HomerStore,
+        stats: &mut ExtractStats,
+        mr: &GlMergeRequest,
+        mr_node_id: NodeId,
+        config: &HomerConfig,
+    ) -> crate::error::Result<()> {
+        // Branch commits are only needed to group them into change sets.
+        let commit_shas = if config.analysis.co_change.group_pull_requests {
+            self.fetch_mr_commits(mr.iid).await.unwrap_or_else(|e| {
+                debug!(mr_iid = mr.iid, error = %e, "Failed to fetch commits");
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        let merge_sha = mr
+            .merge_commit_sha
+            .as_deref()
+            .or(mr.squash_commit_sha.as_deref());
+        link_pr_commits(store, stats, mr_node_id, merge_sha, &commit_shas).await
+    }
+
+    /// SHAs of the commits on an MR's source branch (first 100).
+    async fn fetch_mr_commits(&self, mr_iid: u64) -> crate::error::Result<Vec<String>> {
+        let commits = self
+            .api_get::<Vec<GlCommitRef>>(&format!(
+                "/projects/{}/merge_requests/{mr_iid}/commits?per_page=100",
+                self.project_path
+            ))
+            .await?;
+        Ok(commits.into_iter().map(|c| c.id).collect())
     }
 
     async fn fetch_approvals(
@@ -495,9 +534,16 @@ struct GlMergeRequest {
     state: String,
     description: Option<String>,
     merged_at: Option<String>,
+    merge_commit_sha: Option<String>,
+    squash_commit_sha: Option<String>,
     author: GlUser,
 }
 
+#[derive(Debug, Deserialize)]
+struct GlCommitRef {
+    id: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GlIssue {
     iid: u64,
@@ -10,6 +10,7 @@ use homer_graphs::scope_graph::{FileScopeGraph, ScopeGraph, ScopeNodeId};
 use homer_graphs::{
     HeuristicGraph, LanguageRegistry, ResolutionTier, SymbolKind as GraphSymbolKind,
     call_graph::{self, project_call_graph},
+    clones, signature, testing,
 };
 
 use crate::config::HomerConfig;
@@ -19,6 +20,7 @@ use crate::types::{
     Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeId, NodeKind,
 };
 
+use super::test_links;
 use super::traits::{ExtractStats, Extractor};
 
 #[derive(Debug)]
@@ -116,6 +118,11 @@ impl Extractor for GraphExtractor {
         self.resolve_scope_graphs(store, &mut stats, &file_nodes, config)
             .await;
 
+        // Link tests to the production code they reach through the call graph.
+        if !file_nodes.is_empty() {
+            stats.edges_created += test_links::link_tests(store).await?;
+        }
+
         // Save graph checkpoint using the git HEAD sha
         if let Ok(Some(git_sha)) = store.get_checkpoint("git_last_sha").await {
             store.set_checkpoint("graph_last_sha", &git_sha).await?;
@@ -281,7 +288,9 @@ impl GraphExtractor {
             })?;
 
         // Store definitions, calls, and imports
-        self.store_definitions(store, stats, &graph, file_node)
+        self.store_definitions(store, stats, &graph, file_node, &tree, &source, lang.id())
+            .await?;
+        self.store_test_blocks(store, stats, file_node, &tree, &source, lang.id())
             .await?;
         self.store_calls(store, stats, &graph, file_node).await?;
         self.store_imports(store, stats, &graph, file_node).await?;
@@ -338,6 +347,14 @@ impl GraphExtractor {
             })
             .collect();
 
+        // Record which definitions each file exports (roots for reachability).
+        if let Err(e) = self
+            .mark_exported_definitions(store, &file_scope_graphs)
+            .await
+        {
+            debug!(error = %e, "Failed to mark exports");
+        }
+
         // Merge all file scope graphs and compute enclosing functions
         let mut all_enclosing: HashMap<ScopeNodeId, ScopeNodeId> = HashMap::new();
 
@@ -374,6 +391,87 @@ impl GraphExtractor {
         }
     }
 
+    /// Recompute `exported` on the `Function`/`Type` nodes of each file from
+    /// the names its scope graph marked as exported, clearing it on
+    /// definitions that are no longer exported.
+    ///
+    /// Scope-graph definitions span only the name identifier, while stored
+    /// definitions span the whole item, so a definition matches when its span
+    /// contains the exported name and its unqualified name equals the symbol.
+    async fn mark_exported_definitions(
+        &self,
+        store: &dyn HomerStore,
+        file_scope_graphs: &[FileScopeGraph],
+    ) -> crate::error::Result<()> {
+        use homer_graphs::scope_graph::ScopeNodeKind;
+
+        let mut defs_by_file: HashMap<String, Vec<Node>> = HashMap::new();
+        for kind in [NodeKind::Function, NodeKind::Type] {
+            let defs = store
+                .find_nodes(&crate::types::NodeFilter {
+                    kind: Some(kind),
+                    ..Default::default()
+                })
+                .await?;
+            for def in defs {
+                if let Some((file, _)) = def.name.split_once("::") {
+                    defs_by_file.entry(file.to_string()).or_default().push(def);
+                }
+            }
+        }
+
+        for fsg in file_scope_graphs {
+            let rel = fsg
+                .file_path
+                .strip_prefix(&self.repo_path)
+                .unwrap_or(&fsg.file_path);
+            let Some(defs) = defs_by_file.remove(&rel.display().to_string()) else {
+                continue;
+            };
+            let exports: Vec<(&str, usize)> = fsg
+                .export_nodes
+                .iter()
+                .filter_map(|id| fsg.nodes.iter().find(|n| n.id == *id))
+                .filter_map(|n| match (&n.kind, n.span) {
+                    (ScopeNodeKind::PopSymbol { symbol }, Some(span)) => {
+                        Some((symbol.as_str(), span.start_row))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            for mut def in defs {
+                let qualified = def.name.split_once("::").map_or("", |(_, q)| q);
+                let simple = qualified.rsplit("::").next().unwrap_or(qualified);
+                let simple = simple.rsplit('.').next().unwrap_or(simple);
+                let span = def.metadata.get("span");
+                let row = |key: &str| {
+                    span.and_then(|s| s.get(key))
+                        .and_then(serde_json::Value::as_u64)
+                };
+                let exported = match (row("start_row"), row("end_row")) {
+                    (Some(start), Some(end)) => exports.iter().any(|(symbol, name_row)| {
+                        let name_row = *name_row as u64;
+                        simple == *symbol && (start..=end).contains(&name_row)
+                    }),
+                    _ => false,
+                };
+                let marked = def.metadata.get("exported") == Some(&serde_json::Value::Bool(true));
+                if exported == marked {
+                    continue;
+                }
+                if exported {
+                    def.metadata
+                        .insert("exported".to_string(), serde_json::json!(true));
+                } else {
+                    def.metadata.remove("exported");
+                }
+                store.upsert_node(&def).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn store_resolved_call(
         &self,
         store: &dyn HomerStore,
@@ -442,12 +540,16 @@ impl GraphExtractor {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn store_definitions(
         &self,
         store: &dyn HomerStore,
         stats: &mut ExtractStats,
         graph: &HeuristicGraph,
         file_node: &Node,
+        tree: &tree_sitter::Tree,
+        source: &str,
+        language: &str,
     ) -> crate::error::Result<()> {
         for def in &graph.definitions {
             let node_kind = match def.kind {
@@ -483,6 +585,38 @@ impl GraphExtractor {
                 );
             }
 
+            if let Some(metrics) = &def.metrics {
+                metadata.insert("metrics".to_string(), serde_json::json!(metrics));
+            }
+
+            if let Some(sig) = signature::signature_at(tree, source, &def.span) {
+                metadata.insert("signature".to_string(), serde_json::json!(sig));
+            }
+
+            if node_kind == NodeKind::Function {
+                if let Some(fp) = clones::fingerprint_at(tree, source, &def.span) {
+                    metadata.insert("clone_fingerprint".to_string(), serde_json::json!(fp));
+                }
+                let path = Path::new(&file_node.name);
+                if let Some(framework) =
+                    testing::test_framework_at(tree, source, language, path, def)
+                {
+                    let mut calls: Vec<String> = graph
+                        .calls
+                        .iter()
+                        .filter(|c| c.caller == def.qualified_name)
+                        .map(|c| c.callee_name.clone())
+                        .collect();
+                    calls.extend(testing::macro_calls(tree, source, language, &def.span));
+                    calls.sort_unstable();
+                    calls.dedup();
+                    metadata.insert(
+                        "test".to_string(),
+                        serde_json::json!({ "framework": framework, "calls": calls }),
+                    );
+                }
+            }
+
             // Use file-scoped qualified name to avoid collisions across files
             let scoped_name = format!("{}::{}", file_node.name, def.qualified_name);
 
@@ -526,6 +660,76 @@ impl GraphExtractor {
         Ok(())
     }
 
+    /// Store call-style tests (`it(...)` blocks) as `Function` nodes so they
+    /// can be linked like named test functions.
+    async fn store_test_blocks(
+        &self,
+        store: &dyn HomerStore,
+        stats: &mut ExtractStats,
+        file_node: &Node,
+        tree: &tree_sitter::Tree,
+        source: &str,
+        language: &str,
+    ) -> crate::error::Result<()> {
+        for block in testing::test_blocks(tree, source, language) {
+            let mut metadata = HashMap::new();
+            metadata.insert("file".to_string(), serde_json::json!(file_node.name));
+            metadata.insert(
+                "qualified_name".to_string(),
+                serde_json::json!(block.qualified_name),
+            );
+            metadata.insert(
+                "span".to_string(),
+                serde_json::json!({
+                    "start_row": block.span.start_row,
+                    "start_col": block.span.start_col,
+                    "end_row": block.span.end_row,
+                    "end_col": block.span.end_col,
+                }),
+            );
+            metadata.insert(
+                "test".to_string(),
+                serde_json::json!({ "framework": block.framework, "calls": block.calls }),
+            );
+
+            let block_id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::Function,
+                    name: format!("{}::{}", file_node.name, block.qualified_name),
+                    content_hash: None,
+                    last_extracted: Utc::now(),
+                    metadata,
+                })
+                .await?;
+            stats.nodes_created += 1;
+
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::BelongsTo,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: block_id,
+                            role: "member".to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: file_node.id,
+                            role: "container".to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 1.0,
+                    last_updated: Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await?;
+            stats.edges_created += 1;
+        }
+        Ok(())
+    }
+
     async fn store_calls(
         &self,
         store: &dyn HomerStore,
@@ -885,8 +1089,99 @@ mod tests {
             greet.metadata.contains_key("doc_comment"),
             "greet should have doc_comment metadata"
         );
+        assert_eq!(
+            greet.metadata["metrics"]["cyclomatic"], 1,
+            "greet should carry complexity metrics"
+        );
+        assert!(
+            greet.metadata["clone_fingerprint"]["tokens"].as_u64() > Some(0),
+            "greet should carry a clone fingerprint"
+        );
+        assert_eq!(
+            greet.metadata["signature"], "fn greet()",
+            "greet should carry its signature"
+        );
 
         // Verify edges created (BelongsTo + Imports at minimum)
         assert!(stats.edges_created > 0, "Should create edges");
     }
+
+    #[tokio::test]
+    async fn exported_flag_follows_current_exports() {
+        use homer_graphs::TextRange;
+        use homer_graphs::scope_graph::{ScopeNode, ScopeNodeId, ScopeNodeKind};
+
+        let store = SqliteStore::in_memory().unwrap();
+        let repo = Path::new("/repo");
+        let def = |name: &str, rows: (u64, u64), exported: bool| {
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "span".to_string(),
+                serde_json::json!({ "start_row": rows.0, "end_row": rows.1 }),
+            );
+            if exported {
+                metadata.insert("exported".to_string(), serde_json::json!(true));
+            }
+            Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: chrono::Utc::now(),
+                metadata,
+            }
+        };
+        store
+            .upsert_node(&def("src/lib.rs::api", (0, 2), true))
+            .await
+            .unwrap();
+        store
+            .upsert_node(&def("src/lib.rs::helper", (3, 5), false))
+            .await
+            .unwrap();
+
+        // `api` was made private; `helper` is exported now.
+        let fsg = FileScopeGraph {
+            file_path: repo.join("src/lib.rs"),
+            nodes: vec![ScopeNode {
+                id: ScopeNodeId(1),
+                kind: ScopeNodeKind::PopSymbol {
+                    symbol: "helper".to_string(),
+                },
+                file_path: repo.join("src/lib.rs"),
+                span: Some(TextRange {
+                    start_byte: 0,
+                    end_byte: 6,
+                    start_row: 3,
+                    start_col: 3,
+                    end_row: 3,
+                    end_col: 9,
+                }),
+                symbol_kind: None,
+            }],
+            edges: Vec::new(),
+            root_scope: ScopeNodeId(0),
+            export_nodes: vec![ScopeNodeId(1)],
+            import_nodes: Vec::new(),
+        };
+        GraphExtractor::new(repo)
+            .mark_exported_definitions(&store, &[fsg])
+            .await
+            .unwrap();
+
+        let exported = |name: &'static str| {
+            let store = &store;
+            async move {
+                store
+                    .get_node_by_name(NodeKind::Function, name)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .metadata
+                    .contains_key("exported")
+            }
+        };
+        assert!(!exported("src/lib.rs::api").await, "No longer exported");
+        assert!(exported("src/lib.rs::helper").await);
+    }
 }
@@ -0,0 +1,1794 @@
+// Infrastructure-as-code and container manifest extraction: deployment
+// resources and the images, files and variables they depend on.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use glob::Pattern;
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::config::{HomerConfig, InfraExtractionConfig};
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, Node, NodeFilter, NodeId, NodeKind,
+};
+
+use super::traits::{ExtractStats, Extractor};
+use super::yaml;
+
+/// Kubernetes kinds that run containers.
+const WORKLOAD_KINDS: &[&str] = &[
+    "Deployment",
+    "StatefulSet",
+    "DaemonSet",
+    "ReplicaSet",
+    "Job",
+    "CronJob",
+    "Pod",
+];
+
+/// Terraform reference roots that are not resources.
+const TERRAFORM_NON_RESOURCES: &[&str] =
+    &["var", "local", "each", "count", "path", "terraform", "self"];
+
+/// One deployment resource read from a manifest: a Dockerfile's image, a
+/// compose service, a Kubernetes object (plain or from a Helm chart
+/// template), a chart, a Terraform resource, data source or module call, or
+/// an external image one of them runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InfraResource {
+    /// `dockerfile`, `compose_service`, `kubernetes`, `helm_chart`,
+    /// `terraform_resource`, `terraform_data`, `terraform_module` or `image`.
+    pub kind: &'static str,
+    /// Node name: the Dockerfile path, `<file>::<id>` for resources declared
+    /// in a manifest, `<dir>::<address>` for Terraform, or the image
+    /// reference for images.
+    pub name: String,
+    /// Short display name.
+    pub label: String,
+    /// Repo-relative manifest path; empty for external images.
+    pub file: String,
+    /// Exposed, published or container ports.
+    pub ports: Vec<String>,
+    /// Environment variables the resource sets.
+    pub env: Vec<String>,
+    /// What the resource depends on.
+    pub uses: Vec<InfraRef>,
+    /// Repo-relative source paths the resource is built from.
+    pub builds: Vec<String>,
+    /// Kind-specific details stored on the node.
+    pub details: serde_json::Map<String, Value>,
+}
+
+impl InfraResource {
+    fn new(kind: &'static str, file: &str, name: String, label: String) -> Self {
+        Self {
+            kind,
+            name,
+            label,
+            file: file.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn detail(&mut self, key: &str, value: impl Into<Value>) {
+        self.details.insert(key.to_string(), value.into());
+    }
+
+    fn uses(&mut self, reference: InfraRef) {
+        if !self.uses.contains(&reference) {
+            self.uses.push(reference);
+        }
+    }
+}
+
+/// A dependency, resolved to resources once every manifest is read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InfraRef {
+    /// A resource by node name.
+    Name(String),
+    /// A container image by reference, as written (`postgres:16`).
+    Image(String),
+    /// A Kubernetes object by kind and name.
+    Kube { kind: String, name: String },
+    /// Kubernetes workloads whose pod labels include all of these.
+    Selector(Vec<(String, String)>),
+    /// A Helm chart by chart name.
+    Chart(String),
+}
+
+// ── Paths ─────────────────────────────────────────────────────────
+
+/// Directory part of a repo-relative path; empty at the root.
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Join `rel` onto the repo-relative `dir`, resolving `.` and `..`.
+/// `None` when the result leaves the repository or `rel` is absolute.
+fn join_path(dir: &str, rel: &str) -> Option<String> {
+    if rel.starts_with('/') || rel.contains("://") {
+        return None;
+    }
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in rel.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Cut a source path at its first glob or variable so it names the
+/// directory being copied: `src/*.rs` → `src`, `$APP/x` → nothing.
+fn literal_prefix(path: &str) -> Option<&str> {
+    let cut = path.find(['*', '?', '[', '$', '{']).unwrap_or(path.len());
+    let literal = &path[..cut];
+    if cut == path.len() {
+        return Some(literal);
+    }
+    if cut == 0 {
+        return None;
+    }
+    Some(literal.rsplit_once('/').map_or("", |(dir, _)| dir))
+}
+
+// ── Dockerfile ────────────────────────────────────────────────────
+
+/// Instructions with line continuations joined and comments dropped.
+fn dockerfile_instructions(text: &str) -> Vec<String> {
+    let mut instructions = Vec::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') || (trimmed.is_empty() && current.is_empty()) {
+            continue;
+        }
+        if let Some(continued) = trimmed.strip_suffix('\\') {
+            current.push_str(continued.trim_end());
+            current.push(' ');
+        } else {
+            current.push_str(trimmed);
+            instructions.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.trim().is_empty() {
+        instructions.push(current);
+    }
+    instructions
+}
+
+/// Arguments of an instruction in shell or JSON-array form, without
+/// leading `--flag` options.
+fn instruction_args(args: &str) -> Vec<String> {
+    if let Some(Value::Array(items)) = args
+        .trim()
+        .starts_with('[')
+        .then(|| serde_json::from_str(args.trim()).ok())
+        .flatten()
+    {
+        return items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect();
+    }
+    args.split_whitespace()
+        .skip_while(|a| a.starts_with("--"))
+        .map(|a| a.trim_matches('"').to_string())
+        .collect()
+}
+
+/// The image a Dockerfile builds. `COPY` sources become build sources
+/// relative to the Dockerfile's directory; a compose file may later
+/// re-resolve them against its own build context.
+pub fn parse_dockerfile(path: &str, text: &str) -> InfraResource {
+    let mut res = InfraResource::new("dockerfile", path, path.to_string(), path.to_string());
+    let mut stages: Vec<String> = Vec::new();
+    let mut bases: Vec<String> = Vec::new();
+    let mut copies: Vec<String> = Vec::new();
+    let mut args: Vec<String> = Vec::new();
+
+    for instruction in dockerfile_instructions(text) {
+        let (op, rest) = instruction
+            .split_once(char::is_whitespace)
+            .unwrap_or((instruction.as_str(), ""));
+        match op.to_ascii_uppercase().as_str() {
+            "FROM" => {
+                let words = instruction_args(rest);
+                let Some(image) = words.first() else {
+                    continue;
+                };
+                if !stages.iter().any(|s| s.eq_ignore_ascii_case(image)) && image != "scratch" {
+                    bases.push(image.clone());
+                    res.uses(InfraRef::Image(image.clone()));
+                }
+                if words.len() >= 3 && words[1].eq_ignore_ascii_case("as") {
+                    stages.push(words[2].clone());
+                }
+            }
+            "COPY" | "ADD" if !rest.contains("--from=") => {
+                let words = instruction_args(rest);
+                if let Some((_, sources)) = words.split_last() {
+                    copies.extend(
+                        sources
+                            .iter()
+                            .filter(|s| !s.contains("://") && !s.starts_with("<<"))
+                            .cloned(),
+                    );
+                }
+            }
+            "EXPOSE" => res.ports.extend(
+                rest.split_whitespace()
+                    .map(|p| p.trim_end_matches("/tcp").to_string()),
+            ),
+            "ENV" => {
+                let words: Vec<&str> = rest.split_whitespace().collect();
+                if words.first().is_some_and(|w| w.contains('=')) {
+                    res.env.extend(
+                        words
+                            .iter()
+                            .filter_map(|w| w.split_once('='))
+                            .map(|(k, _)| k.to_string()),
+                    );
+                } else if let Some(name) = words.first() {
+                    res.env.push((*name).to_string());
+                }
+            }
+            "ARG" => args.extend(
+                rest.split_whitespace()
+                    .next()
+                    .map(|a| a.split('=').next().unwrap_or(a).to_string()),
+            ),
+            "WORKDIR" => res.detail("workdir", rest.trim()),
+            "CMD" | "ENTRYPOINT" => res.detail(&op.to_ascii_lowercase(), rest.trim()),
+            _ => {}
+        }
+    }
+
+    res.builds = build_sources(parent_dir(path), &copies);
+    res.detail("base_images", bases);
+    res.detail("stages", stages);
+    res.detail("copy_sources", copies);
+    res.detail("build_args", args);
+    res
+}
+
+/// Repo-relative build sources for `COPY` sources in `context`.
+fn build_sources(context: &str, copies: &[String]) -> Vec<String> {
+    let mut sources = Vec::new();
+    for copy in copies {
+        let Some(literal) = literal_prefix(copy) else {
+            continue;
+        };
+        if let Some(path) = join_path(context, literal) {
+            if !sources.contains(&path) {
+                sources.push(path);
+            }
+        }
+    }
+    sources
+}
+
+// ── docker-compose ────────────────────────────────────────────────
+
+/// One resource per compose service.
+pub fn parse_compose(path: &str, doc: &Value) -> Vec<InfraResource> {
+    let dir = parent_dir(path);
+    let Some(services) = doc.get("services").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    services
+        .iter()
+        .map(|(service, config)| {
+            let mut res = InfraResource::new(
+                "compose_service",
+                path,
+                format!("{path}::{service}"),
+                service.clone(),
+            );
+            if let Some(image) = config.get("image").and_then(Value::as_str) {
+                res.detail("image", image);
+                res.uses(InfraRef::Image(image.to_string()));
+            }
+            let (context, dockerfile) = match config.get("build") {
+                Some(Value::String(context)) => (Some(context.as_str()), None),
+                Some(build) => (
+                    build.get("context").and_then(Value::as_str).or(Some(".")),
+                    build.get("dockerfile").and_then(Value::as_str),
+                ),
+                None => (None, None),
+            };
+            if let Some(context) = context.and_then(|c| join_path(dir, c)) {
+                let dockerfile =
+                    join_path(&context, dockerfile.unwrap_or("Dockerfile")).unwrap_or_default();
+                res.detail("build_context", context.as_str());
+                res.detail("dockerfile", dockerfile.as_str());
+                res.uses(InfraRef::Name(dockerfile));
+            }
+            for key in ["ports", "expose"] {
+                res.ports.extend(
+                    config
+                        .get(key)
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(compose_port),
+                );
+            }
+            res.env = compose_env(config.get("environment"));
+            let depends_on = match config.get("depends_on") {
+                Some(Value::Object(map)) => map.keys().cloned().collect(),
+                other => strings(other),
+            };
+            for dependency in depends_on {
+                res.uses(InfraRef::Name(format!("{path}::{dependency}")));
+            }
+            res
+        })
+        .collect()
+}
+
+fn compose_port(port: &Value) -> Option<String> {
+    match port {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(map) => {
+            let target = map.get("target")?;
+            Some(match map.get("published") {
+                Some(published) => format!("{}:{target}", scalar_text(published)),
+                None => target.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Names from `environment:` as a map or a list of `KEY=value`.
+fn compose_env(env: Option<&Value>) -> Vec<String> {
+    match env {
+        Some(Value::Object(map)) => map.keys().cloned().collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|item| item.split('=').next().unwrap_or(item).to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    value
+        .as_str()
+        .map_or_else(|| value.to_string(), String::from)
+}
+
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// ── Kubernetes and Helm ───────────────────────────────────────────
+
+/// One resource per Kubernetes object, expanding `kind: List`.
+pub fn parse_kubernetes(path: &str, docs: &[Value]) -> Vec<InfraResource> {
+    let mut out = Vec::new();
+    for doc in docs {
+        match doc.get("items").and_then(Value::as_array) {
+            Some(items) if doc.get("kind").and_then(Value::as_str) == Some("List") => {
+                out.extend(items.iter().filter_map(|item| kube_object(path, item)));
+            }
+            _ => out.extend(kube_object(path, doc)),
+        }
+    }
+    out
+}
+
+fn kube_object(path: &str, doc: &Value) -> Option<InfraResource> {
+    doc.get("apiVersion")?;
+    let kind = doc.get("kind")?.as_str()?;
+    let metadata = doc.get("metadata");
+    let name = metadata
+        .and_then(|m| m.get("name").or_else(|| m.get("generateName")))
+        .and_then(Value::as_str)?;
+    let mut res = InfraResource::new(
+        "kubernetes",
+        path,
+        format!("{path}::{kind}/{name}"),
+        format!("{kind}/{name}"),
+    );
+    res.detail("k8s_kind", kind);
+    res.detail("k8s_name", name);
+    if let Some(namespace) = metadata
+        .and_then(|m| m.get("namespace"))
+        .and_then(Value::as_str)
+    {
+        res.detail("namespace", namespace);
+    }
+
+    let spec = doc.get("spec");
+    let pod = match kind {
+        "Pod" => Some((spec, metadata)),
+        "CronJob" => spec
+            .and_then(|s| s.pointer("/jobTemplate/spec/template"))
+            .map(|t| (t.get("spec"), t.get("metadata"))),
+        _ => spec
+            .and_then(|s| s.get("template"))
+            .map(|t| (t.get("spec"), t.get("metadata"))),
+    };
+    if let Some((Some(pod_spec), pod_meta)) = pod {
+        kube_pod(pod_spec, &mut res);
+        if let Some(Value::Object(labels)) = pod_meta.and_then(|m| m.get("labels")) {
+            res.detail("pod_labels", Value::Object(labels.clone()));
+        }
+    }
+
+    match kind {
+        "Service" => {
+            if let Some(Value::Object(selector)) = spec.and_then(|s| s.get("selector")) {
+                res.uses(InfraRef::Selector(label_pairs(selector)));
+            }
+            res.ports.extend(
+                spec.and_then(|s| s.get("ports"))
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|p| p.get("port").map(scalar_text)),
+            );
+        }
+        "Ingress" => {
+            for backend in ingress_backends(spec) {
+                res.uses(InfraRef::Kube {
+                    kind: "Service".to_string(),
+                    name: backend,
+                });
+            }
+        }
+        "PodDisruptionBudget" | "NetworkPolicy" => {
+            let selector = spec
+                .and_then(|s| s.get("selector").or_else(|| s.get("podSelector")))
+                .and_then(|s| s.get("matchLabels"));
+            if let Some(Value::Object(labels)) = selector {
+                res.uses(InfraRef::Selector(label_pairs(labels)));
+            }
+        }
+        _ => {}
+    }
+    if let Some(target) = spec.and_then(|s| s.get("scaleTargetRef")) {
+        if let (Some(kind), Some(name)) = (
+            target.get("kind").and_then(Value::as_str),
+            target.get("name").and_then(Value::as_str),
+        ) {
+            res.uses(InfraRef::Kube {
+                kind: kind.to_string(),
+                name: name.to_string(),
+            });
+        }
+    }
+    Some(res)
+}
+
+/// Images, ports, environment variables, and `ConfigMap` and `Secret`
+/// references of a pod spec's containers.
+fn kube_pod(spec: &Value, res: &mut InfraResource) {
+    let mut kube_ref = |kind: &str, name: Option<&Value>| {
+        if let Some(name) = name.and_then(Value::as_str) {
+            res.uses(InfraRef::Kube {
+                kind: kind.to_string(),
+                name: name.to_string(),
+            });
+        }
+    };
+    let containers = ["initContainers", "containers"]
+        .iter()
+        .filter_map(|key| spec.get(*key).and_then(Value::as_array))
+        .flatten();
+    let mut images = Vec::new();
+    let mut ports = Vec::new();
+    let mut env = Vec::new();
+    for container in containers {
+        if let Some(image) = container.get("image").and_then(Value::as_str) {
+            images.push(image.to_string());
+        }
+        for port in container
+            .get("ports")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            ports.extend(port.get("containerPort").map(scalar_text));
+        }
+        for var in container
+            .get("env")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            env.extend(var.get("name").and_then(Value::as_str).map(String::from));
+            kube_ref("ConfigMap", var.pointer("/valueFrom/configMapKeyRef/name"));
+            kube_ref("Secret", var.pointer("/valueFrom/secretKeyRef/name"));
+        }
+        for source in container
+            .get("envFrom")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            kube_ref("ConfigMap", source.pointer("/configMapRef/name"));
+            kube_ref("Secret", source.pointer("/secretRef/name"));
+        }
+    }
+    for volume in spec
+        .get("volumes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        kube_ref("ConfigMap", volume.pointer("/configMap/name"));
+        kube_ref("Secret", volume.pointer("/secret/secretName"));
+        kube_ref(
+            "PersistentVolumeClaim",
+            volume.pointer("/persistentVolumeClaim/claimName"),
+        );
+    }
+    for image in &images {
+        res.uses(InfraRef::Image(image.clone()));
+    }
+    res.detail("images", images);
+    res.ports.extend(ports);
+    res.env.extend(env);
+}
+
+fn label_pairs(labels: &serde_json::Map<String, Value>) -> Vec<(String, String)> {
+    labels
+        .iter()
+        .map(|(k, v)| (k.clone(), scalar_text(v)))
+        .collect()
+}
+
+/// Service names an Ingress routes to (`networking.k8s.io/v1` and the
+/// older `serviceName` form).
+fn ingress_backends(spec: Option<&Value>) -> Vec<String> {
+    let mut backends = Vec::new();
+    let mut add = |backend: Option<&Value>| {
+        let name = backend.and_then(|b| {
+            b.pointer("/service/name")
+                .or_else(|| b.get("serviceName"))
+                .and_then(Value::as_str)
+        });
+        if let Some(name) = name {
+            backends.push(name.to_string());
+        }
+    };
+    add(spec.and_then(|s| s.get("defaultBackend").or_else(|| s.get("backend"))));
+    for rule in spec
+        .and_then(|s| s.get("rules"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        for path in rule
+            .pointer("/http/paths")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            add(path.get("backend"));
+        }
+    }
+    backends
+}
+
+/// Make a Helm template readable as YAML: control-only lines are dropped
+/// and inline expressions become a placeholder.
+fn strip_templating(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("{{") && trimmed.ends_with("}}") && !trimmed[2..].contains("{{") {
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            out.push_str("templated");
+            rest = rest[start..]
+                .find("}}")
+                .map_or("", |end| &rest[start + end + 2..]);
+        }
+        out.push_str(rest);
+        out.push('\n');
+    }
+    out
+}
+
+/// A Helm chart from its `Chart.yaml`; dependencies are other charts.
+pub fn parse_chart(path: &str, doc: &Value) -> Option<InfraResource> {
+    let name = doc.get("name")?.as_str()?;
+    let mut res = InfraResource::new(
+        "helm_chart",
+        path,
+        format!("{path}::{name}"),
+        name.to_string(),
+    );
+    res.detail("chart", name);
+    if let Some(version) = doc.get("version") {
+        res.detail("version", scalar_text(version));
+    }
+    for dependency in doc
+        .get("dependencies")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if let Some(dep) = dependency.get("name").and_then(Value::as_str) {
+            res.uses(InfraRef::Chart(dep.to_string()));
+        }
+    }
+    Some(res)
+}
+
+/// Environment variable names set in Helm values: `env`, `extraEnv` and
+/// `environment` entries at any depth, as maps or `name:` lists.
+pub fn values_env(doc: &Value) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_values_env(doc, &mut names, 0);
+    names
+}
+
+fn collect_values_env(value: &Value, names: &mut Vec<String>, depth: usize) {
+    let Some(map) = value.as_object() else {
+        return;
+    };
+    if depth > 8 {
+        return;
+    }
+    for (key, child) in map {
+        if matches!(key.as_str(), "env" | "extraEnv" | "environment") {
+            let found: Vec<String> = match child {
+                Value::Object(vars) => vars.keys().cloned().collect(),
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(|i| i.get("name").and_then(Value::as_str))
+                    .map(String::from)
+                    .collect(),
+                _ => Vec::new(),
+            };
+            for name in found {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        } else {
+            collect_values_env(child, names, depth + 1);
+        }
+    }
+}
+
+// ── Terraform ─────────────────────────────────────────────────────
+
+/// A top-level HCL block: its header words, body text and 1-based line.
+struct HclBlock {
+    header: Vec<String>,
+    body: String,
+    line: usize,
+}
+
+/// Split HCL into top-level blocks. Strings, comments and heredocs are
+/// skipped when matching braces.
+fn hcl_blocks(text: &str) -> Vec<HclBlock> {
+    let mut blocks = Vec::new();
+    let mut header = String::new();
+    let mut body = String::new();
+    let mut depth = 0usize;
+    let mut line = 1usize;
+    let mut start_line = 1usize;
+    let mut heredoc: Option<String> = None;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        if let Some(marker) = &heredoc {
+            body.push(c);
+            if c == '\n' {
+                let last = body
+                    .trim_end_matches('\n')
+                    .rsplit('\n')
+                    .next()
+                    .unwrap_or("");
+                if last.trim() == marker {
+                    heredoc = None;
+                }
+            }
+            continue;
+        }
+        let target = if depth == 0 { &mut header } else { &mut body };
+        match c {
+            '#' => skip_line(&mut chars, &mut line),
+            '/' if chars.peek() == Some(&'/') => skip_line(&mut chars, &mut line),
+            '/' if chars.peek() == Some(&'*') => {
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' => {
+                target.push(c);
+                while let Some(s) = chars.next() {
+                    target.push(s);
+                    if s == '\\' {
+                        target.extend(chars.next());
+                    } else if s == '"' || s == '\n' {
+                        break;
+                    }
+                }
+            }
+            '<' if depth > 0 && chars.peek() == Some(&'<') => {
+                chars.next();
+                let mut marker = String::new();
+                while let Some(&m) = chars.peek() {
+                    if m == '\n' {
+                        break;
+                    }
+                    marker.push(m);
+                    chars.next();
+                }
+                body.push_str("<<");
+                body.push_str(&marker);
+                heredoc = Some(marker.trim_start_matches('-').trim().to_string());
+            }
+            '{' => {
+                if depth == 0 {
+                    start_line = line;
+                } else {
+                    body.push(c);
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    blocks.push(HclBlock {
+                        header: hcl_words(&header),
+                        body: std::mem::take(&mut body),
+                        line: start_line,
+                    });
+                    header.clear();
+                } else {
+                    body.push(c);
+                }
+            }
+            c => target.push(c),
+        }
+    }
+    blocks
+}
+
+fn skip_line(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, line: &mut usize) {
+    for c in chars.by_ref() {
+        if c == '\n' {
+            *line += 1;
+            break;
+        }
+    }
+}
+
+/// Header words of a block: `resource "aws_s3_bucket" "logs"`.
+fn hcl_words(header: &str) -> Vec<String> {
+    header
+        .lines()
+        .last()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|w| w.trim_matches('"').to_string())
+        .collect()
+}
+
+/// `key = "value"` at the top level of a block body.
+fn hcl_attribute(body: &str, key: &str) -> Option<String> {
+    body.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Resource, data source and module addresses referenced in `body`.
+fn terraform_references(body: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let is_part = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+    for chain in body.split(|c: char| !is_part(c)) {
+        let parts: Vec<&str> = chain.split('.').collect();
+        let address = match parts.as_slice() {
+            ["data", kind, name, ..] => format!("data.{kind}.{name}"),
+            ["module", name, ..] => format!("module.{name}"),
+            [kind, name, ..]
+                if kind.contains('_')
+                    && !name.is_empty()
+                    && !TERRAFORM_NON_RESOURCES.contains(kind)
+                    && kind.starts_with(|c: char| c.is_ascii_lowercase()) =>
+            {
+                format!("{kind}.{name}")
+            }
+            _ => continue,
+        };
+        if !refs.contains(&address) {
+            refs.push(address);
+        }
+    }
+    refs
+}
+
+/// Resources, data sources and module calls in one `.tf` file. Names are
+/// scoped to the Terraform module directory, so references resolve across
+/// the directory's files.
+pub fn parse_terraform(path: &str, text: &str) -> Vec<InfraResource> {
+    let dir = parent_dir(path);
+    let scope = if dir.is_empty() { "." } else { dir };
+    let mut out = Vec::new();
+    for block in hcl_blocks(text) {
+        let (kind, address) = match block.header.as_slice() {
+            [keyword, kind, name] if keyword == "resource" => {
+                ("terraform_resource", format!("{kind}.{name}"))
+            }
+            [keyword, kind, name] if keyword == "data" => {
+                ("terraform_data", format!("data.{kind}.{name}"))
+            }
+            [keyword, name] if keyword == "module" => {
+                ("terraform_module", format!("module.{name}"))
+            }
+            _ => continue,
+        };
+        let mut res =
+            InfraResource::new(kind, path, format!("{scope}::{address}"), address.clone());
+        res.detail("line", block.line);
+        if let [_, resource_type, _] = block.header.as_slice() {
+            res.detail("resource_type", resource_type.as_str());
+            res.detail(
+                "provider",
+                resource_type.split('_').next().unwrap_or(resource_type),
+            );
+        }
+        if let Some(source) = hcl_attribute(&block.body, "source") {
+            if source.starts_with("./") || source.starts_with("../") {
+                res.builds.extend(join_path(dir, &source));
+            }
+            res.detail("source", source);
+        }
+        for reference in terraform_references(&block.body) {
+            if reference != address {
+                res.uses(InfraRef::Name(format!("{scope}::{reference}")));
+            }
+        }
+        out.push(res);
+    }
+    out
+}
+
+// ── Discovery ─────────────────────────────────────────────────────
+
+/// What a manifest file holds, from its name, location and content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Manifest {
+    Dockerfile,
+    Compose,
+    Chart,
+    Values,
+    HelmTemplate,
+    Kubernetes,
+    Terraform,
+}
+
+fn classify(path: &str, repo_path: &Path) -> Option<Manifest> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let dir = parent_dir(path);
+    let has_chart = |dir: &str| repo_path.join(dir).join("Chart.yaml").is_file();
+    let extension = Path::new(name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase());
+    let yaml = matches!(extension.as_deref(), Some("yaml" | "yml"));
+    Some(
+        if name == "Dockerfile"
+            || name == "Containerfile"
+            || name.starts_with("Dockerfile.")
+            || name.ends_with(".Dockerfile")
+        {
+            Manifest::Dockerfile
+        } else if extension.as_deref() == Some("tf") {
+            Manifest::Terraform
+        } else if !yaml {
+            return None;
+        } else if name.starts_with("docker-compose") || name.starts_with("compose.") {
+            Manifest::Compose
+        } else if name == "Chart.yaml" {
+            Manifest::Chart
+        } else if name == "values.yaml" && has_chart(dir) {
+            Manifest::Values
+        } else if dir.ends_with("templates") && has_chart(parent_dir(dir)) {
+            Manifest::HelmTemplate
+        } else {
+            Manifest::Kubernetes
+        },
+    )
+}
+
+/// Every manifest matching the configured globs and not excluded: repo-
+/// relative path and contents, sorted by path.
+fn discover(
+    repo_path: &Path,
+    settings: &InfraExtractionConfig,
+    exclude: &[String],
+) -> Vec<(String, String)> {
+    if !settings.enabled {
+        return Vec::new();
+    }
+    let exclude: Vec<Pattern> = exclude
+        .iter()
+        .chain(&["**/.terraform/**".to_string()])
+        .filter_map(|p| Pattern::new(p).ok())
+        .collect();
+    let root = Pattern::escape(&repo_path.to_string_lossy());
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for pattern in &settings.manifests {
+        let Ok(paths) = glob::glob(&format!("{root}/{pattern}")) else {
+            continue;
+        };
+        for path in paths.flatten() {
+            let relative = path
+                .strip_prefix(repo_path)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if exclude.iter().any(|p| p.matches(&relative)) || !seen.insert(relative.clone()) {
+                continue;
+            }
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                found.push((relative, text));
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Parse every manifest into resources, with cross-manifest details filled
+/// in: chart values, compose build contexts, and image nodes.
+fn load_resources(repo_path: &Path, files: &[(String, String)]) -> Vec<InfraResource> {
+    let mut resources: Vec<InfraResource> = Vec::new();
+    let mut values: Vec<(&str, Value)> = Vec::new();
+    for (path, text) in files {
+        match classify(path, repo_path) {
+            Some(Manifest::Dockerfile) => resources.push(parse_dockerfile(path, text)),
+            Some(Manifest::Compose) => resources.extend(parse_compose(path, &yaml::parse(text))),
+            Some(Manifest::Chart) => resources.extend(parse_chart(path, &yaml::parse(text))),
+            Some(Manifest::Values) => values.push((path, yaml::parse(text))),
+            Some(Manifest::HelmTemplate) => {
+                let chart = parent_dir(parent_dir(path));
+                for mut res in
+                    parse_kubernetes(path, &yaml::parse_documents(&strip_templating(text)))
+                {
+                    res.detail("chart_dir", chart);
+                    resources.push(res);
+                }
+            }
+            Some(Manifest::Kubernetes) => {
+                resources.extend(parse_kubernetes(path, &yaml::parse_documents(text)));
+            }
+            Some(Manifest::Terraform) => resources.extend(parse_terraform(path, text)),
+            None => {}
+        }
+    }
+
+    // Chart values set the chart's variables.
+    for (path, doc) in values {
+        let chart_file = format!("{}Chart.yaml", dir_prefix(parent_dir(path)));
+        if let Some(chart) = resources
+            .iter_mut()
+            .find(|r| r.kind == "helm_chart" && r.file == chart_file)
+        {
+            for name in values_env(&doc) {
+                if !chart.env.contains(&name) {
+                    chart.env.push(name);
+                }
+            }
+        }
+    }
+
+    apply_compose_builds(&mut resources);
+    add_images(&mut resources);
+    resources
+}
+
+fn dir_prefix(dir: &str) -> String {
+    if dir.is_empty() {
+        String::new()
+    } else {
+        format!("{dir}/")
+    }
+}
+
+/// A compose build context replaces the Dockerfile's own directory as the
+/// base for its `COPY` sources; a service naming both `image` and `build`
+/// tells which Dockerfile produces that image.
+fn apply_compose_builds(resources: &mut [InfraResource]) {
+    let builds: Vec<(String, String, Option<String>)> = resources
+        .iter()
+        .filter(|r| r.kind == "compose_service")
+        .filter_map(|r| {
+            let dockerfile = r.details.get("dockerfile")?.as_str()?.to_string();
+            let context = r.details.get("build_context")?.as_str()?.to_string();
+            let image = r
+                .details
+                .get("image")
+                .and_then(Value::as_str)
+                .map(String::from);
+            Some((dockerfile, context, image))
+        })
+        .collect();
+    for (dockerfile, context, image) in builds {
+        let Some(res) = resources
+            .iter_mut()
+            .find(|r| r.kind == "dockerfile" && r.name == dockerfile)
+        else {
+            continue;
+        };
+        if !res.details.contains_key("context") {
+            let copies = strings(res.details.get("copy_sources"));
+            res.builds = build_sources(&context, &copies);
+            res.detail("context", context.as_str());
+        }
+        if let Some(image) = image {
+            let mut produces = strings(res.details.get("produces"));
+            if !produces.contains(&image) {
+                produces.push(image);
+            }
+            res.detail("produces", produces);
+        }
+    }
+    // A service whose Dockerfile was not found still builds its context.
+    let dockerfiles: HashSet<String> = resources
+        .iter()
+        .filter(|r| r.kind == "dockerfile")
+        .map(|r| r.name.clone())
+        .collect();
+    for res in resources.iter_mut().filter(|r| r.kind == "compose_service") {
+        let missing = res
+            .details
+            .get("dockerfile")
+            .and_then(Value::as_str)
+            .is_some_and(|d| !dockerfiles.contains(d));
+        if let Some(context) = res
+            .details
+            .get("build_context")
+            .and_then(Value::as_str)
+            .filter(|_| missing)
+        {
+            res.builds = vec![context.to_string()];
+        }
+    }
+}
+
+/// One `image` resource per referenced image. An image a compose service
+/// builds uses the Dockerfile that produces it, matched with or without tag.
+fn add_images(resources: &mut Vec<InfraResource>) {
+    let mut producers: HashMap<String, String> = HashMap::new();
+    for res in resources.iter().filter(|r| r.kind == "dockerfile") {
+        for image in strings(res.details.get("produces")) {
+            producers.insert(image_repository(&image).to_string(), res.name.clone());
+            producers.insert(image, res.name.clone());
+        }
+    }
+    let images: BTreeSet<String> = resources
+        .iter()
+        .flat_map(|r| &r.uses)
+        .filter_map(|u| match u {
+            InfraRef::Image(image) => Some(image.clone()),
+            _ => None,
+        })
+        .collect();
+    for image in images {
+        let mut res = InfraResource::new("image", "", image.clone(), image.clone());
+        let producer = producers
+            .get(&image)
+            .or_else(|| producers.get(image_repository(&image)));
+        match producer {
+            Some(dockerfile) => {
+                res.detail("built", true);
+                res.uses(InfraRef::Name(dockerfile.clone()));
+            }
+            None => res.detail("built", false),
+        }
+        resources.push(res);
+    }
+}
+
+/// Image reference without tag or digest: `ghcr.io/acme/api:1.2` →
+/// `ghcr.io/acme/api`.
+fn image_repository(image: &str) -> &str {
+    let image = image.split('@').next().unwrap_or(image);
+    match image.rfind(':') {
+        Some(colon) if !image[colon..].contains('/') => &image[..colon],
+        _ => image,
+    }
+}
+
+/// Hash of every manifest's path and contents, for change detection.
+fn fingerprint(files: &[(String, String)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    files.hash(&mut hasher);
+    hasher.finish()
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Stores resources as `InfraResource` nodes and environment variables as
+/// `EnvVar` nodes. `Uses` edges join a resource to what it depends on,
+/// `Builds` edges to the source files and directories it is built from, and
+/// `Defines` edges to the variables it sets. Everything is rebuilt when a
+/// manifest or the set of tracked files changes; resources that disappear
+/// are marked stale.
+#[derive(Debug)]
+pub struct InfraExtractor {
+    repo_path: PathBuf,
+    settings: InfraExtractionConfig,
+    exclude: Vec<String>,
+}
+
+impl InfraExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.infra.clone(),
+            exclude: config.extraction.structure.exclude_patterns.clone(),
+        }
+    }
+
+    fn manifests(&self) -> Vec<(String, String)> {
+        discover(&self.repo_path, &self.settings, &self.exclude)
+    }
+
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let mut sources = 0;
+        for kind in [NodeKind::File, NodeKind::Module] {
+            sources += store
+                .find_nodes(&NodeFilter {
+                    kind: Some(kind),
+                    ..Default::default()
+                })
+                .await?
+                .len();
+        }
+        Ok(format!("{sources}:{:x}", fingerprint(&self.manifests())))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for InfraExtractor {
+    fn name(&self) -> &'static str {
+        "infra"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "infra_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "infra_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        // Manifests and file sets both shift; rebuild every edge. Other
+        // extractors define variables too, so only our Defines edges go.
+        for kind in [HyperedgeKind::Uses, HyperedgeKind::Builds] {
+            store.delete_edges_by_kind(kind).await?;
+        }
+        store
+            .delete_edges_by_extractor(HyperedgeKind::Defines, "infra")
+            .await?;
+
+        let manifests = self.manifests();
+        let resources = load_resources(&self.repo_path, &manifests);
+        let ids = store_resources(store, &resources, &mut stats).await?;
+        link_uses(store, &resources, &ids, &mut stats).await?;
+        link_builds(store, &resources, &ids, &mut stats).await?;
+        link_env(store, &resources, &ids, &mut stats).await?;
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("infra_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            manifests = manifests.len(),
+            resources = resources.len(),
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "Infrastructure extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Upsert a node per resource and mark resources that no longer exist stale.
+async fn store_resources(
+    store: &dyn HomerStore,
+    resources: &[InfraResource],
+    stats: &mut ExtractStats,
+) -> crate::error::Result<HashMap<String, NodeId>> {
+    let existing = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::InfraResource),
+            ..Default::default()
+        })
+        .await?;
+    let mut ids = HashMap::new();
+    for res in resources {
+        if ids.contains_key(&res.name) {
+            continue;
+        }
+        let mut metadata: HashMap<String, Value> = res.details.clone().into_iter().collect();
+        metadata.insert("infra_kind".to_string(), Value::from(res.kind));
+        metadata.insert("label".to_string(), Value::from(res.label.as_str()));
+        metadata.insert("file".to_string(), Value::from(res.file.as_str()));
+        metadata.insert("ports".to_string(), serde_json::json!(res.ports));
+        metadata.insert("env".to_string(), serde_json::json!(res.env));
+        metadata.insert("builds".to_string(), serde_json::json!(res.builds));
+        let id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::InfraResource,
+                name: res.name.clone(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await?;
+        if existing.iter().any(|n| n.name == res.name) {
+            stats.nodes_updated += 1;
+        } else {
+            stats.nodes_created += 1;
+        }
+        ids.insert(res.name.clone(), id);
+    }
+    for node in existing {
+        if !ids.contains_key(&node.name) {
+            store.mark_node_stale(node.id).await?;
+        }
+    }
+    Ok(ids)
+}
+
+/// Resolve one reference to the names of the resources it points at.
+fn resolve<'a>(reference: &InfraRef, resources: &'a [InfraResource]) -> Vec<&'a str> {
+    let detail = |r: &'a InfraResource, key: &str| r.details.get(key).and_then(Value::as_str);
+    let found = resources.iter().filter(|r| match reference {
+        InfraRef::Name(name) | InfraRef::Image(name) => &r.name == name,
+        InfraRef::Kube { kind, name } => {
+            r.kind == "kubernetes"
+                && detail(r, "k8s_kind") == Some(kind)
+                && detail(r, "k8s_name") == Some(name)
+        }
+        InfraRef::Selector(labels) => {
+            let pod_labels = r.details.get("pod_labels");
+            r.kind == "kubernetes"
+                && detail(r, "k8s_kind").is_some_and(|k| WORKLOAD_KINDS.contains(&k))
+                && !labels.is_empty()
+                && labels.iter().all(|(k, v)| {
+                    pod_labels
+                        .and_then(|l| l.get(k))
+                        .is_some_and(|l| scalar_text(l) == *v)
+                })
+        }
+        InfraRef::Chart(chart) => r.kind == "helm_chart" && detail(r, "chart") == Some(chart),
+    });
+    found.map(|r| r.name.as_str()).collect()
+}
+
+async fn link_uses(
+    store: &dyn HomerStore,
+    resources: &[InfraResource],
+    ids: &HashMap<String, NodeId>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<()> {
+    let mut seen = HashSet::new();
+    for res in resources {
+        let Some(&dependent) = ids.get(&res.name) else {
+            continue;
+        };
+        for reference in &res.uses {
+            for target in resolve(reference, resources) {
+                let Some(&dependency) = ids.get(target) else {
+                    continue;
+                };
+                if dependency == dependent || !seen.insert((dependent, dependency)) {
+                    continue;
+                }
+                store
+                    .upsert_hyperedge(&Hyperedge {
+                        id: HyperedgeId(0),
+                        kind: HyperedgeKind::Uses,
+                        members: vec![
+                            HyperedgeMember {
+                                node_id: dependent,
+                                role: roles::DEPENDENT_RESOURCE.to_string(),
+                                position: 0,
+                            },
+                            HyperedgeMember {
+                                node_id: dependency,
+                                role: roles::DEPENDENCY.to_string(),
+                                position: 1,
+                            },
+                        ],
+                        confidence: 1.0,
+                        last_updated: Utc::now(),
+                        metadata: HashMap::from([(
+                            "source".to_string(),
+                            serde_json::json!(res.file),
+                        )]),
+                    })
+                    .await?;
+                stats.edges_created += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// One `Builds` edge per resource with build sources, joining it to the
+/// Module for each source directory or the File for each source file.
+/// Sources Homer does not track are left in the node's `builds` metadata.
+async fn link_builds(
+    store: &dyn HomerStore,
+    resources: &[InfraResource],
+    ids: &HashMap<String, NodeId>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<()> {
+    if resources.iter().all(|r| r.builds.is_empty()) {
+        return Ok(());
+    }
+    let root = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Module),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .find(|m| m.metadata.contains_key("is_root"))
+        .map(|m| m.id);
+    for res in resources.iter().filter(|r| !r.builds.is_empty()) {
+        let Some(&resource_id) = ids.get(&res.name) else {
+            continue;
+        };
+        let mut members = vec![HyperedgeMember {
+            node_id: resource_id,
+            role: roles::BUILT_RESOURCE.to_string(),
+            position: 0,
+        }];
+        for source in &res.builds {
+            let node = if source.is_empty() {
+                root
+            } else if let Some(module) = store.get_node_by_name(NodeKind::Module, source).await? {
+                Some(module.id)
+            } else {
+                store
+                    .get_node_by_name(NodeKind::File, source)
+                    .await?
+                    .map(|f| f.id)
+            };
+            if let Some(node_id) = node {
+                if members.iter().all(|m| m.node_id != node_id) {
+                    members.push(HyperedgeMember {
+                        node_id,
+                        role: roles::BUILD_SOURCE.to_string(),
+                        position: u32::try_from(members.len()).unwrap_or(u32::MAX),
+                    });
+                }
+            }
+        }
+        if members.len() == 1 {
+            continue;
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Builds,
+                members,
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::from([
+                    ("paths".to_string(), serde_json::json!(res.builds)),
+                    ("source".to_string(), serde_json::json!(res.file)),
+                ]),
+            })
+            .await?;
+        stats.edges_created += 1;
+    }
+    Ok(())
+}
+
+/// `EnvVar` nodes for every variable a resource sets, and one `Defines`
+/// edge per resource joining it to its variables.
+async fn link_env(
+    store: &dyn HomerStore,
+    resources: &[InfraResource],
+    ids: &HashMap<String, NodeId>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<()> {
+    let mut vars: HashMap<&str, NodeId> = HashMap::new();
+    for res in resources.iter().filter(|r| !r.env.is_empty()) {
+        let Some(&definer) = ids.get(&res.name) else {
+            continue;
+        };
+        let mut members = vec![HyperedgeMember {
+            node_id: definer,
+            role: roles::DEFINER.to_string(),
+            position: 0,
+        }];
+        for name in &res.env {
+            let id = if let Some(&id) = vars.get(name.as_str()) {
+                id
+            } else {
+                let id = ensure_env_var(store, stats, name).await?;
+                vars.insert(name, id);
+                id
+            };
+            if members.iter().all(|m| m.node_id != id) {
+                members.push(HyperedgeMember {
+                    node_id: id,
+                    role: roles::DEFINED_VAR.to_string(),
+                    position: u32::try_from(members.len()).unwrap_or(u32::MAX),
+                });
+            }
+        }
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Defines,
+                members,
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::from([
+                    ("extractor".to_string(), serde_json::json!("infra")),
+                    ("source".to_string(), serde_json::json!(res.file)),
+                ]),
+            })
+            .await?;
+        stats.edges_created += 1;
+    }
+    Ok(())
+}
+
+/// The `EnvVar` node for `name`, created if missing.
+pub(crate) async fn ensure_env_var(
+    store: &dyn HomerStore,
+    stats: &mut ExtractStats,
+    name: &str,
+) -> crate::error::Result<NodeId> {
+    if let Some(node) = store.get_node_by_name(NodeKind::EnvVar, name).await? {
+        return Ok(node.id);
+    }
+    stats.nodes_created += 1;
+    store
+        .upsert_node(&Node {
+            id: NodeId(0),
+            kind: NodeKind::EnvVar,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: HashMap::new(),
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dockerfile_images_ports_env_and_sources() {
+        let res = parse_dockerfile(
+            "services/api/Dockerfile",
+            "# syntax=docker/dockerfile:1\n\
+             FROM --platform=$BUILDPLATFORM rust:1.80 AS build\n\
+             WORKDIR /app\n\
+             COPY Cargo.toml Cargo.lock ./\n\
+             COPY src/ ./src/\n\
+             COPY --chown=app:app ../../shared/*.rs /shared/\n\
+             RUN cargo build --release \\\n\
+             \x20   --locked\n\
+             FROM debian:bookworm-slim\n\
+             COPY --from=build /app/target/release/api /usr/bin/api\n\
+             ENV RUST_LOG=info PORT=8080\n\
+             ENV DATABASE_URL postgres://db\n\
+             EXPOSE 8080/tcp 9090\n\
+             ENTRYPOINT [\"api\"]\n",
+        );
+        assert_eq!(res.name, "services/api/Dockerfile");
+        assert_eq!(
+            res.uses,
+            vec![
+                InfraRef::Image("rust:1.80".into()),
+                InfraRef::Image("debian:bookworm-slim".into())
+            ]
+        );
+        assert_eq!(res.details["stages"], serde_json::json!(["build"]));
+        assert_eq!(res.ports, vec!["8080", "9090"]);
+        assert_eq!(res.env, vec!["RUST_LOG", "PORT", "DATABASE_URL"]);
+        assert_eq!(
+            res.builds,
+            vec![
+                "services/api/Cargo.toml",
+                "services/api/Cargo.lock",
+                "services/api/src",
+                "shared"
+            ]
+        );
+    }
+
+    #[test]
+    fn compose_services_and_build_contexts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let files = vec![
+            (
+                "api/Dockerfile".to_string(),
+                "FROM rust:1.80\nCOPY api/src src\nCOPY shared shared\n".to_string(),
+            ),
+            (
+                "docker-compose.yml".to_string(),
+                "services:\n\
+                 \x20 api:\n\
+                 \x20   image: ghcr.io/acme/api:dev\n\
+                 \x20   build:\n\
+                 \x20     context: .\n\
+                 \x20     dockerfile: api/Dockerfile\n\
+                 \x20   ports: ['8080:80', {target: 9000, published: 9001}]\n\
+                 \x20   environment:\n\
+                 \x20     - DATABASE_URL=postgres://db/app\n\
+                 \x20     - DEBUG\n\
+                 \x20   depends_on:\n\
+                 \x20     db: {condition: service_healthy}\n\
+                 \x20 db:\n\
+                 \x20   image: postgres:16\n\
+                 \x20   environment: {POSTGRES_PASSWORD: secret}\n"
+                    .to_string(),
+            ),
+        ];
+        let resources = load_resources(tmp.path(), &files);
+        let get = |name: &str| resources.iter().find(|r| r.name == name).unwrap();
+
+        let api = get("docker-compose.yml::api");
+        assert_eq!(api.ports, vec!["8080:80", "9001:9000"]);
+        assert_eq!(api.env, vec!["DATABASE_URL", "DEBUG"]);
+        assert!(api.uses.contains(&InfraRef::Name("api/Dockerfile".into())));
+        assert!(
+            api.uses
+                .contains(&InfraRef::Name("docker-compose.yml::db".into()))
+        );
+        // The compose context, not the Dockerfile's directory, anchors COPY.
+        assert_eq!(get("api/Dockerfile").builds, vec!["api/src", "shared"]);
+        let image = get("ghcr.io/acme/api:dev");
+        assert_eq!(image.uses, vec![InfraRef::Name("api/Dockerfile".into())]);
+        assert_eq!(get("postgres:16").details["built"], false);
+    }
+
+    #[test]
+    fn kubernetes_objects_and_selectors() {
+        let docs = yaml::parse_documents(
+            "apiVersion: apps/v1\n\
+             kind: Deployment\n\
+             metadata: {name: api, namespace: prod}\n\
+             spec:\n\
+             \x20 template:\n\
+             \x20   metadata:\n\
+             \x20     labels: {app: api, tier: backend}\n\
+             \x20   spec:\n\
+             \x20     containers:\n\
+             \x20       - name: api\n\
+             \x20         image: ghcr.io/acme/api:1.2\n\
+             \x20         ports: [{containerPort: 8080}]\n\
+             \x20         env:\n\
+             \x20           - name: DATABASE_URL\n\
+             \x20             valueFrom: {secretKeyRef: {name: db, key: url}}\n\
+             \x20         envFrom: [{configMapRef: {name: api-config}}]\n\
+             ---\n\
+             apiVersion: v1\n\
+             kind: Service\n\
+             metadata: {name: api}\n\
+             spec:\n\
+             \x20 selector: {app: api}\n\
+             \x20 ports: [{port: 80}]\n\
+             ---\n\
+             apiVersion: networking.k8s.io/v1\n\
+             kind: Ingress\n\
+             metadata: {name: web}\n\
+             spec:\n\
+             \x20 rules:\n\
+             \x20   - http:\n\
+             \x20       paths:\n\
+             \x20         - backend: {service: {name: api, port: {number: 80}}}\n",
+        );
+        let resources = parse_kubernetes("k8s/api.yaml", &docs);
+        assert_eq!(resources.len(), 3);
+        let deployment = &resources[0];
+        assert_eq!(deployment.name, "k8s/api.yaml::Deployment/api");
+        assert_eq!(deployment.ports, vec!["8080"]);
+        assert_eq!(deployment.env, vec!["DATABASE_URL"]);
+        assert!(deployment.uses.contains(&InfraRef::Kube {
+            kind: "Secret".into(),
+            name: "db".into()
+        }));
+        assert!(deployment.uses.contains(&InfraRef::Kube {
+            kind: "ConfigMap".into(),
+            name: "api-config".into()
+        }));
+        let service = &resources[1];
+        assert_eq!(
+            resolve(&service.uses[0], &resources),
+            vec!["k8s/api.yaml::Deployment/api"]
+        );
+        let ingress = &resources[2];
+        assert_eq!(
+            resolve(&ingress.uses[0], &resources),
+            vec!["k8s/api.yaml::Service/api"]
+        );
+
+        let template = strip_templating(
+            "{{- if .Values.enabled }}\n\
+             apiVersion: v1\n\
+             kind: ConfigMap\n\
+             metadata:\n\
+             \x20 name: {{ include \"app.fullname\" . }}-config\n\
+             {{- end }}\n",
+        );
+        let parsed = parse_kubernetes("chart/templates/cm.yaml", &yaml::parse_documents(&template));
+        assert_eq!(parsed[0].label, "ConfigMap/templated-config");
+        assert_eq!(
+            values_env(&yaml::parse(
+                "api:\n  env:\n    - name: LOG_LEVEL\n      value: debug\nworker:\n  extraEnv: {QUEUE: jobs}\n"
+            )),
+            vec!["LOG_LEVEL", "QUEUE"]
+        );
+    }
+
+    #[test]
+    fn terraform_resources_modules_and_references() {
+        let resources = parse_terraform(
+            "infra/main.tf",
+            "# Storage\n\
+             resource \"aws_s3_bucket\" \"logs\" {\n\
+             \x20 bucket = \"${var.prefix}-logs\"\n\
+             \x20 tags   = { Name = \"logs\" }\n\
+             }\n\
+             \n\
+             data \"aws_iam_policy_document\" \"logs\" {\n\
+             \x20 statement {\n\
+             \x20   resources = [\"${aws_s3_bucket.logs.arn}/*\"]\n\
+             \x20 }\n\
+             }\n\
+             \n\
+             module \"vpc\" {\n\
+             \x20 source = \"./modules/vpc\"\n\
+             \x20 policy = data.aws_iam_policy_document.logs.json\n\
+             \x20 user_data = <<-EOT\n\
+             \x20   echo } not a brace\n\
+             \x20 EOT\n\
+             }\n\
+             \n\
+             output \"bucket\" { value = aws_s3_bucket.logs.id }\n",
+        );
+        let names: Vec<&str> = resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "infra::aws_s3_bucket.logs",
+                "infra::data.aws_iam_policy_document.logs",
+                "infra::module.vpc"
+            ]
+        );
+        assert_eq!(resources[0].details["provider"], "aws");
+        assert_eq!(resources[0].details["line"], 2);
+        assert_eq!(
+            resources[1].uses,
+            vec![InfraRef::Name("infra::aws_s3_bucket.logs".into())]
+        );
+        let module = &resources[2];
+        assert_eq!(module.builds, vec!["infra/modules/vpc"]);
+        assert_eq!(
+            module.uses,
+            vec![InfraRef::Name(
+                "infra::data.aws_iam_policy_document.logs".into()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn extracts_resources_and_edges() {
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("api/src")).unwrap();
+        std::fs::write(
+            tmp.path().join("api/Dockerfile"),
+            "FROM rust:1.80\nCOPY src src\nENV PORT=8080\nEXPOSE 8080\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("compose.yaml"),
+            "services:\n  api:\n    build: ./api\n    depends_on: [db]\n  db:\n    image: postgres:16\n",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Module,
+                name: "api/src".to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
+        let extractor = InfraExtractor::new(tmp.path(), &HomerConfig::default());
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let edges = |kind| {
+            let store = &store;
+            async move { store.get_edges_by_kind(kind).await.unwrap() }
+        };
+        // api → Dockerfile, api → db, Dockerfile → rust, db → postgres.
+        assert_eq!(edges(HyperedgeKind::Uses).await.len(), 4);
+        let builds = edges(HyperedgeKind::Builds).await;
+        assert_eq!(builds.len(), 1);
+        assert_eq!(builds[0].metadata["paths"], serde_json::json!(["api/src"]));
+        assert_eq!(edges(HyperedgeKind::Defines).await.len(), 1);
+        let port = store
+            .get_node_by_name(NodeKind::EnvVar, "PORT")
+            .await
+            .unwrap();
+        assert!(port.is_some());
+        let dockerfile = store
+            .get_node_by_name(NodeKind::InfraResource, "api/Dockerfile")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(dockerfile.metadata["infra_kind"], "dockerfile");
+        assert_eq!(dockerfile.metadata["ports"], serde_json::json!(["8080"]));
+    }
+}
@@ -1,9 +1,23 @@
+pub mod api_contract;
+pub mod ci;
+pub mod codeowners;
+pub mod coverage;
 pub mod document;
+pub mod env;
+pub mod flags;
 pub mod forge_common;
 pub mod git;
 pub mod github;
 pub mod gitlab;
 pub mod graph;
+pub mod infra;
+pub mod profile;
 pub mod prompt;
+pub mod refactoring;
+pub mod schema;
 pub mod structure;
+pub(crate) mod test_links;
+pub mod test_results;
 pub mod traits;
+pub(crate) mod xml;
+pub(crate) mod yaml;
@@ -0,0 +1,1331 @@
+// Runtime profile ingestion: records on each `Function` node how much of the
+// profiled CPU time it accounts for.
+
+// Shares intentionally cast sample weights to float.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::{HomerConfig, ProfileExtractionConfig};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{Node, NodeFilter, NodeKind};
+
+use super::coverage::{PathResolver, function_lines};
+use super::traits::{ExtractStats, Extractor};
+
+/// Metadata key holding a function's runtime summary.
+pub const RUNTIME_KEY: &str = "runtime";
+
+/// Largest profile read (and largest decompressed pprof), in bytes.
+const MAX_PROFILE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Profile formats Homer understands. The format is detected from the
+/// content, so any configured path may hold any of them:
+///
+/// - pprof protobuf, gzipped or not (Go, pprof-rs, async-profiler `-o pprof`)
+/// - collapsed stacks, one `a;b;c count` line per stack (inferno, py-spy,
+///   `stackcollapse-perf.pl`, async-profiler `-o collapsed`)
+/// - `perf script` output, one sample per block of leaf-first frames
+/// - Chrome trace event JSON (`"X"` complete events and `"B"`/`"E"` pairs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProfileFormat {
+    Pprof,
+    Folded,
+    PerfScript,
+    ChromeTrace,
+}
+
+impl ProfileFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pprof => "pprof",
+            Self::Folded => "folded",
+            Self::PerfScript => "perf-script",
+            Self::ChromeTrace => "chrome-trace",
+        }
+    }
+}
+
+/// One stack frame as the profile names it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frame {
+    pub symbol: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl Frame {
+    fn symbol(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            file: None,
+            line: None,
+        }
+    }
+}
+
+/// A stack, outermost frame first, and the time or samples spent in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub stack: Vec<Frame>,
+    pub weight: f64,
+}
+
+/// Detect the format of a profile and parse it into weighted stacks.
+pub fn parse_profile(bytes: &[u8]) -> Option<(ProfileFormat, Vec<Sample>)> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let raw = gunzip(bytes)?;
+        return Some((ProfileFormat::Pprof, parse_pprof(&raw)?));
+    }
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return Some((ProfileFormat::Pprof, parse_pprof(bytes)?));
+    };
+    let head = text.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with('{') || head.starts_with('[') {
+        return Some((ProfileFormat::ChromeTrace, parse_chrome_trace(head)?));
+    }
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.is_empty() {
+        return None;
+    }
+    if lines.iter().any(|l| is_perf_frame(l)) {
+        Some((ProfileFormat::PerfScript, parse_perf_script(text)))
+    } else if lines.iter().take(64).all(|l| folded_line(l).is_some()) {
+        Some((ProfileFormat::Folded, parse_folded(text)))
+    } else {
+        None
+    }
+}
+
+// ── Collapsed stacks ──────────────────────────────────────────────
+
+fn folded_line(line: &str) -> Option<(&str, f64)> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (stack, count) = line.trim_end().rsplit_once(' ')?;
+    let count: f64 = count.parse().ok()?;
+    (!stack.is_empty()).then_some((stack, count))
+}
+
+/// Collapsed stacks: `outer;inner count` per line. A py-spy style
+/// `name (file.py:12)` frame carries its source location.
+pub fn parse_folded(text: &str) -> Vec<Sample> {
+    text.lines()
+        .filter_map(folded_line)
+        .filter(|(_, count)| *count > 0.0)
+        .map(|(stack, count)| Sample {
+            stack: stack.split(';').map(folded_frame).collect(),
+            weight: count,
+        })
+        .collect()
+}
+
+fn folded_frame(label: &str) -> Frame {
+    let label = label.trim();
+    if let Some((name, location)) = label.strip_suffix(')').and_then(|l| l.rsplit_once(" (")) {
+        if let Some((file, line)) = location.rsplit_once(':') {
+            if let Ok(line) = line.parse() {
+                return Frame {
+                    symbol: name.to_string(),
+                    file: Some(file.to_string()),
+                    line: Some(line),
+                };
+            }
+        }
+    }
+    Frame::symbol(label)
+}
+
+// ── perf script ───────────────────────────────────────────────────
+
+/// A `perf script` frame line: indented hex address, symbol, `(dso)`.
+fn is_perf_frame(line: &str) -> bool {
+    line.starts_with(char::is_whitespace)
+        && line
+            .split_whitespace()
+            .next()
+            .is_some_and(|addr| addr.len() >= 4 && addr.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// `perf script`: a header line per sample followed by its frames, leaf
+/// first, one per line. Every sample weighs the same.
+pub fn parse_perf_script(text: &str) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut flush = |frames: &mut Vec<Frame>| {
+        if !frames.is_empty() {
+            frames.reverse();
+            samples.push(Sample {
+                stack: std::mem::take(frames),
+                weight: 1.0,
+            });
+        }
+    };
+    for line in text.lines() {
+        if is_perf_frame(line) {
+            let rest = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .map_or("", |r| r.1);
+            let symbol = match rest.trim().rsplit_once(" (") {
+                Some((symbol, dso)) if dso.ends_with(')') => symbol,
+                _ => rest.trim(),
+            };
+            if !symbol.is_empty() && symbol != "[unknown]" {
+                frames.push(Frame::symbol(symbol));
+            }
+        } else {
+            flush(&mut frames);
+        }
+    }
+    flush(&mut frames);
+    samples
+}
+
+// ── Chrome trace events ───────────────────────────────────────────
+
+/// One timed event on a thread.
+struct Span {
+    start: f64,
+    end: f64,
+    frame: Frame,
+}
+
+/// Chrome trace events: `"X"` events with a duration, and `"B"`/`"E"`
+/// pairs per thread. Nesting on a thread forms the stack, and each event
+/// weighs its exclusive time (its duration minus its children's).
+pub fn parse_chrome_trace(text: &str) -> Option<Vec<Sample>> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let events = value
+        .get("traceEvents")
+        .or(Some(&value))
+        .and_then(serde_json::Value::as_array)?;
+
+    let mut threads: HashMap<String, Vec<Span>> = HashMap::new();
+    let mut open: HashMap<String, Vec<(f64, Frame)>> = HashMap::new();
+    for event in events {
+        let Some(ts) = event.get("ts").and_then(serde_json::Value::as_f64) else {
+            continue;
+        };
+        let thread = format!(
+            "{}:{}",
+            event.get("pid").unwrap_or(&serde_json::Value::Null),
+            event.get("tid").unwrap_or(&serde_json::Value::Null)
+        );
+        let frame = || {
+            let name = event.get("name")?.as_str()?;
+            let args = event.get("args");
+            let arg = |key: &str| args.and_then(|a| a.get(key));
+            Some(Frame {
+                symbol: name.to_string(),
+                file: arg("file").and_then(|f| f.as_str()).map(str::to_string),
+                line: arg("line")
+                    .and_then(serde_json::Value::as_u64)
+                    .and_then(|l| u32::try_from(l).ok()),
+            })
+        };
+        match event.get("ph").and_then(serde_json::Value::as_str) {
+            Some("X") => {
+                let dur = event
+                    .get("dur")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0);
+                if let Some(frame) = frame() {
+                    threads.entry(thread).or_default().push(Span {
+                        start: ts,
+                        end: ts + dur,
+                        frame,
+                    });
+                }
+            }
+            Some("B") => {
+                if let Some(frame) = frame() {
+                    open.entry(thread).or_default().push((ts, frame));
+                }
+            }
+            Some("E") => {
+                if let Some((start, frame)) = open.get_mut(&thread).and_then(Vec::pop) {
+                    threads.entry(thread).or_default().push(Span {
+                        start,
+                        end: ts,
+                        frame,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut samples = Vec::new();
+    for mut spans in threads.into_values() {
+        spans.sort_by(|a, b| a.start.total_cmp(&b.start).then(b.end.total_cmp(&a.end)));
+        let mut parents: Vec<Option<usize>> = Vec::with_capacity(spans.len());
+        let mut child_time = vec![0.0; spans.len()];
+        let mut stack: Vec<usize> = Vec::new();
+        for (i, span) in spans.iter().enumerate() {
+            while stack
+                .last()
+                .is_some_and(|&top| spans[top].end <= span.start)
+            {
+                stack.pop();
+            }
+            let parent = stack.last().copied();
+            if let Some(parent) = parent {
+                child_time[parent] += span.end - span.start;
+            }
+            parents.push(parent);
+            stack.push(i);
+        }
+        for (i, span) in spans.iter().enumerate() {
+            let weight = span.end - span.start - child_time[i];
+            if weight <= 0.0 {
+                continue;
+            }
+            let mut frames = vec![span.frame.clone()];
+            let mut cursor = parents[i];
+            while let Some(p) = cursor {
+                frames.push(spans[p].frame.clone());
+                cursor = parents[p];
+            }
+            frames.reverse();
+            samples.push(Sample {
+                stack: frames,
+                weight,
+            });
+        }
+    }
+    Some(samples)
+}
+
+// ── pprof ─────────────────────────────────────────────────────────
+
+/// Decompress a gzip member (RFC 1952).
+fn gunzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    const FEXTRA: u8 = 4;
+    const FNAME: u8 = 8;
+    const FCOMMENT: u8 = 16;
+    const FHCRC: u8 = 2;
+
+    if bytes.len() < 18 || bytes[2] != 8 {
+        return None;
+    }
+    let flags = bytes[3];
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let len = usize::from(u16::from_le_bytes([*bytes.get(pos)?, *bytes.get(pos + 1)?]));
+        pos += 2 + len;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            pos += bytes.get(pos..)?.iter().position(|&b| b == 0)? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let input = bytes.get(pos..)?;
+
+    let mut inflate = zlib_rs::Inflate::new(false, 15);
+    let mut out = Vec::new();
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        let consumed = usize::try_from(inflate.total_in()).ok()?;
+        let before = inflate.total_out();
+        let status = inflate
+            .decompress(
+                input.get(consumed..)?,
+                &mut chunk,
+                zlib_rs::InflateFlush::NoFlush,
+            )
+            .ok()?;
+        let produced = usize::try_from(inflate.total_out() - before).ok()?;
+        out.extend_from_slice(&chunk[..produced]);
+        if out.len() as u64 > MAX_PROFILE_BYTES {
+            return None;
+        }
+        match status {
+            zlib_rs::Status::StreamEnd => return Some(out),
+            zlib_rs::Status::BufError if produced == 0 => return None,
+            _ => {}
+        }
+    }
+}
+
+/// A protobuf field value; fixed-width values are skipped.
+enum Field<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// Protobuf wire-format reader over one message.
+struct Wire<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Wire<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.buf.get(self.pos)?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// The next field, `None` at the end, `Some(Err)` on malformed input.
+    fn field(&mut self) -> Option<Result<(u64, Field<'a>), ()>> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+        let Some(key) = self.varint() else {
+            return Some(Err(()));
+        };
+        let value = match key & 7 {
+            0 => self.varint().map(Field::Varint),
+            1 => self.skip(8),
+            2 => self.varint().and_then(|len| {
+                let end = self.pos.checked_add(usize::try_from(len).ok()?)?;
+                let bytes = self.buf.get(self.pos..end)?;
+                self.pos = end;
+                Some(Field::Bytes(bytes))
+            }),
+            5 => self.skip(4),
+            _ => None,
+        };
+        Some(value.map(|v| (key >> 3, v)).ok_or(()))
+    }
+
+    fn skip(&mut self, n: usize) -> Option<Field<'a>> {
+        self.pos = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.buf.len())?;
+        Some(Field::Fixed)
+    }
+
+    /// Every field of a message, or `None` if it is malformed.
+    fn fields(buf: &'a [u8]) -> Option<Vec<(u64, Field<'a>)>> {
+        let mut wire = Wire::new(buf);
+        std::iter::from_fn(|| wire.field())
+            .collect::<Result<Vec<_>, ()>>()
+            .ok()
+    }
+}
+
+/// The integers in a repeated field, packed or not.
+fn varints(field: &Field<'_>) -> Vec<u64> {
+    match field {
+        Field::Varint(v) => vec![*v],
+        Field::Bytes(bytes) => {
+            let mut wire = Wire::new(bytes);
+            std::iter::from_fn(|| wire.varint()).collect()
+        }
+        Field::Fixed => Vec::new(),
+    }
+}
+
+fn varint(field: &Field<'_>) -> Option<u64> {
+    match field {
+        Field::Varint(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// pprof `profile.proto`. Values come from the default sample type, or the
+/// last one (`cpu` in Go CPU profiles). A sample's first location is the
+/// leaf, and a location's first line is the innermost inlined function.
+pub fn parse_pprof(bytes: &[u8]) -> Option<Vec<Sample>> {
+    let mut strings: Vec<String> = Vec::new();
+    let mut sample_types: Vec<u64> = Vec::new();
+    let mut default_type: Option<u64> = None;
+    let mut samples: Vec<(Vec<u64>, Vec<u64>)> = Vec::new();
+    // location id → [(function id, line)]
+    let mut locations: HashMap<u64, Vec<(u64, u64)>> = HashMap::new();
+    // function id → (name, filename) string indices
+    let mut functions: HashMap<u64, (u64, u64)> = HashMap::new();
+
+    for (number, field) in Wire::fields(bytes)? {
+        match (number, &field) {
+            (1, Field::Bytes(value_type)) => {
+                let ty = Wire::fields(value_type)?
+                    .iter()
+                    .find(|(n, _)| *n == 1)
+                    .and_then(|(_, f)| varint(f))
+                    .unwrap_or(0);
+                sample_types.push(ty);
+            }
+            (2, Field::Bytes(sample)) => {
+                let mut locs = Vec::new();
+                let mut values = Vec::new();
+                for (n, f) in Wire::fields(sample)? {
+                    match n {
+                        1 => locs.extend(varints(&f)),
+                        2 => values.extend(varints(&f)),
+                        _ => {}
+                    }
+                }
+                samples.push((locs, values));
+            }
+            (4, Field::Bytes(location)) => {
+                let (id, lines) = parse_location(location)?;
+                locations.insert(id, lines);
+            }
+            (5, Field::Bytes(function)) => {
+                let (id, name, file) = parse_function(function)?;
+                functions.insert(id, (name, file));
+            }
+            (6, Field::Bytes(s)) => strings.push(String::from_utf8_lossy(s).into_owned()),
+            (14, _) => default_type = varint(&field).filter(|&t| t != 0),
+            _ => {}
+        }
+    }
+    if sample_types.is_empty() {
+        return None;
+    }
+
+    let string = |idx: u64| {
+        usize::try_from(idx)
+            .ok()
+            .and_then(|i| strings.get(i))
+            .filter(|s| !s.is_empty())
+    };
+    let value_index = default_type
+        .and_then(|d| sample_types.iter().position(|&t| string(t) == string(d)))
+        .unwrap_or(sample_types.len() - 1);
+
+    let samples = samples
+        .into_iter()
+        .filter_map(|(locs, values)| {
+            // Values are int64; negative ones (diff profiles) are dropped.
+            let weight = i64::from_ne_bytes(values.get(value_index)?.to_ne_bytes());
+            if weight <= 0 {
+                return None;
+            }
+            let stack: Vec<Frame> = locs
+                .iter()
+                .rev()
+                .filter_map(|loc| locations.get(loc))
+                .flat_map(|lines| lines.iter().rev())
+                .filter_map(|&(function, line)| {
+                    let (name, file) = functions.get(&function)?;
+                    Some(Frame {
+                        symbol: string(*name)?.clone(),
+                        file: string(*file).cloned(),
+                        line: u32::try_from(line).ok().filter(|&l| l > 0),
+                    })
+                })
+                .collect();
+            (!stack.is_empty()).then_some(Sample {
+                stack,
+                weight: weight as f64,
+            })
+        })
+        .collect();
+    Some(samples)
+}
+
+/// A `Location`: its id and `(function id, line)` per line, innermost first.
+fn parse_location(bytes: &[u8]) -> Option<(u64, Vec<(u64, u64)>)> {
+    let mut id = 0;
+    let mut lines = Vec::new();
+    for (n, f) in Wire::fields(bytes)? {
+        match (n, &f) {
+            (1, _) => id = varint(&f).unwrap_or(0),
+            (4, Field::Bytes(line)) => {
+                let mut function = 0;
+                let mut number = 0;
+                for (n, f) in Wire::fields(line)? {
+                    match n {
+                        1 => function = varint(&f).unwrap_or(0),
+                        2 => number = varint(&f).unwrap_or(0),
+                        _ => {}
+                    }
+                }
+                lines.push((function, number));
+            }
+            _ => {}
+        }
+    }
+    Some((id, lines))
+}
+
+/// A `Function`: its id and the string indices of its name and file.
+fn parse_function(bytes: &[u8]) -> Option<(u64, u64, u64)> {
+    let (mut id, mut name, mut file) = (0, 0, 0);
+    for (n, f) in Wire::fields(bytes)? {
+        match n {
+            1 => id = varint(&f).unwrap_or(0),
+            2 => name = varint(&f).unwrap_or(0),
+            4 => file = varint(&f).unwrap_or(0),
+            _ => {}
+        }
+    }
+    Some((id, name, file))
+}
+
+// ── Symbol matching ───────────────────────────────────────────────
+
+/// The name segments of a profiled symbol, outermost first, with hashes,
+/// offsets, generic arguments, parameter lists and closure suffixes removed.
+///
+/// `<myapp::Store as Default>::default::h0123456789abcdef` becomes
+/// `[myapp, Store, default]`, `github.com/x/pkg.(*Server).Handle.func1`
+/// becomes `[pkg, Server, Handle]`, and `com.x.Cache.get(int)` becomes
+/// `[com, x, Cache, get]`.
+pub fn symbol_segments(symbol: &str) -> Vec<String> {
+    let mut s = symbol.trim();
+    // perf annotations: `_[k]` kernel, `_[j]` JIT, `_[i]` inlined.
+    if s.len() > 4 && s.ends_with(']') && s.as_bytes()[s.len() - 4..s.len() - 2] == *b"_[" {
+        s = &s[..s.len() - 4];
+    }
+    for marker in ["+0x", ".llvm."] {
+        if let Some(idx) = s.find(marker) {
+            s = &s[..idx];
+        }
+    }
+    if let Some((head, hash)) = s.rsplit_once("::") {
+        if hash.len() == 17
+            && hash.starts_with('h')
+            && hash[1..].bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            s = head;
+        }
+    }
+
+    // `<Type as Trait>::method` → `Type::method`.
+    let mut owned = s.to_string();
+    if owned.starts_with('<') {
+        if let Some(close) = matching_angle(&owned) {
+            let inner = &owned[1..close];
+            let ty = inner.split(" as ").next().unwrap_or(inner).to_string();
+            owned = format!("{ty}{}", &owned[close + 1..]);
+        }
+    }
+    let mut depth = 0usize;
+    let mut plain = String::with_capacity(owned.len());
+    for c in owned.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => plain.push(c),
+            _ => {}
+        }
+    }
+    let plain = plain.replace("(*", "").replace(").", ".");
+    let plain = plain.split('(').next().unwrap_or_default();
+    let plain = plain.rsplit('/').next().unwrap_or_default();
+
+    let separator = if plain.contains("::") { "::" } else { "." };
+    let mut segments: Vec<String> = plain
+        .split(separator)
+        .map(str::trim)
+        .filter(|seg| !seg.is_empty() && !seg.starts_with('{'))
+        .map(str::to_string)
+        .collect();
+    // Go closures: `Handle.func1`, `Handle.func1.2`.
+    while segments.len() > 1
+        && segments.last().is_some_and(|seg| {
+            seg.bytes().all(|b| b.is_ascii_digit())
+                || seg
+                    .strip_prefix("func")
+                    .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        })
+    {
+        segments.pop();
+    }
+    segments
+}
+
+fn matching_angle(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Leading symbol segments of language runtimes and standard libraries.
+const FOREIGN_ROOTS: &[&str] = &[
+    "std", "core", "alloc", "libc", "runtime", "syscall", "java", "javax", "jdk", "sun", "kotlin",
+];
+
+fn name_segments(qualified: &str) -> Vec<&str> {
+    let separator = if qualified.contains("::") { "::" } else { "." };
+    qualified
+        .split(separator)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// A repo function a frame can map to.
+struct FunctionEntry {
+    name: String,
+    file: String,
+    qualified: Vec<String>,
+    lines: Option<(u32, u32)>,
+}
+
+/// Function nodes indexed by file and by simple name. A frame maps by file
+/// and line when the profile records them (the smallest containing span
+/// wins; files outside the repo stay unmapped), and otherwise by its
+/// demangled qualified name, matched from the right.
+struct FunctionIndex {
+    functions: Vec<FunctionEntry>,
+    by_file: HashMap<String, Vec<usize>>,
+    by_simple: HashMap<String, Vec<usize>>,
+    resolver: PathResolver,
+}
+
+impl FunctionIndex {
+    fn new(root: &Path, files: HashSet<String>, functions: &[Node]) -> Self {
+        let mut index = Self {
+            functions: Vec::new(),
+            by_file: HashMap::new(),
+            by_simple: HashMap::new(),
+            resolver: PathResolver::new(root, files),
+        };
+        for func in functions {
+            let Some(file) = func
+                .metadata
+                .get("file")
+                .and_then(serde_json::Value::as_str)
+            else {
+                continue;
+            };
+            let qualified = func
+                .metadata
+                .get("qualified_name")
+                .and_then(serde_json::Value::as_str)
+                .or_else(|| func.name.strip_prefix(file)?.strip_prefix("::"))
+                .unwrap_or(&func.name);
+            let qualified: Vec<String> = name_segments(qualified)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            let Some(simple) = qualified.last().cloned() else {
+                continue;
+            };
+            let i = index.functions.len();
+            index.by_file.entry(file.to_string()).or_default().push(i);
+            index.by_simple.entry(simple).or_default().push(i);
+            index.functions.push(FunctionEntry {
+                name: func.name.clone(),
+                file: file.to_string(),
+                qualified,
+                lines: function_lines(func).map(|(_, range)| range),
+            });
+        }
+        index
+    }
+
+    /// The function a frame falls in, if it is repo code.
+    fn resolve(&self, frame: &Frame) -> Option<usize> {
+        let segments = symbol_segments(&frame.symbol);
+        let Some(file) = &frame.file else {
+            return self.match_symbol(&segments, self.by_simple.get(segments.last()?)?);
+        };
+        // A located frame outside the repo is library code.
+        let file = self.resolver.resolve(file, &[])?;
+        let in_file = self.by_file.get(&file)?;
+        if let Some(line) = frame.line {
+            let containing = in_file
+                .iter()
+                .filter_map(|&i| Some((i, self.functions[i].lines?)))
+                .filter(|(_, (start, end))| (*start..=*end).contains(&line))
+                .min_by_key(|(_, (start, end))| end - start);
+            if let Some((i, _)) = containing {
+                return Some(i);
+            }
+        }
+        let simple = segments.last()?;
+        let named: Vec<usize> = in_file
+            .iter()
+            .copied()
+            .filter(|&i| self.functions[i].qualified.last() == Some(simple))
+            .collect();
+        self.match_symbol(&segments, &named)
+    }
+
+    /// The candidate whose qualified name matches the most trailing
+    /// segments, with module segments naming its file as a tie-breaker.
+    /// A lone simple-name match on a deeper path is only trusted when a
+    /// module segment names the file, so `std::vec::Vec::push` does not map
+    /// to a repo function that happens to be called `push`.
+    fn match_symbol(&self, segments: &[String], candidates: &[usize]) -> Option<usize> {
+        if segments
+            .first()
+            .is_some_and(|root| FOREIGN_ROOTS.contains(&root.as_str()))
+        {
+            return None;
+        }
+        let mut best: Option<(usize, usize)> = None;
+        let mut tied = false;
+        for &i in candidates {
+            let entry = &self.functions[i];
+            let trailing = entry
+                .qualified
+                .iter()
+                .rev()
+                .zip(segments.iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            if trailing == 0 {
+                continue;
+            }
+            let path: HashSet<&str> = entry
+                .file
+                .split('/')
+                .map(|part| part.split('.').next().unwrap_or(part))
+                .collect();
+            let named = segments[..segments.len() - trailing]
+                .iter()
+                .filter(|s| path.contains(s.as_str()))
+                .count();
+            if trailing < 2 && segments.len() > 2 && named == 0 {
+                continue;
+            }
+            let score = trailing * 2 + named;
+            match best {
+                Some((top, _)) if score < top => {}
+                Some((top, _)) if score == top => tied = true,
+                _ => {
+                    best = Some((score, i));
+                    tied = false;
+                }
+            }
+        }
+        best.filter(|_| !tied).map(|(_, i)| i)
+    }
+}
+
+/// Self and total weight of a function within one profile.
+#[derive(Debug, Default, Clone, Copy)]
+struct Weight {
+    self_weight: f64,
+    total_weight: f64,
+}
+
+/// Per-function runtime shares merged across profiles. Shares are fractions
+/// of each profile's total weight averaged over the profiles read, so
+/// profiles with different units or lengths weigh the same.
+#[derive(Debug, Default)]
+struct Runtime {
+    self_share: f64,
+    total_share: f64,
+    profiles: u32,
+    formats: BTreeSet<ProfileFormat>,
+}
+
+impl Runtime {
+    fn to_json(&self, profile_count: usize) -> serde_json::Value {
+        let round = |r: f64| (r / profile_count as f64 * 10000.0).round() / 10000.0;
+        serde_json::json!({
+            "self_share": round(self.self_share),
+            "total_share": round(self.total_share),
+            "profiles": self.profiles,
+            "formats": self.formats.iter().map(|f| f.as_str()).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Charge each sample to the repo functions on its stack. Self weight goes to
+/// the innermost repo function, so library time is charged to the repo code
+/// that called it; total weight goes to every function on the stack.
+fn attribute(
+    samples: &[Sample],
+    index: &FunctionIndex,
+    cache: &mut HashMap<Frame, Option<usize>>,
+) -> (HashMap<usize, Weight>, f64) {
+    let mut weights: HashMap<usize, Weight> = HashMap::new();
+    let mut total = 0.0;
+    for sample in samples {
+        total += sample.weight;
+        let resolved: Vec<usize> = sample
+            .stack
+            .iter()
+            .filter_map(|frame| {
+                *cache
+                    .entry(frame.clone())
+                    .or_insert_with(|| index.resolve(frame))
+            })
+            .collect();
+        if let Some(&innermost) = resolved.last() {
+            weights.entry(innermost).or_default().self_weight += sample.weight;
+        }
+        let distinct: HashSet<usize> = resolved.into_iter().collect();
+        for i in distinct {
+            weights.entry(i).or_default().total_weight += sample.weight;
+        }
+    }
+    (weights, total)
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// Stores each function's runtime summary under its `runtime` metadata,
+/// reapplied whenever structure or graph extraction rewrites the nodes or
+/// the profiles change.
+#[derive(Debug)]
+pub struct ProfileExtractor {
+    repo_path: PathBuf,
+    settings: ProfileExtractionConfig,
+}
+
+impl ProfileExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.profiles.clone(),
+        }
+    }
+
+    /// Profile files matching the configured patterns.
+    fn discover_profiles(&self) -> Vec<PathBuf> {
+        if !self.settings.enabled {
+            return Vec::new();
+        }
+        let root = glob::Pattern::escape(&self.repo_path.to_string_lossy());
+        let mut profiles: Vec<PathBuf> = self
+            .settings
+            .profiles
+            .iter()
+            .filter_map(|pattern| glob::glob(&format!("{root}/{pattern}")).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|p| p.is_file())
+            .collect();
+        profiles.sort();
+        profiles.dedup();
+        profiles
+    }
+
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let mut hasher = DefaultHasher::new();
+        for profile in self.discover_profiles() {
+            profile.hash(&mut hasher);
+            if let Ok(meta) = std::fs::metadata(&profile) {
+                meta.len().hash(&mut hasher);
+                meta.modified().ok().hash(&mut hasher);
+            }
+        }
+        // Structure and graph extraction rewrite node metadata.
+        let structure = store.get_checkpoint("structure_last_sha").await?;
+        let graph = store.get_checkpoint("graph_last_sha").await?;
+        Ok(format!(
+            "{}:{}:{:x}",
+            structure.unwrap_or_default(),
+            graph.unwrap_or_default(),
+            hasher.finish()
+        ))
+    }
+
+    /// Parse every profile and merge the shares per function. Returns the
+    /// shares by function node name and the number of profiles read.
+    fn load_runtime(&self, index: &FunctionIndex) -> (HashMap<String, Runtime>, usize) {
+        let mut runtime: HashMap<usize, Runtime> = HashMap::new();
+        let mut cache: HashMap<Frame, Option<usize>> = HashMap::new();
+        let mut read = 0usize;
+        for path in self.discover_profiles() {
+            if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_PROFILE_BYTES) {
+                warn!(profile = %path.display(), "Profile too large, skipping");
+                continue;
+            }
+            let Ok(bytes) = std::fs::read(&path) else {
+                continue;
+            };
+            let Some((format, samples)) = parse_profile(&bytes) else {
+                debug!(profile = %path.display(), "Unrecognized profile format");
+                continue;
+            };
+            let (weights, total) = attribute(&samples, index, &mut cache);
+            if total <= 0.0 {
+                continue;
+            }
+            read += 1;
+            for (i, weight) in &weights {
+                let entry = runtime.entry(*i).or_default();
+                entry.self_share += weight.self_weight / total;
+                entry.total_share += weight.total_weight / total;
+                entry.profiles += 1;
+                entry.formats.insert(format);
+            }
+            info!(
+                profile = %path.display(),
+                format = format.as_str(),
+                samples = samples.len(),
+                functions = weights.len(),
+                "Read profile"
+            );
+        }
+        let runtime = runtime
+            .into_iter()
+            .map(|(i, r)| (index.functions[i].name.clone(), r))
+            .collect();
+        (runtime, read)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for ProfileExtractor {
+    fn name(&self) -> &'static str {
+        "profiles"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "profiles_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "profiles_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        let files: HashSet<String> = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            })
+            .await?
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        let functions = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Function),
+                ..Default::default()
+            })
+            .await?;
+        let index = FunctionIndex::new(&self.repo_path, files, &functions);
+        let (runtime, profiles) = self.load_runtime(&index);
+
+        for func in functions {
+            let value = runtime.get(&func.name).map(|r| r.to_json(profiles));
+            if set_runtime(store, func, value).await? {
+                stats.nodes_updated += 1;
+            }
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("profiles_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            profiles,
+            functions = runtime.len(),
+            updated = stats.nodes_updated,
+            duration = ?stats.duration,
+            "Profile extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Store or clear a function's runtime summary. Returns whether it changed.
+async fn set_runtime(
+    store: &dyn HomerStore,
+    mut node: Node,
+    value: Option<serde_json::Value>,
+) -> crate::error::Result<bool> {
+    if node.metadata.get(RUNTIME_KEY) == value.as_ref() {
+        return Ok(false);
+    }
+    match value {
+        Some(value) => node.metadata.insert(RUNTIME_KEY.to_string(), value),
+        None => node.metadata.remove(RUNTIME_KEY),
+    };
+    store.upsert_node(&node).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::NodeId;
+
+    #[test]
+    fn normalizes_symbols() {
+        assert_eq!(
+            symbol_segments("<myapp::Store as core::default::Default>::default::h0123456789abcdef"),
+            vec!["myapp", "Store", "default"]
+        );
+        assert_eq!(
+            symbol_segments("myapp::cache::Cache<K,V>::get::{{closure}}+0x1f"),
+            vec!["myapp", "cache", "Cache", "get"]
+        );
+        assert_eq!(
+            symbol_segments("github.com/acme/srv/pkg.(*Server).Handle.func1"),
+            vec!["pkg", "Server", "Handle"]
+        );
+        assert_eq!(
+            symbol_segments("com.acme.Cache.get(java.lang.String)"),
+            vec!["com", "acme", "Cache", "get"]
+        );
+        assert_eq!(symbol_segments("do_sys_open_[k]"), vec!["do_sys_open"]);
+    }
+
+    #[test]
+    fn parses_folded_and_perf_script() {
+        let (format, samples) =
+            parse_profile(b"main;app::run;app::parse 30\nmain;handle (app/views.py:12) 10\n")
+                .unwrap();
+        assert_eq!(format, ProfileFormat::Folded);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].stack[2].symbol, "app::parse");
+        assert!((samples[0].weight - 30.0).abs() < f64::EPSILON);
+        assert_eq!(samples[1].stack[1].file.as_deref(), Some("app/views.py"));
+        assert_eq!(samples[1].stack[1].line, Some(12));
+
+        let perf = "app 1234 100.000001: 250000 cpu-clock:\n\
+                    \t    55d4c8a1 app::parse::h0123456789abcdef+0x12 (/usr/bin/app)\n\
+                    \t    55d4c8b2 main+0x20 (/usr/bin/app)\n\
+                    \t    7f0000 [unknown] ([unknown])\n\
+                    \n\
+                    app 1234 100.000002: 250000 cpu-clock:\n\
+                    \t    55d4c8b2 main+0x20 (/usr/bin/app)\n";
+        let (format, samples) = parse_profile(perf.as_bytes()).unwrap();
+        assert_eq!(format, ProfileFormat::PerfScript);
+        assert_eq!(samples.len(), 2);
+        let names: Vec<&str> = samples[0].stack.iter().map(|f| f.symbol.as_str()).collect();
+        assert_eq!(names, ["main+0x20", "app::parse::h0123456789abcdef+0x12"]);
+    }
+
+    #[test]
+    fn chrome_trace_weighs_exclusive_time() {
+        let trace = serde_json::json!({"traceEvents": [
+            {"ph": "X", "name": "outer", "ts": 0, "dur": 100, "pid": 1, "tid": 1},
+            {"ph": "X", "name": "inner", "ts": 10, "dur": 30, "pid": 1, "tid": 1},
+            {"ph": "B", "name": "other", "ts": 0, "pid": 1, "tid": 2},
+            {"ph": "E", "ts": 50, "pid": 1, "tid": 2},
+        ]});
+        let (format, samples) = parse_profile(trace.to_string().as_bytes()).unwrap();
+        assert_eq!(format, ProfileFormat::ChromeTrace);
+        let weight = |leaf: &str| {
+            samples
+                .iter()
+                .find(|s| s.stack.last().unwrap().symbol == leaf)
+                .map(|s| (s.stack.len(), s.weight))
+        };
+        assert_eq!(weight("outer"), Some((1, 70.0)));
+        assert_eq!(weight("inner"), Some((2, 30.0)));
+        assert_eq!(weight("other"), Some((1, 50.0)));
+    }
+
+    /// Minimal protobuf encoding for building pprof fixtures.
+    fn put_varint(out: &mut Vec<u8>, mut v: u64) {
+        while v >= 0x80 {
+            out.push(u8::try_from(v & 0x7f).unwrap() | 0x80);
+            v >>= 7;
+        }
+        out.push(u8::try_from(v).unwrap());
+    }
+
+    fn put_field(out: &mut Vec<u8>, number: u64, bytes: &[u8]) {
+        put_varint(out, number << 3 | 2);
+        put_varint(out, bytes.len() as u64);
+        out.extend_from_slice(bytes);
+    }
+
+    fn put_uint(out: &mut Vec<u8>, number: u64, v: u64) {
+        put_varint(out, number << 3);
+        put_varint(out, v);
+    }
+
+    fn packed(values: &[u64]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for &v in values {
+            put_varint(&mut out, v);
+        }
+        out
+    }
+
+    /// Wrap `data` in a gzip member made of one stored deflate block.
+    fn gzip_stored(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255];
+        let len = u16::try_from(data.len()).unwrap();
+        out.push(1);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(data);
+        out.extend_from_slice(&[0; 8]);
+        out
+    }
+
+    #[test]
+    fn parses_gzipped_pprof() {
+        let mut profile = Vec::new();
+        for s in [
+            "",
+            "samples",
+            "count",
+            "cpu",
+            "nanoseconds",
+            "main",
+            "parse",
+            "src/parse.rs",
+        ] {
+            put_field(&mut profile, 6, s.as_bytes());
+        }
+        for (ty, unit) in [(1, 2), (3, 4)] {
+            let mut vt = Vec::new();
+            put_uint(&mut vt, 1, ty);
+            put_uint(&mut vt, 2, unit);
+            put_field(&mut profile, 1, &vt);
+        }
+        for (id, name) in [(1, 5), (2, 6)] {
+            let mut f = Vec::new();
+            put_uint(&mut f, 1, id);
+            put_uint(&mut f, 2, name);
+            put_uint(&mut f, 4, 7);
+            put_field(&mut profile, 5, &f);
+        }
+        for (id, function, line) in [(10, 1, 3), (20, 2, 42)] {
+            let mut l = Vec::new();
+            put_uint(&mut l, 1, function);
+            put_uint(&mut l, 2, line);
+            let mut loc = Vec::new();
+            put_uint(&mut loc, 1, id);
+            put_field(&mut loc, 4, &l);
+            put_field(&mut profile, 4, &loc);
+        }
+        let mut sample = Vec::new();
+        put_field(&mut sample, 1, &packed(&[20, 10]));
+        put_field(&mut sample, 2, &packed(&[3, 30_000_000]));
+        put_field(&mut profile, 2, &sample);
+
+        let (format, samples) = parse_profile(&gzip_stored(&profile)).unwrap();
+        assert_eq!(format, ProfileFormat::Pprof);
+        assert_eq!(samples.len(), 1);
+        assert!((samples[0].weight - 30_000_000.0).abs() < f64::EPSILON);
+        let stack: Vec<(&str, Option<u32>)> = samples[0]
+            .stack
+            .iter()
+            .map(|f| (f.symbol.as_str(), f.line))
+            .collect();
+        assert_eq!(stack, [("main", Some(3)), ("parse", Some(42))]);
+        assert_eq!(samples[0].stack[1].file.as_deref(), Some("src/parse.rs"));
+    }
+
+    fn function(file: &str, qualified: &str, rows: (u64, u64)) -> Node {
+        Node {
+            id: NodeId(0),
+            kind: NodeKind::Function,
+            name: format!("{file}::{qualified}"),
+            content_hash: None,
+            last_extracted: chrono::Utc::now(),
+            metadata: [
+                ("file".to_string(), serde_json::json!(file)),
+                ("qualified_name".to_string(), serde_json::json!(qualified)),
+                (
+                    "span".to_string(),
+                    serde_json::json!({"start_row": rows.0, "start_col": 0, "end_row": rows.1, "end_col": 1}),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn maps_frames_to_functions() {
+        let store = SqliteStore::in_memory().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["src/main.rs", "src/parse.rs", "app/views.py"] {
+            store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: file.to_string(),
+                    content_hash: None,
+                    last_extracted: chrono::Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+        for node in [
+            function("src/main.rs", "main", (0, 10)),
+            function("src/parse.rs", "Parser::parse", (0, 40)),
+            function("src/parse.rs", "push", (41, 50)),
+            function("app/views.py", "handle", (10, 20)),
+        ] {
+            store.upsert_node(&node).await.unwrap();
+        }
+
+        std::fs::create_dir_all(dir.path().join("profiles")).unwrap();
+        std::fs::write(
+            dir.path().join("profiles/cpu.folded"),
+            "app::main;app::parse::Parser::parse::h0123456789abcdef;alloc::vec::Vec<T>::push 60\n\
+             app::main 20\n\
+             serve;handle (/srv/app/views.py:15) 10\n\
+             serve;handle (/usr/lib/python3/site.py:15) 10\n",
+        )
+        .unwrap();
+
+        let extractor = ProfileExtractor::new(dir.path(), &HomerConfig::default());
+        assert!(extractor.has_work(&store).await.unwrap());
+        let stats = extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.nodes_updated, 3);
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let runtime = |name: &str| {
+            let store = &store;
+            let name = name.to_string();
+            async move {
+                store
+                    .get_node_by_name(NodeKind::Function, &name)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .metadata
+                    .get(RUNTIME_KEY)
+                    .cloned()
+            }
+        };
+        // Time in `Vec::push` is charged to its repo caller, not to the
+        // unrelated repo function named `push`.
+        let parse = runtime("src/parse.rs::Parser::parse").await.unwrap();
+        assert_eq!(parse["self_share"], 0.6);
+        assert_eq!(parse["total_share"], 0.6);
+        let main = runtime("src/main.rs::main").await.unwrap();
+        assert_eq!(main["self_share"], 0.2);
+        assert_eq!(main["total_share"], 0.8);
+        let handle = runtime("app/views.py::handle").await.unwrap();
+        assert_eq!(handle["self_share"], 0.1);
+        assert!(runtime("src/parse.rs::push").await.is_none());
+    }
+}
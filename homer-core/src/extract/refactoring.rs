@@ -0,0 +1,865 @@
+// Refactoring detection across history: moved and renamed files and
+// functions are linked to their earlier identities with `Aliases` edges, so
+// they keep their history.
+
+// Similarity ratios intentionally cast counts to float.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use homer_graphs::{LanguageRegistry, SymbolKind};
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::HomerConfig;
+use crate::contracts::roles;
+use crate::error::{ExtractError, HomerError};
+use crate::store::HomerStore;
+use crate::types::{Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, NodeId, NodeKind};
+
+use super::git::GitExtractor;
+use super::traits::{ExtractStats, Extractor};
+
+/// Definitions with fewer non-blank lines than this are too generic to match.
+const MIN_BODY_LINES: usize = 3;
+
+/// Largest file, in bytes, read from history for comparison.
+const MAX_BLOB_BYTES: usize = 512 * 1024;
+
+/// What kind of refactoring was detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefactoringKind {
+    /// A file renamed, or deleted and re-added with similar content.
+    MoveFile,
+    /// A definition renamed within its file.
+    RenameFunction,
+    /// A definition moved to another file.
+    MoveFunction,
+    /// A new definition whose body was cut out of one that still exists.
+    /// Recorded with `source`/`extracted` roles rather than `old`/`new`, since
+    /// the source keeps its own identity.
+    ExtractFunction,
+}
+
+impl RefactoringKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MoveFile => "move_file",
+            Self::RenameFunction => "rename_function",
+            Self::MoveFunction => "move_function",
+            Self::ExtractFunction => "extract_function",
+        }
+    }
+}
+
+/// A refactoring detected between two versions of a set of files.
+#[derive(Debug, Clone)]
+pub struct Refactoring {
+    pub kind: RefactoringKind,
+    /// Old file path, or file-scoped function name (for extractions, the
+    /// function the code was cut from).
+    pub from: String,
+    /// New file path or file-scoped function name.
+    pub to: String,
+    /// Body similarity (0.0–1.0); for extractions, the share of the new
+    /// body that was cut from the source.
+    pub similarity: f64,
+}
+
+/// One version of a file: `(path, content)`.
+pub type Version = (String, String);
+
+/// A file touched by a commit, before and after.
+#[derive(Debug, Clone, Default)]
+pub struct FileVersions {
+    pub old: Option<Version>,
+    pub new: Option<Version>,
+}
+
+/// For each non-merge commit, parses the changed files before and after and
+/// pairs removed definitions with added ones by body similarity. Function
+/// nodes are keyed by file-scoped name, so without this a moved or renamed
+/// function would start a fresh history.
+#[derive(Debug)]
+pub struct RefactoringExtractor {
+    repo_path: PathBuf,
+    registry: LanguageRegistry,
+}
+
+impl RefactoringExtractor {
+    pub fn new(repo_path: &Path) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            registry: LanguageRegistry::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for RefactoringExtractor {
+    fn name(&self) -> &'static str {
+        "refactoring"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let done = store.get_checkpoint("refactoring_last_sha").await?;
+        let git_sha = store.get_checkpoint("git_last_sha").await?;
+        Ok(git_sha.is_some() && done != git_sha)
+    }
+
+    #[instrument(skip_all, name = "refactoring_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+        let settings = &config.extraction.refactoring;
+        if !settings.enabled {
+            return Ok(stats);
+        }
+
+        let repo = gix::open(&self.repo_path)
+            .map_err(|e| HomerError::Extract(ExtractError::Git(e.to_string())))?;
+        let head = repo
+            .head_commit()
+            .map_err(|e| HomerError::Extract(ExtractError::Git(e.to_string())))?;
+        let head_sha = head.id().to_string();
+
+        let checkpoint = store.get_checkpoint("refactoring_last_sha").await?;
+        let since = checkpoint
+            .as_deref()
+            .filter(|sha| GitExtractor::is_ancestor(&head, sha));
+        let commits = GitExtractor::collect_commits(&head, since, config)?;
+
+        // Names seen only mid-history (never extracted as nodes) → the node
+        // whose history they carry, so chains of refactorings stay linked.
+        let mut pending: HashMap<String, NodeId> = HashMap::new();
+        let mut detected = 0usize;
+
+        for oid in &commits {
+            let sha = oid.to_string();
+            let Some((versions, at)) = self.commit_versions(&repo, *oid, &sha, store).await? else {
+                continue;
+            };
+            let found = detect_refactorings(&versions, &self.registry, settings.min_similarity);
+            for refactoring in &found {
+                if let Err(e) =
+                    record_refactoring(store, &mut stats, refactoring, &sha, at, &mut pending).await
+                {
+                    warn!(sha = %sha, error = %e, "Failed to record refactoring");
+                    stats.errors.push((sha.clone(), e));
+                }
+            }
+            detected += found.len();
+        }
+
+        store
+            .set_checkpoint("refactoring_last_sha", &head_sha)
+            .await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            commits = commits.len(),
+            refactorings = detected,
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "Refactoring detection complete"
+        );
+        Ok(stats)
+    }
+}
+
+impl RefactoringExtractor {
+    /// Old and new contents of the files a commit changed, plus its author
+    /// time. `None` for merges, root commits, and commits git extraction
+    /// has not recorded.
+    async fn commit_versions(
+        &self,
+        repo: &gix::Repository,
+        oid: gix::ObjectId,
+        sha: &str,
+        store: &dyn HomerStore,
+    ) -> crate::error::Result<Option<(Vec<FileVersions>, DateTime<Utc>)>> {
+        let git_err =
+            |e: &dyn std::fmt::Display| HomerError::Extract(ExtractError::Git(e.to_string()));
+
+        let commit = repo.find_commit(oid).map_err(|e| git_err(&e))?;
+        let parents: Vec<_> = commit.parent_ids().collect();
+        // Merge diffs repeat the changes already seen on the merged branch.
+        let [parent_id] = parents.as_slice() else {
+            return Ok(None);
+        };
+
+        let Some(commit_node) = store.get_node_by_name(NodeKind::Commit, sha).await? else {
+            return Ok(None);
+        };
+        let edges = store.get_edges_involving(commit_node.id).await?;
+        let Some(modifies) = edges.iter().find(|e| e.kind == HyperedgeKind::Modifies) else {
+            return Ok(None);
+        };
+        let files = modifies
+            .metadata
+            .get("files")
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        let tree = commit.tree().map_err(|e| git_err(&e))?;
+        let parent_tree = parent_id
+            .object()
+            .map_err(|e| git_err(&e))?
+            .try_into_commit()
+            .map_err(|e| git_err(&e))?
+            .tree()
+            .map_err(|e| git_err(&e))?;
+
+        let mut versions = Vec::new();
+        for file in &files {
+            let Some(path) = file.get("path").and_then(serde_json::Value::as_str) else {
+                continue;
+            };
+            let status = file.get("status").and_then(serde_json::Value::as_str);
+            let old_path = file
+                .get("old_path")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or(path);
+            // Modified files only matter when their definitions can be parsed.
+            if status == Some("Modified") && self.registry.for_file(Path::new(path)).is_none() {
+                continue;
+            }
+
+            let old = match status {
+                Some("Added" | "Copied") => None,
+                _ => read_blob(&parent_tree, old_path).map(|c| (old_path.to_string(), c)),
+            };
+            let new = match status {
+                Some("Deleted") => None,
+                _ => read_blob(&tree, path).map(|c| (path.to_string(), c)),
+            };
+            if old.is_some() || new.is_some() {
+                versions.push(FileVersions { old, new });
+            }
+        }
+        debug!(sha, files = versions.len(), "Comparing commit");
+        Ok(Some((versions, modifies.last_updated)))
+    }
+}
+
+/// Text content of a file in a tree; `None` when missing, binary, or large.
+pub(crate) fn read_blob(tree: &gix::Tree<'_>, path: &str) -> Option<String> {
+    let entry = tree.lookup_entry_by_path(path).ok()??;
+    if !entry.mode().is_blob() {
+        return None;
+    }
+    let data = entry.object().ok()?.detach().data;
+    if data.len() > MAX_BLOB_BYTES || data[..data.len().min(8192)].contains(&0) {
+        return None;
+    }
+    String::from_utf8(data).ok()
+}
+
+/// Store one refactoring as an edge between existing nodes. Names without a
+/// node are remembered in `pending` so a later refactoring can still link
+/// the original node to the final name.
+async fn record_refactoring(
+    store: &dyn HomerStore,
+    stats: &mut ExtractStats,
+    refactoring: &Refactoring,
+    sha: &str,
+    at: DateTime<Utc>,
+    pending: &mut HashMap<String, NodeId>,
+) -> crate::error::Result<()> {
+    let kind = if refactoring.kind == RefactoringKind::MoveFile {
+        NodeKind::File
+    } else {
+        NodeKind::Function
+    };
+    let origin = match store
+        .get_node_by_name(kind.clone(), &refactoring.from)
+        .await?
+    {
+        Some(node) => Some(node.id),
+        None => pending.remove(&refactoring.from),
+    };
+    let Some(origin) = origin else {
+        return Ok(());
+    };
+    let Some(target) = store
+        .get_node_by_name(kind, &refactoring.to)
+        .await?
+        .map(|n| n.id)
+    else {
+        if refactoring.kind != RefactoringKind::ExtractFunction {
+            pending.insert(refactoring.to.clone(), origin);
+        }
+        return Ok(());
+    };
+    if origin == target {
+        return Ok(());
+    }
+
+    let (from_role, to_role) = if refactoring.kind == RefactoringKind::ExtractFunction {
+        (roles::EXTRACTED_FROM, roles::EXTRACTED)
+    } else {
+        (roles::ALIAS_OLD, roles::ALIAS_NEW)
+    };
+    let similarity = (refactoring.similarity * 1000.0).round() / 1000.0;
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "refactoring".to_string(),
+        serde_json::json!(refactoring.kind.as_str()),
+    );
+    metadata.insert("from".to_string(), serde_json::json!(refactoring.from));
+    metadata.insert("to".to_string(), serde_json::json!(refactoring.to));
+    metadata.insert("commit".to_string(), serde_json::json!(sha));
+    metadata.insert("similarity".to_string(), serde_json::json!(similarity));
+
+    store
+        .upsert_hyperedge(&Hyperedge {
+            id: HyperedgeId(0),
+            kind: HyperedgeKind::Aliases,
+            members: vec![
+                HyperedgeMember {
+                    node_id: origin,
+                    role: from_role.to_string(),
+                    position: 0,
+                },
+                HyperedgeMember {
+                    node_id: target,
+                    role: to_role.to_string(),
+                    position: 1,
+                },
+            ],
+            confidence: similarity,
+            last_updated: at,
+            metadata,
+        })
+        .await?;
+    stats.edges_created += 1;
+    Ok(())
+}
+
+// ── Detection ─────────────────────────────────────────────────────
+
+/// A function definition with its body normalized to trimmed, non-blank lines.
+struct Definition {
+    key: String,
+    path: String,
+    qualified_name: String,
+    lines: Vec<String>,
+}
+
+/// Detect refactorings between the old and new versions of a commit's files.
+#[allow(clippy::too_many_lines)]
+pub fn detect_refactorings(
+    files: &[FileVersions],
+    registry: &LanguageRegistry,
+    min_similarity: f64,
+) -> Vec<Refactoring> {
+    let mut found = Vec::new();
+
+    // Pair each file's old and new version; renames and similar
+    // delete/add pairs are file moves.
+    let mut pairs: Vec<(&Version, &Version)> = Vec::new();
+    let mut deleted = Vec::new();
+    let mut added = Vec::new();
+    for file in files {
+        match (&file.old, &file.new) {
+            (Some(old), Some(new)) => {
+                if old.0 != new.0 {
+                    found.push(Refactoring {
+                        kind: RefactoringKind::MoveFile,
+                        from: old.0.clone(),
+                        to: new.0.clone(),
+                        similarity: similarity(&normalize(&old.1), &normalize(&new.1)),
+                    });
+                }
+                pairs.push((old, new));
+            }
+            (Some(old), None) => deleted.push(old),
+            (None, Some(new)) => added.push(new),
+            (None, None) => {}
+        }
+    }
+
+    let deleted_lines: Vec<Vec<String>> = deleted.iter().map(|f| normalize(&f.1)).collect();
+    let added_lines: Vec<Vec<String>> = added.iter().map(|f| normalize(&f.1)).collect();
+    let moves = best_matches(deleted.len(), added.len(), min_similarity, |d, a| {
+        if extension(&deleted[d].0) != extension(&added[a].0) {
+            return None;
+        }
+        similarity_at_least(&deleted_lines[d], &added_lines[a], min_similarity)
+    });
+    let mut moved_old = vec![false; deleted.len()];
+    let mut moved_new = vec![false; added.len()];
+    for &(d, a, score) in &moves {
+        found.push(Refactoring {
+            kind: RefactoringKind::MoveFile,
+            from: deleted[d].0.clone(),
+            to: added[a].0.clone(),
+            similarity: score,
+        });
+        pairs.push((deleted[d], added[a]));
+        moved_old[d] = true;
+        moved_new[a] = true;
+    }
+
+    // Definitions that keep their qualified name within a paired file
+    // survive (moving with the file if it moved); the rest are removed or
+    // added.
+    let mut survivors: Vec<(Definition, Definition)> = Vec::new();
+    let mut removed: Vec<Definition> = Vec::new();
+    let mut created: Vec<Definition> = Vec::new();
+    for (old, new) in &pairs {
+        let mut new_defs: HashMap<String, Definition> = definitions(new, registry)
+            .into_iter()
+            .map(|d| (d.qualified_name.clone(), d))
+            .collect();
+        for old_def in definitions(old, registry) {
+            match new_defs.remove(&old_def.qualified_name) {
+                Some(new_def) => {
+                    if old_def.path != new_def.path {
+                        found.push(Refactoring {
+                            kind: RefactoringKind::MoveFunction,
+                            from: old_def.key.clone(),
+                            to: new_def.key.clone(),
+                            similarity: similarity(&old_def.lines, &new_def.lines),
+                        });
+                    }
+                    survivors.push((old_def, new_def));
+                }
+                None => removed.push(old_def),
+            }
+        }
+        created.extend(new_defs.into_values());
+    }
+    for (d, old) in deleted.iter().enumerate() {
+        if !moved_old[d] {
+            removed.extend(definitions(old, registry));
+        }
+    }
+    for (a, new) in added.iter().enumerate() {
+        if !moved_new[a] {
+            created.extend(definitions(new, registry));
+        }
+    }
+    created.sort_by(|a, b| a.key.cmp(&b.key));
+
+    // Removed definitions reappearing under another name or in another file.
+    let same_file: HashMap<&str, &str> = pairs
+        .iter()
+        .map(|(old, new)| (old.0.as_str(), new.0.as_str()))
+        .collect();
+    let renames = best_matches(removed.len(), created.len(), min_similarity, |r, c| {
+        let (old, new) = (&removed[r], &created[c]);
+        if old.lines.len() < MIN_BODY_LINES {
+            return None;
+        }
+        similarity_at_least(&old.lines, &new.lines, min_similarity)
+    });
+    let mut matched_new = vec![false; created.len()];
+    for &(r, c, score) in &renames {
+        let (old, new) = (&removed[r], &created[c]);
+        let kind = if same_file.get(old.path.as_str()) == Some(&new.path.as_str()) {
+            RefactoringKind::RenameFunction
+        } else {
+            RefactoringKind::MoveFunction
+        };
+        found.push(Refactoring {
+            kind,
+            from: old.key.clone(),
+            to: new.key.clone(),
+            similarity: score,
+        });
+        matched_new[c] = true;
+    }
+
+    found.extend(detect_extractions(
+        &survivors,
+        &created,
+        &matched_new,
+        min_similarity,
+    ));
+    found
+}
+
+/// New definitions whose body was cut out of a surviving one.
+fn detect_extractions(
+    survivors: &[(Definition, Definition)],
+    created: &[Definition],
+    matched: &[bool],
+    min_similarity: f64,
+) -> Vec<Refactoring> {
+    let mut found = Vec::new();
+    let cut: Vec<HashMap<&str, usize>> = survivors
+        .iter()
+        .map(|(old, new)| removed_lines(&old.lines, &new.lines))
+        .collect();
+    for (c, new) in created.iter().enumerate() {
+        if matched[c] {
+            continue;
+        }
+        let body: Vec<&str> = new
+            .lines
+            .iter()
+            .skip(1)
+            .map(String::as_str)
+            .filter(|l| l.chars().any(char::is_alphanumeric))
+            .collect();
+        if body.len() < MIN_BODY_LINES {
+            continue;
+        }
+        let best = survivors
+            .iter()
+            .zip(&cut)
+            .map(|((_, source), cut)| {
+                let mut available = cut.clone();
+                let covered = body
+                    .iter()
+                    .filter(|line| match available.get_mut(*line) {
+                        Some(n) if *n > 0 => {
+                            *n -= 1;
+                            true
+                        }
+                        _ => false,
+                    })
+                    .count();
+                (source, covered as f64 / body.len() as f64)
+            })
+            .filter(|(_, share)| *share >= min_similarity)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((source, share)) = best {
+            found.push(Refactoring {
+                kind: RefactoringKind::ExtractFunction,
+                from: source.key.clone(),
+                to: new.key.clone(),
+                similarity: share,
+            });
+        }
+    }
+
+    found
+}
+
+/// Function definitions in one version of a file.
+fn definitions((path, source): &Version, registry: &LanguageRegistry) -> Vec<Definition> {
+    let file_path = Path::new(path);
+    let Some(lang) = registry.for_file(file_path) else {
+        return Vec::new();
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&lang.tree_sitter_language()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let Ok(graph) = lang.extract_heuristic(&tree, source, file_path) else {
+        return Vec::new();
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    graph
+        .definitions
+        .into_iter()
+        .filter(|d| d.kind == SymbolKind::Function)
+        .filter(|d| seen.insert(d.qualified_name.clone()))
+        .map(|d| Definition {
+            key: format!("{path}::{}", d.qualified_name),
+            path: path.clone(),
+            lines: normalize(source.get(d.span.start_byte..d.span.end_byte).unwrap_or("")),
+            qualified_name: d.qualified_name,
+        })
+        .collect()
+}
+
+/// Trimmed, non-blank lines, so re-indentation does not count as change.
+fn normalize(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn similarity(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let (a, b) = (a.join("\n"), b.join("\n"));
+    f64::from(similar::TextDiff::from_lines(&a, &b).ratio())
+}
+
+/// Similarity of `a` and `b` when it can reach `min`. Line-based ratios
+/// cannot exceed `2·min(len) / (len_a + len_b)`, which rules out most
+/// candidates without diffing.
+fn similarity_at_least(a: &[String], b: &[String], min: f64) -> Option<f64> {
+    let (la, lb) = (a.len() as f64, b.len() as f64);
+    if la + lb > 0.0 && 2.0 * la.min(lb) / (la + lb) < min {
+        return None;
+    }
+    Some(similarity(a, b))
+}
+
+/// Lines of `old` missing from `new`, with multiplicity.
+fn removed_lines<'a>(old: &'a [String], new: &[String]) -> HashMap<&'a str, usize> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for line in old {
+        *counts.entry(line.as_str()).or_default() += 1;
+    }
+    for line in new {
+        if let Some(n) = counts.get_mut(line.as_str()) {
+            *n = n.saturating_sub(1);
+        }
+    }
+    counts.retain(|_, n| *n > 0);
+    counts
+}
+
+fn extension(path: &str) -> Option<&str> {
+    Path::new(path).extension().and_then(|e| e.to_str())
+}
+
+/// Greedy one-to-one matching of `left` to `right` items, best score first.
+fn best_matches(
+    left: usize,
+    right: usize,
+    min_score: f64,
+    score: impl Fn(usize, usize) -> Option<f64>,
+) -> Vec<(usize, usize, f64)> {
+    let mut candidates = Vec::new();
+    for l in 0..left {
+        for r in 0..right {
+            if let Some(s) = score(l, r).filter(|s| *s >= min_score) {
+                candidates.push((l, r, s));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut used_left = vec![false; left];
+    let mut used_right = vec![false; right];
+    let mut matches = Vec::new();
+    for (l, r, s) in candidates {
+        if !used_left[l] && !used_right[r] {
+            used_left[l] = true;
+            used_right[r] = true;
+            matches.push((l, r, s));
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::git::GitExtractor;
+    use crate::extract::graph::GraphExtractor;
+    use crate::store::sqlite::SqliteStore;
+    use std::process::Command;
+
+    const PARSE: &str = "fn parse(input: &str) -> Vec<u32> {
+    let mut out = Vec::new();
+    for part in input.split(',') {
+        out.push(part.trim().parse().unwrap_or(0));
+    }
+    out
+}
+";
+
+    fn version(path: &str, content: &str) -> Version {
+        (path.to_string(), content.to_string())
+    }
+
+    fn kinds(found: &[Refactoring]) -> Vec<(RefactoringKind, &str, &str)> {
+        let mut kinds: Vec<_> = found
+            .iter()
+            .map(|r| (r.kind, r.from.as_str(), r.to.as_str()))
+            .collect();
+        kinds.sort_by_key(|k| (k.0.as_str(), k.1, k.2));
+        kinds
+    }
+
+    #[test]
+    fn detects_renamed_and_moved_functions() {
+        let registry = LanguageRegistry::new();
+        let renamed = PARSE.replace("fn parse(", "fn parse_numbers(");
+        let files = [
+            FileVersions {
+                old: Some(version("src/a.rs", &format!("{PARSE}\nfn keep() {{}}\n"))),
+                new: Some(version("src/a.rs", "fn keep() {}\n")),
+            },
+            FileVersions {
+                old: Some(version("src/b.rs", "fn other() {}\n")),
+                new: Some(version(
+                    "src/b.rs",
+                    &format!("fn other() {{}}\n\n{renamed}"),
+                )),
+            },
+        ];
+        let found = detect_refactorings(&files, &registry, 0.8);
+        assert_eq!(
+            kinds(&found),
+            [(
+                RefactoringKind::MoveFunction,
+                "src/a.rs::parse",
+                "src/b.rs::parse_numbers"
+            )]
+        );
+
+        let files = [FileVersions {
+            old: Some(version("src/a.rs", PARSE)),
+            new: Some(version("src/a.rs", &renamed)),
+        }];
+        let found = detect_refactorings(&files, &registry, 0.8);
+        assert_eq!(
+            kinds(&found),
+            [(
+                RefactoringKind::RenameFunction,
+                "src/a.rs::parse",
+                "src/a.rs::parse_numbers"
+            )]
+        );
+        assert!(found[0].similarity >= 0.8);
+    }
+
+    #[test]
+    fn detects_moved_files_and_their_functions() {
+        let registry = LanguageRegistry::new();
+        let files = [
+            FileVersions {
+                old: Some(version("src/old.rs", PARSE)),
+                new: None,
+            },
+            FileVersions {
+                old: None,
+                new: Some(version("src/new.rs", PARSE)),
+            },
+            FileVersions {
+                old: None,
+                new: Some(version(
+                    "src/unrelated.rs",
+                    "fn unrelated() -> u8 {\n    7\n}\n",
+                )),
+            },
+        ];
+        let found = detect_refactorings(&files, &registry, 0.8);
+        assert_eq!(
+            kinds(&found),
+            [
+                (RefactoringKind::MoveFile, "src/old.rs", "src/new.rs"),
+                (
+                    RefactoringKind::MoveFunction,
+                    "src/old.rs::parse",
+                    "src/new.rs::parse"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_extracted_functions() {
+        let registry = LanguageRegistry::new();
+        let before = "fn run(input: &str) -> u32 {
+    let cleaned = input.trim();
+    let mut total = 0;
+    for part in cleaned.split(',') {
+        total += part.parse::<u32>().unwrap_or(0);
+    }
+    total
+}
+";
+        let after = "fn run(input: &str) -> u32 {
+    sum(input.trim())
+}
+
+fn sum(cleaned: &str) -> u32 {
+    let mut total = 0;
+    for part in cleaned.split(',') {
+        total += part.parse::<u32>().unwrap_or(0);
+    }
+    total
+}
+";
+        let files = [FileVersions {
+            old: Some(version("src/lib.rs", before)),
+            new: Some(version("src/lib.rs", after)),
+        }];
+        let found = detect_refactorings(&files, &registry, 0.8);
+        assert_eq!(
+            kinds(&found),
+            [(
+                RefactoringKind::ExtractFunction,
+                "src/lib.rs::run",
+                "src/lib.rs::sum"
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn records_file_moves_as_aliases() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let run = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .env("GIT_AUTHOR_NAME", "Test")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "Test")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com")
+                .output()
+                .expect("git command failed")
+        };
+        run(&["init", "--initial-branch=main"]);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/parser.rs"), PARSE).unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-m", "Add parser"]);
+        run(&["mv", "src/parser.rs", "src/parse.rs"]);
+        run(&["commit", "-m", "Rename parser module"]);
+
+        let store = SqliteStore::in_memory().unwrap();
+        let config = HomerConfig::default();
+        GitExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+        GraphExtractor::new(dir)
+            .extract(&store, &config)
+            .await
+            .unwrap();
+        let extractor = RefactoringExtractor::new(dir);
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor.extract(&store, &config).await.unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let edges = store
+            .get_edges_by_kind(HyperedgeKind::Aliases)
+            .await
+            .unwrap();
+        let moved = edges
+            .iter()
+            .find(|e| e.metadata.get("refactoring") == Some(&serde_json::json!("move_file")))
+            .expect("file move recorded");
+        assert_eq!(moved.metadata["from"], "src/parser.rs");
+        assert_eq!(moved.metadata["to"], "src/parse.rs");
+
+        let old = store
+            .get_node_by_name(NodeKind::File, "src/parser.rs")
+            .await
+            .unwrap()
+            .unwrap();
+        let new = store
+            .get_node_by_name(NodeKind::File, "src/parse.rs")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(store.resolve_canonical(old.id).await.unwrap(), new.id);
+    }
+}
@@ -0,0 +1,2002 @@
+// Database schema extraction: migrations replayed into the current tables
+// and columns, and `Accesses` edges from the code that touches them.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use glob::Pattern;
+use homer_graphs::LanguageRegistry;
+use homer_graphs::data_access;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::config::{HomerConfig, SchemaExtractionConfig};
+use crate::contracts::roles;
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{HyperedgeKind, Node, NodeFilter, NodeId, NodeKind};
+
+use super::api_contract::{function_spans, link};
+use super::traits::{ExtractStats, Extractor};
+
+/// Most history entries kept on a table or column node, newest last.
+const MAX_HISTORY: usize = 50;
+
+/// Words opening a table constraint rather than a column in SQL.
+const CONSTRAINT_WORDS: &[&str] = &[
+    "constraint",
+    "primary",
+    "foreign",
+    "unique",
+    "check",
+    "key",
+    "index",
+    "exclude",
+    "like",
+    "fulltext",
+    "spatial",
+];
+
+/// Words ending a column type in SQL.
+const TYPE_STOP_WORDS: &[&str] = &[
+    "not",
+    "null",
+    "default",
+    "primary",
+    "references",
+    "unique",
+    "check",
+    "constraint",
+    "generated",
+    "collate",
+    "auto_increment",
+    "autoincrement",
+    "identity",
+    "comment",
+    "first",
+    "after",
+    "using",
+];
+
+/// SQL keywords after which a table name follows.
+const TABLE_KEYWORDS: &[&str] = &["from", "join", "into", "update", "table"];
+
+/// SQL keywords that cannot be a table alias.
+const SQL_KEYWORDS: &[&str] = &[
+    "where",
+    "join",
+    "inner",
+    "left",
+    "right",
+    "full",
+    "outer",
+    "cross",
+    "on",
+    "using",
+    "group",
+    "order",
+    "limit",
+    "offset",
+    "having",
+    "set",
+    "values",
+    "select",
+    "returning",
+    "union",
+    "as",
+    "natural",
+    "lateral",
+    "default",
+    "for",
+    "window",
+    "except",
+    "intersect",
+    "only",
+    "if",
+    "exists",
+    "not",
+];
+
+// ── Schema changes ────────────────────────────────────────────────
+
+/// One change a migration makes to a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    /// Table name, lowercase and without its schema prefix.
+    pub table: String,
+    pub action: SchemaAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaAction {
+    CreateTable,
+    DropTable,
+    RenameTable(String),
+    /// Any other table-level change: an index or constraint.
+    AlterTable(String),
+    AddColumn {
+        column: String,
+        sql_type: Option<String>,
+    },
+    DropColumn(String),
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+    AlterColumn(String),
+}
+
+impl SchemaChange {
+    fn new(table: &str, action: SchemaAction) -> Self {
+        Self {
+            table: table.to_lowercase(),
+            action,
+        }
+    }
+
+    fn add_column(table: &str, column: &str, sql_type: Option<String>) -> Self {
+        Self::new(
+            table,
+            SchemaAction::AddColumn {
+                column: column.to_lowercase(),
+                sql_type,
+            },
+        )
+    }
+}
+
+// ── Tokens ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tok {
+    /// Identifier, keyword, or number. Quoted SQL identifiers are words too.
+    Word(String),
+    /// String literal, or a Ruby symbol.
+    Str(String),
+    Punct(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    Sql,
+    Python,
+    Ruby,
+}
+
+impl Tok {
+    /// Lowercase word, if this is one.
+    fn word(&self) -> Option<String> {
+        match self {
+            Self::Word(w) => Some(w.to_lowercase()),
+            _ => None,
+        }
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self, Self::Word(w) if w.eq_ignore_ascii_case(word))
+    }
+
+    fn string(&self) -> Option<&str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+fn tokenize(text: &str, dialect: Dialect) -> Vec<Tok> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let starts_with = |i: usize, s: &str| {
+        s.chars()
+            .enumerate()
+            .all(|(k, c)| chars.get(i + k) == Some(&c))
+    };
+    let line_end = |i: usize| {
+        (i..chars.len())
+            .find(|&k| chars[k] == '\n')
+            .unwrap_or(chars.len())
+    };
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if (dialect == Dialect::Sql && starts_with(i, "--"))
+            || (dialect != Dialect::Sql && c == '#')
+        {
+            i = line_end(i);
+        } else if dialect == Dialect::Sql && starts_with(i, "/*") {
+            i = (i + 2..chars.len())
+                .find(|&k| starts_with(k, "*/"))
+                .map_or(chars.len(), |k| k + 2);
+        } else if dialect == Dialect::Ruby && (starts_with(i, "<<~") || starts_with(i, "<<-")) {
+            let (body, next) = heredoc(&chars, i + 3);
+            tokens.push(Tok::Str(body));
+            i = next;
+        } else if c == '\'' || c == '"' || c == '`' {
+            let triple = dialect == Dialect::Python && starts_with(i, &c.to_string().repeat(3));
+            let (value, next) = quoted(&chars, i, triple, dialect != Dialect::Sql);
+            let identifier = dialect == Dialect::Sql && c != '\'';
+            tokens.push(if identifier {
+                Tok::Word(value)
+            } else {
+                Tok::Str(value)
+            });
+            i = next;
+        } else if dialect == Dialect::Ruby
+            && c == ':'
+            && chars
+                .get(i + 1)
+                .is_some_and(|n| n.is_alphabetic() || *n == '_')
+            && (i == 0 || chars[i - 1] != ':')
+        {
+            let end = (i + 1..chars.len())
+                .find(|&k| !(chars[k].is_alphanumeric() || chars[k] == '_'))
+                .unwrap_or(chars.len());
+            tokens.push(Tok::Str(chars[i + 1..end].iter().collect()));
+            i = end;
+        } else if c.is_alphanumeric() || c == '_' {
+            let end = (i..chars.len())
+                .find(|&k| !(chars[k].is_alphanumeric() || chars[k] == '_'))
+                .unwrap_or(chars.len());
+            tokens.push(Tok::Word(chars[i..end].iter().collect()));
+            i = end;
+        } else {
+            tokens.push(Tok::Punct(c));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// A quoted string starting at `start`: its value and the index after it.
+/// SQL doubles the quote to escape it; the other dialects use backslashes.
+fn quoted(chars: &[char], start: usize, triple: bool, backslash: bool) -> (String, usize) {
+    let quote = chars[start];
+    let width = if triple { 3 } else { 1 };
+    let mut value = String::new();
+    let mut i = start + width;
+    while i < chars.len() {
+        let c = chars[i];
+        if backslash && c == '\\' {
+            if let Some(&next) = chars.get(i + 1) {
+                value.push(next);
+            }
+            i += 2;
+            continue;
+        }
+        if c == quote && (0..width).all(|k| chars.get(i + k) == Some(&quote)) {
+            if !backslash && !triple && chars.get(i + 1) == Some(&quote) {
+                value.push(quote);
+                i += 2;
+                continue;
+            }
+            return (value, i + width);
+        }
+        value.push(c);
+        i += 1;
+    }
+    (value, chars.len())
+}
+
+/// A Ruby `<<~ID` heredoc whose identifier starts at `start`: its body and
+/// the index after the closing line. The rest of the opening line is
+/// skipped.
+fn heredoc(chars: &[char], start: usize) -> (String, usize) {
+    let end = (start..chars.len())
+        .find(|&k| !(chars[k].is_alphanumeric() || chars[k] == '_'))
+        .unwrap_or(chars.len());
+    let id: String = chars[start..end].iter().collect();
+    let rest: String = chars[end..].iter().collect();
+    let mut offset = end + rest.find('\n').map_or(rest.len(), |n| n + 1);
+    let mut body = String::new();
+    for line in rest.lines().skip(1) {
+        offset += line.chars().count() + 1;
+        if line.trim() == id {
+            return (body, offset.min(chars.len()));
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+    (body, chars.len())
+}
+
+/// Top-level comma-separated arguments of the group opening at `open`, and
+/// the index after its closing bracket.
+fn group(tokens: &[Tok], open: usize) -> (Vec<&[Tok]>, usize) {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut start = open + 1;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Tok::Punct('(' | '[' | '{') => depth += 1,
+            Tok::Punct(')' | ']' | '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    if i > start {
+                        args.push(&tokens[start..i]);
+                    }
+                    return (args, i + 1);
+                }
+            }
+            Tok::Punct(',') if depth == 1 => {
+                args.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if tokens.len() > start {
+        args.push(&tokens[start..]);
+    }
+    (args, tokens.len())
+}
+
+/// Split a token run on top-level commas.
+fn split_commas(tokens: &[Tok]) -> Vec<&[Tok]> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Tok::Punct('(' | '[' | '{') => depth += 1,
+            Tok::Punct(')' | ']' | '}') => depth -= 1,
+            Tok::Punct(',') if depth == 0 => {
+                parts.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&tokens[start..]);
+    parts
+}
+
+// ── SQL ───────────────────────────────────────────────────────────
+
+/// Schema changes made by SQL DDL statements.
+pub fn parse_sql(text: &str) -> Vec<SchemaChange> {
+    tokenize(text, Dialect::Sql)
+        .split(|t| *t == Tok::Punct(';'))
+        .flat_map(sql_statement)
+        .collect()
+}
+
+/// Index after any of `words` at `i`.
+fn skip_words(tokens: &[Tok], mut i: usize, words: &[&str]) -> usize {
+    while tokens
+        .get(i)
+        .and_then(Tok::word)
+        .is_some_and(|w| words.contains(&w.as_str()))
+    {
+        i += 1;
+    }
+    i
+}
+
+/// A possibly schema-qualified name at `i`: its last segment, lowercase, and
+/// the index after it.
+fn sql_name(tokens: &[Tok], i: usize) -> Option<(String, usize)> {
+    let mut name = tokens.get(i)?.word()?;
+    let mut next = i + 1;
+    while tokens.get(next) == Some(&Tok::Punct('.')) {
+        let Some(part) = tokens.get(next + 1).and_then(Tok::word) else {
+            break;
+        };
+        name = part;
+        next += 2;
+    }
+    Some((name, next))
+}
+
+fn sql_statement(tokens: &[Tok]) -> Vec<SchemaChange> {
+    let Some(verb) = tokens.first().and_then(Tok::word) else {
+        return Vec::new();
+    };
+    match verb.as_str() {
+        "create" => sql_create(tokens),
+        "alter" if tokens.get(1).is_some_and(|t| t.is_word("table")) => sql_alter(tokens),
+        "drop" if tokens.get(1).is_some_and(|t| t.is_word("table")) => {
+            let i = skip_words(tokens, 2, &["if", "exists"]);
+            split_commas(&tokens[i.min(tokens.len())..])
+                .into_iter()
+                .filter_map(|part| sql_name(part, 0))
+                .map(|(table, _)| SchemaChange::new(&table, SchemaAction::DropTable))
+                .collect()
+        }
+        "rename" if tokens.get(1).is_some_and(|t| t.is_word("table")) => split_commas(&tokens[2..])
+            .into_iter()
+            .filter_map(|part| {
+                let (from, i) = sql_name(part, 0)?;
+                let (to, _) = sql_name(part, skip_words(part, i, &["to"]))?;
+                Some(SchemaChange::new(&from, SchemaAction::RenameTable(to)))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn sql_create(tokens: &[Tok]) -> Vec<SchemaChange> {
+    let i = skip_words(
+        tokens,
+        1,
+        &[
+            "or",
+            "replace",
+            "temporary",
+            "temp",
+            "unlogged",
+            "global",
+            "local",
+            "unique",
+        ],
+    );
+    match tokens.get(i).and_then(Tok::word).as_deref() {
+        Some("table") => {
+            let i = skip_words(tokens, i + 1, &["if", "not", "exists"]);
+            let Some((table, next)) = sql_name(tokens, i) else {
+                return Vec::new();
+            };
+            let mut changes = vec![SchemaChange::new(&table, SchemaAction::CreateTable)];
+            if tokens.get(next) == Some(&Tok::Punct('(')) {
+                let (defs, _) = group(tokens, next);
+                for def in defs {
+                    let Some(column) = def.first().and_then(Tok::word) else {
+                        continue;
+                    };
+                    if !CONSTRAINT_WORDS.contains(&column.as_str()) {
+                        changes.push(SchemaChange::add_column(
+                            &table,
+                            &column,
+                            sql_type(&def[1..]),
+                        ));
+                    }
+                }
+            }
+            changes
+        }
+        Some("index") => {
+            let i = skip_words(tokens, i + 1, &["concurrently", "if", "not", "exists"]);
+            let Some(on) = tokens[i.min(tokens.len())..]
+                .iter()
+                .position(|t| t.is_word("on"))
+            else {
+                return Vec::new();
+            };
+            let index = (on > 0)
+                .then(|| tokens.get(i).and_then(Tok::word))
+                .flatten()
+                .unwrap_or_default();
+            let at = skip_words(tokens, i + on + 1, &["only"]);
+            sql_name(tokens, at)
+                .map(|(table, _)| {
+                    let detail = format!("add index {index}").trim_end().to_string();
+                    SchemaChange::new(&table, SchemaAction::AlterTable(detail))
+                })
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn sql_alter(tokens: &[Tok]) -> Vec<SchemaChange> {
+    let i = skip_words(tokens, 2, &["if", "exists", "only"]);
+    let Some((table, next)) = sql_name(tokens, i) else {
+        return Vec::new();
+    };
+    split_commas(&tokens[next.min(tokens.len())..])
+        .into_iter()
+        .filter_map(|action| sql_alter_action(&table, action))
+        .collect()
+}
+
+fn sql_alter_action(table: &str, action: &[Tok]) -> Option<SchemaChange> {
+    let verb = action.first()?.word()?;
+    let word_at = |i: usize| action.get(i).and_then(Tok::word).unwrap_or_default();
+    let change = match verb.as_str() {
+        "add" | "drop" => {
+            let i = skip_words(action, 1, &["column"]);
+            if CONSTRAINT_WORDS.contains(&word_at(i).as_str()) {
+                SchemaAction::AlterTable(format!("{verb} {}", word_at(i)))
+            } else {
+                let i = skip_words(action, i, &["if", "not", "exists"]);
+                let column = word_at(i);
+                if column.is_empty() {
+                    return None;
+                }
+                if verb == "add" {
+                    SchemaAction::AddColumn {
+                        sql_type: sql_type(&action[i + 1..]),
+                        column,
+                    }
+                } else {
+                    SchemaAction::DropColumn(column)
+                }
+            }
+        }
+        "rename" => {
+            if word_at(1) == "to" || word_at(1) == "as" {
+                SchemaAction::RenameTable(sql_name(action, 2)?.0)
+            } else if word_at(1) == "constraint" || word_at(1) == "index" {
+                SchemaAction::AlterTable(format!("rename {}", word_at(1)))
+            } else {
+                let i = skip_words(action, 1, &["column"]);
+                SchemaAction::RenameColumn {
+                    from: word_at(i),
+                    to: word_at(skip_words(action, i + 1, &["to"])),
+                }
+            }
+        }
+        "alter" | "modify" => {
+            let column = word_at(skip_words(action, 1, &["column"]));
+            if column.is_empty() || CONSTRAINT_WORDS.contains(&column.as_str()) {
+                return None;
+            }
+            SchemaAction::AlterColumn(column)
+        }
+        "change" => {
+            let i = skip_words(action, 1, &["column"]);
+            let (from, to) = (word_at(i), word_at(i + 1));
+            if from == to || to.is_empty() {
+                SchemaAction::AlterColumn(from)
+            } else {
+                SchemaAction::RenameColumn { from, to }
+            }
+        }
+        other => SchemaAction::AlterTable(other.to_string()),
+    };
+    Some(SchemaChange::new(table, change))
+}
+
+/// Column type text from the tokens after a column name, up to the first
+/// constraint keyword: `varchar(255)`, `timestamp with time zone`.
+fn sql_type(tokens: &[Tok]) -> Option<String> {
+    let mut text = String::new();
+    let mut depth = 0;
+    for token in tokens {
+        match token {
+            Tok::Word(w) => {
+                let lower = w.to_lowercase();
+                if depth == 0 && TYPE_STOP_WORDS.contains(&lower.as_str()) {
+                    break;
+                }
+                if text.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    text.push(' ');
+                }
+                text.push_str(&lower);
+            }
+            Tok::Punct(c) => {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                text.push(*c);
+            }
+            Tok::Str(_) => break,
+        }
+    }
+    (!text.is_empty()).then_some(text)
+}
+
+// ── Alembic ───────────────────────────────────────────────────────
+
+/// An Alembic revision: its changes, revision id, and parent revisions.
+#[derive(Debug, Default)]
+struct AlembicRevision {
+    changes: Vec<SchemaChange>,
+    revision: Option<String>,
+    down_revisions: Vec<String>,
+}
+
+fn parse_alembic(text: &str) -> AlembicRevision {
+    let tokens = tokenize(text, Dialect::Python);
+    let mut revision = AlembicRevision::default();
+    for (i, token) in tokens.iter().enumerate() {
+        let Some(name) = token.word() else {
+            continue;
+        };
+        if name != "revision" && name != "down_revision" {
+            continue;
+        }
+        let Some(eq) = tokens[i..].iter().position(|t| *t == Tok::Punct('=')) else {
+            continue;
+        };
+        let values: Vec<String> = tokens[i + eq + 1..]
+            .iter()
+            .take_while(|t| matches!(t, Tok::Str(_) | Tok::Punct('(' | ',' | ')')))
+            .filter_map(|t| t.string().map(str::to_string))
+            .collect();
+        if name == "revision" {
+            revision.revision = revision.revision.or_else(|| values.into_iter().next());
+        } else if revision.down_revisions.is_empty() {
+            revision.down_revisions = values;
+        }
+    }
+
+    let upgrade = text.find("def upgrade").map_or("", |start| {
+        let rest = &text[start..];
+        rest.find("def downgrade").map_or(rest, |end| &rest[..end])
+    });
+    let tokens = tokenize(upgrade, Dialect::Python);
+    let mut batch: Option<String> = None;
+    let mut i = 0;
+    while i + 3 < tokens.len() {
+        let call = (&tokens[i], &tokens[i + 1], &tokens[i + 2], &tokens[i + 3]);
+        let (Tok::Word(receiver), Tok::Punct('.'), Tok::Word(function), Tok::Punct('(')) = call
+        else {
+            i += 1;
+            continue;
+        };
+        let (args, next) = group(&tokens, i + 3);
+        if function == "batch_alter_table" {
+            batch = args.first().and_then(|a| first_string(a));
+        } else if receiver == "op" {
+            revision.changes.extend(alembic_op(function, None, &args));
+        } else if let Some(table) = &batch {
+            revision
+                .changes
+                .extend(alembic_op(function, Some(table), &args));
+        }
+        i = next;
+    }
+    revision
+}
+
+/// Changes made by one `op.<function>(...)` call, or by a batch operation
+/// on `batch_table`.
+fn alembic_op(function: &str, batch_table: Option<&str>, args: &[&[Tok]]) -> Vec<SchemaChange> {
+    let positional: Vec<String> = args
+        .iter()
+        .map_while(|a| match a {
+            [Tok::Str(s)] => Some(s.clone()),
+            _ => None,
+        })
+        .collect();
+    let keyword = |name: &str| {
+        args.iter().find_map(|a| match a {
+            [Tok::Word(k), Tok::Punct('='), Tok::Str(v)] if k == name => Some(v.clone()),
+            _ => None,
+        })
+    };
+    // Batch operations take no table argument.
+    let (table, rest) = match batch_table {
+        Some(table) => (Some(table.to_string()), &positional[..]),
+        None => (
+            positional.first().cloned(),
+            positional.get(1..).unwrap_or(&[]),
+        ),
+    };
+    let column_defs = || args.iter().filter_map(|a| alembic_column(a));
+    match function {
+        "create_table" => {
+            let Some(table) = table else {
+                return Vec::new();
+            };
+            let mut changes = vec![SchemaChange::new(&table, SchemaAction::CreateTable)];
+            changes.extend(
+                column_defs().map(|(column, ty)| SchemaChange::add_column(&table, &column, ty)),
+            );
+            changes
+        }
+        "add_column" => table
+            .map(|table| {
+                column_defs()
+                    .map(|(column, ty)| SchemaChange::add_column(&table, &column, ty))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        "drop_table" => table
+            .map(|t| SchemaChange::new(&t, SchemaAction::DropTable))
+            .into_iter()
+            .collect(),
+        "rename_table" => match (table, rest.first()) {
+            (Some(from), Some(to)) => vec![SchemaChange::new(
+                &from,
+                SchemaAction::RenameTable(to.to_lowercase()),
+            )],
+            _ => Vec::new(),
+        },
+        "drop_column" | "alter_column" => {
+            let (Some(table), Some(column)) = (table, rest.first()) else {
+                return Vec::new();
+            };
+            let column = column.to_lowercase();
+            let action = match (function, keyword("new_column_name")) {
+                ("drop_column", _) => SchemaAction::DropColumn(column),
+                (_, Some(to)) => SchemaAction::RenameColumn {
+                    from: column,
+                    to: to.to_lowercase(),
+                },
+                _ => SchemaAction::AlterColumn(column),
+            };
+            vec![SchemaChange::new(&table, action)]
+        }
+        "create_index"
+        | "drop_index"
+        | "create_foreign_key"
+        | "create_unique_constraint"
+        | "create_check_constraint"
+        | "create_primary_key"
+        | "drop_constraint" => {
+            // These take a constraint or index name first, then the table.
+            let table = batch_table
+                .map(str::to_string)
+                .or_else(|| keyword("table_name"))
+                .or_else(|| positional.get(1).cloned());
+            table
+                .map(|t| {
+                    let detail = function.replace('_', " ");
+                    SchemaChange::new(&t, SchemaAction::AlterTable(detail))
+                })
+                .into_iter()
+                .collect()
+        }
+        "execute" => args
+            .first()
+            .and_then(|a| first_string(a))
+            .map(|sql| parse_sql(&sql))
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Name and type of a `sa.Column("name", sa.Type, ...)` argument.
+fn alembic_column(arg: &[Tok]) -> Option<(String, Option<String>)> {
+    let at = arg
+        .windows(2)
+        .position(|w| matches!(&w[0], Tok::Word(c) if c == "Column") && w[1] == Tok::Punct('('))?;
+    let (inner, _) = group(arg, at + 1);
+    let name = match inner.first()? {
+        [Tok::Str(name)] => name.to_lowercase(),
+        _ => return None,
+    };
+    let sql_type = inner.get(1).and_then(|ty| {
+        ty.iter()
+            .take_while(|t| !matches!(t, Tok::Punct('(')))
+            .filter_map(Tok::word)
+            .last()
+    });
+    Some((name, sql_type))
+}
+
+fn first_string(tokens: &[Tok]) -> Option<String> {
+    tokens.iter().find_map(|t| t.string().map(str::to_string))
+}
+
+// ── Rails ─────────────────────────────────────────────────────────
+
+fn parse_rails(text: &str) -> Vec<SchemaChange> {
+    let up = match text.find("def up") {
+        Some(start) => {
+            let rest = &text[start..];
+            rest.find("def down").map_or(rest, |end| &rest[..end])
+        }
+        None => text.find("def down").map_or(text, |end| &text[..end]),
+    };
+    let tokens = tokenize(up, Dialect::Ruby);
+    let mut changes = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let Some(method) = tokens[i].word() else {
+            i += 1;
+            continue;
+        };
+        if tokens.get(i.wrapping_sub(1)) == Some(&Tok::Punct('.')) {
+            i += 1;
+            continue;
+        }
+        let args = ruby_args(&tokens, i + 1);
+        match method.as_str() {
+            "create_table" | "change_table" => {
+                let Some(table) = args.first() else {
+                    i += 1;
+                    continue;
+                };
+                let creating = method == "create_table";
+                if creating {
+                    changes.push(SchemaChange::new(table, SchemaAction::CreateTable));
+                }
+                let (block, next) = rails_block(&tokens, i + 1);
+                if creating && !rails_without_id(&tokens[i + 1..next]) {
+                    changes.push(SchemaChange::add_column(
+                        table,
+                        "id",
+                        Some("primary_key".to_string()),
+                    ));
+                }
+                if let Some((var, body)) = block {
+                    changes.extend(rails_table_block(table, &var, body, creating));
+                }
+                i = next;
+                continue;
+            }
+            "execute" => {
+                if let Some(sql) = tokens.get(i + 1).and_then(Tok::string) {
+                    changes.extend(parse_sql(sql));
+                }
+            }
+            _ => changes.extend(rails_statement(&method, &args)),
+        }
+        i += 1;
+    }
+    changes
+}
+
+/// Leading string and symbol arguments of a Ruby call starting at `i`,
+/// with or without parentheses.
+fn ruby_args(tokens: &[Tok], mut i: usize) -> Vec<String> {
+    if tokens.get(i) == Some(&Tok::Punct('(')) {
+        i += 1;
+    }
+    let mut args = Vec::new();
+    while let Some(value) = tokens.get(i).and_then(Tok::string) {
+        args.push(value.to_lowercase());
+        if tokens.get(i + 1) != Some(&Tok::Punct(',')) {
+            break;
+        }
+        i += 2;
+    }
+    args
+}
+
+/// The `do |t| ... end` block after a table call starting at `i`: its
+/// variable and body, and the index after `end`.
+fn rails_block(tokens: &[Tok], i: usize) -> (Option<(String, &[Tok])>, usize) {
+    let Some(open) = tokens[i..]
+        .iter()
+        .take_while(|t| !matches!(t, Tok::Punct('\n')))
+        .position(|t| t.is_word("do"))
+        .map(|p| i + p)
+    else {
+        return (None, i);
+    };
+    let var = match (tokens.get(open + 1), tokens.get(open + 2)) {
+        (Some(Tok::Punct('|')), Some(Tok::Word(var))) => var.clone(),
+        _ => return (None, open + 1),
+    };
+    let mut depth = 1;
+    for (k, token) in tokens.iter().enumerate().skip(open + 1) {
+        if token.is_word("do") {
+            depth += 1;
+        } else if token.is_word("end") {
+            depth -= 1;
+            if depth == 0 {
+                return (Some((var, &tokens[open + 4..k])), k + 1);
+            }
+        }
+    }
+    (Some((var, &tokens[open + 4..])), tokens.len())
+}
+
+/// Whether `create_table` options disable the implicit `id` column.
+fn rails_without_id(tokens: &[Tok]) -> bool {
+    let head = tokens
+        .iter()
+        .position(|t| t.is_word("do"))
+        .map_or(tokens, |p| &tokens[..p]);
+    head.windows(3)
+        .any(|w| w[0].is_word("id") && w[1] == Tok::Punct(':') && w[2].is_word("false"))
+}
+
+/// Columns declared in a `create_table` or `change_table` block.
+fn rails_table_block(table: &str, var: &str, body: &[Tok], creating: bool) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for (k, token) in body.iter().enumerate() {
+        let (Tok::Word(receiver), Some(Tok::Punct('.')), Some(Tok::Word(method))) =
+            (token, body.get(k + 1), body.get(k + 2))
+        else {
+            continue;
+        };
+        if receiver != var {
+            continue;
+        }
+        let args = ruby_args(body, k + 3);
+        let action = |column: String| match method.as_str() {
+            "remove" => SchemaAction::DropColumn(column),
+            "change" | "change_default" | "change_null" => SchemaAction::AlterColumn(column),
+            "remove_references" | "remove_belongs_to" => {
+                SchemaAction::DropColumn(format!("{column}_id"))
+            }
+            "references" | "belongs_to" => SchemaAction::AddColumn {
+                column: format!("{column}_id"),
+                sql_type: Some("references".to_string()),
+            },
+            "column" => SchemaAction::AddColumn {
+                column,
+                sql_type: args.get(1).cloned(),
+            },
+            other => SchemaAction::AddColumn {
+                column,
+                sql_type: Some(other.to_string()),
+            },
+        };
+        match method.as_str() {
+            "timestamps" => {
+                for column in ["created_at", "updated_at"] {
+                    changes.push(SchemaChange::add_column(
+                        table,
+                        column,
+                        Some("datetime".to_string()),
+                    ));
+                }
+            }
+            "index" | "remove_index" | "foreign_key" | "check_constraint" => {
+                if !creating {
+                    changes.push(SchemaChange::new(
+                        table,
+                        SchemaAction::AlterTable(method.replace('_', " ")),
+                    ));
+                }
+            }
+            "rename" => {
+                if let [from, to, ..] = &args[..] {
+                    changes.push(SchemaChange::new(
+                        table,
+                        SchemaAction::RenameColumn {
+                            from: from.clone(),
+                            to: to.clone(),
+                        },
+                    ));
+                }
+            }
+            "column" => {
+                if let Some(column) = args.first() {
+                    changes.push(SchemaChange::new(table, action(column.clone())));
+                }
+            }
+            _ => {
+                for column in &args {
+                    changes.push(SchemaChange::new(table, action(column.clone())));
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Changes made by a top-level schema statement in a Rails migration.
+fn rails_statement(method: &str, args: &[String]) -> Vec<SchemaChange> {
+    let (Some(table), rest) = (args.first(), args.get(1..).unwrap_or(&[])) else {
+        return Vec::new();
+    };
+    let single = |action: SchemaAction| vec![SchemaChange::new(table, action)];
+    match (method, rest) {
+        ("add_column", [column, ty, ..]) => {
+            vec![SchemaChange::add_column(table, column, Some(ty.clone()))]
+        }
+        ("add_column", [column]) => vec![SchemaChange::add_column(table, column, None)],
+        ("remove_column" | "remove_columns", columns) => columns
+            .iter()
+            .take(if method == "remove_column" {
+                1
+            } else {
+                usize::MAX
+            })
+            .map(|c| SchemaChange::new(table, SchemaAction::DropColumn(c.clone())))
+            .collect(),
+        ("rename_column", [from, to, ..]) => single(SchemaAction::RenameColumn {
+            from: from.clone(),
+            to: to.clone(),
+        }),
+        (
+            "change_column"
+            | "change_column_null"
+            | "change_column_default"
+            | "change_column_comment",
+            [column, ..],
+        ) => single(SchemaAction::AlterColumn(column.clone())),
+        ("drop_table", _) => single(SchemaAction::DropTable),
+        ("rename_table", [to, ..]) => single(SchemaAction::RenameTable(to.clone())),
+        ("add_reference" | "add_belongs_to", [reference, ..]) => vec![SchemaChange::add_column(
+            table,
+            &format!("{reference}_id"),
+            Some("references".to_string()),
+        )],
+        ("remove_reference" | "remove_belongs_to", [reference, ..]) => {
+            single(SchemaAction::DropColumn(format!("{reference}_id")))
+        }
+        ("add_timestamps", _) => ["created_at", "updated_at"]
+            .iter()
+            .map(|c| SchemaChange::add_column(table, c, Some("datetime".to_string())))
+            .collect(),
+        ("remove_timestamps", _) => ["created_at", "updated_at"]
+            .iter()
+            .map(|c| SchemaChange::new(table, SchemaAction::DropColumn((*c).to_string())))
+            .collect(),
+        (
+            "add_index"
+            | "remove_index"
+            | "add_foreign_key"
+            | "remove_foreign_key"
+            | "add_check_constraint"
+            | "remove_check_constraint",
+            _,
+        ) => single(SchemaAction::AlterTable(method.replace('_', " "))),
+        _ => Vec::new(),
+    }
+}
+
+// ── Migrations ────────────────────────────────────────────────────
+
+/// One migration file and the changes it makes, in order: plain SQL
+/// (including Diesel `up.sql` and Flyway `V<version>__<name>.sql`), an
+/// Alembic revision, or a Rails migration. Only the upgrade half is read.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    /// Repo-relative path.
+    pub file: String,
+    /// Version shown in history: the file-name version, or the Alembic
+    /// revision id.
+    pub version: String,
+    /// Numeric sort key.
+    order: Vec<u64>,
+    pub changes: Vec<SchemaChange>,
+}
+
+/// Version and sort key from a migration's file name: a Flyway
+/// `V<version>__` prefix, a leading timestamp or counter (the directory's
+/// for Diesel `up.sql`), or nothing. `None` for migrations that undo others.
+fn migration_version(path: &str) -> Option<(String, Vec<u64>)> {
+    let path = Path::new(path);
+    let file = path.file_name()?.to_str()?;
+    if file == "down.sql" || (file.starts_with('U') && file.contains("__")) {
+        return None;
+    }
+    let stem = if file == "up.sql" {
+        path.parent()?.file_name()?.to_str()?
+    } else {
+        path.file_stem()?.to_str()?
+    };
+    let version = if let Some(rest) = stem.strip_prefix('V').filter(|_| stem.contains("__")) {
+        rest.split("__").next().unwrap_or_default()
+    } else if stem.starts_with("R__") {
+        return Some(("R".to_string(), vec![u64::MAX]));
+    } else {
+        let end = stem
+            .find(|c: char| !(c.is_ascii_digit() || c == '-' || c == '_'))
+            .unwrap_or(stem.len());
+        stem[..end].trim_end_matches(['-', '_'])
+    };
+    let order = version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect();
+    let label = if version.is_empty() { stem } else { version };
+    Some((label.to_string(), order))
+}
+
+/// Parse every migration and sort them into replay order: file-name
+/// versions for SQL and Rails, the `down_revision` chain for Alembic.
+fn load_migrations(files: &[(String, String)]) -> Vec<Migration> {
+    let mut migrations = Vec::new();
+    let mut alembic: Vec<(usize, Option<String>, Vec<String>)> = Vec::new();
+    for (path, text) in files {
+        let Some((version, order)) = migration_version(path) else {
+            continue;
+        };
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        let changes = match extension.as_str() {
+            "sql" => parse_sql(text),
+            "rb" => parse_rails(text),
+            "py" if text.contains("op.") => {
+                let revision = parse_alembic(text);
+                alembic.push((migrations.len(), revision.revision, revision.down_revisions));
+                revision.changes
+            }
+            _ => continue,
+        };
+        migrations.push(Migration {
+            file: path.clone(),
+            version,
+            order,
+            changes,
+        });
+    }
+
+    // Alembic files are ordered by their depth in the revision chain.
+    let parents: HashMap<&str, &[String]> = alembic
+        .iter()
+        .filter_map(|(_, rev, down)| Some((rev.as_deref()?, down.as_slice())))
+        .collect();
+    for (index, revision, _) in &alembic {
+        if let Some(revision) = revision {
+            let depth = revision_depth(revision, &parents, &mut HashSet::new());
+            migrations[*index].order = vec![depth];
+            migrations[*index].version.clone_from(revision);
+        }
+    }
+    migrations.sort_by(|a, b| a.order.cmp(&b.order).then_with(|| a.file.cmp(&b.file)));
+    migrations
+}
+
+fn revision_depth<'a>(
+    revision: &'a str,
+    parents: &HashMap<&'a str, &'a [String]>,
+    seen: &mut HashSet<&'a str>,
+) -> u64 {
+    if !seen.insert(revision) {
+        return 0;
+    }
+    parents.get(revision).map_or(0, |down| {
+        down.iter()
+            .map(|d| 1 + revision_depth(d, parents, seen))
+            .max()
+            .unwrap_or(0)
+    })
+}
+
+// ── Replay ────────────────────────────────────────────────────────
+
+/// One migration's change to a table or column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaEvent {
+    pub version: String,
+    pub file: String,
+    pub change: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ColumnSchema {
+    pub sql_type: Option<String>,
+    pub history: Vec<SchemaEvent>,
+}
+
+/// A table as the migrations leave it.
+#[derive(Debug, Clone, Default)]
+pub struct TableSchema {
+    pub columns: BTreeMap<String, ColumnSchema>,
+    pub history: Vec<SchemaEvent>,
+}
+
+impl TableSchema {
+    /// Distinct migrations that changed the table or its columns.
+    pub fn schema_changes(&self) -> usize {
+        self.history
+            .iter()
+            .chain(self.columns.values().flat_map(|c| &c.history))
+            .map(|e| e.file.as_str())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+}
+
+/// Current tables after applying every migration in order, each table and
+/// column with the migrations that created, altered, or renamed it. Dropped
+/// tables and columns disappear, and renames carry history across.
+pub fn replay(migrations: &[Migration]) -> BTreeMap<String, TableSchema> {
+    let mut tables: BTreeMap<String, TableSchema> = BTreeMap::new();
+    for migration in migrations {
+        let event = |change: String| SchemaEvent {
+            version: migration.version.clone(),
+            file: migration.file.clone(),
+            change,
+        };
+        for SchemaChange {
+            table: name,
+            action,
+        } in &migration.changes
+        {
+            match action {
+                SchemaAction::CreateTable => {
+                    let table = tables.entry(name.clone()).or_default();
+                    table.history.push(event("create table".to_string()));
+                }
+                SchemaAction::DropTable => {
+                    tables.remove(name);
+                }
+                SchemaAction::RenameTable(to) => {
+                    let mut table = tables.remove(name).unwrap_or_default();
+                    table
+                        .history
+                        .push(event(format!("rename table from {name}")));
+                    tables.insert(to.clone(), table);
+                }
+                SchemaAction::AlterTable(detail) => {
+                    let table = tables.entry(name.clone()).or_default();
+                    table.history.push(event(detail.clone()));
+                }
+                SchemaAction::AddColumn { column, sql_type } => {
+                    let table = tables.entry(name.clone()).or_default();
+                    // Columns created with their table need no table entry.
+                    let with_table = table
+                        .history
+                        .last()
+                        .is_some_and(|e| e.file == migration.file && e.change == "create table");
+                    if !with_table {
+                        table.history.push(event(format!("add column {column}")));
+                    }
+                    let entry = table.columns.entry(column.clone()).or_default();
+                    if sql_type.is_some() {
+                        entry.sql_type.clone_from(sql_type);
+                    }
+                    let change = if with_table {
+                        "create table"
+                    } else {
+                        "add column"
+                    };
+                    entry.history.push(event(change.to_string()));
+                }
+                SchemaAction::DropColumn(column) => {
+                    let table = tables.entry(name.clone()).or_default();
+                    table.columns.remove(column);
+                    table.history.push(event(format!("drop column {column}")));
+                }
+                SchemaAction::RenameColumn { from, to } => {
+                    let table = tables.entry(name.clone()).or_default();
+                    let mut column = table.columns.remove(from).unwrap_or_default();
+                    column
+                        .history
+                        .push(event(format!("rename column from {from}")));
+                    table.columns.insert(to.clone(), column);
+                    table
+                        .history
+                        .push(event(format!("rename column {from} to {to}")));
+                }
+                SchemaAction::AlterColumn(column) => {
+                    let table = tables.entry(name.clone()).or_default();
+                    table
+                        .columns
+                        .entry(column.clone())
+                        .or_default()
+                        .history
+                        .push(event("alter column".to_string()));
+                    table.history.push(event(format!("alter column {column}")));
+                }
+            }
+        }
+    }
+    tables
+}
+
+// ── Code references ───────────────────────────────────────────────
+//
+// SQL literals and ORM models are found with tree-sitter by
+// `homer_graphs::data_access`; a model without an explicit table maps to
+// the one its type name implies (`User` → `users`).
+
+/// Tables and columns a SQL statement refers to, limited to `tables`: the
+/// tables after `FROM`, `JOIN`, `INTO`, `UPDATE` and `TABLE`, and the words
+/// naming a column of one of them, directly or through `alias.column`.
+pub fn sql_references(
+    sql: &str,
+    tables: &BTreeMap<String, TableSchema>,
+) -> Vec<(String, Option<String>)> {
+    let tokens = tokenize(sql, Dialect::Sql);
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut used: BTreeSet<String> = BTreeSet::new();
+    let mut consumed = HashSet::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let after_keyword = tokens[i]
+            .word()
+            .is_some_and(|w| TABLE_KEYWORDS.contains(&w.as_str()));
+        i += 1;
+        if !after_keyword {
+            continue;
+        }
+        // `FROM a x, b y` lists several tables.
+        loop {
+            let at = skip_words(&tokens, i, &["only", "lateral", "if", "exists"]);
+            let Some((table, next)) = sql_name(&tokens, at) else {
+                break;
+            };
+            if !tables.contains_key(&table) {
+                break;
+            }
+            consumed.extend(at..next);
+            used.insert(table.clone());
+            aliases.insert(table.clone(), table.clone());
+            let mut j = skip_words(&tokens, next, &["as"]);
+            if let Some(alias) = tokens.get(j).and_then(Tok::word) {
+                if !SQL_KEYWORDS.contains(&alias.as_str()) {
+                    consumed.insert(j);
+                    aliases.insert(alias, table);
+                    j += 1;
+                }
+            }
+            i = j;
+            if tokens.get(i) != Some(&Tok::Punct(',')) {
+                break;
+            }
+            i += 1;
+        }
+    }
+
+    let mut found: BTreeSet<(String, Option<String>)> =
+        used.iter().map(|t| (t.clone(), None)).collect();
+    for (k, token) in tokens.iter().enumerate() {
+        let Some(word) = token.word() else {
+            continue;
+        };
+        if consumed.contains(&k) || tokens.get(k + 1) == Some(&Tok::Punct('.')) {
+            continue;
+        }
+        let qualifier = (k >= 2 && tokens[k - 1] == Tok::Punct('.'))
+            .then(|| tokens[k - 2].word())
+            .flatten();
+        let candidates: Vec<&String> = match qualifier {
+            Some(q) => aliases.get(&q).into_iter().collect(),
+            None => used.iter().collect(),
+        };
+        for table in candidates {
+            if tables[table].columns.contains_key(&word) {
+                found.insert((table.clone(), Some(word.clone())));
+            }
+        }
+    }
+    found.into_iter().collect()
+}
+
+/// Table an ORM model without an explicit table maps to: its type name in
+/// snake case, pluralized or not, or a Django `<app>_<model>` table.
+fn infer_table(model: &str, tables: &BTreeMap<String, TableSchema>) -> Option<String> {
+    let snake = to_snake(model);
+    let plural =
+        if snake.ends_with(['s', 'x', 'z']) || snake.ends_with("ch") || snake.ends_with("sh") {
+            format!("{snake}es")
+        } else if let Some(stem) = snake
+            .strip_suffix('y')
+            .filter(|s| !s.ends_with(['a', 'e', 'i', 'o', 'u']))
+        {
+            format!("{stem}ies")
+        } else {
+            format!("{snake}s")
+        };
+    [plural, snake.clone()]
+        .into_iter()
+        .find(|t| tables.contains_key(t))
+        .or_else(|| {
+            let suffix = format!("_{snake}");
+            let mut matches = tables.keys().filter(|t| t.ends_with(&suffix));
+            let only = matches.next()?;
+            matches.next().is_none().then(|| only.clone())
+        })
+}
+
+fn to_snake(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+#[derive(Debug)]
+pub struct SchemaExtractor {
+    repo_path: PathBuf,
+    settings: SchemaExtractionConfig,
+    exclude: Vec<String>,
+}
+
+impl SchemaExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.schema.clone(),
+            exclude: config.extraction.structure.exclude_patterns.clone(),
+        }
+    }
+
+    /// Every migration matching the configured globs and not excluded:
+    /// repo-relative path and contents, sorted by path.
+    fn migration_files(&self) -> Vec<(String, String)> {
+        if !self.settings.enabled {
+            return Vec::new();
+        }
+        let exclude: Vec<Pattern> = self
+            .exclude
+            .iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .collect();
+        let root = Pattern::escape(&self.repo_path.to_string_lossy());
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        for pattern in &self.settings.migrations {
+            let Ok(paths) = glob::glob(&format!("{root}/{pattern}")) else {
+                continue;
+            };
+            for path in paths.flatten() {
+                let relative = path
+                    .strip_prefix(&self.repo_path)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                if exclude.iter().any(|p| p.matches(&relative)) || !seen.insert(relative.clone()) {
+                    continue;
+                }
+                if let Ok(text) = std::fs::read_to_string(&path) {
+                    found.push((relative, text));
+                }
+            }
+        }
+        found.sort();
+        found
+    }
+
+    /// Migrations plus the graph checkpoint, since accesses follow the code.
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let graph = store
+            .get_checkpoint("graph_last_sha")
+            .await?
+            .unwrap_or_default();
+        let mut hasher = DefaultHasher::new();
+        self.migration_files().hash(&mut hasher);
+        Ok(format!("{graph}:{:x}", hasher.finish()))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for SchemaExtractor {
+    fn name(&self) -> &'static str {
+        "schema"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "schema_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "schema_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        store.delete_edges_by_kind(HyperedgeKind::Accesses).await?;
+
+        let files = self.migration_files();
+        let migrations = load_migrations(&files);
+        let tables = replay(&migrations);
+        let ids = store_tables(store, &tables, &mut stats).await?;
+        if !tables.is_empty() {
+            stats.edges_created += self.link_code(store, &tables, &ids).await?;
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("schema_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            migrations = migrations.len(),
+            tables = tables.len(),
+            edges = stats.edges_created,
+            duration = ?stats.duration,
+            "Schema extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+impl SchemaExtractor {
+    /// `Accesses` edges from code to the tables and columns its SQL literals
+    /// name, and from ORM models to their tables.
+    async fn link_code(
+        &self,
+        store: &dyn HomerStore,
+        tables: &BTreeMap<String, TableSchema>,
+        ids: &HashMap<String, NodeId>,
+    ) -> crate::error::Result<u64> {
+        let registry = LanguageRegistry::new();
+        let mut linked: HashSet<(NodeId, NodeId)> = HashSet::new();
+        let mut edges = 0;
+        for file in store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::File),
+                ..Default::default()
+            })
+            .await?
+        {
+            let Some(lang) = registry.for_file(Path::new(&file.name)) else {
+                continue;
+            };
+            let Ok(source) = std::fs::read_to_string(self.repo_path.join(&file.name)) else {
+                continue;
+            };
+            let lower = source.to_lowercase();
+            let names_table = tables.keys().any(|t| lower.contains(t.as_str()));
+            let maps_table = data_access::ORM_MARKERS.iter().any(|m| source.contains(m));
+            if !names_table && !maps_table {
+                continue;
+            }
+            let mut parser = tree_sitter::Parser::new();
+            if parser.set_language(&lang.tree_sitter_language()).is_err() {
+                continue;
+            }
+            let Some(tree) = parser.parse(&source, None) else {
+                continue;
+            };
+
+            let mut accesses: Vec<(NodeId, String, f64, Value)> = Vec::new();
+            let spans = function_spans(store, &file.name).await?;
+            for literal in data_access::sql_literals(&tree, &source) {
+                let row = literal.span.start_row;
+                let code = spans
+                    .iter()
+                    .filter(|(_, start, end)| (*start..=*end).contains(&row))
+                    .min_by_key(|(_, start, end)| end - start)
+                    .map_or(file.id, |(f, _, _)| *f);
+                for (table, column) in sql_references(&literal.text, tables) {
+                    let confidence = if column.is_some() { 0.8 } else { 0.9 };
+                    let target = column.map_or(table.clone(), |c| format!("{table}.{c}"));
+                    let metadata = serde_json::json!({ "via": "sql", "line": row + 1 });
+                    accesses.push((code, target, confidence, metadata));
+                }
+            }
+            for model in data_access::orm_models(&tree, &source, lang.id()) {
+                let explicit = model
+                    .table
+                    .as_deref()
+                    .map(str::to_lowercase)
+                    .filter(|t| tables.contains_key(t));
+                let confidence = if explicit.is_some() { 0.9 } else { 0.7 };
+                let Some(table) = explicit.or_else(|| infer_table(&model.name, tables)) else {
+                    continue;
+                };
+                let code = model_node(store, &file, &model).await?;
+                let metadata = serde_json::json!({ "via": "model", "framework": model.framework });
+                accesses.push((code, table, confidence, metadata));
+            }
+
+            for (code, target, confidence, metadata) in accesses {
+                let Some(&id) = ids.get(&target) else {
+                    continue;
+                };
+                if linked.insert((code, id)) {
+                    link(
+                        store,
+                        HyperedgeKind::Accesses,
+                        (code, roles::ACCESSOR),
+                        (id, roles::ACCESSED),
+                        confidence,
+                        metadata,
+                    )
+                    .await?;
+                    edges += 1;
+                }
+            }
+        }
+        Ok(edges)
+    }
+}
+
+/// The `Type` node for an ORM model, preferring one declared on the same
+/// row; the file when the graph has none.
+async fn model_node(
+    store: &dyn HomerStore,
+    file: &Node,
+    model: &data_access::OrmModel,
+) -> crate::error::Result<NodeId> {
+    let types = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Type),
+            name_prefix: Some(format!("{}::", file.name)),
+            ..Default::default()
+        })
+        .await?;
+    let named: Vec<&Node> = types
+        .iter()
+        .filter(|t| {
+            let simple = t.name.rsplit("::").next().unwrap_or(&t.name);
+            simple.rsplit('.').next() == Some(model.name.as_str())
+        })
+        .collect();
+    let same_row = named.iter().find(|t| {
+        t.metadata
+            .get("span")
+            .and_then(|s| s.get("start_row"))
+            .and_then(Value::as_u64)
+            .is_some_and(|r| usize::try_from(r).ok() == Some(model.span.start_row))
+    });
+    Ok(same_row.or_else(|| named.first()).map_or(file.id, |t| t.id))
+}
+
+/// Upsert a node per table and column and mark vanished ones stale. Returns
+/// ids keyed by `table` and `table.column`.
+async fn store_tables(
+    store: &dyn HomerStore,
+    tables: &BTreeMap<String, TableSchema>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<HashMap<String, NodeId>> {
+    let mut existing: HashMap<String, NodeId> = HashMap::new();
+    for kind in [NodeKind::Table, NodeKind::Column] {
+        for node in store
+            .find_nodes(&NodeFilter {
+                kind: Some(kind),
+                ..Default::default()
+            })
+            .await?
+        {
+            existing.insert(node.name, node.id);
+        }
+    }
+    let recent = |history: &[SchemaEvent]| {
+        let skip = history.len().saturating_sub(MAX_HISTORY);
+        serde_json::to_value(&history[skip..]).unwrap_or_default()
+    };
+    let mut ids = HashMap::new();
+    for (name, table) in tables {
+        let mut metadata = HashMap::from([
+            (
+                "columns".to_string(),
+                serde_json::json!(table.columns.keys().collect::<Vec<_>>()),
+            ),
+            (
+                "schema_changes".to_string(),
+                Value::from(table.schema_changes()),
+            ),
+            ("history".to_string(), recent(&table.history)),
+        ]);
+        if let Some(first) = table.history.first() {
+            metadata.insert(
+                "created_in".to_string(),
+                Value::from(first.version.as_str()),
+            );
+        }
+        if let Some(last) = table.history.last() {
+            metadata.insert(
+                "last_changed".to_string(),
+                Value::from(last.version.as_str()),
+            );
+        }
+        let id = upsert(store, NodeKind::Table, name, metadata, &existing, stats).await?;
+        ids.insert(name.clone(), id);
+
+        for (column_name, column) in &table.columns {
+            let qualified = format!("{name}.{column_name}");
+            let mut metadata = HashMap::from([
+                ("table".to_string(), Value::from(name.as_str())),
+                ("history".to_string(), recent(&column.history)),
+            ]);
+            if let Some(ty) = &column.sql_type {
+                metadata.insert("type".to_string(), Value::from(ty.as_str()));
+            }
+            if let Some(first) = column.history.first() {
+                metadata.insert(
+                    "created_in".to_string(),
+                    Value::from(first.version.as_str()),
+                );
+            }
+            let id = upsert(
+                store,
+                NodeKind::Column,
+                &qualified,
+                metadata,
+                &existing,
+                stats,
+            )
+            .await?;
+            ids.insert(qualified, id);
+        }
+    }
+    for (name, id) in existing {
+        if !ids.contains_key(&name) {
+            store.mark_node_stale(id).await?;
+        }
+    }
+    Ok(ids)
+}
+
+async fn upsert(
+    store: &dyn HomerStore,
+    kind: NodeKind,
+    name: &str,
+    metadata: HashMap<String, Value>,
+    existing: &HashMap<String, NodeId>,
+    stats: &mut ExtractStats,
+) -> crate::error::Result<NodeId> {
+    let id = store
+        .upsert_node(&Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata,
+        })
+        .await?;
+    if existing.contains_key(name) {
+        stats.nodes_updated += 1;
+    } else {
+        stats.nodes_created += 1;
+    }
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migration(file: &str, text: &str) -> (String, String) {
+        (file.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn sql_ddl_changes() {
+        let changes = parse_sql(
+            "-- users\n\
+             CREATE TABLE IF NOT EXISTS public.\"Users\" (\n\
+             \x20 id BIGSERIAL PRIMARY KEY,\n\
+             \x20 email VARCHAR(255) NOT NULL,\n\
+             \x20 created_at timestamp with time zone DEFAULT now(),\n\
+             \x20 CONSTRAINT users_email UNIQUE (email)\n\
+             );\n\
+             CREATE UNIQUE INDEX idx_email ON users (email);\n\
+             ALTER TABLE users ADD COLUMN name text, DROP COLUMN created_at,\n\
+             \x20 RENAME COLUMN email TO mail;\n\
+             ALTER TABLE users RENAME TO accounts;\n\
+             /* gone */ DROP TABLE IF EXISTS old, older;\n\
+             INSERT INTO accounts VALUES (1, 'x;y');\n",
+        );
+        let users = |action| SchemaChange::new("users", action);
+        assert_eq!(
+            changes,
+            vec![
+                users(SchemaAction::CreateTable),
+                SchemaChange::add_column("users", "id", Some("bigserial".to_string())),
+                SchemaChange::add_column("users", "email", Some("varchar(255)".to_string())),
+                SchemaChange::add_column(
+                    "users",
+                    "created_at",
+                    Some("timestamp with time zone".to_string())
+                ),
+                users(SchemaAction::AlterTable("add index idx_email".to_string())),
+                SchemaChange::add_column("users", "name", Some("text".to_string())),
+                users(SchemaAction::DropColumn("created_at".to_string())),
+                users(SchemaAction::RenameColumn {
+                    from: "email".to_string(),
+                    to: "mail".to_string()
+                }),
+                users(SchemaAction::RenameTable("accounts".to_string())),
+                SchemaChange::new("old", SchemaAction::DropTable),
+                SchemaChange::new("older", SchemaAction::DropTable),
+            ]
+        );
+    }
+
+    #[test]
+    fn alembic_and_rails_migrations_replay_in_order() {
+        let files = vec![
+            migration(
+                "alembic/versions/b2_add_team.py",
+                "revision = 'b2'\ndown_revision = 'a1'\n\n\
+                 def upgrade():\n\
+                 \x20   op.add_column('users', sa.Column('team', sa.String(40)))\n\
+                 \x20   with op.batch_alter_table('users') as batch:\n\
+                 \x20       batch.alter_column('name', new_column_name='full_name')\n\n\
+                 def downgrade():\n\
+                 \x20   op.drop_column('users', 'team')\n",
+            ),
+            migration(
+                "alembic/versions/a1_create_users.py",
+                "\"\"\"create users\"\"\"\nrevision = 'a1'\ndown_revision = None\n\n\
+                 def upgrade():\n\
+                 \x20   op.create_table('users',\n\
+                 \x20       sa.Column('id', sa.Integer(), primary_key=True),\n\
+                 \x20       sa.Column('name', sa.String(50)),\n\
+                 \x20   )\n\
+                 \x20   op.create_index('ix_name', 'users', ['name'])\n\n\
+                 def downgrade():\n\
+                 \x20   op.drop_table('users')\n",
+            ),
+            migration(
+                "db/migrate/20240102000000_create_posts.rb",
+                "class CreatePosts < ActiveRecord::Migration[7.1]\n\
+                 \x20 def change\n\
+                 \x20   create_table :posts do |t|\n\
+                 \x20     t.string :title, null: false\n\
+                 \x20     t.references :user, foreign_key: true\n\
+                 \x20     t.timestamps\n\
+                 \x20   end\n\
+                 \x20 end\n\
+                 end\n",
+            ),
+            migration(
+                "db/migrate/20240301000000_tweak_posts.rb",
+                "class TweakPosts < ActiveRecord::Migration[7.1]\n\
+                 \x20 def up\n\
+                 \x20   rename_column :posts, :title, :headline\n\
+                 \x20   add_index :posts, :headline\n\
+                 \x20   execute <<~SQL\n\
+                 \x20     ALTER TABLE posts ADD COLUMN slug text;\n\
+                 \x20   SQL\n\
+                 \x20 end\n\
+                 \x20 def down\n\
+                 \x20   remove_column :posts, :slug\n\
+                 \x20 end\n\
+                 end\n",
+            ),
+            migration("migrations/2024_01_create/down.sql", "DROP TABLE users;"),
+        ];
+        let migrations = load_migrations(&files);
+        let order: Vec<&str> = migrations.iter().map(|m| m.version.as_str()).collect();
+        assert_eq!(order, vec!["a1", "b2", "20240102000000", "20240301000000"]);
+
+        let tables = replay(&migrations);
+        let users = &tables["users"];
+        assert_eq!(
+            users.columns.keys().collect::<Vec<_>>(),
+            vec!["full_name", "id", "team"]
+        );
+        assert_eq!(users.columns["team"].sql_type.as_deref(), Some("string"));
+        assert_eq!(users.columns["full_name"].history.len(), 2);
+        assert_eq!(users.schema_changes(), 2);
+        let changes: Vec<&str> = users.history.iter().map(|e| e.change.as_str()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "create table",
+                "create index",
+                "add column team",
+                "rename column name to full_name"
+            ]
+        );
+
+        let posts = &tables["posts"];
+        assert_eq!(
+            posts.columns.keys().collect::<Vec<_>>(),
+            vec![
+                "created_at",
+                "headline",
+                "id",
+                "slug",
+                "updated_at",
+                "user_id"
+            ]
+        );
+        assert_eq!(posts.schema_changes(), 2);
+    }
+
+    #[test]
+    fn sql_references_resolve_aliases() {
+        let tables = replay(&load_migrations(&[migration(
+            "migrations/V1__init.sql",
+            "CREATE TABLE users (id int, email text);\n\
+             CREATE TABLE orders (id int, user_id int, total int);",
+        )]));
+        let refs = sql_references(
+            "SELECT u.email, o.total FROM users u JOIN orders AS o ON o.user_id = u.id \
+             WHERE status = 'paid'",
+            &tables,
+        );
+        let names: Vec<String> = refs
+            .iter()
+            .map(|(t, c)| c.as_ref().map_or(t.clone(), |c| format!("{t}.{c}")))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "orders",
+                "orders.total",
+                "orders.user_id",
+                "users",
+                "users.email",
+                "users.id"
+            ]
+        );
+        assert!(sql_references("SELECT 1 FROM audit_log", &tables).is_empty());
+        assert_eq!(infer_table("User", &tables).as_deref(), Some("users"));
+        assert_eq!(infer_table("Order", &tables).as_deref(), Some("orders"));
+        assert_eq!(infer_table("Invoice", &tables), None);
+    }
+
+    #[tokio::test]
+    async fn links_sql_and_models_to_tables() {
+        use crate::store::sqlite::SqliteStore;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("migrations")).unwrap();
+        std::fs::write(
+            tmp.path().join("migrations/001_users.sql"),
+            "CREATE TABLE users (id integer, email text);",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("app")).unwrap();
+        std::fs::write(
+            tmp.path().join("app/repo.py"),
+            "class User(Base):\n    __tablename__ = \"users\"\n\n\
+             def find(db, email):\n    return db.execute(\"SELECT id FROM users WHERE email = ?\", email)\n",
+        )
+        .unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        let node = |kind, name: &str, metadata: Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        store
+            .upsert_node(&node(
+                NodeKind::File,
+                "app/repo.py",
+                serde_json::json!({ "language": "python" }),
+            ))
+            .await
+            .unwrap();
+        let model = store
+            .upsert_node(&node(
+                NodeKind::Type,
+                "app/repo.py::User",
+                serde_json::json!({ "span": { "start_row": 0, "end_row": 1 } }),
+            ))
+            .await
+            .unwrap();
+        let find = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "app/repo.py::find",
+                serde_json::json!({ "span": { "start_row": 3, "end_row": 4 } }),
+            ))
+            .await
+            .unwrap();
+
+        let extractor = SchemaExtractor::new(tmp.path(), &HomerConfig::default());
+        assert!(extractor.has_work(&store).await.unwrap());
+        extractor
+            .extract(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        let table = store
+            .get_node_by_name(NodeKind::Table, "users")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(table.metadata["created_in"], "001");
+        assert_eq!(table.metadata["schema_changes"], 1);
+        let column = store
+            .get_node_by_name(NodeKind::Column, "users.email")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(column.metadata["type"], "text");
+
+        let mut pairs: Vec<(NodeId, NodeId)> = store
+            .get_edges_by_kind(HyperedgeKind::Accesses)
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| {
+                let member = |role| e.members.iter().find(|m| m.role == role).unwrap().node_id;
+                (member(roles::ACCESSOR), member(roles::ACCESSED))
+            })
+            .collect();
+        pairs.sort_by_key(|(a, b)| (a.0, b.0));
+        let id_column = store
+            .get_node_by_name(NodeKind::Column, "users.id")
+            .await
+            .unwrap()
+            .unwrap()
+            .id;
+        let mut expected = vec![
+            (model, table.id),
+            (find, table.id),
+            (find, id_column),
+            (find, column.id),
+        ];
+        expected.sort_by_key(|(a, b)| (a.0, b.0));
+        assert_eq!(pairs, expected);
+    }
+}
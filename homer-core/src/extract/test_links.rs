@@ -0,0 +1,385 @@
+// Test-to-code linkage: `Tests` edges from test functions to the production
+// code they reach through the call graph.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::analyze::centrality::is_test_file;
+use crate::contracts::{self, roles};
+use crate::store::HomerStore;
+use crate::types::{
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, NodeFilter, NodeId, NodeKind,
+};
+
+/// Deepest call chain followed from a test.
+const MAX_DEPTH: u32 = 6;
+
+/// Confidence lost per call hop between a test and the function it reaches.
+const DEPTH_DECAY: f64 = 0.9;
+
+struct Function {
+    file: String,
+    simple_name: String,
+    /// Framework and called names, for test functions.
+    test: Option<(String, Vec<String>)>,
+    production: bool,
+}
+
+/// Rebuild all `Tests` edges from the current call graph. Returns the number
+/// of edges created.
+///
+/// Graph extraction marks test functions with `test` metadata holding the
+/// framework and the names the test calls. Each test is linked to every
+/// production function it reaches through `Calls` edges, up to `MAX_DEPTH`
+/// hops, with confidence decaying per hop.
+pub(crate) async fn link_tests(store: &dyn HomerStore) -> crate::error::Result<u64> {
+    store.delete_edges_by_kind(HyperedgeKind::Tests).await?;
+
+    let functions = load_functions(store).await?;
+    if functions.values().all(|f| f.test.is_none()) {
+        return Ok(0);
+    }
+
+    let mut by_name: HashMap<&str, Vec<NodeId>> = HashMap::new();
+    for (&id, func) in &functions {
+        by_name
+            .entry(func.simple_name.as_str())
+            .or_default()
+            .push(id);
+    }
+
+    let mut calls: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Calls).await? {
+        let src = contracts::find_member_by_roles(&edge.members, &[roles::CALLER]);
+        let dst = contracts::find_member_by_roles(&edge.members, &[roles::CALLEE]);
+        if let (Some(src), Some(dst)) = (src, dst) {
+            calls.entry(src.node_id).or_default().push(dst.node_id);
+        }
+    }
+    let imports = load_file_imports(store).await?;
+
+    let now = Utc::now();
+    let mut created = 0u64;
+    let mut tests = 0usize;
+    for (&test_id, func) in &functions {
+        let Some((framework, names)) = &func.test else {
+            continue;
+        };
+        tests += 1;
+
+        let mut seeds: Vec<NodeId> = calls.get(&test_id).cloned().unwrap_or_default();
+        for name in names {
+            seeds.extend(resolve_by_name(
+                name, test_id, func, &functions, &by_name, &imports,
+            ));
+        }
+
+        for (target, depth) in reachable(&seeds, &calls, &functions) {
+            let mut metadata = HashMap::new();
+            metadata.insert("depth".to_string(), serde_json::json!(depth));
+            metadata.insert("framework".to_string(), serde_json::json!(framework));
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Tests,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: test_id,
+                            role: roles::TEST.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: target,
+                            role: roles::TESTED.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: DEPTH_DECAY.powf(f64::from(depth.saturating_sub(1))),
+                    last_updated: now,
+                    metadata,
+                })
+                .await?;
+            created += 1;
+        }
+    }
+
+    info!(tests, edges = created, "Linked tests to code");
+    Ok(created)
+}
+
+async fn load_functions(store: &dyn HomerStore) -> crate::error::Result<HashMap<NodeId, Function>> {
+    let nodes = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Function),
+            ..Default::default()
+        })
+        .await?;
+
+    Ok(nodes
+        .into_iter()
+        .map(|node| {
+            let file = node
+                .metadata
+                .get("file")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+                .or_else(|| node.name.split_once("::").map(|(f, _)| f.to_string()))
+                .unwrap_or_default();
+            let qualified = node
+                .metadata
+                .get("qualified_name")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_else(|| node.name.strip_prefix(&format!("{file}::")).unwrap_or(""));
+            let test = node.metadata.get("test").map(|t| {
+                let framework = t
+                    .get("framework")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("unknown")
+                    .to_string();
+                let names = t
+                    .get("calls")
+                    .and_then(serde_json::Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::to_string)
+                    .collect();
+                (framework, names)
+            });
+            let in_test_module = qualified
+                .split([':', '.'])
+                .any(|segment| segment == "tests" || segment == "test");
+            let production = test.is_none() && !in_test_module && !is_test_file(&file);
+            let func = Function {
+                simple_name: simple_name(qualified).to_string(),
+                file,
+                test,
+                production,
+            };
+            (node.id, func)
+        })
+        .collect())
+}
+
+/// File → files it imports.
+async fn load_file_imports(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<String, HashSet<String>>> {
+    let files: HashMap<NodeId, String> = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(|f| (f.id, f.name))
+        .collect();
+
+    let mut imports: HashMap<String, HashSet<String>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Imports).await? {
+        let Some((source, target)) = contracts::find_import_pair(&edge.members) else {
+            continue;
+        };
+        if let (Some(from), Some(to)) = (files.get(&source.node_id), files.get(&target.node_id)) {
+            imports.entry(from.clone()).or_default().insert(to.clone());
+        }
+    }
+    Ok(imports)
+}
+
+/// Last path segment of a qualified or called name: `a::b::c`, `obj.c`,
+/// `$this->c` all give `c`.
+fn simple_name(name: &str) -> &str {
+    name.rsplit(['.', ':', '>', '\\'])
+        .next()
+        .unwrap_or(name)
+        .trim()
+}
+
+/// Functions a test's unresolved call `name` most likely refers to: calls
+/// into other files, or from `it(...)` blocks, which have no scope-graph
+/// definition. Tries the test's own file, then files it imports, then its
+/// directory, then the only function with that name.
+fn resolve_by_name(
+    name: &str,
+    test_id: NodeId,
+    test: &Function,
+    functions: &HashMap<NodeId, Function>,
+    by_name: &HashMap<&str, Vec<NodeId>>,
+    imports: &HashMap<String, HashSet<String>>,
+) -> Vec<NodeId> {
+    let Some(candidates) = by_name.get(simple_name(name)) else {
+        return Vec::new();
+    };
+    let candidates: Vec<NodeId> = candidates
+        .iter()
+        .copied()
+        .filter(|id| *id != test_id && functions[id].test.is_none())
+        .collect();
+
+    let dir = |file: &str| file.rsplit_once('/').map_or("", |(d, _)| d).to_string();
+    let test_dir = dir(&test.file);
+    let imported = imports.get(&test.file);
+    let tiers: [&dyn Fn(&Function) -> bool; 3] = [
+        &|f| f.file == test.file,
+        &|f| imported.is_some_and(|i| i.contains(&f.file)),
+        &|f| dir(&f.file) == test_dir,
+    ];
+    for tier in tiers {
+        let matched: Vec<NodeId> = candidates
+            .iter()
+            .copied()
+            .filter(|id| tier(&functions[id]))
+            .collect();
+        if !matched.is_empty() {
+            return matched;
+        }
+    }
+    if candidates.len() == 1 {
+        return candidates;
+    }
+    Vec::new()
+}
+
+/// Production functions reachable from `seeds` (at depth 1), with the
+/// shortest call depth to each. Other tests are not walked through.
+fn reachable(
+    seeds: &[NodeId],
+    calls: &HashMap<NodeId, Vec<NodeId>>,
+    functions: &HashMap<NodeId, Function>,
+) -> HashMap<NodeId, u32> {
+    let mut depth: HashMap<NodeId, u32> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for &seed in seeds {
+        if depth.insert(seed, 1).is_none() {
+            queue.push_back(seed);
+        }
+    }
+    while let Some(id) = queue.pop_front() {
+        let d = depth[&id];
+        let is_test = functions.get(&id).is_none_or(|f| f.test.is_some());
+        if d >= MAX_DEPTH || is_test {
+            continue;
+        }
+        for &callee in calls.get(&id).into_iter().flatten() {
+            if let std::collections::hash_map::Entry::Vacant(e) = depth.entry(callee) {
+                e.insert(d + 1);
+                queue.push_back(callee);
+            }
+        }
+    }
+    depth.retain(|id, _| functions.get(id).is_some_and(|f| f.production));
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::Node;
+
+    async fn function(store: &SqliteStore, file: &str, qualified: &str, test: bool) -> NodeId {
+        let mut metadata = HashMap::from([
+            ("file".to_string(), serde_json::json!(file)),
+            ("qualified_name".to_string(), serde_json::json!(qualified)),
+        ]);
+        if test {
+            metadata.insert(
+                "test".to_string(),
+                serde_json::json!({ "framework": "js", "calls": ["calc.add", "expect"] }),
+            );
+        }
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: format!("{file}::{qualified}"),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn call(store: &SqliteStore, from: NodeId, to: NodeId) {
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Calls,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: from,
+                        role: roles::CALLER.to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: to,
+                        role: roles::CALLEE.to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn links_tests_to_transitively_called_functions() {
+        let store = SqliteStore::in_memory().unwrap();
+        let test = function(&store, "src/calc.test.js", "calc > adds", true).await;
+        let add = function(&store, "src/calc.js", "add", false).await;
+        let carry = function(&store, "src/calc.js", "carry", false).await;
+        let unrelated = function(&store, "src/other.js", "subtract", false).await;
+        let helper = function(&store, "src/calc.test.js", "makeFixture", false).await;
+        call(&store, add, carry).await;
+        call(&store, test, helper).await;
+
+        let created = link_tests(&store).await.unwrap();
+        assert_eq!(created, 2, "add (by name) and carry (through add)");
+
+        let edges = store.get_edges_by_kind(HyperedgeKind::Tests).await.unwrap();
+        let depth_of = |id: NodeId| {
+            edges
+                .iter()
+                .find(|e| {
+                    e.members
+                        .iter()
+                        .any(|m| m.node_id == id && m.role == roles::TESTED)
+                })
+                .map(|e| e.metadata["depth"].as_u64().unwrap())
+        };
+        assert_eq!(depth_of(add), Some(1));
+        assert_eq!(depth_of(carry), Some(2));
+        assert_eq!(depth_of(unrelated), None);
+        assert_eq!(
+            depth_of(helper),
+            None,
+            "helpers in test files are not production"
+        );
+
+        // Rebuilding replaces rather than duplicates.
+        assert_eq!(link_tests(&store).await.unwrap(), 2);
+        assert_eq!(
+            store
+                .get_edges_by_kind(HyperedgeKind::Tests)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn simple_names_strip_receivers_and_paths() {
+        assert_eq!(simple_name("crate::calc::add"), "add");
+        assert_eq!(simple_name("calc.add"), "add");
+        assert_eq!(simple_name("$this->add"), "add");
+        assert_eq!(simple_name("add"), "add");
+    }
+}
@@ -0,0 +1,983 @@
+// Test-result ingestion: keeps a pass/fail/duration history from CI
+// artifacts on one `Test` node per test case.
+
+// Durations are parsed from floating-point seconds.
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::{HomerConfig, TestResultsExtractionConfig};
+use crate::store::HomerStore;
+use crate::store::incremental;
+use crate::types::{Node, NodeFilter, NodeId, NodeKind};
+
+use super::traits::{ExtractStats, Extractor};
+use super::xml::{XmlEvent, XmlReader};
+
+/// Metadata key holding a `Test` node's run history.
+pub const RUNS_KEY: &str = "runs";
+
+/// Metadata key naming the test `Function` node a `Test` node was linked to.
+pub const FUNCTION_KEY: &str = "function";
+
+/// Largest artifact read, in bytes.
+const MAX_ARTIFACT_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Artifact formats Homer understands. Each artifact file is one run; the
+/// format is detected from the content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultFormat {
+    Junit,
+    Trx,
+    LibtestJson,
+}
+
+impl ResultFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Junit => "junit",
+            Self::Trx => "trx",
+            Self::LibtestJson => "libtest-json",
+        }
+    }
+
+    /// Detect the format of an artifact from its content.
+    pub fn detect(text: &str) -> Option<Self> {
+        let head = text.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with('<') {
+            if text.contains("<TestRun") {
+                Some(Self::Trx)
+            } else if text.contains("<testsuite") || text.contains("<testcase") {
+                Some(Self::Junit)
+            } else {
+                None
+            }
+        } else if head.starts_with('{') {
+            head.lines()
+                .take(64)
+                .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+                .any(|v| v.get("type").and_then(serde_json::Value::as_str) == Some("test"))
+                .then_some(Self::LibtestJson)
+        } else {
+            None
+        }
+    }
+}
+
+/// The result of one test execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestOutcome {
+    Pass,
+    Fail,
+    Skip,
+}
+
+/// One test case as reported by an artifact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    /// Class, module, or suite the case belongs to, if the format names one.
+    pub suite: Option<String>,
+    pub name: String,
+    /// Source file, when the report records it (pytest, jest).
+    pub file: Option<String>,
+    pub outcome: TestOutcome,
+    pub duration_ms: Option<f64>,
+}
+
+impl TestCase {
+    /// The `Test` node name: `suite::name`, or the bare name.
+    pub fn node_name(&self) -> String {
+        match &self.suite {
+            Some(suite) if !suite.is_empty() => format!("{suite}::{}", self.name),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// The test function's own name: the last path segment, without
+    /// parameterization (`test_parse[utf8]`, `Adds(1, 2)`) or Go subtests
+    /// (`TestParse/utf8`). Descriptions with spaces (Jest, `RSpec`) are kept
+    /// whole.
+    pub fn simple_name(&self) -> &str {
+        let name = self
+            .name
+            .split(['[', '('])
+            .next()
+            .unwrap_or(&self.name)
+            .trim();
+        if name.contains(' ') {
+            return name;
+        }
+        let name = name.split('/').next().unwrap_or(name);
+        name.rsplit("::")
+            .next()
+            .and_then(|n| n.rsplit('.').next())
+            .unwrap_or(name)
+    }
+}
+
+/// A parsed artifact: every case it reports.
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    pub cases: Vec<TestCase>,
+    /// When the run started, if the artifact says.
+    pub started: Option<DateTime<Utc>>,
+}
+
+/// Parse an artifact, detecting its format.
+pub fn parse_artifact(text: &str) -> Option<(ResultFormat, TestReport)> {
+    let format = ResultFormat::detect(text)?;
+    let report = match format {
+        ResultFormat::Junit => parse_junit(text),
+        ResultFormat::Trx => parse_trx(text),
+        ResultFormat::LibtestJson => parse_libtest_json(text),
+    };
+    Some((format, report))
+}
+
+/// `JUnit` XML: `<testcase classname name time>` elements, failed when they
+/// hold `<failure>` or `<error>`, skipped with `<skipped>`. A surefire
+/// `<flakyFailure>` is a failed attempt that a rerun passed, so it is
+/// recorded as an extra failing execution.
+pub fn parse_junit(text: &str) -> TestReport {
+    let mut report = TestReport::default();
+    let mut suites: Vec<String> = Vec::new();
+    let mut current: Option<TestCase> = None;
+    let mut retries = 0usize;
+
+    for event in XmlReader::new(text) {
+        match event {
+            XmlEvent::Start(tag) => match tag.local_name() {
+                "testsuite" => {
+                    suites.push(tag.attr("name").unwrap_or_default().to_string());
+                    if report.started.is_none() {
+                        report.started = tag.attr("timestamp").and_then(parse_timestamp);
+                    }
+                }
+                "testcase" => {
+                    let suite = tag
+                        .attr("classname")
+                        .or_else(|| suites.last().map(String::as_str))
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string);
+                    current = Some(TestCase {
+                        suite,
+                        name: tag.attr("name").unwrap_or_default().to_string(),
+                        file: tag.attr("file").map(str::to_string),
+                        outcome: TestOutcome::Pass,
+                        duration_ms: tag.attr("time").and_then(parse_seconds),
+                    });
+                    retries = 0;
+                }
+                "failure" | "error" => {
+                    if let Some(case) = current.as_mut() {
+                        case.outcome = TestOutcome::Fail;
+                    }
+                }
+                "skipped" => {
+                    if let Some(case) = current.as_mut() {
+                        case.outcome = TestOutcome::Skip;
+                    }
+                }
+                "flakyFailure" | "flakyError" => retries += 1,
+                _ => {}
+            },
+            XmlEvent::End(name) => match super::xml::local_name(&name) {
+                "testsuite" => {
+                    suites.pop();
+                }
+                "testcase" => {
+                    if let Some(case) = current.take() {
+                        for _ in 0..retries {
+                            report.cases.push(TestCase {
+                                outcome: TestOutcome::Fail,
+                                duration_ms: None,
+                                ..case.clone()
+                            });
+                        }
+                        if !case.name.is_empty() {
+                            report.cases.push(case);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::Text(_) => {}
+        }
+    }
+    report
+}
+
+/// Visual Studio TRX: `<UnitTestResult testId testName outcome duration>`
+/// results, with class names from the matching `<UnitTest>` definitions.
+pub fn parse_trx(text: &str) -> TestReport {
+    let mut report = TestReport::default();
+    // testId → (class name, method name)
+    let mut definitions: HashMap<String, (String, String)> = HashMap::new();
+    let mut results: Vec<(Option<String>, String, TestOutcome, Option<f64>)> = Vec::new();
+    let mut definition: Option<String> = None;
+
+    for event in XmlReader::new(text) {
+        match event {
+            XmlEvent::Start(tag) => match tag.local_name() {
+                "Times" => report.started = tag.attr("start").and_then(parse_timestamp),
+                "UnitTestResult" => {
+                    let outcome = match tag.attr("outcome").unwrap_or_default() {
+                        "Passed" => TestOutcome::Pass,
+                        "Failed" | "Error" | "Timeout" | "Aborted" => TestOutcome::Fail,
+                        _ => TestOutcome::Skip,
+                    };
+                    results.push((
+                        tag.attr("testId").map(str::to_string),
+                        tag.attr("testName").unwrap_or_default().to_string(),
+                        outcome,
+                        tag.attr("duration").and_then(parse_timespan),
+                    ));
+                }
+                "UnitTest" => definition = tag.attr("id").map(str::to_string),
+                "TestMethod" => {
+                    if let (Some(id), Some(class), Some(name)) =
+                        (definition.as_ref(), tag.attr("className"), tag.attr("name"))
+                    {
+                        // className may carry an assembly suffix.
+                        let class = class.split(',').next().unwrap_or(class).trim();
+                        definitions.insert(id.clone(), (class.to_string(), name.to_string()));
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::End(name) if super::xml::local_name(&name) == "UnitTest" => {
+                definition = None;
+            }
+            XmlEvent::End(_) | XmlEvent::Text(_) => {}
+        }
+    }
+
+    for (id, test_name, outcome, duration_ms) in results {
+        let (suite, name) = match id.and_then(|id| definitions.get(&id)) {
+            Some((class, method)) => {
+                // testName keeps data-row arguments the method name lacks.
+                let name = match test_name.strip_prefix(&format!("{class}.")) {
+                    Some(name) => name,
+                    None if !test_name.is_empty() => &test_name,
+                    None => method,
+                };
+                (Some(class.clone()), name.to_string())
+            }
+            None => match test_name.rsplit_once('.') {
+                Some((class, method)) if !test_name.contains('(') => {
+                    (Some(class.to_string()), method.to_string())
+                }
+                _ => (None, test_name),
+            },
+        };
+        if name.is_empty() {
+            continue;
+        }
+        report.cases.push(TestCase {
+            suite,
+            name,
+            file: None,
+            outcome,
+            duration_ms,
+        });
+    }
+    report
+}
+
+/// libtest JSON lines: `{"type":"test","event":"ok","name":...,"exec_time":...}`.
+/// nextest prefixes names with the test binary and `$`.
+pub fn parse_libtest_json(text: &str) -> TestReport {
+    let mut report = TestReport::default();
+    for line in text.lines() {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+        if value.get("type").and_then(serde_json::Value::as_str) != Some("test") {
+            continue;
+        }
+        let outcome = match value.get("event").and_then(serde_json::Value::as_str) {
+            Some("ok") => TestOutcome::Pass,
+            Some("failed" | "timeout") => TestOutcome::Fail,
+            Some("ignored") => TestOutcome::Skip,
+            _ => continue,
+        };
+        let Some(full) = value.get("name").and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        let (suite, name) = match full.split_once('$') {
+            Some((binary, name)) => (Some(binary.to_string()), name.to_string()),
+            None => (None, full.to_string()),
+        };
+        report.cases.push(TestCase {
+            suite,
+            name,
+            file: None,
+            outcome,
+            duration_ms: value
+                .get("exec_time")
+                .and_then(serde_json::Value::as_f64)
+                .map(|s| s * 1000.0),
+        });
+    }
+    report
+}
+
+/// `"1.25"` or `"1,234.5"` seconds → milliseconds.
+fn parse_seconds(value: &str) -> Option<f64> {
+    let secs: f64 = value.replace(',', "").trim().parse().ok()?;
+    (secs.is_finite() && secs >= 0.0).then_some(secs * 1000.0)
+}
+
+/// TRX `hh:mm:ss.fffffff` → milliseconds.
+fn parse_timespan(value: &str) -> Option<f64> {
+    let mut parts = value.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(((hours * 60.0 + minutes) * 60.0 + seconds) * 1000.0)
+}
+
+/// RFC 3339, or a naive ISO timestamp taken as UTC.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(|t| t.and_utc())
+        })
+}
+
+// ── History ───────────────────────────────────────────────────────
+
+/// One recorded execution of a test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestRunRecord {
+    /// Fingerprint of the artifact the execution came from, so re-reading
+    /// the same file never duplicates history while an overwritten artifact
+    /// adds a new entry.
+    pub run: String,
+    /// Commit the run tested, when the artifact path names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub at: DateTime<Utc>,
+    pub outcome: TestOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+}
+
+impl TestRunRecord {
+    /// The run history stored on a `Test` node, oldest first.
+    pub fn history(node: &Node) -> Vec<Self> {
+        node.metadata
+            .get(RUNS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// The commit a run tested: a hex token in the artifact path that prefixes a
+/// known commit, or failing that, one that looks like a SHA. Flakiness
+/// detection compares runs of the same commit.
+fn commit_from_path(path: &str, commits: &[String]) -> Option<String> {
+    let tokens: Vec<&str> = path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| (7..=40).contains(&t.len()) && t.chars().all(|c| c.is_ascii_hexdigit()))
+        .collect();
+    for token in &tokens {
+        let token = token.to_ascii_lowercase();
+        let mut matches = commits.iter().filter(|c| c.starts_with(&token));
+        if let (Some(commit), None) = (matches.next(), matches.next()) {
+            return Some(commit.clone());
+        }
+    }
+    tokens
+        .iter()
+        .rev()
+        .find(|t| t.chars().any(|c| c.is_ascii_digit()))
+        .map(|t| t.to_ascii_lowercase())
+}
+
+// ── Function linking ──────────────────────────────────────────────
+
+/// Test functions found by graph extraction, indexed by their own name.
+struct TestFunctions {
+    by_name: HashMap<String, Vec<(String, HashSet<String>)>>,
+}
+
+/// Lowercase words of a path or qualified name.
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+impl TestFunctions {
+    async fn load(store: &dyn HomerStore) -> crate::error::Result<Self> {
+        let functions = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Function),
+                ..Default::default()
+            })
+            .await?;
+        let mut by_name: HashMap<String, Vec<(String, HashSet<String>)>> = HashMap::new();
+        for func in functions {
+            if !func.metadata.contains_key("test") {
+                continue;
+            }
+            let qualified = func
+                .metadata
+                .get("qualified_name")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_else(|| func.name.rsplit("::").next().unwrap_or(&func.name));
+            // Call-style tests are named `describe > it`; the title is the key.
+            let simple = if qualified.contains(' ') {
+                qualified.rsplit(" > ").next().unwrap_or(qualified)
+            } else {
+                qualified.rsplit('.').next().unwrap_or(qualified)
+            }
+            .to_string();
+            let file = func
+                .metadata
+                .get("file")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            let file = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+            let mut context = words(file);
+            context.extend(words(&func.name));
+            by_name
+                .entry(simple)
+                .or_default()
+                .push((func.name, context));
+        }
+        Ok(Self { by_name })
+    }
+
+    /// The test function a case most likely ran: the only one with its name,
+    /// or the one whose file and qualified name share the most words with
+    /// the case's suite and file. Ties link nothing.
+    fn resolve(&self, case: &TestCase) -> Option<String> {
+        let simple = case.simple_name();
+        let candidates: Vec<&(String, HashSet<String>)> = match self.by_name.get(simple) {
+            Some(found) => found.iter().collect(),
+            // Jest and RSpec report the suite and test titles joined with
+            // spaces, ending with the test's own title.
+            None if simple.contains(' ') => self
+                .by_name
+                .iter()
+                .filter(|(title, _)| title.contains(' ') && simple.ends_with(title.as_str()))
+                .flat_map(|(_, found)| found)
+                .collect(),
+            None => return None,
+        };
+        if let [(only, _)] = candidates.as_slice() {
+            return Some(only.clone());
+        }
+        let mut context = words(&case.node_name());
+        if let Some(file) = &case.file {
+            context.extend(words(file.rsplit_once('.').map_or(file, |(stem, _)| stem)));
+        }
+        let mut scored: Vec<(usize, &String)> = candidates
+            .iter()
+            .map(|(name, words)| (words.intersection(&context).count(), name))
+            .collect();
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+        match scored.as_slice() {
+            [(best, name), rest @ ..] if rest.first().is_none_or(|(next, _)| next < best) => {
+                Some((*name).clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+// ── Extractor ─────────────────────────────────────────────────────
+
+/// History is kept on the `Test` nodes, so it outlives pruned CI artifacts.
+/// Each test is linked by name to the test function graph extraction found,
+/// so analyzers can follow its `Tests` edges to the code it exercises.
+#[derive(Debug)]
+pub struct TestResultsExtractor {
+    repo_path: PathBuf,
+    settings: TestResultsExtractionConfig,
+}
+
+/// Runs gathered for one test from the artifacts read this pass.
+struct PendingTest {
+    case: TestCase,
+    runs: Vec<TestRunRecord>,
+}
+
+impl TestResultsExtractor {
+    pub fn new(repo_path: &Path, config: &HomerConfig) -> Self {
+        Self {
+            repo_path: repo_path.to_path_buf(),
+            settings: config.extraction.test_results.clone(),
+        }
+    }
+
+    /// Artifact files matching the configured patterns.
+    fn discover_artifacts(&self) -> Vec<PathBuf> {
+        if !self.settings.enabled {
+            return Vec::new();
+        }
+        let root = glob::Pattern::escape(&self.repo_path.to_string_lossy());
+        let mut artifacts: Vec<PathBuf> = self
+            .settings
+            .artifacts
+            .iter()
+            .filter_map(|pattern| glob::glob(&format!("{root}/{pattern}")).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|p| p.is_file())
+            .collect();
+        artifacts.sort();
+        artifacts.dedup();
+        artifacts
+    }
+
+    async fn state(&self, store: &dyn HomerStore) -> crate::error::Result<String> {
+        let mut hasher = DefaultHasher::new();
+        for artifact in self.discover_artifacts() {
+            artifact.hash(&mut hasher);
+            if let Ok(meta) = std::fs::metadata(&artifact) {
+                meta.len().hash(&mut hasher);
+                meta.modified().ok().hash(&mut hasher);
+            }
+        }
+        // New commits resolve short SHAs; graph extraction rewrites test functions.
+        let git = store.get_checkpoint("git_last_sha").await?;
+        let graph = store.get_checkpoint("graph_last_sha").await?;
+        Ok(format!(
+            "{}:{}:{:x}",
+            git.unwrap_or_default(),
+            graph.unwrap_or_default(),
+            hasher.finish()
+        ))
+    }
+
+    /// Parse every artifact into runs per test node name.
+    fn load_runs(&self, commits: &[String]) -> HashMap<String, PendingTest> {
+        let mut pending: HashMap<String, PendingTest> = HashMap::new();
+        for path in self.discover_artifacts() {
+            let meta = std::fs::metadata(&path).ok();
+            if meta.as_ref().is_some_and(|m| m.len() > MAX_ARTIFACT_BYTES) {
+                warn!(artifact = %path.display(), "Test-result artifact too large, skipping");
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some((format, report)) = parse_artifact(&text) else {
+                debug!(artifact = %path.display(), "Unrecognized test-result format");
+                continue;
+            };
+
+            let mut hasher = DefaultHasher::new();
+            text.hash(&mut hasher);
+            let run = format!("{:016x}", hasher.finish());
+            let relative = path.strip_prefix(&self.repo_path).unwrap_or(&path);
+            let commit = commit_from_path(&relative.to_string_lossy(), commits);
+            let at = report
+                .started
+                .or_else(|| Some(meta?.modified().ok()?.into()))
+                .unwrap_or_else(Utc::now);
+
+            for case in &report.cases {
+                let entry = pending
+                    .entry(case.node_name())
+                    .or_insert_with(|| PendingTest {
+                        case: case.clone(),
+                        runs: Vec::new(),
+                    });
+                if entry.case.file.is_none() {
+                    entry.case.file.clone_from(&case.file);
+                }
+                entry.runs.push(TestRunRecord {
+                    run: run.clone(),
+                    commit: commit.clone(),
+                    at,
+                    outcome: case.outcome,
+                    duration_ms: case.duration_ms,
+                });
+            }
+            info!(
+                artifact = %path.display(),
+                format = format.as_str(),
+                cases = report.cases.len(),
+                commit = commit.as_deref().unwrap_or("-"),
+                "Read test results"
+            );
+        }
+        pending
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Extractor for TestResultsExtractor {
+    fn name(&self) -> &'static str {
+        "test_results"
+    }
+
+    async fn has_work(&self, store: &dyn HomerStore) -> crate::error::Result<bool> {
+        let state = self.state(store).await?;
+        incremental::needs_extraction(store, "test_results_state", &state).await
+    }
+
+    #[instrument(skip_all, name = "test_results_extract")]
+    async fn extract(
+        &self,
+        store: &dyn HomerStore,
+        _config: &HomerConfig,
+    ) -> crate::error::Result<ExtractStats> {
+        let start = Instant::now();
+        let mut stats = ExtractStats::default();
+
+        let commits: Vec<String> = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Commit),
+                ..Default::default()
+            })
+            .await?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        let mut pending = self.load_runs(&commits);
+        let functions = TestFunctions::load(store).await?;
+
+        let existing = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Test),
+                ..Default::default()
+            })
+            .await?;
+        let mut seen: HashSet<String> = HashSet::new();
+        for node in existing {
+            seen.insert(node.name.clone());
+            let update = pending.remove(&node.name);
+            if update_test(store, node, update, &functions, self.settings.max_runs).await? {
+                stats.nodes_updated += 1;
+            }
+        }
+        for (name, update) in pending {
+            if seen.contains(&name) {
+                continue;
+            }
+            let node = Node {
+                id: NodeId(0),
+                kind: NodeKind::Test,
+                name,
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata: HashMap::new(),
+            };
+            update_test(
+                store,
+                node,
+                Some(update),
+                &functions,
+                self.settings.max_runs,
+            )
+            .await?;
+            stats.nodes_created += 1;
+        }
+
+        let state = self.state(store).await?;
+        store.set_checkpoint("test_results_state", &state).await?;
+
+        stats.duration = start.elapsed();
+        info!(
+            created = stats.nodes_created,
+            updated = stats.nodes_updated,
+            duration = ?stats.duration,
+            "Test-result extraction complete"
+        );
+        Ok(stats)
+    }
+}
+
+/// Append new runs to a test's history, trim it to `max_runs`, and refresh
+/// its function link. Returns whether the node was written.
+async fn update_test(
+    store: &dyn HomerStore,
+    mut node: Node,
+    update: Option<PendingTest>,
+    functions: &TestFunctions,
+    max_runs: usize,
+) -> crate::error::Result<bool> {
+    let mut history = TestRunRecord::history(&node);
+    let mut changed = false;
+
+    if let Some(update) = update {
+        let known: HashSet<String> = history.iter().map(|r| r.run.clone()).collect();
+        let new_runs: Vec<TestRunRecord> = update
+            .runs
+            .into_iter()
+            .filter(|r| !known.contains(&r.run))
+            .collect();
+        if !new_runs.is_empty() {
+            history.extend(new_runs);
+            history.sort_by_key(|r| r.at);
+            let excess = history.len().saturating_sub(max_runs.max(1));
+            history.drain(..excess);
+            node.metadata
+                .insert(RUNS_KEY.to_string(), serde_json::json!(history));
+            changed = true;
+        }
+        for (key, value) in [
+            ("suite", update.case.suite.clone()),
+            ("case", Some(update.case.name.clone())),
+            ("file", update.case.file.clone()),
+        ] {
+            if let Some(value) = value {
+                let value = serde_json::json!(value);
+                if node.metadata.get(key) != Some(&value) {
+                    node.metadata.insert(key.to_string(), value);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let case = TestCase {
+        suite: meta_str(&node, "suite"),
+        name: meta_str(&node, "case").unwrap_or_else(|| node.name.clone()),
+        file: meta_str(&node, "file"),
+        outcome: TestOutcome::Pass,
+        duration_ms: None,
+    };
+    let function = functions.resolve(&case).map(|f| serde_json::json!(f));
+    if node.metadata.get(FUNCTION_KEY) != function.as_ref() {
+        match function {
+            Some(f) => node.metadata.insert(FUNCTION_KEY.to_string(), f),
+            None => node.metadata.remove(FUNCTION_KEY),
+        };
+        changed = true;
+    }
+
+    if changed {
+        node.last_extracted = Utc::now();
+        store.upsert_node(&node).await?;
+    }
+    Ok(changed)
+}
+
+fn meta_str(node: &Node, key: &str) -> Option<String> {
+    node.metadata
+        .get(key)
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::sqlite::SqliteStore;
+
+    #[test]
+    fn parses_junit_outcomes_and_retries() {
+        let report = parse_junit(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="pytest" timestamp="2026-03-01T10:00:00.123456" tests="4">
+    <testcase classname="tests.test_api.TestUser" name="test_create" file="tests/test_api.py" time="0.250"/>
+    <testcase classname="tests.test_api.TestUser" name="test_delete[soft]" time="1,500.0">
+      <failure message="boom"><![CDATA[assert 1 == 2]]></failure>
+    </testcase>
+    <testcase classname="tests.test_api.TestUser" name="test_skip"><skipped/></testcase>
+    <testcase classname="com.acme.LedgerTest" name="posts" time="0.1">
+      <flakyFailure message="timeout"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#,
+        );
+        assert_eq!(
+            report.started.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2026-03-01T10:00:00.123456+00:00")
+        );
+        let outcomes: Vec<(String, TestOutcome)> = report
+            .cases
+            .iter()
+            .map(|c| (c.node_name(), c.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (
+                    "tests.test_api.TestUser::test_create".into(),
+                    TestOutcome::Pass
+                ),
+                (
+                    "tests.test_api.TestUser::test_delete[soft]".into(),
+                    TestOutcome::Fail
+                ),
+                (
+                    "tests.test_api.TestUser::test_skip".into(),
+                    TestOutcome::Skip
+                ),
+                ("com.acme.LedgerTest::posts".into(), TestOutcome::Fail),
+                ("com.acme.LedgerTest::posts".into(), TestOutcome::Pass),
+            ]
+        );
+        assert_eq!(report.cases[0].file.as_deref(), Some("tests/test_api.py"));
+        assert_eq!(report.cases[1].duration_ms, Some(1_500_000.0));
+        assert_eq!(report.cases[1].simple_name(), "test_delete");
+    }
+
+    #[test]
+    fn parses_trx_with_definitions() {
+        let report = parse_trx(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<TestRun id="1" xmlns="http://microsoft.com/schemas/VisualStudio/TeamTest/2010">
+  <Times creation="2026-03-01T10:00:00.0000000+02:00" start="2026-03-01T10:00:01.0000000+02:00"/>
+  <Results>
+    <UnitTestResult testId="a" testName="Acme.Tests.LedgerTests.Posts" duration="00:00:02.5000000" outcome="Passed"/>
+    <UnitTestResult testId="b" testName="Adds(1, 2)" duration="00:00:00.0100000" outcome="Failed"/>
+  </Results>
+  <TestDefinitions>
+    <UnitTest name="Posts" id="a"><TestMethod className="Acme.Tests.LedgerTests, Acme.Tests" name="Posts"/></UnitTest>
+    <UnitTest name="Adds(1, 2)" id="b"><TestMethod className="Acme.Tests.MathTests" name="Adds"/></UnitTest>
+  </TestDefinitions>
+</TestRun>"#,
+        );
+        assert_eq!(
+            report.started.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2026-03-01T08:00:01+00:00")
+        );
+        assert_eq!(report.cases[0].node_name(), "Acme.Tests.LedgerTests::Posts");
+        assert_eq!(report.cases[0].duration_ms, Some(2500.0));
+        assert_eq!(
+            report.cases[1].node_name(),
+            "Acme.Tests.MathTests::Adds(1, 2)"
+        );
+        assert_eq!(report.cases[1].simple_name(), "Adds");
+        assert_eq!(report.cases[1].outcome, TestOutcome::Fail);
+    }
+
+    #[test]
+    fn parses_libtest_and_nextest_json() {
+        let text = r#"{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "store::tests::round_trip" }
+{ "type": "test", "name": "store::tests::round_trip", "event": "ok", "exec_time": 0.5 }
+{ "type": "test", "name": "homer-core::lib$extract::tests::parse", "event": "failed" }
+{ "type": "test", "name": "slow::bench", "event": "ignored" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1 }"#;
+        assert_eq!(ResultFormat::detect(text), Some(ResultFormat::LibtestJson));
+        let report = parse_libtest_json(text);
+        assert_eq!(report.cases.len(), 3);
+        assert_eq!(report.cases[0].node_name(), "store::tests::round_trip");
+        assert_eq!(report.cases[0].simple_name(), "round_trip");
+        assert_eq!(report.cases[0].duration_ms, Some(500.0));
+        assert_eq!(
+            report.cases[1].node_name(),
+            "homer-core::lib::extract::tests::parse"
+        );
+        assert_eq!(report.cases[1].outcome, TestOutcome::Fail);
+        assert_eq!(report.cases[2].outcome, TestOutcome::Skip);
+    }
+
+    #[test]
+    fn finds_commit_in_artifact_path() {
+        let commits = vec![
+            "4f2a9c1e8b7d6a5f4e3d2c1b0a9f8e7d6c5b4a39".to_string(),
+            "0123456789abcdef0123456789abcdef01234567".to_string(),
+        ];
+        assert_eq!(
+            commit_from_path("test-results/run-42/4f2a9c1/junit.xml", &commits).as_deref(),
+            Some(commits[0].as_str()),
+            "short SHA resolves to the known commit"
+        );
+        assert_eq!(
+            commit_from_path("test-results/ci-deadbee5/junit.xml", &commits).as_deref(),
+            Some("deadbee5"),
+            "unknown SHA kept as written"
+        );
+        assert_eq!(
+            commit_from_path("test-results/defaced/junit.xml", &commits),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn accumulates_history_and_links_functions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let sha = "4f2a9c1e8b7d6a5f4e3d2c1b0a9f8e7d6c5b4a39";
+        let junit = |outcome: &str| {
+            format!(
+                r#"<testsuite name="homer-core" timestamp="2026-03-0{}T10:00:00">
+<testcase classname="homer-core" name="store::tests::round_trip" time="0.5">{outcome}</testcase>
+</testsuite>"#,
+                if outcome.is_empty() { 1 } else { 2 }
+            )
+        };
+        let dir = root.join("test-results").join(&sha[..7]);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("attempt-1.xml"), junit("")).unwrap();
+
+        let store = SqliteStore::in_memory().unwrap();
+        for (kind, name, metadata) in [
+            (NodeKind::Commit, sha, serde_json::json!({})),
+            (
+                NodeKind::Function,
+                "src/store/mod.rs::tests::round_trip",
+                serde_json::json!({
+                    "file": "src/store/mod.rs",
+                    "test": { "framework": "rust", "calls": [] },
+                }),
+            ),
+        ] {
+            store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: Utc::now(),
+                    metadata: serde_json::from_value(metadata).unwrap(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let config = HomerConfig::default();
+        let extractor = TestResultsExtractor::new(root, &config);
+        assert!(extractor.has_work(&store).await.unwrap());
+        let stats = extractor.extract(&store, &config).await.unwrap();
+        assert_eq!(stats.nodes_created, 1);
+        assert!(!extractor.has_work(&store).await.unwrap());
+
+        // A rerun at the same commit fails; the first artifact is pruned.
+        std::fs::write(dir.join("attempt-2.xml"), junit("<failure/>")).unwrap();
+        std::fs::remove_file(dir.join("attempt-1.xml")).unwrap();
+        extractor.extract(&store, &config).await.unwrap();
+        // Re-reading an unchanged artifact adds nothing.
+        extractor.extract(&store, &config).await.unwrap();
+
+        let node = store
+            .get_node_by_name(NodeKind::Test, "homer-core::store::tests::round_trip")
+            .await
+            .unwrap()
+            .expect("test node");
+        let history = TestRunRecord::history(&node);
+        assert_eq!(history.len(), 2, "history outlives pruned artifacts");
+        assert_eq!(history[0].outcome, TestOutcome::Pass);
+        assert_eq!(history[1].outcome, TestOutcome::Fail);
+        assert!(history.iter().all(|r| r.commit.as_deref() == Some(sha)));
+        assert_eq!(history[0].duration_ms, Some(500.0));
+        assert_eq!(
+            node.metadata[FUNCTION_KEY],
+            "src/store/mod.rs::tests::round_trip"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+// Minimal pull reader for the XML report formats Homer ingests.
+//
+// Coverage and test-result files are machine-generated and well formed, so
+// this reader only needs elements, attributes, text, CDATA and the standard
+// entities. Comments, processing instructions and DOCTYPE declarations are
+// skipped; namespaces are left in element names as written.
+
+/// One parsed XML event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XmlEvent {
+    /// An opening tag. Self-closing tags are followed by a matching `End`.
+    Start(XmlTag),
+    /// A closing tag.
+    End(String),
+    /// Character data between tags, unescaped. Whitespace-only runs are dropped.
+    Text(String),
+}
+
+/// An element name and its attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct XmlTag {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+}
+
+impl XmlTag {
+    /// Value of an attribute, if present.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Element name without a namespace prefix.
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+}
+
+/// Element name without a namespace prefix.
+pub(crate) fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+/// Iterator over the events of an XML document.
+pub(crate) struct XmlReader<'a> {
+    rest: &'a str,
+    pending_end: Option<String>,
+}
+
+impl<'a> XmlReader<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            rest: text.strip_prefix('\u{feff}').unwrap_or(text),
+            pending_end: None,
+        }
+    }
+}
+
+impl Iterator for XmlReader<'_> {
+    type Item = XmlEvent;
+
+    fn next(&mut self) -> Option<XmlEvent> {
+        if let Some(name) = self.pending_end.take() {
+            return Some(XmlEvent::End(name));
+        }
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            if !self.rest.starts_with('<') {
+                let end = self.rest.find('<').unwrap_or(self.rest.len());
+                let text = &self.rest[..end];
+                self.rest = &self.rest[end..];
+                if text.trim().is_empty() {
+                    continue;
+                }
+                return Some(XmlEvent::Text(unescape(text)));
+            }
+
+            if let Some(body) = self.rest.strip_prefix("<![CDATA[") {
+                let end = body.find("]]>").unwrap_or(body.len());
+                let text = body[..end].to_string();
+                self.rest = body.get(end + 3..).unwrap_or_default();
+                return Some(XmlEvent::Text(text));
+            }
+            if let Some(body) = self.rest.strip_prefix("<!--") {
+                self.rest = body.find("-->").map_or("", |end| &body[end + 3..]);
+                continue;
+            }
+            if self.rest.starts_with("<?") || self.rest.starts_with("<!") {
+                self.rest = self.rest.find('>').map_or("", |end| &self.rest[end + 1..]);
+                continue;
+            }
+
+            let end = tag_end(self.rest)?;
+            let inner = &self.rest[1..end];
+            self.rest = &self.rest[end + 1..];
+
+            if let Some(name) = inner.strip_prefix('/') {
+                return Some(XmlEvent::End(name.trim().to_string()));
+            }
+            let (inner, self_closing) = match inner.strip_suffix('/') {
+                Some(inner) => (inner, true),
+                None => (inner, false),
+            };
+            let tag = parse_tag(inner);
+            if self_closing {
+                self.pending_end = Some(tag.name.clone());
+            }
+            return Some(XmlEvent::Start(tag));
+        }
+    }
+}
+
+/// Index of the `>` closing the tag at the start of `s`, skipping quoted
+/// attribute values.
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_tag(inner: &str) -> XmlTag {
+    let inner = inner.trim();
+    let name_end = inner
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(inner.len());
+    let name = inner[..name_end].to_string();
+
+    let mut attrs = Vec::new();
+    let mut rest = inner[name_end..].trim_start();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_string();
+        let after = rest[eq + 1..].trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(close) = after[1..].find(quote) else {
+            break;
+        };
+        attrs.push((key, unescape(&after[1..=close])));
+        rest = after[close + 2..].trim_start();
+    }
+
+    XmlTag { name, attrs }
+}
+
+/// Replace the predefined entities and numeric character references.
+pub(crate) fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';').filter(|&s| s <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        if let Some(c) = decoded {
+            out.push(c);
+            rest = &rest[semi + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_elements_attributes_and_text() {
+        let xml = r#"<?xml version="1.0"?>
+<!-- generated -->
+<suite name="core &amp; utils" time='1.5'>
+  <case name="a > b"/>
+  <case name="c"><failure message="boom">trace &lt;here&gt;</failure></case>
+  <out><![CDATA[raw <text>]]></out>
+</suite>"#;
+        let events: Vec<XmlEvent> = XmlReader::new(xml).collect();
+
+        let XmlEvent::Start(suite) = &events[0] else {
+            panic!("expected start, got {:?}", events[0]);
+        };
+        assert_eq!(suite.name, "suite");
+        assert_eq!(suite.attr("name"), Some("core & utils"));
+        assert_eq!(suite.attr("time"), Some("1.5"));
+
+        let XmlEvent::Start(case) = &events[1] else {
+            panic!("expected start");
+        };
+        assert_eq!(case.attr("name"), Some("a > b"));
+        assert_eq!(events[2], XmlEvent::End("case".to_string()));
+        assert!(events.contains(&XmlEvent::Text("trace <here>".to_string())));
+        assert!(events.contains(&XmlEvent::Text("raw <text>".to_string())));
+        assert_eq!(events.last(), Some(&XmlEvent::End("suite".to_string())));
+    }
+}
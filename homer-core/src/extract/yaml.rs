@@ -0,0 +1,610 @@
+// Minimal YAML reader for the CI and task-runner files Homer models.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+/// Parse every document in `text`.
+///
+/// Covers the slice of YAML CI configuration uses: block mappings and
+/// sequences, flow collections, plain, quoted and block scalars, comments,
+/// and anchors, aliases and `<<` merge keys for shared job templates. Tags
+/// such as GitLab's `!reference` are dropped, and keys are always strings,
+/// so GitHub's `on:` stays `"on"` rather than YAML 1.1's `true`. Malformed
+/// input yields whatever could be read instead of an error.
+pub(crate) fn parse_documents(text: &str) -> Vec<Value> {
+    let mut docs = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut flush = |lines: &mut Vec<String>| {
+        if lines.iter().any(|l| !is_blank(l)) {
+            docs.push(Parser::new(std::mem::take(lines)).document());
+        }
+        lines.clear();
+    };
+
+    for line in text.lines() {
+        let marker = line.starts_with("---") && (line.len() == 3 || line[3..].starts_with(' '));
+        if marker || line == "..." {
+            flush(&mut current);
+        } else if line.starts_with('%') && current.is_empty() {
+            // `%YAML 1.2` and `%TAG` directives.
+        } else {
+            current.push(line.replace('\t', "  "));
+        }
+    }
+    flush(&mut current);
+    docs
+}
+
+/// Parse the first document in `text`; `Null` when there is none.
+pub(crate) fn parse(text: &str) -> Value {
+    parse_documents(text)
+        .into_iter()
+        .next()
+        .unwrap_or(Value::Null)
+}
+
+struct Parser {
+    lines: Vec<String>,
+    pos: usize,
+    anchors: HashMap<String, Value>,
+}
+
+impl Parser {
+    fn new(lines: Vec<String>) -> Self {
+        Self {
+            lines,
+            pos: 0,
+            anchors: HashMap::new(),
+        }
+    }
+
+    fn document(mut self) -> Value {
+        self.node(0)
+    }
+
+    /// Skip blank and comment-only lines; the indent of the next content line.
+    fn peek_indent(&mut self) -> Option<usize> {
+        while let Some(line) = self.lines.get(self.pos) {
+            if !is_blank(line) {
+                return Some(indent_of(line));
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    fn content(&self) -> &str {
+        self.lines[self.pos].trim_start()
+    }
+
+    /// The block node on the next content line, if indented at least `min`.
+    fn node(&mut self, min: usize) -> Value {
+        let Some(indent) = self.peek_indent() else {
+            return Value::Null;
+        };
+        if indent < min {
+            return Value::Null;
+        }
+        let content = self.content().to_string();
+        if is_seq_item(&content) {
+            self.sequence(indent)
+        } else if split_key(&content).is_some() {
+            self.mapping(indent)
+        } else {
+            self.pos += 1;
+            self.inline(&content, indent)
+        }
+    }
+
+    fn sequence(&mut self, indent: usize) -> Value {
+        let mut items = Vec::new();
+        while self.peek_indent() == Some(indent) && is_seq_item(self.content()) {
+            let content = self.content().to_string();
+            let rest = content[1..].trim_start();
+            let column = indent + content.len() - rest.len();
+            if rest.is_empty() || rest.starts_with('#') {
+                self.pos += 1;
+                items.push(self.node(indent + 1));
+            } else if is_seq_item(rest) || split_key(rest).is_some() {
+                // A compact nested collection: re-read the item at the
+                // column its first entry starts in.
+                self.lines[self.pos] = format!("{}{rest}", " ".repeat(column));
+                items.push(self.node(column));
+            } else {
+                self.pos += 1;
+                items.push(self.inline(rest, indent));
+            }
+        }
+        Value::Array(items)
+    }
+
+    fn mapping(&mut self, indent: usize) -> Value {
+        let mut map = Map::new();
+        let mut merges = Vec::new();
+        while self.peek_indent() == Some(indent) {
+            let content = self.content().to_string();
+            if is_seq_item(&content) {
+                break;
+            }
+            self.pos += 1;
+            let Some((key, rest)) = split_key(&content) else {
+                continue;
+            };
+            let value = self.value(rest, indent);
+            if key == "<<" {
+                merges.push(value);
+            } else {
+                map.insert(key, value);
+            }
+        }
+        // Explicit keys win over merged ones, wherever they appear.
+        for merge in merges {
+            merge_into(&mut map, merge);
+        }
+        Value::Object(map)
+    }
+
+    /// The value after `key:` on a line indented `parent`: inline, or the
+    /// block below it. A sequence may sit at the key's own indent.
+    fn value(&mut self, rest: &str, parent: usize) -> Value {
+        if !rest.is_empty() && !rest.starts_with('#') {
+            return self.inline(rest, parent);
+        }
+        match self.peek_indent() {
+            Some(next) if next > parent => self.node(next),
+            Some(next) if next == parent && is_seq_item(self.content()) => self.sequence(next),
+            _ => Value::Null,
+        }
+    }
+
+    /// A value written on the same line as its key or sequence dash.
+    fn inline(&mut self, text: &str, parent: usize) -> Value {
+        let text = text.trim();
+        if let Some(rest) = text.strip_prefix('!') {
+            let rest = rest.split_once(' ').map_or("", |(_, r)| r);
+            return self.value(rest.trim_start(), parent);
+        }
+        if let Some(rest) = text.strip_prefix('&') {
+            let (name, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+            let value = self.value(rest.trim_start(), parent);
+            self.anchors.insert(name.to_string(), value.clone());
+            return value;
+        }
+        if let Some(name) = text.strip_prefix('*') {
+            let name = strip_comment(name, false).trim();
+            return self.anchors.get(name).cloned().unwrap_or(Value::Null);
+        }
+        if text.starts_with('|') || text.starts_with('>') {
+            return self.block_scalar(text, parent);
+        }
+        if text.starts_with('[') || text.starts_with('{') {
+            let mut source = strip_comment(text, true).to_string();
+            while !is_balanced(&source) && self.peek_indent().is_some() {
+                source.push(' ');
+                source.push_str(strip_comment(self.content(), true));
+                self.pos += 1;
+            }
+            return Flow::new(&source, &self.anchors).value();
+        }
+        if text.starts_with('"') || text.starts_with('\'') {
+            let mut source = text.to_string();
+            while read_quoted(&source).is_none() && self.pos < self.lines.len() {
+                source.push(' ');
+                source.push_str(self.lines[self.pos].trim());
+                self.pos += 1;
+            }
+            return Value::String(read_quoted(&source).map(|(s, _)| s).unwrap_or(source));
+        }
+
+        // Plain scalars continue on more-indented lines, joined by spaces.
+        let mut scalar = strip_comment(text, false).trim_end().to_string();
+        while let Some(line) = self.lines.get(self.pos) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') || indent_of(line) <= parent {
+                break;
+            }
+            scalar.push(' ');
+            scalar.push_str(strip_comment(trimmed, false).trim_end());
+            self.pos += 1;
+        }
+        plain_scalar(&scalar)
+    }
+
+    /// A `|` literal or `>` folded block scalar; `header` is the indicator.
+    fn block_scalar(&mut self, header: &str, parent: usize) -> Value {
+        let literal = header.starts_with('|');
+        let indicators = strip_comment(&header[1..], false);
+        let mut lines: Vec<String> = Vec::new();
+        let mut block_indent: Option<usize> = None;
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.trim().is_empty() {
+                lines.push(String::new());
+                self.pos += 1;
+                continue;
+            }
+            let indent = indent_of(line);
+            if indent <= parent || block_indent.is_some_and(|b| indent < b) {
+                break;
+            }
+            let block = *block_indent.get_or_insert(indent);
+            lines.push(line[block..].to_string());
+            self.pos += 1;
+        }
+        let trailing = lines.iter().rev().take_while(|l| l.is_empty()).count();
+        lines.truncate(lines.len() - trailing);
+
+        let mut text = if literal {
+            lines.join("\n")
+        } else {
+            fold(&lines)
+        };
+        if indicators.contains('+') {
+            text.push_str(&"\n".repeat(trailing + 1));
+        } else if !indicators.contains('-') && !text.is_empty() {
+            text.push('\n');
+        }
+        Value::String(text)
+    }
+}
+
+/// Join folded-scalar lines: single breaks become spaces, blank lines
+/// become newlines.
+fn fold(lines: &[String]) -> String {
+    let mut out = String::new();
+    let mut pending_space = false;
+    for line in lines {
+        if line.is_empty() {
+            out.push('\n');
+            pending_space = false;
+        } else {
+            if pending_space {
+                out.push(' ');
+            }
+            out.push_str(line);
+            pending_space = true;
+        }
+    }
+    out
+}
+
+fn merge_into(map: &mut Map<String, Value>, merge: Value) {
+    match merge {
+        Value::Object(source) => {
+            for (key, value) in source {
+                map.entry(key).or_insert(value);
+            }
+        }
+        Value::Array(sources) => {
+            for source in sources {
+                merge_into(map, source);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_seq_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+/// Split `key: rest` off a block-mapping line. `None` when the line is not
+/// a mapping entry.
+fn split_key(content: &str) -> Option<(String, &str)> {
+    if content.starts_with(['"', '\'']) {
+        let (key, end) = read_quoted(content)?;
+        let rest = content[end..].trim_start().strip_prefix(':')?;
+        return (rest.is_empty() || rest.starts_with(' ')).then(|| (key, rest.trim_start()));
+    }
+    if content.starts_with(['[', '{', '|', '>', '*', '!', '&', '#']) {
+        return None;
+    }
+    let mut prev = ' ';
+    for (i, c) in content.char_indices() {
+        if c == '#' && prev == ' ' {
+            return None;
+        }
+        if c == ':' && i > 0 {
+            let rest = &content[i + 1..];
+            if rest.is_empty() || rest.starts_with(' ') {
+                return Some((content[..i].trim_end().to_string(), rest.trim_start()));
+            }
+        }
+        prev = c;
+    }
+    None
+}
+
+/// Drop a trailing ` # comment`. With `quotes`, a `#` inside quotes is kept.
+fn strip_comment(text: &str, quotes: bool) -> &str {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            None if quotes && (c == '"' || c == '\'') => quote = Some(c),
+            None if c == '#' && prev == ' ' => return text[..i].trim_end(),
+            Some(_) | None => {}
+        }
+        prev = c;
+    }
+    text
+}
+
+/// Whether every `[`/`{` outside quotes is closed.
+fn is_balanced(text: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    for c in text.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    depth <= 0
+}
+
+/// Read the quoted scalar at the start of `s`: its value and the byte
+/// length consumed. `None` when the closing quote is missing.
+fn read_quoted(s: &str) -> Option<(String, usize)> {
+    let mut chars = s.char_indices();
+    let (_, quote) = chars.next()?;
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            if quote == '\'' && s[i + 1..].starts_with('\'') {
+                chars.next();
+                out.push('\'');
+                continue;
+            }
+            return Some((out, i + 1));
+        }
+        if c == '\\' && quote == '"' {
+            if let Some((_, escaped)) = chars.next() {
+                out.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    '0' => '\0',
+                    other => other,
+                });
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    None
+}
+
+/// Type a plain scalar: null, booleans and numbers; anything else is a string.
+fn plain_scalar(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => return Value::Null,
+        "true" | "True" | "TRUE" => return Value::Bool(true),
+        "false" | "False" | "FALSE" => return Value::Bool(false),
+        _ => {}
+    }
+    if let Ok(n) = text.parse::<i64>() {
+        return Value::from(n);
+    }
+    let numeric = text
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+    if numeric && text.chars().any(|c| c.is_ascii_digit()) {
+        if let Ok(f) = text.parse::<f64>() {
+            return Value::from(f);
+        }
+    }
+    Value::String(text.to_string())
+}
+
+/// Reader for flow collections: `[a, b]`, `{k: v}`, possibly nested.
+struct Flow<'a> {
+    src: &'a str,
+    pos: usize,
+    anchors: &'a HashMap<String, Value>,
+}
+
+impl<'a> Flow<'a> {
+    fn new(src: &'a str, anchors: &'a HashMap<String, Value>) -> Self {
+        Self {
+            src,
+            pos: 0,
+            anchors,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.src[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn value(&mut self) -> Value {
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    match self.peek() {
+                        None => break,
+                        Some(']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(',') => self.pos += 1,
+                        Some(_) => items.push(self.value()),
+                    }
+                }
+                Value::Array(items)
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut map = Map::new();
+                let mut merges = Vec::new();
+                loop {
+                    match self.peek() {
+                        None => break,
+                        Some('}') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(',') => self.pos += 1,
+                        Some(_) => {
+                            let key = match self.scalar(true) {
+                                Value::String(s) => s,
+                                Value::Null => String::new(),
+                                other => other.to_string(),
+                            };
+                            let value = if self.peek() == Some(':') {
+                                self.pos += 1;
+                                self.value()
+                            } else {
+                                Value::Null
+                            };
+                            if key == "<<" {
+                                merges.push(value);
+                            } else {
+                                map.insert(key, value);
+                            }
+                        }
+                    }
+                }
+                for merge in merges {
+                    merge_into(&mut map, merge);
+                }
+                Value::Object(map)
+            }
+            _ => self.scalar(false),
+        }
+    }
+
+    /// A scalar up to the next separator; keys also stop at `: `.
+    fn scalar(&mut self, key: bool) -> Value {
+        let rest = &self.src[self.pos..];
+        if rest.starts_with(['"', '\'']) {
+            if let Some((text, end)) = read_quoted(rest) {
+                self.pos += end;
+                return Value::String(text);
+            }
+        }
+        let mut end = rest.len();
+        for (i, c) in rest.char_indices() {
+            let key_end = key && c == ':' && rest[i + 1..].starts_with([' ', ',', '}']);
+            if matches!(c, ',' | ']' | '}') || key_end || (key && c == ':' && i + 1 == rest.len()) {
+                end = i;
+                break;
+            }
+        }
+        self.pos += end;
+        let text = rest[..end].trim();
+        if let Some(name) = text.strip_prefix('*') {
+            return self.anchors.get(name).cloned().unwrap_or(Value::Null);
+        }
+        plain_scalar(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_workflow_shapes() {
+        let doc = parse(
+            "name: CI\n\
+             on:\n\
+             \x20 push:\n\
+             \x20   branches: [main]\n\
+             \x20   paths:\n\
+             \x20   - 'src/**'\n\
+             \x20   - \"Cargo.*\"  # manifests\n\
+             jobs:\n\
+             \x20 test:\n\
+             \x20   needs: [lint, build]\n\
+             \x20   steps:\n\
+             \x20     - uses: actions/checkout@v4\n\
+             \x20     - name: Test\n\
+             \x20       run: |\n\
+             \x20         cargo test\n\
+             \x20         cargo test --doc\n\
+             \n\
+             \x20     - run: >-\n\
+             \x20         echo one\n\
+             \x20         two\n",
+        );
+        assert_eq!(doc["on"]["push"]["branches"], json!(["main"]));
+        assert_eq!(doc["on"]["push"]["paths"], json!(["src/**", "Cargo.*"]));
+        assert_eq!(doc["jobs"]["test"]["needs"], json!(["lint", "build"]));
+        let steps = &doc["jobs"]["test"]["steps"];
+        assert_eq!(steps[0]["uses"], "actions/checkout@v4");
+        assert_eq!(steps[1]["run"], "cargo test\ncargo test --doc\n");
+        assert_eq!(steps[2]["run"], "echo one two");
+    }
+
+    #[test]
+    fn anchors_merges_and_flow_maps() {
+        let doc = parse(
+            ".base: &base\n\
+             \x20 image: rust\n\
+             \x20 tags: [docker, { arch: amd64 }]\n\
+             test:\n\
+             \x20 <<: *base\n\
+             \x20 image: rust:1.80\n\
+             \x20 script: !reference [.setup, script]\n\
+             list:\n\
+             - *base\n\
+             - - nested\n\
+             \x20 - seq\n\
+             - plain text\n\
+             \x20 continued\n\
+             count: 3\n\
+             enabled: true\n\
+             none: ~\n\
+             quoted: 'it''s'\n",
+        );
+        assert_eq!(doc["test"]["image"], "rust:1.80", "explicit key wins");
+        assert_eq!(doc["test"]["tags"], json!(["docker", {"arch": "amd64"}]));
+        assert_eq!(doc["test"]["script"], json!([".setup", "script"]));
+        assert_eq!(doc["list"][0]["image"], "rust");
+        assert_eq!(doc["list"][1], json!(["nested", "seq"]));
+        assert_eq!(doc["list"][2], "plain text continued");
+        assert_eq!(doc["count"], 3);
+        assert_eq!(doc["enabled"], true);
+        assert_eq!(doc["none"], Value::Null);
+        assert_eq!(doc["quoted"], "it's");
+    }
+
+    #[test]
+    fn multiple_documents_and_sequence_maps() {
+        let docs = parse_documents(
+            "%YAML 1.2\n---\na: 1\n---\n- name: x\n  deps: [a]\n- name: y\n...\n# trailing\n",
+        );
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0], json!({"a": 1}));
+        assert_eq!(
+            docs[1],
+            json!([{"name": "x", "deps": ["a"]}, {"name": "y"}])
+        );
+        assert_eq!(
+            parse("url: http://x.y/z#frag\n")["url"],
+            "http://x.y/z#frag"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! Extract → Analyze → Render pipeline over a [`store::HomerStore`].
 
 pub mod analyze;
+pub mod backfill;
 pub mod config;
 pub mod contracts;
 pub mod error;
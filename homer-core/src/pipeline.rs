@@ -4,26 +4,48 @@ use std::time::Instant;
 
 use tracing::{info, instrument, warn};
 
+use crate::analyze::api_surface::ApiSurfaceAnalyzer;
 use crate::analyze::behavioral::BehavioralAnalyzer;
 use crate::analyze::centrality::CentralityAnalyzer;
+use crate::analyze::clones::CloneAnalyzer;
 use crate::analyze::community::CommunityAnalyzer;
+use crate::analyze::complexity::ComplexityAnalyzer;
 use crate::analyze::convention::ConventionAnalyzer;
+use crate::analyze::dead_code::DeadCodeAnalyzer;
+use crate::analyze::hotness::RuntimeHotnessAnalyzer;
+use crate::analyze::knowledge::KnowledgeLossAnalyzer;
+use crate::analyze::ownership::OwnershipDriftAnalyzer;
 use crate::analyze::semantic::SemanticAnalyzer;
+use crate::analyze::survival::SurvivalAnalyzer;
 use crate::analyze::task_pattern::TaskPatternAnalyzer;
+use crate::analyze::teams::TeamAlignmentAnalyzer;
 use crate::analyze::temporal::TemporalAnalyzer;
+use crate::analyze::test_health::TestHealthAnalyzer;
 use crate::analyze::traits::Analyzer;
 use crate::config::{AnalysisDepth, HomerConfig};
+use crate::extract::api_contract::ContractExtractor;
+use crate::extract::ci::CiExtractor;
+use crate::extract::codeowners::CodeownersExtractor;
+use crate::extract::coverage::CoverageExtractor;
 use crate::extract::document::DocumentExtractor;
+use crate::extract::env::EnvExtractor;
+use crate::extract::flags::FlagExtractor;
 use crate::extract::git::GitExtractor;
 use crate::extract::github::GitHubExtractor;
 use crate::extract::gitlab::GitLabExtractor;
 use crate::extract::graph::GraphExtractor;
+use crate::extract::infra::InfraExtractor;
+use crate::extract::profile::ProfileExtractor;
 use crate::extract::prompt::PromptExtractor;
+use crate::extract::refactoring::RefactoringExtractor;
+use crate::extract::schema::SchemaExtractor;
 use crate::extract::structure::StructureExtractor;
+use crate::extract::test_results::TestResultsExtractor;
 use crate::extract::traits::Extractor;
 use crate::llm::providers::create_provider;
 use crate::progress::ProgressReporter;
 use crate::render::agents_md::AgentsMdRenderer;
+use crate::render::codeowners::CodeownersRenderer;
 use crate::render::module_context::ModuleContextRenderer;
 use crate::render::report::ReportRenderer;
 use crate::render::risk_map::RiskMapRenderer;
@@ -150,7 +172,18 @@ impl HomerPipeline {
             Box::new(GitExtractor::new(&self.repo_path)),
             Box::new(StructureExtractor::new(&self.repo_path)),
             Box::new(GraphExtractor::new(&self.repo_path)),
+            Box::new(RefactoringExtractor::new(&self.repo_path)),
             Box::new(DocumentExtractor::new(&self.repo_path)),
+            Box::new(CodeownersExtractor::new(&self.repo_path)),
+            Box::new(CiExtractor::new(&self.repo_path)),
+            Box::new(InfraExtractor::new(&self.repo_path, config)),
+            Box::new(ContractExtractor::new(&self.repo_path, config)),
+            Box::new(SchemaExtractor::new(&self.repo_path, config)),
+            Box::new(EnvExtractor::new(&self.repo_path, config)),
+            Box::new(FlagExtractor::new(&self.repo_path, config)),
+            Box::new(CoverageExtractor::new(&self.repo_path, config)),
+            Box::new(TestResultsExtractor::new(&self.repo_path, config)),
+            Box::new(ProfileExtractor::new(&self.repo_path, config)),
         ];
 
         if let Some(gh) = GitHubExtractor::from_repo(&self.repo_path, config) {
@@ -359,9 +392,19 @@ impl HomerPipeline {
     /// Build the list of analyzers to run, conditionally including semantic.
     fn build_analyzer_list(&self, config: &HomerConfig) -> Vec<Box<dyn Analyzer>> {
         let mut analyzers: Vec<Box<dyn Analyzer>> = vec![
-            Box::new(BehavioralAnalyzer),
+            Box::new(BehavioralAnalyzer::new(config)),
+            Box::new(SurvivalAnalyzer),
+            Box::new(DeadCodeAnalyzer::new(config)),
+            Box::new(ComplexityAnalyzer),
+            Box::new(CloneAnalyzer),
+            Box::new(ApiSurfaceAnalyzer::new(&self.repo_path, config)),
+            Box::new(RuntimeHotnessAnalyzer),
             Box::new(CentralityAnalyzer::default()),
             Box::new(CommunityAnalyzer),
+            Box::new(KnowledgeLossAnalyzer),
+            Box::new(TeamAlignmentAnalyzer::new(&self.repo_path)),
+            Box::new(OwnershipDriftAnalyzer),
+            Box::new(TestHealthAnalyzer),
             Box::new(TemporalAnalyzer),
             Box::new(ConventionAnalyzer::new(&self.repo_path)),
             Box::new(TaskPatternAnalyzer),
@@ -409,6 +452,11 @@ impl HomerPipeline {
         "topos-spec",
     ];
 
+    /// Renderers that only run when named explicitly, via `--format` or
+    /// `[renderers] enabled`. A generated CODEOWNERS changes who must
+    /// review pull requests, so it is never written unasked.
+    pub const OPT_IN_RENDERER_NAMES: &[&str] = &["codeowners"];
+
     /// Run only the named renderers against an existing store.
     ///
     /// Returns a `PipelineResult` with only the rendering fields populated.
@@ -443,7 +491,13 @@ impl HomerPipeline {
         config: &HomerConfig,
         result: &mut PipelineResult,
     ) {
-        self.run_selected_renderers(store, config, Self::ALL_RENDERER_NAMES, result)
+        let mut names = Self::ALL_RENDERER_NAMES.to_vec();
+        names.extend(
+            Self::OPT_IN_RENDERER_NAMES
+                .iter()
+                .filter(|name| config.renderers.enabled.iter().any(|e| e == *name)),
+        );
+        self.run_selected_renderers(store, config, &names, result)
             .await;
     }
 
@@ -456,6 +510,7 @@ impl HomerPipeline {
             "skills" => Some(Box::new(SkillsRenderer)),
             "report" => Some(Box::new(ReportRenderer)),
             "topos-spec" => Some(Box::new(ToposSpecRenderer)),
+            "codeowners" => Some(Box::new(CodeownersRenderer)),
             _ => None,
         }
     }
@@ -480,6 +535,7 @@ impl HomerPipeline {
                 "skills" => ("render:skills".into(), Box::new(SkillsRenderer)),
                 "report" => ("render:report".into(), Box::new(ReportRenderer)),
                 "topos-spec" => ("render:topos_spec".into(), Box::new(ToposSpecRenderer)),
+                "codeowners" => ("render:codeowners".into(), Box::new(CodeownersRenderer)),
                 unknown => {
                     result.errors.push(PipelineError {
                         stage: "render".into(),
@@ -665,6 +721,16 @@ mod tests {
             pos("behavioral") < pos("centrality"),
             "behavioral before centrality: {names:?}"
         );
+        // dead_code must come before centrality (salience excludes DeadCode)
+        assert!(
+            pos("dead_code") < pos("centrality"),
+            "dead_code before centrality: {names:?}"
+        );
+        // behavioral must come before complexity (hotspots need ChangeFrequency)
+        assert!(
+            pos("behavioral") < pos("complexity"),
+            "behavioral before complexity: {names:?}"
+        );
         // centrality must come before community (community requires CompositeSalience)
         assert!(
             pos("centrality") < pos("community"),
@@ -865,6 +931,24 @@ mod tests {
         assert_eq!(snapshots[1].label, "auto-6");
     }
 
+    #[tokio::test]
+    async fn codeowners_renderer_is_opt_in() {
+        let tmp = tempfile::tempdir().unwrap();
+        create_test_project(tmp.path());
+        let store = SqliteStore::in_memory().unwrap();
+        let pipeline = HomerPipeline::new(tmp.path());
+        let codeowners = tmp.path().join(".github/CODEOWNERS");
+
+        pipeline.run(&store, &HomerConfig::default()).await.unwrap();
+        assert!(!codeowners.exists(), "not written unless enabled");
+
+        let mut config = HomerConfig::default();
+        config.renderers.enabled.push("codeowners".to_string());
+        pipeline.run(&store, &config).await.unwrap();
+        let content = std::fs::read_to_string(&codeowners).unwrap();
+        assert!(content.contains("## Proposed owners"));
+    }
+
     #[tokio::test]
     async fn second_run_skips_unchanged_analysis() {
         let tmp = tempfile::tempdir().unwrap();
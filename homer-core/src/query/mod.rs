@@ -2,8 +2,10 @@
 //
 // Used by both the CLI `query` command and the MCP `homer_query` tool.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use crate::contracts::{find_member_by_roles, roles};
+use crate::extract::coverage::CoverageSummary;
 use crate::store::HomerStore;
 use crate::types::{AnalysisKind, HyperedgeKind, Node, NodeFilter, NodeId, NodeKind};
 
@@ -25,6 +27,11 @@ pub async fn find_entity(store: &dyn HomerStore, name: &str) -> crate::error::Re
         NodeKind::Type,
         NodeKind::Module,
         NodeKind::Document,
+        NodeKind::Table,
+        NodeKind::Column,
+        NodeKind::EnvVar,
+        NodeKind::ConfigKey,
+        NodeKind::FeatureFlag,
     ] {
         if let Some(node) = store.get_node_by_name(kind, name).await? {
             return Ok(Some(node));
@@ -138,6 +145,804 @@ pub async fn collect_neighbors_bfs(
     Ok(result)
 }
 
+// ── Task-scoped context retrieval ───────────────────────────────────
+
+/// Default token budget for a task-scoped context set.
+pub const DEFAULT_CONTEXT_BUDGET: u64 = 8000;
+/// Default number of entities in a task-scoped context set.
+pub const DEFAULT_CONTEXT_TOP: usize = 50;
+/// Restart probability complement for the context random walk.
+const CONTEXT_DAMPING: f64 = 0.85;
+/// Rough token cost per source line for functions and types.
+const TOKENS_PER_LINE: u64 = 10;
+/// Rough bytes-per-token ratio for whole files.
+const BYTES_PER_TOKEN: u64 = 4;
+
+/// A ranked entity selected for a task-scoped context set.
+#[derive(Debug, serde::Serialize)]
+pub struct ContextEntry {
+    pub name: String,
+    pub kind: String,
+    /// Personalized `PageRank` score relative to the seeds.
+    pub score: f64,
+    /// Estimated token cost of including this entity.
+    pub tokens: u64,
+    /// Whether this entity was one of the resolved seeds.
+    pub seed: bool,
+}
+
+/// A token-budgeted set of entities relevant to a set of seeds.
+#[derive(Debug, serde::Serialize)]
+pub struct ContextSet {
+    /// Seed names that resolved to at least one entity.
+    pub seeds: Vec<String>,
+    /// Seed names that matched nothing (by name or full-text search).
+    pub unresolved: Vec<String>,
+    pub budget: u64,
+    pub total_tokens: u64,
+    pub entries: Vec<ContextEntry>,
+}
+
+/// Rank entities near `seeds` with personalized `PageRank` and pack the best
+/// ones into `token_budget`.
+///
+/// Each seed is resolved with [`find_entity`]; seeds that match no entity are
+/// treated as free-text queries against the full-text index. The random walk
+/// runs over `Calls`, `Imports` and `BelongsTo` edges in both directions, so
+/// callers, callees, importers and containing files all receive mass.
+pub async fn task_context(
+    store: &dyn HomerStore,
+    seeds: &[String],
+    token_budget: u64,
+    max_entries: usize,
+) -> crate::error::Result<ContextSet> {
+    let (seed_weights, resolved, unresolved) = resolve_context_seeds(store, seeds).await?;
+    let graph = load_context_graph(store).await?;
+
+    let seed_indices: Vec<_> = seed_weights
+        .iter()
+        .filter_map(|(id, w)| graph.node_to_index.get(id).map(|&idx| (idx, *w)))
+        .collect();
+    let scores = crate::analyze::centrality::personalized_pagerank(
+        &graph.graph,
+        &seed_indices,
+        CONTEXT_DAMPING,
+        100,
+    );
+
+    let mut ranked: Vec<(NodeId, f64)> = graph
+        .index_to_node
+        .iter()
+        .filter_map(|(idx, &id)| scores.get(idx.index()).map(|&s| (id, s)))
+        .filter(|(_, s)| *s > 0.0)
+        .collect();
+    // Seeds outside the graph (e.g. documents) still belong in the context.
+    let total_seed_weight: f64 = seed_weights.values().sum();
+    for (&id, &w) in &seed_weights {
+        if !graph.node_to_index.contains_key(&id) {
+            ranked.push((id, w / total_seed_weight.max(f64::EPSILON)));
+        }
+    }
+    ranked.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.0.0.cmp(&b.0.0))
+    });
+
+    let mut entries = Vec::new();
+    let mut total_tokens = 0u64;
+    for (id, score) in ranked {
+        if entries.len() >= max_entries {
+            break;
+        }
+        let Some(node) = store.get_node(id).await? else {
+            continue;
+        };
+        let tokens = estimate_tokens(&node);
+        if total_tokens + tokens > token_budget {
+            continue;
+        }
+        total_tokens += tokens;
+        entries.push(ContextEntry {
+            name: node.name,
+            kind: node.kind.as_str().to_string(),
+            score,
+            tokens,
+            seed: seed_weights.contains_key(&id),
+        });
+    }
+
+    Ok(ContextSet {
+        seeds: resolved,
+        unresolved,
+        budget: token_budget,
+        total_tokens,
+        entries,
+    })
+}
+
+/// Resolve each seed to weighted entities, by name or by full-text search.
+async fn resolve_context_seeds(
+    store: &dyn HomerStore,
+    seeds: &[String],
+) -> crate::error::Result<(HashMap<NodeId, f64>, Vec<String>, Vec<String>)> {
+    let mut seed_weights: HashMap<NodeId, f64> = HashMap::new();
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+
+    for seed in seeds {
+        if let Some(node) = find_entity(store, seed).await? {
+            *seed_weights.entry(node.id).or_default() += 1.0;
+            resolved.push(seed.clone());
+            continue;
+        }
+
+        let Some(terms) = fts_terms(seed) else {
+            unresolved.push(seed.clone());
+            continue;
+        };
+        let hits = store
+            .search_text(
+                &terms,
+                crate::types::SearchScope {
+                    limit: Some(5),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        if hits.is_empty() {
+            unresolved.push(seed.clone());
+            continue;
+        }
+        // Hits are ordered by relevance; split the seed's weight by rank.
+        let rank_weights: Vec<f64> = std::iter::successors(Some(1.0_f64), |r| Some(r + 1.0))
+            .take(hits.len())
+            .map(|r| 1.0 / r)
+            .collect();
+        let total: f64 = rank_weights.iter().sum();
+        for (hit, w) in hits.iter().zip(&rank_weights) {
+            *seed_weights.entry(hit.node_id).or_default() += w / total;
+        }
+        resolved.push(seed.clone());
+    }
+    Ok((seed_weights, resolved, unresolved))
+}
+
+/// Turn free text into a full-text query matching any of its words, so
+/// punctuation in paths and identifiers is never parsed as query syntax.
+fn fts_terms(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{t}\""))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" OR "))
+}
+
+/// Build the undirected-in-effect graph the context walk runs over.
+async fn load_context_graph(
+    store: &dyn HomerStore,
+) -> crate::error::Result<crate::types::InMemoryGraph> {
+    let mut edges = Vec::new();
+    for kind in [
+        HyperedgeKind::Calls,
+        HyperedgeKind::Imports,
+        HyperedgeKind::BelongsTo,
+    ] {
+        edges.extend(
+            store
+                .get_edges_by_kind(kind)
+                .await?
+                .into_iter()
+                .filter(|e| e.members.len() >= 2),
+        );
+    }
+    let mut graph = crate::types::InMemoryGraph::from_edges(&edges);
+    let reversed: Vec<_> = graph
+        .graph
+        .edge_indices()
+        .filter_map(|e| {
+            let (src, dst) = graph.graph.edge_endpoints(e)?;
+            Some((dst, src, graph.graph[e]))
+        })
+        .collect();
+    for (src, dst, weight) in reversed {
+        graph.graph.add_edge(src, dst, weight);
+    }
+    Ok(graph)
+}
+
+/// Estimate how many tokens an entity costs to include in an agent's context.
+fn estimate_tokens(node: &Node) -> u64 {
+    match node.kind {
+        NodeKind::Function | NodeKind::Type => {
+            let span = node.metadata.get("span");
+            let row = |key: &str| {
+                span.and_then(|s| s.get(key))
+                    .and_then(serde_json::Value::as_u64)
+            };
+            match (row("start_row"), row("end_row")) {
+                (Some(start), Some(end)) => (end.saturating_sub(start) + 1) * TOKENS_PER_LINE,
+                _ => TOKENS_PER_LINE,
+            }
+        }
+        NodeKind::File | NodeKind::Document => node
+            .metadata
+            .get("size_bytes")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(TOKENS_PER_LINE, |b| (b / BYTES_PER_TOKEN).max(1)),
+        _ => (node.name.len() as u64 / BYTES_PER_TOKEN).max(1),
+    }
+}
+
+// ── Blast radius ────────────────────────────────────────────────────
+
+/// An entity that may be affected by a change to the blast-radius target.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ImpactedEntity {
+    pub name: String,
+    pub kind: String,
+    /// Product of edge confidences along the most likely dependency path.
+    pub probability: f64,
+    /// Number of reverse-dependency hops from the target.
+    pub depth: u32,
+    /// Community label of the entity's file, if community detection ran.
+    pub community: Option<String>,
+    /// Whether the entity's file has an associated test file.
+    pub covered: bool,
+}
+
+/// Affected entities that share a community.
+#[derive(Debug, serde::Serialize)]
+pub struct CommunityImpact {
+    pub community: String,
+    pub max_probability: f64,
+    pub entities: Vec<String>,
+}
+
+/// Transitive reverse-dependency impact of changing one entity.
+#[derive(Debug, serde::Serialize)]
+pub struct BlastRadius {
+    pub target: String,
+    pub min_probability: f64,
+    /// Affected non-test entities, most likely first.
+    pub affected: Vec<ImpactedEntity>,
+    /// Affected entities that live in test files.
+    pub tests: Vec<ImpactedEntity>,
+    /// Affected entities grouped by community.
+    pub communities: Vec<CommunityImpact>,
+    /// Names of affected entities whose files have no associated tests.
+    pub uncovered: Vec<String>,
+}
+
+/// Walk reverse `Calls` and `Imports` edges from `target` to find everything
+/// that transitively depends on it.
+///
+/// Each hop multiplies the path probability by the edge's confidence. Both
+/// limits are hard: an entity is affected when some path of at most
+/// `max_depth` hops keeps its probability at or above `min_probability`,
+/// and it reports the most likely such path. A function also lifts into its containing file so that importers of the
+/// file are reached, and a file target seeds its own functions so that their
+/// callers are reached.
+pub async fn blast_radius(
+    store: &dyn HomerStore,
+    target: &Node,
+    min_probability: f64,
+    max_depth: u32,
+) -> crate::error::Result<BlastRadius> {
+    let reverse = load_reverse_dependencies(store, min_probability).await?;
+
+    let mut best: HashMap<NodeId, (f64, u32)> = HashMap::new();
+    best.insert(target.id, (1.0, 0));
+    if target.kind == NodeKind::File {
+        for &(member, _) in reverse.members.get(&target.id).into_iter().flatten() {
+            best.insert(member, (1.0, 0));
+        }
+    }
+
+    // The target and its own members are the change, not its impact.
+    let seeds: HashSet<NodeId> = best.keys().copied().collect();
+
+    // Relax one hop at a time. A path only needs extending when it is more
+    // likely than every shorter path to the same node, since a shorter path
+    // at least as likely can reach everything it can.
+    let mut frontier: HashMap<NodeId, f64> = seeds.iter().map(|&id| (id, 1.0)).collect();
+    for depth in 1..=max_depth {
+        let mut next_frontier: HashMap<NodeId, f64> = HashMap::new();
+        for (&id, &prob) in &frontier {
+            for &(dependent, confidence) in reverse.dependents.get(&id).into_iter().flatten() {
+                let next = prob * confidence;
+                if next < min_probability {
+                    continue;
+                }
+                if best.get(&dependent).is_none_or(|&(p, _)| next > p) {
+                    best.insert(dependent, (next, depth));
+                    next_frontier.insert(dependent, next);
+                }
+            }
+        }
+        if next_frontier.is_empty() {
+            break;
+        }
+        frontier = next_frontier;
+    }
+
+    let coverage = load_test_coverage(store).await?;
+    let file_communities = load_file_communities(store).await?;
+
+    let mut affected = Vec::new();
+    let mut tests = Vec::new();
+    for (id, (probability, depth)) in best {
+        if seeds.contains(&id) {
+            continue;
+        }
+        let Some(node) = store.get_node(id).await? else {
+            continue;
+        };
+        let file_id = reverse.container.get(&id).copied().unwrap_or(id);
+        let file_name = node
+            .metadata
+            .get("file")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+            .or_else(|| node.name.rsplit_once("::").map(|(f, _)| f.to_string()))
+            .unwrap_or_else(|| node.name.clone());
+        let entity = ImpactedEntity {
+            kind: node.kind.as_str().to_string(),
+            probability,
+            depth,
+            community: file_communities.get(&file_id).cloned(),
+            covered: coverage.contains(&file_name),
+            name: node.name,
+        };
+        if crate::analyze::centrality::is_test_file(&file_name) {
+            tests.push(entity);
+        } else {
+            affected.push(entity);
+        }
+    }
+    let by_likelihood = |a: &ImpactedEntity, b: &ImpactedEntity| {
+        b.probability
+            .partial_cmp(&a.probability)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    };
+    affected.sort_by(by_likelihood);
+    tests.sort_by(by_likelihood);
+
+    let communities = group_by_community(&affected);
+
+    let uncovered = affected
+        .iter()
+        .filter(|e| !e.covered)
+        .map(|e| e.name.clone())
+        .collect();
+
+    Ok(BlastRadius {
+        target: target.name.clone(),
+        min_probability,
+        affected,
+        tests,
+        communities,
+        uncovered,
+    })
+}
+
+/// Group affected entities by community, largest groups first.
+fn group_by_community(affected: &[ImpactedEntity]) -> Vec<CommunityImpact> {
+    let mut groups: HashMap<String, CommunityImpact> = HashMap::new();
+    for entity in affected {
+        let label = entity
+            .community
+            .clone()
+            .unwrap_or_else(|| "unassigned".to_string());
+        let group = groups.entry(label.clone()).or_insert(CommunityImpact {
+            community: label,
+            max_probability: 0.0,
+            entities: Vec::new(),
+        });
+        group.max_probability = group.max_probability.max(entity.probability);
+        group.entities.push(entity.name.clone());
+    }
+    let mut communities: Vec<CommunityImpact> = groups.into_values().collect();
+    communities.sort_by(|a, b| {
+        b.entities
+            .len()
+            .cmp(&a.entities.len())
+            .then_with(|| a.community.cmp(&b.community))
+    });
+    communities
+}
+
+/// Reverse dependency adjacency used by [`blast_radius`].
+struct ReverseDependencies {
+    /// dependency → (dependent, confidence)
+    dependents: HashMap<NodeId, Vec<(NodeId, f64)>>,
+    /// container → (member, confidence)
+    members: HashMap<NodeId, Vec<(NodeId, f64)>>,
+    /// member → container
+    container: HashMap<NodeId, NodeId>,
+}
+
+/// Reverse dependency edges, dropping hops whose confidence alone is below
+/// `min_probability` since no path through them can stay above it.
+async fn load_reverse_dependencies(
+    store: &dyn HomerStore,
+    min_probability: f64,
+) -> crate::error::Result<ReverseDependencies> {
+    let mut deps = ReverseDependencies {
+        dependents: HashMap::new(),
+        members: HashMap::new(),
+        container: HashMap::new(),
+    };
+
+    for kind in [HyperedgeKind::Calls, HyperedgeKind::Imports] {
+        for edge in store.get_edges_by_kind(kind).await? {
+            if edge.members.len() < 2 {
+                continue;
+            }
+            let (source, target) = crate::types::extract_directed_pair(&edge.members);
+            if source != target && edge.confidence >= min_probability {
+                deps.dependents
+                    .entry(target)
+                    .or_default()
+                    .push((source, edge.confidence));
+            }
+        }
+    }
+
+    let files: HashSet<NodeId> = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(|n| n.id)
+        .collect();
+    for edge in store.get_edges_by_kind(HyperedgeKind::BelongsTo).await? {
+        let member = edge.members.iter().find(|m| m.role == "member");
+        let container = edge.members.iter().find(|m| m.role == "container");
+        let (Some(m), Some(c)) = (member, container) else {
+            continue;
+        };
+        // Only function/type → file containment; file → module is too coarse.
+        if deps.container.contains_key(&m.node_id) {
+            continue;
+        }
+        if !files.contains(&c.node_id) {
+            continue;
+        }
+        deps.container.insert(m.node_id, c.node_id);
+        deps.members
+            .entry(c.node_id)
+            .or_default()
+            .push((m.node_id, edge.confidence));
+        // A change inside a function is a change to its file.
+        if edge.confidence < min_probability {
+            continue;
+        }
+        deps.dependents
+            .entry(m.node_id)
+            .or_default()
+            .push((c.node_id, edge.confidence));
+    }
+
+    Ok(deps)
+}
+
+/// File paths considered covered by tests. Files an ingested coverage report
+/// covers count when any of their lines ran; other files count when they are
+/// test files, named after one, or hold a function a linked test exercises.
+async fn load_test_coverage(store: &dyn HomerStore) -> crate::error::Result<HashSet<String>> {
+    let files = store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        })
+        .await?;
+    let mut covered = HashSet::new();
+    let mut measured = HashMap::new();
+    for file in files {
+        if let Some(coverage) = CoverageSummary::of(&file) {
+            measured.insert(file.name.clone(), coverage.lines_covered > 0);
+        }
+        if crate::analyze::centrality::is_test_file(&file.name) {
+            if let Some(source) = crate::analyze::centrality::infer_source_from_test(&file.name) {
+                covered.insert(source);
+            }
+            covered.insert(file.name);
+        }
+    }
+    for edge in store.get_edges_by_kind(HyperedgeKind::Tests).await? {
+        let Some(tested) = find_member_by_roles(&edge.members, &[roles::TESTED]) else {
+            continue;
+        };
+        if let Some(node) = store.get_node(tested.node_id).await? {
+            covered.insert(function_file(&node));
+        }
+    }
+    for (file, ran) in measured {
+        if ran {
+            covered.insert(file);
+        } else {
+            covered.remove(&file);
+        }
+    }
+    Ok(covered)
+}
+
+/// Map file node → community label from `CommunityAssignment` results.
+async fn load_file_communities(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<NodeId, String>> {
+    let results = store
+        .get_analyses_by_kind(AnalysisKind::CommunityAssignment)
+        .await?;
+    Ok(results
+        .into_iter()
+        .filter_map(|r| {
+            let label = match r.data.get("community_label").and_then(|v| v.as_str()) {
+                Some(label) => label.to_string(),
+                None => format!("community {}", r.data.get("community_id")?.as_u64()?),
+            };
+            Some((r.node_id, label))
+        })
+        .collect())
+}
+
+// ── Path finding ────────────────────────────────────────────────────
+
+/// One directed hop along a path between two entities.
+#[derive(Debug, serde::Serialize)]
+pub struct PathHop {
+    pub from: String,
+    pub to: String,
+    /// Edge kind: `Calls`, `Imports` or `Inherits`.
+    pub edge: String,
+    pub confidence: f64,
+    /// File containing the source side of the hop.
+    pub file: Option<String>,
+    /// 1-based line span of the source entity, when known.
+    pub start_line: Option<u64>,
+    pub end_line: Option<u64>,
+    /// 1-based line of the call site, for `Calls` edges that record one.
+    pub site_line: Option<u64>,
+}
+
+/// A directed path between two entities.
+#[derive(Debug, serde::Serialize)]
+pub struct EntityPath {
+    /// Product of edge confidences along the path.
+    pub confidence: f64,
+    pub hops: Vec<PathHop>,
+}
+
+/// Find up to `k` shortest directed paths from `from` to `to` over `Calls`,
+/// `Imports` and `Inherits` edges.
+///
+/// Paths are ranked by hop count, then by the product of their edge
+/// confidences. Edges below `min_confidence` are ignored, which keeps
+/// heuristic name-matched calls out of the result when set high enough.
+pub async fn shortest_paths(
+    store: &dyn HomerStore,
+    from: &Node,
+    to: &Node,
+    k: usize,
+    min_confidence: f64,
+) -> crate::error::Result<Vec<EntityPath>> {
+    let mut edges = Vec::new();
+    for kind in [
+        HyperedgeKind::Calls,
+        HyperedgeKind::Imports,
+        HyperedgeKind::Inherits,
+    ] {
+        edges.extend(
+            store
+                .get_edges_by_kind(kind)
+                .await?
+                .into_iter()
+                .filter(|e| e.members.len() >= 2 && e.confidence >= min_confidence),
+        );
+    }
+
+    // Keep the most confident edge per directed pair for hop annotations.
+    let mut best_edge: HashMap<(NodeId, NodeId), &crate::types::Hyperedge> = HashMap::new();
+    for edge in &edges {
+        let pair = crate::types::extract_directed_pair(&edge.members);
+        let slot = best_edge.entry(pair).or_insert(edge);
+        if edge.confidence > slot.confidence {
+            *slot = edge;
+        }
+    }
+
+    let graph = crate::types::InMemoryGraph::from_edges(&edges);
+    let (Some(&src), Some(&dst)) = (
+        graph.node_to_index.get(&from.id),
+        graph.node_to_index.get(&to.id),
+    ) else {
+        return Ok(Vec::new());
+    };
+    if src == dst {
+        return Ok(Vec::new());
+    }
+
+    let mut result = Vec::new();
+    for indices in yen_k_shortest(&graph.graph, src, dst, k) {
+        let ids: Vec<NodeId> = indices.iter().map(|&i| graph.graph[i]).collect();
+        let mut hops = Vec::with_capacity(ids.len() - 1);
+        let mut confidence = 1.0;
+        for pair in ids.windows(2) {
+            let Some(edge) = best_edge.get(&(pair[0], pair[1])) else {
+                continue;
+            };
+            confidence *= edge.confidence;
+            hops.push(annotate_hop(store, pair[0], pair[1], edge).await?);
+        }
+        result.push(EntityPath { confidence, hops });
+    }
+    Ok(result)
+}
+
+/// Describe a hop with the source entity's location and the edge's confidence.
+async fn annotate_hop(
+    store: &dyn HomerStore,
+    from: NodeId,
+    to: NodeId,
+    edge: &crate::types::Hyperedge,
+) -> crate::error::Result<PathHop> {
+    let source = store.get_node(from).await?;
+    let to_name = resolve_name(store, to).await;
+
+    let (from_name, file, span) = match &source {
+        Some(node) => {
+            let file = match node.kind {
+                NodeKind::File => Some(node.name.clone()),
+                _ => node
+                    .metadata
+                    .get("file")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string),
+            };
+            (node.name.clone(), file, node.metadata.get("span"))
+        }
+        None => (format!("node:{}", from.0), None, None),
+    };
+    let row = |value: Option<&serde_json::Value>, key: &str| {
+        value
+            .and_then(|s| s.get(key))
+            .and_then(serde_json::Value::as_u64)
+            .map(|r| r + 1)
+    };
+
+    Ok(PathHop {
+        from: from_name,
+        to: to_name,
+        edge: edge.kind.as_str().to_string(),
+        confidence: edge.confidence,
+        file,
+        start_line: row(span, "start_row"),
+        end_line: row(span, "end_row"),
+        site_line: row(edge.metadata.get("span"), "start_row"),
+    })
+}
+
+/// Yen's algorithm over hop counts, breaking ties by confidence product.
+fn yen_k_shortest(
+    graph: &petgraph::graph::DiGraph<NodeId, f64>,
+    src: petgraph::graph::NodeIndex,
+    dst: petgraph::graph::NodeIndex,
+    k: usize,
+) -> Vec<Vec<petgraph::graph::NodeIndex>> {
+    use petgraph::graph::NodeIndex;
+
+    let path_confidence = |path: &[NodeIndex]| -> f64 {
+        path.windows(2)
+            .map(|w| {
+                graph
+                    .edges_connecting(w[0], w[1])
+                    .map(|e| *e.weight())
+                    .fold(0.0, f64::max)
+            })
+            .product()
+    };
+
+    let mut accepted: Vec<Vec<NodeIndex>> = Vec::new();
+    let Some(first) = bfs_path(graph, src, dst, &HashSet::new(), &HashSet::new()) else {
+        return accepted;
+    };
+    accepted.push(first);
+    let mut candidates: Vec<Vec<NodeIndex>> = Vec::new();
+
+    while accepted.len() < k {
+        let last = accepted.last().cloned().unwrap_or_default();
+        for i in 0..last.len().saturating_sub(1) {
+            let spur = last[i];
+            let root = &last[..=i];
+
+            let banned_edges: HashSet<(NodeIndex, NodeIndex)> = accepted
+                .iter()
+                .filter(|p| p.len() > i + 1 && &p[..=i] == root)
+                .map(|p| (p[i], p[i + 1]))
+                .collect();
+            let banned_nodes: HashSet<NodeIndex> = root[..i].iter().copied().collect();
+
+            if let Some(spur_path) = bfs_path(graph, spur, dst, &banned_nodes, &banned_edges) {
+                let mut path = root[..i].to_vec();
+                path.extend(spur_path);
+                if !accepted.contains(&path) && !candidates.contains(&path) {
+                    candidates.push(path);
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_by(|a, b| {
+            a.len().cmp(&b.len()).then_with(|| {
+                path_confidence(b)
+                    .partial_cmp(&path_confidence(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+        accepted.push(candidates.remove(0));
+    }
+    accepted
+}
+
+/// Breadth-first shortest path that avoids the given nodes and edges.
+///
+/// Among paths with the fewest hops, the one with the highest confidence
+/// product wins: every node at depth `d` is settled before any at `d + 1`.
+fn bfs_path(
+    graph: &petgraph::graph::DiGraph<NodeId, f64>,
+    src: petgraph::graph::NodeIndex,
+    dst: petgraph::graph::NodeIndex,
+    banned_nodes: &HashSet<petgraph::graph::NodeIndex>,
+    banned_edges: &HashSet<(petgraph::graph::NodeIndex, petgraph::graph::NodeIndex)>,
+) -> Option<Vec<petgraph::graph::NodeIndex>> {
+    use petgraph::visit::EdgeRef;
+
+    // node → (depth, best confidence product, parent)
+    let mut best = HashMap::from([(src, (0usize, 1.0_f64, None))]);
+    let mut queue = std::collections::VecDeque::from([src]);
+
+    while let Some(node) = queue.pop_front() {
+        if node == dst {
+            break;
+        }
+        let (depth, confidence, _) = best[&node];
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if banned_nodes.contains(&next) || banned_edges.contains(&(node, next)) {
+                continue;
+            }
+            let candidate = confidence * edge.weight();
+            match best.get_mut(&next) {
+                None => {
+                    best.insert(next, (depth + 1, candidate, Some(node)));
+                    queue.push_back(next);
+                }
+                Some(entry) if entry.0 == depth + 1 && candidate > entry.1 => {
+                    *entry = (depth + 1, candidate, Some(node));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    best.get(&dst)?;
+    let mut path = vec![dst];
+    let mut current = dst;
+    while let Some(&(_, _, Some(prev))) = best.get(&current) {
+        path.push(prev);
+        current = prev;
+    }
+    path.reverse();
+    Some(path)
+}
+
 // ── Diff impact assessment ──────────────────────────────────────────
 
 /// Impact assessment result for a set of changed files.
@@ -148,6 +953,10 @@ pub struct DiffImpact {
     pub low_bus_factor: Vec<(String, u64)>,
     pub communities_affected: Vec<String>,
     pub modules_affected: Vec<String>,
+    /// Tests exercising the changed code, closest first.
+    pub tests_to_run: Vec<CoveringTest>,
+    /// Contract elements the change reaches and the services bound to them.
+    pub contract_impact: ContractBlastRadius,
 }
 
 /// Impact details for a single file.
@@ -159,6 +968,7 @@ pub struct FileImpact {
 }
 
 /// Assess the impact of a set of changed file paths against the Homer store.
+#[allow(clippy::too_many_lines)]
 pub async fn diff_impact(
     store: &dyn HomerStore,
     changed_paths: &[String],
@@ -265,15 +1075,999 @@ pub async fn diff_impact(
     modules.sort();
     modules.dedup();
 
+    let tests_to_run = tests_for_paths(store, changed_paths).await?;
+    let contract_impact = contract_blast_radius(store, changed_paths).await?;
+
     Ok(DiffImpact {
         files_analyzed: changed_set.len(),
         high_salience,
         low_bus_factor,
         communities_affected: communities,
         modules_affected: modules,
+        tests_to_run,
+        contract_impact,
     })
 }
 
+// ── Covering tests ──────────────────────────────────────────────────
+
+/// A test linked to code by a `Tests` edge.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CoveringTest {
+    pub name: String,
+    pub file: String,
+    pub framework: String,
+    /// Call hops from the test to the nearest exercised function; 0 for a
+    /// test that was itself changed.
+    pub depth: u32,
+}
+
+/// Tests exercising `node`: a function's own `Tests` edges, or those of every
+/// function in a file. Closest tests come first.
+pub async fn tests_covering(
+    store: &dyn HomerStore,
+    node: &Node,
+) -> crate::error::Result<Vec<CoveringTest>> {
+    let targets: HashSet<NodeId> = match node.kind {
+        NodeKind::Function => HashSet::from([node.id]),
+        NodeKind::File => functions_in_file(store, &node.name)
+            .await?
+            .into_iter()
+            .map(|f| f.id)
+            .collect(),
+        _ => return Ok(Vec::new()),
+    };
+    collect_covering_tests(store, &targets, HashMap::new()).await
+}
+
+/// Tests to run for a set of changed files: every test exercising a function
+/// in a changed file, plus the tests defined in changed files themselves.
+pub async fn tests_for_paths(
+    store: &dyn HomerStore,
+    changed_paths: &[String],
+) -> crate::error::Result<Vec<CoveringTest>> {
+    let mut targets = HashSet::new();
+    let mut changed_tests = HashMap::new();
+    for path in changed_paths {
+        for function in functions_in_file(store, path).await? {
+            if let Some(framework) = test_framework(&function) {
+                changed_tests.insert(function.id, (function, framework));
+            } else {
+                targets.insert(function.id);
+            }
+        }
+    }
+    collect_covering_tests(store, &targets, changed_tests).await
+}
+
+async fn functions_in_file(store: &dyn HomerStore, path: &str) -> crate::error::Result<Vec<Node>> {
+    store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Function),
+            name_prefix: Some(format!("{path}::")),
+            ..Default::default()
+        })
+        .await
+}
+
+/// Framework of a function the graph extractor identified as a test.
+fn test_framework(node: &Node) -> Option<String> {
+    node.metadata
+        .get("test")?
+        .get("framework")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// File a function node belongs to, from metadata or its `file::name` form.
+fn function_file(node: &Node) -> String {
+    node.metadata
+        .get("file")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .or_else(|| node.name.split_once("::").map(|(f, _)| f.to_string()))
+        .unwrap_or_else(|| node.name.clone())
+}
+
+async fn collect_covering_tests(
+    store: &dyn HomerStore,
+    targets: &HashSet<NodeId>,
+    direct: HashMap<NodeId, (Node, String)>,
+) -> crate::error::Result<Vec<CoveringTest>> {
+    let mut best: HashMap<NodeId, u32> = direct.keys().map(|&id| (id, 0)).collect();
+    if !targets.is_empty() {
+        for edge in store.get_edges_by_kind(HyperedgeKind::Tests).await? {
+            let test = find_member_by_roles(&edge.members, &[roles::TEST]);
+            let tested = find_member_by_roles(&edge.members, &[roles::TESTED]);
+            let (Some(test), Some(tested)) = (test, tested) else {
+                continue;
+            };
+            if !targets.contains(&tested.node_id) {
+                continue;
+            }
+            let depth = edge
+                .metadata
+                .get("depth")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|d| u32::try_from(d).ok())
+                .unwrap_or(1);
+            let entry = best.entry(test.node_id).or_insert(depth);
+            *entry = (*entry).min(depth);
+        }
+    }
+
+    let mut tests = Vec::with_capacity(best.len());
+    for (id, depth) in best {
+        let (node, framework) = if let Some((node, framework)) = direct.get(&id) {
+            (node.clone(), framework.clone())
+        } else {
+            let Some(node) = store.get_node(id).await? else {
+                continue;
+            };
+            let framework = test_framework(&node).unwrap_or_default();
+            (node, framework)
+        };
+        tests.push(CoveringTest {
+            file: function_file(&node),
+            name: node.name,
+            framework,
+            depth,
+        });
+    }
+    tests.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.name.cmp(&b.name)));
+    Ok(tests)
+}
+
+// ── Contract blast radius ───────────────────────────────────────────
+
+/// Hops followed back along `References` edges from a changed element.
+const CONTRACT_REFERENCE_DEPTH: usize = 4;
+
+/// A contract element a change reaches, with the code bound to it.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ContractImpact {
+    /// Node name, `<schema file>::<element>`.
+    pub element: String,
+    /// `endpoint` or `type`.
+    pub kind: String,
+    /// `protobuf`, `openapi` or `graphql`.
+    pub format: String,
+    /// Whether the element is declared in a changed file; otherwise it
+    /// refers, directly or transitively, to an element that is.
+    pub changed: bool,
+    /// Functions, types and files bound to the element.
+    pub bound_code: Vec<String>,
+}
+
+/// Code in one service bound to contract elements a change reaches.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ServiceImpact {
+    /// Label of the deployment resource built from the code, or the code's
+    /// top-level directory when no resource builds it.
+    pub service: String,
+    pub languages: Vec<String>,
+    pub files: Vec<String>,
+    /// Contract elements the service's code is bound to.
+    pub elements: Vec<String>,
+}
+
+/// Cross-service impact of changing contract schemas.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct ContractBlastRadius {
+    /// Changed elements first, then those referring to them.
+    pub elements: Vec<ContractImpact>,
+    pub services: Vec<ServiceImpact>,
+}
+
+/// Contract elements declared in `changed_paths`, the elements that refer to
+/// them through `References` edges, and the code `Binds` edges tie to any of
+/// them, grouped by the service that code belongs to.
+pub async fn contract_blast_radius(
+    store: &dyn HomerStore,
+    changed_paths: &[String],
+) -> crate::error::Result<ContractBlastRadius> {
+    let reached = reached_contract_elements(store, changed_paths).await?;
+    if reached.is_empty() {
+        return Ok(ContractBlastRadius::default());
+    }
+
+    let mut bound: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Binds).await? {
+        let contract = find_member_by_roles(&edge.members, &[roles::CONTRACT_ELEMENT]);
+        let code = find_member_by_roles(&edge.members, &[roles::BOUND_CODE]);
+        if let (Some(contract), Some(code)) = (contract, code) {
+            if reached.contains_key(&contract.node_id) {
+                bound
+                    .entry(contract.node_id)
+                    .or_default()
+                    .push(code.node_id);
+            }
+        }
+    }
+
+    let sources = load_service_sources(store).await?;
+    let mut services: BTreeMap<String, [BTreeSet<String>; 3]> = BTreeMap::new();
+    let mut elements = Vec::with_capacity(reached.len());
+    for (id, (node, changed)) in &reached {
+        let mut bound_code = Vec::new();
+        for &code_id in bound.get(id).into_iter().flatten() {
+            let Some(code) = store.get_node(code_id).await? else {
+                continue;
+            };
+            let file = if code.kind == NodeKind::File {
+                code.name.clone()
+            } else {
+                function_file(&code)
+            };
+            let service = service_for(&sources, &file);
+            let [languages, files, bound_elements] = services.entry(service).or_default();
+            if let Some(language) = store
+                .get_node_by_name(NodeKind::File, &file)
+                .await?
+                .and_then(|f| {
+                    f.metadata
+                        .get("language")
+                        .and_then(serde_json::Value::as_str)
+                        .map(String::from)
+                })
+            {
+                languages.insert(language);
+            }
+            files.insert(file);
+            bound_elements.insert(node.name.clone());
+            bound_code.push(code.name);
+        }
+        bound_code.sort();
+        elements.push(ContractImpact {
+            element: node.name.clone(),
+            kind: if node.kind == NodeKind::Endpoint {
+                "endpoint".to_string()
+            } else {
+                "type".to_string()
+            },
+            format: node
+                .metadata
+                .get("contract")
+                .and_then(|c| c.get("format"))
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            changed: *changed,
+            bound_code,
+        });
+    }
+    elements.sort_by(|a, b| {
+        b.changed
+            .cmp(&a.changed)
+            .then_with(|| a.element.cmp(&b.element))
+    });
+
+    Ok(ContractBlastRadius {
+        elements,
+        services: services
+            .into_iter()
+            .map(|(service, [languages, files, elements])| ServiceImpact {
+                service,
+                languages: languages.into_iter().collect(),
+                files: files.into_iter().collect(),
+                elements: elements.into_iter().collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Contract elements declared in `changed_paths` (marked `true`) and those
+/// referring to them within [`CONTRACT_REFERENCE_DEPTH`] hops.
+async fn reached_contract_elements(
+    store: &dyn HomerStore,
+    changed_paths: &[String],
+) -> crate::error::Result<HashMap<NodeId, (Node, bool)>> {
+    let mut reached: HashMap<NodeId, (Node, bool)> = HashMap::new();
+    for path in changed_paths {
+        for kind in [NodeKind::Endpoint, NodeKind::Type] {
+            let nodes = store
+                .find_nodes(&NodeFilter {
+                    kind: Some(kind),
+                    name_prefix: Some(format!("{path}::")),
+                    ..Default::default()
+                })
+                .await?;
+            for node in nodes {
+                if node.metadata.contains_key("contract") && !node.metadata.contains_key("stale") {
+                    reached.insert(node.id, (node, true));
+                }
+            }
+        }
+    }
+    if reached.is_empty() {
+        return Ok(reached);
+    }
+
+    let mut referrers: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::References).await? {
+        let from = find_member_by_roles(&edge.members, &[roles::REFERRER]);
+        let to = find_member_by_roles(&edge.members, &[roles::REFERENCED]);
+        if let (Some(from), Some(to)) = (from, to) {
+            referrers.entry(to.node_id).or_default().push(from.node_id);
+        }
+    }
+    let mut frontier: Vec<NodeId> = reached.keys().copied().collect();
+    for _ in 0..CONTRACT_REFERENCE_DEPTH {
+        let mut next = Vec::new();
+        for id in frontier {
+            for &referrer in referrers.get(&id).into_iter().flatten() {
+                if reached.contains_key(&referrer) {
+                    continue;
+                }
+                if let Some(node) = store.get_node(referrer).await? {
+                    reached.insert(referrer, (node, false));
+                    next.push(referrer);
+                }
+            }
+        }
+        frontier = next;
+    }
+    Ok(reached)
+}
+
+/// Source paths and the label of the deployment resource built from them,
+/// from `Builds` edges, longest path first. The root module is the empty
+/// path.
+async fn load_service_sources(
+    store: &dyn HomerStore,
+) -> crate::error::Result<Vec<(String, String)>> {
+    let mut sources = Vec::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Builds).await? {
+        let Some(resource) = find_member_by_roles(&edge.members, &[roles::BUILT_RESOURCE]) else {
+            continue;
+        };
+        let Some(resource) = store.get_node(resource.node_id).await? else {
+            continue;
+        };
+        let label = resource
+            .metadata
+            .get("label")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or(&resource.name)
+            .to_string();
+        for member in edge
+            .members
+            .iter()
+            .filter(|m| m.role == roles::BUILD_SOURCE)
+        {
+            if let Some(source) = store.get_node(member.node_id).await? {
+                let path = if source.metadata.contains_key("is_root") {
+                    String::new()
+                } else {
+                    source.name
+                };
+                sources.push((path, label.clone()));
+            }
+        }
+    }
+    sources.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.cmp(b)));
+    Ok(sources)
+}
+
+/// The service a file belongs to: the resource built from the closest source
+/// path holding it, else its top-level directory.
+fn service_for(sources: &[(String, String)], file: &str) -> String {
+    sources
+        .iter()
+        .find(|(path, _)| {
+            path.is_empty()
+                || file == path
+                || file
+                    .strip_prefix(path.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .map_or_else(
+            || file.split_once('/').map_or(".", |(top, _)| top).to_string(),
+            |(_, label)| label.clone(),
+        )
+}
+
+// ── Schema access ───────────────────────────────────────────────────
+
+/// Code reading or writing a table or column, from an `Accesses` edge.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SchemaAccess {
+    /// Function, type or file doing the access.
+    pub code: String,
+    /// Table, or column as `table.column`.
+    pub target: String,
+    /// `sql` for SQL in a string literal, `model` for an ORM model.
+    pub via: String,
+}
+
+/// Schema accesses around `node`: the code touching a table or any of its
+/// columns, the code touching a column, or the tables and columns a
+/// function, type, or file's code touches.
+pub async fn schema_accesses(
+    store: &dyn HomerStore,
+    node: &Node,
+) -> crate::error::Result<Vec<SchemaAccess>> {
+    let (ids, role) = match node.kind {
+        NodeKind::Table => {
+            let columns = store
+                .find_nodes(&NodeFilter {
+                    kind: Some(NodeKind::Column),
+                    name_prefix: Some(format!("{}.", node.name)),
+                    ..Default::default()
+                })
+                .await?;
+            let mut ids = vec![node.id];
+            ids.extend(columns.iter().map(|c| c.id));
+            (ids, roles::ACCESSED)
+        }
+        NodeKind::Column => (vec![node.id], roles::ACCESSED),
+        NodeKind::Function | NodeKind::Type => (vec![node.id], roles::ACCESSOR),
+        NodeKind::File => {
+            let mut ids = vec![node.id];
+            for kind in [NodeKind::Function, NodeKind::Type] {
+                let code = store
+                    .find_nodes(&NodeFilter {
+                        kind: Some(kind),
+                        name_prefix: Some(format!("{}::", node.name)),
+                        ..Default::default()
+                    })
+                    .await?;
+                ids.extend(code.iter().map(|c| c.id));
+            }
+            (ids, roles::ACCESSOR)
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut seen = HashSet::new();
+    let mut accesses = Vec::new();
+    for id in ids {
+        for edge in store.get_edges_involving(id).await? {
+            if edge.kind != HyperedgeKind::Accesses
+                || !edge
+                    .members
+                    .iter()
+                    .any(|m| m.node_id == id && m.role == role)
+                || !seen.insert(edge.id)
+            {
+                continue;
+            }
+            let (Some(code), Some(target)) = (
+                find_member_by_roles(&edge.members, &[roles::ACCESSOR]),
+                find_member_by_roles(&edge.members, &[roles::ACCESSED]),
+            ) else {
+                continue;
+            };
+            accesses.push(SchemaAccess {
+                code: resolve_name(store, code.node_id).await,
+                target: resolve_name(store, target.node_id).await,
+                via: edge
+                    .metadata
+                    .get("via")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+    }
+    accesses.sort_by(|a, b| a.target.cmp(&b.target).then_with(|| a.code.cmp(&b.code)));
+    Ok(accesses)
+}
+
+// ── Configuration usage ─────────────────────────────────────────────
+
+/// An environment variable or config key with the code reading it and the
+/// files, CI jobs, and resources defining it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ConfigUsage {
+    pub variable: String,
+    /// `env` for an environment variable, `config` for a config key.
+    pub kind: String,
+    pub readers: Vec<String>,
+    pub definers: Vec<String>,
+}
+
+/// Configuration usage around `node`: the variable or key itself, or every
+/// variable and key a function or file's code reads.
+pub async fn config_usage(
+    store: &dyn HomerStore,
+    node: &Node,
+) -> crate::error::Result<Vec<ConfigUsage>> {
+    let variables = match node.kind {
+        NodeKind::EnvVar | NodeKind::ConfigKey => vec![node.clone()],
+        NodeKind::Function | NodeKind::File => {
+            let mut readers = vec![node.id];
+            if node.kind == NodeKind::File {
+                let functions = store
+                    .find_nodes(&NodeFilter {
+                        kind: Some(NodeKind::Function),
+                        name_prefix: Some(format!("{}::", node.name)),
+                        ..Default::default()
+                    })
+                    .await?;
+                readers.extend(functions.iter().map(|f| f.id));
+            }
+            let mut seen = HashSet::new();
+            let mut variables = Vec::new();
+            for id in readers {
+                for edge in store.get_edges_involving(id).await? {
+                    if edge.kind != HyperedgeKind::Reads
+                        || !edge
+                            .members
+                            .iter()
+                            .any(|m| m.node_id == id && m.role == roles::READER)
+                    {
+                        continue;
+                    }
+                    if let Some(var) = find_member_by_roles(&edge.members, &[roles::READ_VAR])
+                        && seen.insert(var.node_id)
+                        && let Some(var) = store.get_node(var.node_id).await?
+                    {
+                        variables.push(var);
+                    }
+                }
+            }
+            variables
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut usages = Vec::new();
+    for variable in variables {
+        let mut readers = BTreeSet::new();
+        let mut definers = BTreeSet::new();
+        for edge in store.get_edges_involving(variable.id).await? {
+            let (names, role) = match edge.kind {
+                HyperedgeKind::Reads => (&mut readers, roles::READER),
+                HyperedgeKind::Defines => (&mut definers, roles::DEFINER),
+                _ => continue,
+            };
+            if let Some(member) = find_member_by_roles(&edge.members, &[role]) {
+                names.insert(resolve_name(store, member.node_id).await);
+            }
+        }
+        usages.push(ConfigUsage {
+            kind: if variable.kind == NodeKind::EnvVar {
+                "env"
+            } else {
+                "config"
+            }
+            .to_string(),
+            variable: variable.name,
+            readers: readers.into_iter().collect(),
+            definers: definers.into_iter().collect(),
+        });
+    }
+    usages.sort_by(|a, b| a.variable.cmp(&b.variable));
+    Ok(usages)
+}
+
+// ── Feature flags ───────────────────────────────────────────────────
+
+/// Salience above which code checking an old flag makes it worth removing.
+const FLAG_CHECKER_SALIENCE: f64 = 0.3;
+
+/// Composite salience of a function, type, or file, falling back to its
+/// file's salience when the code itself has none.
+pub async fn code_salience(
+    store: &dyn HomerStore,
+    node: &Node,
+) -> crate::error::Result<Option<f64>> {
+    let salience_of =
+        |data: &serde_json::Value| data.get("score").and_then(serde_json::Value::as_f64);
+    if let Some(s) = store
+        .get_analysis(node.id, AnalysisKind::CompositeSalience)
+        .await?
+        .and_then(|r| salience_of(&r.data))
+    {
+        return Ok(Some(s));
+    }
+    let file = node
+        .metadata
+        .get("file")
+        .and_then(serde_json::Value::as_str)
+        .map_or_else(
+            || node.name.split("::").next().unwrap_or_default().to_string(),
+            str::to_string,
+        );
+    let Some(file_node) = store.get_node_by_name(NodeKind::File, &file).await? else {
+        return Ok(None);
+    };
+    Ok(store
+        .get_analysis(file_node.id, AnalysisKind::CompositeSalience)
+        .await?
+        .and_then(|r| salience_of(&r.data)))
+}
+
+/// Code evaluating a feature flag.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FlagChecker {
+    pub name: String,
+    pub salience: Option<f64>,
+}
+
+/// A feature flag with its age and the code checking it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FlagStatus {
+    pub flag: String,
+    /// When the flag first appeared in history (RFC 3339), if known.
+    pub introduced: Option<String>,
+    pub last_changed: Option<String>,
+    /// Whole days since the flag appeared.
+    pub age_days: Option<i64>,
+    /// Checking code, most salient first.
+    pub checkers: Vec<FlagChecker>,
+    /// At least `stale_after_days` old and checked by high-salience code.
+    pub cleanup_candidate: bool,
+}
+
+/// Every feature flag in the graph: cleanup candidates first, then oldest
+/// first.
+pub async fn flag_inventory(
+    store: &dyn HomerStore,
+    stale_after_days: u32,
+) -> crate::error::Result<Vec<FlagStatus>> {
+    let now = chrono::Utc::now();
+    let mut flags = Vec::new();
+    for flag in store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::FeatureFlag),
+            ..Default::default()
+        })
+        .await?
+    {
+        if flag
+            .metadata
+            .get("stale")
+            .and_then(serde_json::Value::as_bool)
+            == Some(true)
+        {
+            continue;
+        }
+        let mut checkers = Vec::new();
+        let mut seen = HashSet::new();
+        for edge in store.get_edges_involving(flag.id).await? {
+            if edge.kind != HyperedgeKind::Checks {
+                continue;
+            }
+            let Some(member) = find_member_by_roles(&edge.members, &[roles::CHECKER]) else {
+                continue;
+            };
+            if !seen.insert(member.node_id) {
+                continue;
+            }
+            let Some(code) = store.get_node(member.node_id).await? else {
+                continue;
+            };
+            checkers.push(FlagChecker {
+                salience: code_salience(store, &code).await?,
+                name: code.name,
+            });
+        }
+        checkers.sort_by(|a, b| {
+            b.salience
+                .unwrap_or(0.0)
+                .total_cmp(&a.salience.unwrap_or(0.0))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let date = |key: &str| {
+            flag.metadata
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(String::from)
+        };
+        let introduced = date("introduced");
+        let age_days = introduced
+            .as_deref()
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| (now - d.with_timezone(&chrono::Utc)).num_days());
+        let cleanup_candidate = age_days.is_some_and(|age| age >= i64::from(stale_after_days))
+            && checkers
+                .iter()
+                .any(|c| c.salience.is_some_and(|s| s > FLAG_CHECKER_SALIENCE));
+        flags.push(FlagStatus {
+            flag: flag.name,
+            introduced,
+            last_changed: date("last_changed"),
+            age_days,
+            checkers,
+            cleanup_candidate,
+        });
+    }
+    flags.sort_by(|a, b| {
+        b.cleanup_candidate
+            .cmp(&a.cleanup_candidate)
+            .then_with(|| b.age_days.cmp(&a.age_days))
+            .then_with(|| a.flag.cmp(&b.flag))
+    });
+    Ok(flags)
+}
+
+// ── Experts ─────────────────────────────────────────────────────────
+
+/// A contributor's knowledge of a path, aggregated over its files.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Expert {
+    pub email: String,
+    pub name: String,
+    pub commits: u64,
+    pub share: f64,
+    pub active: bool,
+    pub last_commit: Option<String>,
+}
+
+/// Who to ask about a file or directory, and how much knowledge has left.
+#[derive(Debug, serde::Serialize)]
+pub struct ExpertReport {
+    pub path: String,
+    pub files: usize,
+    pub orphaned_files: usize,
+    pub inactive_share: f64,
+    /// Active contributors, most knowledgeable first.
+    pub experts: Vec<Expert>,
+    /// Inactive contributors who held the most knowledge.
+    pub departed: Vec<Expert>,
+}
+
+/// Recommend active contributors to ask about `path` from `KnowledgeLoss`
+/// results.
+///
+/// `path` matches a file exactly or every file beneath a directory; an empty
+/// path covers the whole repository. Experts are ranked by commit share, then
+/// by how recently they committed.
+pub async fn experts_for_path(
+    store: &dyn HomerStore,
+    path: &str,
+    top: usize,
+) -> crate::error::Result<ExpertReport> {
+    let path = path.trim_matches('/');
+    let prefix = format!("{path}/");
+    let file_filter = NodeFilter {
+        kind: Some(NodeKind::File),
+        ..Default::default()
+    };
+    let matching: HashSet<NodeId> = store
+        .find_nodes(&file_filter)
+        .await?
+        .into_iter()
+        .filter(|f| path.is_empty() || f.name == path || f.name.starts_with(&prefix))
+        .map(|f| f.id)
+        .collect();
+
+    let mut by_email: HashMap<String, Expert> = HashMap::new();
+    let mut files = 0;
+    let mut orphaned_files = 0;
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::KnowledgeLoss)
+        .await?
+        .iter()
+        .filter(|r| matching.contains(&r.node_id))
+    {
+        files += 1;
+        if result
+            .data
+            .get("orphaned")
+            .and_then(serde_json::Value::as_bool)
+            == Some(true)
+        {
+            orphaned_files += 1;
+        }
+        let contributors = result
+            .data
+            .get("contributors")
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for c in &contributors {
+            let Some(email) = c.get("email").and_then(serde_json::Value::as_str) else {
+                continue;
+            };
+            let entry = by_email.entry(email.to_string()).or_insert_with(|| Expert {
+                email: email.to_string(),
+                name: c
+                    .get("name")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                commits: 0,
+                share: 0.0,
+                active: c.get("active").and_then(serde_json::Value::as_bool) == Some(true),
+                last_commit: c
+                    .get("last_commit")
+                    .and_then(serde_json::Value::as_str)
+                    .map(String::from),
+            });
+            entry.commits += c
+                .get("commits")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(0);
+        }
+    }
+
+    let total: u64 = by_email.values().map(|e| e.commits).sum();
+    let mut inactive = 0;
+    let (mut experts, mut departed): (Vec<Expert>, Vec<Expert>) = by_email
+        .into_values()
+        .map(|mut e| {
+            #[allow(clippy::cast_precision_loss)]
+            let share = e.commits as f64 / total.max(1) as f64;
+            e.share = (share * 100.0).round() / 100.0;
+            if !e.active {
+                inactive += e.commits;
+            }
+            e
+        })
+        .partition(|e| e.active);
+    for list in [&mut experts, &mut departed] {
+        list.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| b.last_commit.cmp(&a.last_commit))
+                .then_with(|| a.email.cmp(&b.email))
+        });
+        list.truncate(top);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let inactive_share = inactive as f64 / total.max(1) as f64;
+    Ok(ExpertReport {
+        path: path.to_string(),
+        files,
+        orphaned_files,
+        inactive_share: (inactive_share * 100.0).round() / 100.0,
+        experts,
+        departed,
+    })
+}
+
+// ── Public API check ────────────────────────────────────────────────
+
+/// A public function or type an edit would touch.
+#[derive(Debug, serde::Serialize)]
+pub struct PublicItem {
+    pub name: String,
+    pub kind: String,
+    /// Marked exported in the current graph.
+    pub exported: bool,
+    pub signature: Option<String>,
+    /// Latest snapshotted release that already shipped this item.
+    pub released_in: Option<String>,
+    /// Signature at that release, when it differs from the current one.
+    pub released_signature: Option<String>,
+}
+
+/// Whether editing an entity touches the public API.
+#[derive(Debug, serde::Serialize)]
+pub struct PublicApiCheck {
+    pub entity: String,
+    pub public: bool,
+    pub items: Vec<PublicItem>,
+    pub warnings: Vec<String>,
+}
+
+/// Check whether `entity` (a file, function, or type) is part of the public
+/// API, either exported now or shipped in the latest `ApiSurface` snapshot.
+///
+/// Returns `None` when the entity cannot be found.
+pub async fn public_api_check(
+    store: &dyn HomerStore,
+    entity: &str,
+) -> crate::error::Result<Option<PublicApiCheck>> {
+    let Some(node) = find_entity(store, entity).await? else {
+        return Ok(None);
+    };
+
+    let candidates = match node.kind {
+        NodeKind::Function | NodeKind::Type => vec![node.clone()],
+        NodeKind::File => {
+            let mut defs = Vec::new();
+            for kind in [NodeKind::Function, NodeKind::Type] {
+                let nodes = store
+                    .find_nodes(&NodeFilter {
+                        kind: Some(kind),
+                        ..Default::default()
+                    })
+                    .await?;
+                defs.extend(nodes.into_iter().filter(|n| {
+                    n.metadata.get("file").and_then(|v| v.as_str()) == Some(node.name.as_str())
+                }));
+            }
+            defs
+        }
+        _ => Vec::new(),
+    };
+
+    let latest = latest_release_surface(store).await?;
+
+    let mut items = Vec::new();
+    let mut warnings = Vec::new();
+    for def in candidates {
+        let exported = def.metadata.get("exported") == Some(&serde_json::Value::Bool(true));
+        let signature = def
+            .metadata
+            .get("signature")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let shipped = latest
+            .as_ref()
+            .and_then(|(release, surface)| Some((release, surface.get(&def.name)?)));
+        if !exported && shipped.is_none() {
+            continue;
+        }
+
+        let released_in = shipped.map(|(release, _)| release.clone());
+        let released_signature = shipped
+            .and_then(|(_, sig)| sig.clone())
+            .filter(|sig| Some(sig) != signature.as_ref());
+
+        let shipped_in = released_in
+            .as_ref()
+            .map(|release| format!(" (shipped in {release})"))
+            .unwrap_or_default();
+        let keep = signature
+            .as_ref()
+            .map(|sig| format!(" — keep `{sig}` compatible"))
+            .unwrap_or_default();
+        warnings.push(format!(
+            "{} is part of the public API{shipped_in}; removing it or changing its signature breaks callers{keep}",
+            def.name
+        ));
+        if let (Some(release), Some(old)) = (&released_in, &released_signature) {
+            warnings.push(format!(
+                "{} already differs from {release}, which had `{old}`",
+                def.name
+            ));
+        }
+
+        items.push(PublicItem {
+            name: def.name,
+            kind: def.kind.as_str().to_string(),
+            exported,
+            signature,
+            released_in,
+            released_signature,
+        });
+    }
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Some(PublicApiCheck {
+        entity: node.name,
+        public: !items.is_empty(),
+        items,
+        warnings,
+    }))
+}
+
+/// Name of the most recently released `ApiSurface` snapshot and its
+/// item signatures by name.
+async fn latest_release_surface(
+    store: &dyn HomerStore,
+) -> crate::error::Result<Option<(String, HashMap<String, Option<String>>)>> {
+    let results = store.get_analyses_by_kind(AnalysisKind::ApiSurface).await?;
+    let Some(latest) = results.iter().max_by_key(|r| {
+        r.data["released_at"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    }) else {
+        return Ok(None);
+    };
+    let items = latest.data["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|i| {
+                    let name = i["name"].as_str()?;
+                    Some((name.to_string(), i["signature"].as_str().map(String::from)))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Some((resolve_name(store, latest.node_id).await, items)))
+}
+
 /// Parse a user-provided string into a `NodeKind`.
 pub fn parse_node_kind(s: &str) -> Option<NodeKind> {
     match s.to_lowercase().as_str() {
@@ -287,6 +2081,15 @@ pub fn parse_node_kind(s: &str) -> Option<NodeKind> {
         "issue" => Some(NodeKind::Issue),
         "dep" | "dependency" => Some(NodeKind::ExternalDep),
         "document" | "doc" => Some(NodeKind::Document),
+        "table" => Some(NodeKind::Table),
+        "column" => Some(NodeKind::Column),
+        "env" | "envvar" => Some(NodeKind::EnvVar),
+        "config" | "configkey" => Some(NodeKind::ConfigKey),
+        "flag" | "featureflag" => Some(NodeKind::FeatureFlag),
+        "endpoint" | "route" => Some(NodeKind::Endpoint),
+        "ci_job" | "cijob" => Some(NodeKind::CiJob),
+        "infra_resource" | "infraresource" => Some(NodeKind::InfraResource),
+        "test" => Some(NodeKind::Test),
         _ => None,
     }
 }
@@ -294,6 +2097,745 @@ pub fn parse_node_kind(s: &str) -> Option<NodeKind> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::store::sqlite::SqliteStore;
+    use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+
+    async fn add_function(store: &SqliteStore, name: &str, rows: u64) -> NodeId {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "span".to_string(),
+            serde_json::json!({ "start_row": 0, "end_row": rows - 1 }),
+        );
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: name.to_string(),
+                content_hash: None,
+                last_extracted: chrono::Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn add_call(store: &SqliteStore, from: NodeId, to: NodeId, confidence: f64) {
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Calls,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: from,
+                        role: "caller".to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: to,
+                        role: "callee".to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence,
+                last_updated: chrono::Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn task_context_ranks_seed_neighborhood_within_budget() {
+        let store = SqliteStore::in_memory().unwrap();
+        // handler → auth → token   and an unrelated   report → render
+        let handler = add_function(&store, "src/api.rs::handler", 10).await;
+        let auth = add_function(&store, "src/auth.rs::check", 10).await;
+        let token = add_function(&store, "src/auth.rs::token", 10).await;
+        let report = add_function(&store, "src/report.rs::report", 10).await;
+        let render = add_function(&store, "src/report.rs::render", 10).await;
+        add_call(&store, handler, auth, 1.0).await;
+        add_call(&store, auth, token, 1.0).await;
+        add_call(&store, report, render, 1.0).await;
+
+        let ctx = task_context(&store, &["src/auth.rs::check".to_string()], 300, 10)
+            .await
+            .unwrap();
+
+        assert_eq!(ctx.seeds, vec!["src/auth.rs::check".to_string()]);
+        assert!(ctx.unresolved.is_empty());
+        assert_eq!(ctx.entries[0].name, "src/auth.rs::check");
+        assert!(ctx.entries[0].seed);
+        let names: Vec<&str> = ctx.entries.iter().map(|e| e.name.as_str()).collect();
+        assert!(names.contains(&"src/api.rs::handler"), "caller: {names:?}");
+        assert!(names.contains(&"src/auth.rs::token"), "callee: {names:?}");
+        assert!(
+            !names.contains(&"src/report.rs::render"),
+            "unrelated: {names:?}"
+        );
+        assert!(ctx.total_tokens <= 300);
+    }
+
+    #[tokio::test]
+    async fn task_context_reports_unresolved_seeds() {
+        let store = SqliteStore::in_memory().unwrap();
+        let seeds = [
+            "nothing_matches".to_string(),
+            "src/no-such-file.rs".to_string(),
+        ];
+        let ctx = task_context(&store, &seeds, 1000, 10).await.unwrap();
+        assert!(ctx.seeds.is_empty());
+        assert_eq!(ctx.unresolved, seeds);
+        assert!(ctx.entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn blast_radius_follows_reverse_calls_with_confidence_cutoff() {
+        let store = SqliteStore::in_memory().unwrap();
+        // api::handler → auth::check → auth::token ← src/auth_test.rs::it_works
+        // cli::main ⇢ auth::token with low confidence
+        let handler = add_function(&store, "src/api.rs::handler", 5).await;
+        let check = add_function(&store, "src/auth.rs::check", 5).await;
+        let token = add_function(&store, "src/auth.rs::token", 5).await;
+        let test_fn = add_function(&store, "src/auth_test.rs::it_works", 5).await;
+        let main = add_function(&store, "src/cli.rs::main", 5).await;
+        let unrelated = add_function(&store, "src/report.rs::render", 5).await;
+        for path in ["src/api.rs", "src/auth.rs", "src/auth_test.rs"] {
+            store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: path.to_string(),
+                    content_hash: None,
+                    last_extracted: chrono::Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+        add_call(&store, handler, check, 0.9).await;
+        add_call(&store, check, token, 0.9).await;
+        add_call(&store, test_fn, token, 1.0).await;
+        add_call(&store, main, token, 0.2).await;
+        add_call(&store, token, unrelated, 1.0).await;
+
+        let target = store.get_node(token).await.unwrap().unwrap();
+        let radius = blast_radius(&store, &target, 0.5, 10).await.unwrap();
+
+        let names: Vec<&str> = radius.affected.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["src/auth.rs::check", "src/api.rs::handler"]);
+        assert!((radius.affected[1].probability - 0.81).abs() < 1e-9);
+        assert_eq!(radius.affected[1].depth, 2);
+        assert_eq!(radius.tests.len(), 1);
+        assert_eq!(radius.tests[0].name, "src/auth_test.rs::it_works");
+        assert_eq!(radius.uncovered, vec!["src/api.rs::handler".to_string()]);
+        assert_eq!(radius.communities.len(), 1);
+        assert_eq!(radius.communities[0].community, "unassigned");
+
+        let shallow = blast_radius(&store, &target, 0.1, 1).await.unwrap();
+        let names: Vec<&str> = shallow.affected.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["src/auth.rs::check", "src/cli.rs::main"]);
+    }
+
+    #[tokio::test]
+    async fn blast_radius_keeps_short_paths_when_longer_ones_are_likelier() {
+        let store = SqliteStore::in_memory().unwrap();
+        // token ← check (1.0) ← relay (1.0), and token ← relay (0.6) directly.
+        // relay ← handler (1.0) is two hops away only through the weaker edge.
+        let token = add_function(&store, "src/auth.rs::token", 5).await;
+        let check = add_function(&store, "src/auth.rs::check", 5).await;
+        let relay = add_function(&store, "src/api.rs::relay", 5).await;
+        let handler = add_function(&store, "src/api.rs::handler", 5).await;
+        let main = add_function(&store, "src/cli.rs::main", 5).await;
+        add_call(&store, check, token, 1.0).await;
+        add_call(&store, relay, token, 0.6).await;
+        add_call(&store, relay, check, 1.0).await;
+        add_call(&store, handler, relay, 1.0).await;
+        add_call(&store, main, handler, 1.0).await;
+
+        let target = store.get_node(token).await.unwrap().unwrap();
+        let radius = blast_radius(&store, &target, 0.5, 2).await.unwrap();
+        let found: Vec<(&str, u32)> = radius
+            .affected
+            .iter()
+            .map(|e| (e.name.as_str(), e.depth))
+            .collect();
+        // relay reports its likelier two-hop path, handler is reached through
+        // the direct edge, and main is three hops away by any path.
+        assert_eq!(
+            found,
+            vec![
+                ("src/api.rs::relay", 2),
+                ("src/auth.rs::check", 1),
+                ("src/api.rs::handler", 2),
+            ]
+        );
+        assert!((radius.affected[2].probability - 0.6).abs() < 1e-9);
+
+        let strict = blast_radius(&store, &target, 0.7, 2).await.unwrap();
+        let names: Vec<&str> = strict.affected.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["src/api.rs::relay", "src/auth.rs::check"]);
+    }
+
+    #[tokio::test]
+    async fn blast_radius_of_file_excludes_its_own_members() {
+        let store = SqliteStore::in_memory().unwrap();
+        let handler = add_function(&store, "src/api.rs::handler", 5).await;
+        let check = add_function(&store, "src/auth.rs::check", 5).await;
+        let token = add_function(&store, "src/auth.rs::token", 5).await;
+        let file = store
+            .upsert_node(&add(NodeKind::File, "src/auth.rs", serde_json::json!({})))
+            .await
+            .unwrap();
+        for member in [check, token] {
+            store
+                .upsert_hyperedge(&link(
+                    HyperedgeKind::BelongsTo,
+                    [(member, "member"), (file, "container")],
+                ))
+                .await
+                .unwrap();
+        }
+        add_call(&store, check, token, 0.9).await;
+        add_call(&store, handler, check, 0.9).await;
+
+        let target = store.get_node(file).await.unwrap().unwrap();
+        let radius = blast_radius(&store, &target, 0.1, 10).await.unwrap();
+        let names: Vec<&str> = radius.affected.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["src/api.rs::handler"]);
+        assert_eq!(radius.affected[0].depth, 1);
+    }
+
+    #[tokio::test]
+    async fn tests_for_paths_finds_linked_and_changed_tests() {
+        let store = SqliteStore::in_memory().unwrap();
+        let check = add_function(&store, "src/auth.rs::check", 10).await;
+        let token = add_function(&store, "src/auth.rs::token", 10).await;
+        let mut metadata = HashMap::new();
+        metadata.insert("file".to_string(), serde_json::json!("src/auth_test.rs"));
+        metadata.insert(
+            "test".to_string(),
+            serde_json::json!({ "framework": "rust", "calls": [] }),
+        );
+        let it_works = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/auth_test.rs::it_works".to_string(),
+                content_hash: None,
+                last_extracted: chrono::Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap();
+        add_call(&store, it_works, check, 1.0).await;
+        add_call(&store, check, token, 1.0).await;
+        crate::extract::test_links::link_tests(&store)
+            .await
+            .unwrap();
+
+        let tests = tests_for_paths(&store, &["src/auth.rs".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].name, "src/auth_test.rs::it_works");
+        assert_eq!(tests[0].file, "src/auth_test.rs");
+        assert_eq!(tests[0].framework, "rust");
+        assert_eq!(tests[0].depth, 1);
+
+        let token_node = store.get_node(token).await.unwrap().unwrap();
+        let covering = tests_covering(&store, &token_node).await.unwrap();
+        assert_eq!(covering.len(), 1);
+        assert_eq!(covering[0].depth, 2);
+
+        let changed = tests_for_paths(&store, &["src/auth_test.rs".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].depth, 0);
+    }
+
+    fn add(kind: NodeKind, name: &str, metadata: serde_json::Value) -> Node {
+        Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: chrono::Utc::now(),
+            metadata: serde_json::from_value(metadata).unwrap(),
+        }
+    }
+
+    fn link(kind: HyperedgeKind, members: [(NodeId, &str); 2]) -> Hyperedge {
+        Hyperedge {
+            id: HyperedgeId(0),
+            kind,
+            members: members
+                .iter()
+                .zip(0..)
+                .map(|(&(node_id, role), position)| HyperedgeMember {
+                    node_id,
+                    role: role.to_string(),
+                    position,
+                })
+                .collect(),
+            confidence: 1.0,
+            last_updated: chrono::Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// `Money` referenced by a `Charge` endpoint bound to Go and TypeScript
+    /// code, with the Go side built into the `billing` image.
+    async fn seed_billing_contract(store: &SqliteStore) {
+        let contract = serde_json::json!({ "contract": { "format": "protobuf" } });
+        let money = store
+            .upsert_node(&add(
+                NodeKind::Type,
+                "proto/common.proto::Money",
+                contract.clone(),
+            ))
+            .await
+            .unwrap();
+        let charge = store
+            .upsert_node(&add(
+                NodeKind::Endpoint,
+                "proto/billing.proto::Billing.Charge",
+                contract,
+            ))
+            .await
+            .unwrap();
+        store
+            .upsert_hyperedge(&link(
+                HyperedgeKind::References,
+                [(charge, roles::REFERRER), (money, roles::REFERENCED)],
+            ))
+            .await
+            .unwrap();
+        for (file, language, function) in [
+            ("services/billing/server.go", "go", "Charge"),
+            ("web/src/pay.ts", "typescript", "charge"),
+        ] {
+            store
+                .upsert_node(&add(
+                    NodeKind::File,
+                    file,
+                    serde_json::json!({ "language": language }),
+                ))
+                .await
+                .unwrap();
+            let code = store
+                .upsert_node(&add(
+                    NodeKind::Function,
+                    &format!("{file}::{function}"),
+                    serde_json::json!({ "file": file }),
+                ))
+                .await
+                .unwrap();
+            store
+                .upsert_hyperedge(&link(
+                    HyperedgeKind::Binds,
+                    [(charge, roles::CONTRACT_ELEMENT), (code, roles::BOUND_CODE)],
+                ))
+                .await
+                .unwrap();
+        }
+        let module = store
+            .upsert_node(&add(
+                NodeKind::Module,
+                "services/billing",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let image = store
+            .upsert_node(&add(
+                NodeKind::InfraResource,
+                "services/billing/Dockerfile",
+                serde_json::json!({ "label": "billing" }),
+            ))
+            .await
+            .unwrap();
+        store
+            .upsert_hyperedge(&link(
+                HyperedgeKind::Builds,
+                [
+                    (image, roles::BUILT_RESOURCE),
+                    (module, roles::BUILD_SOURCE),
+                ],
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn contract_blast_radius_reaches_bound_services() {
+        let store = SqliteStore::in_memory().unwrap();
+        seed_billing_contract(&store).await;
+
+        let impact = contract_blast_radius(&store, &["proto/common.proto".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(impact.elements.len(), 2);
+        assert_eq!(impact.elements[0].element, "proto/common.proto::Money");
+        assert!(impact.elements[0].changed);
+        let charge = &impact.elements[1];
+        assert!(!charge.changed, "Reached through References");
+        assert_eq!(charge.kind, "endpoint");
+        assert_eq!(charge.bound_code.len(), 2);
+        let services: Vec<(&str, &[String])> = impact
+            .services
+            .iter()
+            .map(|s| (s.service.as_str(), s.languages.as_slice()))
+            .collect();
+        assert_eq!(
+            services,
+            vec![
+                ("billing", ["go".to_string()].as_slice()),
+                ("web", ["typescript".to_string()].as_slice())
+            ]
+        );
+
+        let untouched = contract_blast_radius(&store, &["src/main.rs".to_string()])
+            .await
+            .unwrap();
+        assert!(untouched.elements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn shortest_paths_ranks_by_hops_and_skips_low_confidence() {
+        let store = SqliteStore::in_memory().unwrap();
+        // handler → service → repo, handler → cache → repo, handler ⇢ repo (heuristic)
+        let handler = add_function(&store, "src/api.rs::handler", 5).await;
+        let service = add_function(&store, "src/service.rs::load", 5).await;
+        let cache = add_function(&store, "src/cache.rs::get", 5).await;
+        let repo = add_function(&store, "src/db.rs::query", 5).await;
+        add_call(&store, handler, service, 0.9).await;
+        add_call(&store, service, repo, 0.9).await;
+        add_call(&store, handler, cache, 0.8).await;
+        add_call(&store, cache, repo, 0.8).await;
+        add_call(&store, handler, repo, 0.3).await;
+
+        let from = store.get_node(handler).await.unwrap().unwrap();
+        let to = store.get_node(repo).await.unwrap().unwrap();
+
+        let paths = shortest_paths(&store, &from, &to, 5, 0.0).await.unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0].hops.len(), 1, "direct edge is shortest");
+        assert_eq!(paths[1].hops[0].to, "src/service.rs::load");
+        assert!((paths[1].confidence - 0.81).abs() < 1e-9);
+        assert_eq!(paths[2].hops[0].to, "src/cache.rs::get");
+        assert_eq!(paths[1].hops[0].edge, "Calls");
+        assert_eq!(paths[1].hops[0].start_line, Some(1));
+        assert_eq!(paths[1].hops[0].end_line, Some(5));
+
+        let confident = shortest_paths(&store, &from, &to, 5, 0.5).await.unwrap();
+        assert_eq!(confident.len(), 2);
+        assert!(confident.iter().all(|p| p.hops.len() == 2));
+
+        let reverse = shortest_paths(&store, &to, &from, 5, 0.0).await.unwrap();
+        assert!(reverse.is_empty(), "paths are directed");
+    }
+
+    #[tokio::test]
+    async fn public_api_check_warns_about_exported_and_shipped_items() {
+        let store = SqliteStore::in_memory().unwrap();
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::File,
+                name: "src/lib.rs".to_string(),
+                content_hash: None,
+                last_extracted: chrono::Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        for (name, exported, signature) in [
+            ("parse", true, "pub fn parse(input: &str, strict: bool)"),
+            ("helper", false, "fn helper()"),
+        ] {
+            let mut metadata = HashMap::new();
+            metadata.insert("file".to_string(), serde_json::json!("src/lib.rs"));
+            metadata.insert("exported".to_string(), serde_json::json!(exported));
+            metadata.insert("signature".to_string(), serde_json::json!(signature));
+            store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::Function,
+                    name: format!("src/lib.rs::{name}"),
+                    content_hash: None,
+                    last_extracted: chrono::Utc::now(),
+                    metadata,
+                })
+                .await
+                .unwrap();
+        }
+        let release = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Release,
+                name: "v1.0".to_string(),
+                content_hash: None,
+                last_extracted: chrono::Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&crate::types::AnalysisResult {
+                id: crate::types::AnalysisResultId(0),
+                node_id: release,
+                kind: AnalysisKind::ApiSurface,
+                data: serde_json::json!({
+                    "commit": "abc",
+                    "released_at": "2026-01-01T00:00:00+00:00",
+                    "items": [{
+                        "name": "src/lib.rs::parse",
+                        "kind": "Function",
+                        "signature": "pub fn parse(input: &str)",
+                    }],
+                }),
+                input_hash: 0,
+                computed_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let check = public_api_check(&store, "src/lib.rs")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(check.public);
+        assert_eq!(check.items.len(), 1, "private helper is not listed");
+        let parse = &check.items[0];
+        assert_eq!(parse.released_in.as_deref(), Some("v1.0"));
+        assert_eq!(
+            parse.released_signature.as_deref(),
+            Some("pub fn parse(input: &str)")
+        );
+        assert_eq!(check.warnings.len(), 2);
+
+        let private = public_api_check(&store, "src/lib.rs::helper")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!private.public);
+        assert!(private.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn schema_accesses_from_either_side() {
+        let store = SqliteStore::in_memory().unwrap();
+        let upsert = |kind, name: &str| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: chrono::Utc::now(),
+            metadata: HashMap::new(),
+        };
+        let load = add_function(&store, "src/db.rs::load_user", 5).await;
+        let table = store
+            .upsert_node(&upsert(NodeKind::Table, "users"))
+            .await
+            .unwrap();
+        let column = store
+            .upsert_node(&upsert(NodeKind::Column, "users.email"))
+            .await
+            .unwrap();
+        for (target, via) in [(table, "sql"), (column, "sql")] {
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Accesses,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: load,
+                            role: roles::ACCESSOR.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: target,
+                            role: roles::ACCESSED.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 0.9,
+                    last_updated: chrono::Utc::now(),
+                    metadata: HashMap::from([("via".to_string(), serde_json::json!(via))]),
+                })
+                .await
+                .unwrap();
+        }
+
+        let users = find_entity(&store, "users").await.unwrap().unwrap();
+        assert_eq!(users.kind, NodeKind::Table);
+        let from_table = schema_accesses(&store, &users).await.unwrap();
+        let targets: Vec<&str> = from_table.iter().map(|a| a.target.as_str()).collect();
+        assert_eq!(targets, vec!["users", "users.email"]);
+        assert!(from_table.iter().all(|a| a.code == "src/db.rs::load_user"));
+
+        let email = store.get_node(column).await.unwrap().unwrap();
+        assert_eq!(schema_accesses(&store, &email).await.unwrap().len(), 1);
+        let function = store.get_node(load).await.unwrap().unwrap();
+        assert_eq!(
+            schema_accesses(&store, &function).await.unwrap(),
+            from_table
+        );
+    }
+
+    #[tokio::test]
+    async fn config_usage_from_either_side() {
+        let store = SqliteStore::in_memory().unwrap();
+        let upsert = |kind, name: &str| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: chrono::Utc::now(),
+            metadata: HashMap::new(),
+        };
+        let connect = add_function(&store, "src/db.rs::connect", 5).await;
+        let template = store
+            .upsert_node(&upsert(NodeKind::File, ".env.example"))
+            .await
+            .unwrap();
+        let url = store
+            .upsert_node(&upsert(NodeKind::EnvVar, "DATABASE_URL"))
+            .await
+            .unwrap();
+        let edges = [
+            (
+                HyperedgeKind::Reads,
+                connect,
+                roles::READER,
+                roles::READ_VAR,
+            ),
+            (
+                HyperedgeKind::Defines,
+                template,
+                roles::DEFINER,
+                roles::DEFINED_VAR,
+            ),
+        ];
+        for (kind, from, from_role, to_role) in edges {
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: from,
+                            role: from_role.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: url,
+                            role: to_role.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 0.9,
+                    last_updated: chrono::Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let var = find_entity(&store, "DATABASE_URL").await.unwrap().unwrap();
+        let usage = config_usage(&store, &var).await.unwrap();
+        assert_eq!(
+            usage,
+            vec![ConfigUsage {
+                variable: "DATABASE_URL".to_string(),
+                kind: "env".to_string(),
+                readers: vec!["src/db.rs::connect".to_string()],
+                definers: vec![".env.example".to_string()],
+            }]
+        );
+        let function = store.get_node(connect).await.unwrap().unwrap();
+        assert_eq!(config_usage(&store, &function).await.unwrap(), usage);
+    }
+
+    #[tokio::test]
+    async fn flag_inventory_ranks_old_flags_behind_salient_code() {
+        let store = SqliteStore::in_memory().unwrap();
+        let hot = add_function(&store, "src/checkout.rs::render", 5).await;
+        let cold = add_function(&store, "src/admin.rs::debug", 5).await;
+        store
+            .store_analysis(&crate::types::AnalysisResult {
+                id: crate::types::AnalysisResultId(0),
+                node_id: hot,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({ "score": 0.8 }),
+                input_hash: 0,
+                computed_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        let recent = chrono::Utc::now().to_rfc3339();
+        for (flag, introduced, checker) in [
+            ("new-checkout", "2020-01-01T00:00:00+00:00", hot),
+            ("debug-panel", "2020-01-01T00:00:00+00:00", cold),
+            ("fast-cart", recent.as_str(), hot),
+        ] {
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::FeatureFlag,
+                    name: flag.to_string(),
+                    content_hash: None,
+                    last_extracted: chrono::Utc::now(),
+                    metadata: HashMap::from([
+                        ("introduced".to_string(), serde_json::json!(introduced)),
+                        ("last_changed".to_string(), serde_json::json!(introduced)),
+                    ]),
+                })
+                .await
+                .unwrap();
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Checks,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: checker,
+                            role: roles::CHECKER.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: id,
+                            role: roles::CHECKED_FLAG.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 0.9,
+                    last_updated: chrono::Utc::now(),
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let flags = flag_inventory(&store, 90).await.unwrap();
+        let order: Vec<(&str, bool)> = flags
+            .iter()
+            .map(|f| (f.flag.as_str(), f.cleanup_candidate))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("new-checkout", true),
+                ("debug-panel", false),
+                ("fast-cart", false),
+            ]
+        );
+        assert_eq!(flags[0].checkers[0].name, "src/checkout.rs::render");
+        assert_eq!(flags[0].checkers[0].salience, Some(0.8));
+        assert!(flags[0].age_days.unwrap() > 365);
+        assert_eq!(flags[2].age_days, Some(0));
+    }
 
     #[test]
     fn parse_node_kind_variants() {
@@ -305,6 +2847,15 @@ mod tests {
         assert_eq!(parse_node_kind("commit"), Some(NodeKind::Commit));
         assert_eq!(parse_node_kind("pr"), Some(NodeKind::PullRequest));
         assert_eq!(parse_node_kind("issue"), Some(NodeKind::Issue));
+        assert_eq!(parse_node_kind("table"), Some(NodeKind::Table));
+        assert_eq!(parse_node_kind("endpoint"), Some(NodeKind::Endpoint));
+        assert_eq!(parse_node_kind("ci_job"), Some(NodeKind::CiJob));
+        assert_eq!(parse_node_kind("CiJob"), Some(NodeKind::CiJob));
+        assert_eq!(
+            parse_node_kind("infra_resource"),
+            Some(NodeKind::InfraResource)
+        );
+        assert_eq!(parse_node_kind("test"), Some(NodeKind::Test));
         assert_eq!(parse_node_kind("unknown"), None);
         assert_eq!(parse_node_kind("all"), None);
     }
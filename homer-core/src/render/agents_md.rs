@@ -1,12 +1,13 @@
 #![allow(clippy::cast_precision_loss)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Write as _;
 
 use tracing::{info, instrument};
 
 use crate::config::HomerConfig;
 use crate::contracts::{self, metadata_keys};
+use crate::extract::coverage::CoverageSummary;
 use crate::store::HomerStore;
 use crate::types::{AnalysisKind, HyperedgeKind, NodeKind};
 
@@ -45,12 +46,14 @@ impl Renderer for AgentsMdRenderer {
 
         // Build & Test Commands
         render_build_commands(&mut out, store).await?;
+        render_ci_checks(&mut out, store).await?;
 
         // Architecture Overview (from community analysis)
         render_architecture_overview(&mut out, store).await?;
 
         // Module Map
         render_module_map(&mut out, store).await?;
+        render_deployment_topology(&mut out, store).await?;
 
         // Key Documents
         render_key_documents(&mut out, store).await?;
@@ -63,6 +66,12 @@ impl Renderer for AgentsMdRenderer {
 
         // Danger Zones
         render_danger_zones(&mut out, store).await?;
+        render_orphaned_code(&mut out, store).await?;
+        render_untested_load_bearing(&mut out, store).await?;
+        render_flaky_tests(&mut out, store).await?;
+
+        // Complexity Hotspots (complex functions in frequently changed files)
+        render_complexity_hotspots(&mut out, store).await?;
 
         // Conventions
         render_conventions(&mut out, store).await?;
@@ -208,6 +217,179 @@ async fn render_build_commands(
     Ok(())
 }
 
+// ── CI Checks ─────────────────────────────────────────────────────
+
+/// Most directory rows in the CI checks table.
+const MAX_CI_ROWS: usize = 25;
+
+async fn render_ci_checks(out: &mut String, store: &dyn HomerStore) -> crate::error::Result<()> {
+    let jobs = store
+        .find_nodes(&crate::types::NodeFilter {
+            kind: Some(NodeKind::CiJob),
+            ..Default::default()
+        })
+        .await?;
+    let mut labels: HashMap<crate::types::NodeId, String> = HashMap::new();
+    let mut always: BTreeSet<String> = BTreeSet::new();
+    for job in &jobs {
+        let meta = &job.metadata;
+        let on_change = meta
+            .get("runs_on_change")
+            .and_then(serde_json::Value::as_bool);
+        if meta.contains_key("stale") || on_change != Some(true) {
+            continue;
+        }
+        let label = meta
+            .get("label")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or(&job.name)
+            .to_string();
+        let filtered = ["paths", "paths_ignore"].iter().any(|key| {
+            meta.get(*key)
+                .and_then(serde_json::Value::as_array)
+                .is_some_and(|a| !a.is_empty())
+        });
+        if !filtered {
+            always.insert(label.clone());
+        }
+        labels.insert(job.id, label);
+    }
+    if labels.is_empty() {
+        return Ok(());
+    }
+
+    let checks = ci_checks_by_file(store, &labels).await?;
+    let files = store
+        .find_nodes(&crate::types::NodeFilter {
+            kind: Some(NodeKind::File),
+            ..Default::default()
+        })
+        .await?;
+    let none = BTreeSet::new();
+    let mut by_path: Vec<(&str, &BTreeSet<String>)> = files
+        .iter()
+        .map(|f| (f.name.as_str(), checks.get(&f.id).unwrap_or(&none)))
+        .collect();
+    by_path.sort_unstable();
+    let mut rows = Vec::new();
+    if !by_path.is_empty() {
+        ci_check_rows(&by_path, "", &mut rows);
+    }
+
+    let _ = writeln!(out, "## CI Checks");
+    let _ = writeln!(out);
+    let list = |checks: &BTreeSet<String>| {
+        checks
+            .iter()
+            .map(|c| format!("`{c}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if !always.is_empty() {
+        let _ = writeln!(
+            out,
+            "Every pushed or proposed change runs: {}.",
+            list(&always)
+        );
+        let _ = writeln!(out);
+    }
+    if !rows.is_empty() {
+        let _ = writeln!(
+            out,
+            "Path-filtered checks only run when a change touches their paths:"
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(out, "| Change Touches | Also Runs |");
+        let _ = writeln!(out, "|----------------|-----------|");
+        for (place, checks) in &rows {
+            let _ = writeln!(out, "| {place} | {} |", list(checks));
+        }
+        let _ = writeln!(out);
+    }
+    Ok(())
+}
+
+/// Path-filtered checks each tracked file triggers, from `Triggers` edges
+/// of the jobs in `labels`.
+async fn ci_checks_by_file(
+    store: &dyn HomerStore,
+    labels: &HashMap<crate::types::NodeId, String>,
+) -> crate::error::Result<HashMap<crate::types::NodeId, BTreeSet<String>>> {
+    let mut checks: HashMap<crate::types::NodeId, BTreeSet<String>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Triggers).await? {
+        let Some(label) = edge
+            .members
+            .iter()
+            .find(|m| m.role == contracts::roles::TRIGGERED_JOB)
+            .and_then(|m| labels.get(&m.node_id))
+        else {
+            continue;
+        };
+        for file in edge
+            .members
+            .iter()
+            .filter(|m| m.role == contracts::roles::TRIGGERING_FILE)
+        {
+            checks
+                .entry(file.node_id)
+                .or_default()
+                .insert(label.clone());
+        }
+    }
+    Ok(checks)
+}
+
+/// Collapse files (paths relative to `prefix`) into rows: a directory when
+/// every file under it triggers the same checks, otherwise its subdirectories
+/// and the files directly in it.
+fn ci_check_rows(
+    files: &[(&str, &BTreeSet<String>)],
+    prefix: &str,
+    rows: &mut Vec<(String, BTreeSet<String>)>,
+) {
+    if rows.len() >= MAX_CI_ROWS {
+        return;
+    }
+    let first = files[0].1;
+    if files.iter().all(|(_, checks)| *checks == first) {
+        if !first.is_empty() {
+            let place = if prefix.is_empty() {
+                "any file".to_string()
+            } else {
+                format!("`{prefix}`")
+            };
+            rows.push((place, first.clone()));
+        }
+        return;
+    }
+
+    let mut direct: BTreeMap<&BTreeSet<String>, Vec<&str>> = BTreeMap::new();
+    let mut dirs: BTreeMap<&str, Vec<(&str, &BTreeSet<String>)>> = BTreeMap::new();
+    for &(path, checks) in files {
+        match path.split_once('/') {
+            Some((dir, rest)) => dirs.entry(dir).or_default().push((rest, checks)),
+            None => direct.entry(checks).or_default().push(path),
+        }
+    }
+    for (checks, names) in direct {
+        if checks.is_empty() || rows.len() >= MAX_CI_ROWS {
+            continue;
+        }
+        let mut place: Vec<String> = names
+            .iter()
+            .take(3)
+            .map(|n| format!("`{prefix}{n}`"))
+            .collect();
+        if names.len() > 3 {
+            place.push(format!("{} more", names.len() - 3));
+        }
+        rows.push((place.join(", "), checks.clone()));
+    }
+    for (dir, entries) in dirs {
+        ci_check_rows(&entries, &format!("{prefix}{dir}/"), rows);
+    }
+}
+
 // ── Architecture Overview ─────────────────────────────────────────
 
 async fn render_architecture_overview(
@@ -541,6 +723,157 @@ async fn render_module_map(out: &mut String, store: &dyn HomerStore) -> crate::e
     Ok(())
 }
 
+// ── Deployment Topology ───────────────────────────────────────────
+
+/// Most resources listed in the deployment topology table.
+const MAX_DEPLOY_ROWS: usize = 20;
+
+/// Display kind of an infrastructure resource; `None` for resources the
+/// table leaves out (external images, Terraform data sources).
+fn deploy_kind(node: &crate::types::Node) -> Option<String> {
+    let meta = |key: &str| node.metadata.get(key).and_then(serde_json::Value::as_str);
+    Some(match meta("infra_kind")? {
+        "dockerfile" => "Docker image".to_string(),
+        "compose_service" => "Compose service".to_string(),
+        "kubernetes" => format!("Kubernetes {}", meta("k8s_kind")?),
+        "helm_chart" => "Helm chart".to_string(),
+        "terraform_resource" => "Terraform resource".to_string(),
+        "terraform_module" => "Terraform module".to_string(),
+        _ => return None,
+    })
+}
+
+/// Source directories (with a trailing `/`) and files each resource is
+/// built from, from `Builds` edges.
+async fn deployment_sources(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<crate::types::NodeId, Vec<String>>> {
+    let mut built_from: HashMap<crate::types::NodeId, Vec<String>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Builds).await? {
+        let Some(resource) = edge
+            .members
+            .iter()
+            .find(|m| m.role == contracts::roles::BUILT_RESOURCE)
+        else {
+            continue;
+        };
+        for source in edge
+            .members
+            .iter()
+            .filter(|m| m.role == contracts::roles::BUILD_SOURCE)
+        {
+            if let Some(node) = store.get_node(source.node_id).await? {
+                let name = if node.kind == NodeKind::Module {
+                    format!("{}/", node.name)
+                } else {
+                    node.name
+                };
+                built_from.entry(resource.node_id).or_default().push(name);
+            }
+        }
+    }
+    Ok(built_from)
+}
+
+async fn render_deployment_topology(
+    out: &mut String,
+    store: &dyn HomerStore,
+) -> crate::error::Result<()> {
+    let resources: HashMap<crate::types::NodeId, crate::types::Node> = store
+        .find_nodes(&crate::types::NodeFilter {
+            kind: Some(NodeKind::InfraResource),
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .filter(|n| !n.metadata.contains_key("stale"))
+        .map(|n| (n.id, n))
+        .collect();
+    let label = |id: &crate::types::NodeId| {
+        resources.get(id).map(|n| {
+            n.metadata
+                .get("label")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or(&n.name)
+                .to_string()
+        })
+    };
+
+    let mut depends: HashMap<crate::types::NodeId, BTreeSet<String>> = HashMap::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Uses).await? {
+        let member = |role: &str| edge.members.iter().find(|m| m.role == role);
+        if let (Some(dependent), Some(dependency)) = (
+            member(contracts::roles::DEPENDENT_RESOURCE),
+            member(contracts::roles::DEPENDENCY),
+        ) {
+            if let Some(name) = label(&dependency.node_id) {
+                depends.entry(dependent.node_id).or_default().insert(name);
+            }
+        }
+    }
+    let built_from = deployment_sources(store).await?;
+
+    // Resources built from this repository's code first.
+    let mut rows: Vec<(&crate::types::Node, String)> = resources
+        .values()
+        .filter_map(|n| deploy_kind(n).map(|kind| (n, kind)))
+        .collect();
+    if rows.is_empty() {
+        return Ok(());
+    }
+    rows.sort_by(|(a, _), (b, _)| {
+        (!built_from.contains_key(&a.id), &a.name).cmp(&(!built_from.contains_key(&b.id), &b.name))
+    });
+
+    let _ = writeln!(out, "## Deployment Topology");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "| Resource | Kind | Defined In | Built From | Depends On | Ports |"
+    );
+    let _ = writeln!(
+        out,
+        "|----------|------|------------|------------|------------|-------|"
+    );
+    let code = |items: &mut dyn Iterator<Item = &String>| {
+        let items: Vec<String> = items.take(4).map(|i| format!("`{i}`")).collect();
+        if items.is_empty() {
+            "-".to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    for (node, kind) in rows.iter().take(MAX_DEPLOY_ROWS) {
+        let file = node
+            .metadata
+            .get("file")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        let ports: Vec<String> = node
+            .metadata
+            .get("ports")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.as_str().map(String::from))
+            .collect();
+        let _ = writeln!(
+            out,
+            "| `{}` | {kind} | `{file}` | {} | {} | {} |",
+            label(&node.id).unwrap_or_default(),
+            code(&mut built_from.get(&node.id).into_iter().flatten()),
+            code(&mut depends.get(&node.id).into_iter().flatten()),
+            if ports.is_empty() {
+                "-".to_string()
+            } else {
+                ports.join(", ")
+            },
+        );
+    }
+    let _ = writeln!(out);
+    Ok(())
+}
+
 // ── Change Patterns ───────────────────────────────────────────────
 
 async fn render_change_patterns(
@@ -764,6 +1097,262 @@ async fn render_danger_zones(out: &mut String, store: &dyn HomerStore) -> crate:
     Ok(())
 }
 
+/// Salient files whose knowledge sits mostly with inactive contributors.
+/// Rendered as a subsection of Danger Zones.
+async fn render_orphaned_code(
+    out: &mut String,
+    store: &dyn HomerStore,
+) -> crate::error::Result<()> {
+    let mut orphaned = Vec::new();
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::KnowledgeLoss)
+        .await?
+    {
+        if result
+            .data
+            .get("orphaned")
+            .and_then(serde_json::Value::as_bool)
+            != Some(true)
+        {
+            continue;
+        }
+        let Some(node) = store.get_node(result.node_id).await? else {
+            continue;
+        };
+        if node.kind == NodeKind::File {
+            orphaned.push((node.name, result.data));
+        }
+    }
+    if orphaned.is_empty() {
+        return Ok(());
+    }
+
+    let field = |data: &serde_json::Value, key: &str| {
+        data.get(key)
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.0)
+    };
+    orphaned.sort_by(|a, b| field(&b.1, "salience").total_cmp(&field(&a.1, "salience")));
+
+    let _ = writeln!(out, "### Orphaned Code");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "Load-bearing files written mostly by contributors who are no longer active. \
+         Read carefully and ask the listed people before changing behavior."
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| File | Inactive Share | Salience | Ask |");
+    let _ = writeln!(out, "|------|---------------:|---------:|-----|");
+
+    for (name, data) in orphaned.iter().take(10) {
+        let experts: Vec<&str> = data
+            .get("contributors")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|c| c.get("active").and_then(serde_json::Value::as_bool) == Some(true))
+            .filter_map(|c| c.get("email").and_then(serde_json::Value::as_str))
+            .take(2)
+            .collect();
+        let ask = if experts.is_empty() {
+            "*no active contributor*".to_string()
+        } else {
+            experts.join(", ")
+        };
+        let _ = writeln!(
+            out,
+            "| `{name}` | {:.0}% | {:.2} | {ask} |",
+            field(data, "inactive_share") * 100.0,
+            field(data, "salience"),
+        );
+    }
+    let _ = writeln!(out);
+
+    Ok(())
+}
+
+/// How many of the most salient entities are checked for missing coverage.
+const UNTESTED_SCAN: usize = 50;
+
+/// Highly salient functions and files that an ingested coverage report shows
+/// no test executing. Rendered as a subsection of Danger Zones, and only when
+/// coverage data exists.
+async fn render_untested_load_bearing(
+    out: &mut String,
+    store: &dyn HomerStore,
+) -> crate::error::Result<()> {
+    let mut salience: Vec<_> = store
+        .get_analyses_by_kind(AnalysisKind::CompositeSalience)
+        .await?
+        .into_iter()
+        .filter_map(|r| Some((r.node_id, r.data.get("score")?.as_f64()?)))
+        .collect();
+    salience.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut untested = Vec::new();
+    for (node_id, score) in salience.into_iter().take(UNTESTED_SCAN) {
+        let Some(node) = store.get_node(node_id).await? else {
+            continue;
+        };
+        let Some(coverage) = CoverageSummary::of(&node) else {
+            continue;
+        };
+        if coverage.lines_total > 0 && coverage.lines_covered == 0 {
+            untested.push((node.name, score, coverage.lines_total));
+        }
+    }
+    if untested.is_empty() {
+        return Ok(());
+    }
+
+    let _ = writeln!(out, "### Untested Load-Bearing Code");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "High-salience code with 0% line coverage in the latest coverage report. \
+         Add tests before changing it."
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Entity | Salience | Uncovered Lines |");
+    let _ = writeln!(out, "|--------|---------:|----------------:|");
+    for (name, score, lines) in untested.iter().take(10) {
+        let _ = writeln!(out, "| `{name}` | {score:.2} | {lines} |");
+    }
+    let _ = writeln!(out);
+
+    Ok(())
+}
+
+/// Flaky tests that exercise high-salience code. Rendered as a subsection
+/// of Danger Zones, and only when ingested test results show such tests.
+async fn render_flaky_tests(out: &mut String, store: &dyn HomerStore) -> crate::error::Result<()> {
+    let mut flaky = Vec::new();
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::TestFlakiness)
+        .await?
+    {
+        if result
+            .data
+            .get("high_salience")
+            .and_then(serde_json::Value::as_bool)
+            != Some(true)
+        {
+            continue;
+        }
+        let Some(test) = store.get_node(result.node_id).await? else {
+            continue;
+        };
+        let flaky_commits = result
+            .data
+            .get("flaky_commits")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        let exercises = result
+            .data
+            .get("exercises")
+            .and_then(|e| e.get(0))
+            .and_then(|e| e.get("name"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        flaky.push((test.name, flaky_commits, exercises));
+    }
+    if flaky.is_empty() {
+        return Ok(());
+    }
+    flaky.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let _ = writeln!(out, "### Flaky Tests on Critical Code");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "These tests both passed and failed at the same commit and exercise high-salience code. \
+         A failure here may be noise; a pass is not proof. Rerun before trusting either."
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Test | Flaky Commits | Exercises |");
+    let _ = writeln!(out, "|------|--------------:|-----------|");
+    for (name, commits, exercises) in flaky.iter().take(10) {
+        let _ = writeln!(out, "| `{name}` | {commits} | `{exercises}` |");
+    }
+    let _ = writeln!(out);
+
+    Ok(())
+}
+
+// ── Complexity Hotspots ──────────────────────────────────────────
+
+async fn render_complexity_hotspots(
+    out: &mut String,
+    store: &dyn HomerStore,
+) -> crate::error::Result<()> {
+    let mut hotspots = store
+        .get_analyses_by_kind(AnalysisKind::ComplexityHotspot)
+        .await?;
+    if hotspots.is_empty() {
+        return Ok(());
+    }
+
+    hotspots.sort_by(|a, b| {
+        let score_of = |r: &crate::types::AnalysisResult| {
+            r.data
+                .get("score")
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(0.0)
+        };
+        score_of(b)
+            .partial_cmp(&score_of(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let _ = writeln!(out, "## Complexity Hotspots");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "Complex functions in frequently changed files — simplify or add tests before extending them."
+    );
+    let _ = writeln!(out);
+
+    let _ = writeln!(
+        out,
+        "| Function | Cyclomatic | Nesting | File Changes | Trend |"
+    );
+    let _ = writeln!(
+        out,
+        "|----------|-----------:|--------:|-------------:|-------|"
+    );
+
+    for result in hotspots.iter().take(10) {
+        let name = store
+            .get_node(result.node_id)
+            .await?
+            .map_or_else(|| format!("node:{}", result.node_id.0), |n| n.name);
+        let field = |key: &str| {
+            result
+                .data
+                .get(key)
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(0)
+        };
+        let trend = result
+            .data
+            .get("trend")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("Stable");
+        let _ = writeln!(
+            out,
+            "| `{name}` | {} | {} | {} | {trend} |",
+            field("cyclomatic"),
+            field("max_nesting"),
+            field("file_changes"),
+        );
+    }
+    let _ = writeln!(out);
+
+    Ok(())
+}
+
 // ── Conventions ───────────────────────────────────────────────────
 
 async fn render_conventions(out: &mut String, store: &dyn HomerStore) -> crate::error::Result<()> {
@@ -1583,7 +2172,7 @@ mod tests {
 
         // Run behavioral analysis first
         let config = HomerConfig::default();
-        let analyzer = BehavioralAnalyzer;
+        let analyzer = BehavioralAnalyzer::default();
         analyzer.analyze(&store, &config).await.unwrap();
 
         // Now render
@@ -1623,6 +2212,415 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn render_orphaned_code_table() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut out = String::new();
+        render_orphaned_code(&mut out, &store).await.unwrap();
+        assert!(out.is_empty(), "No section without orphaned code");
+
+        let file_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::File,
+                name: "src/ledger.rs".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&crate::types::AnalysisResult {
+                id: crate::types::AnalysisResultId(0),
+                node_id: file_id,
+                kind: AnalysisKind::KnowledgeLoss,
+                data: serde_json::json!({
+                    "inactive_share": 0.85,
+                    "salience": 0.72,
+                    "orphaned": true,
+                    "contributors": [
+                        { "email": "founder@example.com", "active": false },
+                        { "email": "maintainer@example.com", "active": true },
+                    ],
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
+        render_orphaned_code(&mut out, &store).await.unwrap();
+        assert!(out.contains("### Orphaned Code"));
+        assert!(
+            out.contains("| `src/ledger.rs` | 85% | 0.72 | maintainer@example.com |"),
+            "Should list orphaned file row: {out}"
+        );
+    }
+
+    #[tokio::test]
+    async fn render_untested_load_bearing_table() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut out = String::new();
+        render_untested_load_bearing(&mut out, &store)
+            .await
+            .unwrap();
+        assert!(out.is_empty(), "No section without coverage data");
+
+        for (name, covered) in [("src/ledger.rs::post", 0), ("src/ledger.rs::open", 4)] {
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::Function,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: serde_json::from_value(serde_json::json!({ "coverage": {
+                        "lines_covered": covered, "lines_total": 12,
+                        "branches_covered": 0, "branches_total": 0,
+                    }}))
+                    .unwrap(),
+                })
+                .await
+                .unwrap();
+            store
+                .store_analysis(&crate::types::AnalysisResult {
+                    id: crate::types::AnalysisResultId(0),
+                    node_id: id,
+                    kind: AnalysisKind::CompositeSalience,
+                    data: serde_json::json!({ "score": 0.81 }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await
+                .unwrap();
+        }
+
+        render_untested_load_bearing(&mut out, &store)
+            .await
+            .unwrap();
+        assert!(out.contains("### Untested Load-Bearing Code"));
+        assert!(
+            out.contains("| `src/ledger.rs::post` | 0.81 | 12 |"),
+            "Should list uncovered function: {out}"
+        );
+        assert!(!out.contains("src/ledger.rs::open"), "Covered code omitted");
+    }
+
+    #[tokio::test]
+    async fn render_flaky_tests_table() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut out = String::new();
+        render_flaky_tests(&mut out, &store).await.unwrap();
+        assert!(out.is_empty(), "No section without flaky tests");
+
+        for (name, high_salience) in [("ledger::posts_twice", true), ("ui::renders", false)] {
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::Test,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            store
+                .store_analysis(&crate::types::AnalysisResult {
+                    id: crate::types::AnalysisResultId(0),
+                    node_id: id,
+                    kind: AnalysisKind::TestFlakiness,
+                    data: serde_json::json!({
+                        "flaky_commits": 3,
+                        "high_salience": high_salience,
+                        "exercises": [{ "name": "src/ledger.rs::post", "salience": 0.8 }],
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await
+                .unwrap();
+        }
+
+        render_flaky_tests(&mut out, &store).await.unwrap();
+        assert!(out.contains("### Flaky Tests on Critical Code"));
+        assert!(
+            out.contains("| `ledger::posts_twice` | 3 | `src/ledger.rs::post` |"),
+            "Should list flaky test on salient code: {out}"
+        );
+        assert!(!out.contains("ui::renders"), "Low-salience flake omitted");
+    }
+
+    #[tokio::test]
+    async fn render_ci_checks_by_directory() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut out = String::new();
+        render_ci_checks(&mut out, &store).await.unwrap();
+        assert!(out.is_empty(), "No section without CI jobs");
+
+        let node = |kind, name: &str, meta: serde_json::Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: now,
+            metadata: serde_json::from_value(meta).unwrap(),
+        };
+        let mut files = HashMap::new();
+        for path in [
+            "README.md",
+            "src/lib.rs",
+            "docs/intro.md",
+            "docs/api/index.md",
+            "web/app.ts",
+            "web/docs.md",
+        ] {
+            let id = store
+                .upsert_node(&node(NodeKind::File, path, serde_json::json!({})))
+                .await
+                .unwrap();
+            files.insert(path, id);
+        }
+        for (name, label, paths, on_change) in [
+            ("ci.yml::test", "CI / test", vec![], true),
+            (
+                "docs.yml::build",
+                "Docs / build",
+                vec!["docs/**", "**.md"],
+                true,
+            ),
+            ("nightly.yml::fuzz", "Nightly / fuzz", vec![], false),
+        ] {
+            let id = store
+                .upsert_node(&node(
+                    NodeKind::CiJob,
+                    name,
+                    serde_json::json!({
+                        "label": label,
+                        "paths": paths,
+                        "paths_ignore": [],
+                        "runs_on_change": on_change,
+                    }),
+                ))
+                .await
+                .unwrap();
+            if paths.is_empty() {
+                continue;
+            }
+            let mut members = vec![HyperedgeMember {
+                node_id: id,
+                role: "job".to_string(),
+                position: 0,
+            }];
+            for path in [
+                "README.md",
+                "docs/intro.md",
+                "docs/api/index.md",
+                "web/docs.md",
+            ] {
+                members.push(HyperedgeMember {
+                    node_id: files[path],
+                    role: "file".to_string(),
+                    position: 1,
+                });
+            }
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Triggers,
+                    members,
+                    confidence: 1.0,
+                    last_updated: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        render_ci_checks(&mut out, &store).await.unwrap();
+        assert!(out.contains("## CI Checks"));
+        assert!(
+            out.contains("Every pushed or proposed change runs: `CI / test`."),
+            "Unfiltered check runs everywhere: {out}"
+        );
+        assert!(!out.contains("Nightly"), "Scheduled jobs are not checks");
+        assert!(out.contains("| `README.md` | `Docs / build` |"), "{out}");
+        assert!(out.contains("| `docs/` | `Docs / build` |"), "{out}");
+        assert!(out.contains("| `web/docs.md` | `Docs / build` |"), "{out}");
+        assert!(
+            !out.contains("`src/`"),
+            "Directories without filtered checks omitted"
+        );
+    }
+
+    /// An `api` module built by a Dockerfile, run by a compose service that
+    /// also pulls an external image.
+    async fn seed_topology(store: &SqliteStore) {
+        let now = Utc::now();
+        let node = |kind, name: &str, meta: serde_json::Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: now,
+            metadata: serde_json::from_value(meta).unwrap(),
+        };
+        let module = store
+            .upsert_node(&node(NodeKind::Module, "api", serde_json::json!({})))
+            .await
+            .unwrap();
+        let dockerfile = store
+            .upsert_node(&node(
+                NodeKind::InfraResource,
+                "api/Dockerfile",
+                serde_json::json!({
+                    "infra_kind": "dockerfile",
+                    "label": "api/Dockerfile",
+                    "file": "api/Dockerfile",
+                    "ports": ["8080"],
+                }),
+            ))
+            .await
+            .unwrap();
+        let service = store
+            .upsert_node(&node(
+                NodeKind::InfraResource,
+                "compose.yaml::api",
+                serde_json::json!({
+                    "infra_kind": "compose_service",
+                    "label": "api",
+                    "file": "compose.yaml",
+                    "ports": ["80:8080"],
+                }),
+            ))
+            .await
+            .unwrap();
+        let image = store
+            .upsert_node(&node(
+                NodeKind::InfraResource,
+                "postgres:16",
+                serde_json::json!({ "infra_kind": "image", "label": "postgres:16", "file": "" }),
+            ))
+            .await
+            .unwrap();
+        let edge = |kind, members: Vec<(NodeId, &str)>| Hyperedge {
+            id: HyperedgeId(0),
+            kind,
+            members: members
+                .into_iter()
+                .zip(0..)
+                .map(|((node_id, role), position)| HyperedgeMember {
+                    node_id,
+                    role: role.to_string(),
+                    position,
+                })
+                .collect(),
+            confidence: 1.0,
+            last_updated: now,
+            metadata: HashMap::new(),
+        };
+        for (dependent, dependency) in [(service, dockerfile), (service, image)] {
+            store
+                .upsert_hyperedge(&edge(
+                    HyperedgeKind::Uses,
+                    vec![(dependent, "resource"), (dependency, "dependency")],
+                ))
+                .await
+                .unwrap();
+        }
+        store
+            .upsert_hyperedge(&edge(
+                HyperedgeKind::Builds,
+                vec![(dockerfile, "resource"), (module, "source")],
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn render_deployment_topology_table() {
+        let store = SqliteStore::in_memory().unwrap();
+
+        let mut out = String::new();
+        render_deployment_topology(&mut out, &store).await.unwrap();
+        assert!(out.is_empty(), "No section without infrastructure");
+
+        seed_topology(&store).await;
+        render_deployment_topology(&mut out, &store).await.unwrap();
+        assert!(out.contains("## Deployment Topology"));
+        assert!(
+            out.contains(
+                "| `api/Dockerfile` | Docker image | `api/Dockerfile` | `api/` | - | 8080 |"
+            ),
+            "Dockerfile built from its module: {out}"
+        );
+        assert!(
+            out.contains(
+                "| `api` | Compose service | `compose.yaml` | - | `api/Dockerfile`, `postgres:16` | 80:8080 |"
+            ),
+            "Service lists its dependencies: {out}"
+        );
+        assert!(
+            !out.contains("| `postgres:16` |"),
+            "External images are not rows"
+        );
+    }
+
+    #[tokio::test]
+    async fn render_complexity_hotspots_table() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let mut out = String::new();
+        render_complexity_hotspots(&mut out, &store).await.unwrap();
+        assert!(out.is_empty(), "No section without hotspots");
+
+        let func_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/parser.rs::parse_expr".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&crate::types::AnalysisResult {
+                id: crate::types::AnalysisResultId(0),
+                node_id: func_id,
+                kind: AnalysisKind::ComplexityHotspot,
+                data: serde_json::json!({
+                    "score": 0.9,
+                    "cyclomatic": 24,
+                    "max_nesting": 5,
+                    "file_changes": 41,
+                    "trend": "Rising",
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
+        render_complexity_hotspots(&mut out, &store).await.unwrap();
+        assert!(out.contains("## Complexity Hotspots"));
+        assert!(
+            out.contains("| `src/parser.rs::parse_expr` | 24 | 5 | 41 | Rising |"),
+            "Should list hotspot row: {out}"
+        );
+    }
+
     #[tokio::test]
     #[allow(clippy::too_many_lines)]
     async fn render_agent_intelligence_sections() {
@@ -0,0 +1,515 @@
+// CODEOWNERS renderer — proposes `.github/CODEOWNERS` from who actually
+// writes the code.
+
+#![allow(clippy::cast_precision_loss)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use tracing::{info, instrument};
+
+use crate::config::{CodeownersConfig, HomerConfig};
+use crate::contracts;
+use crate::extract::codeowners::owner_matches;
+use crate::store::HomerStore;
+use crate::types::{AnalysisKind, HyperedgeKind, NodeFilter, NodeId, NodeKind};
+
+use super::traits::{Renderer, merge_with_preserve, staleness_header};
+
+const MANUAL_HEADING: &str = "## Manual rules";
+
+/// Max drifted files listed in the comment section.
+const MAX_DRIFT: usize = 20;
+
+/// Proposes one rule per directory whose owners differ from its parent's.
+/// Hand-written rules survive regeneration inside `# homer:preserve` blocks.
+#[derive(Debug)]
+pub struct CodeownersRenderer;
+
+#[async_trait::async_trait]
+impl Renderer for CodeownersRenderer {
+    fn name(&self) -> &'static str {
+        "codeowners"
+    }
+
+    fn output_path(&self) -> &'static str {
+        ".github/CODEOWNERS"
+    }
+
+    #[instrument(skip_all, name = "codeowners_render")]
+    async fn render(
+        &self,
+        store: &dyn HomerStore,
+        config: &HomerConfig,
+    ) -> crate::error::Result<String> {
+        let settings = &config.renderers.codeowners;
+        let history = load_history(store).await?;
+        let blame = load_blame(store).await?;
+        let inactive = load_inactive(store).await?;
+        let handles = load_handles(store, &history.emails).await?;
+
+        let mut shares: BTreeMap<String, Vec<(String, f64)>> = BTreeMap::new();
+        for (dir, commits) in &history.dirs {
+            let total: u64 = commits.values().sum();
+            if total < u64::from(settings.min_commits) {
+                continue;
+            }
+            shares.insert(dir.clone(), rank_owners(commits, blame.get(dir), &inactive));
+        }
+        let rules = propose_rules(&shares, settings, &handles);
+
+        let mut out = String::with_capacity(2048);
+        render_header(&mut out, store, settings).await;
+        render_rules(&mut out, &rules);
+        render_drift(&mut out, store).await?;
+        let _ = writeln!(out, "{MANUAL_HEADING}");
+
+        info!(rules = rules.len(), "CODEOWNERS rendered");
+        Ok(out)
+    }
+
+    fn merge(&self, existing: &str, new_content: &str) -> String {
+        merge_with_preserve(&adopt_existing(existing), new_content)
+    }
+}
+
+/// A file Homer did not generate is wrapped whole in a preserve block, so
+/// its rules are carried over (and still win, coming last).
+fn adopt_existing(existing: &str) -> String {
+    if existing.contains("homer:generated") || existing.contains("# homer:preserve") {
+        return existing.to_string();
+    }
+    format!(
+        "{MANUAL_HEADING}\n# homer:preserve\n{}\n# /homer:preserve\n",
+        existing.trim_end()
+    )
+}
+
+// ── Data loading ──────────────────────────────────────────────────
+
+struct History {
+    /// Contributor node → email.
+    emails: HashMap<NodeId, String>,
+    /// Directory (`""` for the root) → author email → commits touching it.
+    dirs: HashMap<String, HashMap<String, u64>>,
+}
+
+async fn load_history(store: &dyn HomerStore) -> crate::error::Result<History> {
+    let contributors = NodeFilter {
+        kind: Some(NodeKind::Contributor),
+        ..Default::default()
+    };
+    let emails: HashMap<NodeId, String> = store
+        .find_nodes(&contributors)
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect();
+    let files = NodeFilter {
+        kind: Some(NodeKind::File),
+        ..Default::default()
+    };
+    let paths: HashMap<NodeId, String> = store
+        .find_nodes(&files)
+        .await?
+        .into_iter()
+        .map(|n| (n.id, n.name))
+        .collect();
+
+    let mut dirs: HashMap<String, HashMap<String, u64>> = HashMap::new();
+    for change in contracts::load_commit_changes(store).await? {
+        let Some(author) = change.author.and_then(|a| emails.get(&a)) else {
+            continue;
+        };
+        // Each directory counts a commit once, however many files it touched.
+        let touched: HashSet<String> = change
+            .files
+            .iter()
+            .filter_map(|f| paths.get(f))
+            .flat_map(|path| {
+                Path::new(path)
+                    .ancestors()
+                    .skip(1)
+                    .map(|d| d.to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for dir in touched {
+            *dirs
+                .entry(dir)
+                .or_default()
+                .entry(author.clone())
+                .or_default() += 1;
+        }
+    }
+
+    Ok(History { emails, dirs })
+}
+
+/// Directory → author email → surviving lines, from module `CodeSurvival`.
+async fn load_blame(
+    store: &dyn HomerStore,
+) -> crate::error::Result<HashMap<String, HashMap<String, u64>>> {
+    let filter = NodeFilter {
+        kind: Some(NodeKind::Module),
+        ..Default::default()
+    };
+    let root = contracts::find_root_module_id(store).await?;
+    let modules: HashMap<NodeId, String> = store
+        .find_nodes(&filter)
+        .await?
+        .into_iter()
+        .map(|n| {
+            let dir = if Some(n.id) == root {
+                String::new()
+            } else {
+                n.name
+            };
+            (n.id, dir)
+        })
+        .collect();
+
+    let mut blame = HashMap::new();
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::CodeSurvival)
+        .await?
+    {
+        let Some(dir) = modules.get(&result.node_id) else {
+            continue;
+        };
+        let Some(entries) = result.data.get("blame").and_then(|b| b.as_array()) else {
+            continue;
+        };
+        let lines: HashMap<String, u64> = entries
+            .iter()
+            .filter_map(|e| {
+                let email = e.get("contributor")?.as_str()?;
+                Some((email.to_string(), e.get("lines")?.as_u64()?))
+            })
+            .collect();
+        blame.insert(dir.clone(), lines);
+    }
+    Ok(blame)
+}
+
+/// Emails of contributors marked inactive by `ContributorActivity`.
+async fn load_inactive(store: &dyn HomerStore) -> crate::error::Result<HashSet<String>> {
+    let mut inactive = HashSet::new();
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::ContributorActivity)
+        .await?
+    {
+        if result
+            .data
+            .get("active")
+            .and_then(serde_json::Value::as_bool)
+            == Some(false)
+        {
+            if let Some(node) = store.get_node(result.node_id).await? {
+                inactive.insert(node.name);
+            }
+        }
+    }
+    Ok(inactive)
+}
+
+/// Email → the `@login` or email the current CODEOWNERS already uses for
+/// that person, so proposals keep familiar handles.
+async fn load_handles(
+    store: &dyn HomerStore,
+    emails: &HashMap<NodeId, String>,
+) -> crate::error::Result<HashMap<String, String>> {
+    let mut listed: Vec<String> = Vec::new();
+    for edge in store.get_edges_by_kind(HyperedgeKind::Owns).await? {
+        let owners: Vec<String> = edge
+            .metadata
+            .get("owners")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        for owner in owners {
+            if !listed.contains(&owner) {
+                listed.push(owner);
+            }
+        }
+    }
+
+    let mut handles = HashMap::new();
+    for id in emails.keys() {
+        let Some(node) = store.get_node(*id).await? else {
+            continue;
+        };
+        let display_name = node
+            .metadata
+            .get("display_name")
+            .and_then(serde_json::Value::as_str);
+        if let Some(owner) = listed
+            .iter()
+            .find(|o| owner_matches(o, &node.name, display_name))
+        {
+            handles.insert(node.name, owner.clone());
+        }
+    }
+    Ok(handles)
+}
+
+// ── Proposal ──────────────────────────────────────────────────────
+
+/// Active authors ranked by the mean of their commit share and, where a
+/// blame summary exists, their surviving-line share.
+fn rank_owners(
+    commits: &HashMap<String, u64>,
+    blame: Option<&HashMap<String, u64>>,
+    inactive: &HashSet<String>,
+) -> Vec<(String, f64)> {
+    let total_commits: u64 = commits.values().sum();
+    let total_lines: u64 = blame.map_or(0, |b| b.values().sum());
+
+    let mut authors: HashSet<&String> = commits.keys().collect();
+    if let Some(blame) = blame {
+        authors.extend(blame.keys());
+    }
+    let mut ranked: Vec<(String, f64)> = authors
+        .into_iter()
+        .filter(|a| !inactive.contains(*a))
+        .map(|author| {
+            let commit_share =
+                commits.get(author).copied().unwrap_or(0) as f64 / total_commits.max(1) as f64;
+            let share = match blame {
+                Some(b) if total_lines > 0 => {
+                    let line_share =
+                        b.get(author).copied().unwrap_or(0) as f64 / total_lines as f64;
+                    f64::midpoint(commit_share, line_share)
+                }
+                _ => commit_share,
+            };
+            (author.clone(), share)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// One proposed rule: a CODEOWNERS pattern and its owners.
+#[derive(Debug, PartialEq)]
+struct Rule {
+    pattern: String,
+    owners: Vec<String>,
+}
+
+/// Rules for the root and every directory (parents first) whose owners
+/// differ from those of its nearest ruled ancestor.
+fn propose_rules(
+    shares: &BTreeMap<String, Vec<(String, f64)>>,
+    settings: &CodeownersConfig,
+    handles: &HashMap<String, String>,
+) -> Vec<Rule> {
+    let owners_of = |ranked: &[(String, f64)]| -> Vec<String> {
+        ranked
+            .iter()
+            .filter(|(_, share)| *share >= settings.min_share)
+            .take(settings.max_owners as usize)
+            .map(|(email, _)| handles.get(email).unwrap_or(email).clone())
+            .collect()
+    };
+
+    let mut rules = Vec::new();
+    let mut owners_by_dir: HashMap<&str, Vec<String>> = HashMap::new();
+    // BTreeMap order puts "" first and every parent before its children.
+    for (dir, ranked) in shares {
+        let depth = if dir.is_empty() {
+            0
+        } else {
+            dir.split('/').count()
+        };
+        if depth > settings.max_depth as usize {
+            continue;
+        }
+        let owners = owners_of(ranked);
+        if owners.is_empty() {
+            continue;
+        }
+        let inherited = Path::new(dir)
+            .ancestors()
+            .skip(1)
+            .find_map(|parent| owners_by_dir.get(parent.to_str().unwrap_or_default()));
+        if inherited == Some(&owners) {
+            continue;
+        }
+        let pattern = if dir.is_empty() {
+            "*".to_string()
+        } else {
+            format!("/{dir}/")
+        };
+        owners_by_dir.insert(dir, owners.clone());
+        rules.push(Rule { pattern, owners });
+    }
+    rules
+}
+
+// ── Sections ──────────────────────────────────────────────────────
+
+async fn render_header(out: &mut String, store: &dyn HomerStore, settings: &CodeownersConfig) {
+    let stamp = staleness_header(store).await;
+    let stamp = stamp.trim_start_matches("<!-- ").trim_end_matches(" -->");
+    let _ = writeln!(
+        out,
+        "# CODEOWNERS proposed by Homer from blame and commit history."
+    );
+    let _ = writeln!(out, "# {stamp}");
+    let _ = writeln!(out, "#");
+    let _ = writeln!(
+        out,
+        "# Owners hold at least {:.0}% of a directory's surviving lines and commits",
+        settings.min_share * 100.0
+    );
+    let _ = writeln!(
+        out,
+        "# and are still active. Rules inside `# homer:preserve` ... `# /homer:preserve`"
+    );
+    let _ = writeln!(
+        out,
+        "# survive regeneration; keep them under \"{}\" so they take precedence.",
+        MANUAL_HEADING.trim_start_matches("## ")
+    );
+    out.push('\n');
+}
+
+fn render_rules(out: &mut String, rules: &[Rule]) {
+    let _ = writeln!(out, "## Proposed owners");
+    if rules.is_empty() {
+        let _ = writeln!(out, "# Not enough commit history to propose owners.");
+    }
+    let width = rules.iter().map(|r| r.pattern.len()).max().unwrap_or(0);
+    for rule in rules {
+        let _ = writeln!(out, "{:width$} {}", rule.pattern, rule.owners.join(" "));
+    }
+    out.push('\n');
+}
+
+/// Files flagged by `OwnershipDrift`, listed as comments.
+async fn render_drift(out: &mut String, store: &dyn HomerStore) -> crate::error::Result<()> {
+    let mut drifted = Vec::new();
+    for result in store
+        .get_analyses_by_kind(AnalysisKind::OwnershipDrift)
+        .await?
+    {
+        let Some(node) = store.get_node(result.node_id).await? else {
+            continue;
+        };
+        let commits = result
+            .data
+            .get("total_commits")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        drifted.push((node.name, commits, result.data));
+    }
+    if drifted.is_empty() {
+        return Ok(());
+    }
+    drifted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let strings = |data: &serde_json::Value, key: &str, field: Option<&str>| -> String {
+        data.get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|i| field.map_or(Some(i), |f| i.get(f))?.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default()
+    };
+
+    let _ = writeln!(out, "## Ownership drift");
+    let _ = writeln!(
+        out,
+        "# {} file(s) list owners who have never committed to them:",
+        drifted.len()
+    );
+    for (path, _, data) in drifted.iter().take(MAX_DRIFT) {
+        let _ = writeln!(
+            out,
+            "#   {path}: listed {}; written by {}",
+            strings(data, "owners", None),
+            strings(data, "top_authors", Some("email"))
+        );
+    }
+    out.push('\n');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(entries: &[(&str, f64)]) -> Vec<(String, f64)> {
+        entries
+            .iter()
+            .map(|(e, s)| ((*e).to_string(), *s))
+            .collect()
+    }
+
+    #[test]
+    fn ranks_by_commits_and_blame_skipping_inactive() {
+        let commits = HashMap::from([
+            ("a@x.com".to_string(), 6),
+            ("b@x.com".to_string(), 2),
+            ("gone@x.com".to_string(), 2),
+        ]);
+        let blame = HashMap::from([("a@x.com".to_string(), 10), ("b@x.com".to_string(), 90)]);
+        let inactive = HashSet::from(["gone@x.com".to_string()]);
+
+        let owners = rank_owners(&commits, Some(&blame), &inactive);
+        assert_eq!(owners[0].0, "b@x.com", "most surviving lines: {owners:?}");
+        assert!((owners[0].1 - 0.55).abs() < 1e-9);
+        assert!((owners[1].1 - 0.35).abs() < 1e-9);
+        assert_eq!(owners.len(), 2);
+    }
+
+    #[test]
+    fn children_only_get_rules_when_owners_change() {
+        let shares = BTreeMap::from([
+            (String::new(), ranked(&[("a@x.com", 0.7), ("b@x.com", 0.2)])),
+            ("src".to_string(), ranked(&[("a@x.com", 0.9)])),
+            (
+                "src/billing".to_string(),
+                ranked(&[("b@x.com", 0.6), ("a@x.com", 0.4)]),
+            ),
+            (
+                "src/billing/tax".to_string(),
+                ranked(&[("b@x.com", 0.6), ("a@x.com", 0.4)]),
+            ),
+            ("a/b/c/d".to_string(), ranked(&[("c@x.com", 1.0)])),
+        ]);
+        let handles = HashMap::from([("b@x.com".to_string(), "@bee".to_string())]);
+
+        let rules = propose_rules(&shares, &CodeownersConfig::default(), &handles);
+        assert_eq!(
+            rules,
+            vec![
+                Rule {
+                    pattern: "*".into(),
+                    owners: vec!["a@x.com".into()],
+                },
+                Rule {
+                    pattern: "/src/billing/".into(),
+                    owners: vec!["@bee".into(), "a@x.com".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn adopts_hand_written_files_as_manual_rules() {
+        let generated = "# homer:generated\n## Proposed owners\n* a@x.com\n\n## Manual rules\n";
+
+        let merged = CodeownersRenderer.merge("* @team\n/docs/ @writers\n", generated);
+        assert!(merged.ends_with(
+            "## Manual rules\n# homer:preserve\n* @team\n/docs/ @writers\n# /homer:preserve\n"
+        ));
+        // Regenerating over Homer's own output keeps the block once.
+        assert_eq!(CodeownersRenderer.merge(&merged, generated), merged);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod agents_md;
+pub mod codeowners;
 pub mod module_context;
 pub mod report;
 pub mod risk_map;
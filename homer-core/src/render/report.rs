@@ -1,7 +1,8 @@
 // Report renderer — produces `homer-report.html` with project health dashboard.
 //
 // Sections: Executive Summary, Architecture Diagram, Hotspot Map,
-// Coupling Analysis, Trend Charts, Risk Assessment, Documentation Health,
+// Runtime Hotspots, Coupling Analysis, Trend Charts, Risk Assessment, Dead Code, Code Clones,
+// Schema Hotspots, Configuration, Feature Flags, Documentation Health,
 // Agent Effectiveness, Team Topology.
 
 #![allow(
@@ -10,15 +11,17 @@
     clippy::cast_sign_loss
 )]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Write as _;
 
 use tracing::{info, instrument};
 
 use crate::config::HomerConfig;
 use crate::contracts;
+use crate::contracts::roles;
+use crate::query;
 use crate::store::HomerStore;
-use crate::types::{AnalysisKind, NodeFilter, NodeId, NodeKind};
+use crate::types::{AnalysisKind, Hyperedge, HyperedgeKind, NodeFilter, NodeId, NodeKind};
 
 use super::traits::Renderer;
 
@@ -39,9 +42,9 @@ impl Renderer for ReportRenderer {
     async fn render(
         &self,
         store: &dyn HomerStore,
-        _config: &HomerConfig,
+        config: &HomerConfig,
     ) -> crate::error::Result<String> {
-        let data = load_report_data(store).await?;
+        let data = load_report_data(store, config).await?;
         let out = render_html(&data);
         info!(bytes = out.len(), "Report rendered");
         Ok(out)
@@ -60,9 +63,18 @@ struct ReportData {
     community_count: u32,
     avg_bus_factor: f64,
     hotspots: Vec<HotspotEntry>,
+    runtime_hotspots: Vec<RuntimeEntry>,
     communities: HashMap<u32, Vec<String>>,
     coupling_pairs: Vec<(String, String, f64)>,
     risk_areas: Vec<RiskEntry>,
+    dead_code: Vec<DeadCodeEntry>,
+    dead_code_count: usize,
+    clone_groups: Vec<CloneGroupEntry>,
+    schema_hotspots: Vec<SchemaHotspotEntry>,
+    config_gaps: Vec<ConfigGapEntry>,
+    /// Feature flags worth removing, and the age that makes them so.
+    stale_flags: Vec<query::FlagStatus>,
+    stale_after_days: u32,
     documentation_coverage: f64,
     documented_entity_count: u32,
     total_entity_count: u32,
@@ -70,6 +82,8 @@ struct ReportData {
     correction_hotspots: Vec<AgentEntry>,
     prompt_hotspots: Vec<AgentEntry>,
     contributor_distribution: Vec<ContributorEntry>,
+    knowledge_loss: Vec<KnowledgeLossEntry>,
+    team_alignment: Option<TeamAlignmentData>,
 }
 
 struct HotspotEntry {
@@ -78,15 +92,56 @@ struct HotspotEntry {
     classification: String,
 }
 
+struct RuntimeEntry {
+    name: String,
+    self_share: f64,
+    total_share: f64,
+    /// Composite salience, when the function has one.
+    salience: Option<f64>,
+}
+
+struct SchemaHotspotEntry {
+    table: String,
+    schema_changes: u64,
+    last_changed: String,
+    /// High-salience code accessing the table, with its salience.
+    accessors: Vec<(String, f64)>,
+}
+
+struct ConfigGapEntry {
+    variable: String,
+    /// `env` for an environment variable, `config` for a config key.
+    kind: &'static str,
+    /// Read by code but documented nowhere; otherwise documented but unread.
+    undocumented: bool,
+    /// Readers of an undocumented variable, or files defining an unused one.
+    related: Vec<String>,
+}
+
 struct RiskEntry {
     path: String,
     score: f64,
     reasons: Vec<String>,
 }
 
+struct DeadCodeEntry {
+    name: String,
+    confidence: f64,
+}
+
+struct CloneGroupEntry {
+    members: Vec<String>,
+    similarity: f64,
+    diverged: bool,
+    /// Most recent change that edited some members but not the others.
+    last_divergent_commit: Option<String>,
+}
+
 struct TrendEntry {
     name: String,
     trend: String,
+    /// `PageRank` recorded by recent snapshots: (label, score), oldest first.
+    pagerank_history: Vec<(String, f64)>,
 }
 
 struct AgentEntry {
@@ -100,9 +155,29 @@ struct ContributorEntry {
     top_contributor_pct: f64,
 }
 
+struct KnowledgeLossEntry {
+    name: String,
+    inactive_pct: f64,
+    /// Active contributors, most commits first.
+    experts: Vec<String>,
+    orphaned: bool,
+}
+
+struct TeamAlignmentData {
+    /// Share of cross-community co-change that stays within one team.
+    alignment_score: Option<f64>,
+    /// Communities with their owning team: (label, team, owner share).
+    communities: Vec<(String, String, f64)>,
+    /// (label A, team A, label B, team B, co-changes, coupling)
+    hotspots: Vec<(String, String, String, String, u64, f64)>,
+}
+
 // ── Data loading ─────────────────────────────────────────────────────
 
-async fn load_report_data(store: &dyn HomerStore) -> crate::error::Result<ReportData> {
+async fn load_report_data(
+    store: &dyn HomerStore,
+    config: &HomerConfig,
+) -> crate::error::Result<ReportData> {
     let file_count = count_nodes(store, NodeKind::File).await?;
     let function_count = count_nodes(store, NodeKind::Function).await?;
     let type_count = count_nodes(store, NodeKind::Type).await?;
@@ -110,16 +185,28 @@ async fn load_report_data(store: &dyn HomerStore) -> crate::error::Result<Report
     let contributor_count = count_nodes(store, NodeKind::Contributor).await?;
 
     let hotspots = load_hotspots(store).await?;
+    let runtime_hotspots = load_runtime_hotspots(store).await?;
     let (communities, community_count) = load_communities(store).await?;
     let avg_bus_factor = load_avg_bus_factor(store).await?;
     let coupling_pairs = load_coupling_pairs(store).await?;
     let risk_areas = load_risk_areas(store).await?;
+    let (dead_code, dead_code_count) = load_dead_code(store).await?;
+    let clone_groups = load_clone_groups(store).await?;
+    let schema_hotspots =
+        load_schema_hotspots(store, config.extraction.schema.churn_threshold).await?;
+    let config_gaps = load_config_gaps(store).await?;
+    let stale_after_days = config.extraction.flags.stale_after_days;
+    let mut stale_flags = query::flag_inventory(store, stale_after_days).await?;
+    stale_flags.retain(|f| f.cleanup_candidate);
+    stale_flags.truncate(20);
     let (documentation_coverage, total_entity_count, documented_entity_count) =
         load_doc_coverage(store).await?;
     let trends = load_trends(store).await?;
     let correction_hotspots = load_agent_entries(store, AnalysisKind::CorrectionHotspot).await?;
     let prompt_hotspots = load_agent_entries(store, AnalysisKind::PromptHotspot).await?;
     let contributor_distribution = load_contributor_distribution(store).await?;
+    let knowledge_loss = load_knowledge_loss(store).await?;
+    let team_alignment = load_team_alignment(store).await?;
 
     let commit = store
         .get_checkpoint("git_last_sha")
@@ -138,9 +225,17 @@ async fn load_report_data(store: &dyn HomerStore) -> crate::error::Result<Report
         community_count,
         avg_bus_factor,
         hotspots,
+        runtime_hotspots,
         communities,
         coupling_pairs,
         risk_areas,
+        dead_code,
+        dead_code_count,
+        clone_groups,
+        schema_hotspots,
+        config_gaps,
+        stale_flags,
+        stale_after_days,
         documentation_coverage,
         documented_entity_count,
         total_entity_count,
@@ -148,6 +243,8 @@ async fn load_report_data(store: &dyn HomerStore) -> crate::error::Result<Report
         correction_hotspots,
         prompt_hotspots,
         contributor_distribution,
+        knowledge_loss,
+        team_alignment,
     })
 }
 
@@ -193,6 +290,43 @@ async fn load_hotspots(store: &dyn HomerStore) -> crate::error::Result<Vec<Hotsp
     Ok(hotspots)
 }
 
+async fn load_runtime_hotspots(store: &dyn HomerStore) -> crate::error::Result<Vec<RuntimeEntry>> {
+    let mut entries = Vec::new();
+    for r in store
+        .get_analyses_by_kind(AnalysisKind::RuntimeHotness)
+        .await?
+    {
+        if r.data.get("hot").and_then(serde_json::Value::as_bool) != Some(true) {
+            continue;
+        }
+        let Some(node) = store.get_node(r.node_id).await? else {
+            continue;
+        };
+        if node.kind != NodeKind::Function {
+            continue;
+        }
+        let share = |key: &str| {
+            r.data
+                .get(key)
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(0.0)
+        };
+        let salience = store
+            .get_analysis(r.node_id, AnalysisKind::CompositeSalience)
+            .await?
+            .and_then(|s| s.data.get("score").and_then(serde_json::Value::as_f64));
+        entries.push(RuntimeEntry {
+            name: node.name,
+            self_share: share("self_share"),
+            total_share: share("total_share"),
+            salience,
+        });
+    }
+    entries.sort_by(|a, b| b.total_share.total_cmp(&a.total_share));
+    entries.truncate(20);
+    Ok(entries)
+}
+
 async fn load_communities(
     store: &dyn HomerStore,
 ) -> crate::error::Result<(HashMap<u32, Vec<String>>, u32)> {
@@ -354,26 +488,336 @@ async fn load_doc_coverage(store: &dyn HomerStore) -> crate::error::Result<(f64,
     }))
 }
 
+async fn load_dead_code(
+    store: &dyn HomerStore,
+) -> crate::error::Result<(Vec<DeadCodeEntry>, usize)> {
+    let results = store.get_analyses_by_kind(AnalysisKind::DeadCode).await?;
+    let total = results.len();
+    let mut entries: Vec<DeadCodeEntry> = Vec::new();
+    for r in &results {
+        let confidence = r
+            .data
+            .get("confidence")
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.0);
+        let name = resolve_name(store, r.node_id).await?;
+        entries.push(DeadCodeEntry { name, confidence });
+    }
+    entries.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    entries.truncate(20);
+    Ok((entries, total))
+}
+
+/// One entry per clone group, diverged groups first, then largest.
+async fn load_clone_groups(store: &dyn HomerStore) -> crate::error::Result<Vec<CloneGroupEntry>> {
+    let results = store.get_analyses_by_kind(AnalysisKind::CloneGroup).await?;
+    let mut groups: HashMap<String, CloneGroupEntry> = HashMap::new();
+    for r in &results {
+        let Some(group) = r.data.get("group").and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        if groups.contains_key(group) {
+            continue;
+        }
+        let mut members = vec![resolve_name(store, r.node_id).await?];
+        if let Some(others) = r.data.get("members").and_then(serde_json::Value::as_array) {
+            members.extend(
+                others
+                    .iter()
+                    .filter_map(|m| m.get("name").and_then(serde_json::Value::as_str))
+                    .map(String::from),
+            );
+        }
+        members.sort();
+        let last_divergent_commit = r
+            .data
+            .get("divergent_changes")
+            .and_then(|c| c.get(0))
+            .and_then(|c| c.get("commit"))
+            .and_then(serde_json::Value::as_str)
+            .map(|c| c.chars().take(7).collect());
+        groups.insert(
+            group.to_string(),
+            CloneGroupEntry {
+                members,
+                similarity: r
+                    .data
+                    .get("similarity")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0),
+                diverged: r
+                    .data
+                    .get("diverged")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false),
+                last_divergent_commit,
+            },
+        );
+    }
+    let mut entries: Vec<CloneGroupEntry> = groups.into_values().collect();
+    entries.sort_by(|a, b| {
+        b.diverged
+            .cmp(&a.diverged)
+            .then_with(|| b.members.len().cmp(&a.members.len()))
+            .then_with(|| a.members.cmp(&b.members))
+    });
+    Ok(entries)
+}
+
+/// Tables changed by at least `churn_threshold` migrations and accessed by
+/// high-salience code, most churned first.
+async fn load_schema_hotspots(
+    store: &dyn HomerStore,
+    churn_threshold: u32,
+) -> crate::error::Result<Vec<SchemaHotspotEntry>> {
+    let mut entries = Vec::new();
+    for table in store
+        .find_nodes(&NodeFilter {
+            kind: Some(NodeKind::Table),
+            ..Default::default()
+        })
+        .await?
+    {
+        let schema_changes = table
+            .metadata
+            .get("schema_changes")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        if schema_changes < u64::from(churn_threshold) {
+            continue;
+        }
+        let columns = store
+            .find_nodes(&NodeFilter {
+                kind: Some(NodeKind::Column),
+                name_prefix: Some(format!("{}.", table.name)),
+                ..Default::default()
+            })
+            .await?;
+        let mut code = HashSet::new();
+        for id in std::iter::once(table.id).chain(columns.iter().map(|c| c.id)) {
+            for edge in store.get_edges_involving(id).await? {
+                if edge.kind != HyperedgeKind::Accesses {
+                    continue;
+                }
+                if let Some(m) = contracts::find_member_by_roles(&edge.members, &[roles::ACCESSOR])
+                {
+                    code.insert(m.node_id);
+                }
+            }
+        }
+        let mut accessors = BTreeMap::new();
+        for id in code {
+            let Some((name, salience)) = accessor_salience(store, id).await? else {
+                continue;
+            };
+            if salience > 0.3 {
+                accessors.insert(name, salience);
+            }
+        }
+        if accessors.is_empty() {
+            continue;
+        }
+        let mut accessors: Vec<(String, f64)> = accessors.into_iter().collect();
+        accessors.sort_by(|a, b| b.1.total_cmp(&a.1));
+        entries.push(SchemaHotspotEntry {
+            last_changed: table
+                .metadata
+                .get("last_changed")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            table: table.name,
+            schema_changes,
+            accessors,
+        });
+    }
+    entries.sort_by(|a, b| {
+        b.schema_changes
+            .cmp(&a.schema_changes)
+            .then_with(|| a.table.cmp(&b.table))
+    });
+    entries.truncate(20);
+    Ok(entries)
+}
+
+/// Variables and config keys read by code but documented nowhere, then
+/// environment variables documented for the repository's own code but read
+/// nowhere. Config keys count as undocumented only when the repository has
+/// config files at all.
+async fn load_config_gaps(store: &dyn HomerStore) -> crate::error::Result<Vec<ConfigGapEntry>> {
+    let mut entries = Vec::new();
+    let mut has_config_files = false;
+    for (kind, label) in [(NodeKind::EnvVar, "env"), (NodeKind::ConfigKey, "config")] {
+        for variable in store
+            .find_nodes(&NodeFilter {
+                kind: Some(kind.clone()),
+                ..Default::default()
+            })
+            .await?
+        {
+            let mut readers = BTreeSet::new();
+            let mut definitions = Vec::new();
+            for edge in store.get_edges_involving(variable.id).await? {
+                match edge.kind {
+                    HyperedgeKind::Reads => {
+                        if let Some(m) =
+                            contracts::find_member_by_roles(&edge.members, &[roles::READER])
+                        {
+                            readers.insert(resolve_name(store, m.node_id).await?);
+                        }
+                    }
+                    HyperedgeKind::Defines => definitions.push(edge),
+                    _ => {}
+                }
+            }
+            has_config_files |= kind == NodeKind::ConfigKey && !definitions.is_empty();
+            if !readers.is_empty() && definitions.is_empty() {
+                entries.push(ConfigGapEntry {
+                    variable: variable.name,
+                    kind: label,
+                    undocumented: true,
+                    related: readers.into_iter().collect(),
+                });
+            } else if kind == NodeKind::EnvVar && readers.is_empty() {
+                let mut sources = BTreeSet::new();
+                for edge in &definitions {
+                    if documents_own_code(store, edge).await? {
+                        sources.extend(
+                            edge.metadata
+                                .get("source")
+                                .and_then(serde_json::Value::as_str)
+                                .map(String::from),
+                        );
+                    }
+                }
+                if !sources.is_empty() {
+                    entries.push(ConfigGapEntry {
+                        variable: variable.name,
+                        kind: label,
+                        undocumented: false,
+                        related: sources.into_iter().collect(),
+                    });
+                }
+            }
+        }
+    }
+    if !has_config_files {
+        entries.retain(|e| e.kind != "config");
+    }
+    entries.sort_by(|a, b| {
+        b.undocumented
+            .cmp(&a.undocumented)
+            .then_with(|| a.variable.cmp(&b.variable))
+    });
+    entries.truncate(50);
+    Ok(entries)
+}
+
+/// Whether a `Defines` edge documents a variable for this repository's code:
+/// an env template, or a resource built from the repository's sources,
+/// directly or through one `Uses` hop. CI jobs and third-party images set
+/// variables for tools rather than for the code.
+async fn documents_own_code(
+    store: &dyn HomerStore,
+    edge: &Hyperedge,
+) -> crate::error::Result<bool> {
+    let meta = |key: &str| edge.metadata.get(key).and_then(serde_json::Value::as_str);
+    if meta("via") == Some("template") {
+        return Ok(true);
+    }
+    if meta("extractor") != Some("infra") {
+        return Ok(false);
+    }
+    let Some(definer) = contracts::find_member_by_roles(&edge.members, &[roles::DEFINER]) else {
+        return Ok(false);
+    };
+    let resource_edges = store.get_edges_involving(definer.node_id).await?;
+    let builds = |edges: &[Hyperedge], id: NodeId| {
+        edges.iter().any(|e| {
+            e.kind == HyperedgeKind::Builds
+                && e.members
+                    .iter()
+                    .any(|m| m.node_id == id && m.role == roles::BUILT_RESOURCE)
+        })
+    };
+    if builds(&resource_edges, definer.node_id) {
+        return Ok(true);
+    }
+    for edge in &resource_edges {
+        if edge.kind != HyperedgeKind::Uses
+            || !edge
+                .members
+                .iter()
+                .any(|m| m.node_id == definer.node_id && m.role == roles::DEPENDENT_RESOURCE)
+        {
+            continue;
+        }
+        for dependency in edge.members.iter().filter(|m| m.role == roles::DEPENDENCY) {
+            let edges = store.get_edges_involving(dependency.node_id).await?;
+            if builds(&edges, dependency.node_id) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Name and composite salience of code accessing a table, falling back to
+/// its file's salience when the code itself has none.
+async fn accessor_salience(
+    store: &dyn HomerStore,
+    id: NodeId,
+) -> crate::error::Result<Option<(String, f64)>> {
+    let Some(node) = store.get_node(id).await? else {
+        return Ok(None);
+    };
+    let salience = query::code_salience(store, &node).await?;
+    Ok(salience.map(|s| (node.name, s)))
+}
+
 async fn load_trends(store: &dyn HomerStore) -> crate::error::Result<Vec<TrendEntry>> {
     let results = store
         .get_analyses_by_kind(AnalysisKind::CentralityTrend)
         .await?;
+    let moving: Vec<(NodeId, &str)> = results
+        .iter()
+        .filter_map(|r| {
+            let trend = r
+                .data
+                .get("trend")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("Stable");
+            (trend != "Stable").then_some((r.node_id, trend))
+        })
+        .take(10)
+        .collect();
+    let ids: Vec<NodeId> = moving.iter().map(|&(id, _)| id).collect();
+    let mut series = store
+        .get_metric_series(&ids, AnalysisKind::PageRank)
+        .await?;
+
     let mut trends: Vec<TrendEntry> = Vec::new();
-    for r in &results {
-        let trend = r
-            .data
-            .get("trend")
-            .and_then(serde_json::Value::as_str)
-            .unwrap_or("Stable");
-        if trend != "Stable" {
-            let name = resolve_name(store, r.node_id).await?;
-            trends.push(TrendEntry {
-                name,
-                trend: trend.to_string(),
-            });
-        }
+    for (node_id, trend) in moving {
+        let name = resolve_name(store, node_id).await?;
+        let points = series.remove(&node_id).unwrap_or_default();
+        let pagerank_history = points
+            .iter()
+            .rev()
+            .take(5)
+            .rev()
+            .filter_map(|p| Some((p.label.clone(), p.data.get("pagerank")?.as_f64()?)))
+            .collect();
+        trends.push(TrendEntry {
+            name,
+            trend: trend.to_string(),
+            pagerank_history,
+        });
     }
-    trends.truncate(10);
     Ok(trends)
 }
 
@@ -431,6 +875,129 @@ async fn load_contributor_distribution(
     Ok(entries)
 }
 
+/// Files and modules where at least half the knowledge sits with inactive
+/// contributors, orphaned code first.
+async fn load_knowledge_loss(
+    store: &dyn HomerStore,
+) -> crate::error::Result<Vec<KnowledgeLossEntry>> {
+    let results = store
+        .get_analyses_by_kind(AnalysisKind::KnowledgeLoss)
+        .await?;
+    let mut entries: Vec<(KnowledgeLossEntry, f64)> = Vec::new();
+    for r in &results {
+        let inactive = r
+            .data
+            .get("inactive_share")
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.0);
+        if inactive < 0.5 {
+            continue;
+        }
+        let experts = r
+            .data
+            .get("contributors")
+            .and_then(serde_json::Value::as_array)
+            .map(|cs| {
+                cs.iter()
+                    .filter(|c| c.get("active").and_then(serde_json::Value::as_bool) == Some(true))
+                    .filter_map(|c| c.get("email").and_then(serde_json::Value::as_str))
+                    .take(3)
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let salience = r
+            .data
+            .get("salience")
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.0);
+        entries.push((
+            KnowledgeLossEntry {
+                name: resolve_name(store, r.node_id).await?,
+                inactive_pct: inactive,
+                experts,
+                orphaned: r.data.get("orphaned").and_then(serde_json::Value::as_bool) == Some(true),
+            },
+            salience,
+        ));
+    }
+    entries.sort_by(|(a, a_sal), (b, b_sal)| {
+        b.orphaned
+            .cmp(&a.orphaned)
+            .then(b_sal.total_cmp(a_sal))
+            .then(b.inactive_pct.total_cmp(&a.inactive_pct))
+    });
+    entries.truncate(15);
+    Ok(entries.into_iter().map(|(e, _)| e).collect())
+}
+
+async fn load_team_alignment(
+    store: &dyn HomerStore,
+) -> crate::error::Result<Option<TeamAlignmentData>> {
+    let Some(root) = contracts::find_root_module_id(store).await? else {
+        return Ok(None);
+    };
+    let Some(result) = store
+        .get_analysis(root, AnalysisKind::TeamAlignment)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let text = |v: &serde_json::Value, key: &str| {
+        v.get(key)
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let rows = |key: &str| {
+        result
+            .data
+            .get(key)
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let communities = rows("communities")
+        .iter()
+        .filter(|c| c.get("owner_team").is_some_and(|t| !t.is_null()))
+        .map(|c| {
+            let share = c
+                .get("owner_share")
+                .and_then(serde_json::Value::as_f64)
+                .unwrap_or(0.0);
+            (text(c, "label"), text(c, "owner_team"), share)
+        })
+        .collect();
+    let hotspots = rows("cross_team_coupling")
+        .iter()
+        .map(|h| {
+            (
+                text(h, "label_a"),
+                text(h, "team_a"),
+                text(h, "label_b"),
+                text(h, "team_b"),
+                h.get("co_changes")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or(0),
+                h.get("coupling")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0),
+            )
+        })
+        .take(10)
+        .collect();
+
+    Ok(Some(TeamAlignmentData {
+        alignment_score: result
+            .data
+            .get("alignment_score")
+            .and_then(serde_json::Value::as_f64),
+        communities,
+        hotspots,
+    }))
+}
+
 async fn resolve_name(store: &dyn HomerStore, node_id: NodeId) -> crate::error::Result<String> {
     Ok(store
         .get_node(node_id)
@@ -464,9 +1031,15 @@ fn render_html(data: &ReportData) -> String {
     render_executive_summary(&mut h, data);
     render_architecture_diagram(&mut h, data);
     render_hotspot_map(&mut h, data);
+    render_runtime_section(&mut h, data);
     render_coupling_section(&mut h, data);
     render_trends_section(&mut h, data);
     render_risk_section(&mut h, data);
+    render_dead_code_section(&mut h, data);
+    render_clones_section(&mut h, data);
+    render_schema_section(&mut h, data);
+    render_config_section(&mut h, data);
+    render_flags_section(&mut h, data);
     render_doc_health(&mut h, data);
     render_agent_effectiveness(&mut h, data);
     render_team_topology(&mut h, data);
@@ -571,6 +1144,170 @@ fn render_hotspot_map(h: &mut String, data: &ReportData) {
     let _ = writeln!(h, "</section>");
 }
 
+fn render_runtime_section(h: &mut String, data: &ReportData) {
+    if data.runtime_hotspots.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(h, "<section>");
+    let _ = writeln!(h, "<h2>Runtime Hotspots</h2>");
+    let _ = writeln!(
+        h,
+        "<p>Functions where ingested profiles spend the most time. Self time \
+         includes the library code they call; total time includes their callees.</p>"
+    );
+    let _ = writeln!(
+        h,
+        "<table><thead><tr><th>Function</th><th>Self</th><th>Total</th>\
+         <th>Salience</th></tr></thead><tbody>"
+    );
+
+    for entry in &data.runtime_hotspots {
+        let salience = entry
+            .salience
+            .map_or_else(|| "&mdash;".to_string(), |s| format!("{s:.2}"));
+        let _ = writeln!(
+            h,
+            "<tr><td><code>{}</code></td><td>{:.1}%</td><td>{:.1}%</td><td>{salience}</td></tr>",
+            entry.name,
+            entry.self_share * 100.0,
+            entry.total_share * 100.0
+        );
+    }
+
+    let _ = writeln!(h, "</tbody></table>");
+    let _ = writeln!(h, "</section>");
+}
+
+fn render_schema_section(h: &mut String, data: &ReportData) {
+    if data.schema_hotspots.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(h, "<section>");
+    let _ = writeln!(h, "<h2>Schema Hotspots</h2>");
+    let _ = writeln!(
+        h,
+        "<p>Tables that migrations keep changing and that high-salience code \
+         reads or writes. Each migration here is a change that code has to follow.</p>"
+    );
+    let _ = writeln!(
+        h,
+        "<table><thead><tr><th>Table</th><th>Migrations</th><th>Last Changed</th>\
+         <th>Accessed By</th></tr></thead><tbody>"
+    );
+
+    for entry in &data.schema_hotspots {
+        let accessors: Vec<String> = entry
+            .accessors
+            .iter()
+            .take(5)
+            .map(|(name, salience)| format!("<code>{name}</code> ({salience:.2})"))
+            .collect();
+        let _ = writeln!(
+            h,
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            entry.table,
+            entry.schema_changes,
+            entry.last_changed,
+            accessors.join(", ")
+        );
+    }
+
+    let _ = writeln!(h, "</tbody></table>");
+    let _ = writeln!(h, "</section>");
+}
+
+fn render_config_section(h: &mut String, data: &ReportData) {
+    if data.config_gaps.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(h, "<section>");
+    let _ = writeln!(h, "<h2>Configuration</h2>");
+    let _ = writeln!(
+        h,
+        "<p>Environment variables and config keys the code reads that no template, \
+         CI file, manifest, or config file documents, and environment variables \
+         documented for the code that nothing reads.</p>"
+    );
+    let _ = writeln!(
+        h,
+        "<table><thead><tr><th>Variable</th><th>Kind</th><th>Problem</th>\
+         <th>Where</th></tr></thead><tbody>"
+    );
+
+    for entry in &data.config_gaps {
+        let (problem, verb) = if entry.undocumented {
+            ("undocumented", "read by")
+        } else {
+            ("unused", "defined in")
+        };
+        let related: Vec<String> = entry
+            .related
+            .iter()
+            .take(5)
+            .map(|name| format!("<code>{name}</code>"))
+            .collect();
+        let _ = writeln!(
+            h,
+            "<tr><td><code>{}</code></td><td>{}</td><td>{problem}</td><td>{verb} {}</td></tr>",
+            entry.variable,
+            entry.kind,
+            related.join(", ")
+        );
+    }
+
+    let _ = writeln!(h, "</tbody></table>");
+    let _ = writeln!(h, "</section>");
+}
+
+fn render_flags_section(h: &mut String, data: &ReportData) {
+    if data.stale_flags.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(h, "<section>");
+    let _ = writeln!(h, "<h2>Feature Flags</h2>");
+    let _ = writeln!(
+        h,
+        "<p>Flags introduced more than {} days ago that high-salience code still \
+         checks. Each is a branch the code carries until the flag is removed.</p>",
+        data.stale_after_days
+    );
+    let _ = writeln!(
+        h,
+        "<table><thead><tr><th>Flag</th><th>Age (days)</th><th>Last Changed</th>\
+         <th>Checked By</th></tr></thead><tbody>"
+    );
+
+    for flag in &data.stale_flags {
+        let checkers: Vec<String> = flag
+            .checkers
+            .iter()
+            .take(5)
+            .map(|c| match c.salience {
+                Some(s) => format!("<code>{}</code> ({s:.2})", c.name),
+                None => format!("<code>{}</code>", c.name),
+            })
+            .collect();
+        let last_changed = flag
+            .last_changed
+            .as_deref()
+            .map_or("", |d| d.split('T').next().unwrap_or(d));
+        let _ = writeln!(
+            h,
+            "<tr><td><code>{}</code></td><td>{}</td><td>{last_changed}</td><td>{}</td></tr>",
+            flag.flag,
+            flag.age_days.unwrap_or_default(),
+            checkers.join(", ")
+        );
+    }
+
+    let _ = writeln!(h, "</tbody></table>");
+    let _ = writeln!(h, "</section>");
+}
+
 fn render_coupling_section(h: &mut String, data: &ReportData) {
     if data.coupling_pairs.is_empty() {
         return;
@@ -610,7 +1347,7 @@ fn render_trends_section(h: &mut String, data: &ReportData) {
     let _ = writeln!(h, "<p>Entities with changing structural importance.</p>");
     let _ = writeln!(
         h,
-        "<table><thead><tr><th>Entity</th><th>Trend</th></tr></thead><tbody>"
+        "<table><thead><tr><th>Entity</th><th>Trend</th><th>PageRank by Snapshot</th></tr></thead><tbody>"
     );
 
     for entry in &data.trends {
@@ -620,9 +1357,19 @@ fn render_trends_section(h: &mut String, data: &ReportData) {
             "Falling" => "&#x2193;",
             _ => "&#x2194;",
         };
+        let history = if entry.pagerank_history.is_empty() {
+            "&mdash;".to_string()
+        } else {
+            entry
+                .pagerank_history
+                .iter()
+                .map(|(label, score)| format!("{label}: {score:.4}"))
+                .collect::<Vec<_>>()
+                .join(" &rarr; ")
+        };
         let _ = writeln!(
             h,
-            "<tr><td><code>{short}</code></td><td>{icon} {}</td></tr>",
+            "<tr><td><code>{short}</code></td><td>{icon} {}</td><td>{history}</td></tr>",
             entry.trend
         );
     }
@@ -658,16 +1405,89 @@ fn render_risk_section(h: &mut String, data: &ReportData) {
     let _ = writeln!(h, "</section>");
 }
 
-fn render_doc_health(h: &mut String, data: &ReportData) {
+fn render_dead_code_section(h: &mut String, data: &ReportData) {
+    if data.dead_code.is_empty() {
+        return;
+    }
+
     let _ = writeln!(h, "<section>");
-    let _ = writeln!(h, "<h2>Documentation Health</h2>");
-    let _ = writeln!(h, "<div class=\"metrics\">");
-    emit_metric(
+    let _ = writeln!(h, "<h2>Dead Code</h2>");
+    let _ = writeln!(
         h,
-        "Coverage",
-        &format!("{:.0}%", data.documentation_coverage * 100.0),
+        "<p>{} functions and types are unreachable from any entry point \
+         (main, exported symbols, tests, or configured handlers).</p>",
+        data.dead_code_count
     );
-    emit_metric(h, "Documented", &data.documented_entity_count.to_string());
+    let _ = writeln!(
+        h,
+        "<table><thead><tr><th>Entity</th><th>Confidence</th></tr></thead><tbody>"
+    );
+
+    for entry in &data.dead_code {
+        let _ = writeln!(
+            h,
+            "<tr><td><code>{}</code></td><td>{:.2}</td></tr>",
+            entry.name, entry.confidence
+        );
+    }
+
+    let _ = writeln!(h, "</tbody></table>");
+    let _ = writeln!(h, "</section>");
+}
+
+fn render_clones_section(h: &mut String, data: &ReportData) {
+    if data.clone_groups.is_empty() {
+        return;
+    }
+
+    let diverged = data.clone_groups.iter().filter(|g| g.diverged).count();
+    let _ = writeln!(h, "<section>");
+    let _ = writeln!(h, "<h2>Code Clones</h2>");
+    let _ = writeln!(
+        h,
+        "<p>{} groups of duplicated or near-duplicated functions. {diverged} have \
+         diverged: a later change edited some copies but not the others, which \
+         often means a fix was applied only once.</p>",
+        data.clone_groups.len()
+    );
+    let _ = writeln!(
+        h,
+        "<table><thead><tr><th>Functions</th><th>Similarity</th><th>Diverged</th></tr></thead><tbody>"
+    );
+
+    for group in data.clone_groups.iter().take(20) {
+        let members: Vec<String> = group
+            .members
+            .iter()
+            .map(|m| format!("<code>{m}</code>"))
+            .collect();
+        let diverged = match (&group.diverged, &group.last_divergent_commit) {
+            (true, Some(commit)) => format!("<strong>yes</strong> (<code>{commit}</code>)"),
+            (true, None) => "<strong>yes</strong>".to_string(),
+            (false, _) => "no".to_string(),
+        };
+        let _ = writeln!(
+            h,
+            "<tr><td>{}</td><td>{:.0}%</td><td>{diverged}</td></tr>",
+            members.join("<br>"),
+            group.similarity * 100.0
+        );
+    }
+
+    let _ = writeln!(h, "</tbody></table>");
+    let _ = writeln!(h, "</section>");
+}
+
+fn render_doc_health(h: &mut String, data: &ReportData) {
+    let _ = writeln!(h, "<section>");
+    let _ = writeln!(h, "<h2>Documentation Health</h2>");
+    let _ = writeln!(h, "<div class=\"metrics\">");
+    emit_metric(
+        h,
+        "Coverage",
+        &format!("{:.0}%", data.documentation_coverage * 100.0),
+    );
+    emit_metric(h, "Documented", &data.documented_entity_count.to_string());
     emit_metric(h, "Total Entities", &data.total_entity_count.to_string());
     let _ = writeln!(h, "</div>");
     let _ = writeln!(h, "</section>");
@@ -715,30 +1535,111 @@ fn render_agent_effectiveness(h: &mut String, data: &ReportData) {
 }
 
 fn render_team_topology(h: &mut String, data: &ReportData) {
-    if data.contributor_distribution.is_empty() {
+    if data.contributor_distribution.is_empty()
+        && data.knowledge_loss.is_empty()
+        && data.team_alignment.is_none()
+    {
         return;
     }
     let _ = writeln!(h, "<section>");
     let _ = writeln!(h, "<h2>Team Topology</h2>");
+    if !data.contributor_distribution.is_empty() {
+        let _ = writeln!(
+            h,
+            "<p>Knowledge concentration risks (bus factor &le; 1 or single contributor &gt; 80%):</p>"
+        );
+        let _ = writeln!(
+            h,
+            "<table><tr><th>Entity</th><th>Bus Factor</th><th>Top Contributor %</th></tr>"
+        );
+        for e in &data.contributor_distribution {
+            let _ = writeln!(
+                h,
+                "<tr><td><code>{}</code></td><td>{}</td><td>{:.0}%</td></tr>",
+                e.name,
+                e.bus_factor,
+                e.top_contributor_pct * 100.0
+            );
+        }
+        let _ = writeln!(h, "</table>");
+    }
+    if !data.knowledge_loss.is_empty() {
+        let _ = writeln!(h, "<h3>Knowledge Loss</h3>");
+        let _ = writeln!(
+            h,
+            "<p>Code whose knowledge is held mostly by contributors who are no longer active:</p>"
+        );
+        let _ = writeln!(
+            h,
+            "<table><tr><th>Entity</th><th>Inactive %</th><th>Active Experts</th><th>Orphaned</th></tr>"
+        );
+        for e in &data.knowledge_loss {
+            let experts = if e.experts.is_empty() {
+                "&mdash;".to_string()
+            } else {
+                e.experts.join(", ")
+            };
+            let _ = writeln!(
+                h,
+                "<tr><td><code>{}</code></td><td>{:.0}%</td><td>{experts}</td><td>{}</td></tr>",
+                e.name,
+                e.inactive_pct * 100.0,
+                if e.orphaned { "yes" } else { "" }
+            );
+        }
+        let _ = writeln!(h, "</table>");
+    }
+    if let Some(alignment) = &data.team_alignment {
+        render_team_alignment(h, alignment);
+    }
+    let _ = writeln!(h, "</section>");
+}
+
+fn render_team_alignment(h: &mut String, alignment: &TeamAlignmentData) {
+    let _ = writeln!(h, "<h3>Team Alignment</h3>");
+    if let Some(score) = alignment.alignment_score {
+        let _ = writeln!(
+            h,
+            "<p>{:.0}% of changes spanning several communities stay within one team.</p>",
+            score * 100.0
+        );
+    }
+    if !alignment.communities.is_empty() {
+        let _ = writeln!(
+            h,
+            "<table><tr><th>Community</th><th>Owning Team</th><th>Team Share</th></tr>"
+        );
+        for (label, team, share) in &alignment.communities {
+            let _ = writeln!(
+                h,
+                "<tr><td><code>{label}</code></td><td>{team}</td><td>{:.0}%</td></tr>",
+                share * 100.0
+            );
+        }
+        let _ = writeln!(h, "</table>");
+    }
+    if alignment.hotspots.is_empty() {
+        return;
+    }
     let _ = writeln!(
         h,
-        "<p>Knowledge concentration risks (bus factor &le; 1 or single contributor &gt; 80%):</p>"
+        "<p>Cross-team coupling hotspots: communities owned by different teams that change together.</p>"
     );
     let _ = writeln!(
         h,
-        "<table><tr><th>Entity</th><th>Bus Factor</th><th>Top Contributor %</th></tr>"
+        "<table><tr><th>Community</th><th>Team</th><th>Community</th><th>Team</th>\
+         <th>Co-Changes</th><th>Coupling</th></tr>"
     );
-    for e in &data.contributor_distribution {
+    for (label_a, team_a, label_b, team_b, co_changes, coupling) in &alignment.hotspots {
         let _ = writeln!(
             h,
-            "<tr><td><code>{}</code></td><td>{}</td><td>{:.0}%</td></tr>",
-            e.name,
-            e.bus_factor,
-            e.top_contributor_pct * 100.0
+            "<tr><td><code>{label_a}</code></td><td>{team_a}</td>\
+             <td><code>{label_b}</code></td><td>{team_b}</td>\
+             <td>{co_changes}</td><td>{:.0}%</td></tr>",
+            coupling * 100.0
         );
     }
     let _ = writeln!(h, "</table>");
-    let _ = writeln!(h, "</section>");
 }
 
 fn salience_color(score: f64) -> &'static str {
@@ -784,6 +1685,7 @@ mod tests {
     use chrono::Utc;
 
     #[tokio::test]
+    #[allow(clippy::too_many_lines)]
     async fn renders_html_report() {
         let store = SqliteStore::in_memory().unwrap();
         let now = Utc::now();
@@ -800,6 +1702,18 @@ mod tests {
             .await
             .unwrap();
 
+        let unused_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/main.rs::unused".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
         store
             .upsert_node(&Node {
                 id: NodeId(0),
@@ -852,12 +1766,32 @@ mod tests {
             .await
             .unwrap();
 
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: unused_id,
+                kind: AnalysisKind::DeadCode,
+                data: serde_json::json!({ "confidence": 0.9 }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
         let config = HomerConfig::default();
         let renderer = ReportRenderer;
         let output = renderer.render(&store, &config).await.unwrap();
 
         assert!(output.contains("<!DOCTYPE html>"), "Should be valid HTML");
         assert!(output.contains("Homer Report"), "Should have title");
+        assert!(
+            output.contains("Dead Code"),
+            "Should have dead code section"
+        );
+        assert!(
+            output.contains("src/main.rs::unused"),
+            "Should list dead function"
+        );
         assert!(output.contains("Executive Summary"), "Should have summary");
         assert!(output.contains("Hotspot Map"), "Should have hotspot map");
         assert!(output.contains("main.rs"), "Should mention main.rs");
@@ -867,6 +1801,551 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn trends_show_pagerank_by_snapshot() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let node_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/lib.rs::parse".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let record = |kind: AnalysisKind, data: serde_json::Value| AnalysisResult {
+            id: AnalysisResultId(0),
+            node_id,
+            kind,
+            data,
+            input_hash: 0,
+            computed_at: now,
+        };
+
+        for (label, score) in [("v1.0", 0.01), ("v2.0", 0.03)] {
+            store
+                .store_analysis(&record(
+                    AnalysisKind::PageRank,
+                    serde_json::json!({ "pagerank": score }),
+                ))
+                .await
+                .unwrap();
+            store.create_snapshot(label).await.unwrap();
+        }
+        store
+            .store_analysis(&record(
+                AnalysisKind::CentralityTrend,
+                serde_json::json!({ "trend": "Rising" }),
+            ))
+            .await
+            .unwrap();
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        assert!(output.contains("Centrality Trends"));
+        assert!(
+            output.contains("v1.0: 0.0100 &rarr; v2.0: 0.0300"),
+            "Should chart PageRank across snapshots"
+        );
+    }
+
+    #[tokio::test]
+    async fn runtime_section_lists_hot_functions() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        for (name, hot, total) in [
+            ("src/lib.rs::decode", true, 0.42),
+            ("src/lib.rs::idle", false, 0.01),
+        ] {
+            let node_id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::Function,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id,
+                    kind: AnalysisKind::RuntimeHotness,
+                    data: serde_json::json!({
+                        "self_share": 0.3, "total_share": total, "hot": hot,
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await
+                .unwrap();
+        }
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(output.contains("Runtime Hotspots"));
+        assert!(
+            output.contains("<code>src/lib.rs::decode</code></td><td>30.0%</td><td>42.0%</td>")
+        );
+        assert!(!output.contains("src/lib.rs::idle"));
+    }
+
+    #[tokio::test]
+    async fn schema_section_lists_churned_tables_behind_salient_code() {
+        use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let node = |kind, name: &str, metadata: serde_json::Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: now,
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        let save = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "src/orders.rs::save",
+                serde_json::json!({ "file": "src/orders.rs" }),
+            ))
+            .await
+            .unwrap();
+        let file = store
+            .upsert_node(&node(
+                NodeKind::File,
+                "src/orders.rs",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: file,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({ "score": 0.8 }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+        for (name, changes) in [("orders", 4), ("audit", 1)] {
+            let table = store
+                .upsert_node(&node(
+                    NodeKind::Table,
+                    name,
+                    serde_json::json!({ "schema_changes": changes, "last_changed": "20240301" }),
+                ))
+                .await
+                .unwrap();
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Accesses,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: save,
+                            role: roles::ACCESSOR.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: table,
+                            role: roles::ACCESSED.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 0.9,
+                    last_updated: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(output.contains("Schema Hotspots"));
+        assert!(output.contains(
+            "<tr><td><code>orders</code></td><td>4</td><td>20240301</td>\
+             <td><code>src/orders.rs::save</code> (0.80)</td></tr>"
+        ));
+        assert!(
+            !output.contains("<code>audit</code>"),
+            "below churn threshold"
+        );
+    }
+
+    /// `connect` reads two env vars and a config key; `.env.example` documents
+    /// one of them plus an unused one, and CI sets a tool variable.
+    async fn seed_config(store: &SqliteStore) {
+        use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+
+        let now = Utc::now();
+        let node = |kind, name: &str| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: now,
+            metadata: HashMap::new(),
+        };
+        let connect = store
+            .upsert_node(&node(NodeKind::Function, "src/db.rs::connect"))
+            .await
+            .unwrap();
+        let template = store
+            .upsert_node(&node(NodeKind::File, ".env.example"))
+            .await
+            .unwrap();
+        let workflow = store
+            .upsert_node(&node(NodeKind::File, ".github/workflows/ci.yml"))
+            .await
+            .unwrap();
+        let mut ids = HashMap::new();
+        for name in [
+            "DATABASE_URL",
+            "SECRET_KEY",
+            "UNUSED_FLAG",
+            "CARGO_TERM_COLOR",
+        ] {
+            let id = store
+                .upsert_node(&node(NodeKind::EnvVar, name))
+                .await
+                .unwrap();
+            ids.insert(name, id);
+        }
+        let pool = store
+            .upsert_node(&node(NodeKind::ConfigKey, "db.pool"))
+            .await
+            .unwrap();
+        let edge = |kind, from: (NodeId, &str), to: (NodeId, &str), metadata| Hyperedge {
+            id: HyperedgeId(0),
+            kind,
+            members: vec![
+                HyperedgeMember {
+                    node_id: from.0,
+                    role: from.1.to_string(),
+                    position: 0,
+                },
+                HyperedgeMember {
+                    node_id: to.0,
+                    role: to.1.to_string(),
+                    position: 1,
+                },
+            ],
+            confidence: 1.0,
+            last_updated: now,
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        for variable in [ids["DATABASE_URL"], ids["SECRET_KEY"], pool] {
+            store
+                .upsert_hyperedge(&edge(
+                    HyperedgeKind::Reads,
+                    (connect, roles::READER),
+                    (variable, roles::READ_VAR),
+                    serde_json::json!({ "via": "env" }),
+                ))
+                .await
+                .unwrap();
+        }
+        for (definer, variable, source, via) in [
+            (template, "DATABASE_URL", ".env.example", "template"),
+            (template, "UNUSED_FLAG", ".env.example", "template"),
+            (
+                workflow,
+                "CARGO_TERM_COLOR",
+                ".github/workflows/ci.yml",
+                "ci",
+            ),
+        ] {
+            store
+                .upsert_hyperedge(&edge(
+                    HyperedgeKind::Defines,
+                    (definer, roles::DEFINER),
+                    (ids[variable], roles::DEFINED_VAR),
+                    serde_json::json!({ "extractor": "env", "source": source, "via": via }),
+                ))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn config_section_lists_undocumented_and_unused_variables() {
+        let store = SqliteStore::in_memory().unwrap();
+        seed_config(&store).await;
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(output.contains("<h2>Configuration</h2>"));
+        assert!(output.contains(
+            "<tr><td><code>SECRET_KEY</code></td><td>env</td><td>undocumented</td>\
+             <td>read by <code>src/db.rs::connect</code></td></tr>"
+        ));
+        assert!(output.contains(
+            "<tr><td><code>UNUSED_FLAG</code></td><td>env</td><td>unused</td>\
+             <td>defined in <code>.env.example</code></td></tr>"
+        ));
+        assert!(
+            !output.contains("<code>DATABASE_URL</code>"),
+            "documented and read"
+        );
+        assert!(!output.contains("CARGO_TERM_COLOR"), "set for CI tools");
+        assert!(
+            !output.contains("db.pool"),
+            "no config files to document it"
+        );
+    }
+
+    #[tokio::test]
+    async fn flags_section_lists_old_flags_behind_salient_code() {
+        use crate::types::{Hyperedge, HyperedgeId, HyperedgeMember};
+
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let node = |kind, name: &str, metadata: serde_json::Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: now,
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        let render = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "src/checkout.rs::render",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: render,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({ "score": 0.8 }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+        for (flag, introduced) in [
+            ("new-checkout", "2020-01-01T00:00:00+00:00".to_string()),
+            ("fast-cart", now.to_rfc3339()),
+        ] {
+            let id = store
+                .upsert_node(&node(
+                    NodeKind::FeatureFlag,
+                    flag,
+                    serde_json::json!({
+                        "introduced": introduced,
+                        "last_changed": "2021-03-04T00:00:00+00:00",
+                    }),
+                ))
+                .await
+                .unwrap();
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Checks,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: render,
+                            role: roles::CHECKER.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: id,
+                            role: roles::CHECKED_FLAG.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 0.9,
+                    last_updated: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+        assert!(output.contains("<h2>Feature Flags</h2>"));
+        assert!(output.contains("more than 90 days ago"));
+        assert!(output.contains(
+            "<td>2021-03-04</td><td><code>src/checkout.rs::render</code> (0.80)</td></tr>"
+        ));
+        assert!(!output.contains("fast-cart"), "too young to clean up");
+    }
+
+    #[tokio::test]
+    async fn clone_section_flags_diverged_groups() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let node_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/orders.rs::total".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id,
+                kind: AnalysisKind::CloneGroup,
+                data: serde_json::json!({
+                    "group": "src/cart.rs::total",
+                    "size": 2,
+                    "similarity": 0.87,
+                    "members": [{ "name": "src/cart.rs::total", "similarity": 0.87 }],
+                    "diverged": true,
+                    "divergent_changes": [{ "commit": "abc1234def", "changed": [], "unchanged": [] }],
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        assert!(output.contains("Code Clones"));
+        assert!(
+            output.contains(
+                "<td><code>src/cart.rs::total</code><br><code>src/orders.rs::total</code></td>\
+                 <td>87%</td><td><strong>yes</strong> (<code>abc1234</code>)</td>"
+            ),
+            "Should list the diverged group with its last divergent commit"
+        );
+    }
+
+    #[tokio::test]
+    async fn team_topology_lists_knowledge_loss() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let node_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::File,
+                name: "src/billing.rs".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id,
+                kind: AnalysisKind::KnowledgeLoss,
+                data: serde_json::json!({
+                    "inactive_share": 0.9,
+                    "salience": 0.8,
+                    "orphaned": true,
+                    "contributors": [
+                        { "email": "gone@example.com", "active": false },
+                        { "email": "here@example.com", "active": true },
+                    ],
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        assert!(output.contains("Knowledge Loss"));
+        assert!(
+            output.contains("<td><code>src/billing.rs</code></td><td>90%</td><td>here@example.com</td><td>yes</td>"),
+            "Should list the orphaned file with its active expert"
+        );
+    }
+
+    #[tokio::test]
+    async fn team_topology_shows_cross_team_coupling() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+        let root = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Module,
+                name: ".".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: root,
+                kind: AnalysisKind::TeamAlignment,
+                data: serde_json::json!({
+                    "alignment_score": 0.25,
+                    "communities": [
+                        { "label": "billing", "owner_team": "@acme/payments", "owner_share": 0.8 },
+                        { "label": "auth", "owner_team": "identity", "owner_share": 1.0 },
+                    ],
+                    "cross_team_coupling": [{
+                        "label_a": "billing", "team_a": "@acme/payments",
+                        "label_b": "auth", "team_b": "identity",
+                        "co_changes": 12, "coupling": 0.4,
+                    }],
+                }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
+        let output = ReportRenderer
+            .render(&store, &HomerConfig::default())
+            .await
+            .unwrap();
+
+        assert!(output.contains("Team Alignment"));
+        assert!(output.contains("25% of changes spanning several communities"));
+        assert!(
+            output.contains(
+                "<tr><td><code>billing</code></td><td>@acme/payments</td><td><code>auth</code></td><td>identity</td><td>12</td><td>40%</td></tr>"
+            ),
+            "Should list the cross-team hotspot"
+        );
+    }
+
     #[tokio::test]
     async fn empty_store_produces_minimal_report() {
         let store = SqliteStore::in_memory().unwrap();
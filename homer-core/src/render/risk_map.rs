@@ -1,10 +1,14 @@
 // Risk map renderer — produces `homer-risk.json` with risk and safe areas.
 //
 // Risk factors available in Phase 2:
-// - high_centrality_low_tests: PageRank high but no test file detected
+// - high_centrality_low_tests: PageRank high but line coverage below 50%
+//   (from ingested coverage reports), or no test file detected when no
+//   report covers the file
 // - knowledge_silo: Bus factor == 1
 // - volatile_critical: StabilityClassification == ActiveCritical
 // - undocumented_critical: High centrality + no doc_comment
+// - flaky_tests: A test exercising this salient file passed and failed at
+//   the same commit (from ingested CI test results)
 
 #![allow(clippy::cast_precision_loss)]
 
@@ -15,6 +19,7 @@ use serde::Serialize;
 use tracing::{info, instrument};
 
 use crate::config::HomerConfig;
+use crate::extract::coverage::CoverageSummary;
 use crate::store::HomerStore;
 use crate::types::{AnalysisKind, NodeFilter, NodeId, NodeKind};
 
@@ -83,6 +88,9 @@ pub struct RiskReason {
     pub bus_factor: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has_doc_comment: Option<bool>,
+    /// Measured line coverage (0.0–1.0), when a coverage report was ingested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -100,11 +108,17 @@ struct RiskData {
     bus: HashMap<NodeId, u64>,
     stability: HashMap<NodeId, String>,
     test_files: Vec<String>,
+    /// File path → measured coverage, for files a coverage report covered.
+    file_coverage: HashMap<String, CoverageSummary>,
     file_has_docs: HashMap<String, bool>,
     centrality_trends: HashMap<NodeId, String>,
     doc_freshness: HashMap<NodeId, f64>,
     correction_rates: HashMap<NodeId, f64>,
     prompt_ref_counts: HashMap<NodeId, u32>,
+    /// File path → complexity hotspots in that file, most complex first.
+    complexity_hotspots: HashMap<String, Vec<(String, u64)>>,
+    /// File path → flaky tests exercising that salient file, by name.
+    flaky_tests: HashMap<String, Vec<String>>,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -167,6 +181,10 @@ async fn load_risk_data(db: &dyn HomerStore) -> crate::error::Result<RiskData> {
         })
         .map(|f| f.name.clone())
         .collect();
+    let file_coverage: HashMap<_, _> = files
+        .iter()
+        .filter_map(|f| Some((f.name.clone(), CoverageSummary::of(f)?)))
+        .collect();
 
     let functions = db
         .find_nodes(&NodeFilter {
@@ -241,16 +259,79 @@ async fn load_risk_data(db: &dyn HomerStore) -> crate::error::Result<RiskData> {
         })
         .collect();
 
+    // Complex functions in frequently changed files
+    let mut complexity_hotspots: HashMap<String, Vec<(String, u64)>> = HashMap::new();
+    for r in db
+        .get_analyses_by_kind(AnalysisKind::ComplexityHotspot)
+        .await?
+    {
+        let Some(file) = r.data.get("file").and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        let cyclomatic = r
+            .data
+            .get("cyclomatic")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        let name = db.get_node(r.node_id).await?.map_or_else(
+            || format!("node:{}", r.node_id.0),
+            |n| {
+                n.metadata
+                    .get("qualified_name")
+                    .and_then(serde_json::Value::as_str)
+                    .map_or(n.name.clone(), str::to_string)
+            },
+        );
+        complexity_hotspots
+            .entry(file.to_string())
+            .or_default()
+            .push((name, cyclomatic));
+    }
+    for funcs in complexity_hotspots.values_mut() {
+        funcs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    }
+
+    // Flaky tests that exercise salient files
+    let mut flaky_tests: HashMap<String, Vec<String>> = HashMap::new();
+    for r in db.get_analyses_by_kind(AnalysisKind::TestFlakiness).await? {
+        let files: Vec<&str> = r
+            .data
+            .get("high_salience_files")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(serde_json::Value::as_str)
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        let Some(test) = db.get_node(r.node_id).await? else {
+            continue;
+        };
+        for file in files {
+            flaky_tests
+                .entry(file.to_string())
+                .or_default()
+                .push(test.name.clone());
+        }
+    }
+    for tests in flaky_tests.values_mut() {
+        tests.sort();
+    }
+
     Ok(RiskData {
         salience,
         bus,
         stability,
         test_files,
+        file_coverage,
         file_has_docs,
         centrality_trends,
         doc_freshness,
         correction_rates,
         prompt_ref_counts,
+        complexity_hotspots,
+        flaky_tests,
     })
 }
 
@@ -323,6 +404,9 @@ async fn build_risk_map(db: &dyn HomerStore) -> crate::error::Result<RiskMap> {
     })
 }
 
+/// Line coverage below which a central file counts as poorly tested.
+const LOW_COVERAGE: f64 = 0.5;
+
 #[allow(clippy::too_many_lines)]
 fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<RiskReason>, f64) {
     let mut reasons = Vec::new();
@@ -331,14 +415,32 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
     let pagerank = data.salience.get(&file_id).map_or(0.0, |(_, _, pr)| *pr);
     let high_centrality = pagerank > 0.5;
 
-    // Risk: high centrality but no associated test file
-    if high_centrality && !has_associated_test(file_name, &data.test_files) {
+    // Risk: high centrality but poorly tested. Measured coverage wins; the
+    // test-file heuristic only applies to files no report covered.
+    let coverage = data.file_coverage.get(file_name);
+    let poorly_tested = coverage.map_or_else(
+        || !has_associated_test(file_name, &data.test_files),
+        |c| c.line_rate() < LOW_COVERAGE,
+    );
+    if high_centrality && poorly_tested {
+        let description = coverage.map_or_else(
+            || format!("PageRank {pagerank:.2} but no test file detected"),
+            |c| {
+                format!(
+                    "PageRank {pagerank:.2} with {:.0}% line coverage ({}/{} lines)",
+                    c.line_rate() * 100.0,
+                    c.lines_covered,
+                    c.lines_total
+                )
+            },
+        );
         reasons.push(RiskReason {
             reason_type: "high_centrality_low_tests",
-            description: format!("PageRank {pagerank:.2} but no test file detected"),
+            description,
             centrality: Some(pagerank),
             bus_factor: None,
             has_doc_comment: None,
+            coverage: coverage.map(CoverageSummary::line_rate),
         });
         risk_val += 0.3;
     }
@@ -352,6 +454,7 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
                 centrality: None,
                 bus_factor: Some(bf),
                 has_doc_comment: None,
+                coverage: None,
             });
             risk_val += 0.2;
         }
@@ -369,6 +472,7 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
             centrality: Some(pagerank),
             bus_factor: None,
             has_doc_comment: None,
+            coverage: None,
         });
         risk_val += 0.25;
     }
@@ -381,6 +485,7 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
             centrality: Some(pagerank),
             bus_factor: None,
             has_doc_comment: Some(false),
+            coverage: None,
         });
         risk_val += 0.15;
     }
@@ -397,6 +502,7 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
             centrality: Some(pagerank),
             bus_factor: None,
             has_doc_comment: None,
+            coverage: None,
         });
         risk_val += 0.2;
     }
@@ -410,6 +516,7 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
                 centrality: None,
                 bus_factor: None,
                 has_doc_comment: None,
+                coverage: None,
             });
             risk_val += 0.15;
         }
@@ -424,6 +531,7 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
                 centrality: None,
                 bus_factor: None,
                 has_doc_comment: None,
+                coverage: None,
             });
             risk_val += 0.15;
         }
@@ -440,11 +548,47 @@ fn assess_file_risk(file_id: NodeId, file_name: &str, data: &RiskData) -> (Vec<R
                 centrality: Some(pagerank),
                 bus_factor: None,
                 has_doc_comment: None,
+                coverage: None,
             });
             risk_val += 0.1;
         }
     }
 
+    // Risk: complex functions in a frequently changed file
+    if let Some(funcs) = data.complexity_hotspots.get(file_name) {
+        let (worst, cyclomatic) = &funcs[0];
+        reasons.push(RiskReason {
+            reason_type: "complexity_hotspot",
+            description: format!(
+                "{} complex function(s) in a frequently changed file (worst: {worst}, cyclomatic {cyclomatic})",
+                funcs.len()
+            ),
+            centrality: None,
+            bus_factor: None,
+            has_doc_comment: None,
+            coverage: None,
+        });
+        risk_val += 0.2;
+    }
+
+    // Risk: flaky tests guarding salient code
+    if let Some(tests) = data.flaky_tests.get(file_name) {
+        let shown: Vec<&str> = tests.iter().take(3).map(String::as_str).collect();
+        reasons.push(RiskReason {
+            reason_type: "flaky_tests",
+            description: format!(
+                "{} flaky test(s) exercise this file: {}",
+                tests.len(),
+                shown.join(", ")
+            ),
+            centrality: None,
+            bus_factor: None,
+            has_doc_comment: None,
+            coverage: None,
+        });
+        risk_val += 0.15;
+    }
+
     (reasons, risk_val.min(1.0))
 }
 
@@ -507,6 +651,18 @@ fn generate_recommendations(reasons: &[RiskReason]) -> Vec<String> {
                         .to_string(),
                 );
             }
+            "complexity_hotspot" => {
+                recs.push(
+                    "Complex code that changes often — simplify or add tests before extending"
+                        .to_string(),
+                );
+            }
+            "flaky_tests" => {
+                recs.push(
+                    "Tests covering this file are flaky — rerun failures before trusting them, and fix the flake before relying on the suite here"
+                        .to_string(),
+                );
+            }
             "underprompted" => {
                 recs.push(
                     "This critical code has low agent interaction — ensure thorough manual review"
@@ -615,6 +771,195 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn complexity_hotspot_marks_file_risky() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::File,
+                name: "src/parser.rs".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        let func_id = store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/parser.rs::parse_expr".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::from([(
+                    "qualified_name".to_string(),
+                    serde_json::json!("parse_expr"),
+                )]),
+            })
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: func_id,
+                kind: AnalysisKind::ComplexityHotspot,
+                data: serde_json::json!({ "file": "src/parser.rs", "cyclomatic": 24, "score": 0.9 }),
+                input_hash: 0,
+                computed_at: now,
+            })
+            .await
+            .unwrap();
+
+        let risk_map = build_risk_map(&store).await.unwrap();
+        let area = risk_map
+            .risk_areas
+            .iter()
+            .find(|a| a.path == "src/parser.rs")
+            .expect("parser.rs should be a risk area");
+        let reason = area
+            .reasons
+            .iter()
+            .find(|r| r.reason_type == "complexity_hotspot")
+            .expect("complexity hotspot reason");
+        assert!(reason.description.contains("parse_expr"));
+        assert!(reason.description.contains("cyclomatic 24"));
+    }
+
+    #[tokio::test]
+    async fn measured_coverage_replaces_test_file_heuristic() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        for (name, metadata) in [
+            (
+                "src/engine.rs",
+                serde_json::json!({ "coverage": {
+                    "lines_covered": 3, "lines_total": 20,
+                    "branches_covered": 0, "branches_total": 0,
+                }}),
+            ),
+            (
+                "src/router.rs",
+                serde_json::json!({ "coverage": {
+                    "lines_covered": 18, "lines_total": 20,
+                    "branches_covered": 0, "branches_total": 0,
+                }}),
+            ),
+        ] {
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: serde_json::from_value(metadata).unwrap(),
+                })
+                .await
+                .unwrap();
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: id,
+                    kind: AnalysisKind::CompositeSalience,
+                    data: serde_json::json!({
+                        "score": 0.8,
+                        "classification": "FoundationalStable",
+                        "components": { "pagerank": 0.9 }
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await
+                .unwrap();
+        }
+
+        let risk_map = build_risk_map(&store).await.unwrap();
+        let low_tests = |path: &str| {
+            risk_map
+                .risk_areas
+                .iter()
+                .find(|a| a.path == path)
+                .and_then(|a| {
+                    a.reasons
+                        .iter()
+                        .find(|r| r.reason_type == "high_centrality_low_tests")
+                })
+        };
+        let reason = low_tests("src/engine.rs").expect("15% coverage is low");
+        assert!(reason.description.contains("15% line coverage"));
+        assert_eq!(reason.coverage, Some(0.15));
+        assert!(
+            low_tests("src/router.rs").is_none(),
+            "well-covered file has no test file but is not flagged"
+        );
+    }
+
+    #[tokio::test]
+    async fn flaky_tests_on_salient_files() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::File,
+                name: "src/ledger.rs".to_string(),
+                content_hash: None,
+                last_extracted: now,
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        for (name, files) in [
+            ("ledger::posts_twice", vec!["src/ledger.rs"]),
+            ("ledger::formats_amounts", vec![]),
+        ] {
+            let id = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::Test,
+                    name: name.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: HashMap::new(),
+                })
+                .await
+                .unwrap();
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: id,
+                    kind: AnalysisKind::TestFlakiness,
+                    data: serde_json::json!({ "flaky_commits": 2, "high_salience_files": files }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await
+                .unwrap();
+        }
+
+        let risk_map = build_risk_map(&store).await.unwrap();
+        let area = risk_map
+            .risk_areas
+            .iter()
+            .find(|a| a.path == "src/ledger.rs")
+            .expect("ledger is a risk area");
+        let reason = area
+            .reasons
+            .iter()
+            .find(|r| r.reason_type == "flaky_tests")
+            .expect("flaky_tests reason");
+        assert_eq!(
+            reason.description,
+            "1 flaky test(s) exercise this file: ledger::posts_twice"
+        );
+        assert!(area.recommendations.iter().any(|r| r.contains("flaky")));
+    }
+
     #[tokio::test]
     async fn safe_areas_for_low_risk_files() {
         let store = SqliteStore::in_memory().unwrap();
@@ -33,6 +33,11 @@ pub trait Renderer: Send + Sync {
         config: &HomerConfig,
     ) -> crate::error::Result<String>;
 
+    /// Combine freshly rendered content with the file already on disk.
+    fn merge(&self, existing: &str, new_content: &str) -> String {
+        merge_with_preserve(existing, new_content)
+    }
+
     /// Write the artifact to disk, handling merge modes.
     async fn write(
         &self,
@@ -47,7 +52,7 @@ pub trait Renderer: Send + Sync {
             let existing = std::fs::read_to_string(&output).map_err(|e| {
                 crate::error::HomerError::Extract(crate::error::ExtractError::Io(e))
             })?;
-            let merged = merge_with_preserve(&existing, &content);
+            let merged = self.merge(&existing, &content);
             std::fs::write(&output, merged).map_err(|e| {
                 crate::error::HomerError::Extract(crate::error::ExtractError::Io(e))
             })?;
@@ -66,7 +71,8 @@ pub trait Renderer: Send + Sync {
     }
 }
 
-/// Merge new content with existing, preserving `<!-- homer:preserve -->` blocks.
+/// Merge new content with existing, preserving `<!-- homer:preserve -->`
+/// blocks (or `# homer:preserve` in files with `#` comments).
 pub fn merge_with_preserve(existing: &str, new_content: &str) -> String {
     let preserved = extract_preserved_blocks(existing);
 
@@ -100,6 +106,9 @@ pub fn merge_with_preserve(existing: &str, new_content: &str) -> String {
     result
 }
 
+const PRESERVE_OPEN: &[&str] = &["<!-- homer:preserve -->", "# homer:preserve"];
+const PRESERVE_CLOSE: &[&str] = &["<!-- /homer:preserve -->", "# /homer:preserve"];
+
 struct PreservedBlock {
     content: String,
     after_section: Option<String>,
@@ -111,9 +120,9 @@ fn extract_preserved_blocks(content: &str) -> Vec<PreservedBlock> {
     let mut last_section = None;
 
     for line in content.lines() {
-        if line.trim() == "<!-- homer:preserve -->" {
+        if PRESERVE_OPEN.contains(&line.trim()) {
             current_block = Some(format!("{line}\n"));
-        } else if line.trim() == "<!-- /homer:preserve -->" {
+        } else if PRESERVE_CLOSE.contains(&line.trim()) {
             if let Some(mut block) = current_block.take() {
                 block.push_str(line);
                 block.push('\n');
@@ -156,6 +165,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn preserve_hash_comment_blocks() {
+        let existing =
+            "## Rules\n* @old\n\n## Manual\n# homer:preserve\n/ops/ @ops\n# /homer:preserve\n";
+        let new_content = "## Rules\n* @new\n\n## Manual\n";
+
+        let merged = merge_with_preserve(existing, new_content);
+        assert!(merged.ends_with("## Manual\n# homer:preserve\n/ops/ @ops\n# /homer:preserve\n"));
+        assert!(!merged.contains("@old"));
+    }
+
     #[test]
     fn no_preserve_returns_new() {
         let existing = "# Old\nold content";
@@ -79,7 +79,9 @@ CREATE TABLE IF NOT EXISTS graph_snapshots (
     label TEXT NOT NULL,
     snapshot_at TEXT NOT NULL,
     edge_count INTEGER NOT NULL,
-    node_count INTEGER NOT NULL
+    node_count INTEGER NOT NULL,
+    -- 1 for snapshots recorded by backfill rather than taken from the live graph
+    historical INTEGER NOT NULL DEFAULT 0
 );
 
 -- Snapshot membership: which nodes and edges exist at each snapshot
@@ -94,6 +96,23 @@ CREATE TABLE IF NOT EXISTS snapshot_edges (
     edge_id INTEGER NOT NULL,
     PRIMARY KEY (snapshot_id, edge_id)
 );
+
+-- Entities in a historical snapshot that have no node in the live graph
+CREATE TABLE IF NOT EXISTS snapshot_entities (
+    snapshot_id INTEGER NOT NULL REFERENCES graph_snapshots(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (snapshot_id, kind, name)
+);
+
+-- Per-node analysis values as they were at each snapshot
+CREATE TABLE IF NOT EXISTS snapshot_analyses (
+    snapshot_id INTEGER NOT NULL REFERENCES graph_snapshots(id) ON DELETE CASCADE,
+    node_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (snapshot_id, node_id, kind)
+);
 ";
 
 /// Projected views for common query patterns.
@@ -198,6 +217,7 @@ mod tests {
         assert!(tables.contains(&"analysis_results".to_string()));
         assert!(tables.contains(&"checkpoints".to_string()));
         assert!(tables.contains(&"graph_snapshots".to_string()));
+        assert!(tables.contains(&"snapshot_analyses".to_string()));
         assert!(tables.contains(&"homer_meta".to_string()));
     }
 
@@ -8,11 +8,13 @@ use std::sync::Mutex;
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, OptionalExtension, params};
 
+use crate::contracts::roles;
 use crate::error::{HomerError, StoreError};
 use crate::types::{
-    AnalysisKind, AnalysisResult, AnalysisResultId, GraphDiff, Hyperedge, HyperedgeId,
-    HyperedgeKind, HyperedgeMember, InMemoryGraph, Node, NodeFilter, NodeId, NodeKind, SearchHit,
-    SearchScope, SnapshotId, SnapshotInfo, StoreStats, SubgraphFilter, extract_directed_pair,
+    AnalysisKind, AnalysisResult, AnalysisResultId, EntityRef, GraphDiff, HistoricalSnapshot,
+    Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember, InMemoryGraph, MetricPoint, Node,
+    NodeFilter, NodeId, NodeKind, SearchHit, SearchScope, SnapshotId, SnapshotInfo, StoreStats,
+    SubgraphFilter, extract_directed_pair,
 };
 
 use super::HomerStore;
@@ -83,10 +85,26 @@ impl SqliteStore {
         .map_err(StoreError::Sqlite)?;
 
         Self::migrate_hyperedge_identity(&mut conn).map_err(StoreError::Sqlite)?;
+        Self::ensure_snapshot_historical_column(&conn).map_err(StoreError::Sqlite)?;
 
         Ok(())
     }
 
+    /// Older stores predate the `historical` snapshot flag.
+    fn ensure_snapshot_historical_column(conn: &Connection) -> rusqlite::Result<()> {
+        let mut table_info = conn.prepare("PRAGMA table_info(graph_snapshots)")?;
+        let columns = table_info
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        if !columns.iter().any(|c| c == "historical") {
+            conn.execute(
+                "ALTER TABLE graph_snapshots ADD COLUMN historical INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
     /// Pre-schema compatibility shim for older stores.
     fn ensure_hyperedge_identity_column(conn: &Connection) -> rusqlite::Result<()> {
         let mut has_hyperedges_table = false;
@@ -264,6 +282,92 @@ impl SqliteStore {
         Ok(result)
     }
 
+    /// Each node followed by the names it replaced (reverse `Aliases`
+    /// edges, max 10 hops), from one load of all alias pairs.
+    fn alias_lineages(
+        conn: &Connection,
+        node_ids: &[NodeId],
+    ) -> rusqlite::Result<Vec<Vec<NodeId>>> {
+        let mut replaced: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        let mut stmt = conn.prepare_cached(
+            "SELECT m_new.node_id, m_old.node_id FROM hyperedges e
+             JOIN hyperedge_members m_old ON e.id = m_old.hyperedge_id AND m_old.role = ?1
+             JOIN hyperedge_members m_new ON e.id = m_new.hyperedge_id AND m_new.role = ?2
+             WHERE e.kind = 'Aliases'",
+        )?;
+        let aliases = stmt
+            .query_map(params![roles::ALIAS_OLD, roles::ALIAS_NEW], |row| {
+                Ok((NodeId(row.get(0)?), NodeId(row.get(1)?)))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for (new, old) in aliases {
+            replaced.entry(new).or_default().push(old);
+        }
+
+        Ok(node_ids
+            .iter()
+            .map(|&node_id| {
+                let mut lineage = vec![node_id];
+                let mut frontier = vec![node_id];
+                for _ in 0..10 {
+                    let mut next = Vec::new();
+                    for old in frontier.iter().filter_map(|id| replaced.get(id)).flatten() {
+                        if !lineage.contains(old) {
+                            lineage.push(*old);
+                            next.push(*old);
+                        }
+                    }
+                    if next.is_empty() {
+                        break;
+                    }
+                    frontier = next;
+                }
+                lineage
+            })
+            .collect())
+    }
+
+    /// Snapshot values of one analysis kind for a set of nodes, chunked
+    /// like [`Self::attach_members_batch`].
+    fn snapshot_values_batch(
+        conn: &Connection,
+        ids: &[i64],
+        kind: AnalysisKind,
+    ) -> rusqlite::Result<HashMap<NodeId, Vec<MetricPoint>>> {
+        let mut values: HashMap<NodeId, Vec<MetricPoint>> = HashMap::new();
+        let kind_str = kind.as_str();
+        for chunk in ids.chunks(500) {
+            let placeholders: String = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+            let sql = format!(
+                "SELECT sa.node_id, s.id, s.label, s.snapshot_at, sa.data
+                 FROM snapshot_analyses sa
+                 JOIN graph_snapshots s ON s.id = sa.snapshot_id
+                 WHERE sa.kind = ? AND sa.node_id IN ({placeholders})"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let mut params: Vec<&dyn rusqlite::types::ToSql> = vec![&kind_str];
+            params.extend(chunk.iter().map(|id| id as &dyn rusqlite::types::ToSql));
+
+            let mut rows = stmt.query(params.as_slice())?;
+            while let Some(row) = rows.next()? {
+                let at_str: String = row.get(3)?;
+                let data: String = row.get(4)?;
+                values
+                    .entry(NodeId(row.get(0)?))
+                    .or_default()
+                    .push(MetricPoint {
+                        snapshot_id: SnapshotId(row.get(1)?),
+                        label: row.get(2)?,
+                        snapshot_at: DateTime::parse_from_rfc3339(&at_str)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_default(),
+                        data: serde_json::from_str(&data).unwrap_or_default(),
+                    });
+            }
+        }
+        Ok(values)
+    }
+
     /// Deterministic semantic identity for a hyperedge.
     ///
     /// Identity is edge kind plus a sorted role/node set. Position is intentionally
@@ -596,17 +700,17 @@ impl HomerStore for SqliteStore {
 
     async fn resolve_canonical(&self, node_id: NodeId) -> crate::error::Result<NodeId> {
         let conn = self.conn.lock().expect("homer store mutex poisoned");
-        // Follow Aliases edges: the "old" role points to "new" role.
+        // Follow Aliases edges: the old-name role points to the new-name role.
         // Walk the chain until no more aliases found (max 10 hops to prevent cycles).
         let mut current = node_id;
         for _ in 0..10 {
             let next: Option<i64> = conn
                 .query_row(
                     "SELECT m_new.node_id FROM hyperedges e
-                     JOIN hyperedge_members m_old ON e.id = m_old.hyperedge_id AND m_old.role = 'old'
-                     JOIN hyperedge_members m_new ON e.id = m_new.hyperedge_id AND m_new.role = 'new'
+                     JOIN hyperedge_members m_old ON e.id = m_old.hyperedge_id AND m_old.role = ?2
+                     JOIN hyperedge_members m_new ON e.id = m_new.hyperedge_id AND m_new.role = ?3
                      WHERE e.kind = 'Aliases' AND m_old.node_id = ?1",
-                    params![current.0],
+                    params![current.0, roles::ALIAS_OLD, roles::ALIAS_NEW],
                     |row| row.get(0),
                 )
                 .optional()
@@ -627,10 +731,10 @@ impl HomerStore for SqliteStore {
             let next: Option<i64> = conn
                 .query_row(
                     "SELECT m_new.node_id FROM hyperedges e
-                     JOIN hyperedge_members m_old ON e.id = m_old.hyperedge_id AND m_old.role = 'old'
-                     JOIN hyperedge_members m_new ON e.id = m_new.hyperedge_id AND m_new.role = 'new'
+                     JOIN hyperedge_members m_old ON e.id = m_old.hyperedge_id AND m_old.role = ?2
+                     JOIN hyperedge_members m_new ON e.id = m_new.hyperedge_id AND m_new.role = ?3
                      WHERE e.kind = 'Aliases' AND m_old.node_id = ?1",
-                    params![current.0],
+                    params![current.0, roles::ALIAS_OLD, roles::ALIAS_NEW],
                     |row| row.get(0),
                 )
                 .optional()
@@ -749,6 +853,35 @@ impl HomerStore for SqliteStore {
         Ok(Self::attach_members_batch(&conn, edges).map_err(StoreError::Sqlite)?)
     }
 
+    async fn delete_edges_by_kind(&self, kind: HyperedgeKind) -> crate::error::Result<u64> {
+        let conn = self.conn.lock().expect("homer store mutex poisoned");
+        let deleted = conn
+            .execute(
+                "DELETE FROM hyperedges WHERE kind = ?1",
+                params![kind.as_str()],
+            )
+            .map_err(StoreError::Sqlite)?;
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(deleted as u64)
+    }
+
+    async fn delete_edges_by_extractor(
+        &self,
+        kind: HyperedgeKind,
+        extractor: &str,
+    ) -> crate::error::Result<u64> {
+        let conn = self.conn.lock().expect("homer store mutex poisoned");
+        let deleted = conn
+            .execute(
+                "DELETE FROM hyperedges
+                 WHERE kind = ?1 AND json_extract(metadata, '$.extractor') = ?2",
+                params![kind.as_str(), extractor],
+            )
+            .map_err(StoreError::Sqlite)?;
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(deleted as u64)
+    }
+
     async fn get_co_members(
         &self,
         node_id: NodeId,
@@ -1090,9 +1223,164 @@ impl HomerStore for SqliteStore {
         )
         .map_err(StoreError::Sqlite)?;
 
+        // Record metric values so their history can be queried later
+        let mut capture = conn
+            .prepare_cached(
+                "INSERT INTO snapshot_analyses (snapshot_id, node_id, kind, data)
+                 SELECT ?1, node_id, kind, data FROM analysis_results WHERE kind = ?2",
+            )
+            .map_err(StoreError::Sqlite)?;
+        for kind in AnalysisKind::SNAPSHOTTED {
+            capture
+                .execute(params![snap_id, kind.as_str()])
+                .map_err(StoreError::Sqlite)?;
+        }
+
         Ok(SnapshotId(snap_id))
     }
 
+    async fn create_historical_snapshot(
+        &self,
+        snapshot: &HistoricalSnapshot,
+    ) -> crate::error::Result<SnapshotId> {
+        let mut conn = self.conn.lock().expect("homer store mutex poisoned");
+        let tx = conn.transaction().map_err(StoreError::Sqlite)?;
+
+        tx.execute(
+            "DELETE FROM graph_snapshots WHERE label = ?1 AND historical = 1",
+            params![snapshot.label],
+        )
+        .map_err(StoreError::Sqlite)?;
+        tx.execute(
+            "INSERT INTO graph_snapshots (label, snapshot_at, edge_count, node_count, historical)
+             VALUES (?1, ?2, ?3, ?4, 1)",
+            params![
+                snapshot.label,
+                snapshot.snapshot_at.to_rfc3339(),
+                i64::try_from(snapshot.edge_count).unwrap_or(i64::MAX),
+                i64::try_from(snapshot.node_count).unwrap_or(i64::MAX),
+            ],
+        )
+        .map_err(StoreError::Sqlite)?;
+        let snap_id = tx.last_insert_rowid();
+
+        {
+            let mut insert_node = tx
+                .prepare_cached(
+                    "INSERT OR IGNORE INTO snapshot_nodes (snapshot_id, node_id) VALUES (?1, ?2)",
+                )
+                .map_err(StoreError::Sqlite)?;
+            for node_id in &snapshot.node_ids {
+                insert_node
+                    .execute(params![snap_id, node_id.0])
+                    .map_err(StoreError::Sqlite)?;
+            }
+
+            let mut insert_entity = tx
+                .prepare_cached(
+                    "INSERT OR IGNORE INTO snapshot_entities (snapshot_id, kind, name)
+                     VALUES (?1, ?2, ?3)",
+                )
+                .map_err(StoreError::Sqlite)?;
+            for entity in &snapshot.removed {
+                insert_entity
+                    .execute(params![snap_id, entity.kind.as_str(), entity.name])
+                    .map_err(StoreError::Sqlite)?;
+            }
+
+            let mut insert_analysis = tx
+                .prepare_cached(
+                    "INSERT OR REPLACE INTO snapshot_analyses (snapshot_id, node_id, kind, data)
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(StoreError::Sqlite)?;
+            for analysis in &snapshot.analyses {
+                let data =
+                    serde_json::to_string(&analysis.data).map_err(StoreError::Serialization)?;
+                insert_analysis
+                    .execute(params![
+                        snap_id,
+                        analysis.node_id.0,
+                        analysis.kind.as_str(),
+                        data
+                    ])
+                    .map_err(StoreError::Sqlite)?;
+            }
+        }
+
+        tx.commit().map_err(StoreError::Sqlite)?;
+        Ok(SnapshotId(snap_id))
+    }
+
+    async fn get_snapshot_analyses(
+        &self,
+        snapshot: SnapshotId,
+        kind: AnalysisKind,
+    ) -> crate::error::Result<Vec<(NodeId, serde_json::Value)>> {
+        let conn = self.conn.lock().expect("homer store mutex poisoned");
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT node_id, data FROM snapshot_analyses
+                 WHERE snapshot_id = ?1 AND kind = ?2",
+            )
+            .map_err(StoreError::Sqlite)?;
+
+        let rows = stmt
+            .query_map(params![snapshot.0, kind.as_str()], |row| {
+                let node_id: i64 = row.get(0)?;
+                let data: String = row.get(1)?;
+                Ok((
+                    NodeId(node_id),
+                    serde_json::from_str(&data).unwrap_or_default(),
+                ))
+            })
+            .map_err(StoreError::Sqlite)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(StoreError::Sqlite)?;
+        Ok(rows)
+    }
+
+    async fn get_metric_series(
+        &self,
+        node_ids: &[NodeId],
+        kind: AnalysisKind,
+    ) -> crate::error::Result<HashMap<NodeId, Vec<MetricPoint>>> {
+        let conn = self.conn.lock().expect("homer store mutex poisoned");
+        if node_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let lineages = Self::alias_lineages(&conn, node_ids).map_err(StoreError::Sqlite)?;
+        let ids: Vec<i64> = lineages
+            .iter()
+            .flatten()
+            .map(|id| id.0)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let values = Self::snapshot_values_batch(&conn, &ids, kind).map_err(StoreError::Sqlite)?;
+
+        let mut series = HashMap::new();
+        for (&node_id, lineage) in node_ids.iter().zip(&lineages) {
+            let mut points: Vec<MetricPoint> = Vec::new();
+            // A snapshot that recorded both names keeps the newer name's value.
+            for point in lineage.iter().filter_map(|id| values.get(id)).flatten() {
+                if !points.iter().any(|p| p.snapshot_id == point.snapshot_id) {
+                    points.push(point.clone());
+                }
+            }
+            points.sort_by(|a, b| {
+                a.snapshot_at
+                    .cmp(&b.snapshot_at)
+                    .then(a.snapshot_id.0.cmp(&b.snapshot_id.0))
+            });
+            if !points.is_empty() {
+                series.insert(node_id, points);
+            }
+        }
+        Ok(series)
+    }
+
     async fn list_snapshots(&self) -> crate::error::Result<Vec<SnapshotInfo>> {
         let conn = self.conn.lock().expect("homer store mutex poisoned");
         let mut stmt = conn
@@ -1203,11 +1491,44 @@ impl HomerStore for SqliteStore {
             .collect::<rusqlite::Result<Vec<_>>>()
             .map_err(StoreError::Sqlite)?;
 
+        // Entities without a live node, matched by kind and name against
+        // the other snapshot's entities and live members.
+        let mut entities_only_in = conn
+            .prepare_cached(
+                "SELECT e.kind, e.name FROM snapshot_entities e
+                 WHERE e.snapshot_id = ?1
+                   AND NOT EXISTS (SELECT 1 FROM snapshot_entities o
+                                   WHERE o.snapshot_id = ?2 AND o.kind = e.kind AND o.name = e.name)
+                   AND NOT EXISTS (SELECT 1 FROM snapshot_nodes sn JOIN nodes n ON n.id = sn.node_id
+                                   WHERE sn.snapshot_id = ?2 AND n.kind = e.kind AND n.name = e.name)
+                 ORDER BY e.kind, e.name",
+            )
+            .map_err(StoreError::Sqlite)?;
+        let mut entity_diff = |only_in: SnapshotId, not_in: SnapshotId| {
+            entities_only_in
+                .query_map(params![only_in.0, not_in.0], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })
+                .map_err(StoreError::Sqlite)?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .map_err(StoreError::Sqlite)?
+                .into_iter()
+                .map(|(kind, name)| {
+                    let kind = kind.parse().map_err(StoreError::InvalidValue)?;
+                    Ok(EntityRef { kind, name })
+                })
+                .collect::<crate::error::Result<Vec<_>>>()
+        };
+        let added_entities = entity_diff(to, from)?;
+        let removed_entities = entity_diff(from, to)?;
+
         Ok(GraphDiff {
             added_nodes,
             removed_nodes,
             added_edges,
             removed_edges,
+            added_entities,
+            removed_entities,
         })
     }
 
@@ -1442,6 +1763,87 @@ mod tests {
         assert_eq!(co, vec![target_id]);
     }
 
+    #[tokio::test]
+    async fn delete_edges_by_kind_leaves_other_kinds() {
+        let store = SqliteStore::in_memory().unwrap();
+        let a = store
+            .upsert_node(&make_test_node(NodeKind::File, "a.rs"))
+            .await
+            .unwrap();
+        let b = store
+            .upsert_node(&make_test_node(NodeKind::File, "b.rs"))
+            .await
+            .unwrap();
+        insert_edge(&store, HyperedgeKind::Owns, a, b, "owner", "file").await;
+        insert_edge(&store, HyperedgeKind::Imports, a, b, "importer", "imported").await;
+
+        let deleted = store
+            .delete_edges_by_kind(HyperedgeKind::Owns)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(
+            store
+                .get_edges_by_kind(HyperedgeKind::Owns)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(store.get_edges_involving(a).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn delete_edges_by_extractor_leaves_other_extractors() {
+        let store = SqliteStore::in_memory().unwrap();
+        let var = store
+            .upsert_node(&make_test_node(NodeKind::EnvVar, "PORT"))
+            .await
+            .unwrap();
+        for (name, extractor) in [("Dockerfile", "infra"), (".env.example", "env")] {
+            let file = store
+                .upsert_node(&make_test_node(NodeKind::File, name))
+                .await
+                .unwrap();
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Defines,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: file,
+                            role: "definer".to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: var,
+                            role: "variable".to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 1.0,
+                    last_updated: Utc::now(),
+                    metadata: HashMap::from([(
+                        "extractor".to_string(),
+                        serde_json::json!(extractor),
+                    )]),
+                })
+                .await
+                .unwrap();
+        }
+
+        let deleted = store
+            .delete_edges_by_extractor(HyperedgeKind::Defines, "env")
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        let left = store
+            .get_edges_by_kind(HyperedgeKind::Defines)
+            .await
+            .unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].metadata["extractor"], "infra");
+    }
+
     #[tokio::test]
     async fn hyperedge_upsert_is_idempotent_for_equivalent_edges() {
         let store = SqliteStore::in_memory().unwrap();
@@ -1671,6 +2073,220 @@ mod tests {
         assert!(snap_id.0 > 0);
     }
 
+    #[tokio::test]
+    async fn historical_snapshot_records_values_and_replaces_label() {
+        let store = SqliteStore::in_memory().unwrap();
+        let a = store
+            .upsert_node(&make_test_node(NodeKind::File, "a.rs"))
+            .await
+            .unwrap();
+        let at = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let snapshot = |score: f64| HistoricalSnapshot {
+            label: "v0.1".to_string(),
+            snapshot_at: at,
+            node_count: 7,
+            edge_count: 3,
+            node_ids: vec![a],
+            removed: vec![EntityRef {
+                kind: NodeKind::File,
+                name: "gone.rs".to_string(),
+            }],
+            analyses: vec![crate::types::SnapshotAnalysis {
+                node_id: a,
+                kind: AnalysisKind::PageRank,
+                data: serde_json::json!({ "score": score }),
+            }],
+        };
+
+        store
+            .create_historical_snapshot(&snapshot(0.2))
+            .await
+            .unwrap();
+        // Newer live snapshots sort after the historical one, and a live
+        // snapshot sharing its label is not replaced.
+        let live = store.create_snapshot("v0.1").await.unwrap();
+        let head = store.create_snapshot("head").await.unwrap();
+        let snap_id = store
+            .create_historical_snapshot(&snapshot(0.4))
+            .await
+            .unwrap();
+
+        let snapshots = store.list_snapshots().await.unwrap();
+        let labels: Vec<&str> = snapshots.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["v0.1", "v0.1", "head"]);
+        assert!(snapshots.iter().any(|s| s.id == live));
+        assert_eq!(snapshots[0].snapshot_at, at);
+        assert_eq!(snapshots[0].node_count, 7);
+
+        let values = store
+            .get_snapshot_analyses(snap_id, AnalysisKind::PageRank)
+            .await
+            .unwrap();
+        assert_eq!(values, vec![(a, serde_json::json!({ "score": 0.4 }))]);
+        let diff = store.get_snapshot_diff(snap_id, head).await.unwrap();
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(
+            diff.removed_entities,
+            vec![EntityRef {
+                kind: NodeKind::File,
+                name: "gone.rs".to_string(),
+            }]
+        );
+        assert!(diff.added_entities.is_empty());
+        assert!(
+            store
+                .get_snapshot_analyses(snap_id, AnalysisKind::HITSScore)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn snapshots_capture_metric_series() {
+        let store = SqliteStore::in_memory().unwrap();
+        let a = store
+            .upsert_node(&make_test_node(NodeKind::File, "a.rs"))
+            .await
+            .unwrap();
+        let record = |kind: AnalysisKind, score: f64| AnalysisResult {
+            id: AnalysisResultId(0),
+            node_id: a,
+            kind,
+            data: serde_json::json!({ "score": score }),
+            input_hash: 0,
+            computed_at: Utc::now(),
+        };
+
+        for (label, score) in [("s1", 0.1), ("s2", 0.2)] {
+            store
+                .store_analysis(&record(AnalysisKind::PageRank, score))
+                .await
+                .unwrap();
+            store
+                .store_analysis(&record(AnalysisKind::ChangeFrequency, score))
+                .await
+                .unwrap();
+            store.create_snapshot(label).await.unwrap();
+        }
+
+        let series = store
+            .get_metric_series(&[a], AnalysisKind::PageRank)
+            .await
+            .unwrap()
+            .remove(&a)
+            .unwrap_or_default();
+        let points: Vec<(&str, f64)> = series
+            .iter()
+            .map(|p| (p.label.as_str(), p.data["score"].as_f64().unwrap()))
+            .collect();
+        assert_eq!(points, [("s1", 0.1), ("s2", 0.2)]);
+
+        // Kinds outside the snapshotted set are not captured.
+        assert!(
+            store
+                .get_metric_series(&[a], AnalysisKind::ChangeFrequency)
+                .await
+                .unwrap()
+                .remove(&a)
+                .unwrap_or_default()
+                .is_empty()
+        );
+
+        // Deleting a snapshot drops its values.
+        store.delete_snapshot("s1").await.unwrap();
+        let series = store
+            .get_metric_series(&[a], AnalysisKind::PageRank)
+            .await
+            .unwrap()
+            .remove(&a)
+            .unwrap_or_default();
+        assert_eq!(series.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn metric_series_follows_aliases() {
+        let store = SqliteStore::in_memory().unwrap();
+        let old = store
+            .upsert_node(&make_test_node(NodeKind::Function, "a.rs::parse"))
+            .await
+            .unwrap();
+        let new = store
+            .upsert_node(&make_test_node(NodeKind::Function, "b.rs::parse"))
+            .await
+            .unwrap();
+        let record = |node_id: NodeId, score: f64| AnalysisResult {
+            id: AnalysisResultId(0),
+            node_id,
+            kind: AnalysisKind::PageRank,
+            data: serde_json::json!({ "score": score }),
+            input_hash: 0,
+            computed_at: Utc::now(),
+        };
+
+        store.store_analysis(&record(old, 0.1)).await.unwrap();
+        store.create_snapshot("before").await.unwrap();
+        store.store_analysis(&record(new, 0.3)).await.unwrap();
+        store.create_snapshot("after").await.unwrap();
+
+        store
+            .upsert_hyperedge(&Hyperedge {
+                id: HyperedgeId(0),
+                kind: HyperedgeKind::Aliases,
+                members: vec![
+                    HyperedgeMember {
+                        node_id: old,
+                        role: roles::ALIAS_OLD.to_string(),
+                        position: 0,
+                    },
+                    HyperedgeMember {
+                        node_id: new,
+                        role: roles::ALIAS_NEW.to_string(),
+                        position: 1,
+                    },
+                ],
+                confidence: 1.0,
+                last_updated: Utc::now(),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
+        // "after" recorded both names; the new name's value wins.
+        let series = store
+            .get_metric_series(&[new], AnalysisKind::PageRank)
+            .await
+            .unwrap()
+            .remove(&new)
+            .unwrap_or_default();
+        let points: Vec<(&str, f64)> = series
+            .iter()
+            .map(|p| (p.label.as_str(), p.data["score"].as_f64().unwrap()))
+            .collect();
+        assert_eq!(points, [("before", 0.1), ("after", 0.3)]);
+
+        // The old name does not pick up its successor's history.
+        let series = store
+            .get_metric_series(&[old], AnalysisKind::PageRank)
+            .await
+            .unwrap()
+            .remove(&old)
+            .unwrap_or_default();
+        assert_eq!(series.len(), 2);
+        assert!(series.iter().all(|p| p.data["score"] == 0.1));
+
+        // One call serves several nodes, each with its own lineage.
+        let both = store
+            .get_metric_series(&[new, old], AnalysisKind::PageRank)
+            .await
+            .unwrap();
+        assert_eq!(both.len(), 2);
+        assert_eq!(both[&new][1].data["score"], 0.3);
+        assert_eq!(both[&old][1].data["score"], 0.1);
+    }
+
     #[tokio::test]
     async fn store_stats() {
         let store = SqliteStore::in_memory().unwrap();
@@ -1762,12 +2378,12 @@ mod tests {
                 members: vec![
                     HyperedgeMember {
                         node_id: old_id,
-                        role: "old".to_string(),
+                        role: roles::ALIAS_OLD.to_string(),
                         position: 0,
                     },
                     HyperedgeMember {
                         node_id: new_id,
-                        role: "new".to_string(),
+                        role: roles::ALIAS_NEW.to_string(),
                         position: 1,
                     },
                 ],
@@ -1811,12 +2427,12 @@ mod tests {
                     members: vec![
                         HyperedgeMember {
                             node_id: old,
-                            role: "old".to_string(),
+                            role: roles::ALIAS_OLD.to_string(),
                             position: 0,
                         },
                         HyperedgeMember {
                             node_id: new,
-                            role: "new".to_string(),
+                            role: roles::ALIAS_NEW.to_string(),
                             position: 1,
                         },
                     ],
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 
 use crate::types::{
-    AnalysisKind, AnalysisResult, GraphDiff, Hyperedge, HyperedgeId, HyperedgeKind, InMemoryGraph,
-    Node, NodeFilter, NodeId, NodeKind, SearchHit, SearchScope, SnapshotId, SnapshotInfo,
-    StoreStats, SubgraphFilter,
+    AnalysisKind, AnalysisResult, GraphDiff, HistoricalSnapshot, Hyperedge, HyperedgeId,
+    HyperedgeKind, InMemoryGraph, MetricPoint, Node, NodeFilter, NodeId, NodeKind, SearchHit,
+    SearchScope, SnapshotId, SnapshotInfo, StoreStats, SubgraphFilter,
 };
 
 /// The core store abstraction. All pipeline stages read/write through this trait.
@@ -57,6 +59,19 @@ pub trait HomerStore: Send + Sync {
     /// Get all edges of a specific kind.
     async fn get_edges_by_kind(&self, kind: HyperedgeKind) -> crate::error::Result<Vec<Hyperedge>>;
 
+    /// Delete all edges of a specific kind, for extractors that rebuild
+    /// them wholesale. Returns the number deleted.
+    async fn delete_edges_by_kind(&self, kind: HyperedgeKind) -> crate::error::Result<u64>;
+
+    /// Delete the edges of a kind whose `extractor` metadata names
+    /// `extractor`, for extractors sharing an edge kind. Returns the number
+    /// deleted.
+    async fn delete_edges_by_extractor(
+        &self,
+        kind: HyperedgeKind,
+        extractor: &str,
+    ) -> crate::error::Result<u64>;
+
     /// Get all co-member node IDs for a given node in edges of a specific kind.
     async fn get_co_members(
         &self,
@@ -138,9 +153,34 @@ pub trait HomerStore: Send + Sync {
 
     // ── Graph snapshots ────────────────────────────────────────────
 
-    /// Create a named snapshot of current graph state.
+    /// Create a named snapshot of current graph state, including the values
+    /// of [`AnalysisKind::SNAPSHOTTED`] analyses.
     async fn create_snapshot(&self, label: &str) -> crate::error::Result<SnapshotId>;
 
+    /// Record a snapshot of historical graph state, replacing any historical
+    /// snapshot with the same label. Snapshots of the live graph are kept.
+    async fn create_historical_snapshot(
+        &self,
+        snapshot: &HistoricalSnapshot,
+    ) -> crate::error::Result<SnapshotId>;
+
+    /// Analysis values of one kind recorded in a snapshot.
+    async fn get_snapshot_analyses(
+        &self,
+        snapshot: SnapshotId,
+        kind: AnalysisKind,
+    ) -> crate::error::Result<Vec<(NodeId, serde_json::Value)>>;
+
+    /// Values of one analysis kind for each node across all snapshots that
+    /// recorded it, oldest first. Values recorded under names a node
+    /// replaced (via `Aliases` edges) are included. Nodes with no recorded
+    /// values are absent from the map.
+    async fn get_metric_series(
+        &self,
+        node_ids: &[NodeId],
+        kind: AnalysisKind,
+    ) -> crate::error::Result<HashMap<NodeId, Vec<MetricPoint>>>;
+
     /// List all snapshots ordered by creation time.
     async fn list_snapshots(&self) -> crate::error::Result<Vec<SnapshotInfo>>;
 
@@ -66,6 +66,26 @@ pub enum NodeKind {
     AgentRule,
     /// A recorded AI agent session.
     AgentSession,
+    /// A test case seen in CI test-result artifacts, with its run history.
+    Test,
+    /// A CI job or task-runner recipe, with its triggers and path filters.
+    CiJob,
+    /// A deployment resource: a container image or service, a Kubernetes
+    /// object, a Helm chart, or a Terraform resource or module.
+    InfraResource,
+    /// An environment variable, by name.
+    EnvVar,
+    /// An API operation declared by a contract: a gRPC method, an `OpenAPI`
+    /// operation, or a `GraphQL` root field.
+    Endpoint,
+    /// A database table declared by schema migrations, with its history.
+    Table,
+    /// A column of a migrated table, named `table.column`.
+    Column,
+    /// A configuration key read by code, as a dotted path (`database.host`).
+    ConfigKey,
+    /// A feature flag evaluated by code, named by its key (`new-checkout`).
+    FeatureFlag,
 }
 
 impl NodeKind {
@@ -86,6 +106,15 @@ impl NodeKind {
             Self::Prompt => "Prompt",
             Self::AgentRule => "AgentRule",
             Self::AgentSession => "AgentSession",
+            Self::Test => "Test",
+            Self::CiJob => "CiJob",
+            Self::InfraResource => "InfraResource",
+            Self::EnvVar => "EnvVar",
+            Self::Endpoint => "Endpoint",
+            Self::Table => "Table",
+            Self::Column => "Column",
+            Self::ConfigKey => "ConfigKey",
+            Self::FeatureFlag => "FeatureFlag",
         }
     }
 }
@@ -96,6 +125,41 @@ impl std::fmt::Display for NodeKind {
     }
 }
 
+/// Parses the names [`NodeKind::as_str`] produces.
+impl std::str::FromStr for NodeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "File" => Ok(Self::File),
+            "Function" => Ok(Self::Function),
+            "Type" => Ok(Self::Type),
+            "Module" => Ok(Self::Module),
+            "Commit" => Ok(Self::Commit),
+            "PullRequest" => Ok(Self::PullRequest),
+            "Issue" => Ok(Self::Issue),
+            "Contributor" => Ok(Self::Contributor),
+            "Release" => Ok(Self::Release),
+            "Concept" => Ok(Self::Concept),
+            "ExternalDep" => Ok(Self::ExternalDep),
+            "Document" => Ok(Self::Document),
+            "Prompt" => Ok(Self::Prompt),
+            "AgentRule" => Ok(Self::AgentRule),
+            "AgentSession" => Ok(Self::AgentSession),
+            "Test" => Ok(Self::Test),
+            "CiJob" => Ok(Self::CiJob),
+            "InfraResource" => Ok(Self::InfraResource),
+            "EnvVar" => Ok(Self::EnvVar),
+            "Endpoint" => Ok(Self::Endpoint),
+            "Table" => Ok(Self::Table),
+            "Column" => Ok(Self::Column),
+            "ConfigKey" => Ok(Self::ConfigKey),
+            "FeatureFlag" => Ok(Self::FeatureFlag),
+            other => Err(format!("unknown node kind: {other}")),
+        }
+    }
+}
+
 /// A node in the Homer hypergraph — the fundamental unit of repository data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Node {
@@ -136,7 +200,8 @@ pub enum HyperedgeKind {
     BelongsTo,
     /// Module → `ExternalDep`: a module depends on an external package.
     DependsOn,
-    /// Name → Name: two names refer to the same entity (re-exports).
+    /// Name → Name: two names refer to the same entity (re-exports, or a
+    /// file or function that was moved, renamed, or extracted).
     Aliases,
     /// Document → Entity: a document describes an entity.
     Documents,
@@ -152,6 +217,37 @@ pub enum HyperedgeKind {
     ClusterMembers,
     /// Community → Node: a community encompasses a set of nodes.
     Encompasses,
+    /// Contributor → File: a CODEOWNERS rule assigns files to owners.
+    Owns,
+    /// Test function → Function: a test exercises a production function,
+    /// directly or through the functions it calls.
+    Tests,
+    /// `CiJob` → `CiJob`: a job runs only after the jobs it needs.
+    Needs,
+    /// File → `CiJob`: a change to the files triggers a path-filtered job.
+    Triggers,
+    /// `InfraResource` → `InfraResource`: a deployment resource depends on
+    /// another (base image, `depends_on`, selector, Terraform reference).
+    Uses,
+    /// `InfraResource` → Module/File: a resource is built from these sources.
+    Builds,
+    /// Node → `EnvVar`/`ConfigKey`: a resource, file, or CI job sets an
+    /// environment variable or documents a configuration key.
+    Defines,
+    /// Endpoint/Type → Type: a contract element refers to a message or schema
+    /// (request, response, field type).
+    References,
+    /// Endpoint/Type → Function/Type/File: code that serves, calls or mirrors
+    /// a contract element, matched by name or route.
+    Binds,
+    /// Function/Type/File → Table/Column: code that reads or writes a table,
+    /// through SQL in a string literal or an ORM model mapped to it.
+    Accesses,
+    /// Function/File → `EnvVar`/`ConfigKey`: code reads an environment
+    /// variable or configuration key.
+    Reads,
+    /// Function/File → `FeatureFlag`: code evaluates a feature flag.
+    Checks,
 }
 
 impl HyperedgeKind {
@@ -175,6 +271,18 @@ impl HyperedgeKind {
             Self::CoChanges => "CoChanges",
             Self::ClusterMembers => "ClusterMembers",
             Self::Encompasses => "Encompasses",
+            Self::Owns => "Owns",
+            Self::Tests => "Tests",
+            Self::Needs => "Needs",
+            Self::Triggers => "Triggers",
+            Self::Uses => "Uses",
+            Self::Builds => "Builds",
+            Self::Defines => "Defines",
+            Self::References => "References",
+            Self::Binds => "Binds",
+            Self::Accesses => "Accesses",
+            Self::Reads => "Reads",
+            Self::Checks => "Checks",
         }
     }
 }
@@ -244,6 +352,36 @@ pub enum AnalysisKind {
     ArchitecturalDrift,
     /// Stability classification combining churn and centrality.
     StabilityClassification,
+    /// Function or type unreachable from any entry point.
+    DeadCode,
+    /// Per-function complexity metrics and their trend across snapshots.
+    ComplexityTrend,
+    /// Complex function in a frequently changed file.
+    ComplexityHotspot,
+    /// Group of duplicated or near-duplicated function bodies, and whether
+    /// its members have since been changed inconsistently.
+    CloneGroup,
+    /// Exported functions and types, with their signatures, at a release.
+    ApiSurface,
+    /// Line-age distribution and half-life of a file, module, function, or
+    /// contributor's code.
+    CodeSurvival,
+    /// Whether a contributor is still active, from their most recent commit.
+    ContributorActivity,
+    /// Share of a file's or module's knowledge held by inactive contributors.
+    KnowledgeLoss,
+    /// The team a contributor belongs to, from the roster or CODEOWNERS.
+    TeamAssignment,
+    /// Team ownership of code communities and cross-team change coupling.
+    TeamAlignment,
+    /// A file whose CODEOWNERS owners have never committed to it.
+    OwnershipDrift,
+    /// A test whose outcome flipped between pass and fail at the same commit.
+    TestFlakiness,
+    /// A test whose typical duration is among the slowest in the suite.
+    SlowTest,
+    /// How much of profiled runtime a function (or file) accounts for.
+    RuntimeHotness,
     /// Dominant naming convention (`snake_case`, `camelCase`, etc.).
     NamingPattern,
     /// Testing framework and patterns detected in the repo.
@@ -267,6 +405,17 @@ pub enum AnalysisKind {
 }
 
 impl AnalysisKind {
+    /// Kinds whose per-node values are captured in graph snapshots, so
+    /// their history can be read back as a time series.
+    pub const SNAPSHOTTED: &'static [AnalysisKind] = &[
+        Self::CompositeSalience,
+        Self::PageRank,
+        Self::BetweennessCentrality,
+        Self::HITSScore,
+        Self::CommunityAssignment,
+        Self::ContributorConcentration,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::ChangeFrequency => "ChangeFrequency",
@@ -284,6 +433,20 @@ impl AnalysisKind {
             Self::CentralityTrend => "CentralityTrend",
             Self::ArchitecturalDrift => "ArchitecturalDrift",
             Self::StabilityClassification => "StabilityClassification",
+            Self::DeadCode => "DeadCode",
+            Self::ComplexityTrend => "ComplexityTrend",
+            Self::ComplexityHotspot => "ComplexityHotspot",
+            Self::CloneGroup => "CloneGroup",
+            Self::ApiSurface => "ApiSurface",
+            Self::CodeSurvival => "CodeSurvival",
+            Self::ContributorActivity => "ContributorActivity",
+            Self::KnowledgeLoss => "KnowledgeLoss",
+            Self::TeamAssignment => "TeamAssignment",
+            Self::TeamAlignment => "TeamAlignment",
+            Self::OwnershipDrift => "OwnershipDrift",
+            Self::TestFlakiness => "TestFlakiness",
+            Self::SlowTest => "SlowTest",
+            Self::RuntimeHotness => "RuntimeHotness",
             Self::NamingPattern => "NamingPattern",
             Self::TestingPattern => "TestingPattern",
             Self::ErrorHandlingPattern => "ErrorHandlingPattern",
@@ -542,6 +705,60 @@ pub struct SnapshotInfo {
     pub edge_count: u64,
 }
 
+/// A snapshot of graph state computed outside the live graph, e.g. by
+/// re-extracting a past release in a scratch worktree.
+///
+/// Membership and analysis values refer to nodes of the live graph; entities
+/// that have no live node since are recorded by kind and name in `removed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoricalSnapshot {
+    /// Human-readable label (e.g., "v1.0.0" or "auto-50").
+    pub label: String,
+    /// When the captured state was current (commit time, not capture time).
+    pub snapshot_at: DateTime<Utc>,
+    /// Total number of nodes in the historical graph.
+    pub node_count: u64,
+    /// Total number of edges in the historical graph.
+    pub edge_count: u64,
+    /// Live-graph nodes that existed at this point in history.
+    pub node_ids: Vec<NodeId>,
+    /// Entities that existed at this point in history but have no live node.
+    pub removed: Vec<EntityRef>,
+    /// Per-node analysis values computed against the historical graph.
+    pub analyses: Vec<SnapshotAnalysis>,
+}
+
+/// An entity identified by kind and name rather than by live node.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EntityRef {
+    pub kind: NodeKind,
+    pub name: String,
+}
+
+/// One analysis value recorded in a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotAnalysis {
+    /// The node the value belongs to.
+    pub node_id: NodeId,
+    /// Which analysis produced the value.
+    pub kind: AnalysisKind,
+    /// The analysis payload as it was at snapshot time.
+    pub data: serde_json::Value,
+}
+
+/// One value in a node's metric history, as recorded by a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricPoint {
+    /// The snapshot that recorded the value.
+    pub snapshot_id: SnapshotId,
+    /// Label of that snapshot.
+    pub label: String,
+    /// When the recorded state was current.
+    pub snapshot_at: DateTime<Utc>,
+    /// The analysis payload at that point.
+    pub data: serde_json::Value,
+}
+
 /// Graph diff between two snapshots.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GraphDiff {
@@ -553,6 +770,12 @@ pub struct GraphDiff {
     pub added_edges: Vec<HyperedgeId>,
     /// Edges present in the older snapshot but not the newer.
     pub removed_edges: Vec<HyperedgeId>,
+    /// Entities without a live node (see [`HistoricalSnapshot::removed`])
+    /// present in the newer snapshot but not the older.
+    pub added_entities: Vec<EntityRef>,
+    /// Entities without a live node present in the older snapshot but not
+    /// the newer.
+    pub removed_entities: Vec<EntityRef>,
 }
 
 // ── Extractor-specific types ───────────────────────────────────────
@@ -570,7 +793,11 @@ pub struct FileDiffStats {
 }
 
 /// A contiguous region of changes within a file diff.
+///
+/// Serialized as `[old_start, old_lines, new_start, new_lines]`, since every
+/// `Modifies` edge carries one per changed region.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "[u32; 4]", into = "[u32; 4]")]
 pub struct DiffHunk {
     /// Starting line number in the old file (1-based).
     pub old_start: u32,
@@ -582,6 +809,23 @@ pub struct DiffHunk {
     pub new_lines: u32,
 }
 
+impl From<[u32; 4]> for DiffHunk {
+    fn from([old_start, old_lines, new_start, new_lines]: [u32; 4]) -> Self {
+        Self {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+        }
+    }
+}
+
+impl From<DiffHunk> for [u32; 4] {
+    fn from(h: DiffHunk) -> Self {
+        [h.old_start, h.old_lines, h.new_start, h.new_lines]
+    }
+}
+
 /// Status of a file in a git diff.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DiffStatus {
@@ -644,6 +888,15 @@ mod tests {
             NodeKind::Prompt,
             NodeKind::AgentRule,
             NodeKind::AgentSession,
+            NodeKind::Test,
+            NodeKind::CiJob,
+            NodeKind::InfraResource,
+            NodeKind::EnvVar,
+            NodeKind::Endpoint,
+            NodeKind::Table,
+            NodeKind::Column,
+            NodeKind::ConfigKey,
+            NodeKind::FeatureFlag,
         ] {
             let json = serde_json::to_string(&kind).unwrap();
             let back: NodeKind = serde_json::from_str(&json).unwrap();
@@ -672,6 +925,18 @@ mod tests {
             HyperedgeKind::CoChanges,
             HyperedgeKind::ClusterMembers,
             HyperedgeKind::Encompasses,
+            HyperedgeKind::Owns,
+            HyperedgeKind::Tests,
+            HyperedgeKind::Needs,
+            HyperedgeKind::Triggers,
+            HyperedgeKind::Uses,
+            HyperedgeKind::Builds,
+            HyperedgeKind::Defines,
+            HyperedgeKind::References,
+            HyperedgeKind::Binds,
+            HyperedgeKind::Accesses,
+            HyperedgeKind::Reads,
+            HyperedgeKind::Checks,
         ] {
             let json = serde_json::to_string(&kind).unwrap();
             let back: HyperedgeKind = serde_json::from_str(&json).unwrap();
@@ -798,6 +1063,15 @@ mod tests {
                 Just(NodeKind::Prompt),
                 Just(NodeKind::AgentRule),
                 Just(NodeKind::AgentSession),
+                Just(NodeKind::Test),
+                Just(NodeKind::CiJob),
+                Just(NodeKind::InfraResource),
+                Just(NodeKind::EnvVar),
+                Just(NodeKind::Endpoint),
+                Just(NodeKind::Table),
+                Just(NodeKind::Column),
+                Just(NodeKind::ConfigKey),
+                Just(NodeKind::FeatureFlag),
             ]
         }
 
@@ -821,6 +1095,18 @@ mod tests {
                 Just(HyperedgeKind::CoChanges),
                 Just(HyperedgeKind::ClusterMembers),
                 Just(HyperedgeKind::Encompasses),
+                Just(HyperedgeKind::Owns),
+                Just(HyperedgeKind::Tests),
+                Just(HyperedgeKind::Needs),
+                Just(HyperedgeKind::Triggers),
+                Just(HyperedgeKind::Uses),
+                Just(HyperedgeKind::Builds),
+                Just(HyperedgeKind::Defines),
+                Just(HyperedgeKind::References),
+                Just(HyperedgeKind::Binds),
+                Just(HyperedgeKind::Accesses),
+                Just(HyperedgeKind::Reads),
+                Just(HyperedgeKind::Checks),
             ]
         }
 
@@ -835,6 +1121,20 @@ mod tests {
                 Just(AnalysisKind::HITSScore),
                 Just(AnalysisKind::CompositeSalience),
                 Just(AnalysisKind::CommunityAssignment),
+                Just(AnalysisKind::DeadCode),
+                Just(AnalysisKind::ComplexityTrend),
+                Just(AnalysisKind::ComplexityHotspot),
+                Just(AnalysisKind::CloneGroup),
+                Just(AnalysisKind::ApiSurface),
+                Just(AnalysisKind::CodeSurvival),
+                Just(AnalysisKind::ContributorActivity),
+                Just(AnalysisKind::KnowledgeLoss),
+                Just(AnalysisKind::TeamAssignment),
+                Just(AnalysisKind::TeamAlignment),
+                Just(AnalysisKind::OwnershipDrift),
+                Just(AnalysisKind::TestFlakiness),
+                Just(AnalysisKind::SlowTest),
+                Just(AnalysisKind::RuntimeHotness),
                 Just(AnalysisKind::NamingPattern),
                 Just(AnalysisKind::TaskPattern),
                 Just(AnalysisKind::SemanticSummary),
@@ -869,6 +1169,12 @@ mod tests {
                 prop_assert_eq!(back, kind);
             }
 
+            #[test]
+            fn node_kind_str_roundtrip(kind in arb_node_kind()) {
+                let back: NodeKind = kind.as_str().parse().unwrap();
+                prop_assert_eq!(back, kind);
+            }
+
             #[test]
             fn edge_kind_serde_roundtrip(kind in arb_edge_kind()) {
                 let json = serde_json::to_string(&kind).unwrap();
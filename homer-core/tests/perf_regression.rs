@@ -110,7 +110,10 @@ async fn perf_centrality_under_threshold() {
     graph.extract(&store, &config).await.unwrap();
 
     // Ensure prerequisite behavioral metrics exist for composite salience.
-    BehavioralAnalyzer.analyze(&store, &config).await.unwrap();
+    BehavioralAnalyzer::default()
+        .analyze(&store, &config)
+        .await
+        .unwrap();
 
     let t0 = Instant::now();
     CentralityAnalyzer::default()
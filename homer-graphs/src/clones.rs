@@ -0,0 +1,220 @@
+// Normalized token fingerprints for clone detection.
+//
+// A definition's leaf tokens are abstracted so that renamed identifiers and
+// changed literals do not hide a copy: identifiers become `ID`, literals
+// become `LIT`, comments are dropped, and keywords and punctuation are kept
+// as written. Consecutive token k-grams are hashed and winnowed (keep the
+// minimum hash of every window), which gives a compact set whose overlap
+// estimates how much of two bodies is shared regardless of where in the body
+// the shared code sits.
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Tree};
+
+use crate::TextRange;
+
+/// Tokens per hashed k-gram.
+const KGRAM: usize = 5;
+
+/// K-grams per winnowing window; any match this long is guaranteed a hash.
+const WINDOW: usize = 4;
+
+/// Winnowed fingerprint of a definition's normalized token stream.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloneFingerprint {
+    /// Number of normalized tokens in the definition.
+    pub tokens: usize,
+    /// Selected k-gram hashes, sorted and deduplicated.
+    pub hashes: Vec<u32>,
+}
+
+impl CloneFingerprint {
+    /// Jaccard similarity of the two hash sets (0.0–1.0).
+    pub fn similarity(&self, other: &Self) -> f64 {
+        let shared = shared_count(&self.hashes, &other.hashes);
+        let union = self.hashes.len() + other.hashes.len() - shared;
+        if union == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = shared as f64 / union as f64;
+        ratio
+    }
+}
+
+/// Fingerprint the definition spanning `span`, if the tree has a node there.
+pub fn fingerprint_at(tree: &Tree, source: &str, span: &TextRange) -> Option<CloneFingerprint> {
+    let node = tree
+        .root_node()
+        .descendant_for_byte_range(span.start_byte, span.end_byte)?;
+    Some(fingerprint(node, source))
+}
+
+/// Fingerprint a definition node.
+pub fn fingerprint(node: Node<'_>, source: &str) -> CloneFingerprint {
+    let tokens = normalized_tokens(node, source);
+    let kgrams: Vec<u32> = tokens
+        .windows(KGRAM)
+        .map(|gram| {
+            gram.iter()
+                .fold(FNV_OFFSET, |h, t| fnv(h, &t.to_le_bytes()))
+        })
+        .collect();
+
+    let mut hashes: Vec<u32> = if kgrams.len() <= WINDOW {
+        kgrams
+    } else {
+        kgrams
+            .windows(WINDOW)
+            .filter_map(|w| w.iter().min().copied())
+            .collect()
+    };
+    hashes.sort_unstable();
+    hashes.dedup();
+
+    CloneFingerprint {
+        tokens: tokens.len(),
+        hashes,
+    }
+}
+
+/// Hashed leaf tokens of `node` with identifiers and literals abstracted.
+pub fn normalized_tokens(node: Node<'_>, source: &str) -> Vec<u32> {
+    let mut tokens = Vec::new();
+
+    // Explicit stack: deeply nested ASTs must not overflow the call stack.
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        let kind = current.kind();
+        if kind.contains("comment") {
+            continue;
+        }
+        if current.child_count() == 0 {
+            let token = if !current.is_named() {
+                kind
+            } else if is_literal(kind) {
+                "LIT"
+            } else if kind.contains("identifier") {
+                "ID"
+            } else {
+                // Named leaves such as `self` or `true`.
+                current.utf8_text(source.as_bytes()).unwrap_or(kind)
+            };
+            tokens.push(fnv(FNV_OFFSET, token.as_bytes()));
+            continue;
+        }
+        // Literals are one token however they are split into pieces.
+        if is_literal(kind) {
+            tokens.push(fnv(FNV_OFFSET, b"LIT"));
+            continue;
+        }
+
+        let mut cursor = current.walk();
+        let children: Vec<Node<'_>> = current.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    tokens
+}
+
+fn is_literal(kind: &str) -> bool {
+    [
+        "string", "char", "number", "integer", "float", "literal", "rune",
+    ]
+    .iter()
+    .any(|l| kind.contains(l))
+}
+
+const FNV_OFFSET: u32 = 0x811c_9dc5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+fn fnv(mut hash: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        hash ^= u32::from(b);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Size of the intersection of two sorted, deduplicated slices.
+fn shared_count(a: &[u32], b: &[u32]) -> usize {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    shared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function_fingerprint(source: &str) -> CloneFingerprint {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let func = tree.root_node().child(0).unwrap();
+        fingerprint(func, source)
+    }
+
+    const ORIGINAL: &str = "fn total(items: &[Item]) -> u64 {
+    let mut sum = 0;
+    for item in items {
+        if item.active {
+            sum += item.price * 2;
+        }
+    }
+    sum
+}";
+
+    #[test]
+    fn renamed_copy_is_identical() {
+        let copy = "fn sum_prices(rows: &[Row]) -> u64 {
+    // Same loop, different names.
+    let mut acc = 0;
+    for row in rows {
+        if row.active {
+            acc += row.price * 3;
+        }
+    }
+    acc
+}";
+        let a = function_fingerprint(ORIGINAL);
+        let b = function_fingerprint(copy);
+        assert_eq!(a, b);
+        assert!((a.similarity(&b) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn edited_copy_is_similar_and_different_code_is_not() {
+        let edited = "fn total(items: &[Item]) -> u64 {
+    let mut sum = 0;
+    for item in items {
+        if item.active && !item.refunded {
+            sum += item.price * 2;
+        }
+    }
+    sum
+}";
+        let unrelated = "fn render(out: &mut String, rows: &[Row]) {
+    out.push_str(\"<table>\");
+    rows.iter().for_each(|r| out.push_str(&r.to_string()));
+    out.push_str(\"</table>\");
+}";
+        let a = function_fingerprint(ORIGINAL);
+        let near = a.similarity(&function_fingerprint(edited));
+        let far = a.similarity(&function_fingerprint(unrelated));
+        assert!(near > 0.5 && near < 1.0, "near = {near}");
+        assert!(far < 0.2, "far = {far}");
+    }
+}
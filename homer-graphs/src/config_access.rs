@@ -0,0 +1,496 @@
+// Configuration access: environment variable and config-key reads, found
+// without per-language queries.
+
+use tree_sitter::{Node, Tree};
+
+use crate::TextRange;
+use crate::data_access::{STRING_KINDS, string_value, text};
+
+/// Node kinds for calls, macro invocations, and annotations.
+const CALL_KINDS: &[&str] = &[
+    "call_expression",
+    "call",
+    "method_invocation",
+    "invocation_expression",
+    "function_call_expression",
+    "member_call_expression",
+    "scoped_call_expression",
+    "macro_invocation",
+    "annotation",
+];
+
+/// Node kinds holding a call's arguments.
+const ARGUMENT_KINDS: &[&str] = &[
+    "arguments",
+    "argument_list",
+    "token_tree",
+    "value_arguments",
+    "call_suffix",
+    "annotation_argument_list",
+];
+
+/// Node kinds wrapping a single argument or subscript.
+const WRAPPER_KINDS: &[&str] = &["argument", "value_argument", "bracketed_argument_list"];
+
+/// Node kinds for `object[key]`.
+const INDEX_KINDS: &[&str] = &[
+    "subscript",
+    "subscript_expression",
+    "element_reference",
+    "element_access_expression",
+];
+
+/// Node kinds for `object.property`.
+const MEMBER_KINDS: &[&str] = &["member_expression"];
+
+/// Calls reading an environment variable.
+const ENV_CALLS: &[&str] = &[
+    "env::var",
+    "env::var_os",
+    "env!",
+    "option_env!",
+    "getenv",
+    "os.environ.get",
+    "environ.get",
+    "os.Getenv",
+    "os.LookupEnv",
+    "System.getenv",
+    "Environment.GetEnvironmentVariable",
+    "ENV.fetch",
+    "Deno.env.get",
+    "env",
+];
+
+/// Objects indexed by environment variable name.
+const ENV_INDEXES: &[&str] = &[
+    "os.environ",
+    "environ",
+    "ENV",
+    "process.env",
+    "import.meta.env",
+    "Bun.env",
+    "$_ENV",
+    "$_SERVER",
+    "ProcessInfo.processInfo.environment",
+];
+
+/// Objects whose properties are environment variables.
+const ENV_MEMBERS: &[&str] = &["process.env", "import.meta.env", "Bun.env"];
+
+/// Calls reading a configuration key.
+const CONFIG_CALLS: &[&str] = &[
+    "config.get",
+    "Config.get",
+    "settings.get",
+    "viper.Get*",
+    "System.getProperty",
+    "GetValue*",
+    "GetSection",
+    "GetConnectionString",
+    "config",
+    "@Value",
+];
+
+/// Objects indexed by configuration key.
+const CONFIG_INDEXES: &[&str] = &[
+    "config",
+    "app.config",
+    "current_app.config",
+    "Configuration",
+    "configuration",
+    "_configuration",
+];
+
+/// What a [`KeyedAccess`] does with its key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessForm {
+    /// `target("key")`
+    Call,
+    /// `target["key"]`
+    Index,
+    /// `target.key`
+    Member,
+}
+
+/// A call, subscript, or member access given a string key. A call's key is
+/// its first argument only when that is a plain string literal, so
+/// `os.getenv(prefix + "HOST")` yields nothing rather than a wrong key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyedAccess {
+    /// Callee or indexed object, without whitespace or generic arguments.
+    pub target: String,
+    pub key: String,
+    pub form: AccessForm,
+    /// Source location of the whole access.
+    pub span: TextRange,
+}
+
+/// Whether a read is of an environment variable or a configuration key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ReadKind {
+    EnvVar,
+    ConfigKey,
+}
+
+/// An environment variable or configuration key the code reads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigRead {
+    pub kind: ReadKind,
+    /// Variable name, or config key with `:` separators turned into `.`.
+    pub key: String,
+    pub span: TextRange,
+}
+
+/// Call and index patterns classifying accesses as reads.
+#[derive(Debug, Clone)]
+pub struct AccessPatterns {
+    pub env_calls: Vec<String>,
+    pub env_indexes: Vec<String>,
+    pub env_members: Vec<String>,
+    pub config_calls: Vec<String>,
+    pub config_indexes: Vec<String>,
+}
+
+impl Default for AccessPatterns {
+    fn default() -> Self {
+        let owned = |patterns: &[&str]| patterns.iter().map(|p| (*p).to_string()).collect();
+        Self {
+            env_calls: owned(ENV_CALLS),
+            env_indexes: owned(ENV_INDEXES),
+            env_members: owned(ENV_MEMBERS),
+            config_calls: owned(CONFIG_CALLS),
+            config_indexes: owned(CONFIG_INDEXES),
+        }
+    }
+}
+
+impl AccessPatterns {
+    /// Whether `source` could contain a read, judged by the last segment of
+    /// each pattern; for skipping files before parsing them.
+    pub fn may_match(&self, source: &str) -> bool {
+        [
+            &self.env_calls,
+            &self.env_indexes,
+            &self.env_members,
+            &self.config_calls,
+            &self.config_indexes,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|pattern| {
+            let segment = pattern
+                .rsplit(['.', ':', '>', '\\'])
+                .next()
+                .unwrap_or(pattern)
+                .trim_end_matches(['*', '!']);
+            !segment.is_empty() && source.contains(segment)
+        })
+    }
+}
+
+/// Every call, subscript, and member access in the file given a string key,
+/// in source order.
+pub fn keyed_accesses(tree: &Tree, source: &str) -> Vec<KeyedAccess> {
+    let mut found = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let kind = node.kind();
+        let access = if CALL_KINDS.contains(&kind) {
+            call_access(node, source)
+        } else if INDEX_KINDS.contains(&kind) {
+            index_access(node, source)
+        } else if MEMBER_KINDS.contains(&kind) {
+            member_access(node, source)
+        } else {
+            None
+        };
+        found.extend(access);
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    found.sort_by_key(|access| access.span.start_byte);
+    found
+}
+
+/// Environment variable and configuration key reads in the file, in source
+/// order.
+pub fn config_reads(tree: &Tree, source: &str, patterns: &AccessPatterns) -> Vec<ConfigRead> {
+    keyed_accesses(tree, source)
+        .into_iter()
+        .filter_map(|access| {
+            let (env, config) = match access.form {
+                AccessForm::Call => (&patterns.env_calls, &patterns.config_calls),
+                AccessForm::Index => (&patterns.env_indexes, &patterns.config_indexes),
+                AccessForm::Member => (&patterns.env_members, &Vec::new()),
+            };
+            let matches = |list: &Vec<String>| match access.form {
+                AccessForm::Call => list.iter().any(|p| callee_matches(&access.target, p)),
+                _ => list.contains(&access.target),
+            };
+            let (kind, key) = if matches(env) && is_env_name(&access.key) {
+                (ReadKind::EnvVar, access.key)
+            } else if matches(config) {
+                (ReadKind::ConfigKey, config_key(&access.key)?)
+            } else {
+                return None;
+            };
+            Some(ConfigRead {
+                kind,
+                key,
+                span: access.span,
+            })
+        })
+        .collect()
+}
+
+/// Whether a callee matches a call pattern: equal to it, ending in it after
+/// a path separator, or, for a pattern ending in `*`, starting with the rest
+/// of it in either way.
+pub fn callee_matches(callee: &str, pattern: &str) -> bool {
+    let (pattern, prefix) = match pattern.strip_suffix('*') {
+        Some(stem) => (stem, true),
+        None => (pattern, false),
+    };
+    ["", ".", "::", "->", "\\"].iter().any(|sep| {
+        let needle = format!("{sep}{pattern}");
+        let at = if sep.is_empty() {
+            callee.starts_with(pattern).then_some(0)
+        } else {
+            callee
+                .rfind(&needle)
+                .map(|i| i + needle.len() - pattern.len())
+        };
+        at.is_some_and(|at| {
+            let rest = &callee[at + pattern.len()..];
+            rest.is_empty() || (prefix && !rest.contains(['.', ':']))
+        })
+    })
+}
+
+/// Whether `key` is an upper snake case environment variable name, which
+/// keeps `app.env("production")` out.
+pub fn is_env_name(key: &str) -> bool {
+    key.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase() || c == '_')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// A config key in normal form, or `None` for a string that is not a dotted
+/// or colon path. Spring `${key:default}` placeholders are unwrapped.
+fn config_key(raw: &str) -> Option<String> {
+    let key = raw
+        .strip_prefix("${")
+        .map_or(raw, |rest| rest.split([':', '}']).next().unwrap_or(rest));
+    let valid = !key.is_empty()
+        && key.len() <= 128
+        && key.chars().any(|c| c.is_ascii_alphabetic())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '-'));
+    valid.then(|| key.replace(':', "."))
+}
+
+fn call_access(node: Node<'_>, source: &str) -> Option<KeyedAccess> {
+    let mut cursor = node.walk();
+    let arguments = node
+        .children(&mut cursor)
+        .find(|c| ARGUMENT_KINDS.contains(&c.kind()))?;
+    let callee = source.get(node.start_byte()..arguments.start_byte())?;
+    Some(KeyedAccess {
+        target: normalize_target(callee),
+        key: string_argument(arguments, source)?,
+        form: AccessForm::Call,
+        span: node.range().into(),
+    })
+}
+
+fn index_access(node: Node<'_>, source: &str) -> Option<KeyedAccess> {
+    let object = node
+        .child_by_field_name("value")
+        .or_else(|| node.child_by_field_name("object"))
+        .or_else(|| node.named_child(0))?;
+    let index = node
+        .child_by_field_name("subscript")
+        .or_else(|| node.child_by_field_name("index"))
+        .or_else(|| node.named_child(1))?;
+    Some(KeyedAccess {
+        target: normalize_target(text(object, source)),
+        key: string_argument(index, source)?,
+        form: AccessForm::Index,
+        span: node.range().into(),
+    })
+}
+
+fn member_access(node: Node<'_>, source: &str) -> Option<KeyedAccess> {
+    let object = node.child_by_field_name("object")?;
+    let property = node.child_by_field_name("property")?;
+    Some(KeyedAccess {
+        target: normalize_target(text(object, source)),
+        key: text(property, source).to_string(),
+        form: AccessForm::Member,
+        span: node.range().into(),
+    })
+}
+
+/// The value of a string literal at `node`, or at the first argument inside
+/// it when it is an argument list or wrapper.
+fn string_argument(node: Node<'_>, source: &str) -> Option<String> {
+    let mut node = node;
+    loop {
+        if STRING_KINDS.contains(&node.kind()) {
+            let mut cursor = node.walk();
+            let interpolated = node
+                .named_children(&mut cursor)
+                .any(|c| c.kind().contains("interpolation") || c.kind().contains("substitution"));
+            return (!interpolated).then(|| string_value(node, source));
+        }
+        if !(ARGUMENT_KINDS.contains(&node.kind()) || WRAPPER_KINDS.contains(&node.kind())) {
+            return None;
+        }
+        node = node.named_child(0)?;
+    }
+}
+
+/// Callee or object text without whitespace, trailing generic arguments, or
+/// a turbofish.
+fn normalize_target(raw: &str) -> String {
+    let mut target: String = raw.chars().filter(|c| !c.is_whitespace()).collect();
+    if target.ends_with('>') {
+        if let Some(open) = target.find('<') {
+            target.truncate(open);
+        }
+    }
+    target.trim_end_matches("::").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::LanguageRegistry;
+
+    fn reads(path: &str, source: &str) -> Vec<(ReadKind, String)> {
+        let registry = LanguageRegistry::new();
+        let lang = registry.for_file(Path::new(path)).unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang.tree_sitter_language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        config_reads(&tree, source, &AccessPatterns::default())
+            .into_iter()
+            .map(|r| (r.kind, r.key))
+            .collect()
+    }
+
+    fn env(keys: &[&str]) -> Vec<(ReadKind, String)> {
+        keys.iter()
+            .map(|k| (ReadKind::EnvVar, (*k).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_environment_reads_across_languages() {
+        assert_eq!(
+            reads(
+                "src/main.rs",
+                "fn main() {\n    let a = std::env::var(\"DATABASE_URL\");\n    \
+                 let b = env::var_os(\"HOME\");\n    let c = env!(\"CARGO_PKG_NAME\");\n    \
+                 let d = std::env::var(format!(\"{}_X\", p));\n}\n",
+            ),
+            env(&["DATABASE_URL", "HOME", "CARGO_PKG_NAME"])
+        );
+        assert_eq!(
+            reads(
+                "app/settings.py",
+                "import os\nA = os.environ[\"SECRET_KEY\"]\nB = os.getenv('DEBUG', '0')\n\
+                 C = os.environ.get(\"REDIS_URL\")\nD = os.getenv(f\"{p}_X\")\n",
+            ),
+            env(&["SECRET_KEY", "DEBUG", "REDIS_URL"])
+        );
+        assert_eq!(
+            reads(
+                "web/server.ts",
+                "const port = process.env.PORT ?? 3000;\n\
+                 const key = process.env[\"API_KEY\"];\nconst m = import.meta.env.VITE_MODE;\n\
+                 process.env.hasOwnProperty('x');\n",
+            ),
+            env(&["PORT", "API_KEY", "VITE_MODE"])
+        );
+        assert_eq!(
+            reads(
+                "cmd/main.go",
+                "package main\nfunc main() {\n\tu := os.Getenv(\"DB_URL\")\n\t\
+                 v, ok := os.LookupEnv(\"TOKEN\")\n}\n",
+            ),
+            env(&["DB_URL", "TOKEN"])
+        );
+        assert_eq!(
+            reads(
+                "config/boot.rb",
+                "url = ENV[\"DATABASE_URL\"]\nkey = ENV.fetch(\"SECRET_KEY_BASE\")\n",
+            ),
+            env(&["DATABASE_URL", "SECRET_KEY_BASE"])
+        );
+        assert_eq!(
+            reads(
+                "src/App.java",
+                "class App { String u = System.getenv(\"JDBC_URL\"); }\n",
+            ),
+            env(&["JDBC_URL"])
+        );
+    }
+
+    #[test]
+    fn finds_config_key_reads() {
+        let config = |keys: &[&str]| -> Vec<(ReadKind, String)> {
+            keys.iter()
+                .map(|k| (ReadKind::ConfigKey, (*k).to_string()))
+                .collect()
+        };
+        assert_eq!(
+            reads(
+                "internal/db.go",
+                "package db\nfunc Open() {\n\th := viper.GetString(\"database.host\")\n\t\
+                 p := viper.GetInt(\"database.port\")\n}\n",
+            ),
+            config(&["database.host", "database.port"])
+        );
+        assert_eq!(
+            reads(
+                "src/Db.java",
+                "class Db {\n  @Value(\"${db.pool.size:10}\")\n  int size;\n}\n",
+            ),
+            config(&["db.pool.size"])
+        );
+        assert_eq!(
+            reads(
+                "Startup.cs",
+                "class S { void C() { var c = Configuration[\"Logging:LogLevel\"]; \
+                 var t = configuration.GetValue<int>(\"Cache:Ttl\"); } }\n",
+            ),
+            config(&["Logging.LogLevel", "Cache.Ttl"])
+        );
+        assert_eq!(
+            reads(
+                "app/views.py",
+                "x = app.config[\"SECRET\"]\ny = cfg.get(\"a\")\n"
+            ),
+            config(&["SECRET"])
+        );
+    }
+
+    #[test]
+    fn callee_patterns() {
+        assert!(callee_matches("std::env::var", "env::var"));
+        assert!(callee_matches("os.getenv", "getenv"));
+        assert!(callee_matches("viper.GetString", "viper.Get*"));
+        assert!(!callee_matches("viper.GetString.x", "viper.Get*"));
+        assert!(!callee_matches("mygetenv", "getenv"));
+        assert!(!callee_matches("self.app.env", "env::var"));
+        assert!(AccessPatterns::default().may_match("x = process.env.PORT"));
+        assert!(!AccessPatterns::default().may_match("fn main() {}"));
+    }
+}
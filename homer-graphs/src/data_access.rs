@@ -0,0 +1,609 @@
+// Database access: SQL held in string literals and ORM model declarations.
+
+use tree_sitter::{Node, Tree};
+
+use crate::TextRange;
+
+/// Longest literal kept, in characters.
+const MAX_SQL_CHARS: usize = 4000;
+
+/// Node kinds holding a string literal across the supported grammars.
+pub(crate) const STRING_KINDS: &[&str] = &[
+    "string",
+    "string_literal",
+    "raw_string_literal",
+    "interpreted_string_literal",
+    "template_string",
+    "verbatim_string_literal",
+    "interpolated_string_expression",
+    "encapsed_string",
+    "line_string_literal",
+    "multi_line_string_literal",
+    "multiline_string_literal",
+    "multiline_string",
+    "heredoc",
+    "heredoc_body",
+];
+
+/// Keywords that open a statement touching a table, each with the clauses
+/// that follow it in SQL; a literal needs one of them to count as SQL.
+const SQL_STATEMENTS: &[(&str, &[&str])] = &[
+    ("select", &[" from "]),
+    ("insert", &[" into "]),
+    ("update", &[" set "]),
+    ("delete", &[" from "]),
+    ("with", &[" as ("]),
+    ("create", &[" table ", " index ", " view "]),
+    ("alter", &[" table "]),
+    ("drop", &[" table ", " index ", " view "]),
+    ("merge", &[" into "]),
+    ("replace", &[" into "]),
+    ("truncate", &[" table "]),
+];
+
+/// Text an ORM model declaration contains in some form, for callers that
+/// want to skip files cheaply before parsing them.
+pub const ORM_MARKERS: &[&str] = &[
+    "table_name",
+    "Queryable",
+    "Insertable",
+    "Selectable",
+    "Identifiable",
+    "DeriveEntityModel",
+    "__tablename__",
+    "models.Model",
+    "SQLModel",
+    "ApplicationRecord",
+    "ActiveRecord::Base",
+    "@Entity",
+    "@Table",
+    "[Table",
+    "extends Model",
+    "gorm",
+    "TableName",
+];
+
+/// A string literal holding SQL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlLiteral {
+    /// Literal value with quotes and interpolations removed.
+    pub text: String,
+    /// Source location of the literal.
+    pub span: TextRange,
+}
+
+/// A class or struct mapped to a database table by an ORM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrmModel {
+    /// Simple name of the type.
+    pub name: String,
+    /// Table the model names explicitly, if any.
+    pub table: Option<String>,
+    /// Mapping framework (`diesel`, `sqlalchemy`, `activerecord`, ...).
+    pub framework: &'static str,
+    /// Source location of the declaration.
+    pub span: TextRange,
+}
+
+/// String literals in the file whose value looks like a SQL statement.
+///
+/// Literals are found by node kind and their value is the text of the
+/// content children, so quotes, prefixes, and interpolations drop out; a
+/// literal without content children is unquoted by hand.
+pub fn sql_literals(tree: &Tree, source: &str) -> Vec<SqlLiteral> {
+    let mut found = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.is_named() && STRING_KINDS.contains(&node.kind()) {
+            let value = string_value(node, source);
+            if looks_like_sql(&value) {
+                found.push(SqlLiteral {
+                    text: value.chars().take(MAX_SQL_CHARS).collect(),
+                    span: node.range().into(),
+                });
+            }
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    found.sort_by_key(|literal| literal.span.start_byte);
+    found
+}
+
+/// Whether a string opens with a SQL statement keyword followed by one of
+/// its clauses (`UPDATE ... SET`), which keeps prose like "Update the cache
+/// from disk" out.
+pub fn looks_like_sql(text: &str) -> bool {
+    let lower = text.trim_start().to_lowercase();
+    let verb = lower
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or("");
+    let Some((_, clauses)) = SQL_STATEMENTS.iter().find(|(v, _)| *v == verb) else {
+        return false;
+    };
+    let spaced = format!(
+        " {} ",
+        lower.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    clauses.iter().any(|clause| spaced.contains(clause))
+}
+
+/// Classes and structs the file maps to database tables, recognised from the
+/// marker each framework puts on the class: an attribute or annotation, a
+/// base class, a table-name assignment in the body, or for GORM a struct tag
+/// or a `TableName()` method. The table is reported only when the model
+/// names it; otherwise callers infer it from the type name.
+///
+/// `language` is the [`LanguageSupport::id`](crate::LanguageSupport::id) of
+/// the file's language.
+pub fn orm_models(tree: &Tree, source: &str, language: &str) -> Vec<OrmModel> {
+    let mut models = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let found = match (language, node.kind()) {
+            ("rust", "struct_item") => rust_model(node, source),
+            ("python", "class_definition") => python_model(node, source),
+            ("ruby", "class") => ruby_model(node, source),
+            ("java" | "kotlin", "class_declaration") => jpa_model(node, source),
+            ("csharp", "class_declaration") => ef_model(node, source),
+            ("php", "class_declaration") => eloquent_model(node, source),
+            ("typescript" | "javascript", "class_declaration" | "class") => {
+                typeorm_model(node, source)
+            }
+            ("go", "type_spec") => gorm_model(node, source, tree),
+            _ => None,
+        };
+        if let Some((table, framework)) = found {
+            if let Some(name) = type_name(node, source) {
+                models.push(OrmModel {
+                    name,
+                    table,
+                    framework,
+                    span: node.range().into(),
+                });
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    models.sort_by_key(|model| model.span.start_byte);
+    models
+}
+
+type Mapping = (Option<String>, &'static str);
+
+/// Diesel `#[diesel(table_name = users)]` or derives, `SeaORM`
+/// `#[sea_orm(table_name = "users")]`.
+fn rust_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    let mut attributes = Vec::new();
+    let mut prev = node.prev_named_sibling();
+    while let Some(sibling) = prev {
+        match sibling.kind() {
+            "attribute_item" => attributes.push(text(sibling, source)),
+            k if k.contains("comment") => {}
+            _ => break,
+        }
+        prev = sibling.prev_named_sibling();
+    }
+    for attribute in &attributes {
+        let Some(at) = attribute.find("table_name") else {
+            continue;
+        };
+        let value = attribute[at + "table_name".len()..]
+            .trim_start()
+            .strip_prefix('=')?
+            .split([',', ')', ']'])
+            .next()?
+            .trim()
+            .trim_matches('"');
+        let table = value.rsplit("::").next().unwrap_or(value).to_string();
+        let framework = if attribute.contains("sea_orm") {
+            "sea-orm"
+        } else {
+            "diesel"
+        };
+        return Some((Some(table), framework));
+    }
+    let derives = |names: &[&str]| {
+        attributes
+            .iter()
+            .filter(|a| a.contains("derive"))
+            .any(|a| names.iter().any(|n| a.contains(n)))
+    };
+    if derives(&["DeriveEntityModel"]) {
+        Some((None, "sea-orm"))
+    } else if derives(&["Queryable", "Insertable", "Selectable", "Identifiable"]) {
+        Some((None, "diesel"))
+    } else {
+        None
+    }
+}
+
+/// `SQLAlchemy` `__tablename__`, Django `models.Model` with an optional
+/// `Meta.db_table`, and `SQLModel` `table=True`.
+fn python_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    let bases = node
+        .child_by_field_name("superclasses")
+        .map_or("", |b| text(b, source));
+    let body = node.child_by_field_name("body")?;
+    if let Some(table) = assigned_string(body, source, "__tablename__") {
+        return Some((Some(table), "sqlalchemy"));
+    }
+    if bases.contains("models.Model") {
+        return Some((assigned_string(body, source, "db_table"), "django"));
+    }
+    if bases.contains("SQLModel") && bases.contains("table=True") {
+        return Some((None, "sqlmodel"));
+    }
+    None
+}
+
+/// Active Record models, with an optional `self.table_name = "..."`.
+fn ruby_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    let superclass = node
+        .child_by_field_name("superclass")
+        .map_or("", |s| text(s, source));
+    if !["ApplicationRecord", "ActiveRecord::Base"]
+        .iter()
+        .any(|base| superclass.contains(base))
+    {
+        return None;
+    }
+    let table = node
+        .child_by_field_name("body")
+        .and_then(|body| assigned_string(body, source, "table_name"));
+    Some((table, "activerecord"))
+}
+
+/// JPA `@Entity` and `@Table(name = "...")` on Java and Kotlin classes.
+fn jpa_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    let annotations = header_nodes(node, &["annotation", "marker_annotation"]);
+    let named = |name: &str| {
+        annotations.iter().find(|a| {
+            let raw = text(**a, source).trim_start_matches('@');
+            raw == name || raw.starts_with(&format!("{name}("))
+        })
+    };
+    let table = named("Table").and_then(|a| first_string(*a, source));
+    (table.is_some() || named("Entity").is_some()).then_some((table, "jpa"))
+}
+
+/// Entity Framework `[Table("...")]`.
+fn ef_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    header_nodes(node, &["attribute"])
+        .into_iter()
+        .find(|a| {
+            a.child_by_field_name("name")
+                .is_some_and(|n| text(n, source).rsplit('.').next() == Some("Table"))
+        })
+        .map(|a| (first_string(a, source), "ef"))
+}
+
+/// Eloquent models, with an optional `protected $table = '...'`.
+fn eloquent_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    let mut cursor = node.walk();
+    let base = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "base_clause")?;
+    let parent = text(base, source).trim_start_matches("extends").trim();
+    if parent != "Model" && !parent.ends_with("\\Model") {
+        return None;
+    }
+    let table = node
+        .child_by_field_name("body")
+        .and_then(|body| assigned_string(body, source, "table"));
+    Some((table, "eloquent"))
+}
+
+/// `TypeORM` `@Entity("users")` or `@Entity({ name: "users" })`.
+fn typeorm_model(node: Node<'_>, source: &str) -> Option<Mapping> {
+    let mut decorators = header_nodes(node, &["decorator"]);
+    // Decorators on an exported class attach to the export statement.
+    if let Some(export) = node.parent().filter(|p| p.kind() == "export_statement") {
+        decorators.extend(header_nodes(export, &["decorator"]));
+    }
+    let entity = decorators.into_iter().find(|d| {
+        let raw = text(*d, source).trim_start_matches('@');
+        raw == "Entity" || raw.starts_with("Entity(")
+    })?;
+    Some((first_string(entity, source), "typeorm"))
+}
+
+/// GORM structs: a `gorm` struct tag or an embedded `gorm.Model`, and the
+/// table a `TableName()` method on the type returns.
+fn gorm_model(node: Node<'_>, source: &str, tree: &Tree) -> Option<Mapping> {
+    let ty = node.child_by_field_name("type")?;
+    let name = type_name(node, source)?;
+    let table = table_name_method(tree.root_node(), source, &name);
+    let body = text(ty, source);
+    let tagged =
+        ty.kind() == "struct_type" && (body.contains("gorm:\"") || body.contains("gorm.Model"));
+    (tagged || table.is_some()).then_some((table, "gorm"))
+}
+
+/// The string a Go `func (T) TableName() string` method returns.
+fn table_name_method(root: Node<'_>, source: &str, type_name: &str) -> Option<String> {
+    let mut cursor = root.walk();
+    root.named_children(&mut cursor)
+        .filter(|c| c.kind() == "method_declaration")
+        .find(|method| {
+            let named = method
+                .child_by_field_name("name")
+                .is_some_and(|n| text(n, source) == "TableName");
+            let receiver = method
+                .child_by_field_name("receiver")
+                .map_or("", |r| text(r, source));
+            named
+                && receiver
+                    .trim_matches(|c| c == '(' || c == ')')
+                    .split_whitespace()
+                    .last()
+                    .is_some_and(|t| t.trim_start_matches('*') == type_name)
+        })
+        .and_then(|method| first_string(method.child_by_field_name("body")?, source))
+}
+
+/// Attribute, annotation, or decorator nodes of the given kinds attached to
+/// a declaration: direct children, children of its modifier lists, and
+/// preceding siblings.
+fn header_nodes<'a>(node: Node<'a>, kinds: &[&str]) -> Vec<Node<'a>> {
+    let mut found = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if kinds.contains(&child.kind()) {
+            found.push(child);
+        } else if child.kind().contains("modifier") || child.kind() == "attribute_list" {
+            let mut inner = child.walk();
+            found.extend(
+                child
+                    .named_children(&mut inner)
+                    .filter(|c| kinds.contains(&c.kind())),
+            );
+        }
+    }
+    let mut prev = node.prev_named_sibling();
+    while let Some(sibling) = prev.filter(|s| kinds.contains(&s.kind())) {
+        found.push(sibling);
+        prev = sibling.prev_named_sibling();
+    }
+    found
+}
+
+/// The string assigned to `name` anywhere in a class body, whether written
+/// `name = ...`, `self.name = ...`, or `$name = ...`.
+fn assigned_string(body: Node<'_>, source: &str, name: &str) -> Option<String> {
+    let mut stack = vec![body];
+    while let Some(node) = stack.pop() {
+        if matches!(
+            node.kind(),
+            "assignment" | "property_element" | "assignment_expression"
+        ) {
+            let target = node
+                .child_by_field_name("left")
+                .or_else(|| node.named_child(0))
+                .map_or("", |t| text(t, source));
+            let target = target.trim_start_matches("self.").trim_start_matches('$');
+            if target == name {
+                if let Some(value) = first_string(node, source) {
+                    return Some(value);
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    None
+}
+
+/// Value of the first string literal under `node`, in source order.
+fn first_string(node: Node<'_>, source: &str) -> Option<String> {
+    if STRING_KINDS.contains(&node.kind()) {
+        return Some(string_value(node, source));
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node<'_>> = node.named_children(&mut cursor).collect();
+    children.into_iter().find_map(|c| first_string(c, source))
+}
+
+/// Literal value: the text of its content children, or the unquoted text.
+pub(crate) fn string_value(node: Node<'_>, source: &str) -> String {
+    let mut parts = Vec::new();
+    let mut stack = vec![node];
+    while let Some(n) = stack.pop() {
+        let kind = n.kind();
+        if n != node && kind.contains("interpolation") {
+            continue;
+        }
+        if kind.ends_with("content") || kind == "string_fragment" || kind.ends_with("_str_text") {
+            parts.push((n.start_byte(), text(n, source)));
+            continue;
+        }
+        let mut cursor = n.walk();
+        stack.extend(n.named_children(&mut cursor));
+    }
+    if parts.is_empty() {
+        return unquote(text(node, source));
+    }
+    parts.sort_by_key(|(start, _)| *start);
+    parts.into_iter().map(|(_, part)| part).collect()
+}
+
+fn unquote(raw: &str) -> String {
+    raw.lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches("\\\\")
+                .trim_start_matches(|c: char| "@$rbfuRBFU#".contains(c))
+                .trim_matches(|c: char| "\"'`#".contains(c))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Name of a class, struct, or Go type spec.
+fn type_name(node: Node<'_>, source: &str) -> Option<String> {
+    let name = node.child_by_field_name("name").or_else(|| {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .find(|c| matches!(c.kind(), "type_identifier" | "identifier" | "constant"))
+    })?;
+    let name = text(name, source).rsplit("::").next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+pub(crate) fn text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+    source.get(node.byte_range()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::LanguageRegistry;
+
+    fn parse(path: &str, source: &str) -> (Tree, &'static str) {
+        let registry = LanguageRegistry::new();
+        let lang = registry.for_file(Path::new(path)).unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang.tree_sitter_language()).unwrap();
+        (parser.parse(source, None).unwrap(), lang.id())
+    }
+
+    fn sql_in(path: &str, source: &str) -> Vec<String> {
+        let (tree, _) = parse(path, source);
+        sql_literals(&tree, source)
+            .into_iter()
+            .map(|l| l.text)
+            .collect()
+    }
+
+    fn models_in(path: &str, source: &str) -> Vec<(String, Option<String>, &'static str)> {
+        let (tree, language) = parse(path, source);
+        orm_models(&tree, source, language)
+            .into_iter()
+            .map(|m| (m.name, m.table, m.framework))
+            .collect()
+    }
+
+    #[test]
+    fn finds_sql_in_string_literals() {
+        assert_eq!(
+            sql_in(
+                "src/db.rs",
+                "fn get(id: i32) {\n    let q = r#\"SELECT id FROM users\"#;\n    \
+                 sqlx::query!(\"UPDATE users SET email = $1\", id);\n    \
+                 log(\"Update the cache from disk\");\n}\n",
+            ),
+            vec!["SELECT id FROM users", "UPDATE users SET email = $1"]
+        );
+        assert_eq!(
+            sql_in(
+                "app/db.py",
+                "def f(x):\n    db.execute(f\"SELECT email FROM users WHERE id = {x}\")\n    \
+                 db.execute(\"\"\"DELETE FROM teams\"\"\")\n",
+            ),
+            vec!["SELECT email FROM users WHERE id = ", "DELETE FROM teams"]
+        );
+        assert_eq!(
+            sql_in(
+                "db.go",
+                "package db\nfunc f() { q(`SELECT email\n  FROM users`); q(\"select\") }\n",
+            ),
+            vec!["SELECT email\n  FROM users"]
+        );
+        assert_eq!(
+            sql_in(
+                "db.ts",
+                "const q = `SELECT * FROM users WHERE id = ${id}`;\nconst r = 'INSERT INTO teams (name) VALUES (?)';\n",
+            ),
+            vec![
+                "SELECT * FROM users WHERE id = ",
+                "INSERT INTO teams (name) VALUES (?)"
+            ]
+        );
+    }
+
+    #[test]
+    fn recognises_orm_models() {
+        assert_eq!(
+            models_in(
+                "src/models.rs",
+                "#[derive(Queryable)]\n#[diesel(table_name = crate::schema::users)]\npub struct User { id: i32 }\n\
+                 #[derive(Insertable)]\npub struct NewTeam { name: String }\n\
+                 #[derive(Debug)]\npub struct Plain;\n",
+            ),
+            vec![
+                ("User".to_string(), Some("users".to_string()), "diesel"),
+                ("NewTeam".to_string(), None, "diesel"),
+            ]
+        );
+        assert_eq!(
+            models_in(
+                "app/models.py",
+                "class User(Base):\n    __tablename__ = \"users\"\n\n\
+                 class Team(models.Model):\n    class Meta:\n        db_table = \"teams\"\n\n\
+                 class Helper:\n    pass\n",
+            ),
+            vec![
+                ("User".to_string(), Some("users".to_string()), "sqlalchemy"),
+                ("Team".to_string(), Some("teams".to_string()), "django"),
+            ]
+        );
+        assert_eq!(
+            models_in(
+                "app/models/user.rb",
+                "class User < ApplicationRecord\n  self.table_name = \"people\"\nend\nclass Form < Base\nend\n",
+            ),
+            vec![(
+                "User".to_string(),
+                Some("people".to_string()),
+                "activerecord"
+            )]
+        );
+        assert_eq!(
+            models_in(
+                "User.java",
+                "@Entity\n@Table(name = \"users\")\npublic class User {}\n@Entity\nclass Team {}\nclass Dto {}\n",
+            ),
+            vec![
+                ("User".to_string(), Some("users".to_string()), "jpa"),
+                ("Team".to_string(), None, "jpa"),
+            ]
+        );
+        assert_eq!(
+            models_in(
+                "models.ts",
+                "@Entity(\"users\")\nexport class User {}\n@Entity({ name: \"teams\" })\nclass Team {}\n",
+            ),
+            vec![
+                ("User".to_string(), Some("users".to_string()), "typeorm"),
+                ("Team".to_string(), Some("teams".to_string()), "typeorm"),
+            ]
+        );
+        assert_eq!(
+            models_in(
+                "models.go",
+                "package m\ntype User struct { Email string `gorm:\"column:email\"` }\n\
+                 func (User) TableName() string { return \"accounts\" }\ntype Point struct { X int }\n",
+            ),
+            vec![("User".to_string(), Some("accounts".to_string()), "gorm")]
+        );
+        assert_eq!(
+            models_in(
+                "User.cs",
+                "[Table(\"users\")]\npublic class User {}\npublic class Dto {}\n",
+            ),
+            vec![("User".to_string(), Some("users".to_string()), "ef")]
+        );
+        assert_eq!(
+            models_in(
+                "User.php",
+                "<?php\nclass User extends Model { protected $table = 'people'; }\nclass Job {}\n",
+            ),
+            vec![("User".to_string(), Some("people".to_string()), "eloquent")]
+        );
+    }
+}
@@ -143,6 +143,7 @@ mod tests {
                 end_col: 0,
             },
             doc_comment: None,
+            metrics: None,
         }
     }
 
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -111,6 +112,7 @@ fn walk_csharp_node(
                     kind: SymbolKind::Module,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::XmlDoc, "///"),
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -132,6 +134,7 @@ fn walk_csharp_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::XmlDoc, "///"),
+                    metrics: None,
                 });
             }
         }
@@ -146,6 +149,7 @@ fn walk_csharp_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::XmlDoc, "///"),
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -162,6 +166,7 @@ fn walk_csharp_node(
                     kind: SymbolKind::Field,
                     span: node_range(node),
                     doc_comment: None,
+                    metrics: None,
                 });
             }
         }
@@ -210,6 +215,7 @@ fn heuristic_type_decl(
         kind: SymbolKind::Type,
         span: node_range(node),
         doc_comment: extract_doc_comment_above(node, source, DocStyle::XmlDoc, "///"),
+        metrics: None,
     });
 
     context.push(name);
@@ -236,6 +242,7 @@ fn heuristic_field_decl(
                         kind: SymbolKind::Field,
                         span: node_range(child),
                         doc_comment: None,
+                        metrics: None,
                     });
                 }
             }
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -87,6 +88,7 @@ impl LanguageSupport for GoSupport {
     }
 }
 
+#[allow(clippy::too_many_lines)]
 fn walk_go_node(
     node: tree_sitter::Node<'_>,
     source: &str,
@@ -109,6 +111,7 @@ fn walk_go_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -136,6 +139,7 @@ fn walk_go_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::Godoc, "//"),
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -161,6 +165,7 @@ fn walk_go_node(
                                 DocStyle::Godoc,
                                 "//",
                             ),
+                            metrics: None,
                         });
                     }
                 }
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -108,6 +109,7 @@ fn walk_java_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: extract_block_doc_comment(node, source, DocStyle::Javadoc),
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -127,6 +129,7 @@ fn walk_java_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: extract_block_doc_comment(node, source, DocStyle::Javadoc),
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -147,6 +150,7 @@ fn walk_java_node(
                             kind: SymbolKind::Field,
                             span: node_range(child),
                             doc_comment: None,
+                            metrics: None,
                         });
                     }
                 }
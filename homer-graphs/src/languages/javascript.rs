@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -110,6 +111,7 @@ fn walk_js_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -128,6 +130,7 @@ fn walk_js_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: extract_block_doc_comment(node, source, DocStyle::Jsdoc),
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -147,6 +150,7 @@ fn walk_js_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: extract_block_doc_comment(node, source, DocStyle::Jsdoc),
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
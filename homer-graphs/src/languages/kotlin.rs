@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -111,6 +112,7 @@ fn walk_kotlin_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: extract_block_doc_comment(node, source, DocStyle::KDoc),
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -130,6 +132,7 @@ fn walk_kotlin_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: extract_block_doc_comment(node, source, DocStyle::KDoc),
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = find_child_by_kind(node, "function_body") {
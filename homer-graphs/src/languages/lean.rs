@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocCommentData, DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport,
@@ -123,6 +124,7 @@ fn walk_lean_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
             }
         }
@@ -138,6 +140,7 @@ fn walk_lean_node(
                     kind: SymbolKind::Constant,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
             }
         }
@@ -153,6 +156,7 @@ fn walk_lean_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
 
                 // Walk fields inside structure scope
@@ -174,6 +178,7 @@ fn walk_lean_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
 
                 // Walk constructors
@@ -194,6 +199,7 @@ fn walk_lean_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: None,
+                    metrics: Some(function_metrics(node)),
                 });
             }
         }
@@ -219,6 +225,7 @@ fn walk_lean_node(
                     kind: SymbolKind::Field,
                     span: node_range(node),
                     doc_comment: None,
+                    metrics: None,
                 });
             }
         }
@@ -268,6 +275,7 @@ fn walk_module_children(
                         kind: SymbolKind::Module,
                         span: node_range(child),
                         doc_comment: None,
+                        metrics: None,
                     });
 
                     context.push(name);
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -114,6 +115,7 @@ fn walk_php_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -137,6 +139,7 @@ fn walk_php_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -199,6 +202,7 @@ fn heuristic_type_decl(
         kind: SymbolKind::Type,
         span: node_range(node),
         doc_comment: doc,
+        metrics: None,
     });
 
     context.push(name);
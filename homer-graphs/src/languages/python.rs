@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::{FileScopeGraph, ScopeNodeId};
 use crate::{
     DocCommentData, DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport,
@@ -110,6 +111,7 @@ fn walk_python_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 // Walk body for calls
@@ -134,6 +136,7 @@ fn walk_python_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -113,6 +114,7 @@ fn walk_ruby_node(
                     kind: SymbolKind::Module,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -133,6 +135,7 @@ fn walk_ruby_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -153,6 +156,7 @@ fn walk_ruby_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -172,6 +176,7 @@ fn walk_ruby_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
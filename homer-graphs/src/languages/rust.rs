@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -110,6 +111,7 @@ fn walk_rust_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 // Walk body for calls
@@ -130,6 +132,7 @@ fn walk_rust_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
             }
         }
@@ -154,6 +157,7 @@ fn walk_rust_node(
                     kind: SymbolKind::Module,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::Rustdoc, "///"),
+                    metrics: None,
                 });
 
                 // Walk body with module context
@@ -174,6 +178,7 @@ fn walk_rust_node(
                     kind: SymbolKind::Constant,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::Rustdoc, "///"),
+                    metrics: None,
                 });
             }
         }
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -112,6 +113,7 @@ fn walk_swift_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = child_by_field(node, "body") {
@@ -129,6 +131,7 @@ fn walk_swift_node(
                 kind: SymbolKind::Function,
                 span: node_range(node),
                 doc_comment: doc,
+                metrics: Some(function_metrics(node)),
             });
 
             if let Some(body) = child_by_field(node, "body") {
@@ -147,6 +150,7 @@ fn walk_swift_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::SwiftDoc, "///"),
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -166,6 +170,7 @@ fn walk_swift_node(
                     kind: SymbolKind::Type,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::SwiftDoc, "///"),
+                    metrics: None,
                 });
 
                 context.push(name);
@@ -185,6 +190,7 @@ fn walk_swift_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: extract_doc_comment_above(node, source, DocStyle::SwiftDoc, "///"),
+                    metrics: Some(function_metrics(node)),
                 });
             }
         }
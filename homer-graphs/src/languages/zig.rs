@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::metrics::function_metrics;
 use crate::scope_graph::FileScopeGraph;
 use crate::{
     DocStyle, HeuristicCall, HeuristicDef, HeuristicGraph, HeuristicImport, ResolutionTier, Result,
@@ -110,6 +111,7 @@ fn walk_zig_node(
                     kind: SymbolKind::Function,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: Some(function_metrics(node)),
                 });
 
                 if let Some(body) = node.child_by_field_name("body") {
@@ -141,6 +143,7 @@ fn walk_zig_node(
                     kind,
                     span: node_range(node),
                     doc_comment: doc,
+                    metrics: None,
                 });
 
                 // Check for @import
@@ -165,6 +168,7 @@ fn walk_zig_node(
                 kind: SymbolKind::Function,
                 span: node_range(node),
                 doc_comment: None,
+                metrics: Some(function_metrics(node)),
             });
         }
         _ => {}
@@ -5,10 +5,16 @@
 //! imports, and scope graphs.
 
 pub mod call_graph;
+pub mod clones;
+pub mod config_access;
+pub mod data_access;
 pub mod diff;
 pub mod import_graph;
 pub mod languages;
+pub mod metrics;
 pub mod scope_graph;
+pub mod signature;
+pub mod testing;
 
 use std::path::PathBuf;
 
@@ -137,6 +143,22 @@ pub struct HeuristicDef {
     pub span: TextRange,
     /// Extracted doc comment, if present.
     pub doc_comment: Option<DocCommentData>,
+    /// Size and complexity metrics (functions only).
+    #[serde(default)]
+    pub metrics: Option<FunctionMetrics>,
+}
+
+/// Size and complexity metrics for a single function definition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionMetrics {
+    /// Cyclomatic complexity (1 + number of decision points).
+    pub cyclomatic: usize,
+    /// Deepest nesting of control-flow blocks inside the body.
+    pub max_nesting: usize,
+    /// Number of declared parameters, excluding receivers like `&self`.
+    pub parameters: usize,
+    /// Length of the definition in source lines.
+    pub lines: usize,
 }
 
 /// A call relationship discovered by heuristic extraction.
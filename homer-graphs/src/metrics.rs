@@ -0,0 +1,291 @@
+// Per-definition code metrics computed from the tree-sitter AST.
+//
+// The node-kind tables below cover the control-flow constructs of every
+// supported grammar. Kinds that a grammar does not use simply never match,
+// so one language-agnostic walk serves all languages.
+
+use tree_sitter::Node;
+
+use crate::FunctionMetrics;
+
+/// Named node kinds that add a decision point (cyclomatic +1).
+const DECISION_KINDS: &[&str] = &[
+    // Conditionals
+    "if_statement",
+    "if_expression",
+    "if_let_expression",
+    "elif_clause",
+    "else_if_clause",
+    "if",
+    "elsif",
+    "unless",
+    "if_modifier",
+    "unless_modifier",
+    "guard_statement",
+    "conditional_expression",
+    "ternary_expression",
+    "conditional",
+    // Loops
+    "for_statement",
+    "for_expression",
+    "for_in_statement",
+    "enhanced_for_statement",
+    "foreach_statement",
+    "for",
+    "while_statement",
+    "while_expression",
+    "while",
+    "until",
+    "while_modifier",
+    "until_modifier",
+    "do_statement",
+    "do_while_statement",
+    "repeat_while_statement",
+    // Switch / match arms
+    "match_arm",
+    "case_clause",
+    "expression_case",
+    "type_case",
+    "communication_case",
+    "switch_case",
+    "switch_section",
+    "switch_rule",
+    "switch_block_statement_group",
+    "case_statement",
+    "when",
+    "when_entry",
+    "switch_entry",
+    // Exception handlers
+    "catch_clause",
+    "catch_block",
+    "except_clause",
+    "rescue",
+];
+
+/// Anonymous operator tokens that short-circuit (cyclomatic +1).
+const BOOLEAN_OPERATORS: &[&str] = &["&&", "||", "and", "or"];
+
+/// Named node kinds that open a nested control-flow block.
+const NESTING_KINDS: &[&str] = &[
+    "if_statement",
+    "if_expression",
+    "if_let_expression",
+    "if",
+    "unless",
+    "guard_statement",
+    "for_statement",
+    "for_expression",
+    "for_in_statement",
+    "enhanced_for_statement",
+    "foreach_statement",
+    "for",
+    "while_statement",
+    "while_expression",
+    "while",
+    "until",
+    "loop_expression",
+    "do_statement",
+    "do_while_statement",
+    "repeat_while_statement",
+    "match_expression",
+    "match_statement",
+    "switch_statement",
+    "switch_expression",
+    "expression_switch_statement",
+    "type_switch_statement",
+    "select_statement",
+    "when_expression",
+    "case",
+    "try_statement",
+    "try_expression",
+    "begin",
+];
+
+/// Node kinds that hold a function's parameter list.
+const PARAMETER_LIST_KINDS: &[&str] = &[
+    "parameters",
+    "formal_parameters",
+    "parameter_list",
+    "function_value_parameters",
+    "method_parameters",
+];
+
+/// Compute metrics for a function-like definition node.
+///
+/// Cyclomatic complexity is `1 + decision points`, where decision points are
+/// branches, loops, match/switch arms, exception handlers, and short-circuit
+/// boolean operators. Nested closures count toward the enclosing function.
+pub fn function_metrics(node: Node<'_>) -> FunctionMetrics {
+    let mut decisions = 0;
+    let mut max_nesting = 0;
+
+    // Explicit stack: deeply nested ASTs must not overflow the call stack.
+    let mut stack = vec![(node, 0usize)];
+    while let Some((current, depth)) = stack.pop() {
+        let kind = current.kind();
+        let depth = if current.is_named() {
+            if DECISION_KINDS.contains(&kind) {
+                decisions += 1;
+            }
+            if current != node && NESTING_KINDS.contains(&kind) && !is_else_if(current) {
+                depth + 1
+            } else {
+                depth
+            }
+        } else {
+            if BOOLEAN_OPERATORS.contains(&kind) {
+                decisions += 1;
+            }
+            depth
+        };
+        max_nesting = max_nesting.max(depth);
+
+        let mut cursor = current.walk();
+        for child in current.children(&mut cursor) {
+            stack.push((child, depth));
+        }
+    }
+
+    FunctionMetrics {
+        cyclomatic: 1 + decisions,
+        max_nesting,
+        parameters: count_parameters(node),
+        lines: node.end_position().row - node.start_position().row + 1,
+    }
+}
+
+/// An `if` that continues an `else if` chain does not add nesting.
+fn is_else_if(node: Node<'_>) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    matches!(parent.kind(), "else_clause" | "else")
+        || parent
+            .child_by_field_name("alternative")
+            .is_some_and(|alt| alt == node)
+}
+
+fn count_parameters(node: Node<'_>) -> usize {
+    let mut cursor = node.walk();
+    let list = node.child_by_field_name("parameters").or_else(|| {
+        node.children(&mut cursor)
+            .find(|c| PARAMETER_LIST_KINDS.contains(&c.kind()))
+    });
+
+    // Grammars without a list node (e.g. Swift) attach parameters directly.
+    let parent = list.unwrap_or(node);
+    let mut cursor = parent.walk();
+    parent
+        .named_children(&mut cursor)
+        .filter(|c| {
+            !c.kind().contains("comment")
+                && c.kind() != "self_parameter"
+                && (list.is_some() || c.kind() == "parameter")
+        })
+        .map(|c| {
+            // Go groups `a, b int` into one declaration with several names.
+            let mut names = c.walk();
+            c.children_by_field_name("name", &mut names).count().max(1)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(language: &tree_sitter::Language, source: &str) -> tree_sitter::Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(language).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    fn first_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+        if node.kind() == kind {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        node.children(&mut cursor)
+            .find_map(|c| first_of_kind(c, kind))
+    }
+
+    #[test]
+    fn straight_line_function_has_complexity_one() {
+        let source = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+        let tree = parse(&tree_sitter_rust::LANGUAGE.into(), source);
+        let func = first_of_kind(tree.root_node(), "function_item").unwrap();
+
+        let m = function_metrics(func);
+        assert_eq!(m.cyclomatic, 1);
+        assert_eq!(m.max_nesting, 0);
+        assert_eq!(m.parameters, 2);
+        assert_eq!(m.lines, 3);
+    }
+
+    #[test]
+    fn counts_branches_arms_and_boolean_operators() {
+        let source = r#"
+impl Foo {
+    fn classify(&self, x: i32, strict: bool) -> &str {
+        if x > 0 && strict {
+            for _ in 0..x {
+                if x % 2 == 0 {
+                    return "even";
+                }
+            }
+        } else if x < 0 || !strict {
+            return "neg";
+        }
+        match x {
+            0 => "zero",
+            _ => "other",
+        }
+    }
+}
+"#;
+        let tree = parse(&tree_sitter_rust::LANGUAGE.into(), source);
+        let func = first_of_kind(tree.root_node(), "function_item").unwrap();
+
+        let m = function_metrics(func);
+        // 1 + if + && + for + if + else-if + || + 2 arms
+        assert_eq!(m.cyclomatic, 9);
+        // if > for > if; the else-if chain does not nest
+        assert_eq!(m.max_nesting, 3);
+        // `&self` is a receiver, not a parameter
+        assert_eq!(m.parameters, 2);
+    }
+
+    #[test]
+    fn python_elif_and_boolean_operators() {
+        let source = "\
+def check(a, b, c):
+    if a and b:
+        return 1
+    elif c:
+        return 2
+    try:
+        pass
+    except ValueError:
+        pass
+    return 0
+";
+        let tree = parse(&tree_sitter_python::LANGUAGE.into(), source);
+        let func = first_of_kind(tree.root_node(), "function_definition").unwrap();
+
+        let m = function_metrics(func);
+        // 1 + if + and + elif + except
+        assert_eq!(m.cyclomatic, 5);
+        assert_eq!(m.max_nesting, 1);
+        assert_eq!(m.parameters, 3);
+        assert_eq!(m.lines, 10);
+    }
+
+    #[test]
+    fn go_grouped_parameters_count_each_name() {
+        let source = "package p\n\nfunc f(a, b int, c string) {}\n";
+        let tree = parse(&tree_sitter_go::LANGUAGE.into(), source);
+        let func = first_of_kind(tree.root_node(), "function_declaration").unwrap();
+
+        assert_eq!(function_metrics(func).parameters, 3);
+    }
+}
@@ -0,0 +1,97 @@
+// Definition signatures: the declaration text that precedes the body.
+//
+// Grammars name the body differently (`body` field, `block`, `class_body`,
+// `field_declaration_list`, ...), so the body is the `body` field when the
+// grammar has one and otherwise the first child whose kind looks like a body.
+// Definitions without a body (trait methods, type aliases) use their whole
+// text. Whitespace is collapsed so formatting changes do not count as
+// signature changes.
+
+use tree_sitter::{Node, Tree};
+
+use crate::TextRange;
+
+/// Longest signature kept, in characters.
+const MAX_SIGNATURE_CHARS: usize = 400;
+
+/// Signature of the definition spanning `span`, if the tree has a node there.
+pub fn signature_at(tree: &Tree, source: &str, span: &TextRange) -> Option<String> {
+    let node = tree
+        .root_node()
+        .descendant_for_byte_range(span.start_byte, span.end_byte)?;
+    signature(node, source)
+}
+
+/// Declaration text of a definition node, without its body.
+pub fn signature(node: Node<'_>, source: &str) -> Option<String> {
+    let end = body(node).map_or(node.end_byte(), |b| b.start_byte());
+    let text = source.get(node.start_byte()..end)?;
+    let mut sig = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    while sig.ends_with([':', '=', '{']) {
+        sig.pop();
+        sig.truncate(sig.trim_end().len());
+    }
+    if sig.is_empty() {
+        return None;
+    }
+    if sig.chars().count() > MAX_SIGNATURE_CHARS {
+        sig = sig.chars().take(MAX_SIGNATURE_CHARS).collect();
+    }
+    Some(sig)
+}
+
+fn body(node: Node<'_>) -> Option<Node<'_>> {
+    if let Some(body) = node.child_by_field_name("body") {
+        return Some(body);
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).find(|c| {
+        let kind = c.kind();
+        kind.ends_with("body")
+            || kind == "block"
+            || kind == "declaration_list"
+            || kind == "field_declaration_list"
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_signature(language: &tree_sitter::Language, source: &str) -> Option<String> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(language).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        signature(tree.root_node().named_child(0).unwrap(), source)
+    }
+
+    #[test]
+    fn rust_signatures_stop_at_the_body() {
+        let rust = tree_sitter_rust::LANGUAGE.into();
+        assert_eq!(
+            first_signature(
+                &rust,
+                "pub fn parse(\n    input: &str,\n    strict: bool,\n) -> Result<Ast> {\n    todo!()\n}"
+            )
+            .as_deref(),
+            Some("pub fn parse( input: &str, strict: bool, ) -> Result<Ast>")
+        );
+        assert_eq!(
+            first_signature(&rust, "pub struct Config<T> {\n    pub value: T,\n}").as_deref(),
+            Some("pub struct Config<T>")
+        );
+        assert_eq!(
+            first_signature(&rust, "pub type Id = u64;").as_deref(),
+            Some("pub type Id = u64;")
+        );
+    }
+
+    #[test]
+    fn python_signatures_drop_the_colon() {
+        let python = tree_sitter_python::LANGUAGE.into();
+        assert_eq!(
+            first_signature(&python, "def fetch(url, retries=3):\n    return url\n").as_deref(),
+            Some("def fetch(url, retries=3)")
+        );
+    }
+}
@@ -0,0 +1,548 @@
+// Test identification.
+//
+// Most frameworks mark tests on the definition itself — an attribute
+// (`#[test]`, `[Fact]`), an annotation (`@Test`), or a naming rule inside
+// test files (`test_*`, `TestXxx`). Those are recognised per definition with
+// `test_framework_at`. JavaScript/TypeScript and RSpec declare tests as calls
+// taking a callback (`it("adds", () => ...)`, `it "adds" do ... end`), which
+// have no named definition; `test_blocks` finds those along with the names
+// they call.
+
+use std::path::Path;
+
+use tree_sitter::{Node, Tree};
+
+use crate::{HeuristicDef, TextRange};
+
+/// Most callee names kept per test block.
+const MAX_BLOCK_CALLS: usize = 200;
+
+/// Test framework of a definition, if the definition is a test.
+///
+/// `language` is the [`LanguageSupport::id`](crate::LanguageSupport::id) of
+/// the file's language.
+pub fn test_framework_at(
+    tree: &Tree,
+    source: &str,
+    language: &str,
+    path: &Path,
+    def: &HeuristicDef,
+) -> Option<&'static str> {
+    let node = tree
+        .root_node()
+        .descendant_for_byte_range(def.span.start_byte, def.span.end_byte)?;
+    let name = def.name.as_str();
+    let in_test_file = is_test_path(path);
+    let marked = |names: &[&str]| {
+        annotations(node, source)
+            .iter()
+            .any(|a| names.contains(&a.as_str()))
+    };
+
+    match language {
+        "rust" => marked(&["test", "rstest", "test_case", "proptest"]).then_some("rust"),
+        "java" | "kotlin" => marked(&[
+            "Test",
+            "ParameterizedTest",
+            "RepeatedTest",
+            "TestFactory",
+            "TestTemplate",
+        ])
+        .then_some("junit"),
+        "csharp" => {
+            let found = annotations(node, source);
+            let has = |names: &[&str]| found.iter().any(|a| names.contains(&a.as_str()));
+            if has(&["Fact", "Theory"]) {
+                Some("xunit")
+            } else if has(&["Test", "TestCase", "TestCaseSource"]) {
+                Some("nunit")
+            } else if has(&["TestMethod", "DataTestMethod"]) {
+                Some("mstest")
+            } else {
+                None
+            }
+        }
+        "swift" => {
+            if marked(&["Test"]) {
+                Some("swift-testing")
+            } else {
+                (in_test_file && name.starts_with("test")).then_some("xctest")
+            }
+        }
+        "php" => {
+            let documented = preceding_comment(node, source).is_some_and(|c| c.contains("@test"));
+            (marked(&["Test"]) || documented || (in_test_file && name.starts_with("test")))
+                .then_some("phpunit")
+        }
+        "go" => {
+            let file = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+            let test_name = ["Test", "Fuzz"].iter().any(|prefix| {
+                name.strip_prefix(prefix)
+                    .is_some_and(|rest| !rest.starts_with(|c: char| c.is_lowercase()))
+            });
+            (file.ends_with("_test.go") && test_name).then_some("go")
+        }
+        "python" => {
+            if !name.starts_with("test") {
+                return None;
+            }
+            match enclosing_class(node, source) {
+                Some((_, bases)) if bases.contains("TestCase") => Some("unittest"),
+                Some((class, _)) if class.starts_with("Test") => Some("pytest"),
+                Some(_) => None,
+                None => in_test_file.then_some("pytest"),
+            }
+        }
+        "ruby" => (in_test_file && name.starts_with("test_")).then_some("minitest"),
+        _ => None,
+    }
+}
+
+/// Names called inside Rust macro arguments within `span`.
+///
+/// Tree-sitter leaves macro arguments as unparsed token trees, so calls in
+/// `assert_eq!(add(1, 2), 3)` are missing from the heuristic call list. An
+/// identifier followed by a parenthesized token tree is taken as a call.
+/// Other languages have no such gap and return nothing.
+pub fn macro_calls(tree: &Tree, source: &str, language: &str, span: &TextRange) -> Vec<String> {
+    if language != "rust" {
+        return Vec::new();
+    }
+    let Some(root) = tree
+        .root_node()
+        .descendant_for_byte_range(span.start_byte, span.end_byte)
+    else {
+        return Vec::new();
+    };
+    let mut calls = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "token_tree" {
+            let mut cursor = node.walk();
+            let children: Vec<Node<'_>> = node.children(&mut cursor).collect();
+            for pair in children.windows(2) {
+                let is_call = pair[0].kind() == "identifier"
+                    && pair[1].kind() == "token_tree"
+                    && text(pair[1], source).starts_with('(');
+                let name = text(pair[0], source).to_string();
+                if is_call && !calls.contains(&name) && calls.len() < MAX_BLOCK_CALLS {
+                    calls.push(name);
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    calls
+}
+
+/// A test declared as a call with a callback rather than as a definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestBlock {
+    /// Enclosing suite names and the test name, joined with ` > `.
+    pub qualified_name: String,
+    /// Framework family (`js` for Jest/Vitest/Mocha style, `rspec`).
+    pub framework: &'static str,
+    /// Source location of the whole call.
+    pub span: TextRange,
+    /// Names called inside the test body, as written, without duplicates.
+    pub calls: Vec<String>,
+}
+
+/// Call-style tests: `it`/`test` inside `describe` in JavaScript and
+/// TypeScript, and `it`/`specify` inside `describe`/`context` in `RSpec`.
+pub fn test_blocks(tree: &Tree, source: &str, language: &str) -> Vec<TestBlock> {
+    let style = match language {
+        "javascript" | "typescript" => &JS_STYLE,
+        "ruby" => &RSPEC_STYLE,
+        _ => return Vec::new(),
+    };
+    let mut blocks = Vec::new();
+    let mut suites = Vec::new();
+    collect_blocks(tree.root_node(), source, style, &mut suites, &mut blocks);
+    blocks
+}
+
+struct BlockStyle {
+    framework: &'static str,
+    call_kind: &'static str,
+    /// Field holding the called name.
+    callee_field: &'static str,
+    suites: &'static [&'static str],
+    tests: &'static [&'static str],
+}
+
+const JS_STYLE: BlockStyle = BlockStyle {
+    framework: "js",
+    call_kind: "call_expression",
+    callee_field: "function",
+    suites: &["describe", "describe.only", "context", "suite"],
+    tests: &["it", "it.only", "test", "test.only", "specify"],
+};
+
+const RSPEC_STYLE: BlockStyle = BlockStyle {
+    framework: "rspec",
+    call_kind: "call",
+    callee_field: "method",
+    suites: &["describe", "context", "feature", "shared_examples"],
+    tests: &["it", "specify", "example", "scenario", "its"],
+};
+
+fn collect_blocks(
+    node: Node<'_>,
+    source: &str,
+    style: &BlockStyle,
+    suites: &mut Vec<String>,
+    blocks: &mut Vec<TestBlock>,
+) {
+    if node.kind() == style.call_kind {
+        let callee = node
+            .child_by_field_name(style.callee_field)
+            .map(|c| text(c, source));
+        if let Some(callee) = callee {
+            let label = || {
+                first_argument(node, source)
+                    .unwrap_or_else(|| format!("{callee} (line {})", node.start_position().row + 1))
+            };
+            if style.tests.contains(&callee) {
+                let mut path = suites.clone();
+                path.push(label());
+                blocks.push(TestBlock {
+                    qualified_name: path.join(" > "),
+                    framework: style.framework,
+                    span: node.range().into(),
+                    calls: callees(node, source, style),
+                });
+                return;
+            }
+            if style.suites.contains(&callee) {
+                suites.push(label());
+                recurse_blocks(node, source, style, suites, blocks);
+                suites.pop();
+                return;
+            }
+        }
+    }
+    recurse_blocks(node, source, style, suites, blocks);
+}
+
+fn recurse_blocks(
+    node: Node<'_>,
+    source: &str,
+    style: &BlockStyle,
+    suites: &mut Vec<String>,
+    blocks: &mut Vec<TestBlock>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_blocks(child, source, style, suites, blocks);
+    }
+}
+
+/// The test or suite name: the first argument, unquoted.
+fn first_argument(call: Node<'_>, source: &str) -> Option<String> {
+    let args = call.child_by_field_name("arguments")?;
+    let mut cursor = args.walk();
+    let first = args.named_children(&mut cursor).next()?;
+    let raw = text(first, source);
+    let name = raw
+        .trim_matches(|c| matches!(c, '"' | '\'' | '`'))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!name.is_empty()).then_some(name)
+}
+
+/// Names called anywhere inside a test block, excluding the block's own call.
+fn callees(block: Node<'_>, source: &str, style: &BlockStyle) -> Vec<String> {
+    let mut calls = Vec::new();
+    let mut stack = vec![block];
+    while let Some(node) = stack.pop() {
+        if node.kind() == style.call_kind && node != block {
+            if let Some(callee) = node.child_by_field_name(style.callee_field) {
+                let name = text(callee, source).to_string();
+                if !calls.contains(&name) && calls.len() < MAX_BLOCK_CALLS {
+                    calls.push(name);
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    calls
+}
+
+/// Attribute, annotation, and decorator names on a definition, without
+/// arguments or path prefixes: `#[tokio::test]` → `test`, `@Test` → `Test`,
+/// `[TestCase(1)]` → `TestCase`.
+fn annotations(def: Node<'_>, source: &str) -> Vec<String> {
+    let mut raw = Vec::new();
+
+    // Rust attributes and PHP attributes precede the item as siblings.
+    let mut prev = def.prev_named_sibling();
+    while let Some(sibling) = prev {
+        match sibling.kind() {
+            "attribute_item" | "attribute_list" => raw.push(text(sibling, source)),
+            k if k.contains("comment") => {}
+            _ => break,
+        }
+        prev = sibling.prev_named_sibling();
+    }
+    // Python decorators wrap the definition.
+    if let Some(parent) = def.parent().filter(|p| p.kind() == "decorated_definition") {
+        let mut cursor = parent.walk();
+        raw.extend(
+            parent
+                .named_children(&mut cursor)
+                .filter(|c| c.kind() == "decorator")
+                .map(|c| text(c, source)),
+        );
+    }
+    // Annotations, attributes, and modifiers inside the declaration header.
+    collect_annotations(def, source, 0, &mut raw);
+
+    raw.iter().flat_map(|r| annotation_names(r)).collect()
+}
+
+fn collect_annotations<'a>(node: Node<'_>, source: &'a str, depth: usize, out: &mut Vec<&'a str>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let kind = child.kind();
+        if kind.ends_with("body") || kind == "block" || kind.contains("parameter") {
+            continue;
+        }
+        if matches!(
+            kind,
+            "marker_annotation" | "annotation" | "attribute" | "attribute_list" | "attribute_item"
+        ) {
+            out.push(text(child, source));
+        } else if depth < 2 && (kind.contains("modifier") || kind.contains("attribute")) {
+            collect_annotations(child, source, depth + 1, out);
+        }
+    }
+}
+
+fn annotation_names(raw: &str) -> Vec<String> {
+    let inner = raw
+        .trim()
+        .trim_start_matches("#[")
+        .trim_start_matches(['@', '['])
+        .trim_end_matches(']');
+    split_top_level(inner)
+        .into_iter()
+        .filter_map(|part| {
+            let path = part.split(['(', ' ', '\n']).next()?.trim();
+            let name = path
+                .rsplit(['.', ':', '\\'])
+                .next()?
+                .trim_start_matches('@');
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Split `A, B(x, y)` on commas outside parentheses.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+/// The doc comment immediately above a definition.
+fn preceding_comment<'a>(def: Node<'_>, source: &'a str) -> Option<&'a str> {
+    let mut prev = def.prev_named_sibling();
+    while let Some(sibling) = prev {
+        if sibling.kind().contains("comment") {
+            return Some(text(sibling, source));
+        }
+        if !sibling.kind().contains("attribute") {
+            return None;
+        }
+        prev = sibling.prev_named_sibling();
+    }
+    None
+}
+
+/// Name and base-class list of the Python class enclosing a definition.
+fn enclosing_class<'a>(def: Node<'_>, source: &'a str) -> Option<(&'a str, &'a str)> {
+    let mut node = def.parent();
+    while let Some(n) = node {
+        if n.kind() == "class_definition" {
+            let name = n
+                .child_by_field_name("name")
+                .map_or("", |c| text(c, source));
+            let bases = n
+                .child_by_field_name("superclasses")
+                .map_or("", |c| text(c, source));
+            return Some((name, bases));
+        }
+        if n.kind() == "function_definition" {
+            return None;
+        }
+        node = n.parent();
+    }
+    None
+}
+
+/// Whether a path looks like a test file or lives in a test directory.
+fn is_test_path(path: &Path) -> bool {
+    let in_test_dir = path.components().any(|c| {
+        matches!(
+            c.as_os_str().to_str(),
+            Some("test" | "tests" | "spec" | "__tests__" | "Tests")
+        )
+    });
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    in_test_dir
+        || stem.starts_with("test_")
+        || [
+            "_test", "_tests", "Test", "Tests", "_spec", ".test", ".spec",
+        ]
+        .iter()
+        .any(|suffix| stem.ends_with(suffix))
+}
+
+fn text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+    source.get(node.byte_range()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LanguageRegistry;
+
+    fn tests_in(path: &str, source: &str) -> Vec<(String, &'static str)> {
+        let registry = LanguageRegistry::new();
+        let path = Path::new(path);
+        let lang = registry.for_file(path).unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang.tree_sitter_language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let graph = lang.extract_heuristic(&tree, source, path).unwrap();
+        graph
+            .definitions
+            .iter()
+            .filter_map(|def| {
+                test_framework_at(&tree, source, lang.id(), path, def)
+                    .map(|f| (def.qualified_name.clone(), f))
+            })
+            .collect()
+    }
+
+    fn names<'a>(found: &'a [(String, &'static str)]) -> Vec<&'a str> {
+        found.iter().map(|(n, _)| n.as_str()).collect()
+    }
+
+    #[test]
+    fn detects_attribute_and_annotation_tests() {
+        let rust = tests_in(
+            "src/lib.rs",
+            "fn add() {}\n\
+             #[cfg(test)]\nmod tests {\n\
+                 #[test]\n    fn adds() { add(); }\n\
+                 /// doc\n    #[tokio::test]\n    async fn adds_async() {}\n\
+                 fn helper() {}\n}\n",
+        );
+        assert_eq!(names(&rust), vec!["tests::adds", "tests::adds_async"]);
+
+        let source = "#[test]\nfn adds() { assert_eq!(calc::add(1, two()), 3); }\n";
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let span = TextRange::from(tree.root_node().range());
+        assert_eq!(
+            macro_calls(&tree, source, "rust", &span),
+            vec!["add", "two"]
+        );
+
+        let java = tests_in(
+            "src/test/java/CalcTest.java",
+            "class CalcTest {\n  @Test\n  void adds() {}\n  @ParameterizedTest @ValueSource(ints = {1})\n  void many(int x) {}\n  void helper() {}\n}\n",
+        );
+        assert_eq!(java.len(), 2, "{java:?}");
+        assert!(java.iter().all(|(_, f)| *f == "junit"));
+
+        let csharp = tests_in(
+            "CalcTests.cs",
+            "class CalcTests {\n  [Fact]\n  public void Adds() {}\n  [Theory, InlineData(1)]\n  public void Many(int x) {}\n  public void Helper() {}\n}\n",
+        );
+        assert_eq!(csharp.len(), 2, "{csharp:?}");
+        assert!(csharp.iter().all(|(_, f)| *f == "xunit"));
+    }
+
+    #[test]
+    fn detects_naming_convention_tests() {
+        let go = tests_in(
+            "calc/calc_test.go",
+            "package calc\nfunc TestAdd(t *testing.T) { Add(1, 2) }\nfunc Testify() {}\nfunc helper() {}\n",
+        );
+        assert_eq!(names(&go), vec!["TestAdd"]);
+
+        let python = tests_in(
+            "tests/test_calc.py",
+            "def test_add():\n    add(1, 2)\n\ndef helper():\n    pass\n\n\
+             class CalcTest(unittest.TestCase):\n    def test_sub(self):\n        pass\n",
+        );
+        assert_eq!(
+            python,
+            vec![
+                ("test_add".to_string(), "pytest"),
+                ("CalcTest.test_sub".to_string(), "unittest"),
+            ]
+        );
+
+        let not_test = tests_in("src/calc.py", "def test_mode():\n    pass\n");
+        assert!(
+            not_test.is_empty(),
+            "test_* outside test files is not a test"
+        );
+    }
+
+    #[test]
+    fn finds_js_and_rspec_test_blocks() {
+        let source = r#"
+import { add } from "./calc";
+describe("calc", () => {
+  beforeEach(() => reset());
+  it("adds numbers", () => {
+    expect(add(1, 2)).toBe(3);
+  });
+  test(`handles ${"zero"}`, () => { add(0, 0); });
+});
+"#;
+        let registry = LanguageRegistry::new();
+        let lang = registry.for_file(Path::new("calc.test.js")).unwrap();
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&lang.tree_sitter_language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let blocks = test_blocks(&tree, source, "javascript");
+        assert_eq!(blocks.len(), 2, "{blocks:?}");
+        assert_eq!(blocks[0].qualified_name, "calc > adds numbers");
+        assert!(blocks[0].calls.contains(&"add".to_string()));
+        assert!(!blocks[0].calls.contains(&"reset".to_string()));
+
+        let source = "RSpec.describe Calc do\n  context \"with ints\" do\n    it \"adds\" do\n      Calc.add(1, 2)\n    end\n  end\nend\n";
+        let lang = registry.for_file(Path::new("spec/calc_spec.rb")).unwrap();
+        parser.set_language(&lang.tree_sitter_language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let blocks = test_blocks(&tree, source, "ruby");
+        assert_eq!(blocks.len(), 1, "{blocks:?}");
+        assert_eq!(blocks[0].qualified_name, "Calc > with ints > adds");
+        assert_eq!(blocks[0].framework, "rspec");
+        assert!(blocks[0].calls.contains(&"add".to_string()));
+    }
+}
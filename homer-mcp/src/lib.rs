@@ -7,6 +7,12 @@
 //   homer_diff        — impact analysis for a set of changed files
 //   homer_co_changes  — files that frequently change together
 //   homer_conventions — project coding conventions
+//   homer_context     — token-budgeted context ranked around seed entities
+//   homer_impact      — transitive blast radius of changing one entity
+//   homer_path        — shortest dependency paths between two entities
+//   homer_experts     — active contributors to ask about a path
+//   homer_api_check   — whether editing an entity touches the public API
+//   homer_flags       — feature flag inventory and cleanup candidates
 
 use std::future::Future;
 use std::path::PathBuf;
@@ -37,12 +43,14 @@ pub struct QueryParams {
     /// Entity name or substring to search for
     #[schemars(description = "Entity name or substring to search for")]
     pub entity: String,
-    /// Kind filter: function, type, file, module, or all
-    #[schemars(description = "Kind filter: function, type, file, module (omit for all)")]
+    /// Kind filter: function, type, file, module, table, column, endpoint, `ci_job`, `infra_resource`, test, or all
+    #[schemars(
+        description = "Kind filter: function, type, file, module, table, column, endpoint, ci_job, infra_resource, test (omit for all)"
+    )]
     pub kind: Option<String>,
     /// Sections to include in the response
     #[schemars(
-        description = "Sections to include: summary, metrics, callers, callees, history, co_changes (omit for default)"
+        description = "Sections to include: summary, metrics, callers, callees, history, co_changes, schema, config (omit for default)"
     )]
     pub include: Option<Vec<String>>,
 }
@@ -101,6 +109,92 @@ pub struct DiffParams {
     pub paths: Vec<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ContextParams {
+    /// Seed entities: file paths, function names, or free-text queries
+    #[schemars(
+        description = "Seed entities: file paths, function names, or free-text queries describing the task"
+    )]
+    pub seeds: Vec<String>,
+    /// Token budget for the returned context (default: 8000)
+    #[schemars(description = "Token budget for the returned context (default: 8000)")]
+    pub budget: Option<u64>,
+    /// Maximum number of entities to return (default: 50)
+    #[schemars(description = "Maximum number of entities to return (default: 50)")]
+    pub top: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ImpactParams {
+    /// Entity to change: file path, function name, or qualified name
+    #[schemars(description = "Entity to change: file path, function name, or qualified name")]
+    pub entity: String,
+    /// Minimum path probability to report (default: 0.1)
+    #[schemars(
+        description = "Minimum probability (product of edge confidences) for an entity to be reported (default: 0.1)"
+    )]
+    pub min_probability: Option<f64>,
+    /// Maximum number of reverse-dependency hops (default: 10)
+    #[schemars(description = "Maximum number of reverse-dependency hops (default: 10)")]
+    pub max_depth: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PathParams {
+    /// Starting entity: file path, function name, or qualified name
+    #[schemars(description = "Starting entity: file path, function name, or qualified name")]
+    pub from: String,
+    /// Destination entity: file path, function name, or qualified name
+    #[schemars(description = "Destination entity: file path, function name, or qualified name")]
+    pub to: String,
+    /// Number of shortest paths to return (default: 3)
+    #[schemars(description = "Number of shortest paths to return (default: 3)")]
+    pub k: Option<u32>,
+    /// Ignore edges below this confidence (default: 0.0)
+    #[schemars(
+        description = "Ignore edges below this confidence, e.g. 0.8 to skip heuristic name-matched calls (default: 0.0)"
+    )]
+    pub min_confidence: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ExpertsParams {
+    /// File or directory path (empty for the whole repository)
+    #[schemars(
+        description = "File path or directory prefix, relative to the repo root (empty for the whole repository)"
+    )]
+    pub path: String,
+    /// Maximum number of experts to return (default: 5)
+    #[schemars(description = "Maximum number of experts to return (default: 5)")]
+    pub top: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ApiCheckParams {
+    /// File, function, or type about to be modified
+    #[schemars(
+        description = "File path, function, or type about to be modified (exact or partial name)"
+    )]
+    pub entity: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FlagsParams {
+    /// Only return cleanup candidates (default: false)
+    #[schemars(
+        description = "Only return cleanup candidates: old flags checked by high-salience code (default: false)"
+    )]
+    pub stale_only: Option<bool>,
+    /// Days since a flag appeared before it is a cleanup candidate (default: 90)
+    #[schemars(
+        description = "Days since a flag appeared before it is a cleanup candidate (default: 90)"
+    )]
+    pub min_age_days: Option<u32>,
+    /// Maximum number of flags to return (default: 50)
+    #[schemars(description = "Maximum number of flags to return (default: 50)")]
+    pub top: Option<u32>,
+}
+
 // ── Server struct ─────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -136,7 +230,7 @@ impl HomerMcpServer {
 impl HomerMcpServer {
     #[tool(
         name = "homer_query",
-        description = "Look up entities (functions, types, files, modules) by name in the Homer knowledge base. Returns metadata and salience data."
+        description = "Look up entities (functions, types, files, modules, database tables and columns) by name in the Homer knowledge base. Returns metadata and salience data, and with include=schema the tables and columns code accesses or the code accessing a table."
     )]
     async fn query(&self, Parameters(params): Parameters<QueryParams>) -> String {
         match self.do_query(params).await {
@@ -191,7 +285,7 @@ impl HomerMcpServer {
 
     #[tool(
         name = "homer_diff",
-        description = "Assess architectural impact of changed files. Returns salience, bus factor risk, affected communities and modules. Use before merging to understand risk."
+        description = "Assess architectural impact of changed files. Returns salience, bus factor risk, affected communities and modules, the tests to run, and for changed API contracts (protobuf, OpenAPI, GraphQL) the endpoints and types reached and the services whose code is bound to them. Use before merging to understand risk."
     )]
     async fn diff(&self, Parameters(params): Parameters<DiffParams>) -> String {
         match self.do_diff(params).await {
@@ -199,6 +293,72 @@ impl HomerMcpServer {
             Err(e) => format!("Error: {e}"),
         }
     }
+
+    #[tool(
+        name = "homer_context",
+        description = "Rank the files, functions and types most relevant to a task, starting from seed entities or a free-text description. Returns a token-budgeted reading list. Use at the start of a task to decide what to read."
+    )]
+    async fn context(&self, Parameters(params): Parameters<ContextParams>) -> String {
+        match self.do_context(params).await {
+            Ok(s) => s,
+            Err(e) => format!("Error: {e}"),
+        }
+    }
+
+    #[tool(
+        name = "homer_impact",
+        description = "Find everything that transitively depends on an entity via calls and imports, weighted by edge confidence. Returns affected entities grouped by community, untested entities, and affected tests. Use before changing a function or file to see what could break."
+    )]
+    async fn impact(&self, Parameters(params): Parameters<ImpactParams>) -> String {
+        match self.do_impact(params).await {
+            Ok(s) => s,
+            Err(e) => format!("Error: {e}"),
+        }
+    }
+
+    #[tool(
+        name = "homer_path",
+        description = "Find the shortest directed paths from one entity to another through calls, imports, and inheritance. Each hop includes file, line span, and edge confidence. Use to explain how one part of the code reaches another."
+    )]
+    async fn path(&self, Parameters(params): Parameters<PathParams>) -> String {
+        match self.do_path(params).await {
+            Ok(s) => s,
+            Err(e) => format!("Error: {e}"),
+        }
+    }
+
+    #[tool(
+        name = "homer_experts",
+        description = "Recommend active contributors to ask about a file or directory, ranked by their share of its commits. Also reports how much of the path's knowledge is held by contributors who are no longer active. Use when you need a human reviewer or domain expert."
+    )]
+    async fn experts(&self, Parameters(params): Parameters<ExpertsParams>) -> String {
+        match self.do_experts(params).await {
+            Ok(s) => s,
+            Err(e) => format!("Error: {e}"),
+        }
+    }
+
+    #[tool(
+        name = "homer_api_check",
+        description = "Check whether a file, function, or type is part of the public API: exported now or shipped in a recent release. Returns the affected items, their signatures, and warnings. Call before modifying code so that breaking changes to public items are deliberate."
+    )]
+    async fn api_check(&self, Parameters(params): Parameters<ApiCheckParams>) -> String {
+        match self.do_api_check(params).await {
+            Ok(s) => s,
+            Err(e) => format!("Error: {e}"),
+        }
+    }
+
+    #[tool(
+        name = "homer_flags",
+        description = "List feature flags the code evaluates, with when each appeared, when it last changed, and the code checking it. Flags older than a threshold and checked by high-salience code are marked as cleanup candidates. Use before touching flagged code or when planning flag cleanup."
+    )]
+    async fn flags(&self, Parameters(params): Parameters<FlagsParams>) -> String {
+        match self.do_flags(params).await {
+            Ok(s) => s,
+            Err(e) => format!("Error: {e}"),
+        }
+    }
 }
 
 impl ServerHandler for HomerMcpServer {
@@ -208,8 +368,14 @@ impl ServerHandler for HomerMcpServer {
                 "Homer MCP server — codebase intelligence tools for AI agents. \
                  Use homer_query to look up entities, homer_graph for centrality metrics, \
                  homer_risk to assess modification risk, homer_diff to analyze impact of \
-                 changes, homer_co_changes to find files that change together, and \
-                 homer_conventions to understand project patterns."
+                 changes, homer_co_changes to find files that change together, \
+                 homer_conventions to understand project patterns, homer_context \
+                 to gather the code most relevant to a task, homer_impact to see \
+                 what depends on an entity before changing it, homer_path to \
+                 explain how one entity reaches another, homer_experts to find \
+                 active people who know a path, homer_api_check before \
+                 modifying code that may be part of the public API, and homer_flags \
+                 to find feature flags that are due for cleanup."
                     .into(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
@@ -319,6 +485,20 @@ impl HomerMcpServer {
                 );
             }
 
+            if include.iter().any(|s| s == "schema") {
+                let accesses = query::schema_accesses(&*self.store, node)
+                    .await
+                    .map_err(|e| format!("Store error: {e}"))?;
+                entry["schema_access"] = serde_json::json!(accesses);
+            }
+
+            if include.iter().any(|s| s == "config") {
+                let usages = query::config_usage(&*self.store, node)
+                    .await
+                    .map_err(|e| format!("Store error: {e}"))?;
+                entry["config_usage"] = serde_json::json!(usages);
+            }
+
             results.push(entry);
         }
 
@@ -516,6 +696,9 @@ impl HomerMcpServer {
             if let Some(support) = edge.metadata.get("support") {
                 entry["support"] = support.clone();
             }
+            if let Some(lag) = edge.metadata.get("lag") {
+                entry["lag"] = lag.clone();
+            }
 
             results.push(entry);
         }
@@ -538,6 +721,139 @@ impl HomerMcpServer {
             "low_bus_factor": impact.low_bus_factor,
             "communities_affected": impact.communities_affected,
             "modules_affected": impact.modules_affected,
+            "tests_to_run": impact.tests_to_run,
+            "contract_impact": impact.contract_impact,
+        }))
+        .map_err(|e| format!("JSON error: {e}"))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn do_context(&self, params: ContextParams) -> Result<String, String> {
+        if params.seeds.is_empty() {
+            return Err("At least one seed is required".to_string());
+        }
+        let budget = params.budget.unwrap_or(query::DEFAULT_CONTEXT_BUDGET);
+        let top_n = params
+            .top
+            .map_or(query::DEFAULT_CONTEXT_TOP, |top| top as usize);
+
+        let ctx = query::task_context(&*self.store, &params.seeds, budget, top_n)
+            .await
+            .map_err(|e| format!("Store error: {e}"))?;
+
+        serde_json::to_string_pretty(&ctx).map_err(|e| format!("JSON error: {e}"))
+    }
+
+    async fn do_impact(&self, params: ImpactParams) -> Result<String, String> {
+        let node = query::find_entity(&*self.store, &params.entity)
+            .await
+            .map_err(|e| format!("Store error: {e}"))?;
+        let Some(node) = node else {
+            return serde_json::to_string_pretty(&serde_json::json!({
+                "target": null,
+                "note": format!("No entity found matching '{}'", params.entity),
+            }))
+            .map_err(|e| format!("JSON error: {e}"));
+        };
+
+        let radius = query::blast_radius(
+            &*self.store,
+            &node,
+            params.min_probability.unwrap_or(0.1),
+            params.max_depth.unwrap_or(10),
+        )
+        .await
+        .map_err(|e| format!("Store error: {e}"))?;
+
+        serde_json::to_string_pretty(&radius).map_err(|e| format!("JSON error: {e}"))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn do_path(&self, params: PathParams) -> Result<String, String> {
+        let mut endpoints = Vec::with_capacity(2);
+        for name in [&params.from, &params.to] {
+            let node = query::find_entity(&*self.store, name)
+                .await
+                .map_err(|e| format!("Store error: {e}"))?;
+            let Some(node) = node else {
+                return serde_json::to_string_pretty(&serde_json::json!({
+                    "paths": [],
+                    "note": format!("No entity found matching '{name}'"),
+                }))
+                .map_err(|e| format!("JSON error: {e}"));
+            };
+            endpoints.push(node);
+        }
+
+        let paths = query::shortest_paths(
+            &*self.store,
+            &endpoints[0],
+            &endpoints[1],
+            params.k.unwrap_or(3) as usize,
+            params.min_confidence.unwrap_or(0.0),
+        )
+        .await
+        .map_err(|e| format!("Store error: {e}"))?;
+
+        serde_json::to_string_pretty(&serde_json::json!({
+            "from": endpoints[0].name,
+            "to": endpoints[1].name,
+            "paths": paths,
+        }))
+        .map_err(|e| format!("JSON error: {e}"))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn do_experts(&self, params: ExpertsParams) -> Result<String, String> {
+        let report =
+            query::experts_for_path(&*self.store, &params.path, params.top.unwrap_or(5) as usize)
+                .await
+                .map_err(|e| format!("Store error: {e}"))?;
+
+        if report.files == 0 {
+            return serde_json::to_string_pretty(&serde_json::json!({
+                "path": report.path,
+                "experts": [],
+                "note": "No knowledge-loss data for this path. Run `homer update` first.",
+            }))
+            .map_err(|e| format!("JSON error: {e}"));
+        }
+
+        serde_json::to_string_pretty(&report).map_err(|e| format!("JSON error: {e}"))
+    }
+
+    async fn do_api_check(&self, params: ApiCheckParams) -> Result<String, String> {
+        let check = query::public_api_check(&*self.store, &params.entity)
+            .await
+            .map_err(|e| format!("Store error: {e}"))?;
+        let Some(check) = check else {
+            return serde_json::to_string_pretty(&serde_json::json!({
+                "entity": null,
+                "note": format!("No entity found matching '{}'", params.entity),
+            }))
+            .map_err(|e| format!("JSON error: {e}"));
+        };
+
+        serde_json::to_string_pretty(&check).map_err(|e| format!("JSON error: {e}"))
+    }
+
+    async fn do_flags(&self, params: FlagsParams) -> Result<String, String> {
+        let min_age_days = params.min_age_days.unwrap_or(90);
+        let mut flags = query::flag_inventory(&*self.store, min_age_days)
+            .await
+            .map_err(|e| format!("Store error: {e}"))?;
+        let total = flags.len();
+        let candidates = flags.iter().filter(|f| f.cleanup_candidate).count();
+        if params.stale_only.unwrap_or(false) {
+            flags.retain(|f| f.cleanup_candidate);
+        }
+        flags.truncate(params.top.unwrap_or(50) as usize);
+
+        serde_json::to_string_pretty(&serde_json::json!({
+            "count": total,
+            "cleanup_candidates": candidates,
+            "min_age_days": min_age_days,
+            "flags": flags,
         }))
         .map_err(|e| format!("JSON error: {e}"))
     }
@@ -698,7 +1014,8 @@ mod tests {
     use homer_core::pipeline::HomerPipeline;
     use homer_core::store::HomerStore;
     use homer_core::types::{
-        AnalysisResult, AnalysisResultId, HyperedgeKind, Node, NodeFilter, NodeId, NodeKind,
+        AnalysisResult, AnalysisResultId, Hyperedge, HyperedgeId, HyperedgeKind, HyperedgeMember,
+        Node, NodeFilter, NodeId, NodeKind,
     };
     use std::process::Command;
 
@@ -894,14 +1211,188 @@ mod tests {
         let high = json["high_salience"].as_array().unwrap();
         assert_eq!(high.len(), 1);
         assert_eq!(high[0]["path"], "src/critical.rs");
+        assert_eq!(json["contract_impact"]["elements"], serde_json::json!([]));
     }
 
     #[tokio::test]
-    async fn server_exposes_six_tools() {
+    async fn server_experts_ranks_active_contributors() {
+        let store = SqliteStore::in_memory().unwrap();
+        let now = Utc::now();
+
+        let files = [
+            ("src/auth/login.rs", 6, 2),
+            ("src/auth/token.rs", 4, 0),
+            ("src/other.rs", 0, 9),
+        ];
+        for (path, departed, active) in files {
+            let nid = store
+                .upsert_node(&Node {
+                    id: NodeId(0),
+                    kind: NodeKind::File,
+                    name: path.to_string(),
+                    content_hash: None,
+                    last_extracted: now,
+                    metadata: std::collections::HashMap::new(),
+                })
+                .await
+                .unwrap();
+            let contributors = [
+                ("old@example.com", departed, false),
+                ("new@example.com", active, true),
+            ]
+            .into_iter()
+            .filter(|(_, commits, _)| *commits > 0)
+            .map(|(email, commits, active)| {
+                serde_json::json!({"email": email, "name": "", "commits": commits, "active": active})
+            })
+            .collect::<Vec<_>>();
+            store
+                .store_analysis(&AnalysisResult {
+                    id: AnalysisResultId(0),
+                    node_id: nid,
+                    kind: AnalysisKind::KnowledgeLoss,
+                    data: serde_json::json!({
+                        "contributors": contributors,
+                        "orphaned": path == "src/auth/token.rs",
+                    }),
+                    input_hash: 0,
+                    computed_at: now,
+                })
+                .await
+                .unwrap();
+        }
+
+        let server = HomerMcpServer::from_store(store);
+        let result = server
+            .do_experts(ExpertsParams {
+                path: "src/auth/".to_string(),
+                top: None,
+            })
+            .await
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&result).expect("valid JSON");
+        assert_eq!(json["files"], 2);
+        assert_eq!(json["orphaned_files"], 1);
+        assert_eq!(json["inactive_share"], 0.83);
+        assert_eq!(json["experts"][0]["email"], "new@example.com");
+        assert_eq!(json["experts"][0]["commits"], 2);
+        assert_eq!(json["departed"][0]["email"], "old@example.com");
+
+        let missing = server
+            .do_experts(ExpertsParams {
+                path: "docs".to_string(),
+                top: None,
+            })
+            .await
+            .unwrap();
+        assert!(missing.contains("No knowledge-loss data"));
+    }
+
+    #[tokio::test]
+    async fn server_flags_lists_cleanup_candidates() {
+        let store = SqliteStore::in_memory().unwrap();
+        let node = |kind, name: &str, metadata: serde_json::Value| Node {
+            id: NodeId(0),
+            kind,
+            name: name.to_string(),
+            content_hash: None,
+            last_extracted: Utc::now(),
+            metadata: serde_json::from_value(metadata).unwrap(),
+        };
+        let render = store
+            .upsert_node(&node(
+                NodeKind::Function,
+                "src/checkout.rs::render",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        store
+            .store_analysis(&AnalysisResult {
+                id: AnalysisResultId(0),
+                node_id: render,
+                kind: AnalysisKind::CompositeSalience,
+                data: serde_json::json!({ "score": 0.7 }),
+                input_hash: 0,
+                computed_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        for (flag, introduced) in [
+            ("new-checkout", "2020-01-01T00:00:00+00:00".to_string()),
+            ("fast-cart", Utc::now().to_rfc3339()),
+        ] {
+            let id = store
+                .upsert_node(&node(
+                    NodeKind::FeatureFlag,
+                    flag,
+                    serde_json::json!({ "introduced": introduced }),
+                ))
+                .await
+                .unwrap();
+            store
+                .upsert_hyperedge(&Hyperedge {
+                    id: HyperedgeId(0),
+                    kind: HyperedgeKind::Checks,
+                    members: vec![
+                        HyperedgeMember {
+                            node_id: render,
+                            role: contracts::roles::CHECKER.to_string(),
+                            position: 0,
+                        },
+                        HyperedgeMember {
+                            node_id: id,
+                            role: contracts::roles::CHECKED_FLAG.to_string(),
+                            position: 1,
+                        },
+                    ],
+                    confidence: 0.9,
+                    last_updated: Utc::now(),
+                    metadata: std::collections::HashMap::new(),
+                })
+                .await
+                .unwrap();
+        }
+        let server = HomerMcpServer::from_store(store);
+
+        let result = server
+            .do_flags(FlagsParams {
+                stale_only: None,
+                min_age_days: None,
+                top: None,
+            })
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).expect("valid JSON");
+        assert_eq!(json["count"], 2);
+        assert_eq!(json["cleanup_candidates"], 1);
+        assert_eq!(json["flags"][0]["flag"], "new-checkout");
+        assert_eq!(json["flags"][0]["cleanup_candidate"], true);
+        assert_eq!(
+            json["flags"][0]["checkers"][0]["name"],
+            "src/checkout.rs::render"
+        );
+
+        let stale = server
+            .do_flags(FlagsParams {
+                stale_only: Some(true),
+                min_age_days: Some(10_000),
+                top: None,
+            })
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&stale).expect("valid JSON");
+        assert_eq!(json["cleanup_candidates"], 0);
+        assert!(json["flags"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn server_exposes_all_tools() {
         let store = SqliteStore::in_memory().unwrap();
         let server = HomerMcpServer::from_store(store);
         let tools = server.tool_router.list_all();
-        assert_eq!(tools.len(), 6, "Should expose 6 tools: {tools:?}");
+        assert_eq!(tools.len(), 12, "Should expose 12 tools: {tools:?}");
         let names: Vec<String> = tools.iter().map(|t| t.name.to_string()).collect();
         assert!(
             names.iter().any(|n| n == "homer_diff"),
@@ -909,6 +1400,114 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn server_context_unresolved_seed() {
+        let store = SqliteStore::in_memory().unwrap();
+        let server = HomerMcpServer::from_store(store);
+
+        let result = server
+            .do_context(ContextParams {
+                seeds: vec!["nonexistent".to_string()],
+                budget: None,
+                top: None,
+            })
+            .await
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&result).expect("valid JSON");
+        assert_eq!(json["unresolved"][0], "nonexistent");
+        assert!(json["entries"].as_array().unwrap().is_empty());
+        assert_eq!(json["budget"], 8000);
+    }
+
+    #[tokio::test]
+    async fn server_api_check_flags_exported_items() {
+        let store = SqliteStore::in_memory().unwrap();
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("exported".to_string(), serde_json::json!(true));
+        metadata.insert("signature".to_string(), serde_json::json!("pub fn parse()"));
+        store
+            .upsert_node(&Node {
+                id: NodeId(0),
+                kind: NodeKind::Function,
+                name: "src/lib.rs::parse".to_string(),
+                content_hash: None,
+                last_extracted: Utc::now(),
+                metadata,
+            })
+            .await
+            .unwrap();
+        let server = HomerMcpServer::from_store(store);
+
+        let result = server
+            .do_api_check(ApiCheckParams {
+                entity: "src/lib.rs::parse".to_string(),
+            })
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).expect("valid JSON");
+        assert_eq!(json["public"], true);
+        assert_eq!(json["items"][0]["signature"], "pub fn parse()");
+        assert!(
+            json["warnings"][0]
+                .as_str()
+                .unwrap_or("")
+                .contains("public API")
+        );
+
+        let missing = server
+            .do_api_check(ApiCheckParams {
+                entity: "nonexistent".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(missing.contains("No entity found"));
+    }
+
+    #[tokio::test]
+    async fn server_impact_missing_entity() {
+        let store = SqliteStore::in_memory().unwrap();
+        let server = HomerMcpServer::from_store(store);
+
+        let result = server
+            .do_impact(ImpactParams {
+                entity: "nonexistent".to_string(),
+                min_probability: None,
+                max_depth: None,
+            })
+            .await
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&result).expect("valid JSON");
+        assert!(json["target"].is_null());
+        assert!(
+            json["note"]
+                .as_str()
+                .unwrap_or("")
+                .contains("No entity found")
+        );
+    }
+
+    #[tokio::test]
+    async fn server_path_missing_entity() {
+        let store = SqliteStore::in_memory().unwrap();
+        let server = HomerMcpServer::from_store(store);
+
+        let result = server
+            .do_path(PathParams {
+                from: "src/api.rs".to_string(),
+                to: "src/db.rs".to_string(),
+                k: None,
+                min_confidence: None,
+            })
+            .await
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&result).expect("valid JSON");
+        assert!(json["paths"].as_array().unwrap().is_empty());
+        assert!(json["note"].as_str().unwrap_or("").contains("src/api.rs"));
+    }
+
     #[tokio::test]
     async fn server_graph_hits_uses_authority_score_field() {
         let store = SqliteStore::in_memory().unwrap();